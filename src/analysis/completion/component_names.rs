@@ -0,0 +1,172 @@
+//! 组件名称补全模块
+//!
+//! 光标位于 `#[inject(component = "...")]` 的字符串中时补全组件名称：
+//! - 项目中注册的组件名称（`#[component]` 函数和 `#[derive(Service)]` 结构体），优先显示
+//! - 按约定从组件类型推导的默认名称（类型名的 snake_case 形式，如 `UserRepo` 对应 `user_repo`），
+//!   排在注册名称之后，方便依赖默认命名的用户
+//!
+//! 输入过程中的代码通常无法解析，因此通过光标之前的文本判断是否位于组件名称字符串中。
+
+use std::collections::HashSet;
+
+use lsp_types::{CompletionItem, CompletionItemKind, Documentation, Position};
+
+use crate::analysis::validation::component_registration::default_component_name;
+use crate::core::document::PositionMapper;
+use crate::core::messages::{localized, Locale};
+use crate::scanner::component::ComponentInfoResponse;
+
+/// 判断光标是否位于 `#[inject(component = "...")]` 的组件名称字符串中
+pub fn in_component_name(lines: &PositionMapper, position: Position) -> bool {
+    let Some(cursor) = lines.offset(position) else {
+        return false;
+    };
+    let prefix = &lines.content()[..cursor];
+    let Some(start) = prefix.rfind("#[inject(") else {
+        return false;
+    };
+    let args = &prefix[start + "#[inject(".len()..];
+    let Some(value) = args
+        .trim_start()
+        .strip_prefix("component")
+        .map(str::trim_start)
+        .and_then(|rest| rest.strip_prefix('='))
+        .map(str::trim_start)
+        .and_then(|rest| rest.strip_prefix('"'))
+    else {
+        return false;
+    };
+    // 字符串尚未结束
+    !value.contains(['"', '\n'])
+}
+
+/// 补全组件名称：注册的名称在前，按约定推导的默认名称在后（与注册名称相同的默认名称不重复列出）
+pub fn complete_component_names(
+    components: &[ComponentInfoResponse],
+    locale: Locale,
+) -> Vec<CompletionItem> {
+    let mut seen = HashSet::new();
+    let registered: Vec<_> = components
+        .iter()
+        .filter(|component| seen.insert(component.name.clone()))
+        .map(|component| CompletionItem {
+            label: component.name.clone(),
+            kind: Some(CompletionItemKind::VALUE),
+            detail: Some(localized(
+                locale,
+                "completion.registered-component",
+                &[&component.type_name],
+            )),
+            documentation: component.qualified_name.clone().map(Documentation::String),
+            sort_text: Some(format!("0_{}", component.name)),
+            ..Default::default()
+        })
+        .collect();
+
+    let defaults: Vec<_> = components
+        .iter()
+        .filter_map(|component| {
+            let name = default_component_name(&component.type_name);
+            seen.insert(name.clone()).then(|| CompletionItem {
+                label: name.clone(),
+                kind: Some(CompletionItemKind::VALUE),
+                detail: Some(localized(
+                    locale,
+                    "completion.default-component-name",
+                    &[&component.type_name],
+                )),
+                sort_text: Some(format!("1_{}", name)),
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    registered.into_iter().chain(defaults).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::types::{LocationResponse, PositionResponse, RangeResponse};
+    use crate::scanner::component::{ComponentScope, ComponentSource};
+
+    fn component(name: &str, type_name: &str) -> ComponentInfoResponse {
+        let position = PositionResponse {
+            line: 0,
+            character: 0,
+        };
+        ComponentInfoResponse {
+            name: name.to_string(),
+            qualified_name: None,
+            type_name: type_name.to_string(),
+            scope: ComponentScope::Singleton,
+            source: ComponentSource::Component,
+            dependencies: Vec::new(),
+            deprecated: false,
+            cfg: None,
+            location: LocationResponse {
+                uri: "file:///app/src/main.rs".to_string(),
+                range: RangeResponse {
+                    start: position.clone(),
+                    end: position,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_in_component_name() {
+        let content = "struct S {\n    #[inject(component = \"pri\n    db: Db,\n    #[inject(component)]\n    cache: Cache,\n}\n";
+        assert!(in_component_name(
+            &PositionMapper::new(content),
+            Position::new(1, 26)
+        ));
+        assert!(in_component_name(
+            &PositionMapper::new(content),
+            Position::new(1, 30)
+        ));
+        // 还没有输入引号
+        assert!(!in_component_name(
+            &PositionMapper::new(content),
+            Position::new(1, 25)
+        ));
+        // 字符串所在行之后
+        assert!(!in_component_name(
+            &PositionMapper::new(content),
+            Position::new(2, 4)
+        ));
+        assert!(!in_component_name(
+            &PositionMapper::new(content),
+            Position::new(3, 23)
+        ));
+        assert!(!in_component_name(
+            &PositionMapper::new("#[inject(component = \"db\")]"),
+            Position::new(0, 27)
+        ));
+    }
+
+    #[test]
+    fn test_complete_component_names() {
+        let components = vec![
+            component("UserRepo", "UserRepo"),
+            component("primary", "sea_orm::DatabaseConnection"),
+            component("UserRepo", "UserRepo"),
+            component("cache", "Cache"),
+        ];
+        let items = complete_component_names(&components, Locale::default());
+        let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "UserRepo",
+                "primary",
+                "cache",
+                "user_repo",
+                "database_connection"
+            ]
+        );
+        // 默认名称排在注册名称之后
+        assert!(items[3].sort_text > items[0].sort_text);
+        assert!(items[3].detail.as_ref().unwrap().contains("默认名称"));
+    }
+}
@@ -1,12 +1,18 @@
 //! 智能补全引擎模块
 
+use dashmap::DashMap;
 use lsp_types::{
-    CompletionItem, CompletionItemKind, Documentation, InsertTextFormat, MarkupContent, MarkupKind,
-    Position, Range,
+    CompletionItem, CompletionItemKind, CompletionItemTag, Documentation, InsertTextFormat,
+    MarkupContent, MarkupKind, Position, Range, Url,
 };
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use crate::analysis::rust::macro_analyzer::SpringMacro;
+use crate::analysis::rust::macro_analyzer::{HttpMethod, SpringMacro};
+use crate::analysis::toml::log_level;
 use crate::analysis::toml::toml_analyzer::{TomlAnalyzer, TomlDocument};
+use crate::core::document::PositionMapper;
+use crate::core::messages::{localized, Locale};
 use crate::core::schema::SchemaProvider;
 
 /// 补全上下文
@@ -22,12 +28,79 @@ pub enum CompletionContext {
     Unknown,
 }
 
+/// TOML 补全类型
+///
+/// 同一文档版本中，补全类型相同的位置会得到相同的补全列表，用作缓存键
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum TomlCompletionKind {
+    /// 配置前缀（`[` 之后）
+    ConfigPrefix,
+    /// 环境变量（`${` 之后）
+    EnvVar,
+    /// 配置节内的配置项（值为配置前缀）
+    Properties(String),
+    /// 日志级别（`[logger]` 中的 `level` 和 `override_filter` 的值）
+    LogLevel,
+    /// Schema 中声明了枚举值的配置项的值（配置前缀，配置项）
+    EnumValues(String, String),
+    /// 无补全
+    None,
+}
+
+/// 单个文档的补全缓存
+struct CachedCompletions {
+    /// 缓存对应的文档版本
+    version: i32,
+    /// 按补全类型缓存的补全列表
+    lists: HashMap<TomlCompletionKind, Vec<CompletionItem>>,
+}
+
+/// 带缓存信息的补全结果
+#[derive(Debug, Clone)]
+pub struct CachedCompletionList {
+    /// 补全项列表
+    pub items: Vec<CompletionItem>,
+    /// 是否命中缓存
+    pub cache_hit: bool,
+}
+
+/// 补全缓存统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompletionCacheStats {
+    /// 缓存命中次数
+    pub hits: u64,
+    /// 缓存未命中次数
+    pub misses: u64,
+    /// 当前缓存的文档数
+    pub documents: usize,
+}
+
+impl CompletionCacheStats {
+    /// 缓存命中率（0.0 - 1.0）
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
 /// 补全引擎
 ///
 /// 提供智能补全功能，支持 TOML 配置文件和 Rust 宏的补全
 pub struct CompletionEngine {
     /// TOML 分析器
     toml_analyzer: TomlAnalyzer,
+    /// 补全缓存（按文档 URI 存储）
+    cache: DashMap<Url, CachedCompletions>,
+    /// 缓存命中次数
+    cache_hits: AtomicU64,
+    /// 缓存未命中次数
+    cache_misses: AtomicU64,
+    /// 补全说明的语言
+    locale: Locale,
 }
 
 impl CompletionEngine {
@@ -39,9 +112,20 @@ impl CompletionEngine {
     pub fn new(schema_provider: SchemaProvider) -> Self {
         Self {
             toml_analyzer: TomlAnalyzer::new(schema_provider),
+            cache: DashMap::new(),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
+            locale: Locale::default(),
         }
     }
 
+    /// 使用指定的语言生成补全项的说明
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.toml_analyzer = self.toml_analyzer.with_locale(locale);
+        self.locale = locale;
+        self
+    }
+
     /// 提供补全
     ///
     /// 根据文档类型和位置提供相应的补全项
@@ -106,6 +190,141 @@ impl CompletionEngine {
         self.complete_toml(doc, position)
     }
 
+    /// 带缓存的 TOML 配置补全
+    ///
+    /// 补全列表按（文档版本，补全类型）缓存，在同一文档版本中重复触发同类补全时
+    /// 直接返回缓存结果。文档版本变化时旧缓存自动失效，
+    /// 也可以通过 [`CompletionEngine::invalidate`] 主动清除。
+    ///
+    /// # 参数
+    ///
+    /// * `uri` - 文档 URI
+    /// * `version` - 文档版本
+    /// * `doc` - TOML 文档
+    /// * `position` - 光标位置
+    pub fn complete_toml_cached(
+        &self,
+        uri: &Url,
+        version: i32,
+        doc: &TomlDocument,
+        position: Position,
+    ) -> CachedCompletionList {
+        let kind = self.toml_completion_kind(doc, position);
+
+        if let Some(cached) = self.cache.get(uri) {
+            if cached.version == version {
+                if let Some(items) = cached.lists.get(&kind) {
+                    self.cache_hits.fetch_add(1, Ordering::Relaxed);
+                    return CachedCompletionList {
+                        items: items.clone(),
+                        cache_hit: true,
+                    };
+                }
+            }
+        }
+
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+        let items = self.complete_toml_kind(doc, &kind);
+
+        let mut entry = self
+            .cache
+            .entry(uri.clone())
+            .or_insert_with(|| CachedCompletions {
+                version,
+                lists: HashMap::new(),
+            });
+        if entry.version != version {
+            entry.version = version;
+            entry.lists.clear();
+        }
+        entry.lists.insert(kind, items.clone());
+
+        CachedCompletionList {
+            items,
+            cache_hit: false,
+        }
+    }
+
+    /// 为 profile 配置文件（如 `app-prod.toml`）补充覆盖基础配置的补全项
+    ///
+    /// 光标位于配置节内时，把基础配置 `app.toml` 同一配置节中存在、当前文件尚未覆盖的配置项
+    /// 放在补全列表最前面，插入文本为基础配置中的值，详情显示 `base value: <值>`。
+    /// Schema 补全中同名的配置项会被替换。
+    ///
+    /// 基础配置可能在当前文档版本不变时被修改，因此这部分补全项不参与缓存。
+    ///
+    /// # 参数
+    ///
+    /// * `items` - 已生成的补全列表
+    /// * `doc` - profile 配置文档
+    /// * `base` - 基础配置文档
+    /// * `position` - 光标位置
+    pub fn add_profile_overrides(
+        &self,
+        items: &mut Vec<CompletionItem>,
+        doc: &TomlDocument,
+        base: &TomlDocument,
+        position: Position,
+    ) {
+        let TomlCompletionKind::Properties(prefix) = self.toml_completion_kind(doc, position)
+        else {
+            return;
+        };
+        let Some(base_section) = base.config_sections.get(&prefix) else {
+            return;
+        };
+        let overridden = doc.config_sections.get(&prefix);
+
+        let mut properties: Vec<_> = base_section
+            .properties
+            .values()
+            .filter(|property| overridden.is_none_or(|s| !s.properties.contains_key(&property.key)))
+            .collect();
+        properties.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let overrides: Vec<CompletionItem> = properties
+            .into_iter()
+            .map(|property| {
+                let value = self.toml_analyzer.config_value_to_string(&property.value);
+                CompletionItem {
+                    label: property.key.clone(),
+                    kind: Some(CompletionItemKind::PROPERTY),
+                    detail: Some(format!("base value: {}", value)),
+                    documentation: Some(Documentation::MarkupContent(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value: localized(
+                            self.locale,
+                            "completion.override",
+                            &[&prefix, &property.key, &value],
+                        ),
+                    })),
+                    insert_text: Some(format!("{} = {}", property.key, value)),
+                    insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        items.retain(|item| !overrides.iter().any(|o| o.label == item.label));
+        items.splice(0..0, overrides);
+    }
+
+    /// 清除文档的补全缓存
+    ///
+    /// 在文档修改或关闭时调用
+    pub fn invalidate(&self, uri: &Url) {
+        self.cache.remove(uri);
+    }
+
+    /// 获取补全缓存统计
+    pub fn cache_stats(&self) -> CompletionCacheStats {
+        CompletionCacheStats {
+            hits: self.cache_hits.load(Ordering::Relaxed),
+            misses: self.cache_misses.load(Ordering::Relaxed),
+            documents: self.cache.len(),
+        }
+    }
+
     /// TOML 配置补全（内部方法）
     ///
     /// 为 TOML 配置文件提供补全，支持：
@@ -123,47 +342,86 @@ impl CompletionEngine {
     ///
     /// 补全项列表
     fn complete_toml(&self, doc: &TomlDocument, position: Position) -> Vec<CompletionItem> {
+        let kind = self.toml_completion_kind(doc, position);
+        self.complete_toml_kind(doc, &kind)
+    }
+
+    /// 确定光标位置的补全类型
+    fn toml_completion_kind(&self, doc: &TomlDocument, position: Position) -> TomlCompletionKind {
         // 1. 检查是否在配置前缀位置（[之后）
         if self.is_prefix_position(doc, position) {
-            return self.complete_config_prefix();
+            return TomlCompletionKind::ConfigPrefix;
         }
 
         // 2. 检查是否在环境变量位置（${之后）
         if self.is_env_var_position(doc, position) {
-            return self.complete_env_var();
+            return TomlCompletionKind::EnvVar;
         }
 
         // 3. 检查是否在配置节内
         if let Some(section) = self.find_section_at_position(doc, position) {
-            // TODO: 实现基于 JSON Schema 的枚举值补全
-            // 目前只提供配置项补全
-            return self.complete_config_properties(section);
+            // 日志级别配置项的值
+            if let Some(key) = self.find_property_at_position(section, position) {
+                if log_level::is_log_level_property(&section.prefix, &key) {
+                    return TomlCompletionKind::LogLevel;
+                }
+                if self.enum_values(&section.prefix, &key).is_some() {
+                    return TomlCompletionKind::EnumValues(section.prefix.clone(), key);
+                }
+            }
+            return TomlCompletionKind::Properties(section.prefix.clone());
         }
 
-        Vec::new()
+        TomlCompletionKind::None
+    }
+
+    /// 按补全类型生成补全列表
+    fn complete_toml_kind(
+        &self,
+        doc: &TomlDocument,
+        kind: &TomlCompletionKind,
+    ) -> Vec<CompletionItem> {
+        match kind {
+            TomlCompletionKind::ConfigPrefix => self.complete_config_prefix(),
+            TomlCompletionKind::EnvVar => self.complete_env_var(),
+            TomlCompletionKind::Properties(prefix) => doc
+                .config_sections
+                .get(prefix)
+                .map(|section| self.complete_config_properties(section))
+                .unwrap_or_default(),
+            TomlCompletionKind::LogLevel => self.complete_log_levels(),
+            // 光标位于字符串值内，插入文本不带引号
+            TomlCompletionKind::EnumValues(prefix, key) => self
+                .complete_enum_values(&self.enum_values(prefix, key).unwrap_or_default())
+                .into_iter()
+                .map(|item| CompletionItem {
+                    insert_text: Some(item.label.clone()),
+                    ..item
+                })
+                .collect(),
+            TomlCompletionKind::None => Vec::new(),
+        }
     }
 
     /// 检查是否在配置前缀位置
     ///
     /// 判断光标是否在 `[` 字符之后，需要补全配置前缀
     fn is_prefix_position(&self, doc: &TomlDocument, position: Position) -> bool {
-        // 获取光标所在行的内容
-        let lines: Vec<&str> = doc.content.lines().collect();
-
-        if position.line as usize >= lines.len() {
-            return false;
-        }
-
-        let line = lines[position.line as usize];
-        let char_pos = position.character as usize;
-
         // 光标位置必须在行内或行尾
-        if char_pos > line.len() {
+        let lines = doc.mapper();
+        let (Some(line_start), Some(cursor)) = (
+            lines.offset(Position::new(position.line, 0)),
+            lines.offset(position),
+        ) else {
+            return false;
+        };
+        if lines.position(cursor) != position {
             return false;
         }
 
-        // 检查光标前的字符
-        let before_cursor = if char_pos > 0 { &line[..char_pos] } else { "" };
+        // 获取光标所在行的内容和光标前的字符
+        let line = doc.content[line_start..].lines().next().unwrap_or("");
+        let before_cursor = &doc.content[line_start..cursor];
 
         // 如果光标前是 `[` 或 `[` 后跟一些字符，则认为是前缀位置
         // 但必须确保还没有闭合括号
@@ -235,29 +493,17 @@ impl CompletionEngine {
 
         prefixes
             .into_iter()
-            .map(|prefix: String| {
-                let description = format!("{} 插件配置", prefix);
-
-                CompletionItem {
-                    label: prefix.clone(),
-                    kind: Some(CompletionItemKind::MODULE),
-                    detail: Some(format!("[{}] 配置节", prefix)),
-                    documentation: Some(Documentation::MarkupContent(MarkupContent {
-                        kind: MarkupKind::Markdown,
-                        value: format!(
-                            "**{}** 插件配置节\n\n{}\n\n\
-                             **使用方式**:\n\
-                             ```toml\n\
-                             [{}]\n\
-                             # 配置项...\n\
-                             ```",
-                            prefix, description, prefix
-                        ),
-                    })),
-                    insert_text: Some(format!("[{}]\n", prefix)),
-                    insert_text_format: Some(lsp_types::InsertTextFormat::SNIPPET),
-                    ..Default::default()
-                }
+            .map(|prefix: String| CompletionItem {
+                label: prefix.clone(),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some(localized(self.locale, "completion.section", &[&prefix])),
+                documentation: Some(Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: localized(self.locale, "completion.section.doc", &[&prefix]),
+                })),
+                insert_text: Some(format!("[{}]\n", prefix)),
+                insert_text_format: Some(lsp_types::InsertTextFormat::SNIPPET),
+                ..Default::default()
             })
             .collect()
     }
@@ -332,14 +578,15 @@ impl CompletionEngine {
             let insert_text = format!("{} = {}  # {}", key, default_value, type_name);
 
             // 构建文档
-            let mut doc_parts = vec![format!("**类型**: `{}`", type_name)];
+            let mut doc_parts = vec![localized(self.locale, "completion.type", &[&type_name])];
             if !description.is_empty() {
                 doc_parts.insert(0, description.to_string());
             }
             if let Some(default) = value.get("default") {
-                doc_parts.push(format!(
-                    "**默认值**: `{}`",
-                    self.json_value_to_toml_string(default)
+                doc_parts.push(localized(
+                    self.locale,
+                    "hover.default",
+                    &[&self.json_value_to_toml_string(default)],
                 ));
             }
 
@@ -386,6 +633,22 @@ impl CompletionEngine {
         }
     }
 
+    /// Schema 中声明的配置项枚举值
+    fn enum_values(&self, prefix: &str, key: &str) -> Option<Vec<String>> {
+        match self
+            .toml_analyzer
+            .schema_provider()
+            .property_schema(prefix, key)?
+            .type_info
+        {
+            crate::schema::TypeInfo::String {
+                enum_values: Some(values),
+                ..
+            } => Some(values),
+            _ => None,
+        }
+    }
+
     /// 补全枚举值
     ///
     /// 为具有枚举类型的配置项提供值补全
@@ -395,10 +658,10 @@ impl CompletionEngine {
             .map(|value| CompletionItem {
                 label: value.clone(),
                 kind: Some(CompletionItemKind::ENUM_MEMBER),
-                detail: Some(format!("枚举值: {}", value)),
+                detail: Some(localized(self.locale, "completion.enum-value", &[value])),
                 documentation: Some(Documentation::MarkupContent(MarkupContent {
                     kind: MarkupKind::Markdown,
-                    value: format!("枚举值 `{}`", value),
+                    value: localized(self.locale, "completion.enum-value.doc", &[value]),
                 })),
                 insert_text: Some(format!("\"{}\"", value)),
                 insert_text_format: Some(lsp_types::InsertTextFormat::PLAIN_TEXT),
@@ -407,40 +670,77 @@ impl CompletionEngine {
             .collect()
     }
 
+    /// 补全日志级别
+    ///
+    /// 光标位于字符串值内，插入文本不带引号
+    fn complete_log_levels(&self) -> Vec<CompletionItem> {
+        log_level::LOG_LEVELS
+            .iter()
+            .enumerate()
+            .map(|(i, level)| CompletionItem {
+                label: level.to_string(),
+                kind: Some(CompletionItemKind::ENUM_MEMBER),
+                detail: Some(localized(self.locale, "completion.log-level", &[])),
+                documentation: Some(Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: log_level::level_description(level, self.locale),
+                })),
+                insert_text: Some(level.to_string()),
+                insert_text_format: Some(lsp_types::InsertTextFormat::PLAIN_TEXT),
+                // 按从详细到关闭的顺序排列
+                sort_text: Some(format!("{}", i)),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Rust 代码补全
+    ///
+    /// 当前支持路由处理器中的 HTTP 头名称、常用值和类型化头补全，以及 `Path(` 的路径参数绑定补全
+    pub fn complete_rust(&self, lines: &PositionMapper, position: Position) -> Vec<CompletionItem> {
+        let mut items = super::headers::complete_headers(lines, position, self.locale);
+        items.extend(super::path_params::complete_path_params(
+            lines,
+            position,
+            self.locale,
+        ));
+        items
+    }
+
     /// 补全环境变量
     ///
     /// 提供常见的环境变量名称补全
     pub fn complete_env_var(&self) -> Vec<CompletionItem> {
-        let common_vars = vec![
-            ("HOST", "主机地址"),
-            ("PORT", "端口号"),
-            ("DATABASE_URL", "数据库连接 URL"),
-            ("REDIS_URL", "Redis 连接 URL"),
-            ("LOG_LEVEL", "日志级别"),
-            ("ENV", "运行环境"),
-            ("DEBUG", "调试模式"),
+        let common_vars = [
+            "HOST",
+            "PORT",
+            "DATABASE_URL",
+            "REDIS_URL",
+            "LOG_LEVEL",
+            "ENV",
+            "DEBUG",
         ];
 
         common_vars
             .into_iter()
-            .map(|(name, description)| CompletionItem {
-                label: name.to_string(),
-                kind: Some(CompletionItemKind::VARIABLE),
-                detail: Some(description.to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: format!(
-                        "**{}**\n\n{}\n\n\
-                         **使用方式**:\n\
-                         ```toml\n\
-                         value = \"${{{}:default}}\"\n\
-                         ```",
-                        name, description, name
-                    ),
-                })),
-                insert_text: Some(format!("{}:${{1:default}}}}", name)),
-                insert_text_format: Some(lsp_types::InsertTextFormat::SNIPPET),
-                ..Default::default()
+            .map(|name| {
+                let description = localized(self.locale, &format!("env-var.{}", name), &[]);
+                CompletionItem {
+                    label: name.to_string(),
+                    kind: Some(CompletionItemKind::VARIABLE),
+                    documentation: Some(Documentation::MarkupContent(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value: localized(
+                            self.locale,
+                            "completion.env-var.doc",
+                            &[&name, &description],
+                        ),
+                    })),
+                    detail: Some(description),
+                    insert_text: Some(format!("{}:${{1:default}}}}", name)),
+                    insert_text_format: Some(lsp_types::InsertTextFormat::SNIPPET),
+                    ..Default::default()
+                }
             })
             .collect()
     }
@@ -520,14 +820,23 @@ impl CompletionEngine {
         macro_info: &SpringMacro,
         _cursor_position: Option<&str>,
     ) -> Vec<CompletionItem> {
-        match macro_info {
+        let mut completions = match macro_info {
             SpringMacro::DeriveService(_) => self.complete_service_macro(),
             SpringMacro::Component(_) => self.complete_component_macro(),
             SpringMacro::Inject(_) => self.complete_inject_macro(),
             SpringMacro::AutoConfig(_) => self.complete_auto_config_macro(),
             SpringMacro::Route(_) => self.complete_route_macro(),
             SpringMacro::Job(_) => self.complete_job_macro(),
+        };
+
+        // 已弃用的处理器或服务上的补全项标记为 Deprecated
+        if macro_info.is_deprecated() {
+            for item in &mut completions {
+                item.tags = Some(vec![CompletionItemTag::DEPRECATED]);
+            }
         }
+
+        completions
     }
 
     /// 为 Component 宏提供补全
@@ -537,10 +846,12 @@ impl CompletionEngine {
         vec![CompletionItem {
             label: "name".to_string(),
             kind: Some(CompletionItemKind::PROPERTY),
-            detail: Some("插件名称".to_string()),
-            documentation: Some(Documentation::String(
-                "指定自定义的插件名称，默认使用组件类型名 + \"Plugin\"".to_string(),
-            )),
+            detail: Some(localized(self.locale, "completion.plugin-name", &[])),
+            documentation: Some(Documentation::String(localized(
+                self.locale,
+                "completion.plugin-name.doc",
+                &[],
+            ))),
             insert_text: Some("name = \"$1\"".to_string()),
             insert_text_format: Some(InsertTextFormat::SNIPPET),
             ..Default::default()
@@ -556,17 +867,11 @@ impl CompletionEngine {
             CompletionItem {
                 label: "inject(component)".to_string(),
                 kind: Some(CompletionItemKind::PROPERTY),
-                detail: Some("注入组件".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "从应用上下文中注入已注册的组件实例。\n\n\
-                            **示例**:\n\
-                            ```rust\n\
-                            #[inject(component)]\n\
-                            db: ConnectPool,\n\
-                            ```"
-                        .to_string(),
-                })),
+                detail: Some(localized(self.locale, "completion.inject-component", &[])),
+                documentation: Some(self.rust_example(
+                    localized(self.locale, "completion.inject-component.doc", &[]),
+                    "#[inject(component)]\ndb: ConnectPool,",
+                )),
                 insert_text: Some("inject(component)".to_string()),
                 ..Default::default()
             },
@@ -574,17 +879,11 @@ impl CompletionEngine {
             CompletionItem {
                 label: "inject(component = \"name\")".to_string(),
                 kind: Some(CompletionItemKind::PROPERTY),
-                detail: Some("注入指定名称的组件".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "使用指定名称从应用上下文中注入组件，适用于多实例场景（如多数据源）。\n\n\
-                            **示例**:\n\
-                            ```rust\n\
-                            #[inject(component = \"primary\")]\n\
-                            primary_db: ConnectPool,\n\
-                            ```"
-                        .to_string(),
-                })),
+                detail: Some(localized(self.locale, "completion.inject-named", &[])),
+                documentation: Some(self.rust_example(
+                    localized(self.locale, "completion.inject-named.doc", &[]),
+                    "#[inject(component = \"primary\")]\nprimary_db: ConnectPool,",
+                )),
                 insert_text: Some("inject(component = \"$1\")".to_string()),
                 insert_text_format: Some(lsp_types::InsertTextFormat::SNIPPET),
                 ..Default::default()
@@ -593,17 +892,11 @@ impl CompletionEngine {
             CompletionItem {
                 label: "inject(config)".to_string(),
                 kind: Some(CompletionItemKind::PROPERTY),
-                detail: Some("注入配置".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "从配置文件中加载配置项。配置项通过 `#[config_prefix]` 指定的前缀从 `config/app.toml` 中读取。\n\n\
-                            **示例**:\n\
-                            ```rust\n\
-                            #[inject(config)]\n\
-                            config: MyConfig,\n\
-                            ```"
-                        .to_string(),
-                })),
+                detail: Some(localized(self.locale, "completion.inject-config", &[])),
+                documentation: Some(self.rust_example(
+                    localized(self.locale, "completion.inject-config.doc", &[]),
+                    "#[inject(config)]\nconfig: MyConfig,",
+                )),
                 insert_text: Some("inject(config)".to_string()),
                 ..Default::default()
             },
@@ -619,14 +912,10 @@ impl CompletionEngine {
             CompletionItem {
                 label: "component".to_string(),
                 kind: Some(CompletionItemKind::KEYWORD),
-                detail: Some("注入组件".to_string()),
+                detail: Some(localized(self.locale, "completion.inject-component", &[])),
                 documentation: Some(Documentation::MarkupContent(MarkupContent {
                     kind: MarkupKind::Markdown,
-                    value: "从应用上下文中注入已注册的组件实例。\n\n\
-                            **使用方式**:\n\
-                            - `#[inject(component)]` - 按类型自动查找\n\
-                            - `#[inject(component = \"name\")]` - 按名称查找"
-                        .to_string(),
+                    value: localized(self.locale, "completion.inject-component.usage", &[]),
                 })),
                 insert_text: Some("component".to_string()),
                 ..Default::default()
@@ -635,13 +924,10 @@ impl CompletionEngine {
             CompletionItem {
                 label: "config".to_string(),
                 kind: Some(CompletionItemKind::KEYWORD),
-                detail: Some("注入配置".to_string()),
+                detail: Some(localized(self.locale, "completion.inject-config", &[])),
                 documentation: Some(Documentation::MarkupContent(MarkupContent {
                     kind: MarkupKind::Markdown,
-                    value: "从配置文件中加载配置项。\n\n\
-                            **使用方式**:\n\
-                            - `#[inject(config)]` - 从 config/app.toml 加载配置"
-                        .to_string(),
+                    value: localized(self.locale, "completion.inject-config.usage", &[]),
                 })),
                 insert_text: Some("config".to_string()),
                 ..Default::default()
@@ -653,68 +939,35 @@ impl CompletionEngine {
     ///
     /// 提供常见的配置器类型补全
     fn complete_auto_config_macro(&self) -> Vec<CompletionItem> {
-        vec![
-            CompletionItem {
-                label: "WebConfigurator".to_string(),
-                kind: Some(CompletionItemKind::CLASS),
-                detail: Some("Web 路由配置器".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "自动注册 Web 路由处理器。\n\n\
-                            **示例**:\n\
-                            ```rust\n\
-                            #[auto_config(WebConfigurator)]\n\
-                            #[tokio::main]\n\
-                            async fn main() {\n\
-                                App::new().add_plugin(WebPlugin).run().await\n\
-                            }\n\
-                            ```"
-                    .to_string(),
-                })),
-                insert_text: Some("WebConfigurator".to_string()),
-                ..Default::default()
-            },
-            CompletionItem {
-                label: "JobConfigurator".to_string(),
-                kind: Some(CompletionItemKind::CLASS),
-                detail: Some("任务调度配置器".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "自动注册定时任务。\n\n\
-                            **示例**:\n\
-                            ```rust\n\
-                            #[auto_config(JobConfigurator)]\n\
-                            #[tokio::main]\n\
-                            async fn main() {\n\
-                                App::new().add_plugin(JobPlugin).run().await\n\
-                            }\n\
-                            ```"
-                    .to_string(),
-                })),
-                insert_text: Some("JobConfigurator".to_string()),
-                ..Default::default()
-            },
-            CompletionItem {
-                label: "StreamConfigurator".to_string(),
-                kind: Some(CompletionItemKind::CLASS),
-                detail: Some("流处理配置器".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "自动注册流消费者。\n\n\
-                            **示例**:\n\
-                            ```rust\n\
-                            #[auto_config(StreamConfigurator)]\n\
-                            #[tokio::main]\n\
-                            async fn main() {\n\
-                                App::new().add_plugin(StreamPlugin).run().await\n\
-                            }\n\
-                            ```"
-                    .to_string(),
-                })),
-                insert_text: Some("StreamConfigurator".to_string()),
-                ..Default::default()
-            },
+        [
+            ("WebConfigurator", "web", "WebPlugin"),
+            ("JobConfigurator", "job", "JobPlugin"),
+            ("StreamConfigurator", "stream", "StreamPlugin"),
         ]
+        .into_iter()
+        .map(|(configurator, key, plugin)| CompletionItem {
+            label: configurator.to_string(),
+            kind: Some(CompletionItemKind::CLASS),
+            detail: Some(localized(
+                self.locale,
+                &format!("completion.configurator.{}", key),
+                &[],
+            )),
+            documentation: Some(self.rust_example(
+                localized(
+                    self.locale,
+                    &format!("completion.configurator.{}.doc", key),
+                    &[],
+                ),
+                &format!(
+                    "#[auto_config({})]\n#[tokio::main]\nasync fn main() {{\nApp::new().add_plugin({}).run().await\n}}",
+                    configurator, plugin
+                ),
+            )),
+            insert_text: Some(configurator.to_string()),
+            ..Default::default()
+        })
+        .collect()
     }
 
     /// 为路由宏提供补全
@@ -724,36 +977,30 @@ impl CompletionEngine {
         let mut completions = Vec::new();
 
         // HTTP 方法补全
-        let methods = vec![
-            ("GET", "获取资源"),
-            ("POST", "创建资源"),
-            ("PUT", "更新资源（完整）"),
-            ("DELETE", "删除资源"),
-            ("PATCH", "更新资源（部分）"),
-            ("HEAD", "获取资源头信息"),
-            ("OPTIONS", "获取支持的方法"),
+        let methods = [
+            HttpMethod::Get,
+            HttpMethod::Post,
+            HttpMethod::Put,
+            HttpMethod::Delete,
+            HttpMethod::Patch,
+            HttpMethod::Head,
+            HttpMethod::Options,
         ];
 
-        for (method, description) in methods {
+        for method in methods {
+            let name = method.as_str();
             completions.push(CompletionItem {
-                label: method.to_string(),
+                label: name.to_string(),
                 kind: Some(CompletionItemKind::CONSTANT),
-                detail: Some(description.to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: format!(
-                        "HTTP {} 方法\n\n\
-                         **示例**:\n\
-                         ```rust\n\
-                         #[{}(\"/path\")]\n\
-                         async fn handler() -> impl IntoResponse {{\n\
-                         }}\n\
-                         ```",
-                        method,
-                        method.to_lowercase()
+                detail: Some(method.description(self.locale)),
+                documentation: Some(self.rust_example(
+                    localized(self.locale, "completion.http-method.doc", &[&name]),
+                    &format!(
+                        "#[{}(\"/path\")]\nasync fn handler() -> impl IntoResponse {{\n}}",
+                        name.to_lowercase()
                     ),
-                })),
-                insert_text: Some(method.to_string()),
+                )),
+                insert_text: Some(name.to_string()),
                 ..Default::default()
             });
         }
@@ -762,18 +1009,11 @@ impl CompletionEngine {
         completions.push(CompletionItem {
             label: "{id}".to_string(),
             kind: Some(CompletionItemKind::SNIPPET),
-            detail: Some("路径参数".to_string()),
-            documentation: Some(Documentation::MarkupContent(MarkupContent {
-                kind: MarkupKind::Markdown,
-                value: "路径参数占位符，用于捕获 URL 中的动态部分。\n\n\
-                        **示例**:\n\
-                        ```rust\n\
-                        #[get(\"/users/{id}\")]\n\
-                        async fn get_user(Path(id): Path<i64>) -> impl IntoResponse {\n\
-                        }\n\
-                        ```"
-                .to_string(),
-            })),
+            detail: Some(localized(self.locale, "completion.path-param", &[])),
+            documentation: Some(self.rust_example(
+                localized(self.locale, "completion.path-param.doc", &[]),
+                "#[get(\"/users/{id}\")]\nasync fn get_user(Path(id): Path<i64>) -> impl IntoResponse {\n}",
+            )),
             insert_text: Some("{${1:id}}".to_string()),
             insert_text_format: Some(lsp_types::InsertTextFormat::SNIPPET),
             ..Default::default()
@@ -784,135 +1024,102 @@ impl CompletionEngine {
 
     /// 为任务调度宏提供补全
     ///
-    /// 提供 cron 表达式、延迟和频率值的补全
+    /// 提供 cron 表达式、延迟、频率和一次性任务延迟值的补全
     fn complete_job_macro(&self) -> Vec<CompletionItem> {
-        vec![
-            // Cron 表达式示例
-            CompletionItem {
-                label: "0 0 * * * *".to_string(),
-                kind: Some(CompletionItemKind::SNIPPET),
-                detail: Some("每小时执行".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "Cron 表达式：每小时的第 0 分 0 秒执行\n\n\
-                            **格式**: 秒 分 时 日 月 星期\n\n\
-                            **示例**:\n\
-                            ```rust\n\
-                            #[cron(\"0 0 * * * *\")]\n\
-                            async fn hourly_job() {\n\
-                            }\n\
-                            ```"
-                    .to_string(),
-                })),
-                insert_text: Some("\"0 0 * * * *\"".to_string()),
-                ..Default::default()
-            },
-            CompletionItem {
-                label: "0 0 0 * * *".to_string(),
-                kind: Some(CompletionItemKind::SNIPPET),
-                detail: Some("每天午夜执行".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "Cron 表达式：每天午夜 00:00:00 执行\n\n\
-                            **格式**: 秒 分 时 日 月 星期\n\n\
-                            **示例**:\n\
-                            ```rust\n\
-                            #[cron(\"0 0 0 * * *\")]\n\
-                            async fn daily_job() {\n\
-                            }\n\
-                            ```"
-                    .to_string(),
-                })),
-                insert_text: Some("\"0 0 0 * * *\"".to_string()),
-                ..Default::default()
-            },
-            CompletionItem {
-                label: "0 */5 * * * *".to_string(),
-                kind: Some(CompletionItemKind::SNIPPET),
-                detail: Some("每 5 分钟执行".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "Cron 表达式：每 5 分钟执行一次\n\n\
-                            **格式**: 秒 分 时 日 月 星期\n\n\
-                            **示例**:\n\
-                            ```rust\n\
-                            #[cron(\"0 */5 * * * *\")]\n\
-                            async fn every_five_minutes() {\n\
-                            }\n\
-                            ```"
-                    .to_string(),
-                })),
-                insert_text: Some("\"0 */5 * * * *\"".to_string()),
-                ..Default::default()
-            },
-            // fix_delay 值示例
-            CompletionItem {
-                label: "5".to_string(),
-                kind: Some(CompletionItemKind::VALUE),
-                detail: Some("延迟 5 秒".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "任务完成后延迟 5 秒再次执行\n\n\
-                            **示例**:\n\
-                            ```rust\n\
-                            #[fix_delay(5)]\n\
-                            async fn delayed_job() {\n\
-                            }\n\
-                            ```"
-                    .to_string(),
-                })),
-                insert_text: Some("5".to_string()),
-                ..Default::default()
-            },
-            CompletionItem {
-                label: "10".to_string(),
-                kind: Some(CompletionItemKind::VALUE),
-                detail: Some("延迟/频率 10 秒".to_string()),
-                documentation: Some(Documentation::MarkupContent(MarkupContent {
-                    kind: MarkupKind::Markdown,
-                    value: "延迟或频率为 10 秒\n\n\
-                            **fix_delay 示例**:\n\
-                            ```rust\n\
-                            #[fix_delay(10)]\n\
-                            async fn delayed_job() {\n\
-                            }\n\
-                            ```\n\n\
-                            **fix_rate 示例**:\n\
-                            ```rust\n\
-                            #[fix_rate(10)]\n\
-                            async fn periodic_job() {\n\
-                            }\n\
-                            ```"
-                    .to_string(),
-                })),
-                insert_text: Some("10".to_string()),
-                ..Default::default()
-            },
-            CompletionItem {
-                label: "60".to_string(),
+        let mut completions: Vec<CompletionItem> = [
+            ("0 0 * * * *", "hourly", "hourly_job"),
+            ("0 0 0 * * *", "daily", "daily_job"),
+            ("0 */5 * * * *", "every-five-minutes", "every_five_minutes"),
+        ]
+        .into_iter()
+        .map(|(expression, key, function)| CompletionItem {
+            label: expression.to_string(),
+            kind: Some(CompletionItemKind::SNIPPET),
+            detail: Some(localized(
+                self.locale,
+                &format!("completion.cron.{}", key),
+                &[],
+            )),
+            documentation: Some(self.rust_example(
+                format!(
+                    "{}\n\n{}",
+                    localized(self.locale, &format!("completion.cron.{}.doc", key), &[]),
+                    localized(self.locale, "completion.cron-format", &[])
+                ),
+                &format!(
+                    "#[cron(\"{}\")]\nasync fn {}() {{\n}}",
+                    expression, function
+                ),
+            )),
+            insert_text: Some(format!("\"{}\"", expression)),
+            ..Default::default()
+        })
+        .collect();
+
+        // fix_delay 值示例
+        completions.push(CompletionItem {
+            label: "5".to_string(),
+            kind: Some(CompletionItemKind::VALUE),
+            detail: Some(localized(self.locale, "completion.fix-delay", &[&5])),
+            documentation: Some(self.rust_example(
+                localized(self.locale, "completion.fix-delay.doc", &[&5]),
+                "#[fix_delay(5)]\nasync fn delayed_job() {\n}",
+            )),
+            insert_text: Some("5".to_string()),
+            ..Default::default()
+        });
+        for seconds in [10, 60] {
+            let key = if seconds == 60 {
+                "completion.delay-or-rate-minute"
+            } else {
+                "completion.delay-or-rate"
+            };
+            completions.push(CompletionItem {
+                label: seconds.to_string(),
                 kind: Some(CompletionItemKind::VALUE),
-                detail: Some("延迟/频率 60 秒（1 分钟）".to_string()),
+                detail: Some(localized(self.locale, key, &[&seconds])),
                 documentation: Some(Documentation::MarkupContent(MarkupContent {
                     kind: MarkupKind::Markdown,
-                    value: "延迟或频率为 60 秒（1 分钟）\n\n\
-                            **fix_delay 示例**:\n\
-                            ```rust\n\
-                            #[fix_delay(60)]\n\
-                            async fn delayed_job() {\n\
-                            }\n\
-                            ```\n\n\
-                            **fix_rate 示例**:\n\
-                            ```rust\n\
-                            #[fix_rate(60)]\n\
-                            async fn periodic_job() {\n\
-                            }\n\
-                            ```"
-                    .to_string(),
+                    value: format!(
+                        "{}\n\n{}\n```rust\n#[fix_delay({})]\nasync fn delayed_job() {{\n}}\n```\n\n{}\n```rust\n#[fix_rate({})]\nasync fn periodic_job() {{\n}}\n```",
+                        localized(self.locale, &format!("{}.doc", key), &[&seconds]),
+                        localized(self.locale, "completion.fix-delay-example", &[]),
+                        seconds,
+                        localized(self.locale, "completion.fix-rate-example", &[]),
+                        seconds
+                    ),
                 })),
-                insert_text: Some("60".to_string()),
+                insert_text: Some(seconds.to_string()),
                 ..Default::default()
-            },
-        ]
+            });
+        }
+        // one_shot 值示例
+        completions.push(CompletionItem {
+            label: "3".to_string(),
+            kind: Some(CompletionItemKind::VALUE),
+            detail: Some(localized(self.locale, "completion.one-shot", &[&3])),
+            documentation: Some(self.rust_example(
+                localized(self.locale, "completion.one-shot.doc", &[&3]),
+                "#[one_shot(3)]\nasync fn warm_up_cache() {\n}",
+            )),
+            insert_text: Some("3".to_string()),
+            ..Default::default()
+        });
+
+        completions
+    }
+
+    /// 带 Rust 示例代码的补全文档
+    fn rust_example(&self, description: String, code: &str) -> Documentation {
+        Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!(
+                "{}\n\n{}\n```rust\n{}\n```",
+                description,
+                localized(self.locale, "completion.example", &[]),
+                code
+            ),
+        })
     }
 }
 
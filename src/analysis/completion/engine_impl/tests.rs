@@ -41,6 +41,9 @@ fn test_complete_service_macro() {
         struct_name: "TestService".to_string(),
         fields: vec![],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let completions = engine.complete_macro(&SpringMacro::DeriveService(service_macro), None);
@@ -93,6 +96,7 @@ fn test_complete_inject_macro() {
         inject_type: InjectType::Component,
         component_name: None,
         range: test_range(),
+        name_range: None,
     };
 
     let completions = engine.complete_macro(&SpringMacro::Inject(inject_macro), None);
@@ -155,6 +159,11 @@ fn test_complete_route_macro() {
         handler_name: "test_handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let completions = engine.complete_macro(&SpringMacro::Route(route_macro), None);
@@ -200,6 +209,7 @@ fn test_complete_job_macro_cron() {
     let engine = test_engine();
     let job_macro = JobMacro::Cron {
         expression: "".to_string(),
+        timezone: None,
         range: test_range(),
     };
 
@@ -284,11 +294,15 @@ fn test_completion_items_have_documentation() {
             struct_name: "Test".to_string(),
             fields: vec![],
             range: test_range(),
+            doc: None,
+            deprecated: false,
+            cfg: None,
         }),
         SpringMacro::Inject(InjectMacro {
             inject_type: InjectType::Component,
             component_name: None,
             range: test_range(),
+            name_range: None,
         }),
         SpringMacro::AutoConfig(AutoConfigMacro {
             configurator_type: "".to_string(),
@@ -301,9 +315,15 @@ fn test_completion_items_have_documentation() {
             handler_name: "handler".to_string(),
             range: test_range(),
             is_openapi: false,
+            doc: None,
+            deprecated: false,
+            stub: false,
+            cfg: None,
+            instrument: None,
         }),
         SpringMacro::Job(JobMacro::Cron {
             expression: "".to_string(),
+            timezone: None,
             range: test_range(),
         }),
     ];
@@ -342,6 +362,9 @@ fn test_completion_items_have_correct_kind() {
             struct_name: "Test".to_string(),
             fields: vec![],
             range: test_range(),
+            doc: None,
+            deprecated: false,
+            cfg: None,
         }),
         None,
     );
@@ -359,6 +382,7 @@ fn test_completion_items_have_correct_kind() {
             inject_type: InjectType::Component,
             component_name: None,
             range: test_range(),
+            name_range: None,
         }),
         None,
     );
@@ -415,6 +439,40 @@ fn test_complete_with_toml_context() {
     assert!(completions.len() > 0);
 }
 
+#[test]
+fn test_complete_log_levels() {
+    let engine = test_engine();
+    let toml_analyzer = TomlAnalyzer::new(SchemaProvider::default());
+    let doc = toml_analyzer
+        .parse("[logger]\nlevel = \"\"\noverride_filter = \"info,my_app=\"\n")
+        .unwrap();
+
+    // 光标在 level 的引号内
+    let completions = engine.complete_toml_document(
+        &doc,
+        Position {
+            line: 1,
+            character: 9,
+        },
+    );
+    let labels: Vec<_> = completions.iter().map(|c| c.label.as_str()).collect();
+    assert_eq!(
+        labels,
+        vec!["trace", "debug", "info", "warn", "error", "off"]
+    );
+    assert_eq!(completions[0].insert_text.as_deref(), Some("trace"));
+
+    // override_filter 中同样补全日志级别
+    let completions = engine.complete_toml_document(
+        &doc,
+        Position {
+            line: 2,
+            character: 31,
+        },
+    );
+    assert_eq!(completions.len(), 6);
+}
+
 #[test]
 fn test_complete_with_macro_context() {
     let engine = test_engine();
@@ -422,6 +480,9 @@ fn test_complete_with_macro_context() {
         struct_name: "TestService".to_string(),
         fields: vec![],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let position = Position {
@@ -501,6 +562,9 @@ fn test_complete_dispatches_to_correct_handler() {
                 struct_name: "Test".to_string(),
                 fields: vec![],
                 range: test_range(),
+                doc: None,
+                deprecated: false,
+                cfg: None,
             }),
             3, // Service 宏应该返回 3 个补全项
         ),
@@ -509,6 +573,7 @@ fn test_complete_dispatches_to_correct_handler() {
                 inject_type: InjectType::Component,
                 component_name: None,
                 range: test_range(),
+                name_range: None,
             }),
             2, // Inject 宏应该返回 2 个补全项
         ),
@@ -764,9 +829,9 @@ fn test_complete_redis_section() {
     // 应该提供 redis 配置项的补全
     assert!(completions.len() > 0);
 
-    // 检查是否包含 url
-    let url_completion = completions.iter().find(|c| c.label == "url");
-    assert!(url_completion.is_some(), "应该包含 url 补全");
+    // 检查是否包含 uri
+    let uri_completion = completions.iter().find(|c| c.label == "uri");
+    assert!(uri_completion.is_some(), "应该包含 uri 补全");
 }
 
 #[test]
@@ -979,6 +1044,87 @@ fn test_position_in_range() {
     assert!(!engine.position_in_range(pos_after_char, range));
 }
 
+#[test]
+fn test_complete_toml_cached() {
+    let engine = test_engine();
+    let toml_analyzer = TomlAnalyzer::new(SchemaProvider::default());
+    let uri = Url::parse("file:///config/app.toml").unwrap();
+
+    let doc = toml_analyzer.parse("[web]\nhost = \"localhost\"").unwrap();
+    let position = Position {
+        line: 1,
+        character: 5,
+    };
+
+    // 第一次补全：未命中缓存
+    let first = engine.complete_toml_cached(&uri, 1, &doc, position);
+    assert!(!first.cache_hit);
+    assert!(first.items.iter().any(|c| c.label == "port"));
+
+    // 同一版本、同一补全类型（同一配置节内的其他位置）：命中缓存
+    let other_position = Position {
+        line: 1,
+        character: 0,
+    };
+    let second = engine.complete_toml_cached(&uri, 1, &doc, other_position);
+    assert!(second.cache_hit);
+    assert_eq!(second.items.len(), first.items.len());
+
+    // 文档版本变化：缓存失效
+    let doc = toml_analyzer
+        .parse("[web]\nhost = \"localhost\"\nport = 8080")
+        .unwrap();
+    let third = engine.complete_toml_cached(&uri, 2, &doc, position);
+    assert!(!third.cache_hit);
+    assert!(!third.items.iter().any(|c| c.label == "port"));
+
+    // 主动清除缓存
+    engine.invalidate(&uri);
+    let fourth = engine.complete_toml_cached(&uri, 2, &doc, position);
+    assert!(!fourth.cache_hit);
+
+    let stats = engine.cache_stats();
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.misses, 3);
+    assert_eq!(stats.documents, 1);
+    assert!((stats.hit_rate() - 0.25).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_profile_override_completions() {
+    let engine = test_engine();
+    let toml_analyzer = TomlAnalyzer::new(SchemaProvider::default());
+
+    let base = toml_analyzer
+        .parse("[web]\nhost = \"0.0.0.0\"\nport = 8080\n\n[redis]\nuri = \"redis://localhost\"")
+        .unwrap();
+    let doc = toml_analyzer.parse("[web]\nhost = \"10.0.0.1\"").unwrap();
+    let position = Position {
+        line: 1,
+        character: 0,
+    };
+
+    let mut items = engine.complete_toml_document(&doc, position);
+    let schema_count = items.len();
+    engine.add_profile_overrides(&mut items, &doc, &base, position);
+
+    // 基础配置中未覆盖的配置项排在最前面，替换 Schema 中的同名补全项
+    let port = &items[0];
+    assert_eq!(port.label, "port");
+    assert_eq!(port.detail.as_deref(), Some("base value: 8080"));
+    assert_eq!(port.insert_text.as_deref(), Some("port = 8080"));
+    assert_eq!(items.iter().filter(|c| c.label == "port").count(), 1);
+    assert_eq!(items.len(), schema_count);
+    // 已覆盖的配置项和其他配置节的配置项不补全
+    assert!(!items.iter().any(|c| c.label == "host"));
+    assert!(!items.iter().any(|c| c.label == "uri"));
+
+    // 配置节外不补充
+    let mut items = Vec::new();
+    engine.add_profile_overrides(&mut items, &doc, &base, Position::new(10, 0));
+    assert!(items.is_empty());
+}
+
 // ============================================================================
 // 补全引擎属性测试（任务 9.3）
 // ============================================================================
@@ -1023,6 +1169,7 @@ mod property_tests {
                     character: 100,
                 },
             },
+            key_range: Range::default(),
         }
     }
 
@@ -1650,6 +1797,9 @@ fn test_macro_parameter_insertion_completeness() {
         struct_name: "Test".to_string(),
         fields: vec![],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let completions = engine.complete_macro(&SpringMacro::DeriveService(service_macro), None);
@@ -1685,6 +1835,11 @@ fn test_route_macro_path_parameter_snippet() {
         handler_name: "handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let completions = engine.complete_macro(&SpringMacro::Route(route_macro), None);
@@ -2079,7 +2234,7 @@ fn test_complete_with_nested_config_sections() {
     let toml_analyzer = TomlAnalyzer::new(schema_provider);
 
     // 创建包含多个配置节的文档
-    let toml_content = "[web]\nhost = \"localhost\"\n\n[redis]\nurl = \"redis://localhost\"";
+    let toml_content = "[web]\nhost = \"localhost\"\n\n[redis]\nuri = \"redis://localhost\"";
     let doc = toml_analyzer.parse(toml_content).unwrap();
 
     // 在第一个配置节内
@@ -2092,8 +2247,8 @@ fn test_complete_with_nested_config_sections() {
 
     // 应该只补全 web 配置节的属性
     // 验证不包含 redis 的属性
-    let has_url = completions_web.iter().any(|c| c.label == "url");
-    assert!(!has_url, "在 web 配置节内不应该补全 redis 的属性");
+    let has_uri = completions_web.iter().any(|c| c.label == "uri");
+    assert!(!has_uri, "在 web 配置节内不应该补全 redis 的属性");
 }
 
 #[test]
@@ -2115,3 +2270,62 @@ fn test_completion_context_equality() {
         _ => panic!("不同的上下文应该不匹配"),
     }
 }
+
+#[test]
+fn test_complete_rust_headers() {
+    let engine = test_engine();
+    let content = "#[post(\"/upload\")]\nasync fn upload() -> impl IntoResponse {\n    let mut headers = HeaderMap::new();\n    headers.insert(\"content-type\", \"\n}\n";
+
+    let completions = engine.complete_rust(&PositionMapper::new(content), Position::new(3, 37));
+    assert!(completions
+        .iter()
+        .any(|item| item.label == "multipart/form-data"));
+    assert!(completions
+        .iter()
+        .all(|item| item.kind == Some(CompletionItemKind::VALUE)));
+}
+
+#[test]
+fn test_complete_deprecated_route_macro() {
+    let engine = test_engine();
+    let route_macro = RouteMacro {
+        path: "/legacy".to_string(),
+        methods: vec![HttpMethod::Get],
+        middlewares: vec![],
+        handler_name: "legacy_handler".to_string(),
+        range: test_range(),
+        is_openapi: false,
+        doc: None,
+        deprecated: true,
+        stub: false,
+        cfg: None,
+        instrument: None,
+    };
+
+    let completions = engine.complete_macro(&SpringMacro::Route(route_macro), None);
+
+    // 已弃用处理器上的补全项都带有 Deprecated 标记
+    assert!(!completions.is_empty());
+    assert!(completions
+        .iter()
+        .all(|c| c.tags == Some(vec![CompletionItemTag::DEPRECATED])));
+}
+
+#[test]
+fn test_complete_schema_enum_values() {
+    let engine = test_engine();
+    let toml_analyzer = TomlAnalyzer::new(SchemaProvider::default());
+    let doc = toml_analyzer
+        .parse("[opentelemetry]\nprotocol = \"\"\n")
+        .unwrap();
+
+    let completions = engine.complete(
+        CompletionContext::Toml,
+        Position::new(1, 12),
+        Some(&doc),
+        None,
+    );
+    let labels: Vec<_> = completions.iter().map(|c| c.label.as_str()).collect();
+    assert_eq!(labels, vec!["grpc", "http/protobuf", "http/json"]);
+    assert_eq!(completions[0].insert_text.as_deref(), Some("grpc"));
+}
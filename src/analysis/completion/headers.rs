@@ -0,0 +1,541 @@
+//! HTTP 头补全模块
+//!
+//! 在路由处理器中为 HTTP 头名称和常用值提供补全，支持以下写法：
+//! - `headers.insert("`、`.append("`、`.get("`、`.header("` 等：补全头名称
+//! - `headers.insert("content-type", "` 或 `HeaderValue::from_static("`：补全该头的常用值
+//! - `TypedHeader<`：补全 `headers` crate 提供的类型化头
+//!
+//! 补全只在处理器（带有路由宏的函数）内生效。输入过程中的代码通常无法解析，
+//! 因此通过文本向上查找最近的函数定义及其属性来判断是否位于处理器中。
+
+use lsp_types::{CompletionItem, CompletionItemKind, Documentation, Position};
+
+use crate::analysis::rust::handler_analyzer::ROUTE_ATTRIBUTES;
+use crate::core::document::PositionMapper;
+use crate::core::messages::{localized, Locale};
+
+/// HTTP 头知识库条目
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderInfo {
+    /// 头名称（小写）
+    pub name: &'static str,
+    /// 说明
+    pub description: &'static str,
+    /// 英文说明
+    pub description_en: &'static str,
+    /// 常用值
+    pub values: &'static [&'static str],
+    /// `headers` crate 中对应的类型化头
+    pub typed: Option<&'static str>,
+}
+
+impl HeaderInfo {
+    /// 指定语言的说明
+    pub fn describe(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::ZhCn => self.description,
+            Locale::En => self.description_en,
+        }
+    }
+}
+
+/// 标准 HTTP 头知识库
+pub const HTTP_HEADERS: &[HeaderInfo] = &[
+    HeaderInfo {
+        name: "accept",
+        description: "客户端可以处理的内容类型",
+        description_en: "Content types the client can handle",
+        values: &["application/json", "text/html", "text/plain", "*/*"],
+        typed: None,
+    },
+    HeaderInfo {
+        name: "accept-encoding",
+        description: "客户端支持的内容编码",
+        description_en: "Content encodings the client supports",
+        values: &["gzip", "deflate", "br", "identity"],
+        typed: None,
+    },
+    HeaderInfo {
+        name: "accept-language",
+        description: "客户端偏好的语言",
+        description_en: "Languages the client prefers",
+        values: &["zh-CN", "en-US", "*"],
+        typed: None,
+    },
+    HeaderInfo {
+        name: "access-control-allow-headers",
+        description: "CORS：允许的请求头",
+        description_en: "CORS: allowed request headers",
+        values: &["content-type, authorization", "*"],
+        typed: Some("AccessControlAllowHeaders"),
+    },
+    HeaderInfo {
+        name: "access-control-allow-methods",
+        description: "CORS：允许的请求方法",
+        description_en: "CORS: allowed request methods",
+        values: &["GET, POST, PUT, DELETE, OPTIONS"],
+        typed: Some("AccessControlAllowMethods"),
+    },
+    HeaderInfo {
+        name: "access-control-allow-origin",
+        description: "CORS：允许访问资源的来源",
+        description_en: "CORS: origins allowed to access the resource",
+        values: &["*", "null"],
+        typed: Some("AccessControlAllowOrigin"),
+    },
+    HeaderInfo {
+        name: "authorization",
+        description: "请求的认证凭据",
+        description_en: "Authentication credentials for the request",
+        values: &["Bearer ", "Basic "],
+        typed: Some("Authorization<Bearer>"),
+    },
+    HeaderInfo {
+        name: "cache-control",
+        description: "缓存策略",
+        description_en: "Caching directives",
+        values: &["no-cache", "no-store", "max-age=3600", "private", "public"],
+        typed: Some("CacheControl"),
+    },
+    HeaderInfo {
+        name: "connection",
+        description: "当前请求完成后是否保持连接",
+        description_en: "Whether the connection stays open after the current request",
+        values: &["keep-alive", "close"],
+        typed: Some("Connection"),
+    },
+    HeaderInfo {
+        name: "content-disposition",
+        description: "内容以内联还是附件形式展示",
+        description_en: "Whether the content is displayed inline or as an attachment",
+        values: &["inline", "attachment", "attachment; filename=\"\""],
+        typed: Some("ContentDisposition"),
+    },
+    HeaderInfo {
+        name: "content-encoding",
+        description: "消息体使用的内容编码",
+        description_en: "Content encoding applied to the body",
+        values: &["gzip", "deflate", "br"],
+        typed: Some("ContentEncoding"),
+    },
+    HeaderInfo {
+        name: "content-length",
+        description: "消息体的字节长度",
+        description_en: "Length of the body in bytes",
+        values: &[],
+        typed: Some("ContentLength"),
+    },
+    HeaderInfo {
+        name: "content-type",
+        description: "消息体的媒体类型",
+        description_en: "Media type of the body",
+        values: &[
+            "application/json",
+            "text/plain; charset=utf-8",
+            "text/html; charset=utf-8",
+            "application/x-www-form-urlencoded",
+            "multipart/form-data",
+            "application/octet-stream",
+        ],
+        typed: Some("ContentType"),
+    },
+    HeaderInfo {
+        name: "cookie",
+        description: "客户端发送的 Cookie",
+        description_en: "Cookies sent by the client",
+        values: &[],
+        typed: Some("Cookie"),
+    },
+    HeaderInfo {
+        name: "etag",
+        description: "资源版本标识",
+        description_en: "Identifier for a specific version of the resource",
+        values: &[],
+        typed: Some("ETag"),
+    },
+    HeaderInfo {
+        name: "host",
+        description: "请求的目标主机和端口",
+        description_en: "Target host and port of the request",
+        values: &[],
+        typed: Some("Host"),
+    },
+    HeaderInfo {
+        name: "if-none-match",
+        description: "条件请求：ETag 不匹配时才返回资源",
+        description_en: "Conditional request: return the resource only if the ETag does not match",
+        values: &["*"],
+        typed: Some("IfNoneMatch"),
+    },
+    HeaderInfo {
+        name: "location",
+        description: "重定向的目标地址",
+        description_en: "Target URL of a redirect",
+        values: &[],
+        typed: Some("Location"),
+    },
+    HeaderInfo {
+        name: "origin",
+        description: "请求的来源",
+        description_en: "Origin of the request",
+        values: &[],
+        typed: Some("Origin"),
+    },
+    HeaderInfo {
+        name: "referer",
+        description: "发起请求的页面地址",
+        description_en: "Address of the page that made the request",
+        values: &[],
+        typed: Some("Referer"),
+    },
+    HeaderInfo {
+        name: "retry-after",
+        description: "客户端应等待多久后重试",
+        description_en: "How long the client should wait before retrying",
+        values: &["120"],
+        typed: Some("RetryAfter"),
+    },
+    HeaderInfo {
+        name: "set-cookie",
+        description: "服务端设置的 Cookie",
+        description_en: "Cookies set by the server",
+        values: &["name=value; Path=/; HttpOnly; Secure; SameSite=Lax"],
+        typed: None,
+    },
+    HeaderInfo {
+        name: "strict-transport-security",
+        description: "强制客户端使用 HTTPS",
+        description_en: "Forces the client to use HTTPS",
+        values: &["max-age=31536000; includeSubDomains"],
+        typed: Some("StrictTransportSecurity"),
+    },
+    HeaderInfo {
+        name: "user-agent",
+        description: "客户端的用户代理字符串",
+        description_en: "User agent string of the client",
+        values: &[],
+        typed: Some("UserAgent"),
+    },
+    HeaderInfo {
+        name: "vary",
+        description: "影响缓存的请求头",
+        description_en: "Request headers that affect caching",
+        values: &["accept-encoding", "origin"],
+        typed: Some("Vary"),
+    },
+    HeaderInfo {
+        name: "x-content-type-options",
+        description: "禁止浏览器嗅探内容类型",
+        description_en: "Prevents the browser from sniffing the content type",
+        values: &["nosniff"],
+        typed: None,
+    },
+    HeaderInfo {
+        name: "x-forwarded-for",
+        description: "代理转发链中的客户端地址",
+        description_en: "Client addresses along the proxy chain",
+        values: &[],
+        typed: None,
+    },
+    HeaderInfo {
+        name: "x-frame-options",
+        description: "是否允许页面被嵌入到 frame 中",
+        description_en: "Whether the page may be embedded in a frame",
+        values: &["DENY", "SAMEORIGIN"],
+        typed: None,
+    },
+    HeaderInfo {
+        name: "x-request-id",
+        description: "请求追踪 ID",
+        description_en: "Request tracing ID",
+        values: &[],
+        typed: None,
+    },
+];
+
+/// 以头名称为第一个参数的方法
+const HEADER_NAME_METHODS: &[&str] = &[
+    "insert",
+    "append",
+    "header",
+    "get",
+    "get_all",
+    "contains_key",
+    "remove",
+];
+
+/// 以头值为第二个参数的方法
+const HEADER_VALUE_METHODS: &[&str] = &["insert", "append", "header"];
+
+/// 构造头值的函数（参数为字符串字面量）
+const HEADER_VALUE_CONSTRUCTORS: &[&str] = &["HeaderValue::from_static(", "HeaderValue::from_str("];
+
+/// 查找 HTTP 头知识库条目（不区分大小写）
+pub fn find_header(name: &str) -> Option<&'static HeaderInfo> {
+    HTTP_HEADERS
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+}
+
+/// HTTP 头补全上下文
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HeaderContext {
+    /// 在头名称字符串中
+    Name,
+    /// 在头值字符串中
+    Value(String),
+    /// 在 `TypedHeader<` 之后
+    TypedHeader,
+}
+
+/// 为处理器中的 HTTP 头提供补全
+///
+/// 不在处理器中或光标处不是 HTTP 头相关写法时返回空列表
+pub fn complete_headers(
+    mapper: &PositionMapper,
+    position: Position,
+    locale: Locale,
+) -> Vec<CompletionItem> {
+    let content = mapper.content();
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(line) = lines.get(position.line as usize) else {
+        return Vec::new();
+    };
+    let line_start = mapper
+        .offset(Position::new(position.line, 0))
+        .unwrap_or_default();
+    let cursor = mapper.offset(position).unwrap_or(line_start);
+    let line_prefix = &content[line_start..cursor.min(line_start + line.len())];
+
+    let Some(context) = header_context(line_prefix) else {
+        return Vec::new();
+    };
+
+    let mut preceding = lines[..position.line as usize].to_vec();
+    preceding.push(line_prefix);
+    if !in_handler(&preceding) {
+        return Vec::new();
+    }
+
+    match context {
+        HeaderContext::Name => HTTP_HEADERS
+            .iter()
+            .map(|header| CompletionItem {
+                label: header.name.to_string(),
+                kind: Some(CompletionItemKind::PROPERTY),
+                detail: Some(localized(locale, "completion.header", &[])),
+                documentation: Some(Documentation::String(header.describe(locale).to_string())),
+                ..Default::default()
+            })
+            .collect(),
+        HeaderContext::Value(name) => find_header(&name)
+            .map(|header| {
+                header
+                    .values
+                    .iter()
+                    .map(|value| CompletionItem {
+                        label: value.to_string(),
+                        kind: Some(CompletionItemKind::VALUE),
+                        detail: Some(localized(
+                            locale,
+                            "completion.header-value",
+                            &[&header.name],
+                        )),
+                        ..Default::default()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        HeaderContext::TypedHeader => HTTP_HEADERS
+            .iter()
+            .filter_map(|header| {
+                let typed = header.typed?;
+                Some(CompletionItem {
+                    label: typed.to_string(),
+                    kind: Some(CompletionItemKind::STRUCT),
+                    detail: Some(localized(
+                        locale,
+                        "completion.typed-header",
+                        &[&header.name],
+                    )),
+                    documentation: Some(Documentation::String(header.describe(locale).to_string())),
+                    ..Default::default()
+                })
+            })
+            .collect(),
+    }
+}
+
+/// 根据光标所在行光标之前的文本判断补全上下文
+fn header_context(line_prefix: &str) -> Option<HeaderContext> {
+    // TypedHeader<Con|
+    if let Some(index) = line_prefix.rfind("TypedHeader<") {
+        let rest = &line_prefix[index + "TypedHeader<".len()..];
+        if rest.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Some(HeaderContext::TypedHeader);
+        }
+    }
+
+    // 最后一个头方法调用：.insert( / .get( ...
+    let (method, args) = HEADER_NAME_METHODS
+        .iter()
+        .filter_map(|method| {
+            let call = format!(".{}(", method);
+            line_prefix
+                .rfind(&call)
+                .map(|index| (index, *method, &line_prefix[index + call.len()..]))
+        })
+        .max_by_key(|(index, _, _)| *index)
+        .map(|(_, method, args)| (method, args))?;
+
+    let args = args.trim_start();
+    let (name, rest) = match args.strip_prefix('"') {
+        Some(literal) => match literal.find('"') {
+            Some(end) => (literal[..end].to_string(), &literal[end + 1..]),
+            // 头名称字符串尚未结束
+            None => return Some(HeaderContext::Name),
+        },
+        None => {
+            // header::CONTENT_TYPE 形式的常量
+            let end = args.find(',')?;
+            let constant = args[..end].trim().rsplit("::").next()?;
+            if constant.is_empty() || !constant.chars().all(|c| c.is_ascii_uppercase() || c == '_')
+            {
+                return None;
+            }
+            (
+                constant.to_ascii_lowercase().replace('_', "-"),
+                &args[end..],
+            )
+        }
+    };
+
+    if !HEADER_VALUE_METHODS.contains(&method) {
+        return None;
+    }
+
+    let value = rest.trim_start().strip_prefix(',')?.trim_start();
+    let value = HEADER_VALUE_CONSTRUCTORS
+        .iter()
+        .find_map(|constructor| value.strip_prefix(constructor))
+        .unwrap_or(value);
+    let literal = value.strip_prefix('"')?;
+    if literal.contains('"') {
+        return None;
+    }
+
+    Some(HeaderContext::Value(name))
+}
+
+/// 判断最后一行是否位于路由处理器中
+fn in_handler(lines: &[&str]) -> bool {
+    lines
+        .iter()
+        .rposition(|line| is_fn_definition(line))
+        .is_some_and(|fn_line| !route_attributes(&lines[..fn_line]).is_empty())
+}
+
+/// 函数定义上方紧邻的属性中的路由宏（`lines` 的最后一行紧邻函数定义），按源代码顺序返回去掉缩进的属性行
+pub(crate) fn route_attributes<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let mut attributes: Vec<&str> = lines
+        .iter()
+        .rev()
+        .map(|line| line.trim_start())
+        .take_while(|line| line.starts_with("#[") || line.starts_with("//"))
+        .filter(|line| {
+            ROUTE_ATTRIBUTES.iter().any(|name| {
+                line.strip_prefix("#[")
+                    .and_then(|attr| attr.strip_prefix(name))
+                    .is_some_and(|rest| rest.starts_with('(') || rest.starts_with(']'))
+            })
+        })
+        .collect();
+    attributes.reverse();
+    attributes
+}
+
+/// 判断一行是否为函数定义的开头
+pub(crate) fn is_fn_definition(line: &str) -> bool {
+    let mut rest = line.trim_start();
+    for modifier in ["pub(crate) ", "pub ", "async ", "unsafe "] {
+        rest = rest.strip_prefix(modifier).unwrap_or(rest);
+    }
+    rest.starts_with("fn ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HANDLER: &str = r#"#[get("/users")]
+async fn list_users(TypedHeader(agent): TypedHeader<Us
+) -> impl IntoResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert("con
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("app
+}
+
+fn helper(headers: &mut HeaderMap) {
+    headers.insert("
+}
+"#;
+
+    fn labels(line: u32, character: u32) -> Vec<String> {
+        complete_headers(
+            &PositionMapper::new(HANDLER),
+            Position::new(line, character),
+            Locale::default(),
+        )
+        .into_iter()
+        .map(|item| item.label)
+        .collect()
+    }
+
+    #[test]
+    fn test_header_context() {
+        assert_eq!(
+            header_context("    headers.insert(\"con"),
+            Some(HeaderContext::Name)
+        );
+        assert_eq!(
+            header_context("    req.headers().get(\""),
+            Some(HeaderContext::Name)
+        );
+        assert_eq!(
+            header_context("    headers.insert(\"content-type\", \"app"),
+            Some(HeaderContext::Value("content-type".to_string()))
+        );
+        assert_eq!(
+            header_context("    .header(header::CACHE_CONTROL, \""),
+            Some(HeaderContext::Value("cache-control".to_string()))
+        );
+        assert_eq!(
+            header_context("fn h(h: TypedHeader<Co"),
+            Some(HeaderContext::TypedHeader)
+        );
+        assert_eq!(header_context("    headers.get(\"accept\", \""), None);
+        assert_eq!(header_context("    map.insert(key, \""), None);
+        assert_eq!(header_context("    let x = \"con"), None);
+    }
+
+    #[test]
+    fn test_complete_headers_in_handler() {
+        let typed = labels(1, 54);
+        assert!(typed.contains(&"UserAgent".to_string()));
+        assert!(typed.contains(&"Authorization<Bearer>".to_string()));
+
+        let names = labels(4, 23);
+        assert!(names.contains(&"content-type".to_string()));
+        assert_eq!(names.len(), HTTP_HEADERS.len());
+
+        let values = labels(5, 70);
+        assert!(values.contains(&"application/json".to_string()));
+        assert!(!values.contains(&"gzip".to_string()));
+    }
+
+    #[test]
+    fn test_no_header_completion_outside_handler() {
+        assert!(labels(9, 20).is_empty());
+        assert!(labels(3, 10).is_empty());
+    }
+}
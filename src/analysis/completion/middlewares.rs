@@ -0,0 +1,322 @@
+//! 中间件补全模块
+//!
+//! 光标位于 `#[middlewares(...)]` 的参数列表中、正在输入一个中间件时，补全：
+//! - spring-web 常用的内置中间件（tower-http 提供的 Layer 和 axum 的 `middleware::from_fn` 等），附带说明
+//! - 工作空间中定义的中间件（实现了 `Layer<S>` 的类型和接收 `Next` 参数的函数，见
+//!   [`MiddlewareScanner::workspace_middlewares`](crate::scanner::middleware::MiddlewareScanner::workspace_middlewares)）
+//!
+//! 输入过程中的代码通常无法解析，因此通过光标之前的文本判断是否位于属性参数列表中。
+
+use lsp_types::{
+    CompletionItem, CompletionItemKind, Documentation, InsertTextFormat, MarkupContent, MarkupKind,
+    Position,
+};
+
+use crate::core::document::PositionMapper;
+use crate::core::messages::{localized, Locale};
+use crate::scanner::middleware::{MiddlewareDefinition, MiddlewareKind};
+
+/// 内置中间件知识库条目
+#[derive(Debug, Clone, Copy)]
+pub struct BundledMiddleware {
+    /// 类型名或函数名
+    pub name: &'static str,
+    /// 所在模块
+    pub module: &'static str,
+    /// 说明
+    pub description: &'static str,
+    /// 英文说明
+    pub description_en: &'static str,
+    /// 插入的代码片段（LSP snippet 语法）
+    pub snippet: &'static str,
+}
+
+impl BundledMiddleware {
+    /// 指定语言的说明
+    pub fn describe(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::ZhCn => self.description,
+            Locale::En => self.description_en,
+        }
+    }
+}
+
+/// spring-web 中常用的内置中间件
+pub const BUNDLED_MIDDLEWARES: &[BundledMiddleware] = &[
+    BundledMiddleware {
+        name: "TraceLayer",
+        module: "tower_http::trace",
+        description: "为每个请求创建 tracing span 并记录请求和响应日志",
+        description_en: "Creates a tracing span for every request and logs requests and responses",
+        snippet: "TraceLayer::new_for_http()",
+    },
+    BundledMiddleware {
+        name: "CorsLayer",
+        module: "tower_http::cors",
+        description: "处理跨域资源共享（CORS）预检请求并添加响应头",
+        description_en: "Handles cross-origin resource sharing (CORS) preflight requests and adds response headers",
+        snippet: "CorsLayer::${1:permissive}()",
+    },
+    BundledMiddleware {
+        name: "CompressionLayer",
+        module: "tower_http::compression",
+        description: "按请求的 Accept-Encoding 压缩响应体",
+        description_en: "Compresses response bodies according to the request's Accept-Encoding",
+        snippet: "CompressionLayer::new()",
+    },
+    BundledMiddleware {
+        name: "DecompressionLayer",
+        module: "tower_http::decompression",
+        description: "按 Content-Encoding 解压请求体",
+        description_en: "Decompresses request bodies according to Content-Encoding",
+        snippet: "DecompressionLayer::new()",
+    },
+    BundledMiddleware {
+        name: "TimeoutLayer",
+        module: "tower_http::timeout",
+        description: "请求处理超过指定时间时返回 408 Request Timeout",
+        description_en: "Returns 408 Request Timeout when handling takes longer than the given duration",
+        snippet: "TimeoutLayer::new(Duration::from_secs(${1:30}))",
+    },
+    BundledMiddleware {
+        name: "CatchPanicLayer",
+        module: "tower_http::catch_panic",
+        description: "捕获处理器中的 panic 并返回 500 Internal Server Error",
+        description_en: "Catches panics in handlers and returns 500 Internal Server Error",
+        snippet: "CatchPanicLayer::new()",
+    },
+    BundledMiddleware {
+        name: "RequestBodyLimitLayer",
+        module: "tower_http::limit",
+        description: "限制请求体大小，超出时返回 413 Payload Too Large",
+        description_en: "Limits the request body size and returns 413 Payload Too Large when exceeded",
+        snippet: "RequestBodyLimitLayer::new(${1:1024 * 1024})",
+    },
+    BundledMiddleware {
+        name: "SetRequestIdLayer",
+        module: "tower_http::request_id",
+        description: "为请求生成 x-request-id 请求头",
+        description_en: "Generates an x-request-id header for requests",
+        snippet: "SetRequestIdLayer::x_request_id(${1:MakeRequestUuid})",
+    },
+    BundledMiddleware {
+        name: "PropagateRequestIdLayer",
+        module: "tower_http::request_id",
+        description: "把请求的 x-request-id 复制到响应头",
+        description_en: "Copies the request's x-request-id to the response headers",
+        snippet: "PropagateRequestIdLayer::x_request_id()",
+    },
+    BundledMiddleware {
+        name: "SetResponseHeaderLayer",
+        module: "tower_http::set_header",
+        description: "为响应设置固定的响应头",
+        description_en: "Sets a fixed header on responses",
+        snippet: "SetResponseHeaderLayer::if_not_present(${1:header::CACHE_CONTROL}, HeaderValue::from_static(\"${2:no-cache}\"))",
+    },
+    BundledMiddleware {
+        name: "NormalizePathLayer",
+        module: "tower_http::normalize_path",
+        description: "去掉请求路径末尾的斜杠后再匹配路由",
+        description_en: "Strips the trailing slash from the request path before routing",
+        snippet: "NormalizePathLayer::trim_trailing_slash()",
+    },
+    BundledMiddleware {
+        name: "from_fn",
+        module: "axum::middleware",
+        description: "把 `async fn(Request, Next) -> Response` 形式的函数作为中间件",
+        description_en: "Uses an `async fn(Request, Next) -> Response` function as middleware",
+        snippet: "middleware::from_fn(${1:auth})",
+    },
+    BundledMiddleware {
+        name: "from_fn_with_state",
+        module: "axum::middleware",
+        description: "把需要应用状态的中间件函数作为中间件",
+        description_en: "Uses a middleware function that needs application state as middleware",
+        snippet: "middleware::from_fn_with_state(${1:state}, ${2:auth})",
+    },
+    BundledMiddleware {
+        name: "from_extractor",
+        module: "axum::middleware",
+        description: "在处理器之前运行提取器，提取失败时直接返回拒绝响应（常用于认证）",
+        description_en: "Runs an extractor before the handler and returns its rejection on failure (commonly used for authentication)",
+        snippet: "middleware::from_extractor::<${1:RequireAuth}>()",
+    },
+];
+
+/// 查找中间件表达式中的标识符对应的内置中间件（如 `TraceLayer::new_for_http()` 中的 `TraceLayer`）
+pub fn find_bundled_middleware(idents: &[String]) -> Option<&'static BundledMiddleware> {
+    idents.iter().find_map(|ident| {
+        BUNDLED_MIDDLEWARES
+            .iter()
+            .find(|middleware| middleware.name == ident)
+    })
+}
+
+/// 内置中间件的 Markdown 说明
+pub fn bundled_middleware_docs(middleware: &BundledMiddleware, locale: Locale) -> String {
+    localized(
+        locale,
+        "hover.middleware.bundled",
+        &[
+            &middleware.describe(locale),
+            &middleware.module,
+            &middleware.name,
+        ],
+    )
+}
+
+/// 判断光标是否位于 `#[middlewares(...)]` 的参数列表中、正在输入一个中间件
+pub fn in_middlewares_attr(lines: &PositionMapper, position: Position) -> bool {
+    let Some(cursor) = lines.offset(position) else {
+        return false;
+    };
+    let prefix = &lines.content()[..cursor];
+    let Some(start) = prefix.rfind("#[middlewares(") else {
+        return false;
+    };
+
+    // 跟踪括号深度，记录当前中间件的起始位置
+    let args = &prefix[start + "#[middlewares(".len()..];
+    let mut depth = 1;
+    let mut item_start = 0;
+    for (index, c) in args.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' | '>' => {
+                depth -= 1;
+                if depth == 0 {
+                    return false;
+                }
+            }
+            ',' if depth == 1 => item_start = index + 1,
+            _ => {}
+        }
+    }
+
+    depth == 1
+        && args[item_start..]
+            .trim_start()
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == ':')
+}
+
+/// 补全中间件：工作空间中定义的中间件在前，内置中间件在后
+pub fn complete_middlewares(
+    workspace: &[MiddlewareDefinition],
+    locale: Locale,
+) -> Vec<CompletionItem> {
+    let workspace_items = workspace.iter().map(|definition| {
+        let kind = match definition.kind {
+            MiddlewareKind::Layer => CompletionItemKind::STRUCT,
+            MiddlewareKind::Function => CompletionItemKind::FUNCTION,
+        };
+        CompletionItem {
+            label: definition.name.clone(),
+            kind: Some(kind),
+            detail: Some(localized(locale, "completion.workspace-middleware", &[])),
+            documentation: definition.summary.clone().map(Documentation::String),
+            insert_text: Some(definition.usage()),
+            sort_text: Some(format!("0_{}", definition.name)),
+            ..Default::default()
+        }
+    });
+
+    let bundled_items = BUNDLED_MIDDLEWARES.iter().map(|middleware| {
+        let kind = if middleware.module == "axum::middleware" {
+            CompletionItemKind::FUNCTION
+        } else {
+            CompletionItemKind::STRUCT
+        };
+        CompletionItem {
+            label: middleware.name.to_string(),
+            kind: Some(kind),
+            detail: Some(middleware.module.to_string()),
+            documentation: Some(Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: bundled_middleware_docs(middleware, locale),
+            })),
+            insert_text: Some(middleware.snippet.to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            sort_text: Some(format!("1_{}", middleware.name)),
+            ..Default::default()
+        }
+    });
+
+    workspace_items.chain(bundled_items).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Url;
+
+    const SOURCE: &str = r#"#[middlewares(TraceLayer::new_for_http(), Tim
+#[get("/")]
+async fn index() {}
+
+#[middlewares(middleware::from_fn(auth), )]
+#[get("/admin")]
+async fn admin() {}
+"#;
+
+    #[test]
+    fn test_in_middlewares_attr() {
+        assert!(in_middlewares_attr(
+            &PositionMapper::new(SOURCE),
+            Position::new(0, 14)
+        ));
+        assert!(in_middlewares_attr(
+            &PositionMapper::new(SOURCE),
+            Position::new(0, 45)
+        ));
+        // 在中间件的参数中
+        assert!(!in_middlewares_attr(
+            &PositionMapper::new(SOURCE),
+            Position::new(0, 39)
+        ));
+        // 属性已结束
+        assert!(!in_middlewares_attr(
+            &PositionMapper::new(SOURCE),
+            Position::new(2, 5)
+        ));
+        assert!(in_middlewares_attr(
+            &PositionMapper::new(SOURCE),
+            Position::new(4, 41)
+        ));
+        assert!(!in_middlewares_attr(
+            &PositionMapper::new(SOURCE),
+            Position::new(4, 36)
+        ));
+    }
+
+    #[test]
+    fn test_complete_middlewares() {
+        let workspace = vec![MiddlewareDefinition {
+            name: "auth".to_string(),
+            kind: MiddlewareKind::Function,
+            summary: Some("校验 JWT 令牌".to_string()),
+            uri: Url::parse("file:///app/src/auth.rs").unwrap(),
+        }];
+        let items = complete_middlewares(&workspace, Locale::default());
+
+        assert_eq!(items.len(), BUNDLED_MIDDLEWARES.len() + 1);
+        assert_eq!(items[0].label, "auth");
+        assert_eq!(
+            items[0].insert_text.as_deref(),
+            Some("middleware::from_fn(auth)")
+        );
+        let timeout = items
+            .iter()
+            .find(|item| item.label == "TimeoutLayer")
+            .unwrap();
+        assert_eq!(timeout.detail.as_deref(), Some("tower_http::timeout"));
+        assert_eq!(timeout.insert_text_format, Some(InsertTextFormat::SNIPPET));
+
+        let idents = vec!["TimeoutLayer".to_string(), "new".to_string()];
+        assert_eq!(
+            find_bundled_middleware(&idents).unwrap().name,
+            "TimeoutLayer"
+        );
+        assert!(find_bundled_middleware(&["RateLimitLayer".to_string()]).is_none());
+    }
+}
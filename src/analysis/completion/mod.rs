@@ -3,7 +3,12 @@
 //! 提供智能代码补全功能
 
 // 当前实现
+pub mod component_names;
 mod engine_impl;
+pub mod headers;
+pub mod middlewares;
+pub mod path_params;
+pub mod snippet;
 
 pub use engine_impl::*;
 
@@ -0,0 +1,165 @@
+//! 路径参数绑定补全模块
+//!
+//! 在路由处理器的参数列表中输入 `Path(` 时，按路由路径中声明的参数顺序补全绑定模式：
+//! - 一个参数（`/users/{user_id}`）：补全 `user_id`
+//! - 多个参数（`/orgs/{org_id}/users/{user_id}`）：补全元组 `(org_id, user_id)`
+//!
+//! 处理器带有多个路由宏且参数不同时，每种参数列表各提供一项。
+//! 输入过程中的代码通常无法解析，因此通过文本向上查找函数定义及其路由宏。
+
+use lsp_types::{CompletionItem, CompletionItemKind, Documentation, Position};
+
+use super::headers::{is_fn_definition, route_attributes};
+use crate::analysis::rust::route_path::parse_route_path;
+use crate::core::document::PositionMapper;
+use crate::core::messages::{localized, Locale};
+
+/// 为处理器参数列表中的 `Path(` 补全路径参数绑定
+///
+/// 不在处理器的参数列表中、光标前不是 `Path(` 或路由没有路径参数时返回空列表
+pub fn complete_path_params(
+    mapper: &PositionMapper,
+    position: Position,
+    locale: Locale,
+) -> Vec<CompletionItem> {
+    let content = mapper.content();
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(line) = lines.get(position.line as usize) else {
+        return Vec::new();
+    };
+    let line_start = mapper
+        .offset(Position::new(position.line, 0))
+        .unwrap_or_default();
+    let cursor = mapper.offset(position).unwrap_or(line_start);
+    let line_prefix = &content[line_start..cursor.min(line_start + line.len())];
+    if !after_path_pattern(line_prefix) {
+        return Vec::new();
+    }
+
+    // 光标位于函数签名中：函数定义到光标之间还没有函数体
+    let mut preceding = lines[..position.line as usize].to_vec();
+    preceding.push(line_prefix);
+    let Some(fn_line) = preceding.iter().rposition(|line| is_fn_definition(line)) else {
+        return Vec::new();
+    };
+    if preceding[fn_line..].iter().any(|line| line.contains('{')) {
+        return Vec::new();
+    }
+
+    let mut items: Vec<CompletionItem> = Vec::new();
+    for attribute in route_attributes(&preceding[..fn_line]) {
+        let Some(path) = first_string_literal(attribute) else {
+            continue;
+        };
+        let (route_path, _) = parse_route_path(path);
+        let names = route_path.param_names();
+        let (pattern, extractor) = match names.as_slice() {
+            [] => continue,
+            [name] => (name.to_string(), "Path<T>".to_string()),
+            names => (
+                format!("({})", names.join(", ")),
+                format!("Path<({})>", vec!["_"; names.len()].join(", ")),
+            ),
+        };
+        if items.iter().any(|item| item.label == pattern) {
+            continue;
+        }
+        items.push(CompletionItem {
+            label: pattern.clone(),
+            kind: Some(CompletionItemKind::VARIABLE),
+            detail: Some(extractor),
+            documentation: Some(Documentation::String(localized(
+                locale,
+                "completion.path-binding",
+                &[&path],
+            ))),
+            insert_text: Some(pattern),
+            sort_text: Some(format!("0_{}", items.len())),
+            ..Default::default()
+        });
+    }
+    items
+}
+
+/// 光标是否紧跟在 `Path(` 之后（可能已输入部分绑定名称）
+fn after_path_pattern(line_prefix: &str) -> bool {
+    let Some(index) = line_prefix.rfind("Path(") else {
+        return false;
+    };
+    // 排除 `UserPath(` 等其他类型
+    let standalone = line_prefix[..index]
+        .chars()
+        .next_back()
+        .is_none_or(|c| !(c.is_alphanumeric() || c == '_'));
+    standalone
+        && line_prefix[index + "Path(".len()..]
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// 属性中的第一个字符串字面量（路由宏的路径）
+fn first_string_literal(attribute: &str) -> Option<&str> {
+    let start = attribute.find('"')? + 1;
+    let end = start + attribute[start..].find('"')?;
+    Some(&attribute[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"#[get("/orgs/{org_id}/users/{user_id}")]
+async fn show(Path(
+) -> impl IntoResponse {}
+
+#[get("/users/{id}")]
+#[delete("/users/{id}")]
+async fn remove(
+    State(db): State<Db>,
+    Path(i
+) {
+    let Path(
+}
+
+#[get("/health")]
+async fn health(Path(
+) {}
+
+fn helper(Path(
+) {}
+"#;
+
+    fn labels(position: Position) -> Vec<String> {
+        complete_path_params(&PositionMapper::new(SOURCE), position, Locale::default())
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+    }
+
+    #[test]
+    fn test_complete_path_params() {
+        let items = complete_path_params(
+            &PositionMapper::new(SOURCE),
+            Position::new(1, 19),
+            Locale::default(),
+        );
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "(org_id, user_id)");
+        assert_eq!(items[0].detail.as_deref(), Some("Path<(_, _)>"));
+
+        // 多个路由宏的参数相同时只提供一项，已输入部分名称时仍然补全
+        assert_eq!(labels(Position::new(8, 10)), vec!["id"]);
+    }
+
+    #[test]
+    fn test_no_path_param_completion() {
+        // 函数体中
+        assert!(labels(Position::new(10, 13)).is_empty());
+        // 路由没有路径参数
+        assert!(labels(Position::new(14, 21)).is_empty());
+        // 不是处理器
+        assert!(labels(Position::new(17, 15)).is_empty());
+        assert!(!after_path_pattern("async fn show(UserPath("));
+        assert!(!after_path_pattern("async fn show(Path(id)"));
+    }
+}
@@ -0,0 +1,211 @@
+//! 代码片段格式协商模块
+//!
+//! 补全引擎生成的补全项使用代码片段语法（`$1`、`${1:default}`、`${1|a,b|}`）。
+//! 客户端在 `initialize` 中没有声明 `completionItem.snippetSupport` 时，
+//! 发送前把代码片段转换为纯文本：占位符替换为默认值（或第一个选项），
+//! 光标位置标记（`$1`、`$0`）删除，并把 `insertTextFormat` 改为 `PlainText`。
+
+use lsp_types::{ClientCapabilities, CompletionItem, CompletionTextEdit, InsertTextFormat};
+
+/// 客户端是否支持代码片段格式的补全
+pub fn supports_snippets(capabilities: &ClientCapabilities) -> bool {
+    capabilities
+        .text_document
+        .as_ref()
+        .and_then(|text_document| text_document.completion.as_ref())
+        .and_then(|completion| completion.completion_item.as_ref())
+        .and_then(|item| item.snippet_support)
+        .unwrap_or(false)
+}
+
+/// 根据客户端能力调整补全项，不支持代码片段时转换为纯文本
+pub fn adapt_completions(items: &mut [CompletionItem], snippet_support: bool) {
+    if snippet_support {
+        return;
+    }
+    for item in items {
+        to_plain_text(item);
+    }
+}
+
+/// 把代码片段格式的补全项转换为纯文本
+pub fn to_plain_text(item: &mut CompletionItem) {
+    if item.insert_text_format != Some(InsertTextFormat::SNIPPET) {
+        return;
+    }
+
+    if let Some(text) = &item.insert_text {
+        item.insert_text = Some(strip_snippet(text));
+    }
+    match &mut item.text_edit {
+        Some(CompletionTextEdit::Edit(edit)) => edit.new_text = strip_snippet(&edit.new_text),
+        Some(CompletionTextEdit::InsertAndReplace(edit)) => {
+            edit.new_text = strip_snippet(&edit.new_text)
+        }
+        None => {}
+    }
+    item.insert_text_format = Some(InsertTextFormat::PLAIN_TEXT);
+}
+
+/// 去掉代码片段语法，返回插入的纯文本
+///
+/// - `$1`、`${1}`、`$0`：删除
+/// - `${1:default}`：替换为 `default`（支持嵌套占位符）
+/// - `${1|a,b|}`：替换为第一个选项 `a`
+/// - `\$`、`\}`、`\\`：替换为转义的字符
+pub fn strip_snippet(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::with_capacity(text.len());
+    let mut pos = 0;
+    strip_until(&chars, &mut pos, false, &mut output);
+    output
+}
+
+/// 转换到文本末尾，或者（在占位符内）到匹配的 `}`
+fn strip_until(chars: &[char], pos: &mut usize, nested: bool, output: &mut String) {
+    while *pos < chars.len() {
+        match chars[*pos] {
+            '\\' if matches!(chars.get(*pos + 1), Some('$' | '}' | '\\')) => {
+                output.push(chars[*pos + 1]);
+                *pos += 2;
+            }
+            '}' if nested => {
+                *pos += 1;
+                return;
+            }
+            '$' => strip_marker(chars, pos, output),
+            c => {
+                output.push(c);
+                *pos += 1;
+            }
+        }
+    }
+}
+
+/// 转换以 `$` 开头的标记，不是合法标记时原样输出 `$`
+fn strip_marker(chars: &[char], pos: &mut usize, output: &mut String) {
+    let start = *pos;
+    *pos += 1;
+
+    // `$1`、`$TM_FILENAME`
+    if chars
+        .get(*pos)
+        .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_')
+    {
+        while chars
+            .get(*pos)
+            .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_')
+        {
+            *pos += 1;
+        }
+        return;
+    }
+
+    if chars.get(*pos) != Some(&'{') {
+        output.push('$');
+        return;
+    }
+
+    // `${1}`、`${1:default}`、`${1|a,b|}`
+    let mut cursor = *pos + 1;
+    while chars
+        .get(cursor)
+        .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_')
+    {
+        cursor += 1;
+    }
+    if cursor == *pos + 1 {
+        output.push('$');
+        return;
+    }
+
+    match chars.get(cursor) {
+        Some('}') => *pos = cursor + 1,
+        Some(':') => {
+            *pos = cursor + 1;
+            strip_until(chars, pos, true, output);
+        }
+        Some('|') => {
+            let choices: String = chars[cursor + 1..]
+                .iter()
+                .take_while(|c| **c != '|')
+                .collect();
+            output.push_str(choices.split(',').next().unwrap_or(""));
+            *pos = (cursor + 1 + choices.chars().count() + 2).min(chars.len());
+        }
+        _ => {
+            *pos = start + 1;
+            output.push('$');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{
+        CompletionClientCapabilities, CompletionItemCapability, Position, Range,
+        TextDocumentClientCapabilities, TextEdit,
+    };
+
+    #[test]
+    fn test_strip_snippet() {
+        assert_eq!(strip_snippet("name = \"$1\""), "name = \"\"");
+        assert_eq!(strip_snippet("{${1:id}}"), "{id}");
+        assert_eq!(strip_snippet("PORT:${1:default}}"), "PORT:default}");
+        assert_eq!(strip_snippet("[web]\n"), "[web]\n");
+        assert_eq!(strip_snippet("${1:outer ${2:inner}}$0"), "outer inner");
+        assert_eq!(
+            strip_snippet("level = \"${1|info,debug|}\""),
+            "level = \"info\""
+        );
+        assert_eq!(strip_snippet("cost: \\$5 ${1}"), "cost: $5 ");
+        assert_eq!(strip_snippet("price $ {x}"), "price $ {x}");
+    }
+
+    #[test]
+    fn test_adapt_completions() {
+        let mut capabilities = ClientCapabilities::default();
+        assert!(!supports_snippets(&capabilities));
+        capabilities.text_document = Some(TextDocumentClientCapabilities {
+            completion: Some(CompletionClientCapabilities {
+                completion_item: Some(CompletionItemCapability {
+                    snippet_support: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert!(supports_snippets(&capabilities));
+
+        let snippet = CompletionItem {
+            label: "inject".to_string(),
+            insert_text: Some("inject(component = \"$1\")".to_string()),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                range: Range::new(Position::new(0, 0), Position::new(0, 1)),
+                new_text: "{${1:id}}".to_string(),
+            })),
+            ..Default::default()
+        };
+
+        let mut items = vec![snippet.clone()];
+        adapt_completions(&mut items, true);
+        assert_eq!(items[0], snippet);
+
+        adapt_completions(&mut items, false);
+        assert_eq!(
+            items[0].insert_text.as_deref(),
+            Some("inject(component = \"\")")
+        );
+        assert_eq!(
+            items[0].insert_text_format,
+            Some(InsertTextFormat::PLAIN_TEXT)
+        );
+        match &items[0].text_edit {
+            Some(CompletionTextEdit::Edit(edit)) => assert_eq!(edit.new_text, "{id}"),
+            other => panic!("unexpected text edit: {:?}", other),
+        }
+    }
+}
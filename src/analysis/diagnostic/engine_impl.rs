@@ -2,7 +2,87 @@
 
 use dashmap::DashMap;
 use lsp_server::Connection;
-use lsp_types::{Diagnostic, PublishDiagnosticsParams, Url};
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, NumberOrString, PublishDiagnosticsParams, Range, Url,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::DiagnosticsConfig;
+use crate::core::messages::Locale;
+use crate::core::sarif::RULES;
+
+/// 诊断附带的快速修复
+///
+/// 保存在 `Diagnostic::data` 中，客户端请求代码操作时原样带回，
+/// 修复内容是用 `new_text` 替换 `range`（没有指定时为诊断的范围）。
+/// 一个诊断可以附带多个快速修复（保存为数组），第一个是首选修复
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickFix {
+    /// 代码操作标题
+    pub title: String,
+    /// 替换的文本
+    pub new_text: String,
+    /// 替换的范围（`None` 表示诊断的范围）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<Range>,
+}
+
+impl QuickFix {
+    /// 把快速修复附加到诊断上
+    pub fn attach(self, diagnostic: &mut Diagnostic) {
+        diagnostic.data = serde_json::to_value(self).ok();
+    }
+
+    /// 把多个快速修复附加到诊断上，第一个是首选修复
+    pub fn attach_all(fixes: Vec<Self>, diagnostic: &mut Diagnostic) {
+        diagnostic.data = serde_json::to_value(fixes).ok();
+    }
+
+    /// 读取诊断附带的（首选）快速修复
+    pub fn from_diagnostic(diagnostic: &Diagnostic) -> Option<Self> {
+        Self::all_from_diagnostic(diagnostic).into_iter().next()
+    }
+
+    /// 读取诊断附带的所有快速修复
+    pub fn all_from_diagnostic(diagnostic: &Diagnostic) -> Vec<Self> {
+        let Some(data) = diagnostic.data.clone() else {
+            return Vec::new();
+        };
+        if data.is_array() {
+            serde_json::from_value(data).unwrap_or_default()
+        } else {
+            serde_json::from_value(data).into_iter().collect()
+        }
+    }
+}
+
+/// 规则目录中的一条规则（`spring/listRules` 响应）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleInfo {
+    /// 规则 ID（诊断代码）
+    pub id: &'static str,
+    /// 规则名称（PascalCase）
+    pub name: &'static str,
+    /// 规则描述
+    pub description: String,
+    /// 默认诊断级别：`error`、`warning`、`information` 或 `hint`
+    pub default_severity: &'static str,
+    /// 诊断是否附带快速修复
+    pub fixable: bool,
+    /// 规则文档地址
+    pub docs_url: String,
+    /// 当前配置下是否启用（没有被 `diagnostics.disabled` 禁用）
+    pub enabled: bool,
+}
+
+/// `spring/listRules` 响应
+#[derive(Debug, Clone, Serialize)]
+pub struct ListRulesResponse {
+    /// 所有规则（按登记顺序）
+    pub rules: Vec<RuleInfo>,
+}
 
 /// 诊断引擎
 pub struct DiagnosticEngine {
@@ -36,6 +116,78 @@ impl DiagnosticEngine {
             .unwrap_or_default()
     }
 
+    /// 按诊断代码统计诊断数（没有代码的诊断不计入）
+    ///
+    /// `include` 用于筛选文档，如只统计某个应用目录下的文档
+    pub fn counts_by_code(
+        &self,
+        include: impl Fn(&Url) -> bool,
+    ) -> std::collections::BTreeMap<String, usize> {
+        let mut counts = std::collections::BTreeMap::new();
+        for entry in self.diagnostics.iter().filter(|entry| include(entry.key())) {
+            for diagnostic in entry.value() {
+                let code = match &diagnostic.code {
+                    Some(NumberOrString::String(code)) => code.clone(),
+                    Some(NumberOrString::Number(code)) => code.to_string(),
+                    None => continue,
+                };
+                *counts.entry(code).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// 诊断代码属于 `codes` 的诊断的位置（按文档 URI 和位置排序）
+    ///
+    /// `include` 用于筛选文档，如只查找某个应用目录下的文档
+    pub fn locations_with_codes(
+        &self,
+        include: impl Fn(&Url) -> bool,
+        codes: &[&str],
+    ) -> Vec<lsp_types::Location> {
+        let mut locations: Vec<lsp_types::Location> = self
+            .diagnostics
+            .iter()
+            .filter(|entry| include(entry.key()))
+            .flat_map(|entry| {
+                entry
+                    .value()
+                    .iter()
+                    .filter(|diagnostic| {
+                        matches!(&diagnostic.code, Some(NumberOrString::String(code)) if codes.contains(&code.as_str()))
+                    })
+                    .map(|diagnostic| lsp_types::Location::new(entry.key().clone(), diagnostic.range))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        locations
+            .sort_by(|a, b| (a.uri.as_str(), a.range.start).cmp(&(b.uri.as_str(), b.range.start)));
+        locations
+    }
+
+    /// 所有诊断规则的目录，由规则注册表（[`RULES`]）生成
+    ///
+    /// `config` 用于标记当前被禁用的规则，规则描述使用 `locale` 指定的语言
+    pub fn rule_catalog(config: &DiagnosticsConfig, locale: Locale) -> Vec<RuleInfo> {
+        RULES
+            .iter()
+            .map(|rule| RuleInfo {
+                id: rule.id,
+                name: rule.name,
+                description: rule.description(locale),
+                default_severity: match rule.severity {
+                    DiagnosticSeverity::WARNING => "warning",
+                    DiagnosticSeverity::INFORMATION => "information",
+                    DiagnosticSeverity::HINT => "hint",
+                    _ => "error",
+                },
+                fixable: rule.fixable,
+                docs_url: rule.docs_url(),
+                enabled: !config.is_disabled(rule.id),
+            })
+            .collect()
+    }
+
     /// 发布诊断到客户端
     ///
     /// 通过 LSP 的 `textDocument/publishDiagnostics` 通知将诊断信息发送给客户端。
@@ -0,0 +1,259 @@
+//! 条目级诊断缓存模块
+//!
+//! 编辑通常只涉及文件中的一个函数，但流水线每次都会重新检查整个文件。
+//! 只依赖所在函数的分析步骤（[`AnalysisPass::item_local`]）可以按顶层函数缓存诊断：
+//! 函数的源码没有变化时复用上一次的诊断（按函数移动的行数平移），只重新检查发生变化的函数。
+//!
+//! 重新检查时把未变化的函数替换为等宽的空白（保留换行，按位置编码计算列数，位置不变），
+//! 分析步骤在替换后的内容上执行，因此不需要修改现有的验证器。
+//!
+//! [`AnalysisPass::item_local`]: crate::analysis::pipeline::AnalysisPass::item_local
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Range as ByteRange;
+
+use dashmap::DashMap;
+use lsp_types::{Diagnostic, Position, Range, Url};
+use syn::spanned::Spanned;
+
+use crate::core::document::{PositionEncoding, PositionMapper};
+
+/// 文档中的顶层函数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FnItem {
+    /// 函数（包括属性和文档注释）的范围
+    pub range: Range,
+    /// 函数在文档中的字节范围
+    pub bytes: ByteRange<usize>,
+    /// 函数源码的哈希
+    pub hash: u64,
+}
+
+impl FnItem {
+    /// 位置是否在函数中
+    pub fn contains(&self, position: Position) -> bool {
+        self.range.start <= position && position <= self.range.end
+    }
+}
+
+/// 文档中的顶层函数（按源码顺序），文档无法解析时返回 `None`
+pub fn top_level_fns(lines: &PositionMapper) -> Option<Vec<FnItem>> {
+    let file = syn::parse_file(lines.content()).ok()?;
+    let items = file
+        .items
+        .iter()
+        .filter_map(|item| {
+            let syn::Item::Fn(item_fn) = item else {
+                return None;
+            };
+            let range = lines.span_range(item_fn.span());
+            let bytes = lines.offset(range.start)?..lines.offset(range.end)?;
+            let mut hasher = DefaultHasher::new();
+            lines.content()[bytes.clone()].hash(&mut hasher);
+            Some(FnItem {
+                range,
+                bytes,
+                hash: hasher.finish(),
+            })
+        })
+        .collect();
+    Some(items)
+}
+
+/// 把指定的字节范围替换为空格（保留换行，其余内容的位置不变）
+///
+/// 每个字符替换为与它在位置编码中占用的列数相同个数的空格，
+/// 范围之后同一行的内容（如函数结束的 `}` 之后的注释）列号不变
+pub fn mask(content: &str, ranges: &[ByteRange<usize>], encoding: PositionEncoding) -> String {
+    let mut masked = String::with_capacity(content.len());
+    let mut last = 0;
+    for range in ranges {
+        masked.push_str(&content[last..range.start]);
+        for ch in content[range.clone()].chars() {
+            match ch {
+                '\n' | '\r' => masked.push(ch),
+                _ => masked.extend(std::iter::repeat_n(' ', encoding.width(ch))),
+            }
+        }
+        last = range.end;
+    }
+    masked.push_str(&content[last..]);
+    masked
+}
+
+/// 单个函数缓存的诊断
+#[derive(Debug, Clone)]
+struct CachedFn {
+    hash: u64,
+    start: Position,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// 与上一次分析的比较结果
+#[derive(Debug, Default)]
+pub struct ItemReuse {
+    /// 可以复用诊断的函数（在本次的函数列表中的下标）及平移到新位置的诊断
+    pub reused: Vec<(usize, Vec<Diagnostic>)>,
+    /// 需要重新检查的函数（在本次的函数列表中的下标）
+    pub changed: Vec<usize>,
+}
+
+/// 按文档和顶层函数缓存的诊断
+#[derive(Debug, Default)]
+pub struct ItemDiagnosticsCache {
+    documents: DashMap<Url, Vec<CachedFn>>,
+}
+
+impl ItemDiagnosticsCache {
+    /// 创建空的缓存
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 比较文档当前的函数与上一次分析的结果
+    ///
+    /// 源码相同且起始列不变的函数复用诊断；相同的函数出现多次时按顺序对应
+    pub fn reuse(&self, uri: &Url, items: &[FnItem]) -> ItemReuse {
+        let mut previous = self
+            .documents
+            .get(uri)
+            .map(|cached| cached.clone())
+            .unwrap_or_default();
+        let mut reuse = ItemReuse::default();
+        for (index, item) in items.iter().enumerate() {
+            let matched = previous.iter().position(|cached| {
+                cached.hash == item.hash && cached.start.character == item.range.start.character
+            });
+            match matched {
+                Some(matched) => {
+                    let cached = previous.remove(matched);
+                    let delta = item.range.start.line as i64 - cached.start.line as i64;
+                    let diagnostics = cached
+                        .diagnostics
+                        .into_iter()
+                        .map(|diagnostic| shift(diagnostic, uri, delta))
+                        .collect();
+                    reuse.reused.push((index, diagnostics));
+                }
+                None => reuse.changed.push(index),
+            }
+        }
+        reuse
+    }
+
+    /// 记录文档每个函数的诊断（`diagnostics` 与 `items` 一一对应）
+    pub fn store(&self, uri: &Url, items: &[FnItem], diagnostics: Vec<Vec<Diagnostic>>) {
+        let cached = items
+            .iter()
+            .zip(diagnostics)
+            .map(|(item, diagnostics)| CachedFn {
+                hash: item.hash,
+                start: item.range.start,
+                diagnostics,
+            })
+            .collect();
+        self.documents.insert(uri.clone(), cached);
+    }
+
+    /// 移除文档的缓存
+    pub fn remove(&self, uri: &Url) {
+        self.documents.remove(uri);
+    }
+
+    /// 移除所有文档的缓存（影响分析结果的服务器配置变化后使用）
+    pub fn clear(&self) {
+        self.documents.clear();
+    }
+}
+
+/// 把诊断（及同一文档中的关联信息）平移 `delta` 行
+fn shift(mut diagnostic: Diagnostic, uri: &Url, delta: i64) -> Diagnostic {
+    let shift_range = |range: &mut Range| {
+        range.start.line = (range.start.line as i64 + delta).max(0) as u32;
+        range.end.line = (range.end.line as i64 + delta).max(0) as u32;
+    };
+    shift_range(&mut diagnostic.range);
+    for related in diagnostic.related_information.iter_mut().flatten() {
+        if &related.location.uri == uri {
+            shift_range(&mut related.location.range);
+        }
+    }
+    diagnostic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(line: u32) -> Diagnostic {
+        Diagnostic {
+            range: Range::new(Position::new(line, 0), Position::new(line, 4)),
+            message: format!("line {}", line),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_top_level_fns() {
+        let content =
+            "use a::b;\n\n/// 文档\n#[get(\"/\")]\nasync fn index() {}\n\nfn helper() {\n}\n";
+        let lines = PositionMapper::new(content);
+        let items = top_level_fns(&lines).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].range.start, Position::new(2, 0));
+        assert_eq!(&content[items[1].bytes.clone()], "fn helper() {\n}");
+        assert!(items[0].contains(Position::new(4, 3)));
+        assert!(top_level_fns(&PositionMapper::new("fn broken(")).is_none());
+
+        let masked = mask(content, &[items[0].bytes.clone()], PositionEncoding::Utf16);
+        assert_eq!(masked.lines().count(), content.lines().count());
+        assert!(masked.starts_with("use a::b;\n\n "));
+        assert!(!masked.contains("index"));
+    }
+
+    #[test]
+    fn test_mask_keeps_columns_in_encoding() {
+        let content = "fn a() { \"文😀\" } // 注释\n";
+        let items = top_level_fns(&PositionMapper::new(content)).unwrap();
+        let comment = content.find("//").unwrap();
+        for encoding in [
+            PositionEncoding::Utf8,
+            PositionEncoding::Utf16,
+            PositionEncoding::Utf32,
+        ] {
+            let lines = PositionMapper::with_encoding(content, encoding);
+            let masked = mask(content, &[items[0].bytes.clone()], encoding);
+            let masked_lines = PositionMapper::with_encoding(&masked, encoding);
+            let position = lines.position(comment);
+            assert_eq!(masked_lines.offset(position), masked.find("//"));
+            assert!(!masked.contains('文'));
+        }
+    }
+
+    #[test]
+    fn test_reuse_unchanged_fns() {
+        let uri = Url::parse("file:///app/src/main.rs").unwrap();
+        let cache = ItemDiagnosticsCache::new();
+        let before = "fn a() {}\n\nfn b() {\n    todo!()\n}\n";
+        let items = top_level_fns(&PositionMapper::new(before)).unwrap();
+        let reuse = cache.reuse(&uri, &items);
+        assert!(reuse.reused.is_empty());
+        assert_eq!(reuse.changed, vec![0, 1]);
+        cache.store(&uri, &items, vec![vec![], vec![diagnostic(3)]]);
+
+        // 修改 a 并在前面插入两行：b 的诊断平移到新位置
+        let after = "\n\nfn a() { let x = 1; }\n\nfn b() {\n    todo!()\n}\n";
+        let items = top_level_fns(&PositionMapper::new(after)).unwrap();
+        let reuse = cache.reuse(&uri, &items);
+        assert_eq!(reuse.changed, vec![0]);
+        assert_eq!(reuse.reused.len(), 1);
+        let (index, diagnostics) = &reuse.reused[0];
+        assert_eq!(*index, 1);
+        assert_eq!(diagnostics[0].range.start.line, 5);
+        assert_eq!(diagnostics[0].message, "line 3");
+
+        cache.remove(&uri);
+        assert_eq!(cache.reuse(&uri, &items).changed, vec![0, 1]);
+    }
+}
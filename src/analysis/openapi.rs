@@ -0,0 +1,300 @@
+//! OpenAPI 规范读取模块
+//!
+//! 读取项目中已有的 OpenAPI 规范文件（`openapi.yaml` 或 `openapi.yml`，位于项目根目录或 `docs` 目录），
+//! 提取 `paths` 中声明的操作，用于和项目中的路由互相对照和跳转。
+//!
+//! 只读取按缩进书写的块状 YAML：顶层的 `paths` 映射、其中的路径和 HTTP 方法，
+//! 以及操作的 `operationId`。流式写法（`{ ... }`）和 `$ref` 引用的路径不会被识别。
+//!
+//! 路由路径和规范路径比较时忽略参数名称和约束，`/users/{id}` 与 `/users/{user_id}` 视为同一路径。
+
+use std::path::{Path, PathBuf};
+
+use lsp_types::{Position, Range};
+
+use crate::scanner::route::PathPattern;
+
+/// 规范文件名
+pub const SPEC_FILE_NAMES: &[&str] = &["openapi.yaml", "openapi.yml"];
+
+/// 查找规范文件的目录（相对于项目根目录）
+const SPEC_DIRS: &[&str] = &["", "docs"];
+
+/// OpenAPI 中的 HTTP 方法
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// 规范中声明的操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecOperation {
+    /// HTTP 方法（大写）
+    pub method: String,
+    /// 路径
+    pub path: String,
+    /// 操作 ID
+    pub operation_id: Option<String>,
+    /// 方法键在文件中的位置范围
+    pub range: Range,
+}
+
+impl SpecOperation {
+    /// 判断路由是否与操作匹配
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        self.method.eq_ignore_ascii_case(method)
+            && PathPattern::parse(&self.path).same_shape(&PathPattern::parse(path))
+    }
+}
+
+/// OpenAPI 规范
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpenApiSpec {
+    /// 声明的操作（按在文件中出现的顺序）
+    pub operations: Vec<SpecOperation>,
+}
+
+impl OpenApiSpec {
+    /// 解析规范文件内容
+    pub fn parse(content: &str) -> Self {
+        let mut operations: Vec<SpecOperation> = Vec::new();
+        // `paths` 下路径键、方法键和操作字段的缩进
+        let mut in_paths = false;
+        let mut path_indent = None;
+        let mut method_indent = None;
+        let mut field_indent = None;
+        let mut current_path: Option<String> = None;
+        let mut current_operation: Option<usize> = None;
+
+        for (line_number, line) in content.lines().enumerate() {
+            let text = line.trim_start_matches(' ');
+            if text.is_empty() || text.starts_with('#') || text.starts_with("---") {
+                continue;
+            }
+            let indent = line.len() - text.len();
+            let Some((key, value)) = mapping_entry(text) else {
+                continue;
+            };
+
+            if indent == 0 {
+                in_paths = key == "paths";
+                path_indent = None;
+                current_path = None;
+                current_operation = None;
+                continue;
+            }
+            if !in_paths {
+                continue;
+            }
+
+            if path_indent.is_none_or(|path_indent| indent <= path_indent) {
+                path_indent = Some(indent);
+                current_path = Some(key);
+                method_indent = None;
+                current_operation = None;
+                continue;
+            }
+            let Some(path) = &current_path else {
+                continue;
+            };
+
+            if method_indent.is_none_or(|method_indent| indent <= method_indent) {
+                method_indent = Some(indent);
+                field_indent = None;
+                current_operation = None;
+                if METHODS.contains(&key.as_str()) {
+                    let start = utf16_len(&line[..indent]);
+                    current_operation = Some(operations.len());
+                    operations.push(SpecOperation {
+                        method: key.to_ascii_uppercase(),
+                        path: path.clone(),
+                        operation_id: None,
+                        range: Range::new(
+                            Position::new(line_number as u32, start),
+                            Position::new(line_number as u32, start + utf16_len(&key)),
+                        ),
+                    });
+                }
+                continue;
+            }
+
+            // 只读取操作的直接字段，忽略 `links` 等嵌套对象中的 `operationId`
+            let Some(index) = current_operation else {
+                continue;
+            };
+            if *field_indent.get_or_insert(indent) == indent && key == "operationId" {
+                operations[index].operation_id = Some(unquote(value)).filter(|id| !id.is_empty());
+            }
+        }
+
+        Self { operations }
+    }
+
+    /// 查找项目中的规范文件
+    pub fn find(project_root: &Path) -> Option<PathBuf> {
+        SPEC_DIRS
+            .iter()
+            .flat_map(|dir| {
+                SPEC_FILE_NAMES
+                    .iter()
+                    .map(move |name| project_root.join(dir).join(name))
+            })
+            .find(|path| path.is_file())
+    }
+
+    /// 判断文件是否为规范文件
+    pub fn is_spec_file(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| SPEC_FILE_NAMES.contains(&name))
+    }
+
+    /// 与路由匹配的操作
+    pub fn operation(&self, method: &str, path: &str) -> Option<&SpecOperation> {
+        self.operations
+            .iter()
+            .find(|operation| operation.matches(method, path))
+    }
+
+    /// 位置所在行声明的操作
+    pub fn operation_at(&self, position: Position) -> Option<&SpecOperation> {
+        self.operations
+            .iter()
+            .find(|operation| operation.range.start.line == position.line)
+    }
+}
+
+/// 解析块状映射中的一项，返回键和值的原文
+fn mapping_entry(text: &str) -> Option<(String, &str)> {
+    if let Some(quote) = text.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let end = text[1..].find(quote)? + 1;
+        let rest = text[end + 1..].trim_start().strip_prefix(':')?;
+        return Some((text[1..end].to_string(), rest.trim()));
+    }
+
+    // 键中的冒号（如 `/users/{id}:activate`）后面没有空格
+    let colon = text
+        .match_indices(':')
+        .map(|(i, _)| i)
+        .find(|&i| text[i + 1..].is_empty() || text[i + 1..].starts_with([' ', '\t']))?;
+    let key = text[..colon].trim_end();
+    if key.is_empty() || key.starts_with("- ") {
+        return None;
+    }
+    Some((key.to_string(), text[colon + 1..].trim()))
+}
+
+/// 去掉值两端的引号和行尾注释
+fn unquote(value: &str) -> String {
+    if let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        if let Some(end) = value[1..].find(quote) {
+            return value[1..end + 1].to_string();
+        }
+    }
+    value
+        .split(" #")
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+fn utf16_len(text: &str) -> u32 {
+    text.encode_utf16().count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"openapi: 3.0.3
+info:
+  title: Users
+  version: "1.0"
+paths:
+  /users:
+    get:
+      operationId: listUsers
+      responses:
+        "200":
+          links:
+            GetUser:
+              operationId: getUser
+    post:
+      summary: Create user # 创建用户
+  "/users/{userId}":
+    parameters:
+      - name: userId
+        in: path
+    get:
+      operationId: "getUser"
+  /files/{name}.json:
+    delete: {}
+components:
+  schemas:
+    get:
+      type: object
+"#;
+
+    #[test]
+    fn test_parse_operations() {
+        let spec = OpenApiSpec::parse(SPEC);
+        let operations: Vec<_> = spec
+            .operations
+            .iter()
+            .map(|op| {
+                (
+                    op.method.as_str(),
+                    op.path.as_str(),
+                    op.operation_id.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            operations,
+            vec![
+                ("GET", "/users", Some("listUsers")),
+                ("POST", "/users", None),
+                ("GET", "/users/{userId}", Some("getUser")),
+                ("DELETE", "/files/{name}.json", None),
+            ]
+        );
+        assert_eq!(
+            spec.operations[0].range,
+            Range::new(Position::new(6, 4), Position::new(6, 7))
+        );
+        assert_eq!(
+            spec.operation_at(Position::new(19, 4))
+                .unwrap()
+                .operation_id
+                .as_deref(),
+            Some("getUser")
+        );
+    }
+
+    #[test]
+    fn test_match_route_paths() {
+        let spec = OpenApiSpec::parse(SPEC);
+        assert!(spec.operation("GET", "/users/").is_some());
+        assert!(spec.operation("get", "/users/{id}").is_some());
+        assert!(spec.operation("GET", "/users/{id:\\d+}").is_some());
+        assert!(spec.operation("DELETE", "/files/{file}.json").is_some());
+        assert!(spec.operation("DELETE", "/users/{id}").is_none());
+        assert!(spec.operation("GET", "/users/{id}/posts").is_none());
+    }
+
+    #[test]
+    fn test_find_spec_file() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(OpenApiSpec::find(dir.path()).is_none());
+        std::fs::create_dir_all(dir.path().join("docs")).unwrap();
+        std::fs::write(dir.path().join("docs/openapi.yml"), SPEC).unwrap();
+        assert_eq!(
+            OpenApiSpec::find(dir.path()),
+            Some(dir.path().join("docs/openapi.yml"))
+        );
+        assert!(OpenApiSpec::is_spec_file(Path::new("/app/openapi.yaml")));
+        assert!(!OpenApiSpec::is_spec_file(Path::new(
+            "/app/config/app.toml"
+        )));
+    }
+}
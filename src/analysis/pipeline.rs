@@ -0,0 +1,554 @@
+//! 文档分析流水线模块
+//!
+//! 单个文档的分析按阶段顺序执行：
+//! 1. 解析（[`PassStage::Parse`]）：把文档内容解析为 TOML 文档或路由处理器文件
+//! 2. 提取（[`PassStage::Extract`]）：从解析结果中提取后续检查需要的信息
+//! 3. 验证（[`PassStage::Validate`]）：只依赖当前文档的检查
+//! 4. 交叉引用（[`PassStage::CrossRef`]）：需要结合项目其他文件的检查（任务名称、中间件规则等）
+//!
+//! 每个分析步骤实现 [`AnalysisPass`]，通过共享的 [`AnalysisContext`] 读取解析结果并写入诊断。
+//! 新的验证器只需加入流水线，不需要修改服务器和项目检查器的分析代码。
+//!
+//! 流水线会记录每个步骤的耗时，LSP 服务器把它们汇总到 `spring/perfReport` 的性能报告中。
+//!
+//! 编辑打开的文档时，LSP 服务器使用 [`DocumentPipeline::run_cached`]：
+//! 只依赖所在函数的步骤（[`AnalysisPass::item_local`]）只重新检查发生变化的顶层函数，
+//! 其余函数复用上一次的诊断（见 [`crate::analysis::item_cache`]）。
+
+use std::time::{Duration, Instant};
+
+use lsp_types::{Diagnostic, NumberOrString, Url};
+
+use crate::analysis::item_cache::{mask, top_level_fns, ItemDiagnosticsCache};
+use crate::analysis::rust::handler_analyzer::{HandlerAnalyzer, HandlerFile};
+use crate::analysis::rust::macro_analyzer::{MacroAnalyzer, SpringMacro};
+use crate::analysis::toml::toml_analyzer::{
+    ranges_overlap, TomlAnalyzer, TomlDocument, TomlSyntaxError,
+};
+use crate::analysis::toml::SecretValidator;
+use crate::analysis::validation::{
+    HandlerComplexityValidator, HandlerSignatureValidator, HttpSemanticsValidator,
+};
+use crate::core::config::ServerConfig;
+use crate::core::document::PositionMapper;
+
+/// 分析阶段（按执行顺序排列）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PassStage {
+    /// 解析
+    Parse,
+    /// 提取
+    Extract,
+    /// 验证
+    Validate,
+    /// 交叉引用
+    CrossRef,
+}
+
+/// 单个分析步骤的耗时
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassTiming {
+    /// 步骤名称
+    pub name: &'static str,
+    /// 所属阶段
+    pub stage: PassStage,
+    /// 耗时
+    pub elapsed: Duration,
+}
+
+/// 分析上下文
+///
+/// 在同一文档的所有分析步骤之间共享
+pub struct AnalysisContext<'a> {
+    /// 文档 URI
+    pub uri: &'a Url,
+    /// 文档内容
+    pub content: &'a str,
+    /// 文档的位置转换器
+    pub lines: PositionMapper<'a>,
+    /// 服务器配置
+    pub config: &'a ServerConfig,
+    /// 解析后的 TOML 文档（解析失败或不是 TOML 文档时为 `None`）
+    pub toml: Option<TomlDocument>,
+    /// 解析后的路由处理器文件（解析失败或不是 Rust 文档时为 `None`）
+    pub handlers: Option<HandlerFile>,
+    /// 已生成的诊断
+    pub diagnostics: Vec<Diagnostic>,
+    /// 已执行步骤的耗时
+    pub timings: Vec<PassTiming>,
+}
+
+impl<'a> AnalysisContext<'a> {
+    /// 创建分析上下文（位置按 LSP 默认的 UTF-16 代码单元计算）
+    pub fn new(uri: &'a Url, content: &'a str, config: &'a ServerConfig) -> Self {
+        Self::with_lines(uri, PositionMapper::new(content), config)
+    }
+
+    /// 使用已打开文档缓存的行索引创建分析上下文
+    pub fn with_lines(uri: &'a Url, lines: PositionMapper<'a>, config: &'a ServerConfig) -> Self {
+        Self {
+            uri,
+            content: lines.content(),
+            lines,
+            config,
+            toml: None,
+            handlers: None,
+            diagnostics: Vec::new(),
+            timings: Vec::new(),
+        }
+    }
+}
+
+/// 分析步骤
+pub trait AnalysisPass {
+    /// 步骤名称（用于耗时统计）
+    fn name(&self) -> &'static str;
+
+    /// 所属阶段
+    fn stage(&self) -> PassStage;
+
+    /// 执行分析
+    fn run(&self, ctx: &mut AnalysisContext);
+
+    /// 诊断是否只依赖所在的顶层函数（可以按函数缓存，见 [`DocumentPipeline::run_cached`]）
+    fn item_local(&self) -> bool {
+        false
+    }
+}
+
+/// 由闭包实现的分析步骤
+pub struct FnPass<F> {
+    name: &'static str,
+    stage: PassStage,
+    run: F,
+    item_local: bool,
+}
+
+impl<F> FnPass<F>
+where
+    F: Fn(&mut AnalysisContext),
+{
+    /// 创建分析步骤
+    pub fn new(name: &'static str, stage: PassStage, run: F) -> Self {
+        Self {
+            name,
+            stage,
+            run,
+            item_local: false,
+        }
+    }
+
+    /// 标记诊断只依赖所在的顶层函数
+    pub fn per_item(mut self) -> Self {
+        self.item_local = true;
+        self
+    }
+}
+
+impl<F> AnalysisPass for FnPass<F>
+where
+    F: Fn(&mut AnalysisContext),
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn stage(&self) -> PassStage {
+        self.stage
+    }
+
+    fn run(&self, ctx: &mut AnalysisContext) {
+        (self.run)(ctx)
+    }
+
+    fn item_local(&self) -> bool {
+        self.item_local
+    }
+}
+
+/// 分析流水线
+pub trait AnalysisPipeline {
+    /// 按执行顺序排列的分析步骤
+    fn passes(&self) -> Vec<&dyn AnalysisPass>;
+
+    /// 依次执行所有分析步骤，并记录每个步骤的耗时
+    fn run(&self, ctx: &mut AnalysisContext) {
+        for pass in self.passes() {
+            let timing = run_pass(pass, ctx);
+            ctx.timings.push(timing);
+        }
+    }
+}
+
+/// 执行单个分析步骤并返回耗时
+fn run_pass(pass: &dyn AnalysisPass, ctx: &mut AnalysisContext) -> PassTiming {
+    #[cfg(feature = "profiling")]
+    let _span = tracing::trace_span!("pass", name = pass.name()).entered();
+    let start = Instant::now();
+    pass.run(ctx);
+    let elapsed = start.elapsed();
+    tracing::trace!("Analysis pass {} took {:?}", pass.name(), elapsed);
+    PassTiming {
+        name: pass.name(),
+        stage: pass.stage(),
+        elapsed,
+    }
+}
+
+/// [`DocumentPipeline::run_cached`] 复用和重新检查的函数数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemReuseCount {
+    /// 复用诊断的函数数
+    pub reused: usize,
+    /// 重新检查的函数数
+    pub analyzed: usize,
+}
+
+/// 单个文档的分析流水线
+///
+/// 步骤按阶段排序，同一阶段内按加入的顺序执行
+#[derive(Default)]
+pub struct DocumentPipeline<'a> {
+    passes: Vec<Box<dyn AnalysisPass + 'a>>,
+}
+
+impl<'a> DocumentPipeline<'a> {
+    /// 创建空的流水线
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 加入分析步骤
+    pub fn with_pass(mut self, pass: impl AnalysisPass + 'a) -> Self {
+        let index = self
+            .passes
+            .iter()
+            .position(|existing| existing.stage() > pass.stage())
+            .unwrap_or(self.passes.len());
+        self.passes.insert(index, Box::new(pass));
+        self
+    }
+
+    /// 配置文件的分析流水线：解析、配置验证和明文密钥检查（启用 `[secrets]` 时）
+    ///
+    /// 有语法错误时在错误位置报告诊断，并继续验证可以解析的部分（见 [`TomlAnalyzer::parse_recovering`]）；
+    /// 与语法错误重叠的验证诊断不再报告
+    pub fn toml(analyzer: &'a TomlAnalyzer) -> Self {
+        Self::new()
+            .with_pass(FnPass::new("toml-parse", PassStage::Parse, |ctx| {
+                let (doc, errors) = analyzer.parse_recovering(ctx.content);
+                if !errors.is_empty() {
+                    tracing::debug!("TOML syntax errors in {}: {}", ctx.uri, errors.len());
+                }
+                ctx.diagnostics.extend(
+                    errors
+                        .iter()
+                        .map(|error| error.to_diagnostic(analyzer.locale())),
+                );
+                ctx.toml = Some(doc);
+            }))
+            .with_pass(FnPass::new("toml-validate", PassStage::Validate, |ctx| {
+                if let Some(doc) = &ctx.toml {
+                    let syntax_errors: Vec<_> = ctx
+                        .diagnostics
+                        .iter()
+                        .filter(|diagnostic| {
+                            diagnostic.code
+                                == Some(NumberOrString::String(TomlSyntaxError::CODE.to_string()))
+                        })
+                        .map(|diagnostic| diagnostic.range)
+                        .collect();
+                    let diagnostics = analyzer.validate(doc).into_iter().filter(|diagnostic| {
+                        !syntax_errors
+                            .iter()
+                            .any(|range| ranges_overlap(range, &diagnostic.range))
+                    });
+                    ctx.diagnostics.extend(diagnostics);
+                }
+            }))
+            .with_pass(FnPass::new("secrets", PassStage::Validate, |ctx| {
+                if !ctx.config.secrets.enabled {
+                    return;
+                }
+                if let Some(doc) = &ctx.toml {
+                    let diagnostics = SecretValidator::new(&ctx.config.secrets)
+                        .with_locale(analyzer.locale())
+                        .validate(doc);
+                    ctx.diagnostics.extend(diagnostics);
+                }
+            }))
+    }
+
+    /// Rust 文件的分析流水线：解析路由处理器、提取器验证、路径风格、处理器签名、`#[inject]` 位置和参数、
+    /// HTTP 方法语义检查、未实现处理器检查（启用 `[routes] stub_handlers` 时）
+    /// 和处理器复杂度提示（启用 `[complexity]` 时）
+    ///
+    /// 语法错误由 rust-analyzer 或编译器报告，解析失败时不生成诊断
+    pub fn rust(analyzer: &'a HandlerAnalyzer) -> Self {
+        Self::new()
+            .with_pass(FnPass::new("handler-parse", PassStage::Parse, |ctx| {
+                ctx.handlers = analyzer.analyze_lines(&ctx.lines).ok();
+            }))
+            .with_pass(FnPass::new(
+                "handler-validate",
+                PassStage::Validate,
+                |ctx| {
+                    if let Some(file) = &ctx.handlers {
+                        let mut diagnostics = analyzer.validate(file);
+                        diagnostics.extend(analyzer.validate_route_style(file, &ctx.config.routes));
+                        ctx.diagnostics.extend(diagnostics);
+                    }
+                },
+            ))
+            .with_pass(
+                FnPass::new("handler-signature", PassStage::Validate, |ctx| {
+                    let diagnostics = HandlerSignatureValidator::new()
+                        .with_locale(analyzer.locale())
+                        .validate(&ctx.lines);
+                    ctx.diagnostics.extend(diagnostics);
+                })
+                .per_item(),
+            )
+            .with_pass(FnPass::new(
+                "inject-attributes",
+                PassStage::Validate,
+                |ctx| {
+                    let analyzer = MacroAnalyzer::new()
+                        .with_encoding(ctx.lines.encoding())
+                        .with_locale(analyzer.locale());
+                    let mut diagnostics = analyzer.validate_inject_attributes(&ctx.lines);
+                    // Service 字段上 #[inject] 的参数（如指定了名称的配置注入）
+                    let macros = analyzer
+                        .parse(ctx.uri.clone(), ctx.content.to_string())
+                        .and_then(|doc| analyzer.extract_macros(doc))
+                        .map(|doc| doc.macros)
+                        .unwrap_or_default();
+                    diagnostics.extend(
+                        macros
+                            .iter()
+                            .filter(|m| matches!(m, SpringMacro::DeriveService(_)))
+                            .flat_map(|m| analyzer.validate_macro(m)),
+                    );
+                    ctx.diagnostics.extend(diagnostics);
+                },
+            ))
+            .with_pass(
+                FnPass::new("http-semantics", PassStage::Validate, |ctx| {
+                    if let Some(file) = &ctx.handlers {
+                        let diagnostics = HttpSemanticsValidator::new()
+                            .with_locale(analyzer.locale())
+                            .validate(file);
+                        ctx.diagnostics.extend(diagnostics);
+                    }
+                })
+                .per_item(),
+            )
+            .with_pass(
+                FnPass::new("stub-handlers", PassStage::Validate, |ctx| {
+                    if !ctx.config.routes.stub_handlers {
+                        return;
+                    }
+                    if let Some(file) = &ctx.handlers {
+                        let diagnostics = analyzer.validate_stubs(file);
+                        ctx.diagnostics.extend(diagnostics);
+                    }
+                })
+                .per_item(),
+            )
+            .with_pass(HandlerComplexityValidator::new().with_locale(analyzer.locale()))
+    }
+}
+
+impl DocumentPipeline<'_> {
+    /// 执行所有分析步骤，只依赖所在函数的步骤复用未变化函数的诊断
+    ///
+    /// 这些步骤在把未变化的函数替换为空白的内容上执行，路由处理器也只保留发生变化的函数中的处理器；
+    /// 不在任何顶层函数中的诊断（如结构体上的诊断）每次都重新生成。文档无法解析时执行完整分析
+    pub fn run_cached(
+        &self,
+        ctx: &mut AnalysisContext,
+        cache: &ItemDiagnosticsCache,
+    ) -> ItemReuseCount {
+        let Some(items) = top_level_fns(&ctx.lines) else {
+            cache.remove(ctx.uri);
+            self.run(ctx);
+            return ItemReuseCount::default();
+        };
+        let reuse = cache.reuse(ctx.uri, &items);
+        let unchanged: Vec<_> = reuse
+            .reused
+            .iter()
+            .map(|(index, _)| items[*index].bytes.clone())
+            .collect();
+        let masked = mask(ctx.content, &unchanged, ctx.lines.encoding());
+        let mut item_ctx = AnalysisContext::with_lines(
+            ctx.uri,
+            PositionMapper::with_encoding(&masked, ctx.lines.encoding()),
+            ctx.config,
+        );
+
+        for pass in self.passes() {
+            if !pass.item_local() {
+                let timing = run_pass(pass, ctx);
+                ctx.timings.push(timing);
+                continue;
+            }
+            // 只依赖所在函数的步骤在验证阶段执行，此时已经解析了路由处理器
+            if item_ctx.handlers.is_none() {
+                item_ctx.handlers = ctx.handlers.as_ref().map(|file| HandlerFile {
+                    handlers: file
+                        .handlers
+                        .iter()
+                        .filter(|handler| {
+                            reuse
+                                .changed
+                                .iter()
+                                .any(|index| items[*index].contains(handler.range.start))
+                        })
+                        .cloned()
+                        .collect(),
+                    structs: file.structs.clone(),
+                });
+            }
+            let timing = run_pass(pass, &mut item_ctx);
+            ctx.timings.push(timing);
+        }
+
+        // 按函数归类重新生成的诊断
+        let mut per_item: Vec<Vec<Diagnostic>> = vec![Vec::new(); items.len()];
+        for diagnostic in item_ctx.diagnostics {
+            match items
+                .iter()
+                .position(|item| item.contains(diagnostic.range.start))
+            {
+                Some(index) => per_item[index].push(diagnostic),
+                None => ctx.diagnostics.push(diagnostic),
+            }
+        }
+        let count = ItemReuseCount {
+            reused: reuse.reused.len(),
+            analyzed: reuse.changed.len(),
+        };
+        for (index, diagnostics) in reuse.reused {
+            per_item[index] = diagnostics;
+        }
+        ctx.diagnostics.extend(per_item.iter().flatten().cloned());
+        cache.store(ctx.uri, &items, per_item);
+        count
+    }
+}
+
+impl AnalysisPipeline for DocumentPipeline<'_> {
+    fn passes(&self) -> Vec<&dyn AnalysisPass> {
+        self.passes.iter().map(|pass| pass.as_ref() as _).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::SchemaProvider;
+
+    #[test]
+    fn test_passes_run_in_stage_order() {
+        let uri = Url::parse("file:///app/config/app.toml").unwrap();
+        let config = ServerConfig::default();
+        let pipeline = DocumentPipeline::new()
+            .with_pass(FnPass::new("cross-ref", PassStage::CrossRef, |_| {}))
+            .with_pass(FnPass::new("validate", PassStage::Validate, |_| {}))
+            .with_pass(FnPass::new("parse", PassStage::Parse, |_| {}))
+            .with_pass(FnPass::new("validate-2", PassStage::Validate, |_| {}));
+
+        let mut ctx = AnalysisContext::new(&uri, "", &config);
+        pipeline.run(&mut ctx);
+        let names: Vec<_> = ctx.timings.iter().map(|timing| timing.name).collect();
+        assert_eq!(names, vec!["parse", "validate", "validate-2", "cross-ref"]);
+    }
+
+    #[test]
+    fn test_toml_pipeline() {
+        let uri = Url::parse("file:///app/config/app.toml").unwrap();
+        let mut config = ServerConfig::default();
+        config.secrets.enabled = true;
+        let analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        let pipeline = DocumentPipeline::toml(&analyzer);
+
+        let mut ctx = AnalysisContext::new(&uri, "[mail]\npassword = \"hunter2\"\n", &config);
+        pipeline.run(&mut ctx);
+        assert!(ctx.toml.is_some());
+        assert!(ctx
+            .diagnostics
+            .iter()
+            .any(|d| d.code == Some(NumberOrString::String("hardcoded-secret".to_string()))));
+        assert_eq!(ctx.timings.len(), 3);
+
+        // 语法错误在错误位置报告，其余配置仍然验证
+        let mut ctx = AnalysisContext::new(
+            &uri,
+            "[web]\nport = \n\n[mail]\npassword = \"hunter2\"\n",
+            &config,
+        );
+        pipeline.run(&mut ctx);
+        assert!(ctx.toml.is_some());
+        assert_eq!(
+            ctx.diagnostics[0].code,
+            Some(NumberOrString::String("parse_error".to_string()))
+        );
+        assert_eq!(ctx.diagnostics[0].range.start.line, 1);
+        assert!(ctx
+            .diagnostics
+            .iter()
+            .any(|d| d.code == Some(NumberOrString::String("hardcoded-secret".to_string()))));
+    }
+
+    #[test]
+    fn test_run_cached_reuses_unchanged_fns() {
+        let uri = Url::parse("file:///app/src/routes.rs").unwrap();
+        let mut config = ServerConfig::default();
+        config.routes.stub_handlers = true;
+        let analyzer = HandlerAnalyzer::new();
+        let pipeline = DocumentPipeline::rust(&analyzer);
+        let cache = ItemDiagnosticsCache::new();
+        let codes = |diagnostics: &[Diagnostic]| {
+            let mut codes: Vec<_> = diagnostics
+                .iter()
+                .map(|d| (d.range.start.line, d.code.clone()))
+                .collect();
+            codes.sort_by_key(|(line, _)| *line);
+            codes
+        };
+
+        let before = "#[get(\"/users\")]\nasync fn list() -> String {\n    todo!()\n}\n\n#[post(\"/users\")]\nasync fn create() -> String {\n    String::new()\n}\n";
+        let mut ctx = AnalysisContext::new(&uri, before, &config);
+        let count = pipeline.run_cached(&mut ctx, &cache);
+        assert_eq!(
+            count,
+            ItemReuseCount {
+                reused: 0,
+                analyzed: 2
+            }
+        );
+        let mut full = AnalysisContext::new(&uri, before, &config);
+        pipeline.run(&mut full);
+        assert_eq!(codes(&ctx.diagnostics), codes(&full.diagnostics));
+        assert!(!ctx.diagnostics.is_empty());
+
+        // 只修改 create 并在文件开头插入一行：list 的诊断复用并平移
+        let after = format!(
+            "\n{}",
+            before.replace("String::new()", "\"created\".into()")
+        );
+        let mut ctx = AnalysisContext::new(&uri, &after, &config);
+        let count = pipeline.run_cached(&mut ctx, &cache);
+        assert_eq!(
+            count,
+            ItemReuseCount {
+                reused: 1,
+                analyzed: 1
+            }
+        );
+        let mut full = AnalysisContext::new(&uri, &after, &config);
+        pipeline.run(&mut full);
+        assert_eq!(codes(&ctx.diagnostics), codes(&full.diagnostics));
+    }
+}
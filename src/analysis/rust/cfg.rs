@@ -0,0 +1,167 @@
+//! 条件编译属性模块
+//!
+//! 展开 `#[cfg_attr(条件, 属性...)]`，使 `#[cfg_attr(feature = "web", get("/users"))]`
+//! 这样按条件启用的路由宏也能被识别，并记录启用它的条件（如 `feature = "web"`）。
+//! 嵌套的 `cfg_attr` 的条件合并为 `all(...)`。
+//!
+//! 也读取条目上的 `#[cfg(...)]`，用于标注只在某些条件下注册的组件。
+
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Meta, Token};
+
+/// 展开属性列表中的 `cfg_attr`
+///
+/// 返回每个属性及启用它的条件，不在 `cfg_attr` 中的属性条件为 `None`。
+/// 无法解析的 `cfg_attr` 原样保留
+pub fn expand_cfg_attrs(attrs: &[Attribute]) -> Vec<(Attribute, Option<String>)> {
+    let mut expanded = Vec::new();
+    for attr in attrs {
+        expand_attr(attr, None, &mut expanded);
+    }
+    expanded
+}
+
+fn expand_attr(
+    attr: &Attribute,
+    outer: Option<&str>,
+    expanded: &mut Vec<(Attribute, Option<String>)>,
+) {
+    let parsed = attr.path().is_ident("cfg_attr").then(|| {
+        attr.parse_args_with(|input: syn::parse::ParseStream| {
+            let condition: Meta = input.parse()?;
+            input.parse::<Token![,]>()?;
+            let metas = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+            Ok((condition, metas))
+        })
+    });
+
+    let Some(Ok((condition, metas))) = parsed else {
+        expanded.push((attr.clone(), outer.map(str::to_string)));
+        return;
+    };
+
+    let condition = meta_to_string(&condition);
+    let condition = match outer {
+        Some(outer) => format!("all({}, {})", outer, condition),
+        None => condition,
+    };
+    for meta in metas {
+        let inner = Attribute {
+            pound_token: attr.pound_token,
+            style: attr.style,
+            bracket_token: attr.bracket_token,
+            meta,
+        };
+        expand_attr(&inner, Some(&condition), expanded);
+    }
+}
+
+/// 条目上 `#[cfg(...)]` 的条件
+///
+/// 多个 `#[cfg]` 合并为 `all(...)`，没有 `#[cfg]` 时返回 `None`
+pub fn cfg_condition(attrs: &[Attribute]) -> Option<String> {
+    let conditions: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .filter_map(|attr| attr.parse_args::<Meta>().ok())
+        .map(|meta| meta_to_string(&meta))
+        .collect();
+    match conditions.len() {
+        0 => None,
+        1 => conditions.into_iter().next(),
+        _ => Some(format!("all({})", conditions.join(", "))),
+    }
+}
+
+/// 条件的显示文本（`feature = "web"`、`all(unix, feature = "web")`）
+pub fn meta_to_string(meta: &Meta) -> String {
+    match meta {
+        Meta::Path(path) => path_to_string(path),
+        Meta::NameValue(name_value) => format!(
+            "{} = {}",
+            path_to_string(&name_value.path),
+            name_value.value.to_token_stream()
+        ),
+        Meta::List(list) => {
+            match list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+                Ok(nested) => format!(
+                    "{}({})",
+                    path_to_string(&list.path),
+                    nested
+                        .iter()
+                        .map(meta_to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Err(_) => format!("{}({})", path_to_string(&list.path), list.tokens),
+            }
+        }
+    }
+}
+
+fn path_to_string(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_cfg_attrs() {
+        let item_fn: syn::ItemFn = syn::parse_str(
+            r#"
+            #[cfg_attr(feature = "web", get("/users"), middlewares(Auth))]
+            #[cfg_attr(unix, cfg_attr(any(test, feature = "admin"), post("/admin")))]
+            #[deprecated]
+            async fn handler() {}
+            "#,
+        )
+        .unwrap();
+
+        let expanded: Vec<_> = expand_cfg_attrs(&item_fn.attrs)
+            .into_iter()
+            .map(|(attr, cfg)| (path_to_string(attr.path()), cfg))
+            .collect();
+        assert_eq!(
+            expanded,
+            vec![
+                ("get".to_string(), Some("feature = \"web\"".to_string())),
+                (
+                    "middlewares".to_string(),
+                    Some("feature = \"web\"".to_string())
+                ),
+                (
+                    "post".to_string(),
+                    Some("all(unix, any(test, feature = \"admin\"))".to_string())
+                ),
+                ("deprecated".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cfg_condition() {
+        let item: syn::ItemStruct = syn::parse_str(
+            r#"
+            #[cfg(feature = "redis")]
+            #[cfg(not(test))]
+            #[derive(Clone, Service)]
+            struct CacheService {}
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            cfg_condition(&item.attrs).as_deref(),
+            Some("all(feature = \"redis\", not(test))")
+        );
+
+        let item: syn::ItemStruct = syn::parse_str("#[derive(Service)] struct A {}").unwrap();
+        assert_eq!(cfg_condition(&item.attrs), None);
+    }
+}
@@ -0,0 +1,186 @@
+//! 新建文件模板模块
+//!
+//! 在编辑器中新建空的 Rust 文件后，按所在目录（`[templates]` 配置，参见 [`TemplatesConfig`]）
+//! 生成模块文档、导入和一个带注解的示例函数：
+//!
+//! - `handler`：`#[get("/模块名")]` 路由处理器
+//! - `job`：`#[cron(...)]` 定时任务
+//!
+//! 自定义模板文件中的 `{module}` 替换为模块名称（文件名，`mod.rs` 为所在目录名）。
+
+use std::path::{Path, PathBuf};
+
+use crate::core::config::{TemplateKind, TemplateRule, TemplatesConfig};
+use crate::core::messages::{localized, Locale};
+
+/// 自定义模板中的模块名称占位符
+const MODULE_PLACEHOLDER: &str = "{module}";
+
+/// 项目根目录 `root` 下新建的文件 `file` 的模板内容
+///
+/// 内置模板的模块文档使用 `locale` 指定的语言；
+/// 未启用模板、文件不在任何规则的目录中或自定义模板文件无法读取时返回 `None`
+pub fn template_for(
+    config: &TemplatesConfig,
+    root: &Path,
+    file: &Path,
+    locale: Locale,
+) -> Option<String> {
+    if !config.enabled || file.extension().is_none_or(|ext| ext != "rs") {
+        return None;
+    }
+    let relative = file.strip_prefix(root).ok()?;
+    let rule = rule_for(&config.rules, relative)?;
+    let module = module_name(file)?;
+
+    if let Some(template_file) = &rule.file {
+        return match std::fs::read_to_string(root.join(template_file)) {
+            Ok(template) => Some(template.replace(MODULE_PLACEHOLDER, &module)),
+            Err(e) => {
+                tracing::warn!("Failed to read template {}: {}", template_file, e);
+                None
+            }
+        };
+    }
+    rule.template
+        .map(|kind| builtin_template(kind, &module, locale))
+}
+
+/// 适用于文件（相对于项目根目录）的规则，多个规则匹配时使用目录最长的规则
+fn rule_for<'a>(rules: &'a [TemplateRule], relative: &Path) -> Option<&'a TemplateRule> {
+    rules
+        .iter()
+        .filter(|rule| relative.starts_with(PathBuf::from(rule.dir.trim_end_matches('/'))))
+        .max_by_key(|rule| Path::new(&rule.dir).components().count())
+}
+
+/// 模块名称：文件名，`mod.rs` 为所在目录名；`-` 替换为 `_`
+fn module_name(file: &Path) -> Option<String> {
+    let stem = file.file_stem()?.to_str()?;
+    let name = if stem == "mod" {
+        file.parent()?.file_name()?.to_str()?
+    } else {
+        stem
+    };
+    Some(name.replace('-', "_"))
+}
+
+/// 内置模板
+fn builtin_template(kind: TemplateKind, module: &str, locale: Locale) -> String {
+    match kind {
+        TemplateKind::Handler => format!(
+            r#"//! {doc}
+
+use spring_web::axum::response::IntoResponse;
+use spring_web::get;
+
+#[get("/{route}")]
+async fn list() -> impl IntoResponse {{
+    todo!()
+}}
+"#,
+            doc = localized(locale, "template.handler", &[&module]),
+            route = module.replace('_', "-"),
+        ),
+        TemplateKind::Job => format!(
+            r#"//! {doc}
+
+use spring_job::cron;
+
+#[cron("0 0 * * * *")]
+async fn {module}_job() {{
+    todo!()
+}}
+"#,
+            doc = localized(locale, "template.job", &[&module]),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_templates() {
+        let config = TemplatesConfig::default();
+        let root = Path::new("/app");
+
+        let handler = template_for(
+            &config,
+            root,
+            Path::new("/app/src/handlers/user_roles.rs"),
+            Locale::default(),
+        )
+        .unwrap();
+        assert!(handler.contains("#[get(\"/user-roles\")]"));
+        assert!(handler.contains("use spring_web::get;"));
+
+        let job = template_for(
+            &config,
+            root,
+            Path::new("/app/src/jobs/cleanup/mod.rs"),
+            Locale::default(),
+        )
+        .unwrap();
+        assert!(job.contains("async fn cleanup_job()"));
+
+        assert!(template_for(
+            &config,
+            root,
+            Path::new("/app/src/main.rs"),
+            Locale::default()
+        )
+        .is_none());
+        assert!(template_for(
+            &config,
+            root,
+            Path::new("/app/src/handlers/README.md"),
+            Locale::default()
+        )
+        .is_none());
+
+        let disabled = TemplatesConfig {
+            enabled: false,
+            ..TemplatesConfig::default()
+        };
+        assert!(template_for(
+            &disabled,
+            root,
+            Path::new("/app/src/handlers/a.rs"),
+            Locale::default()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_custom_template_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("task.rs"), "//! {module}\n").unwrap();
+        let config = TemplatesConfig {
+            enabled: true,
+            rules: vec![
+                TemplateRule {
+                    dir: "src".to_string(),
+                    template: Some(TemplateKind::Handler),
+                    file: None,
+                },
+                TemplateRule {
+                    dir: "src/tasks/".to_string(),
+                    template: None,
+                    file: Some("task.rs".to_string()),
+                },
+            ],
+        };
+
+        let file = dir.path().join("src/tasks/daily-report.rs");
+        assert_eq!(
+            template_for(&config, dir.path(), &file, Locale::default()).unwrap(),
+            "//! daily_report\n"
+        );
+
+        // 模板文件不存在
+        std::fs::remove_file(dir.path().join("task.rs")).unwrap();
+        assert!(template_for(&config, dir.path(), &file, Locale::default()).is_none());
+    }
+}
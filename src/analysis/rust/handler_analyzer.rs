@@ -0,0 +1,1450 @@
+//! 路由处理器分析模块
+//!
+//! 分析带有路由宏的处理器函数签名，识别其中使用的提取器（extractor），包括：
+//! - `Path<T>`、`Query<T>`、`Json<T>`、`Form<T>` 等请求提取器
+//! - `State<T>`、`Component<T>`、`Config<T>` 等依赖提取器
+//!
+//! 同时收集文件中定义的结构体，用于验证提取器的类型参数和生成悬停提示。
+//!
+//! 验证时还会检查同一文件中 HTTP 方法相同、路径可能匹配同一请求的路由（路由冲突），
+//! 路径参数的正则约束参与判断，参见 [`crate::analysis::rust::route_path`]；
+//! 以及只有末尾斜杠或大小写不同的路由（按 `[routes]` 配置的约定给出快速修复）。
+//!
+//! 函数体只有 `todo!()`、`unimplemented!()` 或直接返回 `StatusCode::NOT_IMPLEMENTED` 的处理器
+//! 标记为未实现（stub），启用 `[routes] stub_handlers` 时报告提示，便于跟踪未完成的接口。
+//!
+//! `#[route("/path", method = "...")]` 中无法识别的方法（如 `FETCH`）报告错误，
+//! 并给出替换为最接近的有效方法的快速修复。
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, Hover, HoverContents, MarkupContent, MarkupKind};
+use lsp_types::{NumberOrString, Position, Range};
+use std::collections::HashMap;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+
+use crate::analysis::diagnostic::QuickFix;
+use crate::analysis::rust::cfg::expand_cfg_attrs;
+use crate::analysis::rust::macro_analyzer::{HttpMethod, HTTP_METHODS};
+use crate::core::config::{CasePolicy, RoutesConfig, TrailingSlashPolicy};
+use crate::core::document::{PositionEncoding, PositionMapper};
+use crate::core::messages::{localized, Locale};
+use crate::scanner::route::PathPattern;
+
+/// 路由宏名称（包括 OpenAPI 路由宏）
+pub(crate) const ROUTE_ATTRIBUTES: &[&str] = &[
+    "get",
+    "post",
+    "put",
+    "delete",
+    "patch",
+    "head",
+    "options",
+    "trace",
+    "connect",
+    "route",
+    "routes",
+    "get_api",
+    "post_api",
+    "put_api",
+    "delete_api",
+    "patch_api",
+];
+
+/// 不能作为 `Query<T>` 类型参数的基本类型
+const PRIMITIVE_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+    "f64", "bool", "char", "str", "String",
+];
+
+/// 提取器类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractorKind {
+    /// 路径参数 `Path<T>`
+    Path,
+    /// 查询参数 `Query<T>`
+    Query,
+    /// JSON 请求体 `Json<T>`
+    Json,
+    /// 表单请求体 `Form<T>`
+    Form,
+    /// 请求头 `TypedHeader<T>` / `HeaderMap`
+    Header,
+    /// 应用状态 `State<T>`
+    State,
+    /// 组件注入 `Component<T>`
+    Component,
+    /// 配置注入 `Config<T>`
+    Config,
+    /// 其他类型（如 `Request`、`Method` 或自定义提取器）
+    Other(String),
+}
+
+impl ExtractorKind {
+    /// 根据类型名称识别提取器
+    pub fn from_type_name(name: &str) -> Self {
+        match name {
+            "Path" => ExtractorKind::Path,
+            "Query" => ExtractorKind::Query,
+            "Json" => ExtractorKind::Json,
+            "Form" => ExtractorKind::Form,
+            "TypedHeader" | "HeaderMap" => ExtractorKind::Header,
+            "State" => ExtractorKind::State,
+            "Component" => ExtractorKind::Component,
+            "Config" => ExtractorKind::Config,
+            other => ExtractorKind::Other(other.to_string()),
+        }
+    }
+
+    /// 提取器名称
+    pub fn as_str(&self) -> &str {
+        match self {
+            ExtractorKind::Path => "Path",
+            ExtractorKind::Query => "Query",
+            ExtractorKind::Json => "Json",
+            ExtractorKind::Form => "Form",
+            ExtractorKind::Header => "TypedHeader",
+            ExtractorKind::State => "State",
+            ExtractorKind::Component => "Component",
+            ExtractorKind::Config => "Config",
+            ExtractorKind::Other(name) => name,
+        }
+    }
+}
+
+/// 处理器参数（提取器）
+#[derive(Debug, Clone)]
+pub struct ExtractorParam {
+    /// 提取器类型
+    pub kind: ExtractorKind,
+    /// 完整类型文本，如 `Query<SearchParams>`
+    pub type_name: String,
+    /// 泛型参数类型文本，如 `SearchParams`
+    pub inner_type: Option<String>,
+    /// 参数类型在文档中的位置范围
+    pub range: Range,
+}
+
+/// 路由处理器信息
+#[derive(Debug, Clone)]
+pub struct HandlerInfo {
+    /// 处理器函数名
+    pub name: String,
+    /// 是否为异步函数
+    pub is_async: bool,
+    /// 路由宏中声明的路由（每个路由宏一个）
+    pub routes: Vec<HandlerRoute>,
+    /// 处理器参数
+    pub params: Vec<ExtractorParam>,
+    /// 返回类型（外层的 `Result<T, E>` 已展开为 `T`），没有返回类型时为 `None`
+    pub return_type: Option<ExtractorParam>,
+    /// 函数体是否为未实现的占位代码（见 [`is_stub_body`]）
+    pub stub: bool,
+    /// 函数体的行数（包括两端的花括号所在行）
+    pub body_lines: usize,
+    /// 函数名在文档中的位置范围
+    pub range: Range,
+}
+
+/// 路由宏声明的路由
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerRoute {
+    /// 路由路径
+    pub path: String,
+    /// HTTP 方法（大写）
+    pub methods: Vec<String>,
+    /// 路径字符串字面量（包括引号）在文档中的位置范围
+    pub range: Range,
+    /// `#[route(..., method = "...")]` 中的方法字符串字面量
+    pub method_literals: Vec<MethodLiteral>,
+}
+
+/// 路由宏 `method` 参数中的字符串字面量
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodLiteral {
+    /// 字面量的值（保持原样，不转换大小写）
+    pub value: String,
+    /// 字符串字面量（包括引号）在文档中的位置范围
+    pub range: Range,
+}
+
+/// 结构体字段
+#[derive(Debug, Clone)]
+pub struct StructField {
+    /// 字段名
+    pub name: String,
+    /// 序列化后的名称（考虑 `#[serde(rename)]` 和 `#[serde(rename_all)]`）
+    pub serialized_name: String,
+    /// 字段类型
+    pub type_name: String,
+    /// 是否为 `Option<T>`
+    pub optional: bool,
+    /// 字段在文档中的位置范围
+    pub range: Range,
+}
+
+/// 结构体信息
+#[derive(Debug, Clone)]
+pub struct StructInfo {
+    /// 结构体名称
+    pub name: String,
+    /// 命名字段列表
+    pub fields: Vec<StructField>,
+    /// 结构体名在文档中的位置范围
+    pub range: Range,
+}
+
+/// 单个文件的处理器分析结果
+#[derive(Debug, Clone, Default)]
+pub struct HandlerFile {
+    /// 路由处理器
+    pub handlers: Vec<HandlerInfo>,
+    /// 文件中定义的结构体（键为结构体名）
+    pub structs: HashMap<String, StructInfo>,
+}
+
+impl HandlerFile {
+    /// 光标所在的处理器参数
+    pub fn param_at(&self, position: Position) -> Option<&ExtractorParam> {
+        self.handlers
+            .iter()
+            .flat_map(|handler| handler.params.iter())
+            .find(|param| position_in_range(position, param.range))
+    }
+
+    /// 光标所在的路由路径
+    pub fn route_at(&self, position: Position) -> Option<&HandlerRoute> {
+        self.handlers
+            .iter()
+            .flat_map(|handler| handler.routes.iter())
+            .find(|route| position_in_range(position, route.range))
+    }
+}
+
+/// 路由处理器分析器
+#[derive(Debug, Clone, Default)]
+pub struct HandlerAnalyzer {
+    /// 位置编码
+    encoding: PositionEncoding,
+    /// 诊断和悬停提示的语言
+    locale: Locale,
+}
+
+impl HandlerAnalyzer {
+    /// 创建新的处理器分析器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用与客户端协商的位置编码计算处理器和结构体的位置
+    pub fn with_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// 使用指定的语言生成诊断和悬停提示
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 消息语言
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// 解析 Rust 源代码，提取路由处理器和结构体定义
+    pub fn analyze(&self, content: &str) -> Result<HandlerFile, syn::Error> {
+        self.analyze_lines(&PositionMapper::with_encoding(content, self.encoding))
+    }
+
+    /// 使用已建立的位置转换器解析 Rust 源代码（打开的文档复用缓存的行索引）
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
+    pub fn analyze_lines(&self, lines: &PositionMapper) -> Result<HandlerFile, syn::Error> {
+        let file = syn::parse_file(lines.content())?;
+        let mut result = HandlerFile::default();
+
+        for item in &file.items {
+            match item {
+                syn::Item::Fn(item_fn) => {
+                    if let Some(handler) = self.extract_handler(item_fn, lines) {
+                        result.handlers.push(handler);
+                    }
+                }
+                syn::Item::Struct(item_struct) => {
+                    let info = self.extract_struct(item_struct, lines);
+                    result.structs.insert(info.name.clone(), info);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// 验证处理器的提取器用法
+    ///
+    /// - 路由路径不应包含查询字符串（`?` 之后的部分），查询参数应通过 `Query<T>` 提取
+    /// - `Query<T>` 的类型参数必须是结构体（或键值集合），不能是基本类型
+    pub fn validate(&self, file: &HandlerFile) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for handler in &file.handlers {
+            for route in &handler.routes {
+                let path = &route.path;
+                if let Some((_, query)) = path.split_once('?') {
+                    diagnostics.push(Diagnostic {
+                        range: handler.range,
+                        severity: Some(DiagnosticSeverity::WARNING),
+                        code: Some(NumberOrString::String("W003".to_string())),
+                        source: Some("spring-lsp".to_string()),
+                        message: localized(self.locale, "W003", &[path, &query]),
+                        ..Default::default()
+                    });
+                }
+
+                for literal in &route.method_literals {
+                    if HttpMethod::parse_method(&literal.value).is_none() {
+                        diagnostics.push(invalid_method_diagnostic(literal, self.locale));
+                    }
+                }
+            }
+
+            for param in &handler.params {
+                if param.kind != ExtractorKind::Query {
+                    continue;
+                }
+                let Some(inner) = &param.inner_type else {
+                    continue;
+                };
+
+                if is_primitive_type(inner) {
+                    diagnostics.push(Diagnostic {
+                        range: param.range,
+                        severity: Some(DiagnosticSeverity::ERROR),
+                        code: Some(NumberOrString::String("E018".to_string())),
+                        source: Some("spring-lsp".to_string()),
+                        message: localized(self.locale, "E018", &[inner]),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+
+        diagnostics.extend(self.validate_route_conflicts(file));
+        diagnostics
+    }
+
+    /// 检查同一文件中的路由冲突
+    ///
+    /// 两个路由的 HTTP 方法有交集，且路径可能匹配同一个请求时，在后声明的处理器上报告警告。
+    /// 带正则约束的参数只与满足约束的字面量冲突，例如 `/users/{id:\d+}` 与 `/users/me` 不冲突
+    fn validate_route_conflicts(&self, file: &HandlerFile) -> Vec<Diagnostic> {
+        let routes: Vec<_> = file
+            .handlers
+            .iter()
+            .flat_map(|handler| {
+                handler
+                    .routes
+                    .iter()
+                    .map(move |route| (handler, route, PathPattern::parse(&route.path)))
+            })
+            .collect();
+
+        let mut diagnostics = Vec::new();
+        for (i, (handler, route, path)) in routes.iter().enumerate() {
+            let conflict = routes[..i].iter().find(|(other, other_route, other_path)| {
+                other.name != handler.name
+                    && route
+                        .methods
+                        .iter()
+                        .any(|method| other_route.methods.contains(method))
+                    && path.conflicts_with(other_path)
+            });
+
+            if let Some((other, other_route, _)) = conflict {
+                diagnostics.push(Diagnostic {
+                    range: handler.range,
+                    severity: Some(DiagnosticSeverity::WARNING),
+                    code: Some(NumberOrString::String("W004".to_string())),
+                    source: Some("spring-lsp".to_string()),
+                    message: localized(
+                        self.locale,
+                        "W004",
+                        &[
+                            &route.methods.join("|"),
+                            &route.path,
+                            &other.name,
+                            &other_route.methods.join("|"),
+                            &other_route.path,
+                        ],
+                    ),
+                    ..Default::default()
+                });
+            }
+        }
+
+        diagnostics
+    }
+
+    /// 报告未实现的处理器（函数体只有 `todo!()`、`unimplemented!()` 或返回 501 状态码）
+    pub fn validate_stubs(&self, file: &HandlerFile) -> Vec<Diagnostic> {
+        file.handlers
+            .iter()
+            .filter(|handler| handler.stub)
+            .map(|handler| {
+                let routes = handler
+                    .routes
+                    .iter()
+                    .map(|route| format!("{} {}", route.methods.join("|"), route.path))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Diagnostic {
+                    range: handler.range,
+                    severity: Some(DiagnosticSeverity::INFORMATION),
+                    code: Some(NumberOrString::String("W007".to_string())),
+                    source: Some("spring-lsp".to_string()),
+                    message: localized(self.locale, "W007", &[&handler.name, &routes]),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    /// 检查同一文件中只有末尾斜杠或大小写不同的路由
+    ///
+    /// 按配置的约定在不符合约定的路由路径上报告警告，并附带把路径改为约定形式的快速修复
+    pub fn validate_route_style(
+        &self,
+        file: &HandlerFile,
+        config: &RoutesConfig,
+    ) -> Vec<Diagnostic> {
+        let routes: Vec<_> = file
+            .handlers
+            .iter()
+            .flat_map(|handler| handler.routes.iter())
+            .collect();
+
+        let mut diagnostics = Vec::new();
+        for route in &routes {
+            let pattern = PathPattern::parse(&route.path);
+            let siblings = routes
+                .iter()
+                .filter(|other| other.path != route.path)
+                .map(|other| other.path.as_str());
+
+            let mut slash_sibling = None;
+            let mut case_sibling = None;
+            for sibling in siblings {
+                if slash_sibling.is_none()
+                    && pattern.differs_only_by_trailing_slash(&PathPattern::parse(sibling))
+                {
+                    slash_sibling = Some(sibling);
+                } else if case_sibling.is_none()
+                    && lowercase_literals(&route.path) == lowercase_literals(sibling)
+                {
+                    case_sibling = Some(sibling);
+                }
+            }
+
+            if let Some(sibling) = slash_sibling {
+                let fixed = match config.trailing_slash {
+                    TrailingSlashPolicy::Never if pattern.has_trailing_slash() => {
+                        Some(PathPattern::trim_trailing_slash(&route.path).to_string())
+                    }
+                    TrailingSlashPolicy::Always if !route.path.ends_with('/') => {
+                        Some(format!("{}/", route.path))
+                    }
+                    _ => None,
+                };
+                if let Some(fixed) = fixed {
+                    diagnostics.push(route_style_diagnostic(
+                        route,
+                        "W005",
+                        localized(self.locale, "W005", &[&route.path, &sibling]),
+                        fixed,
+                        self.locale,
+                    ));
+                }
+            }
+
+            if let Some(sibling) = case_sibling {
+                let fixed = lowercase_literals(&route.path);
+                if config.case == CasePolicy::Lowercase && fixed != route.path {
+                    diagnostics.push(route_style_diagnostic(
+                        route,
+                        "W006",
+                        localized(self.locale, "W006", &[&route.path, &sibling]),
+                        fixed,
+                        self.locale,
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+
+    /// 为处理器参数中的提取器和路由宏的 `method` 参数提供悬停提示
+    ///
+    /// 提取器目前支持 `Query<T>`：显示从结构体字段推导出的查询参数名称；
+    /// `method = "..."` 显示允许的方法字符串
+    pub fn hover(&self, file: &HandlerFile, position: Position) -> Option<Hover> {
+        let literal = file
+            .handlers
+            .iter()
+            .flat_map(|handler| handler.routes.iter())
+            .flat_map(|route| route.method_literals.iter())
+            .find(|literal| position_in_range(position, literal.range));
+        if let Some(literal) = literal {
+            return Some(method_hover(literal, self.locale));
+        }
+
+        let param = file.param_at(position)?;
+        let (title, description) = match param.kind {
+            ExtractorKind::Query => ("Query", "hover.extractor.query"),
+            ExtractorKind::Json => ("Json", "hover.extractor.json"),
+            ExtractorKind::Form => ("Form", "hover.extractor.form"),
+            _ => return None,
+        };
+
+        let inner = param.inner_type.as_deref()?;
+        let mut hover = String::new();
+        hover.push_str(&localized(self.locale, "hover.extractor.title", &[&title]));
+        hover.push_str(&localized(self.locale, description, &[]));
+        hover.push_str(&localized(self.locale, "hover.extractor.type", &[&inner]));
+
+        match file.structs.get(base_type_name(inner)) {
+            Some(info) if !info.fields.is_empty() => {
+                if param.kind == ExtractorKind::Query {
+                    hover.push_str(&localized(self.locale, "hover.extractor.query-params", &[]));
+                    for field in &info.fields {
+                        let key = if field.optional {
+                            "hover.extractor.optional-param"
+                        } else {
+                            "hover.extractor.param"
+                        };
+                        hover.push_str(&localized(
+                            self.locale,
+                            key,
+                            &[&field.serialized_name, &field.type_name],
+                        ));
+                    }
+
+                    let example = info
+                        .fields
+                        .iter()
+                        .map(|field| format!("{}=...", field.serialized_name))
+                        .collect::<Vec<_>>()
+                        .join("&");
+                    hover.push_str(&localized(
+                        self.locale,
+                        "hover.extractor.example",
+                        &[&example],
+                    ));
+                }
+
+                hover.push_str(&localized(self.locale, "hover.extractor.shape", &[]));
+                hover.push_str(&shape_preview(info, &file.structs, 0));
+                hover.push_str("\n```\n");
+            }
+            Some(_) => {
+                hover.push_str(&localized(self.locale, "hover.extractor.no-fields", &[]));
+            }
+            None if param.kind == ExtractorKind::Query && is_primitive_type(inner) => {
+                hover.push_str(&localized(self.locale, "hover.extractor.primitive", &[]));
+            }
+            None => {
+                hover.push_str(&localized(
+                    self.locale,
+                    "hover.extractor.not-found",
+                    &[&inner],
+                ));
+            }
+        }
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: hover,
+            }),
+            range: Some(param.range),
+        })
+    }
+
+    /// 提取路由处理器（只处理带有路由宏的函数）
+    fn extract_handler(
+        &self,
+        item_fn: &syn::ItemFn,
+        lines: &PositionMapper,
+    ) -> Option<HandlerInfo> {
+        let mut is_handler = false;
+        let mut routes = Vec::new();
+
+        // `#[cfg_attr(feature = "...", get("/..."))]` 中的路由宏同样识别
+        for (attr, _) in expand_cfg_attrs(&item_fn.attrs) {
+            if ROUTE_ATTRIBUTES
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+            {
+                is_handler = true;
+                if let (Some(path), methods) = route_path_and_methods(&attr) {
+                    let range = path_literal_range(&attr, lines)
+                        .unwrap_or_else(|| lines.span_range(item_fn.sig.ident.span()));
+                    routes.push(HandlerRoute {
+                        path,
+                        methods,
+                        range,
+                        method_literals: method_literals(&attr, lines),
+                    });
+                }
+            }
+        }
+
+        if !is_handler {
+            return None;
+        }
+
+        let params = item_fn
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                syn::FnArg::Typed(pat_type) => Some(self.extract_param(&pat_type.ty, lines)),
+                syn::FnArg::Receiver(_) => None,
+            })
+            .collect();
+
+        let return_type = match &item_fn.sig.output {
+            syn::ReturnType::Type(_, ty) => Some(self.extract_param(unwrap_result(ty), lines)),
+            syn::ReturnType::Default => None,
+        };
+
+        Some(HandlerInfo {
+            name: item_fn.sig.ident.to_string(),
+            is_async: item_fn.sig.asyncness.is_some(),
+            routes,
+            params,
+            return_type,
+            stub: is_stub_body(&item_fn.block),
+            body_lines: body_lines(&item_fn.block),
+            range: lines.span_range(item_fn.sig.ident.span()),
+        })
+    }
+
+    /// 提取单个参数的提取器信息
+    fn extract_param(&self, ty: &syn::Type, lines: &PositionMapper) -> ExtractorParam {
+        let (kind, inner_type) = match ty {
+            syn::Type::Path(type_path) => match type_path.path.segments.last() {
+                Some(segment) => {
+                    let inner = match &segment.arguments {
+                        syn::PathArguments::AngleBracketed(args) => {
+                            args.args.iter().find_map(|arg| match arg {
+                                syn::GenericArgument::Type(inner) => Some(type_to_string(inner)),
+                                _ => None,
+                            })
+                        }
+                        _ => None,
+                    };
+                    (
+                        ExtractorKind::from_type_name(&segment.ident.to_string()),
+                        inner,
+                    )
+                }
+                None => (ExtractorKind::Other(type_to_string(ty)), None),
+            },
+            _ => (ExtractorKind::Other(type_to_string(ty)), None),
+        };
+
+        ExtractorParam {
+            kind,
+            type_name: type_to_string(ty),
+            inner_type,
+            range: lines.span_range(ty.span()),
+        }
+    }
+
+    /// 提取结构体定义及其字段
+    fn extract_struct(&self, item_struct: &syn::ItemStruct, lines: &PositionMapper) -> StructInfo {
+        let rename_all = serde_attr_value(&item_struct.attrs, "rename_all");
+
+        let fields = match &item_struct.fields {
+            syn::Fields::Named(named) => named
+                .named
+                .iter()
+                .filter_map(|field| {
+                    let name = field.ident.as_ref()?.to_string();
+                    let serialized_name = serde_attr_value(&field.attrs, "rename")
+                        .unwrap_or_else(|| apply_rename_rule(&name, rename_all.as_deref()));
+
+                    Some(StructField {
+                        serialized_name,
+                        type_name: type_to_string(&field.ty),
+                        optional: base_type_name(&type_to_string(&field.ty)) == "Option",
+                        range: lines.span_range(field.span()),
+                        name,
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        StructInfo {
+            name: item_struct.ident.to_string(),
+            fields,
+            range: lines.span_range(item_struct.ident.span()),
+        }
+    }
+}
+
+/// 判断类型是否为基本类型（包括引用和元组）
+pub(crate) fn is_primitive_type(type_name: &str) -> bool {
+    let trimmed = type_name.trim_start_matches('&');
+    trimmed.starts_with('(') || PRIMITIVE_TYPES.contains(&base_type_name(trimmed))
+}
+
+/// 获取类型的基础名称：去掉模块路径和泛型参数
+///
+/// 例如 `crate::dto::Page<User>` -> `Page`
+pub(crate) fn base_type_name(type_name: &str) -> &str {
+    let without_generics = type_name.split('<').next().unwrap_or(type_name);
+    without_generics
+        .rsplit("::")
+        .next()
+        .unwrap_or(without_generics)
+        .trim()
+}
+
+/// 悬停提示中展开嵌套结构体的最大深度
+const MAX_SHAPE_DEPTH: usize = 3;
+
+/// 结构体序列化后的 JSON 结构预览
+///
+/// 每行一个字段：键为序列化后的名称，`Option<T>` 字段的键带 `?`，值为 JSON 类型，
+/// 注释中是 Rust 字段名和类型。嵌套的结构体在 `structs` 中定义时展开
+fn shape_preview(info: &StructInfo, structs: &HashMap<String, StructInfo>, depth: usize) -> String {
+    let indent = "  ".repeat(depth + 1);
+    let mut lines = vec!["{".to_string()];
+    for (i, field) in info.fields.iter().enumerate() {
+        let comma = if i + 1 < info.fields.len() { "," } else { "" };
+        lines.push(format!(
+            "{}\"{}\"{}: {}{}  // {}: {}",
+            indent,
+            field.serialized_name,
+            if field.optional { "?" } else { "" },
+            json_type(&field.type_name, structs, depth + 1),
+            comma,
+            field.name,
+            field.type_name
+        ));
+    }
+    lines.push(format!("{}}}", "  ".repeat(depth)));
+    lines.join("\n")
+}
+
+/// Rust 类型对应的 JSON 类型
+fn json_type(type_name: &str, structs: &HashMap<String, StructInfo>, depth: usize) -> String {
+    let type_name = type_name.trim_start_matches('&');
+    let args = generic_args(type_name);
+    match base_type_name(type_name) {
+        "Option" => match args.first() {
+            Some(inner) => format!("{} | null", json_type(inner, structs, depth)),
+            None => "null".to_string(),
+        },
+        "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => match args.first() {
+            Some(inner) => format!("{}[]", json_type(inner, structs, depth)),
+            None => "[]".to_string(),
+        },
+        "HashMap" | "BTreeMap" => match args.get(1) {
+            Some(value) => format!("{{ [key: string]: {} }}", json_type(value, structs, depth)),
+            None => "{}".to_string(),
+        },
+        "Box" | "Arc" | "Rc" => args
+            .first()
+            .map(|inner| json_type(inner, structs, depth))
+            .unwrap_or_else(|| type_name.to_string()),
+        "String" | "str" | "char" | "Uuid" | "DateTime" | "NaiveDate" | "NaiveDateTime" => {
+            "string".to_string()
+        }
+        "bool" => "boolean".to_string(),
+        "f32" | "f64" => "number".to_string(),
+        name if PRIMITIVE_TYPES.contains(&name) => "number".to_string(),
+        "Value" => "any".to_string(),
+        name => match structs.get(name) {
+            Some(info) if depth < MAX_SHAPE_DEPTH && !info.fields.is_empty() => {
+                shape_preview(info, structs, depth)
+            }
+            _ => type_name.to_string(),
+        },
+    }
+}
+
+/// 泛型参数列表（只拆分最外层），如 `HashMap<String, Vec<u32>>` -> `["String", "Vec<u32>"]`
+fn generic_args(type_name: &str) -> Vec<&str> {
+    let (Some(start), Some(end)) = (type_name.find('<'), type_name.rfind('>')) else {
+        return Vec::new();
+    };
+    if end <= start {
+        return Vec::new();
+    }
+
+    let inner = &type_name[start + 1..end];
+    let mut args = Vec::new();
+    let mut depth = 0usize;
+    let mut arg_start = 0;
+    for (i, ch) in inner.char_indices() {
+        match ch {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                args.push(inner[arg_start..i].trim());
+                arg_start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    args.push(inner[arg_start..].trim());
+    args.retain(|arg| !arg.is_empty());
+    args
+}
+
+/// 展开 `Result<T, E>`，返回 `T`；其他类型原样返回
+fn unwrap_result(ty: &syn::Type) -> &syn::Type {
+    let syn::Type::Path(type_path) = ty else {
+        return ty;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return ty;
+    };
+    if segment.ident != "Result" {
+        return ty;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .find_map(|arg| match arg {
+                syn::GenericArgument::Type(inner) => Some(inner),
+                _ => None,
+            })
+            .unwrap_or(ty),
+        _ => ty,
+    }
+}
+
+/// 将类型转换为紧凑的字符串表示
+fn type_to_string(ty: &syn::Type) -> String {
+    quote::quote!(#ty)
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(" < ", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
+
+/// 读取路由宏中的路径和 HTTP 方法
+///
+/// `#[get("/users")]` 的方法来自宏名称，`#[route("/users", method = "GET")]`
+/// 的方法来自 `method` 参数
+pub(crate) fn route_path_and_methods(attr: &syn::Attribute) -> (Option<String>, Vec<String>) {
+    let name = attr
+        .path()
+        .get_ident()
+        .map(|ident| ident.to_string())
+        .unwrap_or_default();
+    let Ok(args) = attr.parse_args_with(Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated)
+    else {
+        return (None, Vec::new());
+    };
+
+    let mut path = None;
+    let mut methods = Vec::new();
+    for arg in &args {
+        match arg {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) if path.is_none() => path = Some(lit.value()),
+            syn::Expr::Assign(assign) => {
+                let is_method =
+                    matches!(&*assign.left, syn::Expr::Path(p) if p.path.is_ident("method"));
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit),
+                    ..
+                }) = &*assign.right
+                {
+                    if is_method {
+                        methods.push(lit.value().to_uppercase());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if name != "route" && name != "routes" {
+        methods.push(name.trim_end_matches("_api").to_uppercase());
+    }
+
+    (path, methods)
+}
+
+/// 函数体的行数（从左花括号所在行到右花括号所在行）
+fn body_lines(block: &syn::Block) -> usize {
+    let span = block.brace_token.span;
+    let (start, end) = (span.open().start().line, span.close().end().line);
+    end.saturating_sub(start) + 1
+}
+
+/// 函数体是否为未实现的占位代码
+///
+/// 函数体只有一个表达式（或语句），并且是 `todo!(...)`、`unimplemented!(...)`，
+/// 或者是 `StatusCode::NOT_IMPLEMENTED`（包括 `Ok(...)` 和元组响应 `(StatusCode::NOT_IMPLEMENTED, ...)`）
+pub fn is_stub_body(block: &syn::Block) -> bool {
+    fn is_stub_expr(expr: &syn::Expr) -> bool {
+        match expr {
+            syn::Expr::Macro(expr_macro) => is_stub_macro(&expr_macro.mac),
+            syn::Expr::Path(expr_path) => expr_path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "NOT_IMPLEMENTED"),
+            syn::Expr::Tuple(tuple) => tuple.elems.first().is_some_and(is_stub_expr),
+            syn::Expr::Call(call) if call.args.len() == 1 => {
+                matches!(&*call.func, syn::Expr::Path(func) if func.path.is_ident("Ok"))
+                    && is_stub_expr(&call.args[0])
+            }
+            syn::Expr::Return(syn::ExprReturn {
+                expr: Some(expr), ..
+            })
+            | syn::Expr::Paren(syn::ExprParen { expr, .. }) => is_stub_expr(expr),
+            _ => false,
+        }
+    }
+
+    fn is_stub_macro(mac: &syn::Macro) -> bool {
+        mac.path.is_ident("todo") || mac.path.is_ident("unimplemented")
+    }
+
+    match block.stmts.as_slice() {
+        [syn::Stmt::Expr(expr, _)] => is_stub_expr(expr),
+        [syn::Stmt::Macro(stmt_macro)] => is_stub_macro(&stmt_macro.mac),
+        _ => false,
+    }
+}
+
+/// 路由宏中第一个字符串字面量（路径）的位置范围
+fn path_literal_range(attr: &syn::Attribute, lines: &PositionMapper) -> Option<Range> {
+    let args = attr
+        .parse_args_with(Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated)
+        .ok()?;
+    args.iter().find_map(|arg| match arg {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Some(lines.span_range(lit.span())),
+        _ => None,
+    })
+}
+
+/// 路由宏中 `method = "..."` 参数的字符串字面量及其位置范围
+fn method_literals(attr: &syn::Attribute, lines: &PositionMapper) -> Vec<MethodLiteral> {
+    let Ok(args) = attr.parse_args_with(Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated)
+    else {
+        return Vec::new();
+    };
+    args.iter()
+        .filter_map(|arg| match arg {
+            syn::Expr::Assign(assign) => match (&*assign.left, &*assign.right) {
+                (
+                    syn::Expr::Path(left),
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }),
+                ) if left.path.is_ident("method") => Some(MethodLiteral {
+                    value: lit.value(),
+                    range: lines.span_range(lit.span()),
+                }),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// 读取 `#[serde(key = "value")]` 形式的属性值
+fn serde_attr_value(attrs: &[syn::Attribute], key: &str) -> Option<String> {
+    let mut value = None;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                value = Some(lit.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                // 跳过其他 key = value 形式的参数
+                let _: syn::Expr = meta.value()?.parse()?;
+            }
+            Ok(())
+        });
+    }
+
+    value
+}
+
+/// 按 serde 的 `rename_all` 规则转换字段名
+fn apply_rename_rule(name: &str, rule: Option<&str>) -> String {
+    let words: Vec<&str> = name.split('_').filter(|w| !w.is_empty()).collect();
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+            None => String::new(),
+        }
+    };
+
+    match rule {
+        Some("camelCase") => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| if i == 0 { w.to_string() } else { capitalize(w) })
+            .collect(),
+        Some("PascalCase") => words.iter().map(|w| capitalize(w)).collect(),
+        Some("kebab-case") => words.join("-"),
+        Some("SCREAMING_SNAKE_CASE") => name.to_uppercase(),
+        Some("SCREAMING-KEBAB-CASE") => words.join("-").to_uppercase(),
+        Some("lowercase") => name.to_lowercase(),
+        Some("UPPERCASE") => name.to_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
+/// 生成路由路径风格诊断，附带把路径替换为 `fixed` 的快速修复
+fn route_style_diagnostic(
+    route: &HandlerRoute,
+    code: &str,
+    message: String,
+    fixed: String,
+    locale: Locale,
+) -> Diagnostic {
+    let mut diagnostic = Diagnostic {
+        range: route.range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(code.to_string())),
+        source: Some("spring-lsp".to_string()),
+        message,
+        ..Default::default()
+    };
+    QuickFix {
+        title: localized(locale, "route-style.fix", &[&fixed]),
+        new_text: format!("{:?}", fixed),
+        range: None,
+    }
+    .attach(&mut diagnostic);
+    diagnostic
+}
+
+/// 生成无效 HTTP 方法诊断，附带替换为最接近的有效方法的快速修复
+fn invalid_method_diagnostic(literal: &MethodLiteral, locale: Locale) -> Diagnostic {
+    let closest = HttpMethod::closest(&literal.value);
+    let mut diagnostic = Diagnostic {
+        range: literal.range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String("E020".to_string())),
+        source: Some("spring-lsp".to_string()),
+        message: localized(
+            locale,
+            "E020",
+            &[
+                &literal.value,
+                &HTTP_METHODS
+                    .iter()
+                    .map(HttpMethod::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ],
+        ),
+        ..Default::default()
+    };
+    QuickFix {
+        title: localized(locale, "E020.fix", &[&closest.as_str()]),
+        new_text: format!("{:?}", closest.as_str()),
+        range: None,
+    }
+    .attach(&mut diagnostic);
+    diagnostic
+}
+
+/// `method = "..."` 的悬停提示：当前值和允许的方法字符串
+fn method_hover(literal: &MethodLiteral, locale: Locale) -> Hover {
+    let mut hover = String::new();
+    hover.push_str(&localized(locale, "hover.method.title", &[]));
+    match HttpMethod::parse_method(&literal.value) {
+        Some(method) => hover.push_str(&localized(
+            locale,
+            "hover.method.current",
+            &[&method.as_str(), &method.description(locale)],
+        )),
+        None => hover.push_str(&localized(
+            locale,
+            "hover.method.invalid",
+            &[
+                &literal.value,
+                &HttpMethod::closest(&literal.value).as_str(),
+            ],
+        )),
+    }
+    hover.push_str(&localized(locale, "hover.method.allowed", &[]));
+    for method in HTTP_METHODS {
+        hover.push_str(&format!(
+            "- `{}` - {}\n",
+            method.as_str(),
+            method.description(locale)
+        ));
+    }
+
+    Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: hover,
+        }),
+        range: Some(literal.range),
+    }
+}
+
+/// 把路径中的字面量转换为小写，路径参数（`{...}` 内的部分）保持不变
+fn lowercase_literals(path: &str) -> String {
+    let mut depth = 0usize;
+    path.chars()
+        .map(|ch| {
+            match ch {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            if depth == 0 {
+                ch.to_ascii_lowercase()
+            } else {
+                ch
+            }
+        })
+        .collect()
+}
+
+/// 检查位置是否在范围内
+fn position_in_range(position: Position, range: Range) -> bool {
+    position >= range.start && position <= range.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+use spring_web::extractor::{Query, Path, Component};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchParams {
+    keyword: String,
+    page_size: Option<u32>,
+    #[serde(rename = "p")]
+    page: u32,
+}
+
+#[get("/search")]
+async fn search(Query(params): Query<SearchParams>, Component(db): Component<Db>) -> String {
+    String::new()
+}
+
+#[get("/count?limit")]
+async fn count(Query(limit): Query<u32>) -> String {
+    String::new()
+}
+
+fn not_a_handler(Query(limit): Query<u32>) {}
+"#;
+
+    #[test]
+    fn test_extract_handlers_and_extractors() {
+        let file = HandlerAnalyzer::new().analyze(SOURCE).unwrap();
+
+        assert_eq!(file.handlers.len(), 2);
+        let search = &file.handlers[0];
+        assert_eq!(search.name, "search");
+        assert!(search.is_async);
+        assert_eq!(search.routes.len(), 1);
+        assert_eq!(search.routes[0].path, "/search");
+        assert_eq!(search.routes[0].methods, vec!["GET".to_string()]);
+        assert_eq!(search.routes[0].range.start.character, 6);
+        assert_eq!(search.params.len(), 2);
+        assert_eq!(search.params[0].kind, ExtractorKind::Query);
+        assert_eq!(search.params[0].inner_type.as_deref(), Some("SearchParams"));
+        assert_eq!(search.params[1].kind, ExtractorKind::Component);
+
+        let params = &file.structs["SearchParams"];
+        let names: Vec<_> = params
+            .fields
+            .iter()
+            .map(|f| f.serialized_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["keyword", "pageSize", "p"]);
+        assert!(params.fields[1].optional);
+    }
+
+    #[test]
+    fn test_validate_query_extractor() {
+        let analyzer = HandlerAnalyzer::new();
+        let file = analyzer.analyze(SOURCE).unwrap();
+        let diagnostics = analyzer.validate(&file);
+
+        let codes: Vec<_> = diagnostics
+            .iter()
+            .filter_map(|d| match &d.code {
+                Some(NumberOrString::String(code)) => Some(code.as_str()),
+                _ => None,
+            })
+            .collect();
+        // count 处理器：路径包含查询字符串 + Query<u32>
+        assert_eq!(codes, vec!["W003", "E018"]);
+        assert!(diagnostics[1].message.contains("u32"));
+    }
+
+    #[test]
+    fn test_hover_query_extractor() {
+        let analyzer = HandlerAnalyzer::new();
+        let file = analyzer.analyze(SOURCE).unwrap();
+        let param = &file.handlers[0].params[0];
+
+        let hover = analyzer.hover(&file, param.range.start).unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("Expected markup hover");
+        };
+        assert!(content.value.contains("`keyword`: `String`"));
+        assert!(content.value.contains("`pageSize`: `Option<u32>`（可选）"));
+        assert!(content.value.contains("?keyword=...&pageSize=...&p=..."));
+
+        // Component 提取器没有悬停提示
+        let component = &file.handlers[0].params[1];
+        assert!(analyzer.hover(&file, component.range.start).is_none());
+    }
+
+    #[test]
+    fn test_hover_json_extractor_shape() {
+        let source = r#"
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateOrder {
+    customer_name: String,
+    #[serde(rename = "qty")]
+    quantity: u32,
+    note: Option<String>,
+    items: Vec<OrderItem>,
+    tags: HashMap<String, bool>,
+}
+
+#[derive(Deserialize)]
+struct OrderItem {
+    sku: String,
+}
+
+#[post("/orders")]
+async fn create_order(Json(order): Json<CreateOrder>, Form(f): Form<Missing>) {}
+"#;
+        let analyzer = HandlerAnalyzer::new();
+        let file = analyzer.analyze(source).unwrap();
+        let params = &file.handlers[0].params;
+
+        let hover = analyzer.hover(&file, params[0].range.start).unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("Expected markup hover");
+        };
+        assert!(content.value.contains("# Json 提取器"));
+        assert!(content.value.contains(
+            "```jsonc\n{\n  \"customerName\": string,  // customer_name: String\n  \"qty\": number,  // quantity: u32\n  \"note\"?: string | null,  // note: Option<String>\n  \"items\": {\n    \"sku\": string  // sku: String\n  }[],  // items: Vec<OrderItem>\n  \"tags\": { [key: string]: boolean }  // tags: HashMap<String, bool>\n}\n```"
+        ));
+
+        let hover = analyzer.hover(&file, params[1].range.start).unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("Expected markup hover");
+        };
+        assert!(content.value.contains("# Form 提取器"));
+        assert!(content
+            .value
+            .contains("未在工作空间中找到结构体 `Missing` 的定义"));
+    }
+
+    #[test]
+    fn test_invalid_route_method() {
+        let source = r#"
+#[route("/users", method = "GET", method = "FETCH")]
+async fn users() {}
+"#;
+        let analyzer = HandlerAnalyzer::new();
+        let file = analyzer.analyze(source).unwrap();
+        let route = &file.handlers[0].routes[0];
+        assert_eq!(route.method_literals.len(), 2);
+
+        let diagnostics = analyzer.validate(&file);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("E020".to_string()))
+        );
+        assert_eq!(diagnostics[0].range, route.method_literals[1].range);
+        let fix = QuickFix::from_diagnostic(&diagnostics[0]).unwrap();
+        assert_eq!(fix.new_text, "\"PATCH\"");
+
+        let hover = analyzer
+            .hover(&file, route.method_literals[1].range.start)
+            .unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("Expected markup hover");
+        };
+        assert!(content.value.contains("最接近的是 `PATCH`"));
+        assert!(content.value.contains("- `OPTIONS`"));
+
+        let hover = analyzer
+            .hover(&file, route.method_literals[0].range.start)
+            .unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("Expected markup hover");
+        };
+        assert!(content.value.contains("**当前值**: `GET`"));
+    }
+
+    #[test]
+    fn test_route_conflicts() {
+        let source = r#"
+#[get("/users/{id}")]
+async fn get_user() {}
+
+#[get("/users/me")]
+async fn current_user() {}
+
+#[get("/posts/{id:\\d+}")]
+async fn get_post() {}
+
+#[get("/posts/latest")]
+async fn latest_post() {}
+
+#[post("/users/me")]
+async fn update_current_user() {}
+"#;
+        let analyzer = HandlerAnalyzer::new();
+        let file = analyzer.analyze(source).unwrap();
+        let conflicts: Vec<_> = analyzer
+            .validate(&file)
+            .into_iter()
+            .filter(|d| d.code == Some(NumberOrString::String("W004".to_string())))
+            .collect();
+
+        // 只有未约束的参数与字面量冲突，不同 HTTP 方法不冲突
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].message.contains("'GET /users/me'"));
+        assert!(conflicts[0].message.contains("'get_user'"));
+    }
+
+    #[test]
+    fn test_route_style() {
+        let source = r#"
+#[get("/users")]
+async fn list_users() {}
+
+#[post("/users/")]
+async fn create_user() {}
+
+#[get("/Orders/{orderId}")]
+async fn get_order() {}
+
+#[delete("/orders/{orderId}")]
+async fn delete_order() {}
+"#;
+        let analyzer = HandlerAnalyzer::new();
+        let file = analyzer.analyze(source).unwrap();
+
+        let diagnostics = analyzer.validate_route_style(&file, &RoutesConfig::default());
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("W005".to_string()))
+        );
+        assert_eq!(diagnostics[0].range.start.line, 4);
+        let fix = QuickFix::from_diagnostic(&diagnostics[0]).unwrap();
+        assert_eq!(fix.new_text, "\"/users\"");
+        assert_eq!(
+            diagnostics[1].code,
+            Some(NumberOrString::String("W006".to_string()))
+        );
+        // 路径参数名称保持不变
+        let fix = QuickFix::from_diagnostic(&diagnostics[1]).unwrap();
+        assert_eq!(fix.new_text, "\"/orders/{orderId}\"");
+
+        let config = RoutesConfig {
+            trailing_slash: TrailingSlashPolicy::Always,
+            case: CasePolicy::Ignore,
+            ..Default::default()
+        };
+        let diagnostics = analyzer.validate_route_style(&file, &config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            QuickFix::from_diagnostic(&diagnostics[0]).unwrap().new_text,
+            "\"/users/\""
+        );
+    }
+
+    #[test]
+    fn test_stub_handlers() {
+        let source = r#"
+#[get("/users")]
+async fn list_users() -> Json<Vec<User>> {
+    todo!()
+}
+
+#[post("/users")]
+async fn create_user() -> impl IntoResponse {
+    (StatusCode::NOT_IMPLEMENTED, "coming soon")
+}
+
+#[delete("/users/{id}")]
+async fn delete_user() -> Result<StatusCode> {
+    unimplemented!("soft delete");
+}
+
+#[get("/health")]
+async fn health() -> &'static str {
+    tracing::debug!("health");
+    todo!()
+}
+"#;
+        let analyzer = HandlerAnalyzer::new();
+        let file = analyzer.analyze(source).unwrap();
+        let stubs: Vec<_> = file.handlers.iter().map(|h| h.stub).collect();
+        assert_eq!(stubs, vec![true, true, true, false]);
+
+        let diagnostics = analyzer.validate_stubs(&file);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("W007".to_string()))
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "处理器 'list_users' 尚未实现（GET /users）"
+        );
+    }
+
+    #[test]
+    fn test_primitive_type_detection() {
+        assert!(is_primitive_type("u32"));
+        assert!(is_primitive_type("String"));
+        assert!(is_primitive_type("std::string::String"));
+        assert!(is_primitive_type("&str"));
+        assert!(is_primitive_type("(String, u32)"));
+        assert!(!is_primitive_type("SearchParams"));
+        assert!(!is_primitive_type("HashMap<String, String>"));
+    }
+}
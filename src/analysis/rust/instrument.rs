@@ -0,0 +1,223 @@
+//! 追踪属性模块
+//!
+//! 读取处理器函数上的 `#[instrument]`（或 `#[tracing::instrument]`），计算实际生效的 span 名称、
+//! 级别和记录的字段，使路由悬停提示和路由列表能同时显示可观测性信息：
+//! - span 名称为 `name = "..."` 指定的名称，未指定时为函数名
+//! - 级别为 `level = ...` 指定的级别，未指定时为 `info`
+//! - 记录的字段为未被 `skip(...)` 跳过的参数（`skip_all` 跳过所有参数），
+//!   加上 `fields(...)` 中声明的字段
+
+use proc_macro2::{TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
+use syn::punctuated::Punctuated;
+use syn::{Meta, Token};
+
+use crate::analysis::rust::cfg::expand_cfg_attrs;
+
+/// `#[instrument]` 生成的 span 信息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstrumentInfo {
+    /// span 名称
+    pub span_name: String,
+    /// span 级别（小写，如 `info`）
+    pub level: String,
+    /// 记录的字段（函数参数在前，`fields(...)` 中声明的字段在后）
+    pub fields: Vec<String>,
+}
+
+/// 函数上 `#[instrument]` 生成的 span 信息，函数没有 `#[instrument]` 时返回 `None`
+///
+/// 无法解析的参数会被忽略，按 `#[instrument]` 的默认行为计算
+pub fn instrument_info(item_fn: &syn::ItemFn) -> Option<InstrumentInfo> {
+    let (attr, _) = expand_cfg_attrs(&item_fn.attrs)
+        .into_iter()
+        .find(|(attr, _)| {
+            attr.path()
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "instrument")
+        })?;
+
+    let args = match &attr.meta {
+        Meta::List(list) => list
+            .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .map(|args| args.into_iter().collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    let mut span_name = item_fn.sig.ident.to_string();
+    let mut level = "info".to_string();
+    let mut skip_all = false;
+    let mut skipped: Vec<String> = Vec::new();
+    let mut declared: Vec<String> = Vec::new();
+    for arg in &args {
+        match arg {
+            Meta::NameValue(name_value) if name_value.path.is_ident("name") => {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit),
+                    ..
+                }) = &name_value.value
+                {
+                    span_name = lit.value();
+                }
+            }
+            Meta::NameValue(name_value) if name_value.path.is_ident("level") => {
+                if let Some(value) = level_name(&name_value.value) {
+                    level = value;
+                }
+            }
+            Meta::Path(path) if path.is_ident("skip_all") => skip_all = true,
+            Meta::List(list) if list.path.is_ident("skip") => {
+                skipped.extend(
+                    list.parse_args_with(Punctuated::<syn::Ident, Token![,]>::parse_terminated)
+                        .map(|idents| idents.iter().map(ToString::to_string).collect())
+                        .unwrap_or_else(|_| Vec::new()),
+                );
+            }
+            Meta::List(list) if list.path.is_ident("fields") => {
+                declared.extend(field_names(&list.tokens));
+            }
+            _ => {}
+        }
+    }
+
+    let mut fields = Vec::new();
+    if !skip_all {
+        for input in &item_fn.sig.inputs {
+            match input {
+                syn::FnArg::Typed(pat_type) => pat_idents(&pat_type.pat, &mut fields),
+                syn::FnArg::Receiver(_) => fields.push("self".to_string()),
+            }
+        }
+        fields.retain(|field| !skipped.contains(field));
+    }
+    fields.extend(declared);
+
+    Some(InstrumentInfo {
+        span_name,
+        level,
+        fields,
+    })
+}
+
+/// `level = "debug"`、`level = Level::DEBUG` 或 `level = 2` 对应的级别名称
+fn level_name(value: &syn::Expr) -> Option<String> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Some(lit.value().to_lowercase()),
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => {
+            let name = match lit.base10_parse::<u8>().ok()? {
+                1 => "trace",
+                2 => "debug",
+                3 => "info",
+                4 => "warn",
+                5 => "error",
+                _ => return None,
+            };
+            Some(name.to_string())
+        }
+        syn::Expr::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string().to_lowercase()),
+        _ => None,
+    }
+}
+
+/// `fields(...)` 中声明的字段名称
+///
+/// 支持 `user.id = ...`、`"user.id" = ...`、只有名称的空字段和 `%id`、`?id` 简写
+fn field_names(tokens: &TokenStream) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut name = String::new();
+    let mut in_value = false;
+    for token in tokens.clone().into_iter().chain(std::iter::once(comma())) {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => {
+                if !name.is_empty() {
+                    names.push(std::mem::take(&mut name));
+                }
+                in_value = false;
+            }
+            _ if in_value => {}
+            TokenTree::Punct(punct) if punct.as_char() == '=' => in_value = true,
+            TokenTree::Punct(punct) if matches!(punct.as_char(), '%' | '?') && name.is_empty() => {}
+            TokenTree::Literal(literal) => name.push_str(literal.to_string().trim_matches('"')),
+            _ => name.push_str(&token.to_string()),
+        }
+    }
+    names
+}
+
+fn comma() -> TokenTree {
+    TokenTree::Punct(proc_macro2::Punct::new(',', proc_macro2::Spacing::Alone))
+}
+
+/// 参数模式中绑定的变量名（如 `Path(id)` 中的 `id`）
+fn pat_idents(pat: &syn::Pat, idents: &mut Vec<String>) {
+    match pat {
+        syn::Pat::Ident(pat_ident) => idents.push(pat_ident.ident.to_string()),
+        syn::Pat::Reference(reference) => pat_idents(&reference.pat, idents),
+        syn::Pat::Type(pat_type) => pat_idents(&pat_type.pat, idents),
+        syn::Pat::Tuple(tuple) => tuple.elems.iter().for_each(|p| pat_idents(p, idents)),
+        syn::Pat::TupleStruct(tuple) => tuple.elems.iter().for_each(|p| pat_idents(p, idents)),
+        syn::Pat::Struct(pat_struct) => pat_struct
+            .fields
+            .iter()
+            .for_each(|field| pat_idents(&field.pat, idents)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instrument(source: &str) -> Option<InstrumentInfo> {
+        let item_fn: syn::ItemFn = syn::parse_str(source).unwrap();
+        instrument_info(&item_fn)
+    }
+
+    #[test]
+    fn test_default_instrument() {
+        let info = instrument(
+            "#[tracing::instrument]\nasync fn get_user(Path(id): Path<i64>, Query(SearchParams { page, .. }): Query<SearchParams>) {}",
+        )
+        .unwrap();
+        assert_eq!(info.span_name, "get_user");
+        assert_eq!(info.level, "info");
+        assert_eq!(info.fields, vec!["id", "page"]);
+
+        assert!(instrument("#[get(\"/\")]\nasync fn index() {}").is_none());
+    }
+
+    #[test]
+    fn test_instrument_arguments() {
+        let info = instrument(
+            r#"#[instrument(name = "users.create", level = Level::DEBUG, skip(db), err, fields(user.email = %user.email, "tenant.id", ?request_id))]
+async fn create(Component(db): Component<Db>, Json(user): Json<NewUser>) {}"#,
+        )
+        .unwrap();
+        assert_eq!(info.span_name, "users.create");
+        assert_eq!(info.level, "debug");
+        assert_eq!(
+            info.fields,
+            vec!["user", "user.email", "tenant.id", "request_id"]
+        );
+
+        let info = instrument(
+            "#[cfg_attr(feature = \"trace\", instrument(skip_all, level = \"warn\"))]\nasync fn health(State(state): State<AppState>) {}",
+        )
+        .unwrap();
+        assert_eq!(info.level, "warn");
+        assert!(info.fields.is_empty());
+    }
+}
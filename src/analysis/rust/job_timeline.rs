@@ -0,0 +1,234 @@
+//! 任务调度时间线模块
+//!
+//! `#[fix_delay(N)]` 和 `#[fix_rate(N)]` 的区别在于间隔的计算起点：
+//! - `fix_delay`：上一次执行**完成**后等待 N 秒再开始下一次，执行耗时会推迟后续所有执行
+//! - `fix_rate`：每隔 N 秒**开始**一次执行，与执行耗时无关；执行耗时超过 N 秒时执行会堆积
+//!
+//! 悬停提示按宏参数生成前三次执行的时间线。任务体中的 `sleep(Duration::from_secs(..))`
+//! 等固定等待（[`min_body_duration`]）是每次执行耗时的下限，用于绘制执行时长并判断是否超过频率。
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
+
+use crate::analysis::rust::macro_analyzer::JobMacro;
+use crate::core::messages::{localized, Locale};
+
+/// 时间线中绘制的执行次数
+const TIMELINE_RUNS: u64 = 3;
+
+/// 每个调度周期在时间线中占用的字符数
+const CYCLE_WIDTH: u64 = 10;
+
+/// 任务体中固定等待的总时长（毫秒），没有可识别的等待时返回 `None`
+///
+/// 识别以整数字面量构造的 `Duration::from_secs(..)` / `Duration::from_millis(..)` 作为参数的
+/// `sleep(...)` 调用（`tokio::time::sleep`、`std::thread::sleep` 等），多个等待依次累加
+pub fn min_body_duration(item_fn: &syn::ItemFn) -> Option<u64> {
+    let total = sleep_millis(item_fn.block.to_token_stream());
+    (total > 0).then_some(total)
+}
+
+fn sleep_millis(tokens: TokenStream) -> u64 {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut total = 0;
+    let mut i = 0;
+    while i < tokens.len() {
+        match (&tokens[i], tokens.get(i + 1)) {
+            (TokenTree::Ident(ident), Some(TokenTree::Group(args)))
+                if ident == "sleep" && args.delimiter() == Delimiter::Parenthesis =>
+            {
+                total += duration_millis(args.stream()).unwrap_or(0);
+                i += 1;
+            }
+            // 代码块、闭包等分组中的等待
+            (TokenTree::Group(group), _) => total += sleep_millis(group.stream()),
+            _ => {}
+        }
+        i += 1;
+    }
+    total
+}
+
+/// `Duration::from_secs(N)` / `Duration::from_millis(N)` 表示的毫秒数
+fn duration_millis(tokens: TokenStream) -> Option<u64> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    tokens.windows(2).find_map(|pair| {
+        let (TokenTree::Ident(ident), TokenTree::Group(args)) = (&pair[0], &pair[1]) else {
+            return None;
+        };
+        let unit = match ident.to_string().as_str() {
+            "from_secs" => 1000,
+            "from_millis" => 1,
+            _ => return None,
+        };
+        let value: u64 = args
+            .stream()
+            .to_string()
+            .trim()
+            .replace('_', "")
+            .parse()
+            .ok()?;
+        Some(value * unit)
+    })
+}
+
+/// `#[fix_delay]` / `#[fix_rate]` 悬停提示中的调度时间线（Markdown），其他任务宏返回 `None`
+///
+/// `min_duration` 为每次执行耗时的下限（毫秒，见 [`min_body_duration`]），未知时按间隔的一半示意
+pub fn schedule_timeline(
+    job: &JobMacro,
+    min_duration: Option<u64>,
+    locale: Locale,
+) -> Option<String> {
+    let (seconds, fixed_rate) = match job {
+        JobMacro::FixDelay { seconds, .. } => (*seconds, false),
+        JobMacro::FixRate { seconds, .. } => (*seconds, true),
+        _ => return None,
+    };
+    let interval = seconds * 1000;
+    let duration = min_duration.unwrap_or((interval / 2).max(1));
+    // fix_rate 的周期就是频率；fix_delay 的周期是执行耗时加上延迟
+    let period = if fixed_rate {
+        interval.max(1)
+    } else {
+        (duration + interval).max(1)
+    };
+    let starts: Vec<u64> = (0..TIMELINE_RUNS).map(|run| run * period).collect();
+
+    let column = |millis: u64| (millis * CYCLE_WIDTH / period) as usize;
+    let width = column(TIMELINE_RUNS * period);
+    let mut bar = vec!['·'; width];
+    let mut markers = vec![' '; width];
+    let mut labels = vec![' '; width];
+    for start in &starts {
+        let from = column(*start);
+        let to = column(start + duration).clamp(from + 1, width);
+        bar[from..to].fill('█');
+        markers[from] = '↑';
+        for (offset, c) in format_seconds(*start).chars().enumerate() {
+            if let Some(slot) = labels.get_mut(from + offset) {
+                *slot = c;
+            }
+        }
+    }
+
+    let mut timeline = match min_duration {
+        Some(duration) => localized(
+            locale,
+            "timeline.title.measured",
+            &[&format_seconds(duration)],
+        ),
+        None => localized(
+            locale,
+            "timeline.title.assumed",
+            &[&format_seconds(duration)],
+        ),
+    };
+    timeline.push_str(":\n\n```text\n");
+    for row in [&labels, &markers, &bar] {
+        timeline.push_str(row.iter().collect::<String>().trim_end());
+        timeline.push('\n');
+    }
+    timeline.push_str("```\n\n");
+    timeline.push_str(&localized(locale, "timeline.legend", &[]));
+
+    if fixed_rate {
+        timeline.push_str(&localized(
+            locale,
+            "timeline.fix-rate",
+            &[&format_seconds(interval)],
+        ));
+        if min_duration.is_some_and(|duration| duration >= interval) {
+            timeline.push_str(&localized(
+                locale,
+                "timeline.fix-rate.overlap",
+                &[
+                    &format_seconds(duration),
+                    &format_seconds(interval),
+                    &seconds,
+                ],
+            ));
+        }
+    } else {
+        timeline.push_str(&localized(
+            locale,
+            "timeline.fix-delay",
+            &[&format_seconds(interval)],
+        ));
+    }
+
+    Some(timeline)
+}
+
+/// 毫秒数的显示文本（`30s`、`1.5s`）
+fn format_seconds(millis: u64) -> String {
+    if millis.is_multiple_of(1000) {
+        format!("{}s", millis / 1000)
+    } else {
+        format!("{}s", millis as f64 / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Range;
+
+    fn item_fn(source: &str) -> syn::ItemFn {
+        syn::parse_str(source).unwrap()
+    }
+
+    #[test]
+    fn test_min_body_duration() {
+        let job = item_fn(
+            "async fn job() {
+                tokio::time::sleep(Duration::from_secs(3)).await;
+                if ready { std::thread::sleep(std::time::Duration::from_millis(1_500)); }
+                let timeout = Duration::from_secs(60);
+            }",
+        );
+        assert_eq!(min_body_duration(&job), Some(4500));
+
+        let job = item_fn("async fn job() { sleep(delay).await; }");
+        assert_eq!(min_body_duration(&job), None);
+    }
+
+    #[test]
+    fn test_fix_rate_timeline() {
+        let job = JobMacro::FixRate {
+            seconds: 10,
+            range: Range::default(),
+        };
+        let timeline = schedule_timeline(&job, None, Locale::default()).unwrap();
+        assert!(timeline.contains("（假设每次执行耗时 5s）"));
+        assert!(timeline.contains(
+            "0s        10s       20s\n↑         ↑         ↑\n█████·····█████·····█████·····\n"
+        ));
+        assert!(!timeline.contains("⚠️"));
+
+        // 执行耗时不短于频率时给出警告
+        let timeline = schedule_timeline(&job, Some(12_000), Locale::default()).unwrap();
+        assert!(timeline.contains("⚠️ 任务体中的等待至少需要 12s"));
+        assert!(timeline.contains("`#[fix_delay(10)]`"));
+    }
+
+    #[test]
+    fn test_fix_delay_timeline() {
+        let job = JobMacro::FixDelay {
+            seconds: 5,
+            range: Range::default(),
+        };
+        let timeline = schedule_timeline(&job, Some(1500), Locale::default()).unwrap();
+        assert!(timeline.contains("（任务体中的固定等待至少 1.5s）"));
+        // 周期为 1.5s + 5s
+        assert!(timeline.contains("0s        6.5s      13s\n"));
+        assert!(timeline.contains("██········██········██········\n"));
+        assert!(!timeline.contains("⚠️"));
+
+        let job = JobMacro::OneShot {
+            seconds: 5,
+            range: Range::default(),
+        };
+        assert!(schedule_timeline(&job, None, Locale::default()).is_none());
+    }
+}
@@ -1,9 +1,25 @@
 //! Rust 宏分析模块
+//!
+//! 提取的宏按文件内容的哈希缓存：重新打开或重新验证未修改的文件时不再调用 `syn` 解析。
+//! 缓存最多保留 [`MACRO_CACHE_CAPACITY`] 个文件，超出时淘汰最久未使用的条目。
 
 use lsp_types::{Range, Url};
-use syn::__private::Span;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
 use syn::spanned::Spanned;
 
+use crate::analysis::diagnostic::QuickFix;
+use crate::analysis::rust::cfg::{cfg_condition, expand_cfg_attrs};
+use crate::analysis::rust::handler_analyzer::is_stub_body;
+use crate::analysis::rust::instrument::{instrument_info, InstrumentInfo};
+use crate::analysis::rust::job_timeline;
+use crate::analysis::rust::timezone;
+use crate::core::document::{LineIndex, PositionEncoding, PositionMapper};
+use crate::core::messages::{localized, Locale};
+
 /// Rust 文档模型
 #[derive(Debug, Clone)]
 pub struct RustDocument {
@@ -15,6 +31,24 @@ pub struct RustDocument {
     pub macros: Vec<SpringMacro>,
 }
 
+/// spring/expandAllMacros 请求参数
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpandAllMacrosRequest {
+    /// 要展开的 Rust 文件
+    pub text_document: lsp_types::TextDocumentIdentifier,
+}
+
+/// spring/expandAllMacros 响应
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpandAllMacrosResponse {
+    /// 展开后的文件内容
+    pub content: String,
+    /// 展开的宏数量
+    pub expanded: usize,
+}
+
 /// Spring-rs 宏枚举
 #[derive(Debug, Clone)]
 pub enum SpringMacro {
@@ -32,6 +66,47 @@ pub enum SpringMacro {
     Job(JobMacro),
 }
 
+impl SpringMacro {
+    /// 宏在源代码中的位置
+    pub fn range(&self) -> Range {
+        match self {
+            SpringMacro::DeriveService(service) => service.range,
+            SpringMacro::Component(component) => component.range,
+            SpringMacro::Inject(inject) => inject.range,
+            SpringMacro::AutoConfig(auto_config) => auto_config.range,
+            SpringMacro::Route(route) => route.range,
+            SpringMacro::Job(
+                JobMacro::Cron { range, .. }
+                | JobMacro::FixDelay { range, .. }
+                | JobMacro::FixRate { range, .. }
+                | JobMacro::OneShot { range, .. },
+            ) => *range,
+        }
+    }
+
+    /// 宏的名称（如 `Service 派生宏`）
+    pub fn title(&self, locale: Locale) -> String {
+        let key = match self {
+            SpringMacro::DeriveService(_) => "macro.service",
+            SpringMacro::Component(_) => "macro.component",
+            SpringMacro::Inject(_) => "macro.inject",
+            SpringMacro::AutoConfig(_) => "macro.auto-config",
+            SpringMacro::Route(_) => "macro.route",
+            SpringMacro::Job(_) => "macro.job",
+        };
+        localized(locale, key, &[])
+    }
+
+    /// 宏所在的处理器或服务是否标注了 `#[deprecated]`
+    pub fn is_deprecated(&self) -> bool {
+        match self {
+            SpringMacro::DeriveService(service) => service.deprecated,
+            SpringMacro::Route(route) => route.deprecated,
+            _ => false,
+        }
+    }
+}
+
 /// Service 派生宏信息
 #[derive(Debug, Clone)]
 pub struct ServiceMacro {
@@ -41,6 +116,12 @@ pub struct ServiceMacro {
     pub fields: Vec<Field>,
     /// 宏在源代码中的位置
     pub range: Range,
+    /// 结构体上的 `///` 文档注释
+    pub doc: Option<String>,
+    /// 结构体是否标注了 `#[deprecated]`
+    pub deprecated: bool,
+    /// 结构体上 `#[cfg(...)]` 的条件（如 `feature = "redis"`）
+    pub cfg: Option<String>,
 }
 
 /// Component 属性宏信息
@@ -54,10 +135,14 @@ pub struct ComponentMacro {
     pub dependencies: Vec<ComponentDependency>,
     /// 插件名称（可选，从 #[component(name = "...")] 提取）
     pub plugin_name: Option<String>,
+    /// 插件名称值在源代码中的位置
+    pub plugin_name_range: Option<Range>,
     /// 是否是异步函数
     pub is_async: bool,
     /// 宏在源代码中的位置
     pub range: Range,
+    /// 函数上 `#[cfg(...)]` 的条件（如 `feature = "redis"`）
+    pub cfg: Option<String>,
 }
 
 /// 组件依赖信息
@@ -67,6 +152,8 @@ pub struct ComponentDependency {
     pub dep_type: DependencyType,
     /// 类型名称
     pub type_name: String,
+    /// 参数类型在源代码中的位置
+    pub range: Range,
 }
 
 /// 依赖类型
@@ -87,6 +174,8 @@ pub struct Field {
     pub type_name: String,
     /// 注入宏（如果有）
     pub inject: Option<InjectMacro>,
+    /// 字段名在源代码中的位置
+    pub range: Range,
 }
 
 /// Inject 属性宏信息
@@ -98,6 +187,8 @@ pub struct InjectMacro {
     pub component_name: Option<String>,
     /// 宏在源代码中的位置
     pub range: Range,
+    /// 组件名称值（`component = "..."` 中 `=` 之后的部分）在源代码中的位置
+    pub name_range: Option<Range>,
 }
 
 /// 注入类型
@@ -118,6 +209,19 @@ pub struct AutoConfigMacro {
     pub range: Range,
 }
 
+impl AutoConfigMacro {
+    /// 配置器类型名列表（去掉模块路径），如 `WebConfigurator, spring_job::JobConfigurator`
+    /// 得到 `["WebConfigurator", "JobConfigurator"]`
+    pub fn configurators(&self) -> Vec<&str> {
+        self.configurator_type
+            .split(',')
+            .filter_map(|configurator| configurator.rsplit("::").next())
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+}
+
 /// 路由宏信息
 #[derive(Debug, Clone)]
 pub struct RouteMacro {
@@ -133,6 +237,16 @@ pub struct RouteMacro {
     pub is_openapi: bool,
     /// 宏在源代码中的位置
     pub range: Range,
+    /// 处理器函数上的 `///` 文档注释
+    pub doc: Option<String>,
+    /// 处理器函数是否标注了 `#[deprecated]`
+    pub deprecated: bool,
+    /// 处理器函数体是否只有 `todo!()`、`unimplemented!()` 等未实现的占位代码
+    pub stub: bool,
+    /// 路由宏通过 `#[cfg_attr(条件, ...)]` 应用时的启用条件（如 `feature = "web"`）
+    pub cfg: Option<String>,
+    /// 处理器函数上 `#[instrument]` 生成的 span 信息
+    pub instrument: Option<InstrumentInfo>,
 }
 
 /// HTTP 方法
@@ -180,6 +294,60 @@ impl HttpMethod {
             HttpMethod::Trace => "TRACE",
         }
     }
+
+    /// 方法说明
+    pub fn description(&self, locale: Locale) -> String {
+        localized(
+            locale,
+            &format!("http-method.{}", self.as_str().to_lowercase()),
+            &[],
+        )
+    }
+
+    /// 与字符串最接近的 HTTP 方法（按编辑距离，不区分大小写，距离相同时取靠前的方法）
+    ///
+    /// 用于为无法解析的方法（如 `FETCH`）给出替换建议
+    pub fn closest(s: &str) -> Self {
+        let upper = s.to_uppercase();
+        HTTP_METHODS
+            .iter()
+            .min_by_key(|method| edit_distance(&upper, method.as_str()))
+            .cloned()
+            .unwrap_or(HttpMethod::Get)
+    }
+}
+
+/// 所有 HTTP 方法（按常用程度排列）
+pub const HTTP_METHODS: &[HttpMethod] = &[
+    HttpMethod::Get,
+    HttpMethod::Post,
+    HttpMethod::Put,
+    HttpMethod::Delete,
+    HttpMethod::Patch,
+    HttpMethod::Head,
+    HttpMethod::Options,
+    HttpMethod::Connect,
+    HttpMethod::Trace,
+];
+
+/// 两个字符串的编辑距离（Levenshtein 距离）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                previous.min(current).min(row[j]) + 1
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
 }
 
 /// 任务调度宏信息
@@ -189,6 +357,8 @@ pub enum JobMacro {
     Cron {
         /// Cron 表达式
         expression: String,
+        /// 计算执行时间使用的时区（IANA 时区名称，如 `Asia/Shanghai`），未指定时使用 UTC
+        timezone: Option<String>,
         /// 宏在源代码中的位置
         range: Range,
     },
@@ -206,15 +376,227 @@ pub enum JobMacro {
         /// 宏在源代码中的位置
         range: Range,
     },
+    /// 一次性任务（启动后延迟指定秒数执行一次）
+    OneShot {
+        /// 延迟秒数
+        seconds: u64,
+        /// 宏在源代码中的位置
+        range: Range,
+    },
+}
+
+/// 属性列表中是否包含 `#[deprecated]`（包括 `#[deprecated = "..."]` 和 `#[deprecated(note = "...")]`）
+pub fn is_deprecated(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident("deprecated"))
+}
+
+/// 属性参数中第一个 `=` 之后的值（如 `component = "db"` 中的 `"db"`）的位置范围
+fn assigned_value_range(
+    tokens: &proc_macro2::TokenStream,
+    lines: &PositionMapper,
+) -> Option<Range> {
+    let mut tokens = tokens.clone().into_iter();
+    tokens.find(
+        |token| matches!(token, proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '='),
+    )?;
+    tokens.next().map(|value| lines.span_range(value.span()))
+}
+
+/// 类型名（去掉模块路径和泛型参数）是否像配置类型（以 `Config` 结尾）
+fn is_config_type(type_name: &str) -> bool {
+    let base = type_name.split('<').next().unwrap_or(type_name);
+    base.rsplit("::")
+        .next()
+        .unwrap_or(base)
+        .trim()
+        .ends_with("Config")
+}
+
+/// 属性列表中是否有包含 Service 的 `#[derive(...)]`
+fn derives_service(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("derive")
+            && attr
+                .meta
+                .require_list()
+                .is_ok_and(|list| list.tokens.to_string().contains("Service"))
+    })
+}
+
+/// 属性列表中的 `#[inject]` 属性
+fn inject_attrs(attrs: &[syn::Attribute]) -> impl Iterator<Item = &syn::Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("inject"))
+}
+
+/// 宏缓存默认保留的文件数
+pub const MACRO_CACHE_CAPACITY: usize = 256;
+
+/// 单个文件的缓存条目
+struct CachedMacros {
+    /// 文件内容长度（哈希冲突时的额外校验）
+    len: usize,
+    /// 提取的宏
+    macros: Vec<SpringMacro>,
+    /// 最近一次使用的序号，用于淘汰最久未使用的条目
+    last_used: u64,
+}
+
+/// 按内容哈希缓存的宏提取结果
+#[derive(Default)]
+struct MacroCache {
+    entries: HashMap<u64, CachedMacros>,
+    /// 使用序号
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl MacroCache {
+    /// 查找缓存的宏，命中时更新使用序号
+    fn get(&mut self, hash: u64, len: usize) -> Option<Vec<SpringMacro>> {
+        self.tick += 1;
+        let tick = self.tick;
+        match self.entries.get_mut(&hash) {
+            Some(entry) if entry.len == len => {
+                entry.last_used = tick;
+                self.hits += 1;
+                Some(entry.macros.clone())
+            }
+            _ => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// 是否缓存了该内容（不计入命中统计）
+    fn contains(&self, hash: u64, len: usize) -> bool {
+        self.entries
+            .get(&hash)
+            .is_some_and(|entry| entry.len == len)
+    }
+
+    /// 加入缓存，超出容量时淘汰最久未使用的条目
+    fn insert(&mut self, hash: u64, len: usize, macros: Vec<SpringMacro>, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        while self.entries.len() >= capacity && !self.entries.contains_key(&hash) {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| *key)
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.tick += 1;
+        self.entries.insert(
+            hash,
+            CachedMacros {
+                len,
+                macros,
+                last_used: self.tick,
+            },
+        );
+    }
+}
+
+/// 宏缓存统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MacroCacheStats {
+    /// 缓存命中次数
+    pub hits: u64,
+    /// 缓存未命中次数
+    pub misses: u64,
+    /// 当前缓存的文件数
+    pub entries: usize,
+}
+
+impl MacroCacheStats {
+    /// 缓存命中率（0.0 - 1.0）
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// 文件内容的哈希
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
 }
 
 /// 宏分析器
-pub struct MacroAnalyzer;
+pub struct MacroAnalyzer {
+    /// 按内容哈希缓存的宏提取结果
+    cache: Mutex<MacroCache>,
+    /// 缓存保留的最大文件数（0 表示不缓存）
+    cache_capacity: usize,
+    /// 宏位置使用的位置编码
+    encoding: PositionEncoding,
+    /// 悬停提示、展开代码和诊断的语言
+    locale: Locale,
+}
 
 impl MacroAnalyzer {
     /// 创建新的宏分析器
     pub fn new() -> Self {
-        Self
+        Self::with_cache_capacity(MACRO_CACHE_CAPACITY)
+    }
+
+    /// 创建缓存最多保留 `capacity` 个文件的宏分析器（0 表示不缓存）
+    pub fn with_cache_capacity(capacity: usize) -> Self {
+        Self {
+            cache: Mutex::new(MacroCache::default()),
+            cache_capacity: capacity,
+            encoding: PositionEncoding::default(),
+            locale: Locale::default(),
+        }
+    }
+
+    /// 使用与客户端协商的位置编码计算宏的位置
+    pub fn with_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// 位置编码
+    pub fn encoding(&self) -> PositionEncoding {
+        self.encoding
+    }
+
+    /// 使用指定的语言生成悬停提示、展开代码和诊断
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 消息语言
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// 宏缓存统计
+    pub fn cache_stats(&self) -> MacroCacheStats {
+        let cache = self.cache.lock().unwrap();
+        MacroCacheStats {
+            hits: cache.hits,
+            misses: cache.misses,
+            entries: cache.entries.len(),
+        }
+    }
+
+    /// 清空宏缓存
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().entries.clear();
     }
 
     /// 为宏提供悬停提示
@@ -235,7 +617,7 @@ impl MacroAnalyzer {
             SpringMacro::Inject(inject) => self.hover_inject_macro(inject),
             SpringMacro::AutoConfig(auto_config) => self.hover_auto_config_macro(auto_config),
             SpringMacro::Route(route) => self.hover_route_macro(route),
-            SpringMacro::Job(job) => self.hover_job_macro(job),
+            SpringMacro::Job(job) => self.hover_job(job, None),
         }
     }
 
@@ -246,39 +628,53 @@ impl MacroAnalyzer {
         let mut hover = String::new();
 
         // 添加标题
-        hover.push_str("# Component 属性宏\n\n");
+        hover.push_str(&localized(self.locale, "hover.component.title", &[]));
 
         // 添加说明
-        hover.push_str("自动将组件创建函数转换为 Plugin 实现，无需手动实现 Plugin trait。\n\n");
+        hover.push_str(&localized(self.locale, "hover.component.description", &[]));
 
         // 添加函数信息
-        hover.push_str(&format!("**函数**: `{}`\n\n", component.function_name));
-        hover.push_str(&format!("**组件类型**: `{}`\n\n", component.component_type));
+        hover.push_str(&localized(
+            self.locale,
+            "hover.component.function",
+            &[&component.function_name],
+        ));
+        hover.push_str(&localized(
+            self.locale,
+            "hover.component.type",
+            &[&component.component_type],
+        ));
 
         if let Some(name) = &component.plugin_name {
-            hover.push_str(&format!("**插件名称**: `\"{}\"`\n\n", name));
+            hover.push_str(&localized(
+                self.locale,
+                "hover.component.plugin-name",
+                &[&name],
+            ));
         }
 
-        hover.push_str(&format!(
-            "**异步**: {}\n\n",
-            if component.is_async { "是" } else { "否" }
-        ));
+        let key = if component.is_async {
+            "hover.component.async"
+        } else {
+            "hover.component.sync"
+        };
+        hover.push_str(&localized(self.locale, key, &[]));
 
         // 添加依赖信息
         if !component.dependencies.is_empty() {
-            hover.push_str("**依赖**:\n\n");
+            hover.push_str(&localized(self.locale, "hover.component.dependencies", &[]));
             for dep in &component.dependencies {
-                let dep_type_str = match dep.dep_type {
-                    DependencyType::Config => "配置",
-                    DependencyType::Component => "组件",
+                let key = match dep.dep_type {
+                    DependencyType::Config => "hover.component.config-dependency",
+                    DependencyType::Component => "hover.component.component-dependency",
                 };
-                hover.push_str(&format!("- `{}` - {}\n", dep.type_name, dep_type_str));
+                hover.push_str(&localized(self.locale, key, &[&dep.type_name]));
             }
             hover.push('\n');
         }
 
         // 添加展开后的代码
-        hover.push_str("**展开后的代码**:\n\n");
+        hover.push_str(&localized(self.locale, "hover.expanded", &[]));
         hover.push_str("```rust\n");
         hover.push_str(&self.expand_component_macro(component));
         hover.push_str("```\n");
@@ -290,43 +686,79 @@ impl MacroAnalyzer {
     ///
     /// 显示 Service 宏的说明和生成的 trait 实现代码
     fn hover_service_macro(&self, service: &ServiceMacro) -> String {
+        self.hover_service(service, &HashMap::new())
+    }
+
+    /// 为 Service 宏提供悬停提示，字段类型在 `type_docs` 中有文档摘要时附在字段后面
+    ///
+    /// `type_docs` 为类型名到文档注释摘要的映射（见 [`MacroAnalyzer::type_doc_summaries`]）
+    pub fn hover_service(
+        &self,
+        service: &ServiceMacro,
+        type_docs: &HashMap<String, String>,
+    ) -> String {
         let mut hover = String::new();
 
         // 添加标题
-        hover.push_str("# Service 派生宏\n\n");
+        hover.push_str(&localized(self.locale, "hover.service.title", &[]));
 
         // 添加说明
-        hover.push_str("自动为结构体实现依赖注入功能，从应用上下文中获取组件和配置。\n\n");
+        hover.push_str(&localized(self.locale, "hover.service.description", &[]));
 
         // 添加结构体信息
-        hover.push_str(&format!("**结构体**: `{}`\n\n", service.struct_name));
+        hover.push_str(&localized(
+            self.locale,
+            "hover.service.struct",
+            &[&service.struct_name],
+        ));
+
+        if service.deprecated {
+            hover.push_str(&localized(self.locale, "hover.deprecated", &[]));
+        }
+
+        // 添加开发者的文档注释
+        if let Some(doc) = &service.doc {
+            hover.push_str(doc);
+            hover.push_str("\n\n");
+        }
 
         // 添加字段信息
         if !service.fields.is_empty() {
-            hover.push_str("**注入字段**:\n\n");
+            hover.push_str(&localized(self.locale, "hover.service.fields", &[]));
             for field in &service.fields {
                 hover.push_str(&format!("- `{}`: `{}`", field.name, field.type_name));
                 if let Some(inject) = &field.inject {
                     match inject.inject_type {
                         InjectType::Component => {
                             if let Some(name) = &inject.component_name {
-                                hover.push_str(&format!(" - 注入组件 `\"{}\"`", name));
+                                hover.push_str(&localized(
+                                    self.locale,
+                                    "hover.service.named-component",
+                                    &[&name],
+                                ));
                             } else {
-                                hover.push_str(" - 注入组件");
+                                hover.push_str(&localized(
+                                    self.locale,
+                                    "hover.service.component",
+                                    &[],
+                                ));
                             }
                         }
                         InjectType::Config => {
-                            hover.push_str(" - 注入配置");
+                            hover.push_str(&localized(self.locale, "hover.service.config", &[]));
                         }
                     }
                 }
+                if let Some(summary) = type_docs.get(&field.type_name) {
+                    hover.push_str(&format!(" — {}", summary));
+                }
                 hover.push('\n');
             }
             hover.push('\n');
         }
 
         // 添加展开后的代码
-        hover.push_str("**展开后的代码**:\n\n");
+        hover.push_str(&localized(self.locale, "hover.expanded", &[]));
         hover.push_str("```rust\n");
         hover.push_str(&self.expand_service_macro(service));
         hover.push_str("```\n");
@@ -341,39 +773,49 @@ impl MacroAnalyzer {
         let mut hover = String::new();
 
         // 添加标题
-        hover.push_str("# Inject 属性宏\n\n");
+        hover.push_str(&localized(self.locale, "hover.inject.title", &[]));
 
         // 添加说明
-        hover.push_str("标记字段从应用上下文中自动注入依赖。\n\n");
+        hover.push_str(&localized(self.locale, "hover.inject.description", &[]));
 
         // 添加注入类型信息
         match inject.inject_type {
             InjectType::Component => {
-                hover.push_str("**注入类型**: 组件 (Component)\n\n");
-                hover.push_str("从应用上下文中获取已注册的组件实例。\n\n");
+                hover.push_str(&localized(self.locale, "hover.inject.component", &[]));
+                hover.push_str(&localized(
+                    self.locale,
+                    "hover.inject.component-description",
+                    &[],
+                ));
 
                 if let Some(name) = &inject.component_name {
-                    hover.push_str(&format!("**组件名称**: `\"{}\"`\n\n", name));
-                    hover.push_str("使用指定名称查找组件，适用于多实例场景（如多数据源）。\n\n");
-                    hover.push_str("**注入代码**:\n\n");
+                    hover.push_str(&localized(
+                        self.locale,
+                        "hover.inject.component-name",
+                        &[&name],
+                    ));
+                    hover.push_str(&localized(self.locale, "hover.inject.by-name", &[]));
+                    hover.push_str(&localized(self.locale, "hover.inject.code", &[]));
                     hover.push_str("```rust\n");
                     hover.push_str(&format!("app.get_component::<T>(\"{}\")\n", name));
                     hover.push_str("```\n");
                 } else {
-                    hover.push_str("使用类型自动查找组件。\n\n");
-                    hover.push_str("**注入代码**:\n\n");
+                    hover.push_str(&localized(self.locale, "hover.inject.by-type", &[]));
+                    hover.push_str(&localized(self.locale, "hover.inject.code", &[]));
                     hover.push_str("```rust\n");
                     hover.push_str("app.get_component::<T>()\n");
                     hover.push_str("```\n");
                 }
             }
             InjectType::Config => {
-                hover.push_str("**注入类型**: 配置 (Config)\n\n");
-                hover.push_str("从配置文件中加载配置项。\n\n");
-                hover.push_str(
-                    "配置项通过 `#[config_prefix]` 指定的前缀从 `config/app.toml` 中读取。\n\n",
-                );
-                hover.push_str("**注入代码**:\n\n");
+                hover.push_str(&localized(self.locale, "hover.inject.config", &[]));
+                hover.push_str(&localized(
+                    self.locale,
+                    "hover.inject.config-description",
+                    &[],
+                ));
+                hover.push_str(&localized(self.locale, "hover.inject.config-prefix", &[]));
+                hover.push_str(&localized(self.locale, "hover.inject.code", &[]));
                 hover.push_str("```rust\n");
                 hover.push_str("app.get_config::<T>()\n");
                 hover.push_str("```\n");
@@ -381,7 +823,7 @@ impl MacroAnalyzer {
         }
 
         // 添加示例
-        hover.push_str("\n**使用示例**:\n\n");
+        hover.push_str(&localized(self.locale, "hover.inject.example", &[]));
         hover.push_str("```rust\n");
         hover.push_str("#[derive(Clone, Service)]\n");
         hover.push_str("struct MyService {\n");
@@ -412,13 +854,18 @@ impl MacroAnalyzer {
     fn hover_auto_config_macro(&self, auto_config: &AutoConfigMacro) -> String {
         let mut hover = String::new();
 
-        hover.push_str("# AutoConfig 属性宏\n\n");
-        hover.push_str("自动注册配置器，在应用启动时配置路由、任务等。\n\n");
-        hover.push_str(&format!(
-            "**配置器类型**: `{}`\n\n",
-            auto_config.configurator_type
+        hover.push_str(&localized(self.locale, "hover.auto-config.title", &[]));
+        hover.push_str(&localized(
+            self.locale,
+            "hover.auto-config.description",
+            &[],
+        ));
+        hover.push_str(&localized(
+            self.locale,
+            "hover.auto-config.configurator",
+            &[&auto_config.configurator_type],
         ));
-        hover.push_str("**展开后的代码**:\n\n");
+        hover.push_str(&localized(self.locale, "hover.expanded", &[]));
         hover.push_str("```rust\n");
         hover.push_str(&self.expand_auto_config_macro(auto_config));
         hover.push_str("```\n");
@@ -430,33 +877,68 @@ impl MacroAnalyzer {
     fn hover_route_macro(&self, route: &RouteMacro) -> String {
         let mut hover = String::new();
 
-        hover.push_str("# 路由宏\n\n");
-        hover.push_str("注册 HTTP 路由处理器。\n\n");
-        hover.push_str(&format!("**路由路径**: `{}`\n\n", route.path));
-        hover.push_str(&format!(
-            "**HTTP 方法**: {}\n\n",
-            route
+        hover.push_str(&localized(self.locale, "hover.route.title", &[]));
+        if route.deprecated {
+            hover.push_str(&localized(self.locale, "hover.deprecated", &[]));
+        }
+        // 处理器有文档注释时用它代替通用说明
+        match &route.doc {
+            Some(doc) => {
+                hover.push_str(doc);
+                hover.push_str("\n\n");
+            }
+            None => hover.push_str(&localized(self.locale, "hover.route.description", &[])),
+        }
+        hover.push_str(&localized(self.locale, "hover.route.path", &[&route.path]));
+        hover.push_str(&localized(
+            self.locale,
+            "hover.route.methods",
+            &[&route
                 .methods
                 .iter()
                 .map(|m| format!("`{}`", m.as_str()))
                 .collect::<Vec<_>>()
-                .join(", ")
+                .join(", ")],
         ));
 
         if !route.middlewares.is_empty() {
-            hover.push_str(&format!(
-                "**中间件**: {}\n\n",
-                route
+            hover.push_str(&localized(
+                self.locale,
+                "hover.route.middlewares",
+                &[&route
                     .middlewares
                     .iter()
                     .map(|m| format!("`{}`", m))
                     .collect::<Vec<_>>()
-                    .join(", ")
+                    .join(", ")],
             ));
         }
 
-        hover.push_str(&format!("**处理器函数**: `{}`\n\n", route.handler_name));
-        hover.push_str("**展开后的代码**:\n\n");
+        hover.push_str(&localized(
+            self.locale,
+            "hover.route.handler",
+            &[&route.handler_name],
+        ));
+        if let Some(instrument) = &route.instrument {
+            hover.push_str(&localized(
+                self.locale,
+                "hover.route.span",
+                &[&instrument.span_name, &instrument.level],
+            ));
+            if !instrument.fields.is_empty() {
+                hover.push_str(&localized(
+                    self.locale,
+                    "hover.route.span-fields",
+                    &[&instrument
+                        .fields
+                        .iter()
+                        .map(|field| format!("`{}`", field))
+                        .collect::<Vec<_>>()
+                        .join(", ")],
+                ));
+            }
+        }
+        hover.push_str(&localized(self.locale, "hover.expanded", &[]));
         hover.push_str("```rust\n");
         hover.push_str(&self.expand_route_macro(route));
         hover.push_str("```\n");
@@ -465,28 +947,51 @@ impl MacroAnalyzer {
     }
 
     /// 为任务调度宏提供悬停提示
-    fn hover_job_macro(&self, job: &JobMacro) -> String {
+    ///
+    /// `#[fix_delay]` / `#[fix_rate]` 附带按宏参数生成的调度时间线，
+    /// `min_duration` 为任务体中固定等待的总时长（毫秒，见 [`job_timeline::min_body_duration`]）
+    pub fn hover_job(&self, job: &JobMacro, min_duration: Option<u64>) -> String {
         let mut hover = String::new();
 
-        hover.push_str("# 任务调度宏\n\n");
+        hover.push_str(&localized(self.locale, "hover.job.title", &[]));
 
         match job {
-            JobMacro::Cron { expression, .. } => {
-                hover.push_str("定时任务，使用 Cron 表达式指定执行时间。\n\n");
-                hover.push_str(&format!("**Cron 表达式**: `{}`\n\n", expression));
-                hover.push_str("**格式**: `秒 分 时 日 月 星期`\n\n");
+            JobMacro::Cron {
+                expression,
+                timezone,
+                ..
+            } => {
+                hover.push_str(&localized(self.locale, "hover.job.cron", &[]));
+                hover.push_str(&localized(
+                    self.locale,
+                    "hover.job.expression",
+                    &[&expression],
+                ));
+                hover.push_str(&localized(self.locale, "hover.job.cron-format", &[]));
+                hover.push_str(&localized(
+                    self.locale,
+                    "hover.job.timezone",
+                    &[&timezone.as_deref().unwrap_or("UTC")],
+                ));
             }
             JobMacro::FixDelay { seconds, .. } => {
-                hover.push_str("固定延迟任务，任务完成后延迟指定秒数再次执行。\n\n");
-                hover.push_str(&format!("**延迟秒数**: `{}`\n\n", seconds));
+                hover.push_str(&localized(self.locale, "hover.job.fix-delay", &[]));
+                hover.push_str(&localized(self.locale, "hover.job.delay", &[&seconds]));
             }
             JobMacro::FixRate { seconds, .. } => {
-                hover.push_str("固定频率任务，每隔指定秒数执行一次。\n\n");
-                hover.push_str(&format!("**频率秒数**: `{}`\n\n", seconds));
+                hover.push_str(&localized(self.locale, "hover.job.fix-rate", &[]));
+                hover.push_str(&localized(self.locale, "hover.job.rate", &[&seconds]));
             }
+            JobMacro::OneShot { seconds, .. } => {
+                hover.push_str(&localized(self.locale, "hover.job.one-shot", &[]));
+                hover.push_str(&localized(self.locale, "hover.job.delay", &[&seconds]));
+            }
+        }
+        if let Some(timeline) = job_timeline::schedule_timeline(job, min_duration, self.locale) {
+            hover.push_str(&timeline);
         }
 
-        hover.push_str("**展开后的代码**:\n\n");
+        hover.push_str(&localized(self.locale, "hover.expanded", &[]));
         hover.push_str("```rust\n");
         hover.push_str(&self.expand_job_macro(job));
         hover.push_str("```\n");
@@ -516,6 +1021,72 @@ impl MacroAnalyzer {
         }
     }
 
+    /// 宏生成的代码（Service 和 Component 不包含原始定义）
+    fn generated_code(&self, macro_info: &SpringMacro) -> String {
+        match macro_info {
+            SpringMacro::DeriveService(service) => self.service_impl_code(service),
+            SpringMacro::Component(component) => self.component_plugin_code(component),
+            _ => self.expand_macro(macro_info),
+        }
+    }
+
+    /// 展开整个文件中的 spring-rs 宏
+    ///
+    /// 在每个带 spring-rs 宏的结构体或函数之后插入宏生成的代码，其余代码保持不变，
+    /// 用于审查整个模块中宏生成了什么。`doc` 需要已经提取了宏，返回展开后的内容和展开的宏数量
+    pub fn expand_document(&self, doc: &RustDocument) -> Result<(String, usize), syn::Error> {
+        let file = syn::parse_file(&doc.content)?;
+        let lines = PositionMapper::with_encoding(&doc.content, self.encoding);
+
+        // 每个顶层结构体和函数的行范围，宏的位置落在其中时插入到它的最后一行之后
+        let items: Vec<(u32, u32)> = file
+            .items
+            .iter()
+            .filter(|item| matches!(item, syn::Item::Struct(_) | syn::Item::Fn(_)))
+            .map(|item| {
+                let range = lines.span_range(item.span());
+                (range.start.line, range.end.line)
+            })
+            .collect();
+        let mut insertions: BTreeMap<u32, Vec<&SpringMacro>> = BTreeMap::new();
+        for macro_info in &doc.macros {
+            let line = macro_info.range().start.line;
+            if let Some((_, end)) = items
+                .iter()
+                .find(|(start, end)| (*start..=*end).contains(&line))
+            {
+                insertions.entry(*end).or_default().push(macro_info);
+            }
+        }
+
+        let mut expanded = String::with_capacity(doc.content.len());
+        for (line, text) in doc.content.split_inclusive('\n').enumerate() {
+            expanded.push_str(text);
+            let Some(macros) = insertions.get(&(line as u32)) else {
+                continue;
+            };
+            if !text.ends_with('\n') {
+                expanded.push('\n');
+            }
+            for macro_info in macros {
+                expanded.push_str(&localized(
+                    self.locale,
+                    "expand.begin",
+                    &[&macro_info.title(self.locale)],
+                ));
+                expanded.push_str(self.generated_code(macro_info).trim_end());
+                expanded.push_str(&localized(
+                    self.locale,
+                    "expand.end",
+                    &[&macro_info.title(self.locale)],
+                ));
+            }
+        }
+
+        let count = insertions.values().map(Vec::len).sum();
+        Ok((expanded, count))
+    }
+
     /// 展开 Component 属性宏
     ///
     /// 生成 Plugin trait 的实现代码
@@ -523,7 +1094,7 @@ impl MacroAnalyzer {
         let mut code = String::new();
 
         // 生成原始函数定义（带注释）
-        code.push_str("// 原始定义\n");
+        code.push_str(&localized(self.locale, "expand.original", &[]));
         code.push_str("#[component");
         if let Some(name) = &component.plugin_name {
             code.push_str(&format!("(name = \"{}\")", name));
@@ -554,7 +1125,15 @@ impl MacroAnalyzer {
         code.push_str("}\n\n");
 
         // 生成展开后的 Plugin 实现
-        code.push_str("// 展开后的代码\n");
+        code.push_str(&localized(self.locale, "expand.generated", &[]));
+        code.push_str(&self.component_plugin_code(component));
+
+        code
+    }
+
+    /// Component 属性宏生成的 Plugin 实现和自动注册代码
+    fn component_plugin_code(&self, component: &ComponentMacro) -> String {
+        let mut code = String::new();
 
         let plugin_name = component
             .plugin_name
@@ -629,7 +1208,7 @@ impl MacroAnalyzer {
         let mut code = String::new();
 
         // 生成原始结构体定义（带注释）
-        code.push_str("// 原始定义\n");
+        code.push_str(&localized(self.locale, "expand.original", &[]));
         code.push_str("#[derive(Clone)]\n");
         code.push_str(&format!("pub struct {} {{\n", struct_name));
         for field in &service.fields {
@@ -649,9 +1228,18 @@ impl MacroAnalyzer {
         code.push_str("}\n\n");
 
         // 生成 Service trait 实现
-        code.push_str("// 展开后的代码\n");
+        code.push_str(&localized(self.locale, "expand.generated", &[]));
+        code.push_str(&self.service_impl_code(service));
+
+        code
+    }
+
+    /// Service 派生宏生成的构建函数
+    fn service_impl_code(&self, service: &ServiceMacro) -> String {
+        let struct_name = &service.struct_name;
+        let mut code = String::new();
         code.push_str(&format!("impl {} {{\n", struct_name));
-        code.push_str("    /// 从应用上下文构建服务实例\n");
+        code.push_str(&localized(self.locale, "expand.service.build", &[]));
         code.push_str("    pub fn build(app: &AppBuilder) -> Result<Self> {\n");
 
         // 为每个字段生成注入代码
@@ -680,9 +1268,10 @@ impl MacroAnalyzer {
                 }
             } else {
                 // 没有 inject 属性的字段需要手动初始化
-                code.push_str(&format!(
-                    "        let {} = Default::default(); // 需要手动初始化\n",
-                    field.name
+                code.push_str(&localized(
+                    self.locale,
+                    "expand.service.manual-init",
+                    &[&field.name],
                 ));
             }
         }
@@ -704,26 +1293,31 @@ impl MacroAnalyzer {
     fn expand_inject_macro(&self, inject: &InjectMacro) -> String {
         let mut code = String::new();
 
-        code.push_str("// Inject 属性展开\n");
-        code.push_str("// 这个字段将在运行时从应用上下文中注入\n");
+        code.push_str(&localized(self.locale, "expand.inject.title", &[]));
+        code.push_str(&localized(self.locale, "expand.inject.description", &[]));
 
         match inject.inject_type {
             InjectType::Component => {
                 if let Some(name) = &inject.component_name {
-                    code.push_str("// 注入类型: 组件\n");
-                    code.push_str(&format!("// 组件名称: \"{}\"\n", name));
-                    code.push_str(&format!(
-                        "// 注入代码: app.get_component::<T>(\"{}\")\n",
-                        name
+                    code.push_str(&localized(self.locale, "expand.inject.component", &[]));
+                    code.push_str(&localized(
+                        self.locale,
+                        "expand.inject.component-name",
+                        &[&name],
+                    ));
+                    code.push_str(&localized(
+                        self.locale,
+                        "expand.inject.named-code",
+                        &[&name],
                     ));
                 } else {
-                    code.push_str("// 注入类型: 组件\n");
-                    code.push_str("// 注入代码: app.get_component::<T>()\n");
+                    code.push_str(&localized(self.locale, "expand.inject.component", &[]));
+                    code.push_str(&localized(self.locale, "expand.inject.component-code", &[]));
                 }
             }
             InjectType::Config => {
-                code.push_str("// 注入类型: 配置\n");
-                code.push_str("// 注入代码: app.get_config::<T>()\n");
+                code.push_str(&localized(self.locale, "expand.inject.config", &[]));
+                code.push_str(&localized(self.locale, "expand.inject.config-code", &[]));
             }
         }
 
@@ -736,13 +1330,18 @@ impl MacroAnalyzer {
     fn expand_auto_config_macro(&self, auto_config: &AutoConfigMacro) -> String {
         let mut code = String::new();
 
-        code.push_str("// AutoConfig 宏展开\n");
-        code.push_str(&format!(
-            "// 配置器类型: {}\n",
-            auto_config.configurator_type
+        code.push_str(&localized(self.locale, "expand.auto-config.title", &[]));
+        code.push_str(&localized(
+            self.locale,
+            "expand.auto-config.configurator",
+            &[&auto_config.configurator_type],
+        ));
+        code.push_str(&localized(
+            self.locale,
+            "expand.auto-config.description",
+            &[],
         ));
-        code.push_str("// 这个函数将在应用启动时自动注册配置\n");
-        code.push_str("// 展开后的代码:\n");
+        code.push_str(&localized(self.locale, "expand.generated-heading", &[]));
         code.push_str("// \n");
         code.push_str("// fn main() {\n");
         code.push_str(&format!(
@@ -750,7 +1349,7 @@ impl MacroAnalyzer {
             auto_config.configurator_type
         ));
         code.push_str("//     configurator.configure(&mut app);\n");
-        code.push_str("//     // ... 原函数体\n");
+        code.push_str(&localized(self.locale, "expand.original-body", &[]));
         code.push_str("// }\n");
 
         code
@@ -762,24 +1361,29 @@ impl MacroAnalyzer {
     fn expand_route_macro(&self, route: &RouteMacro) -> String {
         let mut code = String::new();
 
-        code.push_str("// 路由宏展开\n");
-        code.push_str(&format!("// 路由路径: {}\n", route.path));
-        code.push_str(&format!(
-            "// HTTP 方法: {}\n",
-            route
+        code.push_str(&localized(self.locale, "expand.route.title", &[]));
+        code.push_str(&localized(self.locale, "expand.route.path", &[&route.path]));
+        code.push_str(&localized(
+            self.locale,
+            "expand.route.methods",
+            &[&route
                 .methods
                 .iter()
                 .map(|m| m.as_str())
                 .collect::<Vec<_>>()
-                .join(", ")
+                .join(", ")],
         ));
 
         if !route.middlewares.is_empty() {
-            code.push_str(&format!("// 中间件: {}\n", route.middlewares.join(", ")));
+            code.push_str(&localized(
+                self.locale,
+                "expand.route.middlewares",
+                &[&route.middlewares.join(", ")],
+            ));
         }
 
         code.push_str("// \n");
-        code.push_str("// 展开后的代码:\n");
+        code.push_str(&localized(self.locale, "expand.generated-heading", &[]));
         code.push_str("// \n");
 
         for method in &route.methods {
@@ -793,7 +1397,11 @@ impl MacroAnalyzer {
 
         if !route.middlewares.is_empty() {
             code.push_str("// \n");
-            code.push_str("// 应用中间件:\n");
+            code.push_str(&localized(
+                self.locale,
+                "expand.route.apply-middlewares",
+                &[],
+            ));
             for middleware in &route.middlewares {
                 code.push_str(&format!("// .layer({})\n", middleware));
             }
@@ -808,53 +1416,92 @@ impl MacroAnalyzer {
     fn expand_job_macro(&self, job: &JobMacro) -> String {
         let mut code = String::new();
 
-        code.push_str("// 任务调度宏展开\n");
+        code.push_str(&localized(self.locale, "expand.job.title", &[]));
 
         match job {
-            JobMacro::Cron { expression, .. } => {
-                code.push_str("// 任务类型: Cron\n");
-                code.push_str(&format!("// Cron 表达式: {}\n", expression));
+            JobMacro::Cron {
+                expression,
+                timezone,
+                ..
+            } => {
+                code.push_str(&localized(self.locale, "expand.job.cron", &[]));
+                code.push_str(&localized(
+                    self.locale,
+                    "expand.job.expression",
+                    &[&expression],
+                ));
+                if let Some(timezone) = timezone {
+                    code.push_str(&localized(self.locale, "expand.job.timezone", &[&timezone]));
+                }
                 code.push_str("// \n");
-                code.push_str("// 展开后的代码:\n");
+                code.push_str(&localized(self.locale, "expand.generated-heading", &[]));
                 code.push_str("// \n");
                 code.push_str("// scheduler.add_job(\n");
                 code.push_str(&format!(
                     "//     CronJob::new(\"{}\", || async {{\n",
                     expression
                 ));
-                code.push_str("//         // 任务函数体\n");
+                code.push_str(&localized(self.locale, "expand.job.body", &[]));
                 code.push_str("//     }})\n");
                 code.push_str("// );\n");
             }
             JobMacro::FixDelay { seconds, .. } => {
-                code.push_str("// 任务类型: FixDelay\n");
-                code.push_str(&format!("// 延迟秒数: {}\n", seconds));
-                code.push_str("// 说明: 任务完成后延迟指定秒数再次执行\n");
+                code.push_str(&localized(self.locale, "expand.job.fix-delay", &[]));
+                code.push_str(&localized(self.locale, "expand.job.delay", &[&seconds]));
+                code.push_str(&localized(
+                    self.locale,
+                    "expand.job.fix-delay-description",
+                    &[],
+                ));
                 code.push_str("// \n");
-                code.push_str("// 展开后的代码:\n");
+                code.push_str(&localized(self.locale, "expand.generated-heading", &[]));
                 code.push_str("// \n");
                 code.push_str("// scheduler.add_job(\n");
                 code.push_str(&format!(
                     "//     FixDelayJob::new({}, || async {{\n",
                     seconds
                 ));
-                code.push_str("//         // 任务函数体\n");
+                code.push_str(&localized(self.locale, "expand.job.body", &[]));
                 code.push_str("//     }})\n");
                 code.push_str("// );\n");
             }
             JobMacro::FixRate { seconds, .. } => {
-                code.push_str("// 任务类型: FixRate\n");
-                code.push_str(&format!("// 频率秒数: {}\n", seconds));
-                code.push_str("// 说明: 每隔指定秒数执行一次任务\n");
+                code.push_str(&localized(self.locale, "expand.job.fix-rate", &[]));
+                code.push_str(&localized(self.locale, "expand.job.rate", &[&seconds]));
+                code.push_str(&localized(
+                    self.locale,
+                    "expand.job.fix-rate-description",
+                    &[],
+                ));
                 code.push_str("// \n");
-                code.push_str("// 展开后的代码:\n");
+                code.push_str(&localized(self.locale, "expand.generated-heading", &[]));
                 code.push_str("// \n");
                 code.push_str("// scheduler.add_job(\n");
                 code.push_str(&format!(
                     "//     FixRateJob::new({}, || async {{\n",
                     seconds
                 ));
-                code.push_str("//         // 任务函数体\n");
+                code.push_str(&localized(self.locale, "expand.job.body", &[]));
+                code.push_str("//     }})\n");
+                code.push_str("// );\n");
+            }
+            JobMacro::OneShot { seconds, .. } => {
+                code.push_str(&localized(self.locale, "expand.job.one-shot", &[]));
+                code.push_str(&localized(self.locale, "expand.job.delay", &[&seconds]));
+                code.push_str(&localized(
+                    self.locale,
+                    "expand.job.one-shot-description",
+                    &[],
+                ));
+                code.push_str("// \n");
+                code.push_str(&localized(self.locale, "expand.generated-heading", &[]));
+                code.push_str("// \n");
+                code.push_str("// scheduler.add_job(\n");
+                code.push_str(&format!(
+                    "//     OneShotJob::new({}, || async {{\n",
+                    seconds
+                ));
+                code.push_str(&localized(self.locale, "expand.job.body", &[]));
                 code.push_str("//     }})\n");
                 code.push_str("// );\n");
             }
@@ -875,9 +1522,17 @@ impl MacroAnalyzer {
     /// # Returns
     ///
     /// 返回解析后的 RustDocument，如果解析失败则返回错误
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
     pub fn parse(&self, uri: Url, content: String) -> Result<RustDocument, syn::Error> {
-        // 使用 syn 解析 Rust 代码
-        let _syntax_tree = syn::parse_file(&content)?;
+        // 已缓存的内容一定能解析，否则使用 syn 解析 Rust 代码
+        let cached = self
+            .cache
+            .lock()
+            .unwrap()
+            .contains(content_hash(&content), content.len());
+        if !cached {
+            let _syntax_tree = syn::parse_file(&content)?;
+        }
 
         // 创建 RustDocument
         // 注意：实际的宏提取将在 extract_macros 中完成
@@ -890,6 +1545,45 @@ impl MacroAnalyzer {
         Ok(doc)
     }
 
+    /// 提取文件中结构体、枚举、trait 和类型别名的文档注释摘要（第一行），按类型名索引
+    ///
+    /// 包括行内模块中的类型；解析失败或没有文档注释的类型不包含在结果中
+    pub fn type_doc_summaries(&self, content: &str) -> HashMap<String, String> {
+        fn collect(
+            analyzer: &MacroAnalyzer,
+            items: &[syn::Item],
+            docs: &mut HashMap<String, String>,
+        ) {
+            for item in items {
+                let (ident, attrs) = match item {
+                    syn::Item::Struct(item) => (&item.ident, &item.attrs),
+                    syn::Item::Enum(item) => (&item.ident, &item.attrs),
+                    syn::Item::Trait(item) => (&item.ident, &item.attrs),
+                    syn::Item::Type(item) => (&item.ident, &item.attrs),
+                    syn::Item::Mod(item_mod) => {
+                        if let Some((_, items)) = &item_mod.content {
+                            collect(analyzer, items, docs);
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                };
+                let summary = analyzer
+                    .extract_doc_comment(attrs)
+                    .and_then(|doc| doc.lines().next().map(str::to_string));
+                if let Some(summary) = summary {
+                    docs.entry(ident.to_string()).or_insert(summary);
+                }
+            }
+        }
+
+        let mut docs = HashMap::new();
+        if let Ok(file) = syn::parse_file(content) {
+            collect(self, &file.items, &mut docs);
+        }
+        docs
+    }
+
     /// 从 RustDocument 中提取 spring-rs 宏
     ///
     /// 遍历语法树，识别并提取所有 spring-rs 特定的宏
@@ -901,9 +1595,28 @@ impl MacroAnalyzer {
     /// # Returns
     ///
     /// 返回包含提取的宏的新 RustDocument
-    pub fn extract_macros(&self, mut doc: RustDocument) -> Result<RustDocument, syn::Error> {
+    pub fn extract_macros(&self, doc: RustDocument) -> Result<RustDocument, syn::Error> {
+        let line_index = LineIndex::new(&doc.content);
+        self.extract_macros_with_index(doc, &line_index)
+    }
+
+    /// 使用已建立的行索引（必须与 `doc.content` 对应）提取宏，打开的文档复用缓存的行索引
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
+    pub fn extract_macros_with_index(
+        &self,
+        mut doc: RustDocument,
+        line_index: &LineIndex,
+    ) -> Result<RustDocument, syn::Error> {
+        let hash = content_hash(&doc.content);
+        let len = doc.content.len();
+        if let Some(macros) = self.cache.lock().unwrap().get(hash, len) {
+            doc.macros = macros;
+            return Ok(doc);
+        }
+
         // 重新解析内容以获取语法树
         let syntax_tree = syn::parse_file(&doc.content)?;
+        let lines = PositionMapper::with_line_index(&doc.content, line_index, self.encoding);
 
         let mut macros = Vec::new();
 
@@ -913,29 +1626,30 @@ impl MacroAnalyzer {
                 // 处理结构体定义
                 syn::Item::Struct(item_struct) => {
                     // 检查是否有 #[derive(Service)]
-                    if let Some(service_macro) = self.extract_service_macro(item_struct) {
+                    if let Some(service_macro) = self.extract_service_macro(item_struct, &lines) {
                         macros.push(SpringMacro::DeriveService(service_macro));
                     }
                 }
                 // 处理函数定义
                 syn::Item::Fn(item_fn) => {
                     // 检查 Component 宏
-                    if let Some(component_macro) = self.extract_component_macro(item_fn) {
+                    if let Some(component_macro) = self.extract_component_macro(item_fn, &lines) {
                         macros.push(SpringMacro::Component(component_macro));
                     }
 
                     // 检查路由宏
-                    if let Some(route_macro) = self.extract_route_macro(item_fn) {
+                    if let Some(route_macro) = self.extract_route_macro(item_fn, &lines) {
                         macros.push(SpringMacro::Route(route_macro));
                     }
 
                     // 检查 AutoConfig 宏
-                    if let Some(auto_config_macro) = self.extract_auto_config_macro(item_fn) {
+                    if let Some(auto_config_macro) = self.extract_auto_config_macro(item_fn, &lines)
+                    {
                         macros.push(SpringMacro::AutoConfig(auto_config_macro));
                     }
 
                     // 检查任务调度宏
-                    if let Some(job_macro) = self.extract_job_macro(item_fn) {
+                    if let Some(job_macro) = self.extract_job_macro(item_fn, &lines) {
                         macros.push(SpringMacro::Job(job_macro));
                     }
                 }
@@ -943,24 +1657,36 @@ impl MacroAnalyzer {
             }
         }
 
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(hash, len, macros.clone(), self.cache_capacity);
         doc.macros = macros;
         Ok(doc)
     }
 
     /// 提取 Component 属性宏
-    fn extract_component_macro(&self, item_fn: &syn::ItemFn) -> Option<ComponentMacro> {
+    fn extract_component_macro(
+        &self,
+        item_fn: &syn::ItemFn,
+        lines: &PositionMapper,
+    ) -> Option<ComponentMacro> {
         // 检查是否有 #[component] 属性
         for attr in &item_fn.attrs {
             if attr.path().is_ident("component") {
                 // 提取插件名称（如果有）
-                let plugin_name = if let Ok(meta_list) = attr.meta.require_list() {
-                    self.extract_component_name(&meta_list.tokens.to_string())
-                } else {
-                    None
-                };
+                let (plugin_name, plugin_name_range) =
+                    if let Ok(meta_list) = attr.meta.require_list() {
+                        (
+                            self.extract_component_name(&meta_list.tokens.to_string()),
+                            assigned_value_range(&meta_list.tokens, lines),
+                        )
+                    } else {
+                        (None, None)
+                    };
 
                 // 提取函数参数中的依赖
-                let dependencies = self.extract_component_dependencies(&item_fn.sig.inputs);
+                let dependencies = self.extract_component_dependencies(&item_fn.sig.inputs, lines);
 
                 // 提取返回类型
                 let component_type = self.extract_return_type(&item_fn.sig.output);
@@ -973,8 +1699,10 @@ impl MacroAnalyzer {
                     component_type,
                     dependencies,
                     plugin_name,
+                    plugin_name_range,
                     is_async,
-                    range: self.span_to_range(&item_fn.sig.ident.span()),
+                    range: lines.span_range(item_fn.sig.ident.span()),
+                    cfg: cfg_condition(&item_fn.attrs),
                 });
             }
         }
@@ -985,6 +1713,7 @@ impl MacroAnalyzer {
     fn extract_component_dependencies(
         &self,
         inputs: &syn::punctuated::Punctuated<syn::FnArg, syn::token::Comma>,
+        lines: &PositionMapper,
     ) -> Vec<ComponentDependency> {
         let mut dependencies = Vec::new();
 
@@ -1013,6 +1742,7 @@ impl MacroAnalyzer {
                                     dependencies.push(ComponentDependency {
                                         dep_type,
                                         type_name: inner_type_name,
+                                        range: lines.span_range(pat_type.ty.span()),
                                     });
                                 }
                             }
@@ -1052,44 +1782,43 @@ impl MacroAnalyzer {
     }
 
     /// 提取 Service 派生宏
-    fn extract_service_macro(&self, item_struct: &syn::ItemStruct) -> Option<ServiceMacro> {
-        // 检查是否有 #[derive(...)] 属性
-        for attr in &item_struct.attrs {
-            if attr.path().is_ident("derive") {
-                // 解析 derive 属性的内容
-                if let Ok(meta_list) = attr.meta.require_list() {
-                    // 检查是否包含 Service
-                    let has_service = meta_list.tokens.to_string().contains("Service");
-
-                    if has_service {
-                        // 提取字段信息
-                        let fields = self.extract_fields(&item_struct.fields);
-
-                        return Some(ServiceMacro {
-                            struct_name: item_struct.ident.to_string(),
-                            fields,
-                            range: self.span_to_range(&item_struct.ident.span()),
-                        });
-                    }
-                }
-            }
+    fn extract_service_macro(
+        &self,
+        item_struct: &syn::ItemStruct,
+        lines: &PositionMapper,
+    ) -> Option<ServiceMacro> {
+        // 检查是否有 #[derive(Service)]
+        if !derives_service(&item_struct.attrs) {
+            return None;
         }
-        None
+
+        // 提取字段信息
+        let fields = self.extract_fields(&item_struct.fields, lines);
+
+        Some(ServiceMacro {
+            struct_name: item_struct.ident.to_string(),
+            fields,
+            range: lines.span_range(item_struct.ident.span()),
+            doc: self.extract_doc_comment(&item_struct.attrs),
+            deprecated: is_deprecated(&item_struct.attrs),
+            cfg: cfg_condition(&item_struct.attrs),
+        })
     }
 
     /// 提取结构体字段信息
-    fn extract_fields(&self, fields: &syn::Fields) -> Vec<Field> {
+    fn extract_fields(&self, fields: &syn::Fields, lines: &PositionMapper) -> Vec<Field> {
         let mut result = Vec::new();
 
         if let syn::Fields::Named(fields_named) = fields {
             for field in &fields_named.named {
                 if let Some(ident) = &field.ident {
-                    let inject = self.extract_inject_macro(&field.attrs);
+                    let inject = self.extract_inject_macro(&field.attrs, lines);
 
                     result.push(Field {
                         name: ident.to_string(),
                         type_name: self.type_to_string(&field.ty),
                         inject,
+                        range: lines.span_range(ident.span()),
                     });
                 }
             }
@@ -1099,7 +1828,11 @@ impl MacroAnalyzer {
     }
 
     /// 提取 Inject 属性宏
-    fn extract_inject_macro(&self, attrs: &[syn::Attribute]) -> Option<InjectMacro> {
+    fn extract_inject_macro(
+        &self,
+        attrs: &[syn::Attribute],
+        lines: &PositionMapper,
+    ) -> Option<InjectMacro> {
         for attr in attrs {
             if attr.path().is_ident("inject") {
                 // 解析 inject 属性的参数
@@ -1121,7 +1854,8 @@ impl MacroAnalyzer {
                     return Some(InjectMacro {
                         inject_type,
                         component_name,
-                        range: self.span_to_range(&attr.span()),
+                        range: lines.span_range(attr.span()),
+                        name_range: assigned_value_range(&meta_list.tokens, lines),
                     });
                 }
             }
@@ -1144,8 +1878,13 @@ impl MacroAnalyzer {
     }
 
     /// 提取路由宏
-    fn extract_route_macro(&self, item_fn: &syn::ItemFn) -> Option<RouteMacro> {
-        for attr in &item_fn.attrs {
+    fn extract_route_macro(
+        &self,
+        item_fn: &syn::ItemFn,
+        lines: &PositionMapper,
+    ) -> Option<RouteMacro> {
+        for (attr, cfg) in expand_cfg_attrs(&item_fn.attrs) {
+            let attr = &attr;
             // 检查各种路由宏（包括普通路由和 OpenAPI 路由）
             let method_path_and_openapi: Option<(Vec<HttpMethod>, String, bool)> =
                 if attr.path().is_ident("get") {
@@ -1218,13 +1957,53 @@ impl MacroAnalyzer {
                     middlewares,
                     handler_name: item_fn.sig.ident.to_string(),
                     is_openapi,
-                    range: self.span_to_range(&item_fn.sig.ident.span()),
+                    range: lines.span_range(item_fn.sig.ident.span()),
+                    doc: self.extract_doc_comment(&item_fn.attrs),
+                    deprecated: is_deprecated(&item_fn.attrs),
+                    stub: is_stub_body(&item_fn.block),
+                    cfg,
+                    instrument: instrument_info(item_fn),
                 });
             }
         }
         None
     }
 
+    /// 提取 `///` 文档注释
+    ///
+    /// 每行去掉注释标记后的一个前导空格，首尾的空行会被去掉；没有文档注释时返回 `None`
+    fn extract_doc_comment(&self, attrs: &[syn::Attribute]) -> Option<String> {
+        let lines: Vec<String> = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .filter_map(|attr| match &attr.meta {
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    value:
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(lit),
+                            ..
+                        }),
+                    ..
+                }) => Some(lit.value()),
+                _ => None,
+            })
+            .flat_map(|value| {
+                value
+                    .split('\n')
+                    .map(|line| {
+                        line.strip_prefix(' ')
+                            .unwrap_or(line)
+                            .trim_end()
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let doc = lines.join("\n").trim_matches('\n').to_string();
+        (!doc.is_empty()).then_some(doc)
+    }
+
     /// 从属性中提取路径
     fn extract_path_from_attr(&self, attr: &syn::Attribute) -> Option<String> {
         // 解析属性参数，期望是字符串字面量
@@ -1296,7 +2075,11 @@ impl MacroAnalyzer {
     }
 
     /// 提取 AutoConfig 宏
-    fn extract_auto_config_macro(&self, item_fn: &syn::ItemFn) -> Option<AutoConfigMacro> {
+    fn extract_auto_config_macro(
+        &self,
+        item_fn: &syn::ItemFn,
+        lines: &PositionMapper,
+    ) -> Option<AutoConfigMacro> {
         for attr in &item_fn.attrs {
             if attr.path().is_ident("auto_config") {
                 // 提取配置器类型
@@ -1308,7 +2091,7 @@ impl MacroAnalyzer {
 
                 return Some(AutoConfigMacro {
                     configurator_type,
-                    range: self.span_to_range(&attr.span()),
+                    range: lines.span_range(attr.span()),
                 });
             }
         }
@@ -1316,14 +2099,25 @@ impl MacroAnalyzer {
     }
 
     /// 提取任务调度宏
-    fn extract_job_macro(&self, item_fn: &syn::ItemFn) -> Option<JobMacro> {
+    fn extract_job_macro(&self, item_fn: &syn::ItemFn, lines: &PositionMapper) -> Option<JobMacro> {
         for attr in &item_fn.attrs {
             if attr.path().is_ident("cron") {
-                // 提取 cron 表达式
-                if let Some(expression) = self.extract_path_from_attr(attr) {
+                // 提取 cron 表达式和可选的时区：#[cron("0 0 * * * *", "Asia/Shanghai")]
+                let args = attr.parse_args_with(
+                    syn::punctuated::Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated,
+                );
+                let (expression, timezone) = match args {
+                    Ok(args) => {
+                        let mut args = args.iter().map(syn::LitStr::value);
+                        (args.next(), args.next())
+                    }
+                    Err(_) => (self.extract_path_from_attr(attr), None),
+                };
+                if let Some(expression) = expression {
                     return Some(JobMacro::Cron {
                         expression,
-                        range: self.span_to_range(&attr.span()),
+                        timezone,
+                        range: lines.span_range(attr.span()),
                     });
                 }
             } else if attr.path().is_ident("fix_delay") {
@@ -1333,7 +2127,7 @@ impl MacroAnalyzer {
                     if let Ok(seconds) = tokens_str.trim().parse::<u64>() {
                         return Some(JobMacro::FixDelay {
                             seconds,
-                            range: self.span_to_range(&attr.span()),
+                            range: lines.span_range(attr.span()),
                         });
                     }
                 }
@@ -1344,7 +2138,18 @@ impl MacroAnalyzer {
                     if let Ok(seconds) = tokens_str.trim().parse::<u64>() {
                         return Some(JobMacro::FixRate {
                             seconds,
-                            range: self.span_to_range(&attr.span()),
+                            range: lines.span_range(attr.span()),
+                        });
+                    }
+                }
+            } else if attr.path().is_ident("one_shot") {
+                // 提取延迟秒数
+                if let Ok(meta_list) = attr.meta.require_list() {
+                    let tokens_str = meta_list.tokens.to_string();
+                    if let Ok(seconds) = tokens_str.trim().parse::<u64>() {
+                        return Some(JobMacro::OneShot {
+                            seconds,
+                            range: lines.span_range(attr.span()),
                         });
                     }
                 }
@@ -1391,25 +2196,6 @@ impl MacroAnalyzer {
         }
     }
 
-    /// 将 Span 转换为 LSP Range
-    ///
-    /// 使用 proc-macro2 的 span-locations 特性获取准确的位置信息
-    fn span_to_range(&self, span: &Span) -> Range {
-        let start = span.start();
-        let end = span.end();
-
-        Range {
-            start: lsp_types::Position {
-                line: start.line.saturating_sub(1) as u32, // LSP 行号从 0 开始
-                character: start.column as u32,
-            },
-            end: lsp_types::Position {
-                line: end.line.saturating_sub(1) as u32,
-                character: end.column as u32,
-            },
-        }
-    }
-
     /// 验证宏参数的正确性
     ///
     /// 检查宏参数是否符合 spring-rs 的要求，生成错误诊断和修复建议
@@ -1425,7 +2211,7 @@ impl MacroAnalyzer {
         match macro_info {
             SpringMacro::DeriveService(service) => self.validate_service_macro(service),
             SpringMacro::Component(component) => self.validate_component_macro(component),
-            SpringMacro::Inject(inject) => self.validate_inject_macro(inject),
+            SpringMacro::Inject(inject) => self.validate_inject_macro(inject, None),
             SpringMacro::AutoConfig(auto_config) => self.validate_auto_config_macro(auto_config),
             SpringMacro::Route(route) => self.validate_route_macro(route),
             SpringMacro::Job(job) => self.validate_job_macro(job),
@@ -1445,7 +2231,7 @@ impl MacroAnalyzer {
                 severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                 code: Some(lsp_types::NumberOrString::String("E016".to_string())),
                 source: Some("spring-lsp".to_string()),
-                message: "Component 函数必须返回一个具体的类型，不能是 ()".to_string(),
+                message: localized(self.locale, "E016", &[]),
                 related_information: None,
                 tags: None,
                 code_description: None,
@@ -1457,11 +2243,11 @@ impl MacroAnalyzer {
         if let Some(name) = &component.plugin_name {
             if name.is_empty() {
                 diagnostics.push(lsp_types::Diagnostic {
-                    range: component.range,
+                    range: component.plugin_name_range.unwrap_or(component.range),
                     severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                     code: Some(lsp_types::NumberOrString::String("E017".to_string())),
                     source: Some("spring-lsp".to_string()),
-                    message: "插件名称不能为空字符串".to_string(),
+                    message: localized(self.locale, "E017", &[]),
                     related_information: None,
                     tags: None,
                     code_description: None,
@@ -1476,17 +2262,20 @@ impl MacroAnalyzer {
             let key = format!("{:?}:{}", dep.dep_type, dep.type_name);
             if !seen_types.insert(key) {
                 diagnostics.push(lsp_types::Diagnostic {
-                    range: component.range,
+                    range: dep.range,
                     severity: Some(lsp_types::DiagnosticSeverity::WARNING),
                     code: Some(lsp_types::NumberOrString::String("W002".to_string())),
                     source: Some("spring-lsp".to_string()),
-                    message: format!(
-                        "重复的依赖: {} {}",
-                        match dep.dep_type {
-                            DependencyType::Config => "Config",
-                            DependencyType::Component => "Component",
-                        },
-                        dep.type_name
+                    message: localized(
+                        self.locale,
+                        "W002",
+                        &[
+                            &match dep.dep_type {
+                                DependencyType::Config => "Config",
+                                DependencyType::Component => "Component",
+                            },
+                            &dep.type_name,
+                        ],
                     ),
                     related_information: None,
                     tags: None,
@@ -1509,18 +2298,18 @@ impl MacroAnalyzer {
         for field in &service.fields {
             if let Some(inject) = &field.inject {
                 // 验证 inject 属性
-                let inject_diagnostics = self.validate_inject_macro(inject);
+                let inject_diagnostics = self.validate_inject_macro(inject, Some(&field.type_name));
                 diagnostics.extend(inject_diagnostics);
 
                 // 检查组件名称是否为空字符串
                 if let Some(name) = &inject.component_name {
                     if name.is_empty() {
                         diagnostics.push(lsp_types::Diagnostic {
-                            range: inject.range,
+                            range: inject.name_range.unwrap_or(inject.range),
                             severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                             code: Some(lsp_types::NumberOrString::String("E001".to_string())),
                             source: Some("spring-lsp".to_string()),
-                            message: format!("字段 '{}' 的组件名称不能为空字符串", field.name),
+                            message: localized(self.locale, "E001", &[&field.name]),
                             related_information: None,
                             tags: None,
                             code_description: None,
@@ -1536,23 +2325,126 @@ impl MacroAnalyzer {
 
     /// 验证 Inject 宏
     ///
-    /// 检查注入类型和组件名称是否有效
-    fn validate_inject_macro(&self, inject: &InjectMacro) -> Vec<lsp_types::Diagnostic> {
+    /// 检查注入类型和组件名称是否有效。`field_type` 是被注入字段的类型，
+    /// 用于判断指定了名称的配置注入是否其实想注入组件
+    fn validate_inject_macro(
+        &self,
+        inject: &InjectMacro,
+        field_type: Option<&str>,
+    ) -> Vec<lsp_types::Diagnostic> {
         let mut diagnostics = Vec::new();
 
         // 检查 config 类型的注入不应该有组件名称
-        if inject.inject_type == InjectType::Config && inject.component_name.is_some() {
-            diagnostics.push(lsp_types::Diagnostic {
-                range: inject.range,
+        if let (InjectType::Config, Some(name)) = (&inject.inject_type, &inject.component_name) {
+            let mut diagnostic = lsp_types::Diagnostic {
+                range: inject.name_range.unwrap_or(inject.range),
                 severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                 code: Some(lsp_types::NumberOrString::String("E002".to_string())),
                 source: Some("spring-lsp".to_string()),
-                message: "配置注入 (config) 不应该指定组件名称".to_string(),
+                message: localized(self.locale, "E002", &[]),
                 related_information: None,
                 tags: None,
                 code_description: None,
                 data: None,
+            };
+
+            // 快速修复替换整个属性：去掉名称，或者字段类型不像配置类型时改为组件注入
+            let mut fixes = vec![QuickFix {
+                title: localized(self.locale, "E002.remove-name", &[]),
+                new_text: "#[inject(config)]".to_string(),
+                range: Some(inject.range),
+            }];
+            if field_type.is_some_and(|ty| !is_config_type(ty)) {
+                fixes.push(QuickFix {
+                    title: localized(self.locale, "E002.inject-component", &[name]),
+                    new_text: format!("#[inject(component = {:?})]", name),
+                    range: Some(inject.range),
+                });
+            }
+            QuickFix::attach_all(fixes, &mut diagnostic);
+            diagnostics.push(diagnostic);
+        }
+
+        diagnostics
+    }
+
+    /// 检查 `#[inject]` 属性的位置
+    ///
+    /// 报告同一字段上重复的 `#[inject]`、元组结构体字段上的 `#[inject]`，
+    /// 以及没有派生 Service 的结构体或枚举中的 `#[inject]`，诊断范围是出错的属性本身。
+    /// 语法错误由 rust-analyzer 报告，无法解析时返回空列表
+    pub fn validate_inject_attributes(&self, lines: &PositionMapper) -> Vec<lsp_types::Diagnostic> {
+        let Ok(file) = syn::parse_file(lines.content()) else {
+            return Vec::new();
+        };
+
+        let mut diagnostics = Vec::new();
+        let mut report = |attr: &syn::Attribute, code: &str, message: String| {
+            diagnostics.push(lsp_types::Diagnostic {
+                range: lines.span_range(attr.span()),
+                severity: Some(lsp_types::DiagnosticSeverity::ERROR),
+                code: Some(lsp_types::NumberOrString::String(code.to_string())),
+                source: Some("spring-lsp".to_string()),
+                message,
+                ..Default::default()
             });
+        };
+
+        for item in &file.items {
+            match item {
+                syn::Item::Struct(item_struct) if derives_service(&item_struct.attrs) => {
+                    for (index, field) in item_struct.fields.iter().enumerate() {
+                        let mut injects = inject_attrs(&field.attrs);
+                        let Some(first) = injects.next() else {
+                            continue;
+                        };
+                        match &field.ident {
+                            Some(ident) => {
+                                for duplicate in injects {
+                                    report(
+                                        duplicate,
+                                        "E024",
+                                        localized(self.locale, "E024", &[ident]),
+                                    );
+                                }
+                            }
+                            None => report(
+                                first,
+                                "E025",
+                                localized(self.locale, "E025", &[&item_struct.ident, &index]),
+                            ),
+                        }
+                    }
+                }
+                syn::Item::Struct(item_struct) => {
+                    for attr in item_struct
+                        .fields
+                        .iter()
+                        .flat_map(|f| inject_attrs(&f.attrs))
+                    {
+                        report(
+                            attr,
+                            "E026",
+                            localized(self.locale, "E026.struct", &[&item_struct.ident]),
+                        );
+                    }
+                }
+                syn::Item::Enum(item_enum) => {
+                    let attrs = item_enum
+                        .variants
+                        .iter()
+                        .flat_map(|variant| variant.fields.iter())
+                        .flat_map(|field| inject_attrs(&field.attrs));
+                    for attr in attrs {
+                        report(
+                            attr,
+                            "E026",
+                            localized(self.locale, "E026.enum", &[&item_enum.ident]),
+                        );
+                    }
+                }
+                _ => {}
+            }
         }
 
         diagnostics
@@ -1574,7 +2466,7 @@ impl MacroAnalyzer {
                 severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                 code: Some(lsp_types::NumberOrString::String("E003".to_string())),
                 source: Some("spring-lsp".to_string()),
-                message: "AutoConfig 宏必须指定配置器类型".to_string(),
+                message: localized(self.locale, "E003", &[]),
                 related_information: None,
                 tags: None,
                 code_description: None,
@@ -1598,7 +2490,7 @@ impl MacroAnalyzer {
                 severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                 code: Some(lsp_types::NumberOrString::String("E004".to_string())),
                 source: Some("spring-lsp".to_string()),
-                message: "路由路径不能为空".to_string(),
+                message: localized(self.locale, "E004", &[]),
                 related_information: None,
                 tags: None,
                 code_description: None,
@@ -1612,7 +2504,7 @@ impl MacroAnalyzer {
                     severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                     code: Some(lsp_types::NumberOrString::String("E005".to_string())),
                     source: Some("spring-lsp".to_string()),
-                    message: format!("路由路径必须以 '/' 开头，当前路径: '{}'", route.path),
+                    message: localized(self.locale, "E005", &[&route.path]),
                     related_information: None,
                     tags: None,
                     code_description: None,
@@ -1631,7 +2523,7 @@ impl MacroAnalyzer {
                 severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                 code: Some(lsp_types::NumberOrString::String("E006".to_string())),
                 source: Some("spring-lsp".to_string()),
-                message: "路由必须至少指定一个 HTTP 方法".to_string(),
+                message: localized(self.locale, "E006", &[]),
                 related_information: None,
                 tags: None,
                 code_description: None,
@@ -1646,7 +2538,7 @@ impl MacroAnalyzer {
                 severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                 code: Some(lsp_types::NumberOrString::String("E007".to_string())),
                 source: Some("spring-lsp".to_string()),
-                message: "路由处理器函数名称不能为空".to_string(),
+                message: localized(self.locale, "E007", &[]),
                 related_information: None,
                 tags: None,
                 code_description: None,
@@ -1659,105 +2551,23 @@ impl MacroAnalyzer {
 
     /// 验证路径参数格式
     ///
-    /// 检查路径中的参数是否符合 {param} 格式
+    /// 检查路径中的参数是否符合 `{param}`、`{param:regex}` 或 `{*rest}` 格式，
+    /// 参数名称是否重复以及参数数量是否过多
     fn validate_path_parameters(
         &self,
         path: &str,
         range: Range,
         diagnostics: &mut Vec<lsp_types::Diagnostic>,
     ) {
-        let mut open_braces = 0;
-        let mut param_start = None;
-
-        for (i, ch) in path.char_indices() {
-            match ch {
-                '{' => {
-                    if open_braces > 0 {
-                        // 嵌套的大括号
-                        diagnostics.push(lsp_types::Diagnostic {
-                            range,
-                            severity: Some(lsp_types::DiagnosticSeverity::ERROR),
-                            code: Some(lsp_types::NumberOrString::String("E008".to_string())),
-                            source: Some("spring-lsp".to_string()),
-                            message: format!("路径参数不能嵌套，位置: {}", i),
-                            related_information: None,
-                            tags: None,
-                            code_description: None,
-                            data: None,
-                        });
-                    }
-                    open_braces += 1;
-                    param_start = Some(i);
-                }
-                '}' => {
-                    if open_braces == 0 {
-                        // 没有匹配的开括号
-                        diagnostics.push(lsp_types::Diagnostic {
-                            range,
-                            severity: Some(lsp_types::DiagnosticSeverity::ERROR),
-                            code: Some(lsp_types::NumberOrString::String("E009".to_string())),
-                            source: Some("spring-lsp".to_string()),
-                            message: format!("路径参数缺少开括号 '{{', 位置: {}", i),
-                            related_information: None,
-                            tags: None,
-                            code_description: None,
-                            data: None,
-                        });
-                    } else {
-                        open_braces -= 1;
-
-                        // 检查参数名称是否为空
-                        if let Some(start) = param_start {
-                            let param_name = &path[start + 1..i];
-                            if param_name.is_empty() {
-                                diagnostics.push(lsp_types::Diagnostic {
-                                    range,
-                                    severity: Some(lsp_types::DiagnosticSeverity::ERROR),
-                                    code: Some(lsp_types::NumberOrString::String(
-                                        "E010".to_string(),
-                                    )),
-                                    source: Some("spring-lsp".to_string()),
-                                    message: format!("路径参数名称不能为空，位置: {}", start),
-                                    related_information: None,
-                                    tags: None,
-                                    code_description: None,
-                                    data: None,
-                                });
-                            } else if !param_name.chars().all(|c| c.is_alphanumeric() || c == '_') {
-                                // 检查参数名称是否只包含字母、数字和下划线
-                                diagnostics.push(lsp_types::Diagnostic {
-                                    range,
-                                    severity: Some(lsp_types::DiagnosticSeverity::ERROR),
-                                    code: Some(lsp_types::NumberOrString::String(
-                                        "E011".to_string(),
-                                    )),
-                                    source: Some("spring-lsp".to_string()),
-                                    message: format!(
-                                        "路径参数名称只能包含字母、数字和下划线，当前参数: '{}'",
-                                        param_name
-                                    ),
-                                    related_information: None,
-                                    tags: None,
-                                    code_description: None,
-                                    data: None,
-                                });
-                            }
-                        }
-                        param_start = None;
-                    }
-                }
-                _ => {}
-            }
-        }
+        let (_, errors) = crate::analysis::rust::route_path::parse_route_path(path);
 
-        // 检查是否有未闭合的括号
-        if open_braces > 0 {
+        for error in errors {
             diagnostics.push(lsp_types::Diagnostic {
                 range,
-                severity: Some(lsp_types::DiagnosticSeverity::ERROR),
-                code: Some(lsp_types::NumberOrString::String("E012".to_string())),
+                severity: Some(error.severity()),
+                code: Some(lsp_types::NumberOrString::String(error.code().to_string())),
                 source: Some("spring-lsp".to_string()),
-                message: "路径参数缺少闭括号 '}'".to_string(),
+                message: error.message(self.locale),
                 related_information: None,
                 tags: None,
                 code_description: None,
@@ -1773,7 +2583,25 @@ impl MacroAnalyzer {
         let mut diagnostics = Vec::new();
 
         match job {
-            JobMacro::Cron { expression, range } => {
+            JobMacro::Cron {
+                expression,
+                timezone,
+                range,
+            } => {
+                // 检查时区是否存在于时区数据库中
+                if let Some(timezone) = timezone {
+                    if !timezone::is_known_timezone(timezone) {
+                        diagnostics.push(lsp_types::Diagnostic {
+                            range: *range,
+                            severity: Some(lsp_types::DiagnosticSeverity::ERROR),
+                            code: Some(lsp_types::NumberOrString::String("E030".to_string())),
+                            source: Some("spring-lsp".to_string()),
+                            message: localized(self.locale, "E030", &[timezone]),
+                            ..Default::default()
+                        });
+                    }
+                }
+
                 // 检查 cron 表达式是否为空
                 if expression.is_empty() {
                     diagnostics.push(lsp_types::Diagnostic {
@@ -1781,7 +2609,7 @@ impl MacroAnalyzer {
                         severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                         code: Some(lsp_types::NumberOrString::String("E013".to_string())),
                         source: Some("spring-lsp".to_string()),
-                        message: "Cron 表达式不能为空".to_string(),
+                        message: localized(self.locale, "E013", &[]),
                         related_information: None,
                         tags: None,
                         code_description: None,
@@ -1800,7 +2628,7 @@ impl MacroAnalyzer {
                         severity: Some(lsp_types::DiagnosticSeverity::WARNING),
                         code: Some(lsp_types::NumberOrString::String("W001".to_string())),
                         source: Some("spring-lsp".to_string()),
-                        message: "延迟秒数为 0 可能不是预期的行为".to_string(),
+                        message: localized(self.locale, "W001", &[]),
                         related_information: None,
                         tags: None,
                         code_description: None,
@@ -1816,7 +2644,7 @@ impl MacroAnalyzer {
                         severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                         code: Some(lsp_types::NumberOrString::String("E014".to_string())),
                         source: Some("spring-lsp".to_string()),
-                        message: "频率秒数不能为 0".to_string(),
+                        message: localized(self.locale, "E014", &[]),
                         related_information: None,
                         tags: None,
                         code_description: None,
@@ -1824,6 +2652,9 @@ impl MacroAnalyzer {
                     });
                 }
             }
+            JobMacro::OneShot { .. } => {
+                // 延迟为 0 表示启动后立即执行，任何延迟秒数都有效
+            }
         }
 
         diagnostics
@@ -1847,10 +2678,7 @@ impl MacroAnalyzer {
                 severity: Some(lsp_types::DiagnosticSeverity::ERROR),
                 code: Some(lsp_types::NumberOrString::String("E015".to_string())),
                 source: Some("spring-lsp".to_string()),
-                message: format!(
-                    "Cron 表达式应该包含 6 个部分（秒 分 时 日 月 星期），当前有 {} 个部分",
-                    parts.len()
-                ),
+                message: localized(self.locale, "E015", &[&parts.len()]),
                 related_information: None,
                 tags: None,
                 code_description: None,
@@ -52,6 +52,14 @@ fn test_http_method_as_str() {
     assert_eq!(HttpMethod::Trace.as_str(), "TRACE");
 }
 
+#[test]
+fn test_http_method_closest() {
+    assert_eq!(HttpMethod::closest("FETCH"), HttpMethod::Patch);
+    assert_eq!(HttpMethod::closest("delet"), HttpMethod::Delete);
+    assert_eq!(HttpMethod::closest("PSOT"), HttpMethod::Post);
+    assert_eq!(HttpMethod::closest("GET"), HttpMethod::Get);
+}
+
 #[test]
 fn test_inject_type() {
     let component = InjectType::Component;
@@ -68,6 +76,7 @@ fn test_inject_macro_creation() {
         inject_type: InjectType::Component,
         component_name: Some("my_component".to_string()),
         range: test_range(),
+        name_range: None,
     };
 
     assert_eq!(inject.inject_type, InjectType::Component);
@@ -85,9 +94,14 @@ fn test_service_macro_creation() {
                 inject_type: InjectType::Component,
                 component_name: None,
                 range: test_range(),
+                name_range: None,
             }),
+            range: test_range(),
         }],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     assert_eq!(service.struct_name, "MyService");
@@ -106,6 +120,11 @@ fn test_route_macro_creation() {
         handler_name: "get_user".to_string(),
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     assert_eq!(route.path, "/users/{id}");
@@ -123,12 +142,23 @@ fn test_auto_config_macro_creation() {
     };
 
     assert_eq!(auto_config.configurator_type, "WebConfigurator");
+    assert_eq!(auto_config.configurators(), vec!["WebConfigurator"]);
+
+    let auto_config = AutoConfigMacro {
+        configurator_type: "WebConfigurator , spring_job :: JobConfigurator".to_string(),
+        range: test_range(),
+    };
+    assert_eq!(
+        auto_config.configurators(),
+        vec!["WebConfigurator", "JobConfigurator"]
+    );
 }
 
 #[test]
 fn test_job_macro_cron() {
     let job = JobMacro::Cron {
         expression: "0 0 * * * *".to_string(),
+        timezone: None,
         range: test_range(),
     };
 
@@ -176,12 +206,16 @@ fn test_spring_macro_variants() {
         struct_name: "MyService".to_string(),
         fields: vec![],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     });
 
     let inject = SpringMacro::Inject(InjectMacro {
         inject_type: InjectType::Component,
         component_name: None,
         range: test_range(),
+        name_range: None,
     });
 
     let route = SpringMacro::Route(RouteMacro {
@@ -191,6 +225,11 @@ fn test_spring_macro_variants() {
         handler_name: "test_handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     });
 
     let auto_config = SpringMacro::AutoConfig(AutoConfigMacro {
@@ -200,6 +239,7 @@ fn test_spring_macro_variants() {
 
     let job = SpringMacro::Job(JobMacro::Cron {
         expression: "0 0 * * * *".to_string(),
+        timezone: None,
         range: test_range(),
     });
 
@@ -250,6 +290,7 @@ fn test_field_without_inject() {
         name: "name".to_string(),
         type_name: "String".to_string(),
         inject: None,
+        range: test_range(),
     };
 
     assert_eq!(field.name, "name");
@@ -266,6 +307,11 @@ fn test_route_macro_multiple_methods() {
         handler_name: "handle_resource".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     assert_eq!(route.methods.len(), 3);
@@ -287,6 +333,11 @@ fn test_route_macro_multiple_middlewares() {
         handler_name: "protected_handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     assert_eq!(route.middlewares.len(), 3);
@@ -489,6 +540,63 @@ fn test_parse_and_extract_workflow() {
     assert!(extract_result.is_ok());
 }
 
+#[test]
+fn test_macro_cache_keyed_by_content() {
+    let analyzer = MacroAnalyzer::with_cache_capacity(2);
+    let extract = |content: &str| {
+        let uri = Url::parse("file:///test.rs").unwrap();
+        analyzer
+            .parse(uri, content.to_string())
+            .and_then(|doc| analyzer.extract_macros(doc))
+            .unwrap()
+            .macros
+            .len()
+    };
+    let service = "#[derive(Service)]\nstruct A {}\n";
+    let route = "#[get(\"/a\")]\nasync fn a() {}\n";
+    let empty = "fn main() {}\n";
+
+    assert_eq!(extract(service), 1);
+    assert_eq!(extract(service), 1);
+    assert_eq!(
+        analyzer.cache_stats(),
+        MacroCacheStats {
+            hits: 1,
+            misses: 1,
+            entries: 1
+        }
+    );
+
+    // 超出容量时淘汰最久未使用的条目（route 之后 service 被再次使用，淘汰 route）
+    assert_eq!(extract(route), 1);
+    assert_eq!(extract(service), 1);
+    assert_eq!(extract(empty), 0);
+    assert_eq!(analyzer.cache_stats().entries, 2);
+    assert_eq!(extract(service), 1);
+    assert_eq!(extract(route), 1);
+    assert_eq!(
+        analyzer.cache_stats(),
+        MacroCacheStats {
+            hits: 3,
+            misses: 4,
+            entries: 2
+        }
+    );
+
+    // 无法解析的内容不缓存
+    let uri = Url::parse("file:///test.rs").unwrap();
+    assert!(analyzer.parse(uri, "fn broken(".to_string()).is_err());
+
+    analyzer.clear_cache();
+    assert_eq!(analyzer.cache_stats().entries, 0);
+
+    let uncached = MacroAnalyzer::with_cache_capacity(0);
+    let uri = Url::parse("file:///test.rs").unwrap();
+    let doc = uncached.parse(uri, service.to_string()).unwrap();
+    assert_eq!(uncached.extract_macros(doc).unwrap().macros.len(), 1);
+    assert_eq!(uncached.cache_stats().entries, 0);
+}
+
 // ============ 宏识别功能测试 ============
 
 #[test]
@@ -674,6 +782,37 @@ fn test_recognize_cron_job_macro() {
     }
 }
 
+#[test]
+fn test_recognize_cron_job_macro_with_timezone() {
+    let analyzer = MacroAnalyzer::new();
+    let uri = Url::parse("file:///test.rs").unwrap();
+    let content = r#"
+        #[cron("0 0 9 * * *", "Asia/Shanghai")]
+        async fn morning_job() {}
+    "#
+    .to_string();
+
+    let doc = analyzer.parse(uri, content).unwrap();
+    let result = analyzer.extract_macros(doc).unwrap();
+
+    match &result.macros[0] {
+        SpringMacro::Job(
+            job @ JobMacro::Cron {
+                expression,
+                timezone,
+                ..
+            },
+        ) => {
+            assert_eq!(expression, "0 0 9 * * *");
+            assert_eq!(timezone.as_deref(), Some("Asia/Shanghai"));
+            assert!(analyzer
+                .hover_macro(&SpringMacro::Job(job.clone()))
+                .contains("**时区**: `Asia/Shanghai`"));
+        }
+        _ => panic!("Expected Cron job macro"),
+    }
+}
+
 #[test]
 fn test_recognize_fix_delay_job_macro() {
     let analyzer = MacroAnalyzer::new();
@@ -724,6 +863,91 @@ fn test_recognize_fix_rate_job_macro() {
     }
 }
 
+#[test]
+fn test_recognize_one_shot_job_macro() {
+    let analyzer = MacroAnalyzer::new();
+    let uri = Url::parse("file:///test.rs").unwrap();
+    let content = r#"
+        #[one_shot(3)]
+        async fn warm_up_cache() {
+            println!("Warming up cache");
+        }
+    "#
+    .to_string();
+
+    let doc = analyzer.parse(uri, content).unwrap();
+    let result = analyzer.extract_macros(doc).unwrap();
+
+    assert_eq!(result.macros.len(), 1);
+
+    match &result.macros[0] {
+        SpringMacro::Job(JobMacro::OneShot { seconds, .. }) => {
+            assert_eq!(*seconds, 3);
+        }
+        _ => panic!("Expected OneShot job macro"),
+    }
+}
+
+#[test]
+fn test_recognize_cfg_attr_route_macro() {
+    let analyzer = MacroAnalyzer::new();
+    let uri = Url::parse("file:///test.rs").unwrap();
+    let content = r#"
+        #[cfg_attr(feature = "web", get("/users"))]
+        async fn list_users() {}
+
+        #[post("/users")]
+        async fn create_user() {}
+    "#
+    .to_string();
+
+    let doc = analyzer.parse(uri, content).unwrap();
+    let result = analyzer.extract_macros(doc).unwrap();
+
+    let routes: Vec<_> = result
+        .macros
+        .iter()
+        .filter_map(|m| match m {
+            SpringMacro::Route(route) => Some((route.path.as_str(), route.cfg.as_deref())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        routes,
+        vec![("/users", Some("feature = \"web\"")), ("/users", None),]
+    );
+}
+
+#[test]
+fn test_hover_route_macro_with_instrument() {
+    let analyzer = MacroAnalyzer::new();
+    let uri = Url::parse("file:///test.rs").unwrap();
+    let content = r#"
+        #[get("/users/{id}")]
+        #[tracing::instrument(name = "users.get", skip(db))]
+        async fn get_user(Component(db): Component<Db>, Path(id): Path<i64>) {}
+    "#
+    .to_string();
+
+    let doc = analyzer.parse(uri, content).unwrap();
+    let result = analyzer.extract_macros(doc).unwrap();
+    let route = result
+        .macros
+        .iter()
+        .find(|m| matches!(m, SpringMacro::Route(_)))
+        .unwrap();
+    let SpringMacro::Route(route_macro) = route else {
+        unreachable!()
+    };
+    let instrument = route_macro.instrument.as_ref().unwrap();
+    assert_eq!(instrument.span_name, "users.get");
+    assert_eq!(instrument.fields, vec!["id"]);
+
+    let hover = analyzer.hover_macro(route);
+    assert!(hover.contains("**追踪 span**: `users.get`（`info`）"));
+    assert!(hover.contains("**记录字段**: `id`"));
+}
+
 #[test]
 fn test_recognize_multiple_macros_in_file() {
     let analyzer = MacroAnalyzer::new();
@@ -791,7 +1015,9 @@ fn test_expand_service_macro() {
                     inject_type: InjectType::Component,
                     component_name: None,
                     range: test_range(),
+                    name_range: None,
                 }),
+                range: test_range(),
             },
             Field {
                 name: "config".to_string(),
@@ -800,10 +1026,15 @@ fn test_expand_service_macro() {
                     inject_type: InjectType::Config,
                     component_name: None,
                     range: test_range(),
+                    name_range: None,
                 }),
+                range: test_range(),
             },
         ],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let analyzer = MacroAnalyzer::new();
@@ -831,7 +1062,9 @@ fn test_expand_service_macro_with_named_component() {
                     inject_type: InjectType::Component,
                     component_name: Some("primary".to_string()),
                     range: test_range(),
+                    name_range: None,
                 }),
+                range: test_range(),
             },
             Field {
                 name: "secondary_db".to_string(),
@@ -840,10 +1073,15 @@ fn test_expand_service_macro_with_named_component() {
                     inject_type: InjectType::Component,
                     component_name: Some("secondary".to_string()),
                     range: test_range(),
+                    name_range: None,
                 }),
+                range: test_range(),
             },
         ],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let analyzer = MacroAnalyzer::new();
@@ -865,8 +1103,12 @@ fn test_expand_service_macro_without_inject() {
             name: "name".to_string(),
             type_name: "String".to_string(),
             inject: None,
+            range: test_range(),
         }],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let analyzer = MacroAnalyzer::new();
@@ -884,6 +1126,7 @@ fn test_expand_inject_macro_component() {
         inject_type: InjectType::Component,
         component_name: None,
         range: test_range(),
+        name_range: None,
     };
 
     let analyzer = MacroAnalyzer::new();
@@ -901,6 +1144,7 @@ fn test_expand_inject_macro_component_with_name() {
         inject_type: InjectType::Component,
         component_name: Some("my_component".to_string()),
         range: test_range(),
+        name_range: None,
     };
 
     let analyzer = MacroAnalyzer::new();
@@ -917,6 +1161,7 @@ fn test_expand_inject_macro_config() {
         inject_type: InjectType::Config,
         component_name: None,
         range: test_range(),
+        name_range: None,
     };
 
     let analyzer = MacroAnalyzer::new();
@@ -953,6 +1198,11 @@ fn test_expand_route_macro_get() {
         handler_name: "get_user".to_string(),
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let analyzer = MacroAnalyzer::new();
@@ -975,6 +1225,11 @@ fn test_expand_route_macro_multiple_methods() {
         handler_name: "handle_resource".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let analyzer = MacroAnalyzer::new();
@@ -994,6 +1249,11 @@ fn test_expand_route_macro_with_middlewares() {
         handler_name: "protected_handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let analyzer = MacroAnalyzer::new();
@@ -1010,6 +1270,7 @@ fn test_expand_route_macro_with_middlewares() {
 fn test_expand_cron_job_macro() {
     let job = JobMacro::Cron {
         expression: "0 0 * * * *".to_string(),
+        timezone: None,
         range: test_range(),
     };
 
@@ -1057,6 +1318,22 @@ fn test_expand_fix_rate_job_macro() {
     assert!(expanded.contains("FixRateJob::new(10"));
 }
 
+#[test]
+fn test_expand_one_shot_job_macro() {
+    let job = JobMacro::OneShot {
+        seconds: 3,
+        range: test_range(),
+    };
+
+    let analyzer = MacroAnalyzer::new();
+    let expanded = analyzer.expand_macro(&SpringMacro::Job(job));
+
+    // 验证展开的代码包含 OneShot 任务信息
+    assert!(expanded.contains("任务类型: OneShot"));
+    assert!(expanded.contains("延迟秒数: 3"));
+    assert!(expanded.contains("OneShotJob::new(3"));
+}
+
 #[test]
 fn test_expand_macro_produces_valid_syntax() {
     // 测试所有宏展开都生成语法正确的代码（至少是有效的注释）
@@ -1066,6 +1343,9 @@ fn test_expand_macro_produces_valid_syntax() {
         struct_name: "TestService".to_string(),
         fields: vec![],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     });
 
     let expanded = analyzer.expand_macro(&service);
@@ -1083,6 +1363,9 @@ fn test_expand_empty_service() {
         struct_name: "EmptyService".to_string(),
         fields: vec![],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let analyzer = MacroAnalyzer::new();
@@ -1104,11 +1387,15 @@ fn test_expand_all_macro_types() {
             struct_name: "TestService".to_string(),
             fields: vec![],
             range: test_range(),
+            doc: None,
+            deprecated: false,
+            cfg: None,
         }),
         SpringMacro::Inject(InjectMacro {
             inject_type: InjectType::Component,
             component_name: None,
             range: test_range(),
+            name_range: None,
         }),
         SpringMacro::AutoConfig(AutoConfigMacro {
             configurator_type: "TestConfigurator".to_string(),
@@ -1121,9 +1408,15 @@ fn test_expand_all_macro_types() {
             handler_name: "test_handler".to_string(),
             range: test_range(),
             is_openapi: false,
+            doc: None,
+            deprecated: false,
+            stub: false,
+            cfg: None,
+            instrument: None,
         }),
         SpringMacro::Job(JobMacro::Cron {
             expression: "0 0 * * * *".to_string(),
+            timezone: None,
             range: test_range(),
         }),
     ];
@@ -1224,9 +1517,14 @@ fn test_expand_macro_readability() {
                 inject_type: InjectType::Component,
                 component_name: None,
                 range: test_range(),
+                name_range: None,
             }),
+            range: test_range(),
         }],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let expanded = analyzer.expand_macro(&SpringMacro::DeriveService(service));
@@ -1384,7 +1682,9 @@ fn test_hover_service_macro() {
                     inject_type: InjectType::Component,
                     component_name: None,
                     range: test_range(),
+                    name_range: None,
                 }),
+                range: test_range(),
             },
             Field {
                 name: "config".to_string(),
@@ -1393,10 +1693,15 @@ fn test_hover_service_macro() {
                     inject_type: InjectType::Config,
                     component_name: None,
                     range: test_range(),
+                    name_range: None,
                 }),
+                range: test_range(),
             },
         ],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::DeriveService(service));
@@ -1426,9 +1731,14 @@ fn test_hover_service_macro_with_named_component() {
                 inject_type: InjectType::Component,
                 component_name: Some("primary".to_string()),
                 range: test_range(),
+                name_range: None,
             }),
+            range: test_range(),
         }],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::DeriveService(service));
@@ -1446,6 +1756,9 @@ fn test_hover_service_macro_empty_fields() {
         struct_name: "EmptyService".to_string(),
         fields: vec![],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::DeriveService(service));
@@ -1463,6 +1776,7 @@ fn test_hover_inject_macro_component() {
         inject_type: InjectType::Component,
         component_name: None,
         range: test_range(),
+        name_range: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::Inject(inject));
@@ -1483,6 +1797,7 @@ fn test_hover_inject_macro_component_with_name() {
         inject_type: InjectType::Component,
         component_name: Some("my_component".to_string()),
         range: test_range(),
+        name_range: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::Inject(inject));
@@ -1501,6 +1816,7 @@ fn test_hover_inject_macro_config() {
         inject_type: InjectType::Config,
         component_name: None,
         range: test_range(),
+        name_range: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::Inject(inject));
@@ -1539,6 +1855,11 @@ fn test_hover_route_macro() {
         handler_name: "get_user".to_string(),
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::Route(route));
@@ -1563,6 +1884,11 @@ fn test_hover_route_macro_multiple_methods() {
         handler_name: "handle_resource".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::Route(route));
@@ -1582,6 +1908,11 @@ fn test_hover_route_macro_with_middlewares() {
         handler_name: "protected_handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::Route(route));
@@ -1597,6 +1928,7 @@ fn test_hover_cron_job_macro() {
     let analyzer = MacroAnalyzer::new();
     let job = JobMacro::Cron {
         expression: "0 0 * * * *".to_string(),
+        timezone: None,
         range: test_range(),
     };
 
@@ -1644,6 +1976,25 @@ fn test_hover_fix_rate_job_macro() {
     assert!(hover.contains("10"));
 }
 
+#[test]
+fn test_hover_one_shot_job_macro() {
+    let analyzer = MacroAnalyzer::new();
+    let job = JobMacro::OneShot {
+        seconds: 0,
+        range: test_range(),
+    };
+
+    let hover = analyzer.hover_macro(&SpringMacro::Job(job.clone()));
+
+    // 验证悬停提示包含 OneShot 任务信息
+    assert!(hover.contains("# 任务调度宏"));
+    assert!(hover.contains("一次性任务"));
+    assert!(hover.contains("延迟秒数"));
+
+    // 延迟为 0 表示启动后立即执行，不产生诊断
+    assert!(analyzer.validate_macro(&SpringMacro::Job(job)).is_empty());
+}
+
 #[test]
 fn test_hover_all_macro_types() {
     let analyzer = MacroAnalyzer::new();
@@ -1654,11 +2005,15 @@ fn test_hover_all_macro_types() {
             struct_name: "TestService".to_string(),
             fields: vec![],
             range: test_range(),
+            doc: None,
+            deprecated: false,
+            cfg: None,
         }),
         SpringMacro::Inject(InjectMacro {
             inject_type: InjectType::Component,
             component_name: None,
             range: test_range(),
+            name_range: None,
         }),
         SpringMacro::AutoConfig(AutoConfigMacro {
             configurator_type: "TestConfigurator".to_string(),
@@ -1671,9 +2026,15 @@ fn test_hover_all_macro_types() {
             handler_name: "test_handler".to_string(),
             range: test_range(),
             is_openapi: false,
+            doc: None,
+            deprecated: false,
+            stub: false,
+            cfg: None,
+            instrument: None,
         }),
         SpringMacro::Job(JobMacro::Cron {
             expression: "0 0 * * * *".to_string(),
+            timezone: None,
             range: test_range(),
         }),
     ];
@@ -1704,9 +2065,14 @@ fn test_hover_markdown_format() {
                 inject_type: InjectType::Component,
                 component_name: None,
                 range: test_range(),
+                name_range: None,
             }),
+            range: test_range(),
         }],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::DeriveService(service));
@@ -1783,6 +2149,7 @@ fn test_hover_readability() {
         inject_type: InjectType::Component,
         component_name: Some("my_db".to_string()),
         range: test_range(),
+        name_range: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::Inject(inject));
@@ -1816,7 +2183,9 @@ fn test_hover_service_with_mixed_fields() {
                     inject_type: InjectType::Component,
                     component_name: None,
                     range: test_range(),
+                    name_range: None,
                 }),
+                range: test_range(),
             },
             Field {
                 name: "cache".to_string(),
@@ -1825,7 +2194,9 @@ fn test_hover_service_with_mixed_fields() {
                     inject_type: InjectType::Component,
                     component_name: Some("redis".to_string()),
                     range: test_range(),
+                    name_range: None,
                 }),
+                range: test_range(),
             },
             Field {
                 name: "config".to_string(),
@@ -1834,15 +2205,21 @@ fn test_hover_service_with_mixed_fields() {
                     inject_type: InjectType::Config,
                     component_name: None,
                     range: test_range(),
+                    name_range: None,
                 }),
+                range: test_range(),
             },
             Field {
                 name: "name".to_string(),
                 type_name: "String".to_string(),
                 inject: None,
+                range: test_range(),
             },
         ],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let hover = analyzer.hover_macro(&SpringMacro::DeriveService(service));
@@ -1873,9 +2250,14 @@ fn test_validate_service_macro_valid() {
                 inject_type: InjectType::Component,
                 component_name: None,
                 range: test_range(),
+                name_range: None,
             }),
+            range: test_range(),
         }],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::DeriveService(service));
@@ -1896,9 +2278,14 @@ fn test_validate_service_macro_empty_component_name() {
                 inject_type: InjectType::Component,
                 component_name: Some("".to_string()), // 空字符串
                 range: test_range(),
+                name_range: None,
             }),
+            range: test_range(),
         }],
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        cfg: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::DeriveService(service));
@@ -1919,6 +2306,7 @@ fn test_validate_inject_macro_config_with_name() {
         inject_type: InjectType::Config,
         component_name: Some("my_config".to_string()), // Config 不应该有组件名称
         range: test_range(),
+        name_range: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Inject(inject));
@@ -1941,6 +2329,7 @@ fn test_validate_inject_macro_component_valid() {
         inject_type: InjectType::Component,
         component_name: Some("my_component".to_string()),
         range: test_range(),
+        name_range: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Inject(inject));
@@ -1956,6 +2345,7 @@ fn test_validate_inject_macro_config_valid() {
         inject_type: InjectType::Config,
         component_name: None,
         range: test_range(),
+        name_range: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Inject(inject));
@@ -2007,6 +2397,11 @@ fn test_validate_route_macro_empty_path() {
         handler_name: "handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2028,6 +2423,11 @@ fn test_validate_route_macro_path_without_slash() {
         handler_name: "handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2049,6 +2449,11 @@ fn test_validate_route_macro_no_methods() {
         handler_name: "handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2070,6 +2475,11 @@ fn test_validate_route_macro_empty_handler_name() {
         handler_name: "".to_string(), // 空处理器名称
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2091,6 +2501,11 @@ fn test_validate_route_macro_valid() {
         handler_name: "get_user".to_string(),
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2109,6 +2524,11 @@ fn test_validate_route_macro_nested_braces() {
         handler_name: "handler".to_string(),
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2128,6 +2548,11 @@ fn test_validate_route_macro_unmatched_closing_brace() {
         handler_name: "handler".to_string(),
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2147,6 +2572,11 @@ fn test_validate_route_macro_unclosed_brace() {
         handler_name: "handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2166,6 +2596,11 @@ fn test_validate_route_macro_empty_param_name() {
         handler_name: "handler".to_string(),
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2187,6 +2622,11 @@ fn test_validate_route_macro_invalid_param_name() {
         handler_name: "handler".to_string(),
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2198,6 +2638,41 @@ fn test_validate_route_macro_invalid_param_name() {
         .any(|d| d.message.contains("只能包含字母、数字和下划线")));
 }
 
+#[test]
+fn test_validate_route_macro_param_constraints() {
+    let analyzer = MacroAnalyzer::new();
+    let route = |path: &str| {
+        SpringMacro::Route(RouteMacro {
+            path: path.to_string(),
+            methods: vec![HttpMethod::Get],
+            middlewares: vec![],
+            handler_name: "handler".to_string(),
+            is_openapi: false,
+            range: test_range(),
+            doc: None,
+            deprecated: false,
+            stub: false,
+            cfg: None,
+            instrument: None,
+        })
+    };
+
+    // 正则约束（包括约束中的大括号）和通配参数不再被视为非法字符
+    let diagnostics = analyzer.validate_macro(&route(r"/users/{id:\d+}/codes/{code:[A-Z]{3}}"));
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    assert!(analyzer
+        .validate_macro(&route("/static/{*path}"))
+        .is_empty());
+
+    // 非法的正则约束
+    let diagnostics = analyzer.validate_macro(&route(r"/users/{id:\d+(}"));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].code,
+        Some(lsp_types::NumberOrString::String("E019".to_string()))
+    );
+}
+
 #[test]
 fn test_validate_route_macro_valid_param_names() {
     let analyzer = MacroAnalyzer::new();
@@ -2208,6 +2683,11 @@ fn test_validate_route_macro_valid_param_names() {
         handler_name: "handler".to_string(),
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2221,6 +2701,7 @@ fn test_validate_cron_job_empty_expression() {
     let analyzer = MacroAnalyzer::new();
     let job = JobMacro::Cron {
         expression: "".to_string(), // 空表达式
+        timezone: None,
         range: test_range(),
     };
 
@@ -2238,6 +2719,7 @@ fn test_validate_cron_job_invalid_parts() {
     let analyzer = MacroAnalyzer::new();
     let job = JobMacro::Cron {
         expression: "0 0 *".to_string(), // 只有 3 个部分，应该有 6 个
+        timezone: None,
         range: test_range(),
     };
 
@@ -2255,6 +2737,7 @@ fn test_validate_cron_job_valid() {
     let analyzer = MacroAnalyzer::new();
     let job = JobMacro::Cron {
         expression: "0 0 * * * *".to_string(), // 有效的 cron 表达式
+        timezone: None,
         range: test_range(),
     };
 
@@ -2264,6 +2747,33 @@ fn test_validate_cron_job_valid() {
     assert_eq!(diagnostics.len(), 0);
 }
 
+#[test]
+fn test_validate_cron_job_timezone() {
+    let analyzer = MacroAnalyzer::new();
+    let job = |timezone: &str| {
+        SpringMacro::Job(JobMacro::Cron {
+            expression: "0 0 * * * *".to_string(),
+            timezone: Some(timezone.to_string()),
+            range: test_range(),
+        })
+    };
+
+    assert!(analyzer.validate_macro(&job("UTC")).is_empty());
+    assert!(analyzer.validate_macro(&job("Asia/Shanghai")).is_empty());
+
+    let diagnostics = analyzer.validate_macro(&job("Asia/Atlantis"));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].code,
+        Some(lsp_types::NumberOrString::String("E030".to_string()))
+    );
+    assert_eq!(
+        diagnostics[0].severity,
+        Some(lsp_types::DiagnosticSeverity::ERROR)
+    );
+    assert!(diagnostics[0].message.contains("'Asia/Atlantis'"));
+}
+
 #[test]
 fn test_validate_fix_delay_job_zero_seconds() {
     let analyzer = MacroAnalyzer::new();
@@ -2340,11 +2850,15 @@ fn test_validate_all_macro_types() {
             struct_name: "TestService".to_string(),
             fields: vec![],
             range: test_range(),
+            doc: None,
+            deprecated: false,
+            cfg: None,
         }),
         SpringMacro::Inject(InjectMacro {
             inject_type: InjectType::Component,
             component_name: None,
             range: test_range(),
+            name_range: None,
         }),
         SpringMacro::AutoConfig(AutoConfigMacro {
             configurator_type: "TestConfigurator".to_string(),
@@ -2357,9 +2871,15 @@ fn test_validate_all_macro_types() {
             handler_name: "test_handler".to_string(),
             range: test_range(),
             is_openapi: false,
+            doc: None,
+            deprecated: false,
+            stub: false,
+            cfg: None,
+            instrument: None,
         }),
         SpringMacro::Job(JobMacro::Cron {
             expression: "0 0 * * * *".to_string(),
+            timezone: None,
             range: test_range(),
         }),
     ];
@@ -2429,6 +2949,11 @@ fn test_validate_multiple_errors() {
         handler_name: "".to_string(), // 空处理器名称
         is_openapi: false,
         range: test_range(),
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics = analyzer.validate_macro(&SpringMacro::Route(route));
@@ -2474,6 +2999,11 @@ fn test_validate_error_codes() {
         handler_name: "handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let route2 = RouteMacro {
@@ -2483,6 +3013,11 @@ fn test_validate_error_codes() {
         handler_name: "handler".to_string(),
         range: test_range(),
         is_openapi: false,
+        doc: None,
+        deprecated: false,
+        stub: false,
+        cfg: None,
+        instrument: None,
     };
 
     let diagnostics1 = analyzer.validate_macro(&SpringMacro::Route(route1));
@@ -2496,3 +3031,296 @@ fn test_validate_error_codes() {
         assert_ne!(code1, code2);
     }
 }
+
+#[test]
+fn test_extract_doc_comments() {
+    let analyzer = MacroAnalyzer::new();
+    let uri = Url::parse("file:///test.rs").unwrap();
+    let content = r#"
+/// 用户服务
+///
+/// 负责用户的增删改查
+#[derive(Service)]
+struct UserService {}
+
+/// 获取用户详情
+#[get("/users/{id}")]
+async fn get_user() {}
+
+#[post("/users")]
+async fn create_user() {}
+"#
+    .to_string();
+
+    let doc = analyzer.parse(uri, content).unwrap();
+    let doc = analyzer.extract_macros(doc).unwrap();
+    assert_eq!(doc.macros.len(), 3);
+
+    let SpringMacro::DeriveService(service) = &doc.macros[0] else {
+        panic!("Expected DeriveService macro");
+    };
+    assert_eq!(
+        service.doc.as_deref(),
+        Some("用户服务\n\n负责用户的增删改查")
+    );
+
+    let SpringMacro::Route(route) = &doc.macros[1] else {
+        panic!("Expected Route macro");
+    };
+    assert_eq!(route.doc.as_deref(), Some("获取用户详情"));
+    let hover = analyzer.hover_macro(&doc.macros[1]);
+    assert!(hover.contains("获取用户详情"));
+    assert!(!hover.contains("注册 HTTP 路由处理器"));
+
+    let SpringMacro::Route(route) = &doc.macros[2] else {
+        panic!("Expected Route macro");
+    };
+    assert!(route.doc.is_none());
+}
+
+#[test]
+fn test_extract_deprecated() {
+    let analyzer = MacroAnalyzer::new();
+    let uri = Url::parse("file:///test.rs").unwrap();
+    let content = r#"
+#[deprecated(note = "使用 NewService")]
+#[derive(Service)]
+struct OldService {}
+
+#[deprecated]
+#[get("/v1/users")]
+async fn list_users_v1() {}
+
+#[get("/v2/users")]
+async fn list_users() {}
+"#
+    .to_string();
+
+    let doc = analyzer.parse(uri, content).unwrap();
+    let doc = analyzer.extract_macros(doc).unwrap();
+    let deprecated: Vec<_> = doc.macros.iter().map(SpringMacro::is_deprecated).collect();
+    assert_eq!(deprecated, vec![true, true, false]);
+    assert!(analyzer.hover_macro(&doc.macros[1]).contains("**已弃用**"));
+}
+
+#[test]
+fn test_hover_service_with_type_docs() {
+    let analyzer = MacroAnalyzer::new();
+    let content = r#"
+/// 用户数据访问
+///
+/// 封装用户表的查询
+struct UserRepo;
+
+mod cache {
+    /// 分布式缓存客户端
+    pub enum CacheClient {}
+}
+
+struct NoDocs;
+
+#[derive(Clone, Service)]
+struct UserService {
+    #[inject(component)]
+    repo: UserRepo,
+    #[inject(component)]
+    cache: Arc<CacheClient>,
+    #[inject(component)]
+    other: NoDocs,
+}
+"#;
+
+    let type_docs = analyzer.type_doc_summaries(content);
+    assert_eq!(type_docs.len(), 2);
+    assert_eq!(type_docs["UserRepo"], "用户数据访问");
+    assert_eq!(type_docs["CacheClient"], "分布式缓存客户端");
+
+    let doc = analyzer
+        .parse(Url::parse("file:///test.rs").unwrap(), content.to_string())
+        .unwrap();
+    let doc = analyzer.extract_macros(doc).unwrap();
+    let SpringMacro::DeriveService(service) = &doc.macros[0] else {
+        panic!("expected Service macro");
+    };
+
+    let hover = analyzer.hover_service(service, &type_docs);
+    assert!(hover.contains("`repo`: `UserRepo` - 注入组件 — 用户数据访问\n"));
+    assert!(hover.contains("`cache`: `CacheClient` - 注入组件 — 分布式缓存客户端\n"));
+    assert!(hover.contains("`other`: `NoDocs` - 注入组件\n"));
+    assert!(!analyzer
+        .hover_macro(&doc.macros[0])
+        .contains("用户数据访问"));
+}
+
+#[test]
+fn test_validate_inject_attributes() {
+    let analyzer = MacroAnalyzer::new();
+    let content = r#"
+#[derive(Clone, Service)]
+struct UserService {
+    #[inject(component)]
+    #[inject(component = "primary")]
+    db: ConnectPool,
+    #[inject(config)]
+    config: UserConfig,
+}
+
+#[derive(Clone, Service)]
+struct Wrapper(#[inject(component)] ConnectPool);
+
+#[derive(Clone)]
+struct Plain {
+    #[inject(component)]
+    db: ConnectPool,
+}
+
+enum Either {
+    Left(#[inject(component)] ConnectPool),
+}
+"#;
+
+    let diagnostics = analyzer.validate_inject_attributes(&PositionMapper::new(content));
+    let codes: Vec<_> = diagnostics
+        .iter()
+        .filter_map(|d| match &d.code {
+            Some(lsp_types::NumberOrString::String(code)) => Some(code.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(codes, vec!["E024", "E025", "E026", "E026"]);
+
+    // 范围是出错的属性本身
+    assert_eq!(diagnostics[0].range.start, Position::new(4, 4));
+    assert_eq!(diagnostics[0].range.end, Position::new(4, 36));
+    assert!(diagnostics[0].message.contains("'db'"));
+    assert_eq!(diagnostics[1].range.start, Position::new(11, 15));
+    assert!(diagnostics[2].message.contains("'Plain'"));
+    assert!(diagnostics[3].message.contains("'Either'"));
+}
+
+#[test]
+fn test_diagnostics_use_precise_ranges() {
+    let analyzer = MacroAnalyzer::new();
+    let content = r#"#[derive(Clone, Service)]
+struct UserService {
+    #[inject(config = "primary")]
+    config: UserConfig,
+}
+
+#[component]
+fn create_pool(a: Config<DbConfig>, b: Config<DbConfig>) -> Pool {}
+"#;
+    let doc = analyzer
+        .parse(Url::parse("file:///test.rs").unwrap(), content.to_string())
+        .unwrap();
+    let doc = analyzer.extract_macros(doc).unwrap();
+
+    let SpringMacro::DeriveService(service) = &doc.macros[0] else {
+        panic!("expected Service macro");
+    };
+    assert_eq!(service.fields[0].range.start, Position::new(3, 4));
+    let diagnostics = analyzer.validate_macro(&doc.macros[0]);
+    assert_eq!(diagnostics.len(), 1);
+    // 只标出组件名称，而不是整个属性或结构体
+    assert_eq!(diagnostics[0].range.start, Position::new(2, 22));
+    assert_eq!(diagnostics[0].range.end, Position::new(2, 31));
+
+    let diagnostics = analyzer.validate_macro(&doc.macros[1]);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start, Position::new(7, 39));
+    assert_eq!(diagnostics[0].range.end, Position::new(7, 55));
+}
+
+#[test]
+fn test_config_inject_with_name_fixes() {
+    let analyzer = MacroAnalyzer::new();
+    let content = r#"#[derive(Clone, Service)]
+struct UserService {
+    #[inject(config = "primary")]
+    config: UserConfig,
+    #[inject(config = "replica")]
+    db: ConnectPool,
+}
+"#;
+    let doc = analyzer
+        .parse(Url::parse("file:///test.rs").unwrap(), content.to_string())
+        .unwrap();
+    let doc = analyzer.extract_macros(doc).unwrap();
+    let diagnostics = analyzer.validate_macro(&doc.macros[0]);
+    assert_eq!(diagnostics.len(), 2);
+
+    // 配置类型只提供移除名称的修复
+    let fixes = QuickFix::all_from_diagnostic(&diagnostics[0]);
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(fixes[0].new_text, "#[inject(config)]");
+    assert_eq!(fixes[0].range.unwrap().start, Position::new(2, 4));
+
+    let fixes = QuickFix::all_from_diagnostic(&diagnostics[1]);
+    assert_eq!(fixes.len(), 2);
+    assert_eq!(fixes[1].new_text, "#[inject(component = \"replica\")]");
+    assert_eq!(
+        QuickFix::from_diagnostic(&diagnostics[1]),
+        Some(fixes[0].clone())
+    );
+}
+
+#[test]
+fn test_expand_document() {
+    let analyzer = MacroAnalyzer::new();
+    let content = r#"use spring::plugin::service::Service;
+
+/// 用户服务
+#[derive(Clone, Service)]
+struct UserService {
+    #[inject(component)]
+    db: ConnectPool,
+}
+
+#[get("/users")]
+async fn list_users() -> impl IntoResponse {
+    "users"
+}
+
+fn helper() {}"#;
+    let doc = analyzer
+        .parse(Url::parse("file:///test.rs").unwrap(), content.to_string())
+        .unwrap();
+    let doc = analyzer.extract_macros(doc).unwrap();
+
+    let (expanded, count) = analyzer.expand_document(&doc).unwrap();
+    assert_eq!(count, 2);
+    let lines: Vec<&str> = expanded.lines().collect();
+
+    // 展开代码插入在结构体之后，不包含原始定义
+    assert_eq!(lines[7], "}");
+    assert_eq!(lines[8], "// >>> Service 派生宏展开");
+    assert_eq!(lines[9], "impl UserService {");
+    assert!(expanded.contains("let db = app.get_component::<ConnectPool>()?;"));
+    assert!(!expanded.contains("// 原始定义"));
+
+    let route_start = lines
+        .iter()
+        .position(|line| *line == "// >>> 路由宏展开")
+        .unwrap();
+    assert_eq!(lines[route_start - 1], "}");
+    assert_eq!(
+        lines[route_start - 3],
+        "async fn list_users() -> impl IntoResponse {"
+    );
+    assert!(expanded.contains("// router.route(\"/users\", get, list_users);"));
+    assert!(expanded.ends_with("// <<< 路由宏展开结束\n\nfn helper() {}"));
+
+    // 去掉插入的部分后与原始内容相同
+    let mut original = String::new();
+    let mut inserted = false;
+    for line in expanded.split_inclusive('\n') {
+        if line.starts_with("// >>> ") {
+            inserted = true;
+        } else if line.starts_with("// <<< ") {
+            inserted = false;
+        } else if !inserted {
+            original.push_str(line);
+        }
+    }
+    assert_eq!(original, content);
+}
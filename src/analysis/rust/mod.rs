@@ -1,7 +1,22 @@
 //! Rust 代码分析模块
 //!
-//! 负责 Rust 代码的解析和宏分析
+//! 负责 Rust 代码的解析、宏分析、路由处理器分析、Router 路由注册识别、路由路径解析、路由整理、模块路径计算、条件编译属性展开、追踪属性读取、任务调度时间线、提取器类型定义、spring 构造的重命名、新建文件模板和时区名称检查
 
+pub mod cfg;
+pub mod file_template;
+pub mod handler_analyzer;
+pub mod instrument;
+pub mod job_timeline;
 pub mod macro_analyzer;
+pub mod module_path;
+pub mod rename;
+pub mod route_organizer;
+pub mod route_path;
+pub mod router_calls;
+pub mod timezone;
+pub mod type_definition;
 
+pub use handler_analyzer::HandlerAnalyzer;
 pub use macro_analyzer::MacroAnalyzer;
+pub use route_organizer::RouteOrganizer;
+pub use route_path::{parse_route_path, RoutePath};
@@ -0,0 +1,214 @@
+//! 模块路径模块
+//!
+//! 根据 Rust 文件在 `src` 目录下的位置计算模块路径：
+//! `src/api/users.rs` 和 `src/api/users/mod.rs` 都对应 `crate::api::users`，
+//! `src/lib.rs` 和 `src/main.rs` 对应 crate 根模块。
+//!
+//! 索引中的符号、路由处理器和组件使用模块路径加名称作为完整路径（如 `crate::api::users::list`），
+//! 避免不同模块中的同名条目相互混淆。
+//!
+//! 文件重命名或移动时，用于查找并更新代码中以 `crate::` 开头的模块路径引用，
+//! 以及检查新位置的父模块是否声明了 `mod`。
+
+use std::path::{Path, PathBuf};
+
+use lsp_types::{Range, TextEdit, Url};
+use regex::Regex;
+
+use crate::core::document::PositionMapper;
+
+/// 文件的模块路径（不含 `crate`）
+///
+/// 文件不在 `src` 目录下（或位于 `src/bin` 等独立 crate 根目录下）时返回 `None`
+pub fn module_path(file: &Path) -> Option<Vec<String>> {
+    if file.extension().is_none_or(|ext| ext != "rs") {
+        return None;
+    }
+
+    let components: Vec<_> = file
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let src = components.iter().rposition(|c| c == "src")?;
+    let mut segments = components[src + 1..].to_vec();
+    if segments.first().is_some_and(|s| s == "bin") {
+        return None;
+    }
+
+    let file_name = segments.pop()?;
+    let stem = file_name.strip_suffix(".rs")?;
+    match stem {
+        "lib" | "main" if segments.is_empty() => {}
+        "mod" => {}
+        _ => segments.push(stem.to_string()),
+    }
+
+    segments
+        .iter()
+        .all(|s| is_identifier(s))
+        .then_some(segments)
+}
+
+/// 以 `crate::` 开头的模块路径字符串
+pub fn display_path(module: &[String]) -> String {
+    std::iter::once("crate")
+        .chain(module.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// 文件中定义的条目的完整路径（如 `src/api/users.rs` 中的 `list` 为 `crate::api::users::list`）
+///
+/// 文件不属于 crate 的模块树时返回 `None`
+pub fn qualified_name(file: &Path, item: &str) -> Option<String> {
+    let module = module_path(file)?;
+    Some(format!("{}::{}", display_path(&module), item))
+}
+
+/// URI 对应文件中定义的条目的完整路径
+pub fn qualified_name_for_uri(uri: &Url, item: &str) -> Option<String> {
+    qualified_name(&uri.to_file_path().ok()?, item)
+}
+
+/// 查找代码中对模块的引用（`crate::a::b` 及其子路径，如 `crate::a::b::Foo`）
+///
+/// 返回 `crate::a::b` 部分的范围
+pub fn find_references(mapper: &PositionMapper, module: &[String]) -> Vec<Range> {
+    if module.is_empty() {
+        return Vec::new();
+    }
+
+    let pattern = module
+        .iter()
+        .map(|segment| regex::escape(segment))
+        .collect::<Vec<_>>()
+        .join(r"\s*::\s*");
+    let regex = Regex::new(&format!(r"\bcrate\s*::\s*{}\b", pattern)).expect("valid regex");
+
+    let content = mapper.content();
+    regex
+        .find_iter(content)
+        .filter(|m| !content[..m.start()].ends_with('$'))
+        .map(|m| mapper.range(m.range()))
+        .collect()
+}
+
+/// 把对 `old` 模块的引用改为 `new` 模块的文本编辑
+pub fn rename_edits(lines: &PositionMapper, old: &[String], new: &[String]) -> Vec<TextEdit> {
+    let new_text = display_path(new);
+    find_references(lines, old)
+        .into_iter()
+        .map(|range| TextEdit {
+            range,
+            new_text: new_text.clone(),
+        })
+        .collect()
+}
+
+/// 声明模块的父模块文件的候选位置
+///
+/// `crate::api::users` 需要在 `src/api.rs` 或 `src/api/mod.rs` 中声明，
+/// `crate::users` 需要在 `src/lib.rs` 或 `src/main.rs` 中声明。
+pub fn parent_module_files(src_dir: &Path, module: &[String]) -> Vec<PathBuf> {
+    let Some((_, parent)) = module.split_last() else {
+        return Vec::new();
+    };
+    if parent.is_empty() {
+        return vec![src_dir.join("lib.rs"), src_dir.join("main.rs")];
+    }
+
+    let dir = parent
+        .iter()
+        .fold(src_dir.to_path_buf(), |dir, s| dir.join(s));
+    vec![dir.with_extension("rs"), dir.join("mod.rs")]
+}
+
+/// 代码中是否声明了子模块 `mod name;` 或 `mod name { ... }`
+pub fn declares_module(content: &str, name: &str) -> bool {
+    Regex::new(&format!(r"\bmod\s+{}\s*[;{{]", regex::escape(name)))
+        .expect("valid regex")
+        .is_match(content)
+}
+
+fn is_identifier(segment: &str) -> bool {
+    let mut chars = segment.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Position;
+
+    fn segments(path: &str) -> Vec<String> {
+        path.split("::").map(String::from).collect()
+    }
+
+    #[test]
+    fn test_module_path() {
+        let path = |p: &str| module_path(Path::new(p));
+        assert_eq!(path("/app/src/api/users.rs"), Some(segments("api::users")));
+        assert_eq!(
+            path("/app/src/api/users/mod.rs"),
+            Some(segments("api::users"))
+        );
+        assert_eq!(path("/app/src/lib.rs"), Some(vec![]));
+        assert_eq!(path("/app/src/api/main.rs"), Some(segments("api::main")));
+        assert_eq!(path("/app/src/bin/tool.rs"), None);
+        assert_eq!(path("/app/build.rs"), None);
+        assert_eq!(path("/app/src/my-module.rs"), None);
+    }
+
+    #[test]
+    fn test_qualified_name() {
+        assert_eq!(
+            qualified_name(Path::new("/app/src/api/users.rs"), "list").as_deref(),
+            Some("crate::api::users::list")
+        );
+        assert_eq!(
+            qualified_name(Path::new("/app/src/main.rs"), "index").as_deref(),
+            Some("crate::index")
+        );
+        assert!(qualified_name(Path::new("/app/src/bin/tool.rs"), "index").is_none());
+        let uri = Url::parse("file:///app/src/services/mod.rs").unwrap();
+        assert_eq!(
+            qualified_name_for_uri(&uri, "UserService").as_deref(),
+            Some("crate::services::UserService")
+        );
+    }
+
+    #[test]
+    fn test_rename_references() {
+        let content = "use crate::handlers::{create, list};\n\
+                       use crate::handlers_v2::list;\n\
+                       fn f() { crate :: handlers::list(); }\n\
+                       macro_rules! m { () => { $crate::handlers::list() } }\n";
+        let old = segments("handlers");
+        let new = segments("api::users");
+
+        let edits = rename_edits(&PositionMapper::new(content), &old, &new);
+        assert_eq!(edits.len(), 2);
+        assert_eq!(edits[0].range.start, Position::new(0, 4));
+        assert_eq!(edits[0].range.end, Position::new(0, 19));
+        assert_eq!(edits[0].new_text, "crate::api::users");
+        assert_eq!(edits[1].range.start.line, 2);
+    }
+
+    #[test]
+    fn test_parent_module_declaration() {
+        let src = Path::new("/app/src");
+        assert_eq!(
+            parent_module_files(src, &segments("api::users")),
+            vec![src.join("api.rs"), src.join("api/mod.rs")]
+        );
+        assert_eq!(
+            parent_module_files(src, &segments("users")),
+            vec![src.join("lib.rs"), src.join("main.rs")]
+        );
+
+        assert!(declares_module("pub mod users;\n", "users"));
+        assert!(declares_module("mod users {\n}\n", "users"));
+        assert!(!declares_module("mod users_v2;\n", "users"));
+    }
+}
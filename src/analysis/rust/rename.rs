@@ -0,0 +1,479 @@
+//! 重命名模块
+//!
+//! `textDocument/prepareRename` 判断光标处的 spring 构造能否重命名：
+//! - HTTP 方法宏（`#[get]`）、`method = "GET"` 参数和其他 spring 宏名称不能重命名，返回原因
+//! - 路由路径参数（`{id}`）及其在处理器中的绑定可以重命名，同时修改路由路径和处理器中的同名标识符
+//! - 指定名称的组件注入（`#[inject(component = "primary")]`）可以重命名，修改项目中引用该名称的所有注入
+//!
+//! 其余位置不处理，由 rust-analyzer 负责。
+
+use lsp_types::{Position, Range, TextEdit};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+use crate::analysis::rust::handler_analyzer::ROUTE_ATTRIBUTES;
+use crate::core::document::PositionMapper;
+use crate::core::messages::{localized, Locale};
+use crate::scanner::job::JOB_ATTRIBUTES;
+
+/// HTTP 方法名称（路由宏名称去掉 `_api` 后缀）
+const HTTP_METHODS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "trace", "connect",
+];
+
+/// 除路由宏和任务宏之外的 spring 宏
+const SPRING_ATTRIBUTES: &[&str] = &[
+    "component",
+    "inject",
+    "config_prefix",
+    "middlewares",
+    "auto_config",
+    "stream_listener",
+];
+
+/// 可以重命名的 spring 构造
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameTarget {
+    /// 路由路径参数
+    PathParam {
+        /// 参数名称
+        name: String,
+        /// 光标处名称的范围
+        range: Range,
+        /// 所在处理器函数的范围
+        handler: Range,
+    },
+    /// 指定名称的组件注入
+    ComponentName {
+        /// 组件名称
+        name: String,
+        /// 光标处名称（不含引号）的范围
+        range: Range,
+    },
+}
+
+impl RenameTarget {
+    /// 当前名称
+    pub fn name(&self) -> &str {
+        match self {
+            RenameTarget::PathParam { name, .. } | RenameTarget::ComponentName { name, .. } => name,
+        }
+    }
+
+    /// 光标处名称的范围
+    pub fn range(&self) -> Range {
+        match self {
+            RenameTarget::PathParam { range, .. } | RenameTarget::ComponentName { range, .. } => {
+                *range
+            }
+        }
+    }
+
+    /// 新名称是否有效：路径参数必须是标识符，组件名称不能为空或包含引号
+    pub fn validate_name(&self, new_name: &str, locale: Locale) -> Result<(), String> {
+        let valid = match self {
+            RenameTarget::PathParam { .. } => is_identifier(new_name),
+            RenameTarget::ComponentName { .. } => {
+                !new_name.trim().is_empty() && !new_name.contains(['"', '\\'])
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(localized(locale, "rename.invalid-name", &[&new_name]))
+        }
+    }
+}
+
+/// 判断光标处的 spring 构造能否重命名
+///
+/// 可以重命名时返回 `Some(Ok(目标))`，不能重命名时返回 `Some(Err(原因))`，
+/// 不是 spring 构造（或文档无法解析）时返回 `None`
+pub fn prepare_rename(
+    lines: &PositionMapper,
+    position: Position,
+    locale: Locale,
+) -> Option<Result<RenameTarget, String>> {
+    let file = syn::parse_file(lines.content()).ok()?;
+    let contains = |span: Span| {
+        let range = lines.span_range(span);
+        range.start <= position && position <= range.end
+    };
+
+    for item in &file.items {
+        let attrs: Vec<&syn::Attribute> = match item {
+            syn::Item::Fn(item_fn) => item_fn.attrs.iter().collect(),
+            syn::Item::Struct(item_struct) => item_struct
+                .attrs
+                .iter()
+                .chain(item_struct.fields.iter().flat_map(|field| &field.attrs))
+                .collect(),
+            _ => continue,
+        };
+        for attr in attrs {
+            let Some(ident) = attr.path().get_ident() else {
+                continue;
+            };
+            let name = ident.to_string();
+            if contains(ident.span()) {
+                if HTTP_METHODS.contains(&name.trim_end_matches("_api")) {
+                    return Some(Err(localized(locale, "rename.http-method", &[&name])));
+                }
+                if ROUTE_ATTRIBUTES.contains(&name.as_str())
+                    || JOB_ATTRIBUTES.contains(&name.as_str())
+                    || SPRING_ATTRIBUTES.contains(&name.as_str())
+                {
+                    return Some(Err(localized(locale, "rename.spring-macro", &[&name])));
+                }
+            }
+        }
+
+        match item {
+            syn::Item::Fn(item_fn) => {
+                if !contains(item_fn.span()) {
+                    continue;
+                }
+                let handler = lines.span_range(item_fn.span());
+                let route_attrs = || {
+                    item_fn.attrs.iter().filter(|attr| {
+                        ROUTE_ATTRIBUTES
+                            .iter()
+                            .any(|name| attr.path().is_ident(name))
+                    })
+                };
+                for attr in route_attrs() {
+                    for (literal, value, previous) in literals(attr) {
+                        if !contains(literal.span()) {
+                            continue;
+                        }
+                        if previous.as_deref() == Some("method") {
+                            return Some(Err(localized(locale, "rename.http-method", &[&value])));
+                        }
+                        let (name, range) = path_params(lines, &literal)
+                            .into_iter()
+                            .find(|(_, range)| range.start <= position && position <= range.end)?;
+                        return Some(Ok(RenameTarget::PathParam {
+                            name,
+                            range,
+                            handler,
+                        }));
+                    }
+                }
+
+                // 处理器参数和函数体中与路径参数同名的标识符
+                let params: Vec<String> = route_attrs()
+                    .flat_map(literals)
+                    .filter(|(_, _, previous)| previous.is_none())
+                    .flat_map(|(literal, _, _)| path_params(lines, &literal))
+                    .map(|(name, _)| name)
+                    .collect();
+                let ident = renamable_idents(item_fn)
+                    .into_iter()
+                    .find(|ident| contains(ident.span()))?;
+                let name = ident.to_string();
+                return params.contains(&name).then(|| {
+                    Ok(RenameTarget::PathParam {
+                        name,
+                        range: lines.span_range(ident.span()),
+                        handler,
+                    })
+                });
+            }
+            syn::Item::Struct(item_struct) => {
+                for attr in item_struct
+                    .fields
+                    .iter()
+                    .flat_map(|field| &field.attrs)
+                    .filter(|attr| attr.path().is_ident("inject"))
+                {
+                    if let Some((name, range)) = component_names(lines, attr)
+                        .into_iter()
+                        .find(|(_, range)| range.start <= position && position <= range.end)
+                    {
+                        return Some(Ok(RenameTarget::ComponentName { name, range }));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 重命名路径参数：修改处理器的路由路径中的参数和处理器中的同名标识符
+pub fn path_param_edits(
+    lines: &PositionMapper,
+    handler: Range,
+    old_name: &str,
+    new_name: &str,
+) -> Vec<TextEdit> {
+    let Ok(file) = syn::parse_file(lines.content()) else {
+        return Vec::new();
+    };
+    let Some(item_fn) = file.items.iter().find_map(|item| match item {
+        syn::Item::Fn(item_fn) if lines.span_range(item_fn.span()) == handler => Some(item_fn),
+        _ => None,
+    }) else {
+        return Vec::new();
+    };
+
+    let mut ranges: Vec<Range> = item_fn
+        .attrs
+        .iter()
+        .filter(|attr| {
+            ROUTE_ATTRIBUTES
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        })
+        .flat_map(literals)
+        .filter(|(_, _, previous)| previous.is_none())
+        .flat_map(|(literal, _, _)| path_params(lines, &literal))
+        .filter(|(name, _)| name == old_name)
+        .map(|(_, range)| range)
+        .collect();
+    ranges.extend(
+        renamable_idents(item_fn)
+            .into_iter()
+            .filter(|ident| ident == old_name)
+            .map(|ident| lines.span_range(ident.span())),
+    );
+    ranges
+        .into_iter()
+        .map(|range| TextEdit::new(range, new_name.to_string()))
+        .collect()
+}
+
+/// 重命名组件名称：修改文档中所有 `#[inject(component = "old_name")]` 中的名称
+pub fn component_name_edits(
+    lines: &PositionMapper,
+    old_name: &str,
+    new_name: &str,
+) -> Vec<TextEdit> {
+    let Ok(file) = syn::parse_file(lines.content()) else {
+        return Vec::new();
+    };
+    let mut edits = Vec::new();
+    collect_component_name_edits(lines, &file.items, old_name, new_name, &mut edits);
+    edits
+}
+
+fn collect_component_name_edits(
+    lines: &PositionMapper,
+    items: &[syn::Item],
+    old_name: &str,
+    new_name: &str,
+    edits: &mut Vec<TextEdit>,
+) {
+    for item in items {
+        match item {
+            syn::Item::Struct(item_struct) => {
+                let names = item_struct
+                    .fields
+                    .iter()
+                    .flat_map(|field| &field.attrs)
+                    .filter(|attr| attr.path().is_ident("inject"))
+                    .flat_map(|attr| component_names(lines, attr))
+                    .filter(|(name, _)| name == old_name);
+                edits.extend(names.map(|(_, range)| TextEdit::new(range, new_name.to_string())));
+            }
+            syn::Item::Mod(item_mod) => {
+                if let Some((_, items)) = &item_mod.content {
+                    collect_component_name_edits(lines, items, old_name, new_name, edits);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 属性参数中的字符串字面量：（字面量、去掉引号的值、前面 `=` 左侧的参数名）
+fn literals(attr: &syn::Attribute) -> Vec<(proc_macro2::Literal, String, Option<String>)> {
+    let Ok(list) = attr.meta.require_list() else {
+        return Vec::new();
+    };
+    let tokens: Vec<TokenTree> = list.tokens.clone().into_iter().collect();
+    tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(i, token)| {
+            let TokenTree::Literal(literal) = token else {
+                return None;
+            };
+            let text = literal.to_string();
+            let value = text.strip_prefix('"')?.strip_suffix('"')?.to_string();
+            let previous = match i.checked_sub(2).map(|j| &tokens[j..i]) {
+                Some([TokenTree::Ident(name), TokenTree::Punct(punct)])
+                    if punct.as_char() == '=' =>
+                {
+                    Some(name.to_string())
+                }
+                _ => None,
+            };
+            Some((literal.clone(), value, previous))
+        })
+        .collect()
+}
+
+/// 路由路径字面量中的参数名称及其范围（`{id}`、`{id:\d+}`、`{*rest}` 中的名称）
+fn path_params(lines: &PositionMapper, literal: &proc_macro2::Literal) -> Vec<(String, Range)> {
+    let range = lines.span_range(literal.span());
+    let Some(start) = lines.offset(range.start) else {
+        return Vec::new();
+    };
+    let text = literal.to_string();
+    let mut params = Vec::new();
+    let mut rest = 0;
+    while let Some(open) = text[rest..].find('{').map(|i| rest + i) {
+        let Some(close) = text[open..].find('}').map(|i| open + i) else {
+            break;
+        };
+        let inner = &text[open + 1..close];
+        let skip = usize::from(inner.starts_with('*'));
+        let name = inner[skip..].split(':').next().unwrap_or_default();
+        if is_identifier(name) {
+            let name_start = start + open + 1 + skip;
+            params.push((
+                name.to_string(),
+                lines.range(name_start..name_start + name.len()),
+            ));
+        }
+        rest = close + 1;
+    }
+    params
+}
+
+/// `#[inject(component = "name")]` 中的组件名称及其范围（不含引号）
+fn component_names(lines: &PositionMapper, attr: &syn::Attribute) -> Vec<(String, Range)> {
+    literals(attr)
+        .into_iter()
+        .filter(|(_, _, previous)| previous.as_deref() == Some("component"))
+        .filter_map(|(literal, value, _)| {
+            let range = lines.span_range(literal.span());
+            let start = lines.offset(range.start)? + 1;
+            Some((value.clone(), lines.range(start..start + value.len())))
+        })
+        .collect()
+}
+
+/// 处理器参数和函数体中可能引用路径参数绑定的标识符
+///
+/// 跳过字段访问（`user.id`）和路径（`Self::id`）中的标识符
+fn renamable_idents(item_fn: &syn::ItemFn) -> Vec<proc_macro2::Ident> {
+    let mut idents = Vec::new();
+    collect_idents(item_fn.sig.inputs.to_token_stream(), &mut idents);
+    collect_idents(item_fn.block.to_token_stream(), &mut idents);
+    idents
+}
+
+fn collect_idents(tokens: TokenStream, idents: &mut Vec<proc_macro2::Ident>) {
+    let mut previous: Option<TokenTree> = None;
+    for token in tokens {
+        match &token {
+            TokenTree::Ident(ident) => {
+                let qualified = matches!(
+                    &previous,
+                    Some(TokenTree::Punct(punct)) if punct.as_char() == '.' || punct.as_char() == ':'
+                );
+                if !qualified {
+                    idents.push(ident.clone());
+                }
+            }
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+        previous = Some(token);
+    }
+}
+
+/// 是否为 Rust 标识符
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"#[get("/orgs/{org_id}/users/{id}")]
+#[route("/users/{id}", method = "DELETE")]
+async fn user(Path((org_id, id)): Path<(i64, i64)>, Component(db): Component<Db>) -> String {
+    let user = db.find(id).await;
+    format!("{} {}", user.id, id)
+}
+
+#[derive(Clone, Service)]
+struct UserService {
+    #[inject(component = "primary")]
+    db: Db,
+}
+"#;
+
+    fn prepare(line: u32, character: u32) -> Option<Result<RenameTarget, String>> {
+        prepare_rename(
+            &PositionMapper::new(SOURCE),
+            Position::new(line, character),
+            Locale::default(),
+        )
+    }
+
+    #[test]
+    fn test_reject_spring_constructs() {
+        assert!(prepare(0, 3).unwrap().unwrap_err().contains("'get'"));
+        assert!(prepare(1, 35).unwrap().unwrap_err().contains("'DELETE'"));
+        assert!(prepare(1, 3).unwrap().unwrap_err().contains("#[route]"));
+        assert!(prepare(9, 6).unwrap().unwrap_err().contains("#[inject]"));
+        // 路径中的字面量段、与路径参数无关的标识符交给 rust-analyzer
+        assert!(prepare(0, 8).is_none());
+        assert!(prepare(3, 9).is_none());
+    }
+
+    #[test]
+    fn test_prepare_path_param() {
+        let target = prepare(0, 30).unwrap().unwrap();
+        assert_eq!(target.name(), "id");
+        assert_eq!(
+            target.range(),
+            Range::new(Position::new(0, 29), Position::new(0, 31))
+        );
+        // 处理器中的绑定
+        let target = prepare(2, 22).unwrap().unwrap();
+        assert_eq!(target.name(), "org_id");
+        assert!(target
+            .validate_name("organization", Locale::default())
+            .is_ok());
+        assert!(target.validate_name("org-id", Locale::default()).is_err());
+    }
+
+    #[test]
+    fn test_path_param_edits() {
+        let RenameTarget::PathParam { handler, .. } = prepare(0, 30).unwrap().unwrap() else {
+            panic!("expected a path param");
+        };
+        let edits = path_param_edits(&PositionMapper::new(SOURCE), handler, "id", "user_id");
+        let lines: Vec<_> = edits
+            .iter()
+            .map(|edit| (edit.range.start.line, edit.range.start.character))
+            .collect();
+        // 两个路由路径、参数绑定、函数体中的两处使用（不包括字段访问 user.id）
+        assert_eq!(lines, vec![(0, 29), (1, 17), (2, 28), (3, 23), (4, 30)]);
+    }
+
+    #[test]
+    fn test_component_name() {
+        let target = prepare(9, 27).unwrap().unwrap();
+        assert_eq!(
+            target,
+            RenameTarget::ComponentName {
+                name: "primary".to_string(),
+                range: Range::new(Position::new(9, 26), Position::new(9, 33)),
+            }
+        );
+        let edits = component_name_edits(&PositionMapper::new(SOURCE), "primary", "main");
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].range, target.range());
+        assert!(target.validate_name("", Locale::default()).is_err());
+    }
+}
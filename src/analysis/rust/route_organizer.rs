@@ -0,0 +1,270 @@
+//! 路由整理模块
+//!
+//! 将文件中的路由处理器按（路径，HTTP 方法）排序，并统一路由宏的格式，
+//! 例如 `#[get( "/users" )]` 会被规范化为 `#[get("/users")]`。
+//!
+//! 处理器在文件中占据的位置保持不变，只交换位置中的处理器；
+//! 非处理器项（结构体、普通函数等）以及它们之间的文本不受影响。
+
+use lsp_types::Range;
+use proc_macro2::LineColumn;
+use syn::punctuated::Punctuated;
+
+use super::handler_analyzer::{route_path_and_methods, ROUTE_ATTRIBUTES};
+use crate::core::document::PositionMapper;
+
+/// HTTP 方法的排序顺序
+const METHOD_ORDER: &[&str] = &[
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+/// 路由整理结果：用 `new_text` 替换 `range` 范围内的文本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteOrganizeEdit {
+    /// 被替换的范围（从第一个处理器开始到最后一个处理器结束）
+    pub range: Range,
+    /// 整理后的文本
+    pub new_text: String,
+}
+
+/// 文件中的一个路由处理器
+struct HandlerItem {
+    /// 处理器在原文中的字节范围（包括属性和文档注释）
+    start: usize,
+    end: usize,
+    /// 排序键：第一个路由路径
+    path: String,
+    /// 排序键：第一个 HTTP 方法的顺序
+    method_rank: usize,
+    /// 规范化路由宏之后的处理器文本
+    text: String,
+}
+
+/// 路由整理器
+#[derive(Debug, Clone, Default)]
+pub struct RouteOrganizer;
+
+impl RouteOrganizer {
+    /// 创建新的路由整理器
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 整理文件中的路由处理器
+    ///
+    /// # 返回
+    ///
+    /// 需要应用的编辑；文件中没有处理器或已经整理好时返回 `None`
+    pub fn organize(
+        &self,
+        lines: &PositionMapper,
+    ) -> Result<Option<RouteOrganizeEdit>, syn::Error> {
+        let content = lines.content();
+        let file = syn::parse_file(content)?;
+
+        let mut handlers: Vec<HandlerItem> = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Fn(item_fn) => self.extract_handler(item_fn, content, lines),
+                _ => None,
+            })
+            .collect();
+
+        let (Some(first), Some(last)) = (handlers.first(), handlers.last()) else {
+            return Ok(None);
+        };
+        let (region_start, region_end) = (first.start, last.end);
+
+        // 处理器原来占据的位置，排序后的处理器依次填入
+        let slots: Vec<(usize, usize)> = handlers.iter().map(|h| (h.start, h.end)).collect();
+
+        // 稳定排序：路径和方法都相同的处理器保持原有顺序
+        handlers.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then_with(|| a.method_rank.cmp(&b.method_rank))
+        });
+
+        let mut new_text = String::new();
+        for (i, handler) in handlers.iter().enumerate() {
+            new_text.push_str(&handler.text);
+            if let Some(&(next_start, _)) = slots.get(i + 1) {
+                new_text.push_str(&content[slots[i].1..next_start]);
+            }
+        }
+
+        if new_text == content[region_start..region_end] {
+            return Ok(None);
+        }
+
+        Ok(Some(RouteOrganizeEdit {
+            range: Range {
+                start: lines.position(region_start),
+                end: lines.position(region_end),
+            },
+            new_text,
+        }))
+    }
+
+    /// 提取路由处理器（只处理带有路由宏的函数）
+    fn extract_handler(
+        &self,
+        item_fn: &syn::ItemFn,
+        content: &str,
+        lines: &PositionMapper,
+    ) -> Option<HandlerItem> {
+        let route_attrs: Vec<&syn::Attribute> = item_fn
+            .attrs
+            .iter()
+            .filter(|attr| {
+                ROUTE_ATTRIBUTES
+                    .iter()
+                    .any(|name| attr.path().is_ident(name))
+            })
+            .collect();
+        if route_attrs.is_empty() {
+            return None;
+        }
+
+        let start = match item_fn.attrs.first() {
+            Some(attr) => line_offset(lines, attr.pound_token.span.start()),
+            None => line_offset(lines, item_fn.sig.fn_token.span.start()),
+        };
+        let end = line_offset(lines, item_fn.block.brace_token.span.close().end());
+
+        let mut path = String::new();
+        let mut method_rank = METHOD_ORDER.len();
+        if let Some(attr) = route_attrs.first() {
+            let (attr_path, methods) = route_path_and_methods(attr);
+            path = attr_path.unwrap_or_default();
+            method_rank = methods
+                .iter()
+                .filter_map(|method| METHOD_ORDER.iter().position(|m| m == method))
+                .min()
+                .unwrap_or(METHOD_ORDER.len());
+        }
+
+        // 从后往前替换路由宏，避免偏移量失效
+        let mut text = content[start..end].to_string();
+        for attr in route_attrs.iter().rev() {
+            let attr_start = line_offset(lines, attr.pound_token.span.start()) - start;
+            let attr_end = line_offset(lines, attr.bracket_token.span.close().end()) - start;
+            if let Some(normalized) = normalize_route_attribute(attr) {
+                text.replace_range(attr_start..attr_end, &normalized);
+            }
+        }
+
+        Some(HandlerItem {
+            start,
+            end,
+            path,
+            method_rank,
+            text,
+        })
+    }
+}
+
+/// 将路由宏规范化为 `#[name(arg1, arg2)]` 形式
+///
+/// 参数无法解析时返回 `None`，保留原文
+fn normalize_route_attribute(attr: &syn::Attribute) -> Option<String> {
+    let name = attr.path().get_ident()?.to_string();
+    let args = attr
+        .parse_args_with(Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated)
+        .ok()?;
+
+    let args = args
+        .iter()
+        .map(|arg| quote::quote!(#arg).to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Some(format!("#[{}({})]", name, args))
+}
+
+/// proc_macro2 的行列位置对应的字节偏移
+fn line_offset(lines: &PositionMapper, location: LineColumn) -> usize {
+    lines.line_column_offset(location.line, location.column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Position;
+
+    #[test]
+    fn test_organize_routes() {
+        let content = r#"use spring_web::get;
+
+/// 创建用户
+#[post( "/users" )]
+async fn create_user() {}
+
+struct Helper;
+
+#[get("/orders")]
+async fn list_orders() {}
+
+#[get(  "/users")]
+async fn list_users() {}
+"#;
+
+        let edit = RouteOrganizer::new()
+            .organize(&PositionMapper::new(content))
+            .unwrap()
+            .unwrap();
+        assert_eq!(edit.range.start, Position::new(2, 0));
+        assert_eq!(edit.range.end, Position::new(12, 24));
+        assert_eq!(
+            edit.new_text,
+            r#"#[get("/orders")]
+async fn list_orders() {}
+
+struct Helper;
+
+#[get("/users")]
+async fn list_users() {}
+
+/// 创建用户
+#[post("/users")]
+async fn create_user() {}"#
+        );
+    }
+
+    #[test]
+    fn test_organize_routes_with_route_macro() {
+        let content = r#"#[route("/b",method="POST")]
+async fn b() {}
+
+#[route("/a", method = "GET", method = "POST")]
+async fn a() {}
+"#;
+
+        let edit = RouteOrganizer::new()
+            .organize(&PositionMapper::new(content))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            edit.new_text,
+            r#"#[route("/a", method = "GET", method = "POST")]
+async fn a() {}
+
+#[route("/b", method = "POST")]
+async fn b() {}"#
+        );
+    }
+
+    #[test]
+    fn test_already_organized() {
+        let content = "#[get(\"/a\")]\nasync fn a() {}\n\n#[post(\"/a\")]\nasync fn b() {}\n";
+        assert!(RouteOrganizer::new()
+            .organize(&PositionMapper::new(content))
+            .unwrap()
+            .is_none());
+        assert!(RouteOrganizer::new()
+            .organize(&PositionMapper::new("fn main() {}"))
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,468 @@
+//! 路由路径解析模块
+//!
+//! 解析路由宏中的路径模板，支持以下参数语法：
+//! - `{id}`：普通路径参数，匹配任意一段路径
+//! - `{id:\d+}`：带正则约束的路径参数，只匹配满足约束的路径段
+//! - `{*rest}`：通配参数，匹配剩余的所有路径段
+//!
+//! 约束中的正则表达式可以包含大括号（如 `{code:[A-Z]{3}}`），反斜杠转义的字符不参与括号匹配。
+//! 解析结果同时用于路径格式验证和路由冲突检测。
+//!
+//! 同一路径中的参数名称不能重复（`/a/{id}/b/{id}` 在运行时无法正确提取），
+//! 参数数量超过 [`MAX_PATH_PARAMS`] 时提示拆分路由或改用查询参数。
+
+use regex::Regex;
+
+use lsp_types::DiagnosticSeverity;
+
+use crate::core::messages::{localized, Locale};
+
+/// 单个路由路径中参数数量的上限，超过时给出警告
+pub const MAX_PATH_PARAMS: usize = 8;
+
+/// 路径中的一段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// 字面量，如 `users`
+    Literal(String),
+    /// 路径参数，如 `{id}` 或 `{id:\d+}`
+    Param {
+        /// 参数名称
+        name: String,
+        /// 正则约束
+        constraint: Option<String>,
+    },
+    /// 通配参数，如 `{*rest}`
+    Wildcard(String),
+    /// 字面量和参数混合的路径段，如 `{name}.json`
+    Mixed(String),
+}
+
+impl PathSegment {
+    /// 判断两个路径段是否可能匹配同一段请求路径
+    fn overlaps(&self, other: &PathSegment) -> bool {
+        use PathSegment::*;
+
+        match (self, other) {
+            (Wildcard(_), _) | (_, Wildcard(_)) => true,
+            (Literal(a), Literal(b)) => a == b,
+            (Literal(literal), Param { constraint, .. })
+            | (Param { constraint, .. }, Literal(literal)) => match constraint {
+                Some(constraint) => constraint_matches(constraint, literal),
+                None => true,
+            },
+            (
+                Param {
+                    constraint: Some(a),
+                    ..
+                },
+                Param {
+                    constraint: Some(b),
+                    ..
+                },
+            ) => a == b,
+            (Param { .. }, Param { .. }) => true,
+            (Mixed(_), Param { constraint, .. }) | (Param { constraint, .. }, Mixed(_)) => {
+                constraint.is_none()
+            }
+            (Mixed(a), Mixed(b)) => a == b,
+            (Mixed(_), Literal(_)) | (Literal(_), Mixed(_)) => false,
+        }
+    }
+}
+
+/// 解析后的路由路径
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoutePath {
+    /// 路径段（不包括空段）
+    pub segments: Vec<PathSegment>,
+}
+
+impl RoutePath {
+    /// 路径中声明的参数名称
+    pub fn param_names(&self) -> Vec<&str> {
+        self.segments
+            .iter()
+            .filter_map(|segment| match segment {
+                PathSegment::Param { name, .. } | PathSegment::Wildcard(name) => {
+                    Some(name.as_str())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 判断两个路径是否可能匹配同一个请求路径
+    ///
+    /// 例如 `/users/{id}` 与 `/users/me` 冲突，而 `/users/{id:\d+}` 与 `/users/me` 不冲突
+    pub fn conflicts_with(&self, other: &RoutePath) -> bool {
+        let mut left = self.segments.iter();
+        let mut right = other.segments.iter();
+
+        loop {
+            match (left.next(), right.next()) {
+                (None, None) => return true,
+                // 通配参数匹配剩余的所有路径段（至少一段）
+                (Some(PathSegment::Wildcard(_)), Some(_))
+                | (Some(_), Some(PathSegment::Wildcard(_))) => return true,
+                (Some(a), Some(b)) if a.overlaps(b) => {}
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// 路径格式错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    /// 参数名称中出现嵌套的大括号
+    Nested { offset: usize },
+    /// 没有匹配的开括号
+    MissingOpenBrace { offset: usize },
+    /// 参数名称为空
+    EmptyName { offset: usize },
+    /// 参数名称包含非法字符
+    InvalidName { name: String },
+    /// 没有匹配的闭括号
+    MissingCloseBrace,
+    /// 约束不是合法的正则表达式
+    InvalidConstraint {
+        name: String,
+        constraint: String,
+        reason: String,
+    },
+    /// 参数名称在同一路径中重复
+    DuplicateName { name: String },
+    /// 参数数量超过 [`MAX_PATH_PARAMS`]
+    TooManyParams { count: usize },
+}
+
+impl PathError {
+    /// 诊断代码
+    pub fn code(&self) -> &'static str {
+        match self {
+            PathError::Nested { .. } => "E008",
+            PathError::MissingOpenBrace { .. } => "E009",
+            PathError::EmptyName { .. } => "E010",
+            PathError::InvalidName { .. } => "E011",
+            PathError::MissingCloseBrace => "E012",
+            PathError::InvalidConstraint { .. } => "E019",
+            PathError::DuplicateName { .. } => "E028",
+            PathError::TooManyParams { .. } => "E029",
+        }
+    }
+
+    /// 诊断级别：参数过多不影响路由匹配，只给出警告
+    pub fn severity(&self) -> DiagnosticSeverity {
+        match self {
+            PathError::TooManyParams { .. } => DiagnosticSeverity::WARNING,
+            _ => DiagnosticSeverity::ERROR,
+        }
+    }
+
+    /// 诊断消息
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            PathError::Nested { offset }
+            | PathError::MissingOpenBrace { offset }
+            | PathError::EmptyName { offset } => localized(locale, self.code(), &[offset]),
+            PathError::InvalidName { name } | PathError::DuplicateName { name } => {
+                localized(locale, self.code(), &[name])
+            }
+            PathError::MissingCloseBrace => localized(locale, self.code(), &[]),
+            PathError::InvalidConstraint {
+                name,
+                constraint,
+                reason,
+            } => localized(locale, self.code(), &[name, constraint, reason]),
+            PathError::TooManyParams { count } => {
+                localized(locale, self.code(), &[count, &MAX_PATH_PARAMS])
+            }
+        }
+    }
+}
+
+/// 解析路由路径
+///
+/// 遇到格式错误时继续解析，返回尽量完整的路径和所有错误
+pub fn parse_route_path(path: &str) -> (RoutePath, Vec<PathError>) {
+    let mut errors = Vec::new();
+    let mut segments = Vec::new();
+    let mut segment = String::new();
+    // 当前段包含的参数
+    let mut params: Vec<PathSegment> = Vec::new();
+    // 已声明的参数名称
+    let mut names: Vec<String> = Vec::new();
+    let mut has_literal = false;
+
+    let mut chars = path.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '/' => {
+                if let Some(parsed) = finish_segment(&segment, &mut params, has_literal) {
+                    segments.push(parsed);
+                }
+                segment.clear();
+                has_literal = false;
+            }
+            '{' => {
+                let start = i;
+                let mut name_end = None;
+                let mut depth = 0usize;
+                let mut end = None;
+                let mut nested = false;
+
+                while let Some((j, ch)) = chars.next() {
+                    match ch {
+                        // 约束中的转义字符不参与括号匹配
+                        '\\' if name_end.is_some() => {
+                            chars.next();
+                        }
+                        ':' if name_end.is_none() => name_end = Some(j),
+                        '{' if name_end.is_some() => depth += 1,
+                        '{' => {
+                            errors.push(PathError::Nested { offset: j });
+                            nested = true;
+                        }
+                        '}' if depth > 0 => depth -= 1,
+                        '}' => {
+                            end = Some(j);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+
+                let Some(end) = end else {
+                    errors.push(PathError::MissingCloseBrace);
+                    break;
+                };
+
+                segment.push_str(&path[start..=end]);
+                let raw_name = &path[start + 1..name_end.unwrap_or(end)];
+                let constraint = name_end.map(|colon| path[colon + 1..end].to_string());
+                let mut param_errors = Vec::new();
+                let param = parse_param(raw_name, constraint, start, &mut param_errors);
+                // 嵌套的参数已经报告过错误，不再检查名称
+                if !nested {
+                    errors.extend(param_errors);
+                }
+                if let PathSegment::Param { name, .. } | PathSegment::Wildcard(name) = &param {
+                    if !name.is_empty() {
+                        if names.contains(name) {
+                            if !errors.contains(&PathError::DuplicateName { name: name.clone() }) {
+                                errors.push(PathError::DuplicateName { name: name.clone() });
+                            }
+                        } else {
+                            names.push(name.clone());
+                        }
+                    }
+                }
+                params.push(param);
+            }
+            '}' => {
+                errors.push(PathError::MissingOpenBrace { offset: i });
+                segment.push(ch);
+                has_literal = true;
+            }
+            _ => {
+                segment.push(ch);
+                has_literal = true;
+            }
+        }
+    }
+
+    if let Some(parsed) = finish_segment(&segment, &mut params, has_literal) {
+        segments.push(parsed);
+    }
+
+    if names.len() > MAX_PATH_PARAMS {
+        errors.push(PathError::TooManyParams { count: names.len() });
+    }
+
+    (RoutePath { segments }, errors)
+}
+
+/// 解析单个参数，验证名称和约束
+fn parse_param(
+    raw_name: &str,
+    constraint: Option<String>,
+    offset: usize,
+    errors: &mut Vec<PathError>,
+) -> PathSegment {
+    let (wildcard, name) = match raw_name.strip_prefix('*') {
+        Some(name) => (true, name),
+        None => (false, raw_name),
+    };
+
+    if name.is_empty() {
+        errors.push(PathError::EmptyName { offset });
+    } else if !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        errors.push(PathError::InvalidName {
+            name: raw_name.to_string(),
+        });
+    }
+
+    if wildcard {
+        return PathSegment::Wildcard(name.to_string());
+    }
+
+    if let Some(constraint) = &constraint {
+        if let Err(e) = Regex::new(&anchored(constraint)) {
+            errors.push(PathError::InvalidConstraint {
+                name: name.to_string(),
+                constraint: constraint.clone(),
+                reason: e.to_string().lines().last().unwrap_or_default().to_string(),
+            });
+        }
+    }
+
+    PathSegment::Param {
+        name: name.to_string(),
+        constraint,
+    }
+}
+
+/// 结束当前路径段，空段返回 `None`
+fn finish_segment(
+    raw: &str,
+    params: &mut Vec<PathSegment>,
+    has_literal: bool,
+) -> Option<PathSegment> {
+    let params = std::mem::take(params);
+    if raw.is_empty() {
+        return None;
+    }
+
+    if !has_literal && params.len() == 1 {
+        return params.into_iter().next();
+    }
+    if params.is_empty() {
+        return Some(PathSegment::Literal(raw.to_string()));
+    }
+    Some(PathSegment::Mixed(raw.to_string()))
+}
+
+/// 约束需要匹配整个路径段
+fn anchored(constraint: &str) -> String {
+    format!("^(?:{})$", constraint)
+}
+
+/// 判断字面量路径段是否满足约束，非法的约束视为匹配
+pub(crate) fn constraint_matches(constraint: &str, literal: &str) -> bool {
+    Regex::new(&anchored(constraint))
+        .map(|re| re.is_match(literal))
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(path: &str) -> RoutePath {
+        let (route, errors) = parse_route_path(path);
+        assert!(
+            errors.is_empty(),
+            "unexpected errors for {}: {:?}",
+            path,
+            errors
+        );
+        route
+    }
+
+    #[test]
+    fn test_parse_constraints() {
+        let route = parse(r"/users/{id:\d+}/codes/{code:[A-Z]{3}}/{*rest}");
+        assert_eq!(
+            route.segments,
+            vec![
+                PathSegment::Literal("users".to_string()),
+                PathSegment::Param {
+                    name: "id".to_string(),
+                    constraint: Some(r"\d+".to_string()),
+                },
+                PathSegment::Literal("codes".to_string()),
+                PathSegment::Param {
+                    name: "code".to_string(),
+                    constraint: Some("[A-Z]{3}".to_string()),
+                },
+                PathSegment::Wildcard("rest".to_string()),
+            ]
+        );
+        assert_eq!(route.param_names(), vec!["id", "code", "rest"]);
+
+        assert_eq!(
+            parse("/files/{name}.json").segments[1],
+            PathSegment::Mixed("{name}.json".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let codes = |path: &str| -> Vec<&'static str> {
+            parse_route_path(path)
+                .1
+                .iter()
+                .map(PathError::code)
+                .collect()
+        };
+
+        assert_eq!(codes("/users/{a{b}"), vec!["E008"]);
+        assert_eq!(codes("/users/id}"), vec!["E009"]);
+        assert_eq!(codes("/users/{}"), vec!["E010"]);
+        assert_eq!(codes("/users/{user-id}"), vec!["E011"]);
+        assert_eq!(codes("/users/{id"), vec!["E012"]);
+        assert_eq!(codes(r"/users/{id:\d+(}"), vec!["E019"]);
+        assert!(codes(r"/users/{id:\d+}").is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_and_too_many_params() {
+        let (_, errors) = parse_route_path("/a/{id}/b/{id}/c/{id}.json");
+        assert_eq!(
+            errors,
+            vec![PathError::DuplicateName {
+                name: "id".to_string()
+            }]
+        );
+        assert_eq!(errors[0].severity(), DiagnosticSeverity::ERROR);
+
+        let (_, errors) = parse_route_path("/files/{name}/{*name}");
+        assert_eq!(
+            errors.iter().map(PathError::code).collect::<Vec<_>>(),
+            vec!["E028"]
+        );
+
+        let path: String = (0..=MAX_PATH_PARAMS)
+            .map(|i| format!("/{{p{}}}", i))
+            .collect();
+        let (_, errors) = parse_route_path(&path);
+        assert_eq!(
+            errors,
+            vec![PathError::TooManyParams {
+                count: MAX_PATH_PARAMS + 1
+            }]
+        );
+        assert_eq!(errors[0].severity(), DiagnosticSeverity::WARNING);
+
+        let path: String = (0..MAX_PATH_PARAMS)
+            .map(|i| format!("/{{p{}}}", i))
+            .collect();
+        assert!(parse_route_path(&path).1.is_empty());
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let conflicts = |a: &str, b: &str| parse(a).conflicts_with(&parse(b));
+
+        assert!(conflicts("/users/{id}", "/users/me"));
+        assert!(!conflicts(r"/users/{id:\d+}", "/users/me"));
+        assert!(conflicts(r"/users/{id:\d+}", "/users/42"));
+        assert!(conflicts("/users/{id}", "/users/{name}"));
+        assert!(!conflicts(r"/users/{id:\d+}", "/users/{name:[a-z]+}"));
+        assert!(!conflicts("/users/{id}", "/users/{id}/posts"));
+        assert!(conflicts("/static/{*path}", "/static/css/app.css"));
+        assert!(!conflicts("/static/{*path}", "/static"));
+        assert!(!conflicts("/users", "/posts"));
+    }
+}
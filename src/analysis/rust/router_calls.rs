@@ -0,0 +1,231 @@
+//! Router 路由注册模块
+//!
+//! 除了路由宏，路由也可以通过代码注册：
+//!
+//! ```text
+//! Router::new()
+//!     .route("/users", get(list_users).post(create_user))
+//!     .route("/users/{id}", routing::delete(users::remove))
+//! ```
+//!
+//! 本模块识别 `.route("路径", 方法(处理器)...)` 调用，提取每个方法对应的处理器。
+//! 处理器必须是函数路径（闭包等无法定位的处理器被忽略）。
+//! 路由可能被 `nest` 等加上前缀，提取的路径只是注册时的字面量，因此这类路由的可信度低于路由宏。
+
+use std::path::Path;
+use std::str::FromStr;
+
+use lsp_types::{Position, Range};
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+use crate::analysis::rust::module_path::qualified_name;
+use crate::core::document::PositionMapper;
+
+/// 注册路由的方法函数（`axum::routing` 中的同名函数和 `MethodRouter` 的同名方法）
+const ROUTING_METHODS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "trace", "connect",
+];
+
+/// 通过 `.route(...)` 注册的一个路由（一个方法和处理器）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouterRoute {
+    /// HTTP 方法（大写）
+    pub method: String,
+    /// 路由路径
+    pub path: String,
+    /// 路径字符串字面量（包括引号）的范围
+    pub path_range: Range,
+    /// 处理器函数名（路径的最后一段）
+    pub handler: String,
+    /// 代码中书写的处理器路径（如 `users::list`）
+    pub handler_path: String,
+    /// 处理器路径的范围
+    pub handler_range: Range,
+}
+
+impl RouterRoute {
+    /// 处理器的完整路径（如 `crate::api::users::list`），`file` 是注册路由的文件
+    ///
+    /// 相对路径按注册所在的模块解析（通过 `use` 导入的处理器会被解析到当前模块）；
+    /// 以 `super::` 开头或文件不属于 crate 的模块树时返回 `None`
+    pub fn qualified_handler(&self, file: &Path) -> Option<String> {
+        if self.handler_path.starts_with("crate::") {
+            return Some(self.handler_path.clone());
+        }
+        if self.handler_path.starts_with("super::") {
+            return None;
+        }
+        let relative = self
+            .handler_path
+            .strip_prefix("self::")
+            .unwrap_or(&self.handler_path);
+        qualified_name(file, relative)
+    }
+}
+
+/// 提取文档中所有通过 `.route(...)` 注册的路由（按源码顺序）
+///
+/// 文档无法分词时返回空列表
+pub fn router_routes(lines: &PositionMapper) -> Vec<RouterRoute> {
+    let Ok(tokens) = TokenStream::from_str(lines.content()) else {
+        return Vec::new();
+    };
+    let mut routes = Vec::new();
+    collect_routes(lines, tokens, &mut routes);
+    routes
+}
+
+/// 光标位于路径字面量上的路由注册（同一路径注册的每个方法各一项）
+pub fn router_routes_at(lines: &PositionMapper, position: Position) -> Vec<RouterRoute> {
+    router_routes(lines)
+        .into_iter()
+        .filter(|route| route.path_range.start <= position && position <= route.path_range.end)
+        .collect()
+}
+
+fn collect_routes(lines: &PositionMapper, tokens: TokenStream, routes: &mut Vec<RouterRoute>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    for (i, token) in tokens.iter().enumerate() {
+        let TokenTree::Group(group) = token else {
+            continue;
+        };
+        let is_route_call = group.delimiter() == Delimiter::Parenthesis
+            && i >= 2
+            && matches!(&tokens[i - 1], TokenTree::Ident(ident) if ident == "route")
+            && matches!(&tokens[i - 2], TokenTree::Punct(punct) if punct.as_char() == '.');
+        if is_route_call {
+            route_call(lines, group.stream(), routes);
+        }
+        collect_routes(lines, group.stream(), routes);
+    }
+}
+
+/// 解析 `.route(...)` 的参数：路径字面量和方法路由表达式
+fn route_call(lines: &PositionMapper, args: TokenStream, routes: &mut Vec<RouterRoute>) {
+    let mut args = args.into_iter();
+    let Some(TokenTree::Literal(literal)) = args.next() else {
+        return;
+    };
+    let Ok(syn::Lit::Str(path)) = syn::parse2::<syn::Lit>(literal.to_token_stream()) else {
+        return;
+    };
+    if !matches!(args.next(), Some(TokenTree::Punct(punct)) if punct.as_char() == ',') {
+        return;
+    }
+    let Ok(method_router) = syn::parse2::<syn::Expr>(args.collect()) else {
+        return;
+    };
+
+    let mut handlers = Vec::new();
+    method_handlers(&method_router, &mut handlers);
+    let path_range = lines.span_range(literal.span());
+    routes.extend(handlers.into_iter().map(|(method, handler)| {
+        let segments: Vec<String> = handler
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        RouterRoute {
+            method: method.to_uppercase(),
+            path: path.value(),
+            path_range,
+            handler: segments.last().cloned().unwrap_or_default(),
+            handler_path: segments.join("::"),
+            handler_range: lines.span_range(handler.span()),
+        }
+    }));
+}
+
+/// 方法路由表达式中的（方法、处理器），如 `get(list).post(create)`
+fn method_handlers<'a>(expr: &'a syn::Expr, handlers: &mut Vec<(String, &'a syn::ExprPath)>) {
+    let (method, args) = match expr {
+        syn::Expr::MethodCall(call) => {
+            method_handlers(&call.receiver, handlers);
+            (call.method.to_string(), &call.args)
+        }
+        syn::Expr::Call(call) => {
+            let syn::Expr::Path(func) = &*call.func else {
+                return;
+            };
+            let Some(segment) = func.path.segments.last() else {
+                return;
+            };
+            (segment.ident.to_string(), &call.args)
+        }
+        _ => return,
+    };
+    if !ROUTING_METHODS.contains(&method.as_str()) || args.len() != 1 {
+        return;
+    }
+    if let Some(syn::Expr::Path(handler)) = args.first() {
+        handlers.push((method, handler));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"pub fn router() -> Router {
+    Router::new()
+        .route("/users", get(list_users).post(handlers::users::create))
+        .route("/health", routing::get(|| async { "ok" }))
+        .nest("/api", Router::new().route("/items/{id}", delete(remove_item)))
+}
+"#;
+
+    #[test]
+    fn test_router_routes() {
+        let routes = router_routes(&PositionMapper::new(SOURCE));
+        let summary: Vec<_> = routes
+            .iter()
+            .map(|route| {
+                (
+                    route.method.as_str(),
+                    route.path.as_str(),
+                    route.handler.as_str(),
+                )
+            })
+            .collect();
+        // 闭包处理器被忽略；nest 中的路由只提取注册时的路径
+        assert_eq!(
+            summary,
+            vec![
+                ("GET", "/users", "list_users"),
+                ("POST", "/users", "create"),
+                ("DELETE", "/items/{id}", "remove_item"),
+            ]
+        );
+        assert_eq!(routes[1].handler_path, "handlers::users::create");
+        assert_eq!(
+            routes[0].path_range,
+            Range::new(Position::new(2, 15), Position::new(2, 23))
+        );
+        assert_eq!(routes[0].handler_range.start, Position::new(2, 29));
+    }
+
+    #[test]
+    fn test_router_routes_at() {
+        let routes = router_routes_at(&PositionMapper::new(SOURCE), Position::new(2, 18));
+        assert_eq!(routes.len(), 2);
+        assert!(router_routes_at(&PositionMapper::new(SOURCE), Position::new(2, 30)).is_empty());
+        assert!(router_routes(&PositionMapper::new("fn broken( {")).is_empty());
+    }
+
+    #[test]
+    fn test_qualified_handler() {
+        let routes = router_routes(&PositionMapper::new(SOURCE));
+        let file = Path::new("/app/src/api/mod.rs");
+        assert_eq!(
+            routes[0].qualified_handler(file).unwrap(),
+            "crate::api::list_users"
+        );
+        assert_eq!(
+            routes[1].qualified_handler(file).unwrap(),
+            "crate::api::handlers::users::create"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! 提取器类型定义模块
+//!
+//! `textDocument/typeDefinition` 从处理器参数的提取器跳转到其泛型参数的类型定义：
+//! `Json<CreateUser>` 跳转到 `CreateUser` 结构体，`Component<ConnectPool>` 跳转到 `ConnectPool`
+//! （类型不在项目中定义时由服务器跳转到提供它的 `#[component]` 函数）。
+//!
+//! 类型定义通过符号索引（[`crate::core::index::SymbolIndex`]）查找，不依赖 rust-analyzer。
+
+use lsp_types::Position;
+
+use crate::analysis::rust::handler_analyzer::{ExtractorKind, HandlerFile};
+
+/// 包装类型：跳转到其中的类型（`Json<Vec<User>>` 跳转到 `User`）
+const WRAPPER_TYPES: &[&str] = &["Vec", "Option", "Box", "Arc", "Rc", "HashMap", "BTreeMap"];
+
+/// 光标所在的提取器参数指向的类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractorTarget {
+    /// 提取器类型
+    pub kind: ExtractorKind,
+    /// 目标类型名称（路径的最后一段，如 `CreateUser`）
+    pub type_name: String,
+}
+
+/// 光标所在的处理器参数的目标类型
+///
+/// 提取器带有泛型参数时为泛型参数中的类型，否则为参数类型本身（如自定义的 guard 提取器）
+pub fn extractor_target(file: &HandlerFile, position: Position) -> Option<ExtractorTarget> {
+    let param = file.param_at(position)?;
+    let ty = param.inner_type.as_deref().unwrap_or(&param.type_name);
+    Some(ExtractorTarget {
+        kind: param.kind.clone(),
+        type_name: target_type_name(ty)?,
+    })
+}
+
+/// 类型文本中要跳转的类型名称，无法解析或为元组等非路径类型时返回 `None`
+fn target_type_name(ty: &str) -> Option<String> {
+    let mut ty: syn::Type = syn::parse_str(ty).ok()?;
+    loop {
+        ty = match ty {
+            syn::Type::Reference(reference) => *reference.elem,
+            syn::Type::Path(type_path) => {
+                let segment = type_path.path.segments.last()?;
+                let inner = match &segment.arguments {
+                    syn::PathArguments::AngleBracketed(args)
+                        if WRAPPER_TYPES.contains(&segment.ident.to_string().as_str()) =>
+                    {
+                        // 映射类型取值的类型
+                        args.args.iter().rev().find_map(|arg| match arg {
+                            syn::GenericArgument::Type(ty) => Some(ty.clone()),
+                            _ => None,
+                        })
+                    }
+                    _ => None,
+                };
+                match inner {
+                    Some(inner) => inner,
+                    None => return Some(segment.ident.to_string()),
+                }
+            }
+            _ => return None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rust::handler_analyzer::HandlerAnalyzer;
+
+    #[test]
+    fn test_target_type_name() {
+        assert_eq!(target_type_name("CreateUser").unwrap(), "CreateUser");
+        assert_eq!(target_type_name("crate::models::User").unwrap(), "User");
+        assert_eq!(target_type_name("Vec<Option<User>>").unwrap(), "User");
+        assert_eq!(target_type_name("HashMap<String, Role>").unwrap(), "Role");
+        assert_eq!(target_type_name("&Page<User>").unwrap(), "Page");
+        assert!(target_type_name("(u32, String)").is_none());
+    }
+
+    #[test]
+    fn test_extractor_target() {
+        let content = r#"#[post("/users")]
+async fn create(
+    Component(db): Component<ConnectPool>,
+    Json(body): Json<CreateUser>,
+) {}
+"#;
+        let file = HandlerAnalyzer::new().analyze(content).unwrap();
+
+        let target = extractor_target(&file, Position::new(3, 24)).unwrap();
+        assert_eq!(target.kind, ExtractorKind::Json);
+        assert_eq!(target.type_name, "CreateUser");
+
+        let target = extractor_target(&file, Position::new(2, 20)).unwrap();
+        assert_eq!(target.kind, ExtractorKind::Component);
+        assert_eq!(target.type_name, "ConnectPool");
+
+        // 参数名不是类型
+        assert!(extractor_target(&file, Position::new(3, 8)).is_none());
+    }
+}
@@ -0,0 +1,237 @@
+//! 颜色配置值模块
+//!
+//! 为 Schema 中声明了 `"format": "color"` 的配置项（如横幅、日志颜色）提供
+//! `textDocument/documentColor` 和 `textDocument/colorPresentation` 支持，编辑器会在值旁边显示色块。
+//!
+//! 支持的写法：
+//! - 十六进制：`#rgb`、`#rgba`、`#rrggbb`、`#rrggbbaa`
+//! - 终端颜色名：`black`、`red`、`green`、`yellow`、`blue`、`magenta`、`cyan`、`white`
+//!   及对应的 `bright_` 前缀形式（大小写不敏感）
+
+use lsp_types::{Color, ColorInformation, ColorPresentation, Position, Range};
+
+use super::toml_analyzer::{ConfigValue, TomlDocument};
+use crate::schema::{PropertySchema, SchemaProvider};
+
+/// 终端颜色名及对应的 RGB 值（xterm 默认调色板）
+const NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("black", [0x00, 0x00, 0x00]),
+    ("red", [0xcd, 0x00, 0x00]),
+    ("green", [0x00, 0xcd, 0x00]),
+    ("yellow", [0xcd, 0xcd, 0x00]),
+    ("blue", [0x00, 0x00, 0xee]),
+    ("magenta", [0xcd, 0x00, 0xcd]),
+    ("cyan", [0x00, 0xcd, 0xcd]),
+    ("white", [0xe5, 0xe5, 0xe5]),
+    ("bright_black", [0x7f, 0x7f, 0x7f]),
+    ("bright_red", [0xff, 0x00, 0x00]),
+    ("bright_green", [0x00, 0xff, 0x00]),
+    ("bright_yellow", [0xff, 0xff, 0x00]),
+    ("bright_blue", [0x5c, 0x5c, 0xff]),
+    ("bright_magenta", [0xff, 0x00, 0xff]),
+    ("bright_cyan", [0x00, 0xff, 0xff]),
+    ("bright_white", [0xff, 0xff, 0xff]),
+];
+
+/// 判断配置项是否为颜色
+pub fn is_color_property(schema: Option<&PropertySchema>) -> bool {
+    schema
+        .and_then(|schema| schema.format.as_deref())
+        .is_some_and(|format| format == "color")
+}
+
+/// 解析颜色值，无法识别时返回 `None`
+pub fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+
+    let name = value.to_ascii_lowercase().replace('-', "_");
+    NAMED_COLORS
+        .iter()
+        .find(|(named, _)| *named == name)
+        .map(|(_, [r, g, b])| rgba(*r, *g, *b, 0xff))
+}
+
+/// 解析 `#` 之后的十六进制颜色
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize, width: usize| {
+        let digits = &hex[i * width..(i + 1) * width];
+        let value = u8::from_str_radix(digits, 16).ok()?;
+        // 简写形式中每一位重复一次，如 `f` 表示 `ff`
+        Some(if width == 1 { value * 0x11 } else { value })
+    };
+
+    match hex.len() {
+        3 | 4 => Some(rgba(
+            channel(0, 1)?,
+            channel(1, 1)?,
+            channel(2, 1)?,
+            if hex.len() == 4 { channel(3, 1)? } else { 0xff },
+        )),
+        6 | 8 => Some(rgba(
+            channel(0, 2)?,
+            channel(1, 2)?,
+            channel(2, 2)?,
+            if hex.len() == 8 { channel(3, 2)? } else { 0xff },
+        )),
+        _ => None,
+    }
+}
+
+fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+    Color {
+        red: f32::from(r) / 255.0,
+        green: f32::from(g) / 255.0,
+        blue: f32::from(b) / 255.0,
+        alpha: f32::from(a) / 255.0,
+    }
+}
+
+/// 颜色分量转换为 0-255 的整数
+fn channel(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// 文档中所有颜色配置项的颜色
+///
+/// 颜色范围是字符串引号内的部分，选择其他颜色时引号保持不变。
+/// 多行字符串和使用环境变量插值的值不显示颜色。
+pub fn document_colors(
+    doc: &TomlDocument,
+    schema_provider: &SchemaProvider,
+) -> Vec<ColorInformation> {
+    let mut colors = Vec::new();
+
+    for (prefix, section) in &doc.config_sections {
+        for (key, property) in &section.properties {
+            let schema = schema_provider.property_schema(prefix, key);
+            if !is_color_property(schema.as_ref()) {
+                continue;
+            }
+            let ConfigValue::String(value) = &property.value else {
+                continue;
+            };
+            let Some(color) = parse_color(value) else {
+                continue;
+            };
+
+            // 只处理单行的 `"..."` 或 `'...'`，值本身是 ASCII，字符数与编码无关
+            let range = property.range;
+            if range.start.line != range.end.line
+                || range.end.character - range.start.character != value.len() as u32 + 2
+            {
+                continue;
+            }
+            colors.push(ColorInformation {
+                range: Range::new(
+                    Position::new(range.start.line, range.start.character + 1),
+                    Position::new(range.end.line, range.end.character - 1),
+                ),
+                color,
+            });
+        }
+    }
+
+    colors.sort_by_key(|info| (info.range.start.line, info.range.start.character));
+    colors
+}
+
+/// 颜色的可选写法
+///
+/// 依次为 `#rrggbb`（不透明时）或 `#rrggbbaa`，以及与之完全相同的终端颜色名
+pub fn color_presentations(color: Color) -> Vec<ColorPresentation> {
+    let [r, g, b, a] = [color.red, color.green, color.blue, color.alpha].map(channel);
+    let hex = if a == 0xff {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    };
+
+    let mut presentations = vec![ColorPresentation {
+        label: hex,
+        ..Default::default()
+    }];
+    if a == 0xff {
+        if let Some((name, _)) = NAMED_COLORS.iter().find(|(_, rgb)| *rgb == [r, g, b]) {
+            presentations.push(ColorPresentation {
+                label: name.to_string(),
+                ..Default::default()
+            });
+        }
+    }
+    presentations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::toml::toml_analyzer::TomlAnalyzer;
+    use crate::schema::ConfigSchema;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#ff8000"), Some(rgba(0xff, 0x80, 0x00, 0xff)));
+        assert_eq!(parse_color("#f80"), Some(rgba(0xff, 0x88, 0x00, 0xff)));
+        assert_eq!(parse_color("#ff800080"), Some(rgba(0xff, 0x80, 0x00, 0x80)));
+        assert_eq!(
+            parse_color("Bright-Red"),
+            Some(rgba(0xff, 0x00, 0x00, 0xff))
+        );
+        assert_eq!(parse_color("#ff80"), Some(rgba(0xff, 0xff, 0x88, 0x00)));
+        assert!(parse_color("#ff800").is_none());
+        assert!(parse_color("#gg0000").is_none());
+        assert!(parse_color("orange").is_none());
+    }
+
+    #[test]
+    fn test_color_presentations() {
+        let labels = |color| {
+            color_presentations(color)
+                .into_iter()
+                .map(|p| p.label)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(rgba(0xff, 0x80, 0x00, 0xff)), vec!["#ff8000"]);
+        assert_eq!(labels(rgba(0xff, 0x80, 0x00, 0x80)), vec!["#ff800080"]);
+        assert_eq!(labels(rgba(0xcd, 0x00, 0x00, 0xff)), vec!["#cd0000", "red"]);
+    }
+
+    #[test]
+    fn test_document_colors() {
+        let schema = ConfigSchema {
+            schema_type: "object".to_string(),
+            plugins: [(
+                "banner".to_string(),
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "color": { "type": "string", "format": "color" },
+                        "accent": { "type": "string", "format": "color" },
+                        "text": { "type": "string" }
+                    }
+                }),
+            )]
+            .into_iter()
+            .collect(),
+        };
+        let schema_provider = SchemaProvider::from_schema(schema);
+        let doc = TomlAnalyzer::new(schema_provider.clone())
+            .parse("[banner]\ncolor = \"#00ff00\"\naccent = 'cyan'\ntext = \"#ffffff\"\n")
+            .unwrap();
+
+        let colors = document_colors(&doc, &schema_provider);
+        assert_eq!(colors.len(), 2);
+        assert_eq!(
+            colors[0].range,
+            Range::new(Position::new(1, 9), Position::new(1, 16))
+        );
+        assert_eq!(colors[0].color, rgba(0x00, 0xff, 0x00, 0xff));
+        assert_eq!(colors[1].range.start, Position::new(2, 10));
+        assert_eq!(colors[1].color, rgba(0x00, 0xcd, 0xcd, 0xff));
+    }
+}
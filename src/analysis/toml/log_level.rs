@@ -0,0 +1,301 @@
+//! 日志级别配置验证模块
+//!
+//! 验证 `[logger]` 配置节中与 tracing 相关的配置项：
+//! - `level`：必须是 tracing 支持的日志级别（`trace`、`debug`、`info`、`warn`、`error`、`off`）
+//! - `override_filter`：必须是合法的 env-filter 指令列表，如 `info,my_app::db=debug,hyper=off`
+//!
+//! env-filter 指令的格式为 `target[span{field=value}]=level`，各部分均可省略。
+//! 这里只检查目标路径、大括号/方括号匹配和日志级别，不验证 span 字段的值。
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Range};
+
+use super::toml_analyzer::{ConfigProperty, ConfigValue, TomlDocument};
+use crate::core::messages::{localized, Locale};
+
+/// 日志配置节名称
+pub const LOGGER_CONFIG_PREFIX: &str = "logger";
+
+/// 日志级别配置项
+pub const LEVEL_KEY: &str = "level";
+
+/// env-filter 指令配置项
+pub const OVERRIDE_FILTER_KEY: &str = "override_filter";
+
+/// tracing 支持的日志级别（从详细到关闭）
+pub const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error", "off"];
+
+/// 日志级别不合法
+pub const INVALID_LOG_LEVEL: &str = "invalid-log-level";
+
+/// env-filter 指令不合法
+pub const INVALID_LOG_FILTER: &str = "invalid-log-filter";
+
+/// 判断配置项是否接受日志级别（用于值补全）
+pub fn is_log_level_property(prefix: &str, key: &str) -> bool {
+    prefix == LOGGER_CONFIG_PREFIX && (key == LEVEL_KEY || key == OVERRIDE_FILTER_KEY)
+}
+
+/// 日志级别的说明
+pub fn level_description(level: &str, locale: Locale) -> String {
+    if LOG_LEVELS.contains(&level) {
+        localized(locale, &format!("log-level.describe.{}", level), &[])
+    } else {
+        String::new()
+    }
+}
+
+/// 日志配置验证器
+#[derive(Debug, Clone, Default)]
+pub struct LogLevelValidator {
+    /// 消息语言
+    locale: Locale,
+}
+
+impl LogLevelValidator {
+    /// 创建新的日志配置验证器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 验证文档中的 `[logger]` 配置节
+    pub fn validate(&self, doc: &TomlDocument) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let Some(section) = doc.config_sections.get(LOGGER_CONFIG_PREFIX) else {
+            return diagnostics;
+        };
+
+        if let Some(property) = section.properties.get(LEVEL_KEY) {
+            if let ConfigValue::String(level) = &property.value {
+                if !LOG_LEVELS.contains(&level.as_str()) {
+                    diagnostics.push(invalid_level(property, level, self.locale));
+                }
+            }
+        }
+
+        if let Some(property) = section.properties.get(OVERRIDE_FILTER_KEY) {
+            if let ConfigValue::String(filter) = &property.value {
+                diagnostics.extend(
+                    validate_filter(filter, self.locale)
+                        .into_iter()
+                        .map(|message| diagnostic(property.range, INVALID_LOG_FILTER, message)),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn invalid_level(property: &ConfigProperty, level: &str, locale: Locale) -> Diagnostic {
+    let lowercase = level.to_lowercase();
+    let message = if LOG_LEVELS.contains(&lowercase.as_str()) {
+        localized(locale, "log-level.lowercase", &[&lowercase])
+    } else {
+        localized(
+            locale,
+            "log-level.invalid",
+            &[&level, &LOG_LEVELS.join(", ")],
+        )
+    };
+    diagnostic(property.range, INVALID_LOG_LEVEL, message)
+}
+
+/// 验证 env-filter 指令列表，返回错误消息
+pub fn validate_filter(filter: &str, locale: Locale) -> Vec<String> {
+    split_directives(filter)
+        .into_iter()
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .filter_map(|directive| validate_directive(directive, locale).err())
+        .collect()
+}
+
+/// 按逗号拆分指令，忽略 span 字段中的逗号
+fn split_directives(filter: &str) -> Vec<&str> {
+    let mut directives = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, ch) in filter.char_indices() {
+        match ch {
+            '[' | '{' => depth += 1,
+            ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                directives.push(&filter[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    directives.push(&filter[start..]);
+    directives
+}
+
+/// 验证单条指令 `target[span{field=value}]=level`
+fn validate_directive(directive: &str, locale: Locale) -> Result<(), String> {
+    // 找到 span 之外的最后一个 `=`
+    let mut depth = 0usize;
+    let mut level_start = None;
+    let mut span_start = None;
+    for (i, ch) in directive.char_indices() {
+        match ch {
+            '[' => {
+                if depth == 0 && span_start.is_none() {
+                    span_start = Some(i);
+                }
+                depth += 1;
+            }
+            '{' => depth += 1,
+            ']' | '}' => {
+                if depth == 0 {
+                    return Err(localized(locale, "log-directive.unbalanced", &[&directive]));
+                }
+                depth -= 1;
+            }
+            '=' if depth == 0 => level_start = Some(i),
+            _ => {}
+        }
+    }
+    if depth > 0 {
+        return Err(localized(locale, "log-directive.unbalanced", &[&directive]));
+    }
+
+    let (selector, level) = match level_start {
+        Some(i) => (&directive[..i], Some(directive[i + 1..].trim())),
+        None => (directive, None),
+    };
+
+    match level {
+        Some(level) if !is_filter_level(level) => {
+            return Err(localized(
+                locale,
+                "log-directive.invalid-level",
+                &[&directive, &level, &LOG_LEVELS.join(", ")],
+            ));
+        }
+        // 只有级别的指令设置全局级别
+        None if is_filter_level(selector.trim()) => return Ok(()),
+        _ => {}
+    }
+
+    let target = match span_start {
+        Some(i) => &selector[..i],
+        None => selector,
+    }
+    .trim();
+    if !target.is_empty() && !is_module_path(target) {
+        // crate 名称中的 '-' 在模块路径中需要写成 '_'
+        let key = if target.contains('-') {
+            "log-directive.invalid-target-dash"
+        } else {
+            "log-directive.invalid-target"
+        };
+        return Err(localized(locale, key, &[&directive, &target]));
+    }
+
+    Ok(())
+}
+
+/// env-filter 接受的级别：级别名称（不区分大小写）或 0-5 的数字
+fn is_filter_level(level: &str) -> bool {
+    LOG_LEVELS.contains(&level.to_lowercase().as_str())
+        || matches!(level, "0" | "1" | "2" | "3" | "4" | "5")
+}
+
+/// 判断是否为 `a::b::c` 形式的模块路径
+fn is_module_path(target: &str) -> bool {
+    target.split("::").all(|segment| {
+        let mut chars = segment.chars();
+        chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+fn diagnostic(range: Range, code: &str, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(code.to_string())),
+        source: Some("spring-lsp".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::toml::toml_analyzer::TomlAnalyzer;
+    use crate::core::schema::SchemaProvider;
+
+    fn validate(content: &str) -> Vec<Diagnostic> {
+        let doc = TomlAnalyzer::new(SchemaProvider::default())
+            .parse(content)
+            .unwrap();
+        LogLevelValidator::new().validate(&doc)
+    }
+
+    #[test]
+    fn test_validate_level() {
+        assert!(validate("[logger]\nlevel = \"debug\"\n").is_empty());
+
+        let diagnostics = validate("[logger]\nlevel = \"verbose\"\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String(INVALID_LOG_LEVEL.to_string()))
+        );
+        assert_eq!(diagnostics[0].range.start.line, 1);
+
+        let diagnostics = validate("[logger]\nlevel = \"INFO\"\n");
+        assert!(diagnostics[0].message.contains("'info'"));
+
+        // 其他配置节中的 level 不检查
+        assert!(validate("[web]\nlevel = \"verbose\"\n").is_empty());
+    }
+
+    #[test]
+    fn test_validate_filter() {
+        assert!(validate_filter(
+            "info,my_app::db=debug,hyper=OFF,tower_http=2",
+            Locale::default()
+        )
+        .is_empty());
+        assert!(validate_filter(
+            "warn,my_app[request{id=1,user=a}]=trace,",
+            Locale::default()
+        )
+        .is_empty());
+        assert!(validate_filter("my_app", Locale::default()).is_empty());
+
+        let errors = validate_filter("my_app=verbose", Locale::default());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'verbose'"));
+
+        assert!(validate_filter("my-app=debug", Locale::default())[0].contains("'_'"));
+        assert_eq!(
+            validate_filter("my_app:db=debug", Locale::default()).len(),
+            1
+        );
+        assert_eq!(
+            validate_filter("my_app[span=debug", Locale::default()).len(),
+            1
+        );
+
+        let diagnostics = validate("[logger]\noverride_filter = \"info,sqlx=loud\"\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String(INVALID_LOG_FILTER.to_string()))
+        );
+    }
+}
@@ -2,6 +2,13 @@
 //!
 //! 负责 TOML 配置文件的解析和分析
 
+pub mod color_value;
+pub mod log_level;
+pub mod secrets;
 pub mod toml_analyzer;
+pub mod url_value;
 
+pub use log_level::LogLevelValidator;
+pub use secrets::SecretValidator;
 pub use toml_analyzer::TomlAnalyzer;
+pub use url_value::UrlValueValidator;
@@ -7,6 +7,10 @@ use lsp_types::{
 use std::collections::HashMap;
 use taplo::dom::node::IntegerValue;
 
+use super::log_level::LogLevelValidator;
+use super::url_value::{self, UrlValueValidator};
+use crate::core::document::{PositionEncoding, PositionMapper};
+use crate::core::messages::{localized, Locale};
 use crate::schema::{PropertySchema, SchemaProvider, TypeInfo};
 
 /// TOML 文档
@@ -22,6 +26,75 @@ pub struct TomlDocument {
     pub config_sections: HashMap<String, ConfigSection>,
     /// 原始内容（用于计算行列位置）
     pub content: String,
+    /// 预处理时替换的环境变量占位符（DOM 偏移换算回原始内容时使用）
+    pub placeholders: PlaceholderOffsets,
+    /// 文档中位置使用的位置编码
+    pub encoding: PositionEncoding,
+}
+
+impl TomlDocument {
+    /// 使用文档的位置编码为原始内容创建位置转换器
+    pub fn mapper(&self) -> PositionMapper<'_> {
+        PositionMapper::with_encoding(&self.content, self.encoding)
+    }
+}
+
+/// 环境变量占位符的偏移对应关系
+///
+/// taplo 解析的是把 `${VAR:default}` 替换为占位符后的内容，占位符与原引用长度不同，
+/// DOM 和语法错误给出的字节偏移需按此换算回原始内容
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlaceholderOffsets {
+    /// 按位置排序的替换记录：(预处理后的字节范围, 原始内容中的字节范围)
+    replacements: Vec<(std::ops::Range<usize>, std::ops::Range<usize>)>,
+}
+
+impl PlaceholderOffsets {
+    /// 把预处理后内容中的字节偏移换算为原始内容中的字节偏移
+    ///
+    /// 落在占位符内部的偏移按相对位置映射到原引用内，不超过原引用的结尾
+    pub fn original_offset(&self, offset: usize) -> usize {
+        let index = self
+            .replacements
+            .partition_point(|(preprocessed, _)| preprocessed.start <= offset);
+        let Some((preprocessed, original)) = index.checked_sub(1).map(|i| &self.replacements[i])
+        else {
+            return offset;
+        };
+        if offset >= preprocessed.end {
+            original.end + (offset - preprocessed.end)
+        } else {
+            (original.start + (offset - preprocessed.start)).min(original.end)
+        }
+    }
+}
+
+/// 把 taplo DOM 中的字节偏移转换为原始内容中的 LSP 位置
+struct DomPositions<'a> {
+    mapper: PositionMapper<'a>,
+    placeholders: &'a PlaceholderOffsets,
+}
+
+impl<'a> DomPositions<'a> {
+    fn new(mapper: PositionMapper<'a>, placeholders: &'a PlaceholderOffsets) -> Self {
+        Self {
+            mapper,
+            placeholders,
+        }
+    }
+
+    fn of(doc: &'a TomlDocument) -> Self {
+        Self::new(doc.mapper(), &doc.placeholders)
+    }
+
+    fn position(&self, offset: impl Into<usize>) -> Position {
+        self.mapper
+            .position(self.placeholders.original_offset(offset.into()))
+    }
+
+    fn range(&self, range: taplo::rowan::TextRange) -> Range {
+        Range::new(self.position(range.start()), self.position(range.end()))
+    }
 }
 
 /// 环境变量引用
@@ -37,6 +110,32 @@ pub struct EnvVarReference {
     pub range: Range,
 }
 
+/// TOML 语法错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TomlSyntaxError {
+    /// 错误说明（taplo 的解析错误信息）
+    pub message: String,
+    /// 在文档中的位置范围
+    pub range: Range,
+}
+
+impl TomlSyntaxError {
+    /// 语法错误诊断的代码
+    pub const CODE: &'static str = "parse_error";
+
+    /// 转换为诊断
+    pub fn to_diagnostic(&self, locale: Locale) -> Diagnostic {
+        Diagnostic {
+            range: self.range,
+            severity: Some(DiagnosticSeverity::ERROR),
+            code: Some(lsp_types::NumberOrString::String(Self::CODE.to_string())),
+            source: Some("spring-lsp".to_string()),
+            message: localized(locale, "toml-syntax-error", &[&self.message]),
+            ..Default::default()
+        }
+    }
+}
+
 /// 配置节
 ///
 /// 表示 TOML 配置文件中的一个配置节，如 `[web]` 或 `[redis]`
@@ -48,6 +147,8 @@ pub struct ConfigSection {
     pub properties: HashMap<String, ConfigProperty>,
     /// 在文档中的位置范围
     pub range: Range,
+    /// 节名称（如 `[web]` 中的 `web`）在文档中的位置范围
+    pub key_range: Range,
 }
 
 /// 配置属性
@@ -82,18 +183,65 @@ pub enum ConfigValue {
     Table(HashMap<String, ConfigValue>),
 }
 
+/// 任务配置节名称
+pub const JOB_CONFIG_PREFIX: &str = "job";
+
+/// 任务配置节中列出禁用任务名称的配置项
+pub const JOB_DISABLED_KEY: &str = "disabled";
+
+/// 配置文件中对任务名称的引用
+///
+/// 例如 `[job] disabled = ["hourly_cleanup"]` 中的 `"hourly_cleanup"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobNameReference {
+    /// 任务名称（对应任务函数名）
+    pub name: String,
+    /// 字符串字面量在文档中的位置范围（包含引号）
+    pub range: Range,
+}
+
 /// TOML 分析器
 ///
 /// 负责解析 TOML 配置文件，提取环境变量引用和配置节
 pub struct TomlAnalyzer {
     /// Schema 提供者
     schema_provider: SchemaProvider,
+    /// 文档中位置使用的位置编码
+    encoding: PositionEncoding,
+    /// 诊断和悬停提示的语言
+    locale: Locale,
 }
 
 impl TomlAnalyzer {
     /// 创建新的 TOML 分析器
     pub fn new(schema_provider: SchemaProvider) -> Self {
-        Self { schema_provider }
+        Self {
+            schema_provider,
+            encoding: PositionEncoding::default(),
+            locale: Locale::default(),
+        }
+    }
+
+    /// 使用与客户端协商的位置编码计算文档中的位置
+    pub fn with_encoding(mut self, encoding: PositionEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// 位置编码
+    pub fn encoding(&self) -> PositionEncoding {
+        self.encoding
+    }
+
+    /// 使用指定的语言生成诊断和悬停提示
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 消息语言
+    pub fn locale(&self) -> Locale {
+        self.locale
     }
 
     /// 获取 Schema 提供者的引用
@@ -118,6 +266,7 @@ impl TomlAnalyzer {
     ///
     /// 1. 配置项悬停：显示配置项的文档、类型信息、默认值等
     /// 2. 环境变量悬停：显示环境变量的当前值（如果可用）
+    /// 3. 配置节悬停：汇总整个配置节（所属插件、已配置的配置项数量、缺少的必需配置项、文档链接）
     pub fn hover(&self, doc: &TomlDocument, position: Position) -> Option<Hover> {
         // 首先检查是否悬停在环境变量上
         if let Some(hover) = self.hover_env_var(doc, position) {
@@ -129,9 +278,103 @@ impl TomlAnalyzer {
             return Some(hover);
         }
 
+        // 最后检查是否悬停在配置节名称上
+        if let Some(hover) = self.hover_config_section(doc, position) {
+            return Some(hover);
+        }
+
         None
     }
 
+    /// 为配置节名称提供汇总的悬停提示
+    fn hover_config_section(&self, doc: &TomlDocument, position: Position) -> Option<Hover> {
+        let section = doc
+            .config_sections
+            .values()
+            .find(|section| self.position_in_range(position, section.key_range))?;
+
+        let mut hover_text = format!(
+            "{}\n\n",
+            localized(self.locale, "hover.section.title", &[&section.prefix])
+        );
+        match self.schema_provider.get_plugin_schema(&section.prefix) {
+            Some(plugin_schema) => {
+                if let Some(description) = plugin_schema
+                    .get("description")
+                    .and_then(|description| description.as_str())
+                {
+                    hover_text.push_str(&format!("{}\n\n", description));
+                }
+                let plugin = format!("spring-{}", section.prefix);
+                hover_text.push_str(&format!(
+                    "{}\n\n",
+                    localized(self.locale, "hover.section.plugin", &[&plugin])
+                ));
+
+                let available = plugin_schema
+                    .get("properties")
+                    .and_then(|properties| properties.as_object())
+                    .map(|properties| properties.len())
+                    .unwrap_or_default();
+                hover_text.push_str(&format!(
+                    "{}\n\n",
+                    localized(
+                        self.locale,
+                        "hover.section.configured-of",
+                        &[&section.properties.len(), &available]
+                    )
+                ));
+
+                let missing: Vec<String> = plugin_schema
+                    .get("required")
+                    .and_then(|required| required.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|key| key.as_str())
+                    .filter(|key| !section.properties.contains_key(*key))
+                    .map(|key| format!("`{}`", key))
+                    .collect();
+                if !missing.is_empty() {
+                    hover_text.push_str(&format!(
+                        "{}\n\n",
+                        localized(
+                            self.locale,
+                            "hover.section.missing-required",
+                            &[&missing.join(", ")]
+                        )
+                    ));
+                }
+
+                hover_text.push_str(&format!(
+                    "{}\n",
+                    localized(self.locale, "hover.section.docs", &[&plugin])
+                ));
+            }
+            None => {
+                hover_text.push_str(&format!(
+                    "{}\n\n",
+                    localized(
+                        self.locale,
+                        "hover.section.configured",
+                        &[&section.properties.len()]
+                    )
+                ));
+                hover_text.push_str(&format!(
+                    "{}\n",
+                    localized(self.locale, "hover.section.custom", &[])
+                ));
+            }
+        }
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: hover_text,
+            }),
+            range: Some(section.key_range),
+        })
+    }
+
     /// 为环境变量提供悬停提示
     fn hover_env_var(&self, doc: &TomlDocument, position: Position) -> Option<Hover> {
         // 查找光标位置的环境变量
@@ -140,29 +383,43 @@ impl TomlAnalyzer {
                 let mut hover_text = String::new();
 
                 // 添加标题
-                hover_text.push_str("# 环境变量\n\n");
+                hover_text.push_str(&format!(
+                    "{}\n\n",
+                    localized(self.locale, "hover.env.title", &[])
+                ));
 
                 // 添加变量名
-                hover_text.push_str(&format!("**变量名**: `{}`\n\n", env_var.name));
+                hover_text.push_str(&format!(
+                    "{}\n\n",
+                    localized(self.locale, "hover.env.name", &[&env_var.name])
+                ));
 
                 // 添加默认值（如果有）
                 if let Some(default) = &env_var.default {
-                    hover_text.push_str(&format!("**默认值**: `{}`\n\n", default));
+                    hover_text.push_str(&format!(
+                        "{}\n\n",
+                        localized(self.locale, "hover.default", &[default])
+                    ));
                 }
 
                 // 尝试获取环境变量的当前值
                 if let Ok(value) = std::env::var(&env_var.name) {
-                    hover_text.push_str(&format!("**当前值**: `{}`\n\n", value));
+                    hover_text.push_str(&format!(
+                        "{}\n\n",
+                        localized(self.locale, "hover.current", &[&value])
+                    ));
                 } else {
-                    hover_text.push_str("**当前值**: *未设置*\n\n");
+                    hover_text.push_str(&format!(
+                        "{}\n\n",
+                        localized(self.locale, "hover.env.unset", &[])
+                    ));
                 }
 
                 // 添加说明
-                hover_text.push_str("**说明**:\n\n");
-                hover_text.push_str("环境变量插值允许在配置文件中引用系统环境变量。\n\n");
-                hover_text.push_str("**格式**:\n");
-                hover_text.push_str("- `${VAR}` - 引用环境变量，如果未设置则报错\n");
-                hover_text.push_str("- `${VAR:default}` - 引用环境变量，如果未设置则使用默认值\n");
+                hover_text.push_str(&format!(
+                    "{}\n",
+                    localized(self.locale, "hover.env.help", &[])
+                ));
 
                 return Some(Hover {
                     contents: HoverContents::Markup(MarkupContent {
@@ -184,6 +441,11 @@ impl TomlAnalyzer {
             // 检查是否悬停在配置节的某个属性上
             for (key, property) in &section.properties {
                 if self.position_in_range(position, property.range) {
+                    // 有 Schema 定义时显示文档、默认值和取值范围
+                    if let Some(schema) = self.schema_provider.property_schema(prefix, key) {
+                        return Some(self.create_property_hover(prefix, key, property, &schema));
+                    }
+
                     // 检查配置项是否在 Schema 中定义
                     let is_defined = self.schema_provider.has_property(prefix, key);
 
@@ -208,7 +470,10 @@ impl TomlAnalyzer {
         let mut hover_text = String::new();
 
         // 添加标题
-        hover_text.push_str(&format!("# 配置项: `{}.{}`\n\n", prefix, key));
+        hover_text.push_str(&format!(
+            "{}\n\n",
+            localized(self.locale, "hover.property.title", &[&prefix, &key])
+        ));
 
         // 添加描述
         if !schema.description.is_empty() {
@@ -217,27 +482,47 @@ impl TomlAnalyzer {
 
         // 添加类型信息
         hover_text.push_str(&format!(
-            "**类型**: {}\n\n",
-            self.type_info_to_string(&schema.type_info)
+            "{}\n\n",
+            localized(
+                self.locale,
+                "hover.type",
+                &[&self.type_info_to_string(&schema.type_info)]
+            )
         ));
 
-        // 添加当前值
+        // 添加当前值及其说明（如日志级别 `debug` 的含义）
         hover_text.push_str(&format!(
-            "**当前值**: `{}`\n\n",
-            self.config_value_to_string(&property.value)
+            "{}\n\n",
+            localized(
+                self.locale,
+                "hover.current",
+                &[&self.config_value_to_string(&property.value)]
+            )
         ));
+        if let ConfigValue::String(value) = &property.value {
+            if let Some(doc) = self.schema_provider.value_doc(prefix, key, value) {
+                hover_text.push_str(&format!("> {}\n\n", doc));
+            }
+        }
 
         // 添加默认值（如果有）
         if let Some(default) = &schema.default {
             hover_text.push_str(&format!(
-                "**默认值**: `{}`\n\n",
-                self.value_to_string(default)
+                "{}\n\n",
+                localized(
+                    self.locale,
+                    "hover.default",
+                    &[&self.value_to_string(default)]
+                )
             ));
         }
 
         // 添加是否必需
         if schema.required {
-            hover_text.push_str("**必需**: 是\n\n");
+            hover_text.push_str(&format!(
+                "{}\n\n",
+                localized(self.locale, "hover.property.required", &[])
+            ));
         }
 
         // 添加枚举值（如果有）
@@ -246,9 +531,18 @@ impl TomlAnalyzer {
             ..
         } = &schema.type_info
         {
-            hover_text.push_str("**允许的值**:\n");
+            hover_text.push_str(&format!(
+                "{}\n",
+                localized(self.locale, "hover.property.allowed-values", &[])
+            ));
             for val in enum_vals {
-                hover_text.push_str(&format!("- `{}`\n", val));
+                match schema.value_docs.get(val) {
+                    Some(doc) => hover_text.push_str(&format!(
+                        "{}\n",
+                        localized(self.locale, "hover.property.allowed-value", &[val, doc])
+                    )),
+                    None => hover_text.push_str(&format!("- `{}`\n", val)),
+                }
             }
             hover_text.push('\n');
         }
@@ -257,24 +551,42 @@ impl TomlAnalyzer {
         match &schema.type_info {
             TypeInfo::Integer { min, max } => {
                 if min.is_some() || max.is_some() {
-                    hover_text.push_str("**值范围**:\n");
+                    hover_text.push_str(&format!(
+                        "{}\n",
+                        localized(self.locale, "hover.property.range", &[])
+                    ));
                     if let Some(min_val) = min {
-                        hover_text.push_str(&format!("- 最小值: `{}`\n", min_val));
+                        hover_text.push_str(&format!(
+                            "{}\n",
+                            localized(self.locale, "hover.property.min", &[min_val])
+                        ));
                     }
                     if let Some(max_val) = max {
-                        hover_text.push_str(&format!("- 最大值: `{}`\n", max_val));
+                        hover_text.push_str(&format!(
+                            "{}\n",
+                            localized(self.locale, "hover.property.max", &[max_val])
+                        ));
                     }
                     hover_text.push('\n');
                 }
             }
             TypeInfo::Float { min, max } => {
                 if min.is_some() || max.is_some() {
-                    hover_text.push_str("**值范围**:\n");
+                    hover_text.push_str(&format!(
+                        "{}\n",
+                        localized(self.locale, "hover.property.range", &[])
+                    ));
                     if let Some(min_val) = min {
-                        hover_text.push_str(&format!("- 最小值: `{}`\n", min_val));
+                        hover_text.push_str(&format!(
+                            "{}\n",
+                            localized(self.locale, "hover.property.min", &[min_val])
+                        ));
                     }
                     if let Some(max_val) = max {
-                        hover_text.push_str(&format!("- 最大值: `{}`\n", max_val));
+                        hover_text.push_str(&format!(
+                            "{}\n",
+                            localized(self.locale, "hover.property.max", &[max_val])
+                        ));
                     }
                     hover_text.push('\n');
                 }
@@ -285,12 +597,21 @@ impl TomlAnalyzer {
                 ..
             } => {
                 if min_length.is_some() || max_length.is_some() {
-                    hover_text.push_str("**长度限制**:\n");
+                    hover_text.push_str(&format!(
+                        "{}\n",
+                        localized(self.locale, "hover.property.length", &[])
+                    ));
                     if let Some(min_len) = min_length {
-                        hover_text.push_str(&format!("- 最小长度: `{}`\n", min_len));
+                        hover_text.push_str(&format!(
+                            "{}\n",
+                            localized(self.locale, "hover.property.min-length", &[min_len])
+                        ));
                     }
                     if let Some(max_len) = max_length {
-                        hover_text.push_str(&format!("- 最大长度: `{}`\n", max_len));
+                        hover_text.push_str(&format!(
+                            "{}\n",
+                            localized(self.locale, "hover.property.max-length", &[max_len])
+                        ));
                     }
                     hover_text.push('\n');
                 }
@@ -300,7 +621,10 @@ impl TomlAnalyzer {
 
         // 添加示例代码（如果有）
         if let Some(example) = &schema.example {
-            hover_text.push_str("**示例**:\n\n");
+            hover_text.push_str(&format!(
+                "{}\n\n",
+                localized(self.locale, "hover.property.example", &[])
+            ));
             hover_text.push_str("```toml\n");
             hover_text.push_str(example);
             hover_text.push_str("\n```\n\n");
@@ -308,13 +632,23 @@ impl TomlAnalyzer {
 
         // 添加废弃警告（如果有）
         if let Some(deprecated_msg) = &schema.deprecated {
-            hover_text.push_str(&format!("⚠️ **已废弃**: {}\n\n", deprecated_msg));
+            hover_text.push_str(&format!(
+                "{}\n\n",
+                localized(self.locale, "hover.property.deprecated", &[deprecated_msg])
+            ));
+        }
+
+        // 添加连接地址的组成部分（如果是连接地址）
+        if let Some(section) = self.url_hover_section(key, Some(schema), property) {
+            hover_text.push_str(&section);
         }
 
         // 添加配置文件位置提示
         hover_text.push_str("---\n\n");
-        hover_text.push_str(&format!("*配置节*: `[{}]`\n", prefix));
-        hover_text.push_str("*配置文件*: `config/app.toml`\n");
+        hover_text.push_str(&format!(
+            "{}\n",
+            localized(self.locale, "hover.property.footer", &[&prefix])
+        ));
 
         Hover {
             contents: HoverContents::Markup(MarkupContent {
@@ -336,29 +670,55 @@ impl TomlAnalyzer {
         let mut hover_text = String::new();
 
         // 添加标题
-        hover_text.push_str(&format!("# 配置项: `{}.{}`\n\n", prefix, key));
+        hover_text.push_str(&format!(
+            "{}\n\n",
+            localized(self.locale, "hover.property.title", &[&prefix, &key])
+        ));
 
-        // 添加当前值
+        // 添加当前值及其说明（如日志级别 `debug` 的含义）
         hover_text.push_str(&format!(
-            "**当前值**: `{}`\n\n",
-            self.config_value_to_string(&property.value)
+            "{}\n\n",
+            localized(
+                self.locale,
+                "hover.current",
+                &[&self.config_value_to_string(&property.value)]
+            )
         ));
+        if let ConfigValue::String(value) = &property.value {
+            if let Some(doc) = self.schema_provider.value_doc(prefix, key, value) {
+                hover_text.push_str(&format!("> {}\n\n", doc));
+            }
+        }
 
         // 添加类型
         hover_text.push_str(&format!(
-            "**类型**: {}\n\n",
-            self.config_value_type_name(&property.value)
+            "{}\n\n",
+            localized(
+                self.locale,
+                "hover.type",
+                &[&self.config_value_type_name(&property.value)]
+            )
         ));
 
         // 如果未在 Schema 中定义，添加警告
         if !is_defined {
-            hover_text.push_str("⚠️ **警告**: 此配置项未在 Schema 中定义\n\n");
+            hover_text.push_str(&format!(
+                "{}\n\n",
+                localized(self.locale, "hover.property.undefined", &[])
+            ));
+        }
+
+        // 添加连接地址的组成部分（如果是连接地址）
+        if let Some(section) = self.url_hover_section(key, None, property) {
+            hover_text.push_str(&section);
         }
 
         // 添加配置文件位置提示
         hover_text.push_str("---\n\n");
-        hover_text.push_str(&format!("*配置节*: `[{}]`\n", prefix));
-        hover_text.push_str("*配置文件*: `config/app.toml`\n");
+        hover_text.push_str(&format!(
+            "{}\n",
+            localized(self.locale, "hover.property.footer", &[&prefix])
+        ));
 
         Hover {
             contents: HoverContents::Markup(MarkupContent {
@@ -369,6 +729,24 @@ impl TomlAnalyzer {
         }
     }
 
+    /// 连接地址配置项的悬停内容（协议、主机、端口和数据库）
+    fn url_hover_section(
+        &self,
+        key: &str,
+        schema: Option<&PropertySchema>,
+        property: &ConfigProperty,
+    ) -> Option<String> {
+        let ConfigValue::String(value) = &property.value else {
+            return None;
+        };
+        if !url_value::is_url_property(key, schema) {
+            return None;
+        }
+        url_value::parse_url(value, self.locale)
+            .ok()
+            .map(|parsed| parsed.hover_section(self.locale))
+    }
+
     /// 检查位置是否在范围内
     fn position_in_range(&self, position: Position, range: Range) -> bool {
         // 检查行号
@@ -389,7 +767,7 @@ impl TomlAnalyzer {
     }
 
     /// 将配置值转换为字符串
-    fn config_value_to_string(&self, value: &ConfigValue) -> String {
+    pub(crate) fn config_value_to_string(&self, value: &ConfigValue) -> String {
         match value {
             ConfigValue::String(s) => format!("\"{}\"", s),
             ConfigValue::Integer(i) => i.to_string(),
@@ -451,6 +829,9 @@ impl TomlAnalyzer {
     /// 4. 废弃项检查：检查是否使用了废弃的配置项
     /// 5. 环境变量语法验证：检查环境变量插值语法是否正确
     /// 6. 值范围验证：检查配置值是否在允许的范围内
+    /// 7. 日志配置验证：检查 `[logger]` 中的日志级别和 env-filter 指令
+    /// 8. 连接地址验证：检查数据库 URI、OTLP 地址等配置值的协议和格式
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
     pub fn validate(&self, doc: &TomlDocument) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -462,7 +843,7 @@ impl TomlAnalyzer {
             // 检查配置节是否在 Schema 中定义
             if self.schema_provider.has_plugin(prefix) {
                 // 验证配置节中的属性
-                diagnostics.extend(self.validate_section_properties(section));
+                diagnostics.extend(self.validate_section_properties(section, &doc.env_vars));
             } else {
                 // 配置节未在 Schema 中定义
                 diagnostics.push(Diagnostic {
@@ -471,21 +852,70 @@ impl TomlAnalyzer {
                     code: Some(lsp_types::NumberOrString::String(
                         "undefined-section".to_string(),
                     )),
-                    message: format!("配置节 '{}' 未在 Schema 中定义", prefix),
+                    message: localized(self.locale, "undefined-section", &[&prefix]),
                     source: Some("spring-lsp".to_string()),
                     ..Default::default()
                 });
             }
         }
 
+        // 3. 验证日志配置
+        diagnostics.extend(
+            LogLevelValidator::new()
+                .with_locale(self.locale)
+                .validate(doc),
+        );
+
+        // 4. 验证连接地址
+        diagnostics.extend(
+            UrlValueValidator::new()
+                .with_locale(self.locale)
+                .validate(doc, &self.schema_provider),
+        );
+
         diagnostics
     }
 
-    /// 验证配置节中的属性（简化版）
-    fn validate_section_properties(&self, section: &ConfigSection) -> Vec<Diagnostic> {
+    /// 验证配置节中的属性
+    ///
+    /// 有 Schema 定义的属性检查废弃、类型和取值范围，其余属性只检查是否已定义
+    fn validate_section_properties(
+        &self,
+        section: &ConfigSection,
+        env_vars: &[EnvVarReference],
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
         for (key, property) in &section.properties {
+            if let Some(schema) = self.schema_provider.property_schema(&section.prefix, key) {
+                if let Some(deprecated_msg) = &schema.deprecated {
+                    diagnostics.push(Diagnostic {
+                        range: property.range,
+                        severity: Some(DiagnosticSeverity::WARNING),
+                        code: Some(lsp_types::NumberOrString::String(
+                            "deprecated-property".to_string(),
+                        )),
+                        message: localized(
+                            self.locale,
+                            "deprecated-property",
+                            &[key, deprecated_msg],
+                        ),
+                        source: Some("spring-lsp".to_string()),
+                        ..Default::default()
+                    });
+                }
+
+                // 使用环境变量插值的值在运行时才确定，不检查类型和范围
+                if !self.uses_env_var(property, env_vars) {
+                    let type_errors = self.validate_property_type(property, &schema);
+                    if type_errors.is_empty() {
+                        diagnostics.extend(self.validate_property_range(property, &schema));
+                    }
+                    diagnostics.extend(type_errors);
+                }
+                continue;
+            }
+
             // 检查配置项是否在 Schema 中定义
             if !self.schema_provider.has_property(&section.prefix, key) {
                 diagnostics.push(Diagnostic {
@@ -494,7 +924,7 @@ impl TomlAnalyzer {
                     code: Some(lsp_types::NumberOrString::String(
                         "undefined-property".to_string(),
                     )),
-                    message: format!("配置项 '{}' 未在 Schema 中定义", key),
+                    message: localized(self.locale, "undefined-property", &[key]),
                     source: Some("spring-lsp".to_string()),
                     ..Default::default()
                 });
@@ -504,6 +934,14 @@ impl TomlAnalyzer {
         diagnostics
     }
 
+    /// 配置值是否使用了环境变量插值（`${VAR}` 或 `"${VAR:default}"`）
+    fn uses_env_var(&self, property: &ConfigProperty, env_vars: &[EnvVarReference]) -> bool {
+        matches!(&property.value, ConfigValue::String(s) if s.contains("${"))
+            || env_vars
+                .iter()
+                .any(|env_var| self.position_in_range(env_var.range.start, property.range))
+    }
+
     fn validate_env_var_syntax(&self, env_vars: &[EnvVarReference]) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -516,7 +954,7 @@ impl TomlAnalyzer {
                     code: Some(lsp_types::NumberOrString::String(
                         "empty-var-name".to_string(),
                     )),
-                    message: "环境变量名不能为空".to_string(),
+                    message: localized(self.locale, "empty-var-name", &[]),
                     source: Some("spring-lsp".to_string()),
                     ..Default::default()
                 });
@@ -534,10 +972,7 @@ impl TomlAnalyzer {
                     code: Some(lsp_types::NumberOrString::String(
                         "invalid-var-name".to_string(),
                     )),
-                    message: format!(
-                        "环境变量名 '{}' 不符合命名规范，建议使用大写字母、数字和下划线",
-                        env_var.name
-                    ),
+                    message: localized(self.locale, "invalid-var-name", &[&env_var.name]),
                     source: Some("spring-lsp".to_string()),
                     ..Default::default()
                 });
@@ -547,6 +982,126 @@ impl TomlAnalyzer {
         diagnostics
     }
 
+    /// 提取配置中引用的任务名称
+    ///
+    /// 目前支持 `[job]` 配置节中的 `disabled` 数组
+    pub fn job_name_references(&self, doc: &TomlDocument) -> Vec<JobNameReference> {
+        let mut references = Vec::new();
+
+        let Some(job_table) = doc
+            .root
+            .as_table()
+            .and_then(|root| root.get(JOB_CONFIG_PREFIX))
+        else {
+            return references;
+        };
+        let Some(disabled) = job_table
+            .as_table()
+            .and_then(|table| table.get(JOB_DISABLED_KEY))
+        else {
+            return references;
+        };
+
+        if let Some(array) = disabled.as_array() {
+            for item in array.items().get().iter() {
+                if let taplo::dom::Node::Str(name) = item {
+                    references.push(JobNameReference {
+                        name: name.value().to_string(),
+                        range: self.node_to_range(item, &DomPositions::of(doc)),
+                    });
+                }
+            }
+        }
+
+        references
+    }
+
+    /// 查找光标位置处的任务名称引用
+    pub fn job_name_reference_at(
+        &self,
+        doc: &TomlDocument,
+        position: Position,
+    ) -> Option<JobNameReference> {
+        self.job_name_references(doc)
+            .into_iter()
+            .find(|reference| self.position_in_range(position, reference.range))
+    }
+
+    /// 光标处的配置节或配置项不能重命名的原因
+    ///
+    /// 插件通过 Schema 中定义的名称读取配置，重命名这些配置节或配置项会使配置失效；
+    /// 其余位置返回 `None`
+    pub fn rename_rejection(&self, doc: &TomlDocument, position: Position) -> Option<String> {
+        let positions = DomPositions::of(doc);
+        for (prefix, section) in &doc.config_sections {
+            let plugin = format!("spring-{}", prefix);
+            if self.position_in_range(position, section.key_range)
+                && self.schema_provider.get_plugin_schema(prefix).is_some()
+            {
+                return Some(localized(
+                    self.locale,
+                    "rename.schema-key",
+                    &[prefix, &plugin],
+                ));
+            }
+            let Some(table) = doc
+                .root
+                .as_table()
+                .and_then(|root| root.get(prefix))
+                .and_then(|node| node.as_table().cloned())
+            else {
+                continue;
+            };
+            for (key, _) in table.entries().get().iter() {
+                let name = key.value().to_string();
+                let on_key = key
+                    .text_ranges()
+                    .any(|range| self.position_in_range(position, positions.range(range)));
+                if on_key && self.schema_provider.has_property(prefix, &name) {
+                    return Some(localized(
+                        self.locale,
+                        "rename.schema-key",
+                        &[&name, &plugin],
+                    ));
+                }
+            }
+        }
+        None
+    }
+
+    /// 验证配置中引用的任务名称是否存在
+    ///
+    /// # 参数
+    ///
+    /// * `doc` - TOML 文档
+    /// * `known_jobs` - 项目中所有任务函数的名称
+    pub fn validate_job_names(&self, doc: &TomlDocument, known_jobs: &[&str]) -> Vec<Diagnostic> {
+        self.job_name_references(doc)
+            .into_iter()
+            .filter(|reference| !known_jobs.contains(&reference.name.as_str()))
+            .map(|reference| {
+                let suggestion = if known_jobs.is_empty() {
+                    String::new()
+                } else {
+                    localized(
+                        self.locale,
+                        "unknown-job.available",
+                        &[&known_jobs.join(", ")],
+                    )
+                };
+
+                Diagnostic {
+                    range: reference.range,
+                    severity: Some(DiagnosticSeverity::WARNING),
+                    code: Some(lsp_types::NumberOrString::String("unknown-job".to_string())),
+                    message: localized(self.locale, "unknown-job", &[&reference.name, &suggestion]),
+                    source: Some("spring-lsp".to_string()),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
     /// 验证配置节中的属性
     fn validate_section(
         &self,
@@ -565,7 +1120,11 @@ impl TomlAnalyzer {
                         code: Some(lsp_types::NumberOrString::String(
                             "deprecated-property".to_string(),
                         )),
-                        message: format!("配置项 '{}' 已废弃: {}", key, deprecated_msg),
+                        message: localized(
+                            self.locale,
+                            "deprecated-property",
+                            &[key, deprecated_msg],
+                        ),
                         source: Some("spring-lsp".to_string()),
                         ..Default::default()
                     });
@@ -584,7 +1143,7 @@ impl TomlAnalyzer {
                     code: Some(lsp_types::NumberOrString::String(
                         "undefined-property".to_string(),
                     )),
-                    message: format!("配置项 '{}' 未在 Schema 中定义", key),
+                    message: localized(self.locale, "undefined-property", &[key]),
                     source: Some("spring-lsp".to_string()),
                     ..Default::default()
                 });
@@ -622,9 +1181,10 @@ impl TomlAnalyzer {
                 code: Some(lsp_types::NumberOrString::String(
                     "type-mismatch".to_string(),
                 )),
-                message: format!(
-                    "配置项 '{}' 的类型不匹配：期望 {}，实际 {}",
-                    property.key, expected_type, actual_type
+                message: localized(
+                    self.locale,
+                    "type-mismatch",
+                    &[&property.key, &expected_type, &actual_type],
                 ),
                 source: Some("spring-lsp".to_string()),
                 ..Default::default()
@@ -661,9 +1221,10 @@ impl TomlAnalyzer {
                             code: Some(lsp_types::NumberOrString::String(
                                 "invalid-enum-value".to_string(),
                             )),
-                            message: format!(
-                                "配置项 '{}' 的值 '{}' 不在允许的枚举值中：{:?}",
-                                property.key, s, enum_vals
+                            message: localized(
+                                self.locale,
+                                "invalid-enum-value",
+                                &[&property.key, s, &format!("{:?}", enum_vals)],
                             ),
                             source: Some("spring-lsp".to_string()),
                             ..Default::default()
@@ -680,11 +1241,10 @@ impl TomlAnalyzer {
                             code: Some(lsp_types::NumberOrString::String(
                                 "string-too-short".to_string(),
                             )),
-                            message: format!(
-                                "配置项 '{}' 的值长度 {} 小于最小长度 {}",
-                                property.key,
-                                s.len(),
-                                min_len
+                            message: localized(
+                                self.locale,
+                                "string-too-short",
+                                &[&property.key, &s.len(), min_len],
                             ),
                             source: Some("spring-lsp".to_string()),
                             ..Default::default()
@@ -701,11 +1261,10 @@ impl TomlAnalyzer {
                             code: Some(lsp_types::NumberOrString::String(
                                 "string-too-long".to_string(),
                             )),
-                            message: format!(
-                                "配置项 '{}' 的值长度 {} 超过最大长度 {}",
-                                property.key,
-                                s.len(),
-                                max_len
+                            message: localized(
+                                self.locale,
+                                "string-too-long",
+                                &[&property.key, &s.len(), max_len],
                             ),
                             source: Some("spring-lsp".to_string()),
                             ..Default::default()
@@ -724,9 +1283,10 @@ impl TomlAnalyzer {
                             code: Some(lsp_types::NumberOrString::String(
                                 "value-too-small".to_string(),
                             )),
-                            message: format!(
-                                "配置项 '{}' 的值 {} 小于最小值 {}",
-                                property.key, i, min_val
+                            message: localized(
+                                self.locale,
+                                "value-too-small",
+                                &[&property.key, i, min_val],
                             ),
                             source: Some("spring-lsp".to_string()),
                             ..Default::default()
@@ -742,9 +1302,10 @@ impl TomlAnalyzer {
                             code: Some(lsp_types::NumberOrString::String(
                                 "value-too-large".to_string(),
                             )),
-                            message: format!(
-                                "配置项 '{}' 的值 {} 超过最大值 {}",
-                                property.key, i, max_val
+                            message: localized(
+                                self.locale,
+                                "value-too-large",
+                                &[&property.key, i, max_val],
                             ),
                             source: Some("spring-lsp".to_string()),
                             ..Default::default()
@@ -763,9 +1324,10 @@ impl TomlAnalyzer {
                             code: Some(lsp_types::NumberOrString::String(
                                 "value-too-small".to_string(),
                             )),
-                            message: format!(
-                                "配置项 '{}' 的值 {} 小于最小值 {}",
-                                property.key, f, min_val
+                            message: localized(
+                                self.locale,
+                                "value-too-small",
+                                &[&property.key, f, min_val],
                             ),
                             source: Some("spring-lsp".to_string()),
                             ..Default::default()
@@ -781,9 +1343,10 @@ impl TomlAnalyzer {
                             code: Some(lsp_types::NumberOrString::String(
                                 "value-too-large".to_string(),
                             )),
-                            message: format!(
-                                "配置项 '{}' 的值 {} 超过最大值 {}",
-                                property.key, f, max_val
+                            message: localized(
+                                self.locale,
+                                "value-too-large",
+                                &[&property.key, f, max_val],
                             ),
                             source: Some("spring-lsp".to_string()),
                             ..Default::default()
@@ -814,7 +1377,7 @@ impl TomlAnalyzer {
                     code: Some(lsp_types::NumberOrString::String(
                         "missing-required-property".to_string(),
                     )),
-                    message: format!("缺少必需的配置项 '{}'", key),
+                    message: localized(self.locale, "missing-required-property", &[key]),
                     source: Some("spring-lsp".to_string()),
                     ..Default::default()
                 });
@@ -827,24 +1390,24 @@ impl TomlAnalyzer {
     /// 将 TypeInfo 转换为字符串描述
     fn type_info_to_string(&self, type_info: &TypeInfo) -> String {
         match type_info {
-            TypeInfo::String { .. } => "字符串".to_string(),
-            TypeInfo::Integer { .. } => "整数".to_string(),
-            TypeInfo::Float { .. } => "浮点数".to_string(),
-            TypeInfo::Boolean => "布尔值".to_string(),
-            TypeInfo::Array { .. } => "数组".to_string(),
-            TypeInfo::Object { .. } => "对象".to_string(),
+            TypeInfo::String { .. } => localized(self.locale, "type.string", &[]),
+            TypeInfo::Integer { .. } => localized(self.locale, "type.integer", &[]),
+            TypeInfo::Float { .. } => localized(self.locale, "type.float", &[]),
+            TypeInfo::Boolean => localized(self.locale, "type.boolean", &[]),
+            TypeInfo::Array { .. } => localized(self.locale, "type.array", &[]),
+            TypeInfo::Object { .. } => localized(self.locale, "type.object", &[]),
         }
     }
 
     /// 获取配置值的类型名称
     fn config_value_type_name(&self, value: &ConfigValue) -> String {
         match value {
-            ConfigValue::String(_) => "字符串".to_string(),
-            ConfigValue::Integer(_) => "整数".to_string(),
-            ConfigValue::Float(_) => "浮点数".to_string(),
-            ConfigValue::Boolean(_) => "布尔值".to_string(),
-            ConfigValue::Array(_) => "数组".to_string(),
-            ConfigValue::Table(_) => "对象".to_string(),
+            ConfigValue::String(_) => localized(self.locale, "type.string", &[]),
+            ConfigValue::Integer(_) => localized(self.locale, "type.integer", &[]),
+            ConfigValue::Float(_) => localized(self.locale, "type.float", &[]),
+            ConfigValue::Boolean(_) => localized(self.locale, "type.boolean", &[]),
+            ConfigValue::Array(_) => localized(self.locale, "type.array", &[]),
+            ConfigValue::Table(_) => localized(self.locale, "type.object", &[]),
         }
     }
 
@@ -872,49 +1435,102 @@ impl TomlAnalyzer {
     /// assert_eq!(doc.config_sections.len(), 1);
     /// ```
     pub fn parse(&self, content: &str) -> Result<TomlDocument, String> {
+        let (doc, errors) = self.parse_recovering(content);
+        if !errors.is_empty() {
+            let error_messages: Vec<String> = errors
+                .iter()
+                .map(|e| {
+                    format!(
+                        "{}:{} - {}",
+                        e.range.start.line + 1,
+                        e.range.start.character + 1,
+                        e.message
+                    )
+                })
+                .collect();
+            return Err(localized(
+                self.locale,
+                "toml-syntax-error",
+                &[&error_messages.join("; ")],
+            ));
+        }
+        Ok(doc)
+    }
+
+    /// 容错解析 TOML 文档
+    ///
+    /// 有语法错误时仍然返回文档：taplo 跳过无法解析的部分，其余配置节和配置项照常提取，
+    /// 与语法错误重叠的配置项（如只写了一半的值）被丢弃，避免对不完整的内容给出类型错误等诊断。
+    /// 编辑器中的配置文件使用此方法，一行语法错误不会使整个文件失去补全、悬停和验证
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
+    pub fn parse_recovering(&self, content: &str) -> (TomlDocument, Vec<TomlSyntaxError>) {
         // 预处理：提取环境变量引用并替换为占位符
-        let (preprocessed_content, env_vars) = self.preprocess_env_vars(content);
+        let (preprocessed_content, env_vars, placeholders) = self.preprocess_env_vars(content);
+        let positions = DomPositions::new(
+            PositionMapper::with_encoding(content, self.encoding),
+            &placeholders,
+        );
 
         // 使用 taplo 解析预处理后的 TOML
         let parse_result = taplo::parser::parse(&preprocessed_content);
-
-        // 检查语法错误
-        if !parse_result.errors.is_empty() {
-            let error_messages: Vec<String> = parse_result
-                .errors
-                .iter()
-                .map(|e| format!("{:?}:{:?} - {}", e.range.start(), e.range.end(), e.message))
-                .collect();
-            return Err(format!("TOML 语法错误: {}", error_messages.join("; ")));
-        }
+        let errors: Vec<TomlSyntaxError> = parse_result
+            .errors
+            .iter()
+            .map(|e| TomlSyntaxError {
+                message: e.message.clone(),
+                range: positions.range(e.range),
+            })
+            .collect();
 
         // 转换为 DOM
         let root = parse_result.into_dom();
 
         // 提取配置节
-        let config_sections = self.extract_config_sections(&root, content);
+        let mut config_sections = self.extract_config_sections(&root, &positions);
+        for section in config_sections.values_mut() {
+            section.properties.retain(|_, property| {
+                !errors
+                    .iter()
+                    .any(|error| ranges_overlap(&property.range, &error.range))
+            });
+        }
 
-        Ok(TomlDocument {
-            root,
-            env_vars,
-            config_sections,
-            content: content.to_string(),
-        })
+        (
+            TomlDocument {
+                root,
+                env_vars,
+                config_sections,
+                content: content.to_string(),
+                placeholders,
+                encoding: self.encoding,
+            },
+            errors,
+        )
     }
 
     /// 预处理环境变量引用
     ///
     /// 将 `${VAR:default}` 或 `${VAR}` 替换为占位符，以便 TOML 解析器能够正常解析
-    /// 同时提取所有环境变量引用的位置信息
+    /// 同时提取所有环境变量引用的位置信息，并记录占位符与原引用的偏移对应关系
     ///
     /// **注意**：只处理引号外的环境变量引用，引号内的会被保留（因为它们是合法的 TOML 字符串）
-    fn preprocess_env_vars(&self, content: &str) -> (String, Vec<EnvVarReference>) {
+    fn preprocess_env_vars(
+        &self,
+        content: &str,
+    ) -> (String, Vec<EnvVarReference>, PlaceholderOffsets) {
         let mut result = String::with_capacity(content.len());
         let mut env_vars = Vec::new();
+        let mut placeholders = PlaceholderOffsets::default();
+        let byte_offsets: Vec<usize> = content
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(std::iter::once(content.len()))
+            .collect();
         let mut line = 0u32;
         let mut line_start = 0;
         let mut i = 0;
         let chars: Vec<char> = content.chars().collect();
+        let mapper = PositionMapper::with_encoding(content, self.encoding);
         let mut in_string = false; // 跟踪是否在字符串内
         let mut in_multiline_string = false; // 跟踪是否在多行字符串内
         let mut escape_next = false; // 跟踪下一个字符是否被转义
@@ -990,14 +1606,8 @@ impl TomlAnalyzer {
                             name: name.clone(),
                             default: default.clone(),
                             range: Range {
-                                start: Position {
-                                    line,
-                                    character: start_char as u32,
-                                },
-                                end: Position {
-                                    line,
-                                    character: end_char as u32,
-                                },
+                                start: mapper.line_column_position(line as usize + 1, start_char),
+                                end: mapper.line_column_position(line as usize + 1, end_char),
                             },
                         });
 
@@ -1019,6 +1629,10 @@ impl TomlAnalyzer {
                             "\"\"".to_string()
                         };
 
+                        placeholders.replacements.push((
+                            result.len()..result.len() + placeholder.len(),
+                            byte_offsets[i]..byte_offsets[j + 1],
+                        ));
                         result.push_str(&placeholder);
                         i = j + 1;
                         continue;
@@ -1030,7 +1644,7 @@ impl TomlAnalyzer {
             i += 1;
         }
 
-        (result, env_vars)
+        (result, env_vars, placeholders)
     }
 
     /// 提取环境变量引用
@@ -1040,7 +1654,7 @@ impl TomlAnalyzer {
     fn extract_config_sections(
         &self,
         root: &taplo::dom::Node,
-        content: &str,
+        positions: &DomPositions,
     ) -> HashMap<String, ConfigSection> {
         let mut sections = HashMap::new();
 
@@ -1055,8 +1669,13 @@ impl TomlAnalyzer {
 
                 // 只处理表类型的节（配置节）
                 if value.as_table().is_some() {
-                    let properties = self.extract_properties(value, content);
-                    let range = self.node_to_range(value, content);
+                    let properties = self.extract_properties(value, positions);
+                    let range = self.node_to_range(value, positions);
+                    let key_range = key
+                        .text_ranges()
+                        .next()
+                        .map(|key_range| positions.range(key_range))
+                        .unwrap_or(range);
 
                     sections.insert(
                         prefix.clone(),
@@ -1064,6 +1683,7 @@ impl TomlAnalyzer {
                             prefix,
                             properties,
                             range,
+                            key_range,
                         },
                     );
                 }
@@ -1079,7 +1699,7 @@ impl TomlAnalyzer {
     fn extract_properties(
         &self,
         node: &taplo::dom::Node,
-        content: &str,
+        positions: &DomPositions,
     ) -> HashMap<String, ConfigProperty> {
         let mut properties = HashMap::new();
 
@@ -1092,7 +1712,7 @@ impl TomlAnalyzer {
             for (key, value) in entries_arc.iter() {
                 let key_str = key.value().to_string();
                 let config_value = self.node_to_config_value(value);
-                let range = self.node_to_range(value, content);
+                let range = self.node_to_range(value, positions);
 
                 properties.insert(
                     key_str.clone(),
@@ -1153,21 +1773,12 @@ impl TomlAnalyzer {
     /// 将 TOML 节点转换为 LSP 范围
     ///
     /// 将 taplo 提供的字节偏移量转换为行号和字符位置
-    fn node_to_range(&self, node: &taplo::dom::Node, content: &str) -> Range {
+    fn node_to_range(&self, node: &taplo::dom::Node, positions: &DomPositions) -> Range {
         // taplo 的 text_ranges 返回一个迭代器
         let mut text_ranges = node.text_ranges();
         if let Some(first_range) = text_ranges.next() {
-            let start: usize = first_range.start().into();
-            let end: usize = first_range.end().into();
-
             // 将字节偏移量转换为行号和字符位置
-            let start_pos = self.byte_offset_to_position(content, start);
-            let end_pos = self.byte_offset_to_position(content, end);
-
-            Range {
-                start: start_pos,
-                end: end_pos,
-            }
+            positions.range(first_range)
         } else {
             // 默认范围
             Range {
@@ -1182,41 +1793,129 @@ impl TomlAnalyzer {
             }
         }
     }
+}
 
-    /// 将字节偏移量转换为 LSP Position
-    ///
-    /// 遍历内容，计算字节偏移量对应的行号和字符位置
-    fn byte_offset_to_position(&self, content: &str, byte_offset: usize) -> lsp_types::Position {
-        let mut line = 0;
-        let mut character = 0;
-        let mut current_offset = 0;
-
-        for ch in content.chars() {
-            if current_offset >= byte_offset {
-                break;
-            }
-
-            if ch == '\n' {
-                line += 1;
-                character = 0;
-            } else {
-                character += 1;
-            }
-
-            current_offset += ch.len_utf8();
-        }
-
-        lsp_types::Position {
-            line: line as u32,
-            character: character as u32,
-        }
-    }
+/// 两个范围是否重叠（相接也视为重叠）
+pub(crate) fn ranges_overlap(a: &Range, b: &Range) -> bool {
+    a.start <= b.end && b.start <= a.end
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_recovering() {
+        let analyzer = TomlAnalyzer::new(SchemaProvider::new());
+        let content = "[web]\nport = 8080\nhost = \n\n[redis]\nuri = \"redis://localhost\"\n";
+
+        let (doc, errors) = analyzer.parse_recovering(content);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "expected value");
+        assert_eq!(errors[0].range.start, Position::new(2, 7));
+        let diagnostic = errors[0].to_diagnostic(Locale::default());
+        assert_eq!(diagnostic.message, "TOML 语法错误: expected value");
+
+        // 错误之外的配置照常提取，不完整的配置项被丢弃
+        let web = &doc.config_sections["web"];
+        assert!(web.properties.contains_key("port"));
+        assert!(!web.properties.contains_key("host"));
+        assert!(doc.config_sections["redis"].properties.contains_key("uri"));
+
+        assert_eq!(
+            analyzer.parse(content).unwrap_err(),
+            "TOML 语法错误: 3:8 - expected value"
+        );
+    }
+
+    #[test]
+    fn test_parse_recovering_after_env_var_placeholder() {
+        let analyzer = TomlAnalyzer::new(SchemaProvider::new());
+        let content = "[web]\nport = ${PORT:8080}\nhost = \nname = ${APP_NAME:demo} # 应用名\n";
+
+        // 占位符与原引用长度不同，错误位置仍按原始内容计算
+        let (doc, errors) = analyzer.parse_recovering(content);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].range.start, Position::new(2, 7));
+
+        let web = &doc.config_sections["web"];
+        assert!(web.properties.contains_key("port"));
+        assert!(web.properties.contains_key("name"));
+        assert!(!web.properties.contains_key("host"));
+        assert_eq!(
+            web.properties["port"].range,
+            Range::new(Position::new(1, 7), Position::new(1, 19))
+        );
+        assert_eq!(
+            web.properties["name"].range,
+            Range::new(Position::new(3, 7), Position::new(3, 23))
+        );
+    }
+
+    #[test]
+    fn test_job_name_references() {
+        let analyzer = TomlAnalyzer::new(SchemaProvider::new());
+        let doc = analyzer
+            .parse("[job]\ndisabled = [\"hourly_cleanup\", \"missing\"]\n")
+            .unwrap();
+
+        let references = analyzer.job_name_references(&doc);
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].name, "hourly_cleanup");
+        assert_eq!(references[0].range.start, Position::new(1, 12));
+        assert_eq!(references[0].range.end, Position::new(1, 28));
+
+        let found = analyzer.job_name_reference_at(&doc, Position::new(1, 33));
+        assert_eq!(found.map(|r| r.name), Some("missing".to_string()));
+
+        let diagnostics = analyzer.validate_job_names(&doc, &["hourly_cleanup"]);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("missing"));
+        assert_eq!(
+            diagnostics[0].code,
+            Some(lsp_types::NumberOrString::String("unknown-job".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_rename_rejection() {
+        let schema = crate::schema::ConfigSchema {
+            schema_type: "object".to_string(),
+            plugins: [(
+                "web".to_string(),
+                serde_json::json!({
+                    "type": "object",
+                    "properties": { "port": { "type": "integer" } }
+                }),
+            )]
+            .into_iter()
+            .collect(),
+        };
+        let analyzer = TomlAnalyzer::new(SchemaProvider::from_schema(schema));
+        let doc = analyzer
+            .parse("[web]\nport = 8080\nextra = 1\n\n[custom]\nkey = 1\n")
+            .unwrap();
+
+        let reason = analyzer
+            .rename_rejection(&doc, Position::new(0, 2))
+            .unwrap();
+        assert!(reason.contains("spring-web"));
+        let reason = analyzer
+            .rename_rejection(&doc, Position::new(1, 1))
+            .unwrap();
+        assert!(reason.contains("'port'"));
+        // Schema 中未定义的配置项和自定义配置节
+        assert!(analyzer
+            .rename_rejection(&doc, Position::new(2, 1))
+            .is_none());
+        assert!(analyzer
+            .rename_rejection(&doc, Position::new(4, 2))
+            .is_none());
+        assert!(analyzer
+            .rename_rejection(&doc, Position::new(5, 1))
+            .is_none());
+    }
+
     #[test]
     fn test_preprocess_env_vars_in_quotes() {
         let schema_provider = SchemaProvider::new();
@@ -1224,7 +1923,7 @@ mod tests {
 
         // 测试：引号内的环境变量应该保持不变
         let content = r#"test_pay_amount = "${TEST_PAY_AMOUNT:false}""#;
-        let (preprocessed, env_vars) = analyzer.preprocess_env_vars(content);
+        let (preprocessed, env_vars, _) = analyzer.preprocess_env_vars(content);
 
         println!("原始: {}", content);
         println!("预处理后: {}", preprocessed);
@@ -1242,7 +1941,7 @@ mod tests {
 
         // 测试：引号外的环境变量应该被替换
         let content = r#"test_pay_amount = ${TEST_PAY_AMOUNT:false}"#;
-        let (preprocessed, env_vars) = analyzer.preprocess_env_vars(content);
+        let (preprocessed, env_vars, _) = analyzer.preprocess_env_vars(content);
 
         println!("原始: {}", content);
         println!("预处理后: {}", preprocessed);
@@ -1299,4 +1998,94 @@ port = ${PORT:8080}
         let doc = result.unwrap();
         assert_eq!(doc.env_vars.len(), 2, "应该提取到 2 个环境变量");
     }
+
+    #[test]
+    fn test_section_header_hover() {
+        let analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        let doc = analyzer
+            .parse("[sqlx]\nmax_connections = 10\n\n[shop]\ncurrency = \"EUR\"\n")
+            .unwrap();
+        assert_eq!(
+            doc.config_sections["sqlx"].key_range,
+            Range::new(Position::new(0, 1), Position::new(0, 5))
+        );
+
+        let hover = analyzer.hover(&doc, Position::new(0, 2)).unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("expected markdown hover");
+        };
+        assert!(content.value.starts_with("# 配置节: `[sqlx]`"));
+        assert!(content.value.contains("**所属插件**: `spring-sqlx`"));
+        assert!(content.value.contains("**已配置**: 1 / "));
+        assert!(content.value.contains("**缺少必需配置项**: `uri`"));
+        assert!(content.value.contains("(https://docs.rs/spring-sqlx)"));
+
+        let hover = analyzer.hover(&doc, Position::new(3, 3)).unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("expected markdown hover");
+        };
+        assert!(content.value.contains("**已配置**: 1 个配置项"));
+        assert!(content.value.contains("自定义配置"));
+    }
+
+    #[test]
+    fn test_bundled_schema_hover_and_validation() {
+        let analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        let doc = analyzer
+            .parse("[mail]\nport = 70000\nsecure = \"yes\"\n\n[opentelemetry]\nprotocol = \"thrift\"\n\n[web]\nport = ${PORT}\n")
+            .unwrap();
+
+        let hover = analyzer.hover(&doc, Position::new(1, 9)).unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("expected markdown hover");
+        };
+        assert!(content.value.contains("SMTP 服务器端口"));
+        assert!(content.value.contains("**默认值**: `465`"));
+        assert!(content.value.contains("最大值: `65535`"));
+
+        let codes: Vec<_> = analyzer
+            .validate(&doc)
+            .into_iter()
+            .filter_map(|d| match d.code {
+                Some(lsp_types::NumberOrString::String(code)) => Some(code),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            codes.iter().filter(|c| *c == "value-too-large").count(),
+            1,
+            "{:?}",
+            codes
+        );
+        assert!(codes.contains(&"type-mismatch".to_string()));
+        assert!(codes.contains(&"invalid-enum-value".to_string()));
+        // 环境变量插值不检查类型
+        assert_eq!(codes.len(), 3, "{:?}", codes);
+    }
+
+    #[test]
+    fn test_value_hover() {
+        let analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        let doc = analyzer
+            .parse("[logger]\nlevel = \"debug\"\nformat = \"json\"\n")
+            .unwrap();
+
+        let hover = analyzer.hover(&doc, Position::new(1, 10)).unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("expected markdown hover");
+        };
+        assert!(content.value.contains("**当前值**: `\"debug\"`"));
+        assert!(content.value.contains(&format!(
+            "> {}",
+            super::super::log_level::level_description("debug", Locale::default())
+        )));
+
+        // 没有取值说明的枚举值
+        let hover = analyzer.hover(&doc, Position::new(2, 11)).unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("expected markdown hover");
+        };
+        assert!(!content.value.contains("> "));
+        assert!(content.value.contains("- `json`\n"));
+    }
 }
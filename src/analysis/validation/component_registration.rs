@@ -0,0 +1,233 @@
+//! 注册缺失组件的快速修复模块
+//!
+//! 依赖注入验证报告 `component-not-registered`（注入的类型已定义但没有注册为组件）时，
+//! 在诊断的 `data` 中记录组件类型（[`MissingComponent`]），代码操作按模板提供两种修复：
+//! - 在应用入口的 `App::new()` 之后插入 `.add_component(T::new())`（[`ADD_COMPONENT_TEMPLATE`]）
+//! - 在服务所在文件末尾添加提供该组件的 `#[component]` 函数（[`PROVIDER_TEMPLATE`]）
+//!
+//! 应用入口是 crate 中调用 `App::new()` 的文件，`src/main.rs` 优先。
+
+use lsp_types::{Diagnostic, NumberOrString, Range, TextEdit, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::core::document::{PositionEncoding, PositionMapper};
+
+/// 注入的组件类型已定义但没有注册
+pub const COMPONENT_NOT_REGISTERED: &str = "component-not-registered";
+
+/// 在应用构建器上注册组件的模板（`{type}` 为组件类型）
+pub const ADD_COMPONENT_TEMPLATE: &str = ".add_component({type}::new())";
+
+/// 提供组件的 `#[component]` 函数模板（`{type}` 为组件类型，`{name}` 为类型名的 snake_case 形式）
+pub const PROVIDER_TEMPLATE: &str =
+    "\n#[component]\nfn create_{name}() -> {type} {\n    todo!(\"构建 {type}\")\n}\n";
+
+/// 应用构建器的创建表达式
+const APP_NEW: &str = "App::new()";
+
+/// 未注册的组件，保存在 `component-not-registered` 诊断的 `data` 中
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingComponent {
+    /// 组件类型
+    pub component_type: String,
+}
+
+impl MissingComponent {
+    /// 把组件类型附加到诊断上
+    pub fn attach(&self, diagnostic: &mut Diagnostic) {
+        diagnostic.data = serde_json::to_value(self).ok();
+    }
+
+    /// 读取 `component-not-registered` 诊断附带的组件类型
+    pub fn from_diagnostic(diagnostic: &Diagnostic) -> Option<Self> {
+        if diagnostic.code != Some(NumberOrString::String(COMPONENT_NOT_REGISTERED.to_string())) {
+            return None;
+        }
+        serde_json::from_value(diagnostic.data.clone()?).ok()
+    }
+
+    /// 在应用入口注册组件的编辑
+    ///
+    /// `sources` 是 crate 中的 Rust 文件，没有文件调用 `App::new()` 时返回 `None`。
+    /// 构建器调用链换行书写时，新的调用单独占一行并沿用下一行的缩进；编辑位置按 `encoding` 计算
+    pub fn add_component_edit(
+        &self,
+        sources: &[(Url, String)],
+        encoding: PositionEncoding,
+    ) -> Option<(Url, TextEdit)> {
+        let (uri, content) = sources
+            .iter()
+            .filter(|(_, content)| content.contains(APP_NEW))
+            .min_by_key(|(uri, _)| !uri.path().ends_with("/src/main.rs"))?;
+
+        let end = content.find(APP_NEW)? + APP_NEW.len();
+        let call = render(ADD_COMPONENT_TEMPLATE, &self.component_type);
+        let rest = &content[end..];
+        let line_end = rest.find('\n').unwrap_or(rest.len());
+        let next_line = rest[line_end..].trim_start_matches('\n').lines().next();
+        let new_text = match next_line {
+            Some(line)
+                if rest[..line_end].trim().is_empty() && line.trim_start().starts_with('.') =>
+            {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                format!("\n{}{}", indent, call)
+            }
+            _ => call,
+        };
+
+        let position = PositionMapper::with_encoding(content, encoding).position(end);
+        Some((
+            uri.clone(),
+            TextEdit {
+                range: Range::new(position, position),
+                new_text,
+            },
+        ))
+    }
+
+    /// 在文档末尾添加提供组件的 `#[component]` 函数的编辑
+    pub fn provider_edit(&self, lines: &PositionMapper) -> TextEdit {
+        let content = lines.content();
+        let end = lines.position(content.len());
+        let mut new_text = render(PROVIDER_TEMPLATE, &self.component_type);
+        if !content.is_empty() && !content.ends_with('\n') {
+            new_text.insert(0, '\n');
+        }
+        TextEdit {
+            range: Range::new(end, end),
+            new_text,
+        }
+    }
+}
+
+/// 用组件类型填充模板
+fn render(template: &str, component_type: &str) -> String {
+    template
+        .replace("{type}", component_type)
+        .replace("{name}", &default_component_name(component_type))
+}
+
+/// 组件类型按约定的默认名称（`crate::repo::UserRepo` 对应 `user_repo`）
+///
+/// 泛型参数和路径前缀不参与名称
+pub fn default_component_name(component_type: &str) -> String {
+    let base = component_type
+        .split('<')
+        .next()
+        .unwrap_or(component_type)
+        .rsplit("::")
+        .next()
+        .unwrap_or(component_type)
+        .trim();
+    snake_case(base)
+}
+
+/// 把类型名（`UserRepo`）转换为 snake_case（`user_repo`）
+fn snake_case(name: &str) -> String {
+    let mut output = String::with_capacity(name.len() + 4);
+    let chars: Vec<char> = name.chars().collect();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            // 连续的大写字母（如 `HTTPClient`）只在单词边界处分隔
+            let boundary = i > 0
+                && (chars[i - 1].is_lowercase()
+                    || chars[i - 1].is_ascii_digit()
+                    || chars.get(i + 1).is_some_and(|next| next.is_lowercase())
+                        && chars[i - 1].is_uppercase());
+            if boundary {
+                output.push('_');
+            }
+            output.extend(c.to_lowercase());
+        } else {
+            output.push(*c);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Position;
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("UserRepo"), "user_repo");
+        assert_eq!(snake_case("HTTPClient"), "http_client");
+        assert_eq!(snake_case("Db2Pool"), "db2_pool");
+        assert_eq!(default_component_name("crate::db::Pool<Postgres>"), "pool");
+    }
+
+    #[test]
+    fn test_from_diagnostic() {
+        let mut diagnostic = Diagnostic {
+            range: Range::default(),
+            code: Some(NumberOrString::String(COMPONENT_NOT_REGISTERED.to_string())),
+            ..Default::default()
+        };
+        let missing = MissingComponent {
+            component_type: "UserRepo".to_string(),
+        };
+        missing.attach(&mut diagnostic);
+        assert_eq!(
+            MissingComponent::from_diagnostic(&diagnostic),
+            Some(missing)
+        );
+
+        diagnostic.code = Some(NumberOrString::String(
+            "component-type-not-found".to_string(),
+        ));
+        assert_eq!(MissingComponent::from_diagnostic(&diagnostic), None);
+    }
+
+    #[test]
+    fn test_add_component_edit() {
+        let missing = MissingComponent {
+            component_type: "UserRepo".to_string(),
+        };
+        let lib = Url::parse("file:///app/src/lib.rs").unwrap();
+        let main = Url::parse("file:///app/src/main.rs").unwrap();
+        let sources = vec![
+            (lib.clone(), "pub fn build() { App::new().run(); }\n".to_string()),
+            (
+                main.clone(),
+                "#[tokio::main]\nasync fn main() {\n    App::new()\n        .add_plugin(WebPlugin)\n        .run()\n        .await\n}\n"
+                    .to_string(),
+            ),
+        ];
+
+        // src/main.rs 优先，调用链换行书写时沿用缩进
+        let (uri, edit) = missing
+            .add_component_edit(&sources, PositionEncoding::Utf16)
+            .unwrap();
+        assert_eq!(uri, main);
+        assert_eq!(edit.range.start, Position::new(2, 14));
+        assert_eq!(edit.new_text, "\n        .add_component(UserRepo::new())");
+
+        // 单行调用链直接追加
+        let (uri, edit) = missing
+            .add_component_edit(&sources[..1], PositionEncoding::Utf16)
+            .unwrap();
+        assert_eq!(uri, lib);
+        assert_eq!(edit.range.start, Position::new(0, 27));
+        assert_eq!(edit.new_text, ".add_component(UserRepo::new())");
+
+        assert!(missing
+            .add_component_edit(&[], PositionEncoding::Utf16)
+            .is_none());
+    }
+
+    #[test]
+    fn test_provider_edit() {
+        let missing = MissingComponent {
+            component_type: "crate::repo::UserRepo".to_string(),
+        };
+        let edit = missing.provider_edit(&PositionMapper::new("struct A;"));
+        assert_eq!(edit.range.start, Position::new(0, 9));
+        assert_eq!(
+            edit.new_text,
+            "\n\n#[component]\nfn create_user_repo() -> crate::repo::UserRepo {\n    todo!(\"构建 crate::repo::UserRepo\")\n}\n"
+        );
+    }
+}
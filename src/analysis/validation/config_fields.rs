@@ -0,0 +1,240 @@
+//! 配置结构体必需字段检查模块
+//!
+//! `#[derive(Configurable)]` 结构体在应用启动时从 `[prefix]` 配置节反序列化，
+//! 既不是 `Option<T>`、也没有 `#[serde(default)]` 的字段在配置文件中缺少时启动会失败。
+//! 配置节存在但缺少这些字段时同时报告两处：
+//! - Rust 文件中的字段：提示添加配置项，或改为 `Option<T>` / 添加 `#[serde(default)]`
+//! - 配置文件中的配置节（基础配置 `app.toml` 优先）：列出需要添加的配置项
+//!
+//! 任一配置文件（`app.toml` 或 `app-<profile>.toml`）定义了该配置项即视为已配置；
+//! 所有配置文件都没有 `[prefix]` 配置节时由依赖注入验证的 `config-not-found` 报告，这里不重复报告。
+
+use lsp_types::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, NumberOrString};
+use lsp_types::{Location, Url};
+
+use crate::analysis::toml::toml_analyzer::TomlDocument;
+use crate::core::messages::{localized, Locale};
+use crate::scanner::config::{config_file_profile, ConfigField, ConfigurationStruct};
+
+/// 配置结构体的必需字段在配置文件中缺少
+pub const CONFIG_FIELD_MISSING: &str = "config-field-missing";
+
+/// 配置结构体必需字段验证器
+#[derive(Debug, Clone, Default)]
+pub struct ConfigFieldValidator {
+    /// 消息语言
+    locale: Locale,
+}
+
+impl ConfigFieldValidator {
+    /// 创建验证器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 对照配置文件验证配置结构体，返回诊断及其所在文件（Rust 文件或配置文件）的 URI
+    pub fn validate(
+        &self,
+        configs: &[ConfigurationStruct],
+        docs: &[(Url, TomlDocument)],
+    ) -> Vec<(Url, Diagnostic)> {
+        // 基础配置排在 profile 配置之前
+        let mut docs: Vec<_> = docs
+            .iter()
+            .filter_map(|(uri, doc)| Some((config_file_profile(uri)?, uri, doc)))
+            .collect();
+        docs.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut diagnostics = Vec::new();
+        for config in configs {
+            let Some(location) = &config.location else {
+                continue;
+            };
+            let sections: Vec<_> = docs
+                .iter()
+                .filter_map(|(_, uri, doc)| Some((*uri, doc.config_sections.get(&config.prefix)?)))
+                .collect();
+            let Some((section_uri, section)) = sections.first() else {
+                continue;
+            };
+
+            let missing: Vec<&ConfigField> = config
+                .fields
+                .iter()
+                .filter(|field| field.is_required())
+                .filter(|field| {
+                    !sections
+                        .iter()
+                        .any(|(_, section)| section.properties.contains_key(&field.name))
+                })
+                .collect();
+            if missing.is_empty() {
+                continue;
+            }
+
+            let section_location = Location::new((*section_uri).clone(), section.key_range);
+            for field in &missing {
+                diagnostics.push((
+                    location.uri.clone(),
+                    diagnostic(
+                        field.range,
+                        localized(
+                            self.locale,
+                            CONFIG_FIELD_MISSING,
+                            &[&config.prefix, &field.name, &config.name, &field.type_name],
+                        ),
+                        vec![DiagnosticRelatedInformation {
+                            location: section_location.clone(),
+                            message: localized(
+                                self.locale,
+                                "config-field-missing.section-related",
+                                &[&config.prefix],
+                            ),
+                        }],
+                    ),
+                ));
+            }
+
+            let names: Vec<_> = missing.iter().map(|field| field.name.as_str()).collect();
+            diagnostics.push((
+                (*section_uri).clone(),
+                diagnostic(
+                    section.key_range,
+                    localized(
+                        self.locale,
+                        "config-field-missing.section",
+                        &[
+                            &config.prefix,
+                            &config.name,
+                            &names.join(&localized(self.locale, "list.separator", &[])),
+                        ],
+                    ),
+                    missing
+                        .iter()
+                        .map(|field| DiagnosticRelatedInformation {
+                            location: Location::new(location.uri.clone(), field.range),
+                            message: format!("{}.{}: {}", config.name, field.name, field.type_name),
+                        })
+                        .collect(),
+                ),
+            ));
+        }
+
+        diagnostics
+    }
+}
+
+fn diagnostic(
+    range: lsp_types::Range,
+    message: String,
+    related: Vec<DiagnosticRelatedInformation>,
+) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(CONFIG_FIELD_MISSING.to_string())),
+        source: Some("spring-lsp".to_string()),
+        message,
+        related_information: Some(related),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::toml::toml_analyzer::TomlAnalyzer;
+    use crate::scanner::config::ConfigScanner;
+    use crate::schema::SchemaProvider;
+
+    const SOURCE: &str = r#"
+#[derive(Debug, Configurable, Deserialize)]
+#[config_prefix = "payment"]
+struct PaymentConfig {
+    api_key: String,
+    endpoint: String,
+    timeout: Option<u64>,
+    #[serde(default = "default_retries")]
+    retries: u32,
+}
+
+#[derive(Debug, Configurable, Deserialize)]
+#[config_prefix = "mail"]
+#[serde(default)]
+struct MailConfig {
+    host: String,
+}
+
+#[derive(Debug, Configurable, Deserialize)]
+#[config_prefix = "cache"]
+struct CacheConfig {
+    url: String,
+}
+"#;
+
+    fn docs(files: &[(&str, &str)]) -> Vec<(Url, TomlDocument)> {
+        let analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        files
+            .iter()
+            .map(|(uri, content)| (Url::parse(uri).unwrap(), analyzer.parse(content).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_missing_required_fields() {
+        let rust_uri = Url::parse("file:///app/src/config.rs").unwrap();
+        let configs = ConfigScanner::new().scan_content(&rust_uri, SOURCE);
+        let docs = docs(&[
+            (
+                "file:///app/config/app-prod.toml",
+                "[payment]\nendpoint = \"https://pay\"\n",
+            ),
+            (
+                "file:///app/config/app.toml",
+                "[payment]\ntimeout = 5\n\n[mail]\n",
+            ),
+        ]);
+
+        let diagnostics = ConfigFieldValidator::new().validate(&configs, &docs);
+        assert_eq!(diagnostics.len(), 2);
+
+        let (uri, field) = &diagnostics[0];
+        assert_eq!(uri, &rust_uri);
+        assert_eq!(field.range.start, lsp_types::Position::new(4, 4));
+        assert!(field.message.contains("'api_key'"));
+        assert_eq!(field.severity, Some(DiagnosticSeverity::ERROR));
+        let related = field.related_information.as_ref().unwrap();
+        assert_eq!(related[0].location.uri.path(), "/app/config/app.toml");
+
+        let (uri, section) = &diagnostics[1];
+        assert_eq!(uri.path(), "/app/config/app.toml");
+        assert_eq!(section.range.start, lsp_types::Position::new(0, 1));
+        assert_eq!(
+            section.message,
+            "配置节 [payment] 缺少 PaymentConfig 的必需配置项，应用启动时会失败。请在此添加：api_key"
+        );
+    }
+
+    #[test]
+    fn test_fields_satisfied() {
+        let rust_uri = Url::parse("file:///app/src/config.rs").unwrap();
+        let configs = ConfigScanner::new().scan_content(&rust_uri, SOURCE);
+        let docs = docs(&[
+            (
+                "file:///app/config/app.toml",
+                "[payment]\napi_key = \"k\"\nendpoint = \"e\"\n",
+            ),
+            ("file:///app/config/other.toml", "[cache]\n"),
+        ]);
+
+        assert!(ConfigFieldValidator::new()
+            .validate(&configs, &docs)
+            .is_empty());
+    }
+}
@@ -0,0 +1,524 @@
+//! 增量依赖注入验证模块
+//!
+//! 基于 [`crate::core::incremental`] 的记忆化查询验证依赖注入，只重新计算依赖修改过的文件的部分：
+//! - 每个文件的宏提取结果和服务只依赖该文件
+//! - 每个文件的组件注入和配置注入诊断依赖该文件和工作空间中的配置文件
+//! - 每个 crate 的应用入口检查依赖该 crate 的所有源文件
+//!
+//! 循环依赖检测需要完整的依赖图，但只使用缓存的服务信息（不重新解析文件），每次都重新计算。
+//!
+//! 项目级验证（[`super::DiValidationTask`]）按 crate 分批执行同样的查询，完成后验证器交给编辑时的增量验证，
+//! 第一次编辑就可以复用所有结果。验证器记录每个 crate 的文件列表，编辑器或磁盘上的文件变化只通过
+//! [`IncrementalDiValidator::set_file`] 更新对应的文件，不重新遍历工作空间。
+//!
+//! 大型工作空间中保留的文件内容和语法树可能占用大量内存，超出内存预算时可以释放
+//! （[`IncrementalDiValidator::evict_parsed`]、[`IncrementalDiValidator::evict_sources`]），
+//! 提取的服务和诊断仍然保留，之后只有变化的文件需要重新解析（内容已释放的文件从磁盘读取）。
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use lsp_types::{Diagnostic, Url};
+
+use super::di_task::CrateSources;
+use super::di_validator::{DependencyInjectionValidator, ServiceInfo};
+use super::entry_point::EntryPointValidator;
+use crate::analysis::rust::macro_analyzer::{MacroAnalyzer, RustDocument, SpringMacro};
+use crate::analysis::toml::toml_analyzer::{TomlAnalyzer, TomlDocument};
+use crate::core::incremental::{Inputs, Query, QueryStats, Tracker};
+
+/// 配置文件语法树相对于内容长度的估算倍数
+const TOML_DOM_FACTOR: usize = 4;
+
+/// 一个 crate 中的文件
+#[derive(Debug, Clone)]
+struct CrateFiles {
+    /// crate 名称
+    name: String,
+    /// crate 根目录
+    root: PathBuf,
+    /// `src` 目录中的 Rust 文件
+    files: BTreeSet<Url>,
+    /// `config` 目录中的配置文件
+    config_files: BTreeSet<Url>,
+}
+
+/// 增量依赖注入验证器
+pub struct IncrementalDiValidator {
+    /// 宏分析器
+    macro_analyzer: Arc<MacroAnalyzer>,
+    /// 工作空间中的 crate（按名称排序）
+    crates: Vec<CrateFiles>,
+    /// 源文件和配置文件的内容
+    inputs: Inputs<Url>,
+    /// 每个文件提取的宏（无法解析时为 `None`）
+    documents: Query<Url, Url, Option<RustDocument>>,
+    /// 每个配置文件的解析结果（无法解析时为 `None`）
+    configs: Query<Url, Url, Option<TomlDocument>>,
+    /// 每个文件中的服务
+    services: Query<Url, Url, HashMap<String, ServiceInfo>>,
+    /// 每个文件的组件注入和配置注入诊断
+    injections: Query<Url, Url, Vec<Diagnostic>>,
+    /// 每个 crate 的应用入口检查
+    entry_points: Query<PathBuf, Url, BTreeMap<Url, Vec<Diagnostic>>>,
+}
+
+impl IncrementalDiValidator {
+    /// 创建增量验证器
+    pub fn new(macro_analyzer: Arc<MacroAnalyzer>) -> Self {
+        Self {
+            macro_analyzer,
+            crates: Vec::new(),
+            inputs: Inputs::new(),
+            documents: Query::new(),
+            configs: Query::new(),
+            services: Query::new(),
+            injections: Query::new(),
+            entry_points: Query::new(),
+        }
+    }
+
+    /// 更换宏分析器（如消息语言变化后），保留文件列表和内容，清空所有派生结果
+    pub fn set_macro_analyzer(&mut self, macro_analyzer: Arc<MacroAnalyzer>) {
+        self.macro_analyzer = macro_analyzer;
+        self.documents.clear();
+        self.services.clear();
+        self.invalidate();
+    }
+
+    /// 设置工作空间中的所有 crate 及其文件内容
+    ///
+    /// 内容与上一次相同的文件保留缓存的结果，不在 `crates` 中的文件从缓存中移除
+    pub fn load(&mut self, crates: &[CrateSources]) {
+        let mut present = HashSet::new();
+        for sources in crates {
+            for (uri, content) in sources.files.iter().chain(&sources.config_files) {
+                self.inputs.set(uri.clone(), content);
+                present.insert(uri.clone());
+            }
+        }
+        self.inputs.retain(|uri| present.contains(uri));
+        self.documents.retain(|uri| present.contains(uri));
+        self.configs.retain(|uri| present.contains(uri));
+        self.services.retain(|uri| present.contains(uri));
+        self.injections.retain(|uri| present.contains(uri));
+        self.entry_points
+            .retain(|root| crates.iter().any(|sources| &sources.root == root));
+
+        self.crates = crates
+            .iter()
+            .map(|sources| CrateFiles {
+                name: sources.name.clone(),
+                root: sources.root.clone(),
+                files: sources.files.iter().map(|(uri, _)| uri.clone()).collect(),
+                config_files: sources
+                    .config_files
+                    .iter()
+                    .map(|(uri, _)| uri.clone())
+                    .collect(),
+            })
+            .collect();
+    }
+
+    /// 更新单个文件的内容，`None` 表示文件已删除
+    ///
+    /// 只处理已知 crate 的 `src` 目录中的 Rust 文件和 `config` 目录中的配置文件，
+    /// 返回文件是否属于某个 crate
+    pub fn set_file(&mut self, uri: &Url, content: Option<&str>) -> bool {
+        let Ok(path) = uri.to_file_path() else {
+            return false;
+        };
+        // 嵌套的 crate 中的文件属于最内层的 crate
+        let Some(files) = self
+            .crates
+            .iter_mut()
+            .filter(|files| path.starts_with(&files.root))
+            .max_by_key(|files| files.root.components().count())
+        else {
+            return false;
+        };
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        let list = if extension == Some("rs") && path.starts_with(files.root.join("src")) {
+            &mut files.files
+        } else if extension == Some("toml") && path.parent() == Some(&files.root.join("config")) {
+            &mut files.config_files
+        } else {
+            return false;
+        };
+
+        match content {
+            Some(content) => {
+                self.inputs.set(uri.clone(), content);
+                list.insert(uri.clone());
+            }
+            None => {
+                self.inputs.remove(uri);
+                list.remove(uri);
+                self.documents.retain(|key| key != uri);
+                self.configs.retain(|key| key != uri);
+                self.services.retain(|key| key != uri);
+                self.injections.retain(|key| key != uri);
+            }
+        }
+        true
+    }
+
+    /// crate 数
+    pub fn crates_len(&self) -> usize {
+        self.crates.len()
+    }
+
+    /// crate 名称
+    pub fn crate_name(&self, index: usize) -> Option<&str> {
+        self.crates.get(index).map(|files| files.name.as_str())
+    }
+
+    /// 验证所有 crate，返回按文件分组的诊断
+    pub fn validate(
+        &mut self,
+        validator: &DependencyInjectionValidator,
+        toml_analyzer: &TomlAnalyzer,
+    ) -> BTreeMap<Url, Vec<Diagnostic>> {
+        let mut diagnostics: BTreeMap<Url, Vec<Diagnostic>> = BTreeMap::new();
+        for index in 0..self.crates.len() {
+            for (uri, crate_diagnostics) in self.validate_crate(index, validator, toml_analyzer) {
+                diagnostics
+                    .entry(uri)
+                    .or_default()
+                    .extend(crate_diagnostics);
+            }
+        }
+        for (uri, graph_diagnostics) in self.validate_graph(validator) {
+            diagnostics
+                .entry(uri)
+                .or_default()
+                .extend(graph_diagnostics);
+        }
+        diagnostics
+    }
+
+    /// 设置所有 crate 并验证（[`Self::load`] 和 [`Self::validate`]）
+    pub fn update(
+        &mut self,
+        crates: &[CrateSources],
+        validator: &DependencyInjectionValidator,
+        toml_analyzer: &TomlAnalyzer,
+    ) -> BTreeMap<Url, Vec<Diagnostic>> {
+        self.load(crates);
+        self.validate(validator, toml_analyzer)
+    }
+
+    /// 验证单个 crate 中的组件注入、配置注入和应用入口
+    pub fn validate_crate(
+        &mut self,
+        index: usize,
+        validator: &DependencyInjectionValidator,
+        toml_analyzer: &TomlAnalyzer,
+    ) -> BTreeMap<Url, Vec<Diagnostic>> {
+        let mut diagnostics: BTreeMap<Url, Vec<Diagnostic>> = BTreeMap::new();
+        let Some(files) = self.crates.get(index) else {
+            return diagnostics;
+        };
+        let config_uris: Vec<&Url> = self
+            .crates
+            .iter()
+            .flat_map(|files| files.config_files.iter())
+            .collect();
+        let Self {
+            macro_analyzer,
+            inputs,
+            documents,
+            configs,
+            services,
+            injections,
+            entry_points,
+            ..
+        } = self;
+
+        for uri in &files.files {
+            let file_diagnostics = injections.get(inputs, uri, |tracker| {
+                let toml_docs: Vec<(Url, TomlDocument)> = config_uris
+                    .iter()
+                    .filter_map(|config_uri| {
+                        let doc = configs.get_tracked(tracker, config_uri, |tracker| {
+                            toml_analyzer.parse(&read(tracker, config_uri)?).ok()
+                        });
+                        Some(((*config_uri).clone(), doc.as_ref().clone()?))
+                    })
+                    .collect();
+                let file_services = services.get_tracked(tracker, uri, |tracker| {
+                    document_services(documents, macro_analyzer, validator, tracker, uri)
+                });
+                file_services
+                    .values()
+                    .flat_map(|service| validator.validate_service(service, &toml_docs))
+                    .collect()
+            });
+            if !file_diagnostics.is_empty() {
+                diagnostics
+                    .entry(uri.clone())
+                    .or_default()
+                    .extend(file_diagnostics.iter().cloned());
+            }
+        }
+
+        let crate_diagnostics = entry_points.get(inputs, &files.root, |tracker| {
+            let rust_docs: Vec<RustDocument> = files
+                .files
+                .iter()
+                .filter_map(|uri| {
+                    documents
+                        .get_tracked(tracker, uri, |tracker| {
+                            parse_document(macro_analyzer, uri, &read(tracker, uri)?)
+                        })
+                        .as_ref()
+                        .clone()
+                })
+                .collect();
+            EntryPointValidator::new()
+                .with_locale(macro_analyzer.locale())
+                .validate(&files.root, &rust_docs)
+        });
+        for (uri, entry_diagnostics) in crate_diagnostics.iter() {
+            diagnostics
+                .entry(uri.clone())
+                .or_default()
+                .extend(entry_diagnostics.iter().cloned());
+        }
+
+        diagnostics
+    }
+
+    /// 基于所有 crate 中的服务检测循环依赖
+    pub fn validate_graph(
+        &mut self,
+        validator: &DependencyInjectionValidator,
+    ) -> BTreeMap<Url, Vec<Diagnostic>> {
+        let Self {
+            macro_analyzer,
+            crates,
+            inputs,
+            documents,
+            services,
+            ..
+        } = self;
+        let mut all_services = HashMap::new();
+        for uri in crates.iter().flat_map(|files| files.files.iter()) {
+            let file_services = services.get(inputs, uri, |tracker| {
+                document_services(documents, macro_analyzer, validator, tracker, uri)
+            });
+            all_services.extend(
+                file_services
+                    .iter()
+                    .map(|(name, service)| (name.clone(), service.clone())),
+            );
+        }
+
+        let mut diagnostics: BTreeMap<Url, Vec<Diagnostic>> = BTreeMap::new();
+        for (uri, diagnostic) in validator.detect_circular_dependencies(&all_services) {
+            diagnostics.entry(uri).or_default().push(diagnostic);
+        }
+        diagnostics
+    }
+
+    /// 清空注入诊断和应用入口检查的缓存
+    ///
+    /// 注入诊断还依赖组件索引，组件索引重建后需要调用
+    pub fn invalidate(&mut self) {
+        self.injections.clear();
+        self.entry_points.clear();
+    }
+
+    /// 文件宏提取的计算统计（重新解析的文件数和复用的次数）
+    pub fn stats(&self) -> QueryStats {
+        self.documents.stats()
+    }
+
+    /// 保留的源文件和配置文件内容占用的字节数
+    pub fn source_bytes(&self) -> usize {
+        self.inputs.memory_usage()
+    }
+
+    /// 估算保留的解析结果（Rust 文件的宏提取结果和配置文件的语法树）占用的字节数
+    pub fn parsed_bytes(&self) -> usize {
+        let rust = self.documents.memory_usage(|doc| {
+            doc.as_ref().map_or(0, |doc| {
+                doc.content.len() + doc.macros.len() * std::mem::size_of::<SpringMacro>()
+            })
+        });
+        let toml = self.configs.memory_usage(|doc| {
+            doc.as_ref()
+                .map_or(0, |doc| doc.content.len() * TOML_DOM_FACTOR)
+        });
+        rust + toml
+    }
+
+    /// 释放解析结果（只保留提取的服务和诊断），返回释放的文件数
+    pub fn evict_parsed(&mut self) -> usize {
+        let evicted = self.documents.len() + self.configs.len();
+        self.documents.clear();
+        self.configs.clear();
+        evicted
+    }
+
+    /// 释放不满足条件的文件的内容，返回释放的字节数
+    ///
+    /// 之后需要重新计算的文件从磁盘读取内容，内容没有变化的文件不需要重新计算
+    pub fn evict_sources(&mut self, keep: impl FnMut(&Url) -> bool) -> usize {
+        self.inputs.evict(keep)
+    }
+}
+
+/// 读取文件内容并记录依赖，内容已释放时从磁盘读取
+fn read<'a>(tracker: &mut Tracker<'a, Url>, uri: &Url) -> Option<Cow<'a, str>> {
+    match tracker.read(uri) {
+        Some(content) => Some(Cow::Borrowed(content)),
+        None => std::fs::read_to_string(uri.to_file_path().ok()?)
+            .ok()
+            .map(Cow::Owned),
+    }
+}
+
+/// 文件中的服务
+fn document_services(
+    documents: &mut Query<Url, Url, Option<RustDocument>>,
+    macro_analyzer: &MacroAnalyzer,
+    validator: &DependencyInjectionValidator,
+    tracker: &mut Tracker<Url>,
+    uri: &Url,
+) -> HashMap<String, ServiceInfo> {
+    let doc = documents.get_tracked(tracker, uri, |tracker| {
+        parse_document(macro_analyzer, uri, &read(tracker, uri)?)
+    });
+    doc.as_ref()
+        .as_ref()
+        .map(|doc| validator.extract_services(std::slice::from_ref(doc)))
+        .unwrap_or_default()
+}
+
+/// 解析文件并提取宏，语法错误由 rust-analyzer 报告，无法解析时返回 `None`
+fn parse_document(
+    macro_analyzer: &MacroAnalyzer,
+    uri: &Url,
+    content: &str,
+) -> Option<RustDocument> {
+    let doc = macro_analyzer
+        .parse(uri.clone(), content.to_string())
+        .ok()?;
+    macro_analyzer.extract_macros(doc).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::index::IndexManager;
+    use crate::core::schema::SchemaProvider;
+
+    fn sources(files: &[(&str, &str)], config: &str) -> Vec<CrateSources> {
+        let url = |path: &str| Url::parse(&format!("file:///app/{}", path)).unwrap();
+        vec![CrateSources {
+            name: "app".to_string(),
+            root: PathBuf::from("/app"),
+            files: files
+                .iter()
+                .map(|(path, content)| (url(path), content.to_string()))
+                .collect(),
+            config_files: vec![(url("config/app.toml"), config.to_string())],
+        }]
+    }
+
+    #[test]
+    fn test_update_recomputes_changed_files_only() {
+        let mut incremental = IncrementalDiValidator::new(Arc::new(MacroAnalyzer::new()));
+        let validator = DependencyInjectionValidator::new(IndexManager::new());
+        let toml_analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        let mail = "#[derive(Clone, Service)]\nstruct MailService {\n    #[inject(config)]\n    config: MailConfig,\n}\n";
+        let users = "#[derive(Clone, Service)]\nstruct UserService {}\n";
+        let mail_uri = Url::parse("file:///app/src/mail.rs").unwrap();
+
+        let crates = sources(&[("src/mail.rs", mail), ("src/users.rs", users)], "");
+        let diagnostics = incremental.update(&crates, &validator, &toml_analyzer);
+        assert_eq!(diagnostics[&mail_uri].len(), 1);
+        assert_eq!(incremental.stats().computed, 2);
+
+        // 没有变化的文件不重新解析
+        let diagnostics = incremental.update(&crates, &validator, &toml_analyzer);
+        assert_eq!(diagnostics[&mail_uri].len(), 1);
+        assert_eq!(incremental.stats().computed, 2);
+
+        let users = "#[derive(Clone, Service)]\nstruct UserService {\n    #[inject(config)]\n    config: UserConfig,\n}\n";
+        let crates = sources(
+            &[("src/mail.rs", mail), ("src/users.rs", users)],
+            "[mail]\n",
+        );
+        let diagnostics = incremental.update(&crates, &validator, &toml_analyzer);
+        assert_eq!(incremental.stats().computed, 3);
+        assert!(!diagnostics.contains_key(&mail_uri));
+        let users_uri = Url::parse("file:///app/src/users.rs").unwrap();
+        assert_eq!(diagnostics[&users_uri].len(), 1);
+        assert!(diagnostics[&users_uri][0].message.contains("'user'"));
+
+        // 删除的文件不再产生诊断
+        let crates = sources(&[("src/mail.rs", mail)], "");
+        let diagnostics = incremental.update(&crates, &validator, &toml_analyzer);
+        assert!(!diagnostics.contains_key(&users_uri));
+        assert_eq!(incremental.stats().computed, 3);
+    }
+
+    #[test]
+    fn test_evicted_caches_keep_diagnostics() {
+        let mut incremental = IncrementalDiValidator::new(Arc::new(MacroAnalyzer::new()));
+        let validator = DependencyInjectionValidator::new(IndexManager::new());
+        let toml_analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        let mail = "#[derive(Clone, Service)]\nstruct MailService {\n    #[inject(config)]\n    config: MailConfig,\n}\n";
+        let mail_uri = Url::parse("file:///app/src/mail.rs").unwrap();
+        let crates = sources(&[("src/mail.rs", mail)], "");
+        incremental.update(&crates, &validator, &toml_analyzer);
+        assert!(incremental.parsed_bytes() >= mail.len());
+        assert_eq!(incremental.source_bytes(), mail.len());
+
+        assert_eq!(incremental.evict_parsed(), 2);
+        assert_eq!(incremental.parsed_bytes(), 0);
+        assert_eq!(incremental.evict_sources(|_| false), mail.len());
+        assert_eq!(incremental.source_bytes(), 0);
+
+        // 内容没有变化：服务和诊断直接复用，不重新解析
+        let diagnostics = incremental.update(&crates, &validator, &toml_analyzer);
+        assert_eq!(diagnostics[&mail_uri].len(), 1);
+        assert_eq!(incremental.stats().computed, 1);
+        assert_eq!(incremental.source_bytes(), mail.len());
+    }
+
+    #[test]
+    fn test_set_file_updates_crate_files() {
+        let mut incremental = IncrementalDiValidator::new(Arc::new(MacroAnalyzer::new()));
+        let validator = DependencyInjectionValidator::new(IndexManager::new());
+        let toml_analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        let users = "#[derive(Clone, Service)]\nstruct UserService {}\n";
+        incremental.load(&sources(&[("src/users.rs", users)], ""));
+        assert!(incremental.validate(&validator, &toml_analyzer).is_empty());
+
+        // 新建的文件加入 crate，只解析这个文件
+        let mail = "#[derive(Clone, Service)]\nstruct MailService {\n    #[inject(config)]\n    config: MailConfig,\n}\n";
+        let mail_uri = Url::parse("file:///app/src/mail.rs").unwrap();
+        assert!(incremental.set_file(&mail_uri, Some(mail)));
+        let diagnostics = incremental.validate(&validator, &toml_analyzer);
+        assert_eq!(diagnostics[&mail_uri].len(), 1);
+        assert_eq!(incremental.stats().computed, 2);
+
+        // 配置文件的变化影响注入诊断
+        let config_uri = Url::parse("file:///app/config/app.toml").unwrap();
+        assert!(incremental.set_file(&config_uri, Some("[mail]\n")));
+        assert!(incremental.validate(&validator, &toml_analyzer).is_empty());
+
+        // 删除的文件不再产生诊断，crate 之外的文件被忽略
+        assert!(incremental.set_file(&config_uri, None));
+        assert!(incremental.set_file(&mail_uri, None));
+        assert!(incremental.validate(&validator, &toml_analyzer).is_empty());
+        let other = Url::parse("file:///other/src/lib.rs").unwrap();
+        assert!(!incremental.set_file(&other, Some(mail)));
+        assert!(!incremental.set_file(&Url::parse("file:///app/build.rs").unwrap(), Some(mail)));
+    }
+}
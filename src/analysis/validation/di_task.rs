@@ -0,0 +1,408 @@
+//! 依赖注入验证任务模块
+//!
+//! 项目级的依赖注入验证可能很耗时，这里把它拆分为可恢复的分块任务：
+//! - 每次 [`DiValidationTask::step`] 只处理一个 crate，产出该 crate 的诊断批次后让出控制权
+//!   （批次中同时包含该 crate 的 `#[auto_config]` 入口检查，参见 [`super::EntryPointValidator`]）
+//! - 每个批次开始前检查 [`CancellationToken`]，被取消时保留进度，之后可以从下一个 crate 继续
+//! - 所有 crate 处理完后，再基于完整的依赖图检测循环依赖（最后一个批次）
+//!
+//! 调用方可以在批次之间处理其他消息，并在每个批次完成后立即发布诊断，
+//! 而不必等待整个依赖图分析完成。批次通过 [`IncrementalDiValidator`] 的记忆化查询计算，
+//! 任务结束后验证器继续用于编辑时的增量验证。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use lsp_types::{Diagnostic, Url};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use super::di_incremental::IncrementalDiValidator;
+use super::di_validator::DependencyInjectionValidator;
+use crate::analysis::toml::toml_analyzer::TomlAnalyzer;
+use crate::core::document::DocumentManager;
+
+/// 取消令牌
+///
+/// 克隆得到的令牌共享同一个取消状态
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// 创建新的取消令牌
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 请求取消
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// 是否已请求取消
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// 一个 crate 的源文件
+#[derive(Debug, Clone)]
+pub struct CrateSources {
+    /// crate 名称
+    pub name: String,
+    /// crate 根目录（`Cargo.toml` 所在目录）
+    pub root: PathBuf,
+    /// Rust 源文件（URI 和内容）
+    pub files: Vec<(Url, String)>,
+    /// 配置文件（`config/*.toml`，URI 和内容）
+    pub config_files: Vec<(Url, String)>,
+}
+
+impl CrateSources {
+    /// 发现目录下的所有 crate
+    ///
+    /// 查找包含 `[package]` 的 `Cargo.toml`（跳过 `target` 和隐藏目录），
+    /// 收集每个 crate 的 `src/**/*.rs` 和 `config/*.toml`，按 crate 名称排序
+    pub fn discover(root: &Path) -> Vec<CrateSources> {
+        let mut crates: Vec<CrateSources> = WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                e.depth() == 0 || (name != "target" && !name.starts_with('.'))
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name() == "Cargo.toml")
+            .filter_map(|manifest| {
+                let crate_root = manifest.path().parent()?;
+                let name = package_name(manifest.path())?;
+                Some(CrateSources {
+                    name,
+                    root: crate_root.to_path_buf(),
+                    files: read_files(&crate_root.join("src"), "rs", usize::MAX),
+                    config_files: read_files(&crate_root.join("config"), "toml", 1),
+                })
+            })
+            .collect();
+
+        crates.sort_by(|a, b| a.name.cmp(&b.name));
+        crates
+    }
+
+    /// 用编辑器中打开的文档（可能包含未保存的修改）替换从磁盘读取的内容
+    pub fn overlay(&mut self, documents: &DocumentManager) {
+        for (file, content) in self.files.iter_mut().chain(self.config_files.iter_mut()) {
+            if let Some(open) = documents.with_document(file, |doc| doc.content.clone()) {
+                *content = open;
+            }
+        }
+    }
+}
+
+/// 读取 `Cargo.toml` 中的包名，虚拟工作空间清单返回 `None`
+fn package_name(manifest: &Path) -> Option<String> {
+    let content = fs::read_to_string(manifest).ok()?;
+    let table: toml::Table = content.parse().ok()?;
+    table
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(|name| name.to_string())
+}
+
+/// 读取目录下指定扩展名的文件
+fn read_files(dir: &Path, extension: &str, max_depth: usize) -> Vec<(Url, String)> {
+    WalkDir::new(dir)
+        .max_depth(max_depth)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == extension))
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path()).ok()?;
+            let url = Url::from_file_path(entry.path()).ok()?;
+            Some((url, content))
+        })
+        .collect()
+}
+
+/// 诊断批次的范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiBatchScope {
+    /// 单个 crate 中的组件注入、配置注入和应用入口
+    Crate(String),
+    /// 跨 crate 的完整依赖图（循环依赖检测）
+    DependencyGraph,
+}
+
+/// 一个批次产出的诊断
+#[derive(Debug, Clone)]
+pub struct DiValidationBatch {
+    /// 批次范围
+    pub scope: DiBatchScope,
+    /// 按文件分组的诊断
+    pub diagnostics: BTreeMap<Url, Vec<Diagnostic>>,
+}
+
+/// 单步执行的结果
+#[derive(Debug, Clone)]
+pub enum DiTaskStep {
+    /// 完成了一个批次
+    Batch(DiValidationBatch),
+    /// 已请求取消，进度保留
+    Cancelled,
+    /// 所有批次均已完成
+    Completed,
+}
+
+/// 可恢复的项目级依赖注入验证任务
+pub struct DiValidationTask {
+    /// 按文件记忆化的验证器，完成后交给编辑时的增量验证（[`Self::into_incremental`]）
+    incremental: IncrementalDiValidator,
+    /// 下一个待处理的 crate
+    next_crate: usize,
+    /// 依赖图是否已经分析
+    graph_checked: bool,
+}
+
+impl DiValidationTask {
+    /// 在增量验证器上创建验证任务
+    ///
+    /// 验证器中内容没有变化的文件复用已缓存的解析结果
+    pub fn new(crates: &[CrateSources], mut incremental: IncrementalDiValidator) -> Self {
+        incremental.load(crates);
+        Self {
+            incremental,
+            next_crate: 0,
+            graph_checked: false,
+        }
+    }
+
+    /// crate 总数
+    pub fn crates_total(&self) -> usize {
+        self.incremental.crates_len()
+    }
+
+    /// 已处理的 crate 数
+    pub fn crates_done(&self) -> usize {
+        self.next_crate
+    }
+
+    /// 任务是否已经完成
+    pub fn is_finished(&self) -> bool {
+        self.graph_checked
+    }
+
+    /// 任务使用的增量验证器（任务运行期间文件发生变化时通过它更新）
+    pub fn incremental_mut(&mut self) -> &mut IncrementalDiValidator {
+        &mut self.incremental
+    }
+
+    /// 结束任务，取回增量验证器（包含已处理的 crate 的缓存结果）
+    pub fn into_incremental(self) -> IncrementalDiValidator {
+        self.incremental
+    }
+
+    /// 执行一个批次
+    ///
+    /// 依次处理每个 crate，最后分析跨 crate 的依赖图。
+    /// 取消令牌在批次开始前检查，返回 [`DiTaskStep::Cancelled`] 后任务仍可继续执行
+    pub fn step(
+        &mut self,
+        validator: &DependencyInjectionValidator,
+        toml_analyzer: &TomlAnalyzer,
+        token: &CancellationToken,
+    ) -> DiTaskStep {
+        if self.graph_checked {
+            return DiTaskStep::Completed;
+        }
+        if token.is_cancelled() {
+            return DiTaskStep::Cancelled;
+        }
+
+        let Some(name) = self.incremental.crate_name(self.next_crate) else {
+            self.graph_checked = true;
+            return DiTaskStep::Batch(DiValidationBatch {
+                scope: DiBatchScope::DependencyGraph,
+                diagnostics: self.incremental.validate_graph(validator),
+            });
+        };
+
+        let scope = DiBatchScope::Crate(name.to_string());
+        let diagnostics =
+            self.incremental
+                .validate_crate(self.next_crate, validator, toml_analyzer);
+        self.next_crate += 1;
+
+        DiTaskStep::Batch(DiValidationBatch { scope, diagnostics })
+    }
+}
+
+/// spring/validateDependencies 请求参数
+#[derive(Debug, Deserialize)]
+pub struct ValidateDependenciesRequest {
+    /// 应用路径
+    #[serde(rename = "appPath")]
+    pub app_path: String,
+}
+
+/// spring/validateDependencies 响应
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateDependenciesResponse {
+    /// 验证的 crate 数
+    pub crates: usize,
+    /// 产出的诊断总数
+    pub diagnostics: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rust::macro_analyzer::MacroAnalyzer;
+    use crate::core::index::IndexManager;
+    use crate::core::schema::SchemaProvider;
+
+    fn task(crates: &[CrateSources]) -> DiValidationTask {
+        DiValidationTask::new(
+            crates,
+            IncrementalDiValidator::new(Arc::new(MacroAnalyzer::new())),
+        )
+    }
+
+    fn crate_sources(name: &str, content: &str) -> CrateSources {
+        CrateSources {
+            name: name.to_string(),
+            root: PathBuf::from(format!("/{}", name)),
+            files: vec![(
+                Url::parse(&format!("file:///{}/src/lib.rs", name)).unwrap(),
+                content.to_string(),
+            )],
+            config_files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_task_batches_per_crate() {
+        let crates = vec![
+            crate_sources(
+                "app",
+                "#[derive(Service)]\nstruct UserService {\n    #[inject(component)]\n    orders: OrderService,\n}\n",
+            ),
+            crate_sources(
+                "orders",
+                "#[derive(Service)]\nstruct OrderService {\n    #[inject(component)]\n    users: UserService,\n}\n",
+            ),
+        ];
+        let validator = DependencyInjectionValidator::new(IndexManager::new());
+        let token = CancellationToken::new();
+        let toml_analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        let mut task = task(&crates);
+
+        let mut scopes = Vec::new();
+        let mut graph_diagnostics = Vec::new();
+        loop {
+            match task.step(&validator, &toml_analyzer, &token) {
+                DiTaskStep::Batch(batch) => {
+                    if batch.scope == DiBatchScope::DependencyGraph {
+                        graph_diagnostics = batch.diagnostics.into_values().flatten().collect();
+                    }
+                    scopes.push(match batch.scope {
+                        DiBatchScope::Crate(name) => name,
+                        DiBatchScope::DependencyGraph => "graph".to_string(),
+                    });
+                }
+                DiTaskStep::Completed => break,
+                DiTaskStep::Cancelled => panic!("task was not cancelled"),
+            }
+        }
+
+        assert_eq!(scopes, vec!["app", "orders", "graph"]);
+        assert_eq!(task.crates_done(), 2);
+        assert!(task.is_finished());
+        // 跨 crate 的循环依赖只有在完整依赖图上才能发现
+        assert_eq!(graph_diagnostics.len(), 1);
+        assert_eq!(
+            graph_diagnostics[0].code,
+            Some(lsp_types::NumberOrString::String(
+                "circular-dependency".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_task_cancel_and_resume() {
+        let crates = vec![
+            crate_sources("a", "#[derive(Service)]\nstruct A {}\n"),
+            crate_sources("b", "#[derive(Service)]\nstruct B {}\n"),
+        ];
+        let validator = DependencyInjectionValidator::new(IndexManager::new());
+        let token = CancellationToken::new();
+        let toml_analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        let mut task = task(&crates);
+
+        assert!(matches!(
+            task.step(&validator, &toml_analyzer, &token),
+            DiTaskStep::Batch(_)
+        ));
+
+        token.clone().cancel();
+        assert!(matches!(
+            task.step(&validator, &toml_analyzer, &token),
+            DiTaskStep::Cancelled
+        ));
+        assert_eq!(task.crates_done(), 1);
+
+        // 使用新令牌从下一个 crate 继续
+        let token = CancellationToken::new();
+        match task.step(&validator, &toml_analyzer, &token) {
+            DiTaskStep::Batch(batch) => {
+                assert_eq!(batch.scope, DiBatchScope::Crate("b".to_string()))
+            }
+            other => panic!("Expected batch, got {:?}", other),
+        }
+        assert!(matches!(
+            task.step(&validator, &toml_analyzer, &token),
+            DiTaskStep::Batch(_)
+        ));
+        assert!(matches!(
+            task.step(&validator, &toml_analyzer, &token),
+            DiTaskStep::Completed
+        ));
+    }
+
+    #[test]
+    fn test_discover_crates() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"web\"]\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("web/src/handlers")).unwrap();
+        fs::create_dir_all(root.join("web/config")).unwrap();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::write(
+            root.join("web/Cargo.toml"),
+            "[package]\nname = \"web\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(root.join("web/src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(root.join("web/src/handlers/mod.rs"), "\n").unwrap();
+        fs::write(root.join("web/config/app.toml"), "[web]\n").unwrap();
+        fs::write(
+            root.join("target/debug/Cargo.toml"),
+            "[package]\nname = \"ignored\"\n",
+        )
+        .unwrap();
+
+        let crates = CrateSources::discover(root);
+        assert_eq!(crates.len(), 1);
+        assert_eq!(crates[0].name, "web");
+        assert_eq!(crates[0].files.len(), 2);
+        assert_eq!(crates[0].config_files.len(), 1);
+    }
+}
@@ -6,23 +6,45 @@
 //! - 组件名称匹配验证
 //! - 循环依赖检测
 //! - 配置注入验证
+//! - 条件注册组件的注入验证（`#[cfg(feature = "...")]`）
 
 use crate::analysis::rust::macro_analyzer::{InjectMacro, InjectType, RustDocument, SpringMacro};
 use crate::analysis::toml::toml_analyzer::TomlDocument;
+use crate::analysis::validation::component_registration::{
+    MissingComponent, COMPONENT_NOT_REGISTERED,
+};
 use crate::core::index::IndexManager;
+use crate::core::messages::{localized, Locale};
 use lsp_types::{Diagnostic, DiagnosticSeverity, Location, NumberOrString};
 use std::collections::{HashMap, HashSet};
 
+/// 注入的配置在配置文件中不存在
+pub const CONFIG_NOT_FOUND: &str = "config-not-found";
+
+/// 注入的组件只在某个 `#[cfg]` 条件下注册，而注入它的服务不受同一条件限制
+pub const COMPONENT_REQUIRES_CFG: &str = "component-requires-cfg";
+
 /// 依赖注入验证器
 pub struct DependencyInjectionValidator {
     /// 索引管理器
     index_manager: IndexManager,
+    /// 消息语言
+    locale: Locale,
 }
 
 impl DependencyInjectionValidator {
     /// 创建新的依赖注入验证器
     pub fn new(index_manager: IndexManager) -> Self {
-        Self { index_manager }
+        Self {
+            index_manager,
+            locale: Locale::default(),
+        }
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
     }
 
     /// 验证依赖注入
@@ -55,42 +77,55 @@ impl DependencyInjectionValidator {
         rust_docs: &[RustDocument],
         toml_docs: &[(lsp_types::Url, TomlDocument)],
     ) -> Vec<Diagnostic> {
-        let mut diagnostics = Vec::new();
-
         // 提取所有服务和注入信息
         let services = self.extract_services(rust_docs);
 
         // 验证每个服务的依赖注入
-        for (service_name, service_info) in &services {
-            for field in &service_info.fields {
-                if let Some(inject) = &field.inject {
-                    match inject.inject_type {
-                        InjectType::Component => {
-                            // 验证组件注入
-                            diagnostics.extend(self.validate_component_injection(
-                                service_name,
-                                field,
-                                inject,
-                                &service_info.location,
-                            ));
-                        }
-                        InjectType::Config => {
-                            // 验证配置注入
-                            diagnostics.extend(self.validate_config_injection(
-                                field,
-                                inject,
-                                toml_docs,
-                                &service_info.location,
-                            ));
-                        }
+        let mut diagnostics: Vec<Diagnostic> = services
+            .values()
+            .flat_map(|service_info| self.validate_service(service_info, toml_docs))
+            .collect();
+
+        // 检测循环依赖
+        diagnostics.extend(
+            self.detect_circular_dependencies(&services)
+                .into_iter()
+                .map(|(_, diagnostic)| diagnostic),
+        );
+
+        diagnostics
+    }
+
+    /// 验证单个服务的依赖注入（组件注入和配置注入）
+    ///
+    /// 诊断位于服务所在的文件（`service_info.location.uri`）
+    pub(crate) fn validate_service(
+        &self,
+        service_info: &ServiceInfo,
+        toml_docs: &[(lsp_types::Url, TomlDocument)],
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for field in &service_info.fields {
+            if let Some(inject) = &field.inject {
+                match inject.inject_type {
+                    InjectType::Component => {
+                        // 验证组件注入
+                        diagnostics.extend(self.validate_component_injection(
+                            service_info,
+                            field,
+                            inject,
+                        ));
+                    }
+                    InjectType::Config => {
+                        // 验证配置注入
+                        diagnostics
+                            .extend(self.validate_config_injection(field, inject, toml_docs));
                     }
                 }
             }
         }
 
-        // 检测循环依赖
-        diagnostics.extend(self.detect_circular_dependencies(&services));
-
         diagnostics
     }
 
@@ -103,66 +138,92 @@ impl DependencyInjectionValidator {
     /// - 11.3: 验证组件名称是否匹配
     fn validate_component_injection(
         &self,
-        _service_name: &str,
+        service_info: &ServiceInfo,
         field: &FieldInfo,
         inject: &InjectMacro,
-        location: &Location,
     ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
+        // 索引尚未构建时无法判断组件是否注册，跳过检查以免每个注入都报错
+        if self.index_manager.is_empty() {
+            return diagnostics;
+        }
+
         // 获取组件名称（如果指定）
         let component_name = inject.component_name.as_deref().unwrap_or(&field.type_name);
+        // 与组件名称有关的诊断标在名称上，没有指定名称时标在字段上
+        let name_range = inject.name_range.unwrap_or(field.range);
 
         // 验证组件是否已注册（需求 11.1）
         if let Some(component_info) = self.index_manager.find_component(component_name) {
             // 组件已注册，验证类型是否匹配
             if component_info.type_name != field.type_name {
                 diagnostics.push(Diagnostic {
-                    range: location.range,
+                    range: field.range,
                     severity: Some(DiagnosticSeverity::WARNING),
                     code: Some(NumberOrString::String(
                         "component-type-mismatch".to_string(),
                     )),
-                    message: format!(
-                        "组件 '{}' 的类型不匹配。期望类型: {}，实际类型: {}",
-                        component_name, field.type_name, component_info.type_name
+                    message: localized(
+                        self.locale,
+                        "component-type-mismatch",
+                        &[&component_name, &field.type_name, &component_info.type_name],
                     ),
                     source: Some("spring-lsp".to_string()),
                     ..Default::default()
                 });
             }
+
+            // 组件只在某个条件下注册：未启用该条件时编译会失败，提示需要的 feature
+            if let Some(cfg) = &component_info.cfg {
+                if service_info.cfg.as_ref() != Some(cfg) {
+                    diagnostics.push(Diagnostic {
+                        range: field.range,
+                        severity: Some(DiagnosticSeverity::WARNING),
+                        code: Some(NumberOrString::String(COMPONENT_REQUIRES_CFG.to_string())),
+                        message: localized(
+                            self.locale,
+                            COMPONENT_REQUIRES_CFG,
+                            &[&component_name, cfg, &service_info.name],
+                        ),
+                        source: Some("spring-lsp".to_string()),
+                        ..Default::default()
+                    });
+                }
+            }
         } else {
             // 组件未注册，检查类型是否存在（需求 11.2）
             let symbols = self.index_manager.find_symbol(&field.type_name);
             if symbols.is_empty() {
                 diagnostics.push(Diagnostic {
-                    range: location.range,
+                    range: field.range,
                     severity: Some(DiagnosticSeverity::ERROR),
                     code: Some(NumberOrString::String(
                         "component-type-not-found".to_string(),
                     )),
-                    message: format!(
-                        "组件类型 '{}' 不存在。请确保该类型已定义。",
-                        field.type_name
+                    message: localized(
+                        self.locale,
+                        "component-type-not-found",
+                        &[&field.type_name],
                     ),
                     source: Some("spring-lsp".to_string()),
                     ..Default::default()
                 });
             } else {
                 // 类型存在但组件未注册（需求 11.1）
-                diagnostics.push(Diagnostic {
-                    range: location.range,
+                let mut diagnostic = Diagnostic {
+                    range: name_range,
                     severity: Some(DiagnosticSeverity::ERROR),
-                    code: Some(NumberOrString::String(
-                        "component-not-registered".to_string(),
-                    )),
-                    message: format!(
-                        "组件 '{}' 未注册。请确保该组件已通过插件注册。",
-                        component_name
-                    ),
+                    code: Some(NumberOrString::String(COMPONENT_NOT_REGISTERED.to_string())),
+                    message: localized(self.locale, COMPONENT_NOT_REGISTERED, &[&component_name]),
                     source: Some("spring-lsp".to_string()),
                     ..Default::default()
-                });
+                };
+                MissingComponent {
+                    component_type: field.type_name.clone(),
+                }
+                .attach(&mut diagnostic);
+                diagnostics.push(diagnostic);
             }
         }
 
@@ -178,22 +239,28 @@ impl DependencyInjectionValidator {
                     let suggestion = if available_components.is_empty() {
                         String::new()
                     } else {
-                        format!(
-                            "\n可用的 {} 类型组件: {}",
-                            field.type_name,
-                            available_components.join(", ")
+                        localized(
+                            self.locale,
+                            "component-available",
+                            &[&field.type_name, &available_components.join(", ")],
                         )
                     };
 
                     diagnostics.push(Diagnostic {
-                        range: location.range,
+                        range: name_range,
                         severity: Some(DiagnosticSeverity::ERROR),
                         code: Some(NumberOrString::String(
                             "component-name-mismatch".to_string(),
                         )),
-                        message: format!(
-                            "组件名称 '{}' 的类型不匹配。期望类型: {}，实际类型: {}{}",
-                            specified_name, field.type_name, component_info.type_name, suggestion
+                        message: localized(
+                            self.locale,
+                            "component-name-mismatch",
+                            &[
+                                specified_name,
+                                &field.type_name,
+                                &component_info.type_name,
+                                &suggestion,
+                            ],
                         ),
                         source: Some("spring-lsp".to_string()),
                         ..Default::default()
@@ -206,20 +273,24 @@ impl DependencyInjectionValidator {
                 let suggestion = if available_components.is_empty() {
                     String::new()
                 } else {
-                    format!(
-                        "\n可用的 {} 类型组件: {}",
-                        field.type_name,
-                        available_components.join(", ")
+                    localized(
+                        self.locale,
+                        "component-available",
+                        &[&field.type_name, &available_components.join(", ")],
                     )
                 };
 
                 diagnostics.push(Diagnostic {
-                    range: location.range,
+                    range: name_range,
                     severity: Some(DiagnosticSeverity::ERROR),
                     code: Some(NumberOrString::String(
                         "component-name-not-found".to_string(),
                     )),
-                    message: format!("组件名称 '{}' 不存在。{}", specified_name, suggestion),
+                    message: localized(
+                        self.locale,
+                        "component-name-not-found",
+                        &[specified_name, &suggestion],
+                    ),
                     source: Some("spring-lsp".to_string()),
                     ..Default::default()
                 });
@@ -239,7 +310,6 @@ impl DependencyInjectionValidator {
         field: &FieldInfo,
         _inject: &InjectMacro,
         toml_docs: &[(lsp_types::Url, TomlDocument)],
-        location: &Location,
     ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -261,21 +331,19 @@ impl DependencyInjectionValidator {
 
         if !config_found {
             let message = if let Some(uri) = &config_file_uri {
-                format!(
-                    "配置项 '{}' 不存在。请在配置文件 {} 中添加 [{}] 配置节。",
-                    config_prefix, uri, config_prefix
+                localized(
+                    self.locale,
+                    "config-not-found.in-file",
+                    &[&config_prefix, uri],
                 )
             } else {
-                format!(
-                    "配置项 '{}' 不存在。请在配置文件中添加 [{}] 配置节。",
-                    config_prefix, config_prefix
-                )
+                localized(self.locale, CONFIG_NOT_FOUND, &[&config_prefix])
             };
 
             diagnostics.push(Diagnostic {
-                range: location.range,
+                range: field.range,
                 severity: Some(DiagnosticSeverity::ERROR),
-                code: Some(NumberOrString::String("config-not-found".to_string())),
+                code: Some(NumberOrString::String(CONFIG_NOT_FOUND.to_string())),
                 message,
                 source: Some("spring-lsp".to_string()),
                 related_information: config_file_uri.map(|uri| {
@@ -293,7 +361,11 @@ impl DependencyInjectionValidator {
                                 },
                             },
                         },
-                        message: format!("在此文件中添加 [{}] 配置节", config_prefix),
+                        message: localized(
+                            self.locale,
+                            "config-not-found.related",
+                            &[&config_prefix],
+                        ),
                     }]
                 }),
                 ..Default::default()
@@ -308,10 +380,12 @@ impl DependencyInjectionValidator {
     /// # Requirements
     ///
     /// - 11.4: 检测循环依赖并建议使用 LazyComponent
-    fn detect_circular_dependencies(
+    ///
+    /// 返回诊断及其所在文件的 URI
+    pub(crate) fn detect_circular_dependencies(
         &self,
         services: &HashMap<String, ServiceInfo>,
-    ) -> Vec<Diagnostic> {
+    ) -> Vec<(lsp_types::Url, Diagnostic)> {
         let mut diagnostics = Vec::new();
 
         // 构建依赖图
@@ -347,17 +421,23 @@ impl DependencyInjectionValidator {
                 ) {
                     // 找到循环依赖
                     if let Some(service_info) = services.get(service_name) {
-                        diagnostics.push(Diagnostic {
-                            range: service_info.location.range,
-                            severity: Some(DiagnosticSeverity::WARNING),
-                            code: Some(NumberOrString::String("circular-dependency".to_string())),
-                            message: format!(
-                                "检测到循环依赖: {}。建议使用 LazyComponent<T> 打破循环。",
-                                cycle.join(" -> ")
-                            ),
-                            source: Some("spring-lsp".to_string()),
-                            ..Default::default()
-                        });
+                        diagnostics.push((
+                            service_info.location.uri.clone(),
+                            Diagnostic {
+                                range: service_info.location.range,
+                                severity: Some(DiagnosticSeverity::WARNING),
+                                code: Some(NumberOrString::String(
+                                    "circular-dependency".to_string(),
+                                )),
+                                message: localized(
+                                    self.locale,
+                                    "circular-dependency",
+                                    &[&cycle.join(" -> ")],
+                                ),
+                                source: Some("spring-lsp".to_string()),
+                                ..Default::default()
+                            },
+                        ));
                     }
                 }
             }
@@ -403,7 +483,10 @@ impl DependencyInjectionValidator {
     }
 
     /// 提取服务信息
-    fn extract_services(&self, rust_docs: &[RustDocument]) -> HashMap<String, ServiceInfo> {
+    pub(crate) fn extract_services(
+        &self,
+        rust_docs: &[RustDocument],
+    ) -> HashMap<String, ServiceInfo> {
         let mut services = HashMap::new();
 
         for doc in rust_docs {
@@ -418,12 +501,14 @@ impl DependencyInjectionValidator {
                                 name: f.name.clone(),
                                 type_name: f.type_name.clone(),
                                 inject: f.inject.clone(),
+                                range: f.range,
                             })
                             .collect(),
                         location: Location {
                             uri: doc.uri.clone(),
                             range: service_macro.range,
                         },
+                        cfg: service_macro.cfg.clone(),
                     };
                     services.insert(service_macro.struct_name.clone(), service_info);
                 }
@@ -463,18 +548,36 @@ impl DependencyInjectionValidator {
 }
 
 /// 服务信息
-struct ServiceInfo {
+#[derive(Clone)]
+pub(crate) struct ServiceInfo {
     /// 服务名称
-    #[allow(dead_code)]
     name: String,
     /// 字段列表
     fields: Vec<FieldInfo>,
     /// 位置
-    location: Location,
+    pub(crate) location: Location,
+    /// 结构体上 `#[cfg(...)]` 的条件
+    cfg: Option<String>,
+}
+
+impl ServiceInfo {
+    /// 通过 `#[inject(config)]` 注入的配置类型名称
+    pub(crate) fn config_types(&self) -> impl Iterator<Item = &str> {
+        self.fields
+            .iter()
+            .filter(|field| {
+                field
+                    .inject
+                    .as_ref()
+                    .is_some_and(|inject| inject.inject_type == InjectType::Config)
+            })
+            .map(|field| field.type_name.as_str())
+    }
 }
 
 /// 字段信息
-struct FieldInfo {
+#[derive(Clone)]
+pub(crate) struct FieldInfo {
     /// 字段名称
     #[allow(dead_code)]
     name: String,
@@ -482,6 +585,8 @@ struct FieldInfo {
     type_name: String,
     /// 注入宏
     inject: Option<InjectMacro>,
+    /// 字段名的位置（注入诊断标在字段上）
+    range: lsp_types::Range,
 }
 
 #[cfg(test)]
@@ -514,4 +619,65 @@ mod tests {
         let diagnostics = validator.validate(&[], &[]);
         assert_eq!(diagnostics.len(), 0);
     }
+
+    #[test]
+    fn test_component_requires_cfg() {
+        use crate::analysis::rust::macro_analyzer::MacroAnalyzer;
+        use crate::core::index::ComponentInfo;
+
+        let uri = lsp_types::Url::parse("file:///app/src/main.rs").unwrap();
+        let analyzer = MacroAnalyzer::new();
+        let doc = analyzer
+            .parse(
+                uri.clone(),
+                r#"
+#[cfg(feature = "redis")]
+#[component]
+fn redis_cache() -> RedisCache { RedisCache::default() }
+
+#[derive(Clone, Service)]
+struct UserService {
+    #[inject(component)]
+    cache: RedisCache,
+}
+
+#[cfg(feature = "redis")]
+#[derive(Clone, Service)]
+struct SessionService {
+    #[inject(component)]
+    cache: RedisCache,
+}
+"#
+                .to_string(),
+            )
+            .and_then(|doc| analyzer.extract_macros(doc))
+            .unwrap();
+
+        let index_manager = IndexManager::new();
+        for spring_macro in &doc.macros {
+            if let SpringMacro::Component(component) = spring_macro {
+                index_manager.add_component(ComponentInfo {
+                    name: component.component_type.clone(),
+                    qualified_name: None,
+                    type_name: component.component_type.clone(),
+                    location: Location {
+                        uri: uri.clone(),
+                        range: component.range,
+                    },
+                    plugin: None,
+                    cfg: component.cfg.clone(),
+                });
+            }
+        }
+
+        let diagnostics = DependencyInjectionValidator::new(index_manager).validate(&[doc], &[]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String(COMPONENT_REQUIRES_CFG.to_string()))
+        );
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert!(diagnostics[0].message.contains("cfg(feature = \"redis\")"));
+        assert!(diagnostics[0].message.contains("'UserService'"));
+    }
 }
@@ -0,0 +1,299 @@
+//! 应用入口验证模块
+//!
+//! 检查 crate 中的 `#[auto_config(...)]` 入口，包括：
+//! - 同一个二进制目标中存在多个 `#[auto_config]` 入口（每个入口都会报告，并关联其他入口的位置）
+//! - 二进制 crate 中定义了路由或任务，但没有任何入口使用对应的配置器注册它们
+//!
+//! `src/bin/<name>.rs` 和 `src/bin/<name>/` 下的文件属于独立的二进制目标，
+//! 其余文件视为属于 `src/main.rs` 所在的默认目标。只有库的 crate 不检查缺少入口，
+//! 它的路由和任务可能由其他 crate 的入口注册。
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString, Range,
+    Url,
+};
+
+use crate::analysis::rust::macro_analyzer::{AutoConfigMacro, JobMacro, RustDocument, SpringMacro};
+use crate::core::messages::{localized, Locale};
+
+/// 同一个二进制目标中有多个入口
+pub const MULTIPLE_AUTO_CONFIG: &str = "multiple-auto-config";
+
+/// 路由或任务没有入口注册
+pub const MISSING_AUTO_CONFIG: &str = "missing-auto-config";
+
+/// 应用入口验证器
+#[derive(Debug, Clone, Default)]
+pub struct EntryPointValidator {
+    /// 消息语言
+    locale: Locale,
+}
+
+impl EntryPointValidator {
+    /// 创建新的应用入口验证器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 验证一个 crate 的入口
+    ///
+    /// `crate_root` 是 `Cargo.toml` 所在目录，`rust_docs` 是已提取宏的源文件
+    pub fn validate(
+        &self,
+        crate_root: &Path,
+        rust_docs: &[RustDocument],
+    ) -> BTreeMap<Url, Vec<Diagnostic>> {
+        let mut diagnostics: BTreeMap<Url, Vec<Diagnostic>> = BTreeMap::new();
+
+        // 二进制目标 -> 入口
+        let mut targets: BTreeMap<String, Vec<(&Url, &AutoConfigMacro)>> = BTreeMap::new();
+        let mut routes = Vec::new();
+        let mut jobs = Vec::new();
+
+        for doc in rust_docs {
+            for spring_macro in &doc.macros {
+                match spring_macro {
+                    SpringMacro::AutoConfig(auto_config) => targets
+                        .entry(binary_target(crate_root, &doc.uri))
+                        .or_default()
+                        .push((&doc.uri, auto_config)),
+                    SpringMacro::Route(route) => routes.push(location(&doc.uri, route.range)),
+                    SpringMacro::Job(job) => jobs.push(location(&doc.uri, job_range(job))),
+                    _ => {}
+                }
+            }
+        }
+
+        for (target, entries) in &targets {
+            if entries.len() < 2 {
+                continue;
+            }
+
+            for (i, (uri, auto_config)) in entries.iter().enumerate() {
+                let related = entries
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, (uri, other))| DiagnosticRelatedInformation {
+                        location: location(uri, other.range),
+                        message: localized(self.locale, "multiple-auto-config.related", &[]),
+                    })
+                    .collect();
+
+                diagnostics
+                    .entry((*uri).clone())
+                    .or_default()
+                    .push(Diagnostic {
+                        range: auto_config.range,
+                        severity: Some(DiagnosticSeverity::ERROR),
+                        code: Some(NumberOrString::String(MULTIPLE_AUTO_CONFIG.to_string())),
+                        source: Some("spring-lsp".to_string()),
+                        message: localized(
+                            self.locale,
+                            MULTIPLE_AUTO_CONFIG,
+                            &[target, &entries.len()],
+                        ),
+                        related_information: Some(related),
+                        ..Default::default()
+                    });
+            }
+        }
+
+        if !is_binary_crate(crate_root) {
+            return diagnostics;
+        }
+
+        let registered = |configurator: &str| {
+            targets
+                .values()
+                .flatten()
+                .any(|(_, auto_config)| auto_config.configurators().contains(&configurator))
+        };
+
+        for (items, kind, configurator) in [
+            (&routes, "missing-auto-config.routes", "WebConfigurator"),
+            (&jobs, "missing-auto-config.jobs", "JobConfigurator"),
+        ] {
+            let Some((first, rest)) = items.split_first() else {
+                continue;
+            };
+            if registered(configurator) {
+                continue;
+            }
+            let kind = localized(self.locale, kind, &[]);
+
+            diagnostics
+                .entry(first.uri.clone())
+                .or_default()
+                .push(Diagnostic {
+                    range: first.range,
+                    severity: Some(DiagnosticSeverity::WARNING),
+                    code: Some(NumberOrString::String(MISSING_AUTO_CONFIG.to_string())),
+                    source: Some("spring-lsp".to_string()),
+                    message: localized(
+                        self.locale,
+                        MISSING_AUTO_CONFIG,
+                        &[&items.len(), &kind, &configurator],
+                    ),
+                    related_information: Some(
+                        rest.iter()
+                            .map(|location| DiagnosticRelatedInformation {
+                                location: location.clone(),
+                                message: localized(
+                                    self.locale,
+                                    "missing-auto-config.related",
+                                    &[&kind],
+                                ),
+                            })
+                            .collect(),
+                    ),
+                    ..Default::default()
+                });
+        }
+
+        diagnostics
+    }
+}
+
+/// 文件所属的二进制目标名称
+fn binary_target(crate_root: &Path, uri: &Url) -> String {
+    let bin = crate_root.join("src").join("bin");
+    uri.to_file_path()
+        .ok()
+        .and_then(|path| {
+            let relative = path.strip_prefix(&bin).ok()?.to_path_buf();
+            let first = relative.components().next()?;
+            let name = Path::new(first.as_os_str()).file_stem()?;
+            Some(name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "main".to_string())
+}
+
+/// crate 是否包含二进制目标
+fn is_binary_crate(crate_root: &Path) -> bool {
+    crate_root.join("src").join("main.rs").is_file() || crate_root.join("src").join("bin").is_dir()
+}
+
+fn job_range(job: &JobMacro) -> Range {
+    match job {
+        JobMacro::Cron { range, .. }
+        | JobMacro::FixDelay { range, .. }
+        | JobMacro::FixRate { range, .. }
+        | JobMacro::OneShot { range, .. } => *range,
+    }
+}
+
+fn location(uri: &Url, range: Range) -> Location {
+    Location {
+        uri: uri.clone(),
+        range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rust::macro_analyzer::MacroAnalyzer;
+
+    fn docs(root: &Path, files: &[(&str, &str)]) -> Vec<RustDocument> {
+        let analyzer = MacroAnalyzer::new();
+        files
+            .iter()
+            .map(|(path, content)| {
+                let full = root.join(path);
+                std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+                std::fs::write(&full, content).unwrap();
+                let doc = analyzer
+                    .parse(Url::from_file_path(&full).unwrap(), content.to_string())
+                    .unwrap();
+                analyzer.extract_macros(doc).unwrap()
+            })
+            .collect()
+    }
+
+    fn codes(diagnostics: &BTreeMap<Url, Vec<Diagnostic>>) -> Vec<&str> {
+        diagnostics
+            .values()
+            .flatten()
+            .filter_map(|d| match &d.code {
+                Some(NumberOrString::String(code)) => Some(code.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    const MAIN: &str = "#[auto_config(WebConfigurator)]\n#[tokio::main]\nasync fn main() {}\n";
+
+    #[test]
+    fn test_multiple_entry_points() {
+        let dir = tempfile::tempdir().unwrap();
+        let rust_docs = docs(
+            dir.path(),
+            &[
+                ("src/main.rs", MAIN),
+                ("src/app.rs", MAIN),
+                // 独立的二进制目标不与默认目标冲突
+                ("src/bin/worker.rs", MAIN),
+            ],
+        );
+
+        let diagnostics = EntryPointValidator::new().validate(dir.path(), &rust_docs);
+        assert_eq!(
+            codes(&diagnostics),
+            vec![MULTIPLE_AUTO_CONFIG, MULTIPLE_AUTO_CONFIG]
+        );
+
+        let main_uri = Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+        let app_uri = Url::from_file_path(dir.path().join("src/app.rs")).unwrap();
+        let related = diagnostics[&main_uri][0]
+            .related_information
+            .as_ref()
+            .unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].location.uri, app_uri);
+    }
+
+    #[test]
+    fn test_missing_entry_point() {
+        let dir = tempfile::tempdir().unwrap();
+        let handlers = "#[get(\"/a\")]\nasync fn a() {}\n\n#[get(\"/b\")]\nasync fn b() {}\n\n#[cron(\"0 0 * * * *\")]\nasync fn cleanup() {}\n";
+
+        // 只有库的 crate 不检查
+        let rust_docs = docs(dir.path(), &[("src/lib.rs", handlers)]);
+        assert!(EntryPointValidator::new()
+            .validate(dir.path(), &rust_docs)
+            .is_empty());
+
+        // 入口只注册了路由，任务没有被注册
+        let rust_docs = docs(
+            dir.path(),
+            &[("src/lib.rs", handlers), ("src/main.rs", MAIN)],
+        );
+        let diagnostics = EntryPointValidator::new().validate(dir.path(), &rust_docs);
+        assert_eq!(codes(&diagnostics), vec![MISSING_AUTO_CONFIG]);
+        let diagnostic = diagnostics.values().flatten().next().unwrap();
+        assert!(diagnostic.message.contains("JobConfigurator"));
+
+        // 没有入口
+        let main_rs = dir.path().join("src/main.rs");
+        std::fs::write(&main_rs, "fn main() {}\n").unwrap();
+        let rust_docs = docs(dir.path(), &[("src/lib.rs", handlers)]);
+        let diagnostics = EntryPointValidator::new().validate(dir.path(), &rust_docs);
+        assert_eq!(
+            codes(&diagnostics),
+            vec![MISSING_AUTO_CONFIG, MISSING_AUTO_CONFIG]
+        );
+        let routes = &diagnostics.values().flatten().next().unwrap();
+        assert!(routes.message.contains("2 个路由"));
+        assert_eq!(routes.related_information.as_ref().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,206 @@
+//! 路由处理器复杂度提示模块
+//!
+//! 处理器中堆积过多业务逻辑时，建议把逻辑提取到 Service 中，处理器只负责提取参数和组装响应：
+//! - 函数体行数超过 `[complexity] max_lines` 时报告 `handler-complexity/too-long`
+//! - 注入的组件（`Component<T>` 参数）超过 `[complexity] max_components` 时报告
+//!   `handler-complexity/too-many-components`
+//!
+//! 提示默认关闭，需要在 `[complexity]` 中开启。诊断级别为 Information，所有诊断代码属于
+//! `handler-complexity` 规则组，可以在 `[diagnostics] disabled` 中按规则组整体禁用。
+//!
+//! [`HandlerComplexityValidator`] 直接实现 [`AnalysisPass`]，加入 Rust 文件的分析流水线即可生效。
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+use crate::analysis::pipeline::{AnalysisContext, AnalysisPass, PassStage};
+use crate::analysis::rust::handler_analyzer::{ExtractorKind, HandlerFile};
+use crate::core::config::ComplexityConfig;
+use crate::core::messages::{localized, Locale};
+
+/// 规则组名称
+pub const HANDLER_COMPLEXITY_GROUP: &str = "handler-complexity";
+
+/// 处理器函数体过长
+pub const TOO_LONG: &str = "handler-complexity/too-long";
+
+/// 处理器注入的组件过多
+pub const TOO_MANY_COMPONENTS: &str = "handler-complexity/too-many-components";
+
+/// 路由处理器复杂度验证器
+#[derive(Debug, Clone, Default)]
+pub struct HandlerComplexityValidator {
+    /// 消息语言
+    locale: Locale,
+}
+
+impl HandlerComplexityValidator {
+    /// 创建新的路由处理器复杂度验证器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 按配置的阈值检查文件中的所有路由处理器
+    pub fn validate(&self, file: &HandlerFile, config: &ComplexityConfig) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for handler in &file.handlers {
+            if handler.body_lines > config.max_lines {
+                diagnostics.push(diagnostic(
+                    handler.range,
+                    TOO_LONG,
+                    localized(
+                        self.locale,
+                        TOO_LONG,
+                        &[&handler.name, &handler.body_lines, &config.max_lines],
+                    ),
+                ));
+            }
+
+            let components = handler
+                .params
+                .iter()
+                .filter(|param| param.kind == ExtractorKind::Component)
+                .count();
+            if components > config.max_components {
+                diagnostics.push(diagnostic(
+                    handler.range,
+                    TOO_MANY_COMPONENTS,
+                    localized(
+                        self.locale,
+                        TOO_MANY_COMPONENTS,
+                        &[&handler.name, &components, &config.max_components],
+                    ),
+                ));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl AnalysisPass for HandlerComplexityValidator {
+    fn name(&self) -> &'static str {
+        HANDLER_COMPLEXITY_GROUP
+    }
+
+    fn stage(&self) -> PassStage {
+        PassStage::Validate
+    }
+
+    fn run(&self, ctx: &mut AnalysisContext) {
+        if !ctx.config.complexity.enabled {
+            return;
+        }
+        if let Some(file) = &ctx.handlers {
+            let diagnostics = self.validate(file, &ctx.config.complexity);
+            ctx.diagnostics.extend(diagnostics);
+        }
+    }
+
+    fn item_local(&self) -> bool {
+        true
+    }
+}
+
+fn diagnostic(range: lsp_types::Range, code: &str, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::INFORMATION),
+        code: Some(NumberOrString::String(code.to_string())),
+        source: Some("spring-lsp".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::pipeline::{AnalysisPipeline, DocumentPipeline};
+    use crate::analysis::rust::handler_analyzer::HandlerAnalyzer;
+    use crate::core::config::ServerConfig;
+    use lsp_types::Url;
+
+    const SOURCE: &str = r#"
+#[get("/orders")]
+async fn list(Component(db): Component<Db>) -> Json<Vec<Order>> {
+    let orders = db.orders().await;
+    Json(orders)
+}
+
+#[post("/orders")]
+async fn create(
+    Component(db): Component<Db>,
+    Component(mail): Component<Mailer>,
+    Component(stock): Component<Stock>,
+    Json(order): Json<NewOrder>,
+) -> Json<Order> {
+    stock.reserve(&order).await;
+    let order = db.insert(order).await;
+    mail.send(&order).await;
+    Json(order)
+}
+"#;
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics
+            .iter()
+            .filter_map(|d| match &d.code {
+                Some(NumberOrString::String(code)) => Some(code.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_handler_complexity_thresholds() {
+        let file = HandlerAnalyzer::new().analyze(SOURCE).unwrap();
+        assert_eq!(file.handlers[0].body_lines, 4);
+        assert_eq!(file.handlers[1].body_lines, 6);
+
+        let config = ComplexityConfig {
+            enabled: true,
+            max_lines: 5,
+            max_components: 2,
+        };
+        let diagnostics = HandlerComplexityValidator::new().validate(&file, &config);
+        assert_eq!(codes(&diagnostics), vec![TOO_LONG, TOO_MANY_COMPONENTS]);
+        assert_eq!(
+            diagnostics[0].message,
+            "处理器 'create' 的函数体有 6 行（超过 5 行），建议把业务逻辑提取到 Service 中"
+        );
+        assert_eq!(diagnostics[0].range.start.line, 8);
+        assert_eq!(
+            diagnostics[0].severity,
+            Some(DiagnosticSeverity::INFORMATION)
+        );
+
+        assert!(HandlerComplexityValidator::new()
+            .validate(&file, &ComplexityConfig::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_complexity_pass_is_off_by_default() {
+        let uri = Url::parse("file:///app/src/orders.rs").unwrap();
+        let analyzer = HandlerAnalyzer::new();
+        let pipeline = DocumentPipeline::rust(&analyzer);
+
+        let mut config = ServerConfig::default();
+        config.complexity.max_components = 2;
+        let mut ctx = AnalysisContext::new(&uri, SOURCE, &config);
+        pipeline.run(&mut ctx);
+        assert!(!codes(&ctx.diagnostics).contains(&TOO_MANY_COMPONENTS));
+
+        config.complexity.enabled = true;
+        let mut ctx = AnalysisContext::new(&uri, SOURCE, &config);
+        pipeline.run(&mut ctx);
+        assert!(codes(&ctx.diagnostics).contains(&TOO_MANY_COMPONENTS));
+    }
+}
@@ -0,0 +1,213 @@
+//! 处理器重名检查模块
+//!
+//! 不同模块中定义的同名路由处理器（如 `crate::api::users::list` 和 `crate::api::orders::list`）
+//! 在按函数名反查路由时无法区分，导航可能跳转到错误的处理器。
+//! 这里在当前文档的处理器上给出提示级别的诊断，并列出其他模块中的同名处理器；
+//! 需要精确定位时应使用处理器的完整路径（见 [`RouteIndex::find_routes_by_handler`]）。
+//!
+//! [`RouteIndex::find_routes_by_handler`]: crate::scanner::route::RouteIndex::find_routes_by_handler
+
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString,
+    Position, Range, Url,
+};
+
+use crate::analysis::rust::handler_analyzer::HandlerFile;
+use crate::analysis::rust::module_path::qualified_name;
+use crate::core::messages::{localized, Locale};
+use crate::scanner::route::RouteInfoResponse;
+
+/// 处理器与其他模块中的处理器重名
+pub const DUPLICATE_HANDLER_NAME: &str = "duplicate-handler-name";
+
+/// 处理器重名验证器
+#[derive(Debug, Clone, Default)]
+pub struct HandlerNameValidator {
+    /// 消息语言
+    locale: Locale,
+}
+
+impl HandlerNameValidator {
+    /// 创建验证器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 检查当前文档中的处理器是否与项目中其他文件的处理器重名
+    ///
+    /// `routes` 为项目中的所有路由，当前文档中的路由会被忽略（使用编辑器中的内容分析），
+    /// 其他文件通过 `Router::route` 注册当前处理器的路由也不算重名
+    pub fn validate(
+        &self,
+        uri: &Url,
+        handler_file: &HandlerFile,
+        routes: &[RouteInfoResponse],
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for handler in &handler_file.handlers {
+            if handler.routes.is_empty() {
+                continue;
+            }
+
+            // 同一个处理器的多个 HTTP 方法只列出一次
+            let own_path = uri
+                .to_file_path()
+                .ok()
+                .and_then(|path| qualified_name(&path, &handler.name));
+            let mut others: Vec<&RouteInfoResponse> = Vec::new();
+            for route in routes {
+                if route.handler == handler.name
+                    && route.location.uri != uri.as_str()
+                    && (own_path.is_none() || route.handler_path != own_path)
+                    && !others.iter().any(|other| {
+                        other.location.uri == route.location.uri
+                            && other.handler_path == route.handler_path
+                    })
+                {
+                    others.push(route);
+                }
+            }
+            if others.is_empty() {
+                continue;
+            }
+
+            let paths: Vec<String> = others.iter().map(|route| display_name(route)).collect();
+            diagnostics.push(Diagnostic {
+                range: handler.range,
+                severity: Some(DiagnosticSeverity::INFORMATION),
+                code: Some(NumberOrString::String(DUPLICATE_HANDLER_NAME.to_string())),
+                source: Some("spring-lsp".to_string()),
+                message: localized(
+                    self.locale,
+                    DUPLICATE_HANDLER_NAME,
+                    &[
+                        &handler.name,
+                        &paths.join(&localized(self.locale, "list.separator", &[])),
+                    ],
+                ),
+                related_information: Some(
+                    others
+                        .iter()
+                        .zip(&paths)
+                        .filter_map(|(route, path)| {
+                            Some(DiagnosticRelatedInformation {
+                                location: Location::new(
+                                    Url::parse(&route.location.uri).ok()?,
+                                    location_range(route),
+                                ),
+                                message: path.clone(),
+                            })
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            });
+        }
+        diagnostics
+    }
+}
+
+/// 扫描到的路由在文档中的位置范围
+pub(crate) fn location_range(route: &RouteInfoResponse) -> Range {
+    let range = &route.location.range;
+    Range::new(
+        Position::new(range.start.line, range.start.character),
+        Position::new(range.end.line, range.end.character),
+    )
+}
+
+/// 处理器的显示名称：完整路径，不属于模块树时为文件 URI 和函数名
+pub(crate) fn display_name(route: &RouteInfoResponse) -> String {
+    match &route.handler_path {
+        Some(path) => path.clone(),
+        None => format!("{} ({})", route.handler, route.location.uri),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rust::handler_analyzer::HandlerAnalyzer;
+    use crate::protocol::types::{LocationResponse, PositionResponse, RangeResponse};
+
+    fn route(uri: &str, method: &str, handler_path: &str) -> RouteInfoResponse {
+        let position = PositionResponse {
+            line: 1,
+            character: 0,
+        };
+        RouteInfoResponse {
+            method: method.to_string(),
+            path: "/".to_string(),
+            handler: "list".to_string(),
+            handler_path: Some(handler_path.to_string()),
+            is_openapi: false,
+            doc: None,
+            deprecated: false,
+            stub: false,
+            cfg: None,
+            instrument: None,
+            confidence: crate::scanner::route::RouteConfidence::High,
+            location: LocationResponse {
+                uri: uri.to_string(),
+                range: RangeResponse {
+                    start: position.clone(),
+                    end: position,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_duplicate_handler_names() {
+        let uri = Url::parse("file:///app/src/api/users.rs").unwrap();
+        let source = "#[get(\"/users\")]\nasync fn list() {}\n\n#[get(\"/users/{id}\")]\nasync fn show() {}\n\nfn helper() {}\n";
+        let file = HandlerAnalyzer::new().analyze(source).unwrap();
+        let routes = vec![
+            route(uri.as_str(), "GET", "crate::api::users::list"),
+            route(
+                "file:///app/src/api/orders.rs",
+                "GET",
+                "crate::api::orders::list",
+            ),
+            route(
+                "file:///app/src/api/orders.rs",
+                "HEAD",
+                "crate::api::orders::list",
+            ),
+        ];
+
+        let diagnostics = HandlerNameValidator::new().validate(&uri, &file, &routes);
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.range.start, lsp_types::Position::new(1, 9));
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::INFORMATION));
+        assert_eq!(
+            diagnostic.message,
+            "处理器 'list' 与 crate::api::orders::list 重名，按函数名查找路由时无法区分，请使用完整路径"
+        );
+        let related = diagnostic.related_information.as_ref().unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].location.uri.path(), "/app/src/api/orders.rs");
+
+        // 其他文件通过 `.route` 注册的是当前处理器本身
+        let registered = vec![route(
+            "file:///app/src/router.rs",
+            "GET",
+            "crate::api::users::list",
+        )];
+        assert!(HandlerNameValidator::new()
+            .validate(&uri, &file, &registered)
+            .is_empty());
+
+        // 只有当前文档中的路由时不报告
+        assert!(HandlerNameValidator::new()
+            .validate(&uri, &file, &routes[..1])
+            .is_empty());
+    }
+}
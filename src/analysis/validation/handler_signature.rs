@@ -0,0 +1,512 @@
+//! 处理器签名检查模块
+//!
+//! 检查路由宏和任务调度宏标注的函数签名，在编译器报告难以理解的宏展开错误之前给出可操作的诊断：
+//! - 路由处理器必须是 `async fn`（或返回 `impl Future` 的函数）
+//! - 消费请求体的提取器（`Json`、`Form`、`String`、`Bytes`、`Multipart` 等实现 `FromRequest` 的提取器）
+//!   必须是路由处理器的最后一个参数，且只能有一个
+//! - 任务函数必须是 `async fn`（或返回 `impl Future` 的函数），且不能返回值（spring-job
+//!   要求任务的 Future 输出为 `()`）
+//! - 任务函数的参数必须能由 spring-job 提供：`Component<T>`、`Config<T>`、`JobId`、`JobScheduler`
+//!   或其他实现 `FromApp` 的类型；引用、`self`、`impl Trait` 和 Web 提取器（`Json`、`Path` 等）无法提供
+//!
+//! 任务签名的诊断附带指向 `docs/rules.md` 的文档链接（`codeDescription`），说明 spring-job 期望的任务签名。
+//! 缺少 `async` 时附带在 `fn` 前插入 `async` 的快速修复；请求体提取器不是最后一个参数时附带
+//! 把它移到参数列表末尾的快速修复。
+
+use lsp_types::{CodeDescription, Diagnostic, DiagnosticSeverity, NumberOrString, Url};
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+use crate::analysis::diagnostic::QuickFix;
+use crate::analysis::rust::cfg::expand_cfg_attrs;
+use crate::analysis::rust::handler_analyzer::ROUTE_ATTRIBUTES;
+use crate::core::document::PositionMapper;
+use crate::core::messages::{localized, Locale};
+use crate::core::sarif::find_rule;
+use crate::scanner::job::JOB_ATTRIBUTES;
+
+/// 路由处理器不是异步函数
+pub const NON_ASYNC_HANDLER: &str = "E021";
+
+/// 任务函数不是异步函数
+pub const NON_ASYNC_JOB: &str = "E022";
+
+/// 任务函数有返回值
+pub const JOB_RETURNS_VALUE: &str = "E023";
+
+/// 请求体提取器不是路由处理器的最后一个参数
+pub const BODY_EXTRACTOR_NOT_LAST: &str = "E027";
+
+/// 任务函数的参数无法由 spring-job 提供
+pub const JOB_UNSUPPORTED_PARAM: &str = "E031";
+
+/// 消费请求体的提取器（实现 `FromRequest` 而不是 `FromRequestParts`）
+const BODY_EXTRACTORS: &[&str] = &[
+    "Json",
+    "Form",
+    "String",
+    "Bytes",
+    "Body",
+    "Request",
+    "Multipart",
+    "RawForm",
+    "Protobuf",
+];
+
+/// 只能在路由处理器中使用的提取器，任务没有请求可供提取
+const WEB_EXTRACTORS: &[&str] = &[
+    "Path",
+    "Query",
+    "Json",
+    "Form",
+    "TypedHeader",
+    "HeaderMap",
+    "State",
+    "Extension",
+    "Method",
+    "Uri",
+    "Request",
+    "Bytes",
+    "Body",
+    "Multipart",
+    "RawForm",
+    "ConnectInfo",
+    "OriginalUri",
+    "WebSocketUpgrade",
+    "Protobuf",
+];
+
+/// 基本类型和标准库类型没有实现 `FromApp`
+const PLAIN_TYPES: &[&str] = &[
+    "bool", "char", "str", "String", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16",
+    "u32", "u64", "u128", "usize", "f32", "f64", "Vec", "Option", "Result", "HashMap", "BTreeMap",
+];
+
+/// 处理器签名验证器
+#[derive(Debug, Clone, Default)]
+pub struct HandlerSignatureValidator {
+    /// 消息语言
+    locale: Locale,
+}
+
+impl HandlerSignatureValidator {
+    /// 创建新的处理器签名验证器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 验证文件中带有路由宏或任务调度宏的函数
+    ///
+    /// 语法错误由 rust-analyzer 报告，无法解析时返回空列表
+    pub fn validate(&self, lines: &PositionMapper) -> Vec<Diagnostic> {
+        let Ok(file) = syn::parse_file(lines.content()) else {
+            return Vec::new();
+        };
+
+        let mut diagnostics = Vec::new();
+        for item in &file.items {
+            let syn::Item::Fn(item_fn) = item else {
+                continue;
+            };
+            let attrs = expand_cfg_attrs(&item_fn.attrs);
+            let macro_name = |names: &[&str]| {
+                attrs.iter().find_map(|(attr, _)| {
+                    names
+                        .iter()
+                        .find(|name| attr.path().is_ident(name))
+                        .map(|name| name.to_string())
+                })
+            };
+            let sig = &item_fn.sig;
+            let name = sig.ident.to_string();
+
+            if let Some(route) = macro_name(ROUTE_ATTRIBUTES) {
+                if !is_async(sig) {
+                    diagnostics.push(non_async_diagnostic(
+                        sig,
+                        lines,
+                        NON_ASYNC_HANDLER,
+                        localized(self.locale, NON_ASYNC_HANDLER, &[&name, &route]),
+                        self.locale,
+                    ));
+                }
+                diagnostics.extend(body_order_diagnostic(sig, lines, self.locale));
+            }
+
+            if let Some(job) = macro_name(JOB_ATTRIBUTES) {
+                if !is_async(sig) {
+                    diagnostics.push(with_docs(non_async_diagnostic(
+                        sig,
+                        lines,
+                        NON_ASYNC_JOB,
+                        localized(self.locale, NON_ASYNC_JOB, &[&name, &job]),
+                        self.locale,
+                    )));
+                } else if let syn::ReturnType::Type(_, ty) = &sig.output {
+                    if !is_unit(ty) && !returns_future(sig) {
+                        diagnostics.push(with_docs(Diagnostic {
+                            range: lines.span_range(ty.span()),
+                            severity: Some(DiagnosticSeverity::ERROR),
+                            code: Some(NumberOrString::String(JOB_RETURNS_VALUE.to_string())),
+                            source: Some("spring-lsp".to_string()),
+                            message: localized(self.locale, JOB_RETURNS_VALUE, &[&name]),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                diagnostics.extend(job_param_diagnostics(sig, lines, self.locale));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// 函数是 `async fn` 或返回 `impl Future`
+fn is_async(sig: &syn::Signature) -> bool {
+    sig.asyncness.is_some() || returns_future(sig)
+}
+
+/// 函数的返回类型是 Future（如 `impl Future<Output = ()>`、`BoxFuture<'static, ()>`）
+fn returns_future(sig: &syn::Signature) -> bool {
+    match &sig.output {
+        syn::ReturnType::Type(_, ty) => ty.to_token_stream().to_string().contains("Future"),
+        syn::ReturnType::Default => false,
+    }
+}
+
+/// 类型是 `()`
+fn is_unit(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Tuple(tuple) if tuple.elems.is_empty())
+}
+
+/// 参数类型对应的请求体提取器名称（`Option<Json<T>>` 和 `Result<Json<T>, E>` 按内层类型判断）
+fn body_extractor(ty: &syn::Type) -> Option<String> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    let name = segment.ident.to_string();
+    if name == "Option" || name == "Result" {
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        return args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(inner) => body_extractor(inner),
+            _ => None,
+        });
+    }
+    BODY_EXTRACTORS.contains(&name.as_str()).then_some(name)
+}
+
+/// 检查请求体提取器是否是最后一个参数
+///
+/// 只有一个请求体提取器时附带把它移到参数列表末尾的快速修复（参数之间的分隔符保持原样）
+fn body_order_diagnostic(
+    sig: &syn::Signature,
+    lines: &PositionMapper,
+    locale: Locale,
+) -> Option<Diagnostic> {
+    let params: Vec<_> = sig.inputs.iter().collect();
+    let bodies: Vec<(usize, String)> = params
+        .iter()
+        .enumerate()
+        .filter_map(|(index, param)| match param {
+            syn::FnArg::Typed(pat_type) => Some((index, body_extractor(&pat_type.ty)?)),
+            syn::FnArg::Receiver(_) => None,
+        })
+        .collect();
+    let last = params.len().checked_sub(1)?;
+    let &(index, ref extractor) = bodies.iter().find(|(index, _)| *index != last)?;
+
+    let name = sig.ident.to_string();
+    let mut diagnostic = Diagnostic {
+        range: lines.span_range(params[index].span()),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(BODY_EXTRACTOR_NOT_LAST.to_string())),
+        source: Some("spring-lsp".to_string()),
+        ..Default::default()
+    };
+    if bodies.len() > 1 {
+        diagnostic.message = localized(locale, "E027.multiple", &[&name, &bodies.len()]);
+        return Some(diagnostic);
+    }
+    diagnostic.message = localized(locale, BODY_EXTRACTOR_NOT_LAST, &[&extractor, &name]);
+
+    let ranges: Vec<_> = params
+        .iter()
+        .map(|param| lines.span_range(param.span()))
+        .collect();
+    let text = |start, end| -> Option<&str> {
+        lines
+            .content()
+            .get(lines.offset(start)?..lines.offset(end)?)
+    };
+    let mut order: Vec<usize> = (0..params.len()).filter(|i| *i != index).collect();
+    order.push(index);
+    let mut new_text = String::new();
+    for (position, param) in order.iter().enumerate() {
+        if position > 0 {
+            new_text.push_str(text(ranges[position - 1].end, ranges[position].start)?);
+        }
+        new_text.push_str(text(ranges[*param].start, ranges[*param].end)?);
+    }
+    QuickFix {
+        title: localized(locale, "E027.fix", &[&extractor]),
+        new_text,
+        range: Some(lsp_types::Range::new(ranges[0].start, ranges[last].end)),
+    }
+    .attach(&mut diagnostic);
+    Some(diagnostic)
+}
+
+/// 无法由 spring-job 提供的任务参数的类型文本，可以提供时返回 `None`
+fn unsupported_job_param(param: &syn::FnArg) -> Option<String> {
+    match param {
+        syn::FnArg::Receiver(receiver) => Some(receiver.to_token_stream().to_string()),
+        syn::FnArg::Typed(pat_type) => {
+            (!is_job_param_type(&pat_type.ty)).then(|| pat_type.ty.to_token_stream().to_string())
+        }
+    }
+}
+
+/// 类型是否可能由 spring-job 提供（未知的路径类型可能实现了 `FromApp`，视为可以提供）
+fn is_job_param_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => {
+            type_path.qself.is_none()
+                && type_path.path.segments.last().is_some_and(|segment| {
+                    let name = segment.ident.to_string();
+                    !WEB_EXTRACTORS.contains(&name.as_str())
+                        && !PLAIN_TYPES.contains(&name.as_str())
+                })
+        }
+        syn::Type::Paren(paren) => is_job_param_type(&paren.elem),
+        syn::Type::Group(group) => is_job_param_type(&group.elem),
+        // 宏展开的类型无法判断
+        syn::Type::Macro(_) | syn::Type::Verbatim(_) => true,
+        _ => false,
+    }
+}
+
+/// 检查任务函数的参数，每个无法提供的参数一条诊断
+fn job_param_diagnostics(
+    sig: &syn::Signature,
+    lines: &PositionMapper,
+    locale: Locale,
+) -> Vec<Diagnostic> {
+    let name = sig.ident.to_string();
+    sig.inputs
+        .iter()
+        .filter_map(|param| {
+            let ty = unsupported_job_param(param)?;
+            Some(with_docs(Diagnostic {
+                range: lines.span_range(param.span()),
+                severity: Some(DiagnosticSeverity::ERROR),
+                code: Some(NumberOrString::String(JOB_UNSUPPORTED_PARAM.to_string())),
+                source: Some("spring-lsp".to_string()),
+                message: localized(locale, JOB_UNSUPPORTED_PARAM, &[&name, &ty]),
+                ..Default::default()
+            }))
+        })
+        .collect()
+}
+
+/// 附带诊断代码在 `docs/rules.md` 中的文档链接
+fn with_docs(mut diagnostic: Diagnostic) -> Diagnostic {
+    let href = match &diagnostic.code {
+        Some(NumberOrString::String(code)) => {
+            find_rule(code).and_then(|rule| Url::parse(&rule.docs_url()).ok())
+        }
+        _ => None,
+    };
+    diagnostic.code_description = href.map(|href| CodeDescription { href });
+    diagnostic
+}
+
+/// 生成缺少 `async` 的诊断
+///
+/// 诊断范围是 `fn` 关键字，没有 `const`、`unsafe` 和 `extern` 时附带把 `fn` 替换为 `async fn` 的快速修复
+fn non_async_diagnostic(
+    sig: &syn::Signature,
+    lines: &PositionMapper,
+    code: &str,
+    message: String,
+    locale: Locale,
+) -> Diagnostic {
+    let mut diagnostic = Diagnostic {
+        range: lines.span_range(sig.fn_token.span()),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String(code.to_string())),
+        source: Some("spring-lsp".to_string()),
+        message,
+        ..Default::default()
+    };
+    if sig.unsafety.is_none() && sig.abi.is_none() && sig.constness.is_none() {
+        QuickFix {
+            title: localized(locale, "make-async.fix", &[]),
+            new_text: "async fn".to_string(),
+            range: None,
+        }
+        .attach(&mut diagnostic);
+    }
+    diagnostic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+#[get("/users")]
+fn list_users() -> Json<Vec<User>> {}
+
+#[cfg_attr(feature = "web", post("/users"))]
+async fn create_user() {}
+
+#[get("/health")]
+fn health() -> impl Future<Output = &'static str> {}
+
+#[cron("0 0 * * * *")]
+fn cleanup() {}
+
+#[fix_rate(60)]
+async fn refresh() -> Result<(), Error> {}
+
+#[one_shot(5)]
+async fn warmup() -> () {}
+
+#[fix_delay(10)]
+const fn ping() {}
+"#;
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics
+            .iter()
+            .filter_map(|d| match &d.code {
+                Some(NumberOrString::String(code)) => Some(code.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_handler_signatures() {
+        let diagnostics = HandlerSignatureValidator::new().validate(&PositionMapper::new(SOURCE));
+
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                NON_ASYNC_HANDLER,
+                NON_ASYNC_JOB,
+                JOB_RETURNS_VALUE,
+                NON_ASYNC_JOB
+            ]
+        );
+        assert!(diagnostics[0].message.contains("'list_users'"));
+        assert_eq!(diagnostics[0].range.start.line, 2);
+        assert_eq!(diagnostics[0].range.start.character, 0);
+        assert_eq!(diagnostics[0].range.end.character, 2);
+        let fix = QuickFix::from_diagnostic(&diagnostics[0]).unwrap();
+        assert_eq!(fix.new_text, "async fn");
+
+        assert!(diagnostics[1].message.contains("#[cron]"));
+        assert!(diagnostics[2].message.contains("'refresh'"));
+        // const fn 不能简单地加上 async，不提供快速修复
+        assert!(QuickFix::from_diagnostic(&diagnostics[3]).is_none());
+    }
+
+    #[test]
+    fn test_body_extractor_order() {
+        let source = r#"
+#[post("/users")]
+async fn create_user(
+    Json(user): Json<NewUser>,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) {}
+
+#[post("/upload")]
+async fn upload(Component(db): Component<Db>, body: Bytes) {}
+
+#[put("/users/:id")]
+async fn update_user(form: Option<Form<User>>, Json(user): Json<User>) {}
+
+#[get("/users")]
+async fn list_users(raw: String, Query(q): Query<Search>) {}
+"#;
+        let diagnostics = HandlerSignatureValidator::new().validate(&PositionMapper::new(source));
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                BODY_EXTRACTOR_NOT_LAST,
+                BODY_EXTRACTOR_NOT_LAST,
+                BODY_EXTRACTOR_NOT_LAST
+            ]
+        );
+
+        assert!(diagnostics[0].message.contains("Json"));
+        assert_eq!(diagnostics[0].range.start.line, 3);
+        let fix = QuickFix::from_diagnostic(&diagnostics[0]).unwrap();
+        assert_eq!(
+            fix.new_text,
+            "State(state): State<AppState>,\n    Path(id): Path<i64>,\n    Json(user): Json<NewUser>"
+        );
+        assert_eq!(fix.range.unwrap().start.line, 3);
+        assert_eq!(fix.range.unwrap().end.line, 5);
+
+        // 多个请求体提取器无法通过调整顺序修复
+        assert!(diagnostics[1].message.contains("2 个"));
+        assert!(QuickFix::from_diagnostic(&diagnostics[1]).is_none());
+
+        let fix = QuickFix::from_diagnostic(&diagnostics[2]).unwrap();
+        assert_eq!(fix.new_text, "Query(q): Query<Search>, raw: String");
+    }
+
+    #[test]
+    fn test_job_params() {
+        let source = r#"
+#[cron("0 0 * * * *")]
+async fn cleanup(
+    Component(db): Component<Db>,
+    Config(conf): Config<CleanupConfig>,
+    id: JobId,
+    scheduler: JobScheduler,
+    cache: Cache,
+) {}
+
+#[fix_rate(60)]
+async fn sync(Json(body): Json<Payload>, name: &str, count: u64, hooks: impl Hooks) {}
+
+#[get("/users")]
+async fn list_users(name: String) {}
+"#;
+        let diagnostics = HandlerSignatureValidator::new().validate(&PositionMapper::new(source));
+        assert_eq!(codes(&diagnostics), vec![JOB_UNSUPPORTED_PARAM; 4]);
+        assert!(diagnostics[0].message.contains("Json < Payload >"));
+        assert!(diagnostics[0].message.contains("'sync'"));
+        assert_eq!(diagnostics[0].range.start.line, 11);
+        assert!(diagnostics[1].message.contains("& str"));
+
+        let href = &diagnostics[0].code_description.as_ref().unwrap().href;
+        assert!(href.as_str().ends_with("docs/rules.md#e031"));
+    }
+
+    #[test]
+    fn test_job_diagnostics_link_docs() {
+        let diagnostics = HandlerSignatureValidator::new().validate(&PositionMapper::new(SOURCE));
+        for diagnostic in &diagnostics {
+            let is_job = matches!(
+                &diagnostic.code,
+                Some(NumberOrString::String(code)) if code == NON_ASYNC_JOB || code == JOB_RETURNS_VALUE
+            );
+            assert_eq!(diagnostic.code_description.is_some(), is_job);
+        }
+    }
+}
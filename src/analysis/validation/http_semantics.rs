@@ -0,0 +1,164 @@
+//! HTTP 方法语义检查模块
+//!
+//! 检查路由处理器的签名是否符合 HTTP 方法的语义约定，包括：
+//! - GET/HEAD 处理器不应读取请求体（`Json<T>` 提取器）
+//! - DELETE 处理器通常不需要返回实体内容（返回 `Json<T>`）
+//!
+//! 这些约定不是错误，诊断级别为 Information。所有诊断代码属于 `http-semantics`
+//! 规则组，可以在 `[diagnostics] disabled` 中按规则组整体禁用。
+
+use crate::analysis::rust::handler_analyzer::{
+    is_primitive_type, ExtractorKind, HandlerFile, HandlerInfo,
+};
+use crate::core::messages::{localized, Locale};
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+/// 规则组名称
+pub const HTTP_SEMANTICS_GROUP: &str = "http-semantics";
+
+/// GET/HEAD 处理器读取请求体
+pub const GET_WITH_BODY: &str = "http-semantics/get-with-body";
+
+/// DELETE 处理器返回实体内容
+pub const DELETE_WITH_BODY: &str = "http-semantics/delete-with-body";
+
+/// HTTP 方法语义验证器
+#[derive(Debug, Clone, Default)]
+pub struct HttpSemanticsValidator {
+    /// 消息语言
+    locale: Locale,
+}
+
+impl HttpSemanticsValidator {
+    /// 创建新的 HTTP 方法语义验证器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 验证文件中的所有路由处理器
+    pub fn validate(&self, file: &HandlerFile) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for handler in &file.handlers {
+            let methods = handler_methods(handler);
+
+            if let Some(method) = methods
+                .iter()
+                .find(|method| matches!(method.as_str(), "GET" | "HEAD"))
+            {
+                for param in handler
+                    .params
+                    .iter()
+                    .filter(|param| param.kind == ExtractorKind::Json)
+                {
+                    diagnostics.push(diagnostic(
+                        param.range,
+                        GET_WITH_BODY,
+                        localized(self.locale, GET_WITH_BODY, &[method, &param.type_name]),
+                    ));
+                }
+            }
+
+            if methods.iter().any(|method| method == "DELETE") {
+                let Some(return_type) = &handler.return_type else {
+                    continue;
+                };
+                let returns_entity = return_type.kind == ExtractorKind::Json
+                    && return_type
+                        .inner_type
+                        .as_deref()
+                        .is_some_and(|inner| inner != "()" && !is_primitive_type(inner));
+                if returns_entity {
+                    diagnostics.push(diagnostic(
+                        return_type.range,
+                        DELETE_WITH_BODY,
+                        localized(self.locale, DELETE_WITH_BODY, &[&return_type.type_name]),
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// 处理器绑定的所有 HTTP 方法
+fn handler_methods(handler: &HandlerInfo) -> Vec<String> {
+    let mut methods: Vec<_> = handler
+        .routes
+        .iter()
+        .flat_map(|route| route.methods.iter().cloned())
+        .collect();
+    methods.sort();
+    methods.dedup();
+    methods
+}
+
+fn diagnostic(range: lsp_types::Range, code: &str, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::INFORMATION),
+        code: Some(NumberOrString::String(code.to_string())),
+        source: Some("spring-lsp".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rust::handler_analyzer::HandlerAnalyzer;
+
+    const SOURCE: &str = r#"
+#[get("/users")]
+async fn search(Json(filter): Json<UserFilter>) -> Json<Vec<User>> {}
+
+#[post("/users")]
+async fn create(Json(user): Json<NewUser>) -> Json<User> {}
+
+#[delete("/users/{id}")]
+async fn delete(Path(id): Path<i64>) -> Result<Json<User>> {}
+
+#[delete("/sessions/{id}")]
+async fn logout(Path(id): Path<i64>) -> Json<bool> {}
+
+#[route("/items", method = "HEAD", method = "DELETE")]
+async fn items(Json(body): Json<Items>) -> StatusCode {}
+"#;
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics
+            .iter()
+            .filter_map(|d| match &d.code {
+                Some(NumberOrString::String(code)) => Some(code.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_http_semantics() {
+        let file = HandlerAnalyzer::new().analyze(SOURCE).unwrap();
+        let diagnostics = HttpSemanticsValidator::new().validate(&file);
+
+        assert_eq!(
+            codes(&diagnostics),
+            vec![GET_WITH_BODY, DELETE_WITH_BODY, GET_WITH_BODY]
+        );
+        assert!(diagnostics
+            .iter()
+            .all(|d| d.severity == Some(DiagnosticSeverity::INFORMATION)));
+        assert!(diagnostics[0].message.contains("Json<UserFilter>"));
+        assert_eq!(diagnostics[0].range.start.line, 2);
+        // Result<Json<T>> 被展开
+        assert!(diagnostics[1].message.contains("'Json<User>'"));
+        assert!(diagnostics[2].message.starts_with("HEAD"));
+    }
+}
@@ -0,0 +1,207 @@
+//! 定时任务项目级检查模块
+//!
+//! 汇总项目中所有定时任务（`#[cron]`、`#[fix_delay]`、`#[fix_rate]`、`#[one_shot]`）后检查：
+//! - 任务名称重复：spring-job 以函数名作为任务名称，`[job] disabled` 等配置按名称引用任务，
+//!   不同模块中的同名任务无法区分
+//! - 调度重叠：同名或定义在同一文件中的固定频率任务使用相同的极短间隔（不超过
+//!   [`SHORT_FIX_RATE_SECONDS`] 秒），它们会同时触发，通常应该合并或错开间隔
+
+use lsp_types::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, NumberOrString};
+use lsp_types::{Location, Url};
+
+use crate::core::messages::{localized, Locale};
+use crate::scanner::job::{JobInfoResponse, JobType};
+
+/// 任务名称在项目中重复定义
+pub const DUPLICATE_JOB_NAME: &str = "duplicate-job-name";
+
+/// 固定频率任务的极短间隔相互重叠
+pub const JOB_SCHEDULE_OVERLAP: &str = "job-schedule-overlap";
+
+/// 视为极短间隔的固定频率秒数上限
+pub const SHORT_FIX_RATE_SECONDS: u64 = 5;
+
+/// 定时任务验证器
+#[derive(Debug, Clone, Default)]
+pub struct JobValidator {
+    /// 消息语言
+    locale: Locale,
+}
+
+impl JobValidator {
+    /// 创建验证器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 验证项目中的所有任务，返回诊断及其所在文件的 URI
+    pub fn validate(&self, jobs: &[JobInfoResponse]) -> Vec<(Url, Diagnostic)> {
+        let jobs: Vec<_> = jobs
+            .iter()
+            .filter_map(|job| Some((job, job.lsp_location()?)))
+            .collect();
+
+        let mut diagnostics = Vec::new();
+        for (index, (job, location)) in jobs.iter().enumerate() {
+            let others = || {
+                jobs.iter()
+                    .enumerate()
+                    .filter(move |(other_index, _)| *other_index != index)
+                    .map(|(_, other)| other)
+            };
+
+            let duplicates: Vec<_> = others()
+                .filter(|(other, _)| other.name == job.name)
+                .map(|(_, other_location)| other_location)
+                .collect();
+            if !duplicates.is_empty() {
+                diagnostics.push((
+                    location.uri.clone(),
+                    diagnostic(
+                        location,
+                        DUPLICATE_JOB_NAME,
+                        localized(
+                            self.locale,
+                            DUPLICATE_JOB_NAME,
+                            &[&job.name, &(duplicates.len() + 1)],
+                        ),
+                        related(
+                            &duplicates,
+                            &localized(self.locale, "duplicate-job-name.related", &[]),
+                        ),
+                    ),
+                ));
+            }
+
+            let Some(seconds) = short_fix_rate(job) else {
+                continue;
+            };
+            let overlapping: Vec<_> = others()
+                .filter(|(other, other_location)| {
+                    short_fix_rate(other) == Some(seconds)
+                        && (other.name == job.name || other_location.uri == location.uri)
+                })
+                .collect();
+            if !overlapping.is_empty() {
+                let names: Vec<_> = overlapping
+                    .iter()
+                    .map(|(other, _)| format!("'{}'", other.name))
+                    .collect();
+                let locations: Vec<_> = overlapping.iter().map(|(_, location)| location).collect();
+                diagnostics.push((
+                    location.uri.clone(),
+                    diagnostic(
+                        location,
+                        JOB_SCHEDULE_OVERLAP,
+                        localized(
+                            self.locale,
+                            JOB_SCHEDULE_OVERLAP,
+                            &[
+                                &job.name,
+                                &names.join(&localized(self.locale, "list.separator", &[])),
+                                &seconds,
+                            ],
+                        ),
+                        related(
+                            &locations,
+                            &localized(self.locale, "job-schedule-overlap.related", &[]),
+                        ),
+                    ),
+                ));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// 极短间隔的固定频率任务的间隔秒数
+fn short_fix_rate(job: &JobInfoResponse) -> Option<u64> {
+    if job.job_type != JobType::FixRate {
+        return None;
+    }
+    job.seconds
+        .filter(|seconds| *seconds <= SHORT_FIX_RATE_SECONDS)
+}
+
+fn related(locations: &[&Location], message: &str) -> Vec<DiagnosticRelatedInformation> {
+    locations
+        .iter()
+        .map(|location| DiagnosticRelatedInformation {
+            location: (*location).clone(),
+            message: message.to_string(),
+        })
+        .collect()
+}
+
+fn diagnostic(
+    location: &Location,
+    code: &str,
+    message: String,
+    related_information: Vec<DiagnosticRelatedInformation>,
+) -> Diagnostic {
+    Diagnostic {
+        range: location.range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(code.to_string())),
+        source: Some("spring-lsp".to_string()),
+        message,
+        related_information: Some(related_information),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::job::JobScanner;
+
+    #[test]
+    fn test_validate_jobs() {
+        let jobs_rs = Url::parse("file:///app/src/jobs.rs").unwrap();
+        let billing_rs = Url::parse("file:///app/src/billing.rs").unwrap();
+        let files = vec![
+            (
+                jobs_rs.clone(),
+                "#[fix_rate(2)]\nasync fn poll_queue() {}\n\n#[fix_rate(2)]\nasync fn flush_metrics() {}\n\n#[fix_rate(60)]\nasync fn refresh() {}\n\n#[cron(\"0 0 * * * *\")]\nasync fn cleanup() {}\n".to_string(),
+            ),
+            (
+                billing_rs.clone(),
+                "#[fix_rate(2)]\nasync fn charge() {}\n\n#[fix_delay(10)]\nasync fn cleanup() {}\n".to_string(),
+            ),
+        ];
+        let jobs = JobScanner::new().scan_sources(&files);
+        let diagnostics = JobValidator::new().validate(&jobs);
+
+        let codes: Vec<_> = diagnostics
+            .iter()
+            .map(|(uri, diag)| {
+                let Some(NumberOrString::String(code)) = &diag.code else {
+                    panic!("missing code");
+                };
+                (uri.path(), diag.range.start.line, code.as_str())
+            })
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                ("/app/src/jobs.rs", 0, JOB_SCHEDULE_OVERLAP),
+                ("/app/src/jobs.rs", 3, JOB_SCHEDULE_OVERLAP),
+                ("/app/src/jobs.rs", 9, DUPLICATE_JOB_NAME),
+                ("/app/src/billing.rs", 3, DUPLICATE_JOB_NAME),
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].1.message,
+            "固定频率任务 'poll_queue' 与 'flush_metrics' 都每 2 秒执行一次，会同时触发，建议合并任务或错开间隔"
+        );
+        let related = diagnostics[2].1.related_information.as_ref().unwrap();
+        assert_eq!(related[0].location.uri, billing_rs);
+    }
+}
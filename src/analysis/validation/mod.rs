@@ -1,7 +1,36 @@
 //! 验证引擎模块
 //!
-//! 提供依赖注入等高级验证功能
+//! 提供依赖注入（含注册缺失组件的快速修复）、配置结构体必需字段、应用入口、处理器签名、HTTP 方法语义、处理器复杂度、处理器重名、路径段拼写一致性、路由安全、路由前缀约定、路由重复注册、定时任务、OpenAPI 规范对照等高级验证功能
 
+pub mod component_registration;
+pub mod config_fields;
+pub mod di_incremental;
+pub mod di_task;
 pub mod di_validator;
+pub mod entry_point;
+pub mod handler_complexity;
+pub mod handler_names;
+pub mod handler_signature;
+pub mod http_semantics;
+pub mod job_validator;
+pub mod openapi_spec;
+pub mod route_prefix;
+pub mod route_registration;
+pub mod route_security;
+pub mod segment_vocabulary;
 
+pub use config_fields::ConfigFieldValidator;
+pub use di_incremental::IncrementalDiValidator;
+pub use di_task::{CancellationToken, DiValidationTask};
 pub use di_validator::DependencyInjectionValidator;
+pub use entry_point::EntryPointValidator;
+pub use handler_complexity::HandlerComplexityValidator;
+pub use handler_names::HandlerNameValidator;
+pub use handler_signature::HandlerSignatureValidator;
+pub use http_semantics::HttpSemanticsValidator;
+pub use job_validator::JobValidator;
+pub use openapi_spec::OpenApiSpecValidator;
+pub use route_prefix::RoutePrefixValidator;
+pub use route_registration::RouteRegistrationValidator;
+pub use route_security::RouteSecurityValidator;
+pub use segment_vocabulary::{SegmentStats, SegmentVocabularyValidator};
@@ -0,0 +1,190 @@
+//! OpenAPI 规范对照模块
+//!
+//! 项目中存在 OpenAPI 规范文件（见 [`crate::analysis::openapi`]）时，将路由与规范中的操作互相对照：
+//! - 路由没有在规范中声明时，在路由路径上报告 `route-not-in-spec`
+//! - 规范中的操作没有对应的路由处理器时，在规范文件的方法键上报告 `spec-operation-without-handler`
+//!
+//! 路由与操作按 HTTP 方法和规范化后的路径（忽略参数名称）匹配。
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+use crate::analysis::openapi::OpenApiSpec;
+use crate::analysis::rust::handler_analyzer::HandlerFile;
+use crate::core::messages::{localized, Locale};
+use crate::scanner::route::RouteInfoResponse;
+
+/// 路由没有在 OpenAPI 规范中声明
+pub const ROUTE_NOT_IN_SPEC: &str = "route-not-in-spec";
+
+/// OpenAPI 规范中的操作没有对应的路由处理器
+pub const SPEC_OPERATION_WITHOUT_HANDLER: &str = "spec-operation-without-handler";
+
+/// OpenAPI 规范对照验证器
+#[derive(Debug, Clone, Copy)]
+pub struct OpenApiSpecValidator<'a> {
+    spec: &'a OpenApiSpec,
+    /// 规范文件名（用于诊断消息）
+    spec_name: &'a str,
+    /// 消息语言
+    locale: Locale,
+}
+
+impl<'a> OpenApiSpecValidator<'a> {
+    /// 创建新的 OpenAPI 规范对照验证器
+    pub fn new(spec: &'a OpenApiSpec, spec_name: &'a str) -> Self {
+        Self {
+            spec,
+            spec_name,
+            locale: Locale::default(),
+        }
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 检查文件中的路由是否都在规范中声明
+    pub fn validate_routes(&self, file: &HandlerFile) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for route in file
+            .handlers
+            .iter()
+            .flat_map(|handler| handler.routes.iter())
+        {
+            for method in &route.methods {
+                if self.spec.operation(method, &route.path).is_some() {
+                    continue;
+                }
+                diagnostics.push(Diagnostic {
+                    range: route.range,
+                    severity: Some(DiagnosticSeverity::WARNING),
+                    code: Some(NumberOrString::String(ROUTE_NOT_IN_SPEC.to_string())),
+                    source: Some("spring-lsp".to_string()),
+                    message: localized(
+                        self.locale,
+                        ROUTE_NOT_IN_SPEC,
+                        &[&method, &route.path, &self.spec_name],
+                    ),
+                    ..Default::default()
+                });
+            }
+        }
+
+        diagnostics
+    }
+
+    /// 检查规范中的操作是否都有对应的路由处理器
+    pub fn validate_spec(&self, routes: &[RouteInfoResponse]) -> Vec<Diagnostic> {
+        self.spec
+            .operations
+            .iter()
+            .filter(|operation| {
+                !routes
+                    .iter()
+                    .any(|route| operation.matches(&route.method, &route.path))
+            })
+            .map(|operation| Diagnostic {
+                range: operation.range,
+                severity: Some(DiagnosticSeverity::WARNING),
+                code: Some(NumberOrString::String(
+                    SPEC_OPERATION_WITHOUT_HANDLER.to_string(),
+                )),
+                source: Some("spring-lsp".to_string()),
+                message: match &operation.operation_id {
+                    Some(id) => localized(
+                        self.locale,
+                        "spec-operation-without-handler.with-id",
+                        &[&operation.method, &operation.path, id],
+                    ),
+                    None => localized(
+                        self.locale,
+                        SPEC_OPERATION_WITHOUT_HANDLER,
+                        &[&operation.method, &operation.path],
+                    ),
+                },
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rust::handler_analyzer::HandlerAnalyzer;
+    use crate::protocol::types::{LocationResponse, PositionResponse, RangeResponse};
+
+    const SPEC: &str = "paths:\n  /users:\n    get:\n      operationId: listUsers\n  /users/{userId}:\n    get:\n      operationId: getUser\n    delete:\n      operationId: deleteUser\n";
+
+    fn route(method: &str, path: &str) -> RouteInfoResponse {
+        let position = PositionResponse {
+            line: 0,
+            character: 0,
+        };
+        RouteInfoResponse {
+            method: method.to_string(),
+            path: path.to_string(),
+            handler: "handler".to_string(),
+            handler_path: None,
+            is_openapi: false,
+            doc: None,
+            deprecated: false,
+            stub: false,
+            cfg: None,
+            instrument: None,
+            confidence: crate::scanner::route::RouteConfidence::High,
+            location: LocationResponse {
+                uri: "file:///app/src/main.rs".to_string(),
+                range: RangeResponse {
+                    start: position.clone(),
+                    end: position,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_routes_missing_from_spec() {
+        let spec = OpenApiSpec::parse(SPEC);
+        let file = HandlerAnalyzer::new()
+            .analyze(
+                "#[get(\"/users/{id}\")]\nasync fn get_user() {}\n\n#[route(\"/users\", method = \"GET\", method = \"POST\")]\nasync fn users() {}\n",
+            )
+            .unwrap();
+
+        let diagnostics = OpenApiSpecValidator::new(&spec, "openapi.yaml").validate_routes(&file);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String(ROUTE_NOT_IN_SPEC.to_string()))
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "路由 POST /users 没有在 OpenAPI 规范 openapi.yaml 中声明"
+        );
+        assert_eq!(diagnostics[0].range.start.line, 3);
+    }
+
+    #[test]
+    fn test_spec_operations_without_handler() {
+        let spec = OpenApiSpec::parse(SPEC);
+        let routes = vec![route("GET", "/users"), route("GET", "/users/{id}")];
+
+        let diagnostics = OpenApiSpecValidator::new(&spec, "openapi.yaml").validate_spec(&routes);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String(
+                SPEC_OPERATION_WITHOUT_HANDLER.to_string()
+            ))
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "OpenAPI 操作 DELETE /users/{userId}（deleteUser）没有对应的路由处理器"
+        );
+        assert_eq!(diagnostics[0].range.start.line, 7);
+    }
+}
@@ -0,0 +1,171 @@
+//! 路由前缀约定检查模块
+//!
+//! 按 `.spring-lsp/rules.toml` 中的 `[[prefix]]` 约定（参见 [`crate::core::security`]）检查路由：
+//! 位于约定目录或模块文件中的路由必须以约定的前缀开头，否则报告 `route-prefix-convention` 警告。
+//!
+//! 多个约定匹配同一文件时使用路径最长（最具体）的约定，
+//! 例如 `src/handlers/admin/audit` 的约定优先于 `src/handlers/admin`。
+
+use std::path::{Path, PathBuf};
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Url};
+
+use crate::analysis::rust::handler_analyzer::HandlerFile;
+use crate::core::messages::{localized, Locale};
+use crate::core::security::{PrefixRule, SecurityRules};
+
+/// 路由不符合路由前缀约定
+pub const ROUTE_PREFIX_CONVENTION: &str = "route-prefix-convention";
+
+/// 路由前缀约定验证器
+#[derive(Debug, Clone)]
+pub struct RoutePrefixValidator<'a> {
+    rules: &'a SecurityRules,
+    /// 消息语言
+    locale: Locale,
+}
+
+impl<'a> RoutePrefixValidator<'a> {
+    /// 使用规则文件中的路由前缀约定创建验证器
+    pub fn new(rules: &'a SecurityRules) -> Self {
+        Self {
+            rules,
+            locale: Locale::default(),
+        }
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 适用于文件（相对于项目根目录）的约定
+    pub fn rule_for(&self, relative: &Path) -> Option<&'a PrefixRule> {
+        // `src/handlers/admin.rs` 与 `src/handlers/admin/` 中的文件都属于 `src/handlers/admin`
+        let module = relative.with_extension("");
+        self.rules
+            .prefixes
+            .iter()
+            .filter(|rule| module.starts_with(PathBuf::from(rule.path.trim_end_matches('/'))))
+            .max_by_key(|rule| Path::new(&rule.path).components().count())
+    }
+
+    /// 为文件中不符合约定的路由生成诊断（位于路由路径上）
+    ///
+    /// 文件不在项目根目录 `root` 下或没有适用的约定时返回空列表
+    pub fn validate(&self, root: &Path, uri: &Url, file: &HandlerFile) -> Vec<Diagnostic> {
+        let Ok(path) = uri.to_file_path() else {
+            return Vec::new();
+        };
+        let Some(rule) = path
+            .strip_prefix(root)
+            .ok()
+            .and_then(|relative| self.rule_for(relative))
+        else {
+            return Vec::new();
+        };
+
+        file.handlers
+            .iter()
+            .flat_map(|handler| handler.routes.iter())
+            .filter(|route| !has_prefix(&route.path, &rule.prefix))
+            .map(|route| Diagnostic {
+                range: route.range,
+                severity: Some(DiagnosticSeverity::WARNING),
+                code: Some(NumberOrString::String(ROUTE_PREFIX_CONVENTION.to_string())),
+                source: Some("spring-lsp".to_string()),
+                message: localized(
+                    self.locale,
+                    ROUTE_PREFIX_CONVENTION,
+                    &[&route.path, &rule.path, &rule.prefix],
+                ),
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
+/// 路径是否以前缀开头（按路径段比较，`/administration` 不以 `/admin` 开头）
+fn has_prefix(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rust::handler_analyzer::HandlerAnalyzer;
+
+    fn rules() -> SecurityRules {
+        SecurityRules {
+            prefixes: vec![
+                PrefixRule {
+                    path: "src/handlers/admin".to_string(),
+                    prefix: "/admin".to_string(),
+                },
+                PrefixRule {
+                    path: "src/handlers/admin/audit/".to_string(),
+                    prefix: "/admin/audit".to_string(),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_has_prefix() {
+        assert!(has_prefix("/admin", "/admin"));
+        assert!(has_prefix("/admin/users", "/admin/"));
+        assert!(has_prefix("/users", "/"));
+        assert!(!has_prefix("/administration", "/admin"));
+    }
+
+    #[test]
+    fn test_rule_for() {
+        let rules = rules();
+        let validator = RoutePrefixValidator::new(&rules);
+        let prefix = |path: &str| validator.rule_for(Path::new(path)).map(|rule| &rule.prefix);
+        assert_eq!(prefix("src/handlers/admin.rs").unwrap(), "/admin");
+        assert_eq!(prefix("src/handlers/admin/users.rs").unwrap(), "/admin");
+        assert_eq!(
+            prefix("src/handlers/admin/audit/mod.rs").unwrap(),
+            "/admin/audit"
+        );
+        assert!(prefix("src/handlers/administration.rs").is_none());
+        assert!(prefix("src/handlers/users.rs").is_none());
+    }
+
+    #[test]
+    fn test_route_prefix_violations() {
+        let content = r#"
+#[get("/admin/users")]
+async fn list_users() {}
+
+#[get("/users/{id}")]
+#[post("/admin/users/{id}")]
+async fn user() {}
+"#;
+        let rules = rules();
+        let validator = RoutePrefixValidator::new(&rules);
+        let file = HandlerAnalyzer::new().analyze(content).unwrap();
+        let root = std::env::temp_dir();
+        let uri = Url::from_file_path(root.join("src/handlers/admin/users.rs")).unwrap();
+
+        let diagnostics = validator.validate(&root, &uri, &file);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String(ROUTE_PREFIX_CONVENTION.to_string()))
+        );
+        assert_eq!(diagnostics[0].range.start.line, 4);
+        assert!(diagnostics[0].message.contains("/users/{id}"));
+
+        // 不在约定目录中
+        let uri = Url::from_file_path(root.join("src/handlers/users.rs")).unwrap();
+        assert!(validator.validate(&root, &uri, &file).is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+//! 路由重复注册检查模块
+//!
+//! 路由既可以通过路由宏声明，也可以通过 `Router::route` 注册（参见 [`crate::analysis::rust::router_calls`]）。
+//! 两种方式为同一个 HTTP 方法和路径注册了处理器时，axum 在构建路由表时会因路由重叠而 panic。
+//! 这里把当前文档的路由与项目中的路由合并比较，对宏声明与 `.route` 注册之间的重复报告
+//! `duplicate-route-registration` 警告，并列出另一处注册。
+//!
+//! 路径按形状比较（忽略参数名称、约束和末尾斜杠，见 [`PathPattern::same_shape`]）。
+//! `.route` 注册的路径可能被 `nest` 加上前缀，这类路由的可信度较低，因此只报告警告。
+
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString, Url,
+};
+
+use crate::analysis::validation::handler_names::{display_name, location_range};
+use crate::core::messages::{localized, Locale};
+use crate::scanner::route::{PathPattern, RouteInfoResponse};
+
+/// 同一个方法和路径同时通过路由宏和 `Router::route` 注册
+pub const DUPLICATE_ROUTE_REGISTRATION: &str = "duplicate-route-registration";
+
+/// 路由重复注册验证器
+#[derive(Debug, Clone, Default)]
+pub struct RouteRegistrationValidator {
+    /// 消息语言
+    locale: Locale,
+}
+
+impl RouteRegistrationValidator {
+    /// 创建验证器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 检查当前文档中的路由是否以另一种方式重复注册
+    ///
+    /// `document_routes` 为按编辑器中的内容扫描到的当前文档路由；
+    /// `routes` 为项目中的所有路由，其中当前文档的路由会被忽略
+    pub fn validate(
+        &self,
+        uri: &Url,
+        document_routes: &[RouteInfoResponse],
+        routes: &[RouteInfoResponse],
+    ) -> Vec<Diagnostic> {
+        let all: Vec<&RouteInfoResponse> = document_routes
+            .iter()
+            .chain(
+                routes
+                    .iter()
+                    .filter(|route| route.location.uri != uri.as_str()),
+            )
+            .collect();
+
+        let mut diagnostics = Vec::new();
+        for route in document_routes {
+            let pattern = PathPattern::parse(&route.path);
+            let others: Vec<&RouteInfoResponse> = all
+                .iter()
+                .copied()
+                .filter(|other| {
+                    other.confidence != route.confidence
+                        && other.method == route.method
+                        && PathPattern::parse(&other.path).same_shape(&pattern)
+                })
+                .collect();
+            if others.is_empty() {
+                continue;
+            }
+
+            let names: Vec<String> = others.iter().map(|other| display_name(other)).collect();
+            diagnostics.push(Diagnostic {
+                range: location_range(route),
+                severity: Some(DiagnosticSeverity::WARNING),
+                code: Some(NumberOrString::String(
+                    DUPLICATE_ROUTE_REGISTRATION.to_string(),
+                )),
+                source: Some("spring-lsp".to_string()),
+                message: localized(
+                    self.locale,
+                    DUPLICATE_ROUTE_REGISTRATION,
+                    &[
+                        &route.method,
+                        &route.path,
+                        &names.join(&localized(self.locale, "list.separator", &[])),
+                    ],
+                ),
+                related_information: Some(
+                    others
+                        .iter()
+                        .zip(&names)
+                        .filter_map(|(other, name)| {
+                            Some(DiagnosticRelatedInformation {
+                                location: Location::new(
+                                    Url::parse(&other.location.uri).ok()?,
+                                    location_range(other),
+                                ),
+                                message: name.clone(),
+                            })
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            });
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::route::RouteScanner;
+
+    const HANDLERS: &str = r#"#[get("/users/{id}")]
+async fn show() {}
+
+#[post("/users")]
+async fn create() {}
+"#;
+
+    const ROUTER: &str = r#"pub fn router() -> Router {
+    Router::new()
+        .route("/users/{user_id}/", get(users::show))
+        .route("/users", get(users::list))
+}
+"#;
+
+    fn scan(path: &str, content: &str) -> Vec<RouteInfoResponse> {
+        RouteScanner::new().scan_file(std::path::Path::new(path), content.to_string())
+    }
+
+    #[test]
+    fn test_duplicate_route_registration() {
+        let uri = Url::parse("file:///app/src/users.rs").unwrap();
+        let document = scan("/app/src/users.rs", HANDLERS);
+        let project = scan("/app/src/router.rs", ROUTER);
+
+        let diagnostics = RouteRegistrationValidator::new().validate(&uri, &document, &project);
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.range.start.line, 1);
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::WARNING));
+        assert!(diagnostic.message.contains("GET /users/{id}"));
+        assert!(diagnostic.message.contains("crate::router::users::show"));
+        let related = diagnostic.related_information.as_ref().unwrap();
+        assert_eq!(related[0].location.uri.path(), "/app/src/router.rs");
+        assert_eq!(related[0].location.range.start.line, 2);
+
+        // 从注册路由的文件看也是重复注册
+        let router_uri = Url::parse("file:///app/src/router.rs").unwrap();
+        let diagnostics =
+            RouteRegistrationValidator::new().validate(&router_uri, &project, &document);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start.line, 2);
+    }
+
+    #[test]
+    fn test_same_style_is_not_reported() {
+        let uri = Url::parse("file:///app/src/users.rs").unwrap();
+        let document = scan("/app/src/users.rs", HANDLERS);
+        // 项目路由中当前文档的旧内容被忽略，同为宏声明的路由不在这里检查
+        let project = scan("/app/src/users.rs", HANDLERS);
+        assert!(RouteRegistrationValidator::new()
+            .validate(&uri, &document, &project)
+            .is_empty());
+
+        // 同一文件中混用两种方式
+        let mixed = format!("{}\n{}", HANDLERS, ROUTER);
+        let document = scan("/app/src/users.rs", &mixed);
+        let diagnostics = RouteRegistrationValidator::new().validate(&uri, &document, &[]);
+        assert_eq!(diagnostics.len(), 2);
+    }
+}
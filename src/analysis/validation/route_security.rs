@@ -0,0 +1,174 @@
+//! 路由安全检查模块
+//!
+//! 按 `.spring-lsp/rules.toml` 中的安全规则（参见 [`crate::core::security`]）检查路由：
+//! 路径匹配规则的路由必须应用规则中的某个中间件，或者在处理器参数中使用某个 guard 提取器，
+//! 否则报告 `unprotected-route` 警告。
+//!
+//! 中间件链包括路由器级、模块级和路由级中间件，参见 [`crate::scanner::middleware`]。
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+
+use crate::core::messages::{localized, Locale};
+use crate::core::security::{SecurityRule, SecurityRules};
+use crate::scanner::middleware::{path_matches, RouteMiddlewares};
+
+/// 路由匹配安全规则但没有受到保护
+pub const UNPROTECTED_ROUTE: &str = "unprotected-route";
+
+/// 路由安全验证器
+#[derive(Debug, Clone)]
+pub struct RouteSecurityValidator<'a> {
+    rules: &'a SecurityRules,
+    /// 消息语言
+    locale: Locale,
+}
+
+impl<'a> RouteSecurityValidator<'a> {
+    /// 使用安全规则创建验证器
+    pub fn new(rules: &'a SecurityRules) -> Self {
+        Self {
+            rules,
+            locale: Locale::default(),
+        }
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 路由没有满足的安全规则
+    pub fn unsatisfied(&self, route: &RouteMiddlewares) -> Vec<&'a SecurityRule> {
+        self.rules
+            .security
+            .iter()
+            .filter(|rule| path_matches(&rule.pattern, &route.path))
+            .filter(|rule| !is_protected(rule, route))
+            .collect()
+    }
+
+    /// 为没有受到保护的路由生成诊断（位于处理器函数名上）
+    pub fn validate(&self, route: &RouteMiddlewares) -> Vec<Diagnostic> {
+        let range = &route.location.range;
+        let range = Range {
+            start: Position::new(range.start.line, range.start.character),
+            end: Position::new(range.end.line, range.end.character),
+        };
+
+        self.unsatisfied(route)
+            .into_iter()
+            .map(|rule| Diagnostic {
+                range,
+                severity: Some(DiagnosticSeverity::WARNING),
+                code: Some(NumberOrString::String(UNPROTECTED_ROUTE.to_string())),
+                source: Some("spring-lsp".to_string()),
+                message: localized(
+                    self.locale,
+                    UNPROTECTED_ROUTE,
+                    &[&route.path, &rule.pattern, &requirement(rule, self.locale)],
+                ),
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
+/// 路由是否满足规则：应用了规则中的中间件，或使用了规则中的 guard 提取器
+fn is_protected(rule: &SecurityRule, route: &RouteMiddlewares) -> bool {
+    rule.middleware.iter().any(|required| {
+        route
+            .middlewares
+            .iter()
+            .any(|middleware| middleware.name.contains(required.as_str()))
+    }) || rule.guards.iter().any(|guard| {
+        route
+            .extractors
+            .iter()
+            .any(|extractor| contains_type_name(extractor, guard))
+    })
+}
+
+/// 类型字符串中是否包含完整的类型名（`User` 不匹配 `UserQuery`）
+fn contains_type_name(ty: &str, name: &str) -> bool {
+    ty.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .any(|segment| segment == name)
+}
+
+/// 规则要求的描述
+fn requirement(rule: &SecurityRule, locale: Locale) -> String {
+    let mut parts = Vec::new();
+    if !rule.middleware.is_empty() {
+        parts.push(localized(
+            locale,
+            "unprotected-route.middleware",
+            &[&rule.middleware.join(" / ")],
+        ));
+    }
+    if !rule.guards.is_empty() {
+        parts.push(localized(
+            locale,
+            "unprotected-route.guard",
+            &[&rule.guards.join(" / ")],
+        ));
+    }
+    parts.join(&localized(locale, "unprotected-route.or", &[]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::middleware::MiddlewareScanner;
+    use lsp_types::Url;
+
+    #[test]
+    fn test_unprotected_routes() {
+        let rules = SecurityRules {
+            security: vec![SecurityRule {
+                pattern: "/admin/**".to_string(),
+                middleware: vec!["AuthLayer".to_string()],
+                guards: vec!["AdminUser".to_string()],
+            }],
+            ..Default::default()
+        };
+        let content = r#"
+#[get("/admin/users")]
+async fn list_users() {}
+
+#[get("/admin/stats")]
+#[middlewares(AuthLayer::new())]
+async fn stats() {}
+
+#[post("/admin/users")]
+async fn create_user(admin: AdminUser, Json(body): Json<CreateUser>) {}
+
+#[delete("/admin/users/{id}")]
+async fn delete_user(Query(q): Query<AdminUserQuery>) {}
+
+#[get("/public")]
+async fn public() {}
+"#;
+        let uri = Url::parse("file:///src/admin.rs").unwrap();
+        let file = MiddlewareScanner::new()
+            .analyze_file(&uri, content)
+            .unwrap();
+        let validator = RouteSecurityValidator::new(&rules);
+
+        let unprotected: Vec<_> = file
+            .routes
+            .iter()
+            .filter(|route| !validator.validate(route).is_empty())
+            .map(|route| route.handler.as_str())
+            .collect();
+        assert_eq!(unprotected, vec!["list_users", "delete_user"]);
+
+        let diagnostics = validator.validate(&file.routes[0]);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String(UNPROTECTED_ROUTE.to_string()))
+        );
+        assert!(diagnostics[0].message.contains("AuthLayer"));
+        assert!(diagnostics[0].message.contains("AdminUser"));
+        assert_eq!(diagnostics[0].range.start.line, 2);
+    }
+}
@@ -0,0 +1,277 @@
+//! 路径段拼写一致性检查模块
+//!
+//! 统计项目中所有路由路径的字面量路径段，找出与更常用的路径段拼写相近的路径段，
+//! 例如 `/user` 与 `/users`、`/organisations` 与 `/organizations`、`/user_profile` 与 `/user-profile`。
+//!
+//! 两个路径段在以下规范化之后相同时视为拼写相近：
+//! - 单复数（`user`/`users`、`category`/`categories`、`box`/`boxes`）
+//! - 英式/美式拼写（`-isation`/`-ization`、`-ise`/`-ize`、`-our`/`-or`、`-tre`/`-ter`、`-lled`/`-led`）
+//! - 单词分隔符（`_` 和 `-`）
+//!
+//! 只有大小写不同的路径段由路由风格检查（W006）报告，这里不重复报告。
+//! 只有另一种拼写出现得更多时才报告，并建议改为项目中占多数的拼写。
+//! 这个检查默认关闭，通过 `[routes] segment_spelling = true` 启用。
+
+use std::collections::BTreeMap;
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+use crate::analysis::diagnostic::QuickFix;
+use crate::analysis::rust::handler_analyzer::HandlerFile;
+use crate::analysis::rust::route_path::{parse_route_path, PathSegment};
+use crate::core::messages::{localized, Locale};
+
+/// 路径段与项目中更常用的路径段拼写相近
+pub const PATH_SEGMENT_SPELLING: &str = "path-segment-spelling";
+
+/// 路径段统计索引
+///
+/// 记录每个字面量路径段在路由路径中出现的次数
+#[derive(Debug, Clone, Default)]
+pub struct SegmentStats {
+    counts: BTreeMap<String, usize>,
+}
+
+impl SegmentStats {
+    /// 创建空的统计索引
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 从路由路径列表构建统计索引
+    pub fn from_paths<'a>(paths: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut stats = Self::new();
+        for path in paths {
+            stats.add_path(path);
+        }
+        stats
+    }
+
+    /// 统计一条路由路径中的字面量路径段
+    pub fn add_path(&mut self, path: &str) {
+        for segment in literal_segments(path) {
+            *self.counts.entry(segment).or_default() += 1;
+        }
+    }
+
+    /// 路径段出现的次数
+    pub fn count(&self, segment: &str) -> usize {
+        self.counts.get(segment).copied().unwrap_or_default()
+    }
+
+    /// 与路径段拼写相近且出现次数更多的路径段（出现次数最多的一个）
+    pub fn dominant_spelling(&self, segment: &str) -> Option<(&str, usize)> {
+        let count = self.count(segment);
+        let normalized = normalize(segment);
+
+        let mut dominant: Option<(&str, usize)> = None;
+        for (other, &other_count) in &self.counts {
+            if other_count <= count
+                || other.eq_ignore_ascii_case(segment)
+                || normalize(other) != normalized
+            {
+                continue;
+            }
+            if dominant.is_none_or(|(_, best)| other_count > best) {
+                dominant = Some((other, other_count));
+            }
+        }
+        dominant
+    }
+}
+
+/// 路径段拼写一致性验证器
+#[derive(Debug, Clone, Default)]
+pub struct SegmentVocabularyValidator {
+    /// 消息语言
+    locale: Locale,
+}
+
+impl SegmentVocabularyValidator {
+    /// 创建新的路径段拼写一致性验证器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的语言生成诊断消息
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 按项目的路径段统计验证文件中的路由，统计中应包含这个文件的路由
+    pub fn validate(&self, file: &HandlerFile, stats: &SegmentStats) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for route in file
+            .handlers
+            .iter()
+            .flat_map(|handler| handler.routes.iter())
+        {
+            for segment in literal_segments(&route.path) {
+                let Some((dominant, dominant_count)) = stats.dominant_spelling(&segment) else {
+                    continue;
+                };
+
+                let fixed = replace_segment(&route.path, &segment, dominant);
+                let mut diagnostic = Diagnostic {
+                    range: route.range,
+                    severity: Some(DiagnosticSeverity::WARNING),
+                    code: Some(NumberOrString::String(PATH_SEGMENT_SPELLING.to_string())),
+                    source: Some("spring-lsp".to_string()),
+                    message: localized(
+                        self.locale,
+                        PATH_SEGMENT_SPELLING,
+                        &[&segment, &dominant, &dominant_count, &stats.count(&segment)],
+                    ),
+                    ..Default::default()
+                };
+                QuickFix {
+                    title: localized(self.locale, "route-style.fix", &[&fixed]),
+                    new_text: format!("{:?}", fixed),
+                    range: None,
+                }
+                .attach(&mut diagnostic);
+                diagnostics.push(diagnostic);
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// 路径中的字面量路径段
+fn literal_segments(path: &str) -> Vec<String> {
+    let (route_path, _) = parse_route_path(path);
+    route_path
+        .segments
+        .into_iter()
+        .filter_map(|segment| match segment {
+            PathSegment::Literal(literal) => Some(literal),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 把路径中等于 `from` 的路径段替换为 `to`
+fn replace_segment(path: &str, from: &str, to: &str) -> String {
+    path.split('/')
+        .map(|segment| if segment == from { to } else { segment })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// 规范化路径段：统一大小写和分隔符，去掉复数和英式拼写差异
+fn normalize(segment: &str) -> String {
+    segment
+        .to_ascii_lowercase()
+        .split(['-', '_'])
+        .map(|word| american_spelling(&singular(word)))
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// 单数形式
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if ["sses", "xes", "ches", "shes"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        word[..word.len() - 2].to_string()
+    } else if word.len() > 1 && word.ends_with('s') && !word.ends_with("ss") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/// 美式拼写
+fn american_spelling(word: &str) -> String {
+    let word = word.replace("isation", "ization");
+    for (british, american) in [
+        ("ised", "ized"),
+        ("ising", "izing"),
+        ("ise", "ize"),
+        ("lled", "led"),
+        ("lling", "ling"),
+        ("our", "or"),
+        ("tre", "ter"),
+        ("ogue", "og"),
+    ] {
+        if let Some(stem) = word.strip_suffix(british) {
+            if stem.len() > 1 {
+                return format!("{}{}", stem, american);
+            }
+        }
+    }
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rust::handler_analyzer::HandlerAnalyzer;
+
+    #[test]
+    fn test_dominant_spelling() {
+        let stats = SegmentStats::from_paths([
+            "/users",
+            "/users/{id}",
+            "/user/{id}/orders",
+            "/organizations/{id}",
+            "/organizations",
+            "/organisations",
+            "/user-profile",
+            "/user_profile/avatar",
+            "/posts",
+            "/hosts",
+        ]);
+
+        assert_eq!(stats.dominant_spelling("user"), Some(("users", 2)));
+        assert_eq!(stats.dominant_spelling("users"), None);
+        assert_eq!(
+            stats.dominant_spelling("organisations"),
+            Some(("organizations", 2))
+        );
+        // 出现次数相同时无法判断哪种拼写占多数
+        assert_eq!(stats.dominant_spelling("user-profile"), None);
+        assert_eq!(stats.dominant_spelling("posts"), None);
+
+        assert_eq!(normalize("categories"), normalize("category"));
+        assert_eq!(normalize("colours"), normalize("color"));
+        assert_ne!(normalize("orders"), normalize("order-items"));
+    }
+
+    #[test]
+    fn test_validate_segment_spelling() {
+        let content = r#"
+#[get("/users")]
+async fn list() {}
+
+#[post("/users")]
+async fn create() {}
+
+#[get("/user/{id}")]
+async fn get() {}
+"#;
+        let file = HandlerAnalyzer::new().analyze(content).unwrap();
+        let stats = SegmentStats::from_paths(
+            file.handlers
+                .iter()
+                .flat_map(|handler| handler.routes.iter())
+                .map(|route| route.path.as_str()),
+        );
+
+        let diagnostics = SegmentVocabularyValidator::new().validate(&file, &stats);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String(PATH_SEGMENT_SPELLING.to_string()))
+        );
+        assert_eq!(diagnostics[0].range.start.line, 7);
+        let fix = QuickFix::from_diagnostic(&diagnostics[0]).unwrap();
+        assert_eq!(fix.new_text, "\"/users/{id}\"");
+    }
+}
@@ -0,0 +1,167 @@
+//! 嵌入式分析 API
+//!
+//! 不建立 LSP 连接，直接在其他工具（代码生成器、文档生成器等）中复用 spring-lsp 的扫描器和验证器：
+//!
+//! ```no_run
+//! use spring_lsp::api::ProjectAnalyzer;
+//!
+//! let project = ProjectAnalyzer::load_workspace("path/to/app")?;
+//! for route in project.routes() {
+//!     println!("{} {} -> {}", route.method, route.path, route.handler);
+//! }
+//! for component in project.components() {
+//!     println!("{}: {:?}", component.name, component.dependencies);
+//! }
+//! let report = project.diagnostics();
+//! println!("{} error(s), {} warning(s)", report.errors, report.warnings);
+//! # Ok::<(), spring_lsp::Error>(())
+//! ```
+//!
+//! 这里导出的类型和方法保持稳定，内部模块的重构不需要调用方跟着修改。
+//! 工作空间可以是单个 crate，也可以包含多个 crate（每个含 `[package]` 的 `Cargo.toml` 视为一个 crate）。
+
+use std::path::{Path, PathBuf};
+
+use crate::analysis::validation::di_task::CrateSources;
+use crate::core::check::ProjectChecker;
+use crate::core::config::ServerConfig;
+use crate::core::schema::SchemaProvider;
+use crate::scanner::component::ComponentScanner;
+use crate::scanner::generated::GeneratedCodeCache;
+use crate::scanner::route::RouteScanner;
+use crate::{Error, Result};
+
+pub use crate::core::check::{CheckReport, FileDiagnostics};
+pub use crate::scanner::component::ComponentInfoResponse as ComponentInfo;
+pub use crate::scanner::route::RouteInfoResponse as RouteInfo;
+
+/// 项目分析器
+///
+/// 加载时扫描工作空间中所有 crate 的路由和组件，诊断在调用 [`ProjectAnalyzer::diagnostics`] 时计算
+pub struct ProjectAnalyzer {
+    root: PathBuf,
+    config: ServerConfig,
+    schema_provider: SchemaProvider,
+    routes: Vec<RouteInfo>,
+    components: Vec<ComponentInfo>,
+}
+
+impl ProjectAnalyzer {
+    /// 加载工作空间
+    ///
+    /// 使用工作空间的 `.spring-lsp.toml`（和用户配置）以及内置的插件 Schema，不访问网络
+    pub fn load_workspace(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref();
+        Self::load_workspace_with(
+            root,
+            ServerConfig::load(Some(root)),
+            SchemaProvider::default(),
+        )
+    }
+
+    /// 使用指定的配置和 Schema 加载工作空间
+    pub fn load_workspace_with(
+        root: impl AsRef<Path>,
+        config: ServerConfig,
+        schema_provider: SchemaProvider,
+    ) -> Result<Self> {
+        let root = root.as_ref();
+        if !root.is_dir() {
+            return Err(Error::Config(format!(
+                "Not a directory: {}",
+                root.display()
+            )));
+        }
+        config.validate().map_err(Error::Config)?;
+
+        // 文件 URI 需要绝对路径
+        let root = root.canonicalize()?;
+        let generated_code = GeneratedCodeCache::new();
+        let route_scanner = RouteScanner::new();
+        let component_scanner = ComponentScanner::new();
+        let mut routes = Vec::new();
+        let mut components = Vec::new();
+
+        for sources in CrateSources::discover(&root) {
+            match route_scanner.scan_routes(&sources.root) {
+                Ok(crate_routes) => routes.extend(crate_routes),
+                Err(e) => tracing::warn!("Failed to scan routes in {}: {}", sources.name, e),
+            }
+            routes.extend(generated_code.routes(&sources.root, &config.index.generated_dirs));
+
+            match component_scanner.scan_components(&sources.root) {
+                Ok(crate_components) => components.extend(crate_components),
+                Err(e) => tracing::warn!("Failed to scan components in {}: {}", sources.name, e),
+            }
+            components
+                .extend(generated_code.components(&sources.root, &config.index.generated_dirs));
+        }
+
+        Ok(Self {
+            root,
+            config,
+            schema_provider,
+            routes,
+            components,
+        })
+    }
+
+    /// 工作空间根目录（绝对路径）
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 所有路由
+    pub fn routes(&self) -> &[RouteInfo] {
+        &self.routes
+    }
+
+    /// 所有组件（`#[derive(Service)]` 和 `#[component]`）
+    pub fn components(&self) -> &[ComponentInfo] {
+        &self.components
+    }
+
+    /// 检查整个工作空间，与 `spring-lsp check` 的结果相同（应用项目的诊断基线）
+    pub fn diagnostics(&self) -> CheckReport {
+        ProjectChecker::new(self.schema_provider.clone(), self.config.clone()).check(&self.root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_load_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("config")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/main.rs"),
+            "#[get(\"/users\")]\nasync fn list() {}\n\n#[derive(Clone, Service)]\nstruct UserService {\n    #[inject(component)]\n    db: DbConn,\n}\n",
+        )
+        .unwrap();
+        fs::write(root.join("config/app.toml"), "[web]\nport = 99999\n").unwrap();
+
+        let project = ProjectAnalyzer::load_workspace_with(
+            root,
+            ServerConfig::default(),
+            SchemaProvider::default(),
+        )
+        .unwrap();
+        assert_eq!(project.routes().len(), 1);
+        assert_eq!(project.routes()[0].path, "/users");
+        assert_eq!(project.components().len(), 1);
+        assert_eq!(project.components()[0].name, "UserService");
+        assert!(project.diagnostics().errors > 0);
+
+        assert!(ProjectAnalyzer::load_workspace(root.join("missing")).is_err());
+    }
+}
@@ -0,0 +1,337 @@
+//! 诊断基线模块
+//!
+//! 在已有项目中引入 spring-lsp 时，可以把当前的所有诊断记录到
+//! `.spring-lsp/baseline.json`，之后编辑器和 `spring-lsp check` 只报告新出现的问题。
+//!
+//! 基线条目按文件路径（相对于项目根目录）、诊断代码和消息匹配，不记录行号，
+//! 因此在文件中插入或删除代码不会让已记录的诊断重新出现。消息按默认语言（简体中文）记录和比较
+//! （见 [`canonical_message`]），编辑器使用其他语言时已记录的诊断同样被过滤。同一文件中相同的诊断
+//! 出现多次时按次数匹配，超出基线次数的部分仍然会被报告。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use lsp_types::{Diagnostic, NumberOrString, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::core::check::CheckReport;
+use crate::core::messages::canonical_message;
+use crate::{Error, Result};
+
+/// 基线文件相对于项目根目录的路径
+pub const BASELINE_FILE: &str = ".spring-lsp/baseline.json";
+
+/// 当前的基线文件格式版本
+const BASELINE_VERSION: u32 = 1;
+
+/// 基线中记录的一个诊断
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// 文件路径（相对于项目根目录，使用 `/` 分隔）
+    pub path: String,
+    /// 诊断代码
+    pub code: String,
+    /// 诊断消息（默认语言）
+    pub message: String,
+}
+
+/// 诊断基线
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// 文件格式版本
+    pub version: u32,
+    /// 已记录的诊断（按路径、代码、消息排序）
+    pub entries: Vec<BaselineEntry>,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl Baseline {
+    /// 基线文件的完整路径
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(BASELINE_FILE)
+    }
+
+    /// 从项目根目录加载基线
+    ///
+    /// 基线文件不存在时返回 `None`
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let path = Self::path(root);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let baseline: Baseline = serde_json::from_str(&content).map_err(|e| {
+            Error::Config(format!("Invalid baseline file {}: {}", path.display(), e))
+        })?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(Error::Config(format!(
+                "Unsupported baseline version {} in {}",
+                baseline.version,
+                path.display()
+            )));
+        }
+
+        Ok(Some(baseline))
+    }
+
+    /// 写入项目根目录下的基线文件，返回文件路径
+    pub fn save(&self, root: &Path) -> Result<PathBuf> {
+        let path = Self::path(root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        std::fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    /// 从检查报告生成基线
+    pub fn from_report(report: &CheckReport) -> Self {
+        let mut entries: Vec<_> = report
+            .files
+            .iter()
+            .flat_map(|file| {
+                let path = normalize_path(&file.path);
+                file.diagnostics
+                    .iter()
+                    .map(move |diagnostic| entry(&path, diagnostic))
+            })
+            .collect();
+        entries.sort_by(|a, b| (&a.path, &a.code, &a.message).cmp(&(&b.path, &b.code, &b.message)));
+
+        Self {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    /// 基线中的诊断数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 基线是否为空
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 过滤掉基线中已记录的诊断，返回新出现的诊断
+    ///
+    /// `path` 是文件相对于项目根目录的路径
+    pub fn filter(&self, path: &str, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        let path = normalize_path(path);
+        let mut remaining: HashMap<(String, String), usize> = HashMap::new();
+        for entry in self.entries.iter().filter(|entry| entry.path == path) {
+            *remaining
+                .entry((
+                    entry.code.clone(),
+                    canonical_message(&entry.code, &entry.message),
+                ))
+                .or_default() += 1;
+        }
+        if remaining.is_empty() {
+            return diagnostics;
+        }
+
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                let code = code_of(diagnostic);
+                let message = canonical_message(&code, &diagnostic.message);
+                let key = (code, message);
+                match remaining.get_mut(&key) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                }
+            })
+            .collect()
+    }
+
+    /// 过滤文件 URI 对应的诊断
+    ///
+    /// URI 不在项目根目录下时原样返回
+    pub fn filter_uri(
+        &self,
+        root: &Path,
+        uri: &Url,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Vec<Diagnostic> {
+        match relative_path(root, uri) {
+            Some(path) => self.filter(&path, diagnostics),
+            None => diagnostics,
+        }
+    }
+
+    /// 过滤检查报告，并重新统计错误和警告数
+    pub fn apply(&self, report: CheckReport) -> CheckReport {
+        let mut filtered = CheckReport::default();
+        for mut file in report.files {
+            file.diagnostics = self.filter(&file.path, file.diagnostics);
+            filtered.add_file(file);
+        }
+        filtered
+    }
+}
+
+/// 文件 URI 相对于项目根目录的路径（使用 `/` 分隔）
+pub fn relative_path(root: &Path, uri: &Url) -> Option<String> {
+    let path = uri.to_file_path().ok()?;
+    let relative = path.strip_prefix(root).ok()?;
+    Some(normalize_path(&relative.to_string_lossy()))
+}
+
+/// 统一使用 `/` 作为路径分隔符，使基线文件可以在不同平台之间共享
+fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+/// 诊断代码的字符串形式
+fn code_of(diagnostic: &Diagnostic) -> String {
+    match &diagnostic.code {
+        Some(NumberOrString::String(code)) => code.clone(),
+        Some(NumberOrString::Number(code)) => code.to_string(),
+        None => String::new(),
+    }
+}
+
+fn entry(path: &str, diagnostic: &Diagnostic) -> BaselineEntry {
+    let code = code_of(diagnostic);
+    BaselineEntry {
+        path: path.to_string(),
+        message: canonical_message(&code, &diagnostic.message),
+        code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::check::FileDiagnostics;
+    use lsp_types::{DiagnosticSeverity, Position, Range};
+
+    fn diagnostic(code: &str, message: &str, line: u32) -> Diagnostic {
+        Diagnostic {
+            range: Range {
+                start: Position::new(line, 0),
+                end: Position::new(line, 5),
+            },
+            severity: Some(DiagnosticSeverity::ERROR),
+            code: Some(NumberOrString::String(code.to_string())),
+            source: Some("spring-lsp".to_string()),
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn report(diagnostics: Vec<Diagnostic>) -> CheckReport {
+        let mut report = CheckReport::default();
+        report.add_file(FileDiagnostics {
+            uri: Url::parse("file:///project/src/main.rs").unwrap(),
+            path: "src\\main.rs".to_string(),
+            diagnostics,
+        });
+        report
+    }
+
+    #[test]
+    fn test_filter_ignores_line_changes_and_counts_duplicates() {
+        let baseline = Baseline::from_report(&report(vec![
+            diagnostic("E018", "query", 1),
+            diagnostic("E018", "query", 4),
+        ]));
+        assert_eq!(baseline.len(), 2);
+        assert_eq!(baseline.entries[0].path, "src/main.rs");
+
+        // 行号变化后仍然匹配，超出基线次数的诊断和新诊断会被报告
+        let remaining = baseline.filter(
+            "src/main.rs",
+            vec![
+                diagnostic("E018", "query", 10),
+                diagnostic("E018", "query", 12),
+                diagnostic("E018", "query", 14),
+                diagnostic("E004", "empty path", 20),
+            ],
+        );
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining[0].range.start.line, 14);
+        assert_eq!(remaining[1].message, "empty path");
+
+        // 其他文件不受影响
+        assert_eq!(
+            baseline
+                .filter("src/lib.rs", vec![diagnostic("E018", "query", 1)])
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_apply_recounts_report() {
+        let baseline = Baseline::from_report(&report(vec![diagnostic("E018", "query", 1)]));
+        let filtered = baseline.apply(report(vec![
+            diagnostic("E018", "query", 1),
+            diagnostic("E004", "empty path", 2),
+        ]));
+        assert_eq!(filtered.errors, 1);
+        assert_eq!(filtered.files[0].diagnostics.len(), 1);
+
+        let filtered = baseline.apply(report(vec![diagnostic("E018", "query", 3)]));
+        assert!(filtered.files.is_empty());
+        assert_eq!(filtered.exit_code(), 0);
+    }
+
+    #[test]
+    fn test_filter_ignores_message_locale() {
+        use crate::core::messages::{localized, Locale};
+
+        // 基线由默认语言的检查写入，编辑器使用英文消息
+        let dir = tempfile::tempdir().unwrap();
+        let message = |locale| localized(locale, "E005", &[&"users"]);
+        Baseline::from_report(&report(vec![diagnostic("E005", &message(Locale::ZhCn), 1)]))
+            .save(dir.path())
+            .unwrap();
+        let baseline = Baseline::load(dir.path()).unwrap().unwrap();
+        let remaining = baseline.filter(
+            "src/main.rs",
+            vec![
+                diagnostic("E005", &message(Locale::En), 3),
+                diagnostic("E005", &localized(Locale::En, "E005", &[&"orders"]), 5),
+            ],
+        );
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining[0].message.contains("'orders'"));
+
+        // 英文消息写入的基线按默认语言记录
+        let baseline =
+            Baseline::from_report(&report(vec![diagnostic("E005", &message(Locale::En), 1)]));
+        assert_eq!(baseline.entries[0].message, message(Locale::ZhCn));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Baseline::load(dir.path()).unwrap().is_none());
+
+        let baseline = Baseline::from_report(&report(vec![diagnostic("E018", "query", 1)]));
+        let path = baseline.save(dir.path()).unwrap();
+        assert!(path.ends_with("baseline.json"));
+        assert_eq!(Baseline::load(dir.path()).unwrap(), Some(baseline));
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(Baseline::load(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,715 @@
+//! 项目检查模块（无客户端模式）
+//!
+//! `spring-lsp check <path>` 使用的批量检查：不建立 LSP 连接，直接对项目中的
+//! 每个 crate 运行与编辑器相同的扫描器和验证器，包括：
+//! - 配置文件验证（`config/*.toml`，包括任务名称引用）
+//! - 配置文件明文密钥检查（启用 `[secrets]` 时）
+//! - 路由处理器提取器验证和路由路径风格检查
+//! - HTTP 方法语义检查
+//! - 中间件规则检查（配置了 `[middleware]` 规则时）
+//! - 路由安全检查（存在 `.spring-lsp/rules.toml` 时）
+//! - 路径段拼写一致性检查（启用 `[routes] segment_spelling` 时）
+//! - 路由宏与 `Router::route` 重复注册检查
+//! - 不同模块中同名路由处理器检查
+//! - 路由与 OpenAPI 规范文件的对照（存在 `openapi.yaml` 时）
+//! - 定时任务名称重复和调度重叠检查
+//! - 配置结构体必需字段检查
+//! - 项目级依赖注入验证和 `#[auto_config]` 入口检查
+//!
+//! 项目根目录下存在 `.spring-lsp/baseline.json` 时，基线中已记录的诊断不会被报告，
+//! 参见 [`crate::core::baseline`]。
+//!
+//! 诊断以文本、JSON 或 SARIF 形式输出，存在错误级诊断时退出码为 1，便于在 CI 中执行。
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Url};
+use serde::Serialize;
+
+use crate::analysis::openapi::OpenApiSpec;
+use crate::analysis::pipeline::{
+    AnalysisContext, AnalysisPipeline, DocumentPipeline, FnPass, PassStage,
+};
+use crate::analysis::rust::handler_analyzer::{HandlerAnalyzer, HandlerFile};
+use crate::analysis::rust::macro_analyzer::MacroAnalyzer;
+use crate::analysis::toml::toml_analyzer::{TomlAnalyzer, TomlDocument};
+use crate::analysis::validation::di_task::{
+    CancellationToken, CrateSources, DiTaskStep, DiValidationTask,
+};
+use crate::analysis::validation::{
+    ConfigFieldValidator, DependencyInjectionValidator, HandlerNameValidator,
+    IncrementalDiValidator, JobValidator, OpenApiSpecValidator, RoutePrefixValidator,
+    RouteRegistrationValidator, RouteSecurityValidator, SegmentStats, SegmentVocabularyValidator,
+};
+use crate::core::baseline::Baseline;
+use crate::core::config::ServerConfig;
+use crate::core::index::IndexManager;
+use crate::core::messages::Locale;
+use crate::core::sarif;
+use crate::core::schema::SchemaProvider;
+use crate::core::security::SecurityRules;
+use crate::scanner::config::ConfigScanner;
+use crate::scanner::job::JobScanner;
+use crate::scanner::middleware::MiddlewareScanner;
+use crate::scanner::route::RouteScanner;
+
+/// 检查结果的输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// 便于阅读的文本（`path:line:column: severity[code]: message`）
+    #[default]
+    Text,
+    /// JSON
+    Json,
+    /// SARIF 2.1.0（GitHub Code Scanning 等）
+    Sarif,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "sarif" => Ok(OutputFormat::Sarif),
+            other => Err(format!("Unknown output format: {}", other)),
+        }
+    }
+}
+
+/// 单个文件的诊断
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDiagnostics {
+    /// 文件 URI
+    pub uri: Url,
+    /// 相对于检查根目录的路径
+    pub path: String,
+    /// 诊断列表
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// 项目检查报告
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CheckReport {
+    /// 有诊断的文件（按路径排序）
+    pub files: Vec<FileDiagnostics>,
+    /// 错误数
+    pub errors: usize,
+    /// 警告数
+    pub warnings: usize,
+}
+
+impl CheckReport {
+    /// 添加一个文件的诊断并更新统计，没有诊断的文件会被忽略
+    pub fn add_file(&mut self, file: FileDiagnostics) {
+        if file.diagnostics.is_empty() {
+            return;
+        }
+
+        for diagnostic in &file.diagnostics {
+            match diagnostic.severity {
+                Some(DiagnosticSeverity::WARNING) => self.warnings += 1,
+                Some(DiagnosticSeverity::INFORMATION) | Some(DiagnosticSeverity::HINT) => {}
+                _ => self.errors += 1,
+            }
+        }
+        self.files.push(file);
+    }
+
+    /// 进程退出码：存在错误时为 1，否则为 0
+    pub fn exit_code(&self) -> i32 {
+        if self.errors > 0 {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// 按指定格式输出报告，SARIF 的规则描述使用 `locale` 指定的语言
+    pub fn render(&self, format: OutputFormat, locale: Locale) -> String {
+        match format {
+            OutputFormat::Text => self.to_text(),
+            OutputFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+            OutputFormat::Sarif => {
+                serde_json::to_string_pretty(&sarif::to_sarif(self, locale)).unwrap_or_default()
+            }
+        }
+    }
+
+    /// 文本格式，行列号从 1 开始
+    fn to_text(&self) -> String {
+        let mut output = String::new();
+
+        for file in &self.files {
+            for diagnostic in &file.diagnostics {
+                let code = match &diagnostic.code {
+                    Some(NumberOrString::String(code)) => format!("[{}]", code),
+                    Some(NumberOrString::Number(code)) => format!("[{}]", code),
+                    None => String::new(),
+                };
+                let _ = writeln!(
+                    output,
+                    "{}:{}:{}: {}{}: {}",
+                    file.path,
+                    diagnostic.range.start.line + 1,
+                    diagnostic.range.start.character + 1,
+                    severity_name(diagnostic.severity),
+                    code,
+                    diagnostic.message
+                );
+            }
+        }
+
+        let _ = writeln!(
+            output,
+            "{} error(s), {} warning(s) in {} file(s)",
+            self.errors,
+            self.warnings,
+            self.files.len()
+        );
+        output
+    }
+}
+
+/// 诊断级别的显示名称
+fn severity_name(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        Some(DiagnosticSeverity::INFORMATION) => "info",
+        Some(DiagnosticSeverity::HINT) => "hint",
+        _ => "error",
+    }
+}
+
+/// 项目检查器
+pub struct ProjectChecker {
+    /// 服务器配置（诊断过滤、中间件规则）
+    config: ServerConfig,
+    /// TOML 分析器
+    toml_analyzer: TomlAnalyzer,
+    /// 路由处理器分析器
+    handler_analyzer: HandlerAnalyzer,
+    /// 是否应用项目的诊断基线
+    use_baseline: bool,
+    /// 诊断消息的语言
+    locale: Locale,
+}
+
+impl ProjectChecker {
+    /// 创建项目检查器
+    pub fn new(schema_provider: SchemaProvider, config: ServerConfig) -> Self {
+        Self {
+            config,
+            toml_analyzer: TomlAnalyzer::new(schema_provider),
+            handler_analyzer: HandlerAnalyzer::new(),
+            use_baseline: true,
+            locale: Locale::default(),
+        }
+    }
+
+    /// 设置诊断消息的语言
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.toml_analyzer = self.toml_analyzer.with_locale(locale);
+        self.handler_analyzer = self.handler_analyzer.with_locale(locale);
+        self.locale = locale;
+        self
+    }
+
+    /// 不应用诊断基线，报告所有诊断（用于重新生成基线）
+    pub fn without_baseline(mut self) -> Self {
+        self.use_baseline = false;
+        self
+    }
+
+    /// 检查目录下的所有 crate
+    pub fn check(&self, root: &Path) -> CheckReport {
+        // 文件 URI 需要绝对路径
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let report = self.check_all(&root);
+        if !self.use_baseline {
+            return report;
+        }
+
+        match Baseline::load(&root) {
+            Ok(Some(baseline)) => baseline.apply(report),
+            Ok(None) => report,
+            Err(e) => {
+                tracing::warn!("Ignoring baseline: {}", e);
+                report
+            }
+        }
+    }
+
+    /// 检查目录下的所有 crate，不应用基线
+    fn check_all(&self, root: &Path) -> CheckReport {
+        let crates = CrateSources::discover(root);
+        let mut diagnostics: BTreeMap<Url, Vec<Diagnostic>> = BTreeMap::new();
+        let security_rules = SecurityRules::load(root).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring security rules: {}", e);
+            None
+        });
+
+        for sources in &crates {
+            let mut crate_docs = Vec::new();
+            for (uri, content) in &sources.config_files {
+                let (toml_doc, file_diagnostics) = self.check_toml(sources, uri, content);
+                diagnostics
+                    .entry(uri.clone())
+                    .or_default()
+                    .extend(file_diagnostics);
+                if let Some(toml_doc) = toml_doc {
+                    crate_docs.push((uri.clone(), toml_doc));
+                }
+            }
+
+            // 语法错误由编译器报告，这里直接跳过
+            let mut handler_files = Vec::new();
+            for (uri, content) in &sources.files {
+                let (handler_file, file_diagnostics) = self.check_rust(uri, content);
+                diagnostics
+                    .entry(uri.clone())
+                    .or_default()
+                    .extend(file_diagnostics);
+                if let Some(handler_file) = handler_file {
+                    handler_files.push((uri, handler_file));
+                }
+            }
+
+            if let Some(rules) = &security_rules {
+                let validator = RoutePrefixValidator::new(rules).with_locale(self.locale);
+                for (uri, handler_file) in &handler_files {
+                    diagnostics
+                        .entry((*uri).clone())
+                        .or_default()
+                        .extend(validator.validate(root, uri, handler_file));
+                }
+            }
+
+            if self.config.routes.segment_spelling {
+                let stats = SegmentStats::from_paths(
+                    handler_files
+                        .iter()
+                        .flat_map(|(_, file)| file.handlers.iter())
+                        .flat_map(|handler| handler.routes.iter())
+                        .map(|route| route.path.as_str()),
+                );
+                for (uri, handler_file) in &handler_files {
+                    diagnostics.entry((*uri).clone()).or_default().extend(
+                        SegmentVocabularyValidator::new()
+                            .with_locale(self.locale)
+                            .validate(handler_file, &stats),
+                    );
+                }
+            }
+
+            let scanner = RouteScanner::new();
+            let file_routes: Vec<_> = sources
+                .files
+                .iter()
+                .filter_map(|(uri, content)| {
+                    let path = uri.to_file_path().ok()?;
+                    Some((uri, scanner.scan_file(&path, content.clone())))
+                })
+                .collect();
+            let project_routes: Vec<_> = file_routes
+                .iter()
+                .flat_map(|(_, routes)| routes.iter().cloned())
+                .collect();
+            for (uri, routes) in &file_routes {
+                diagnostics.entry((*uri).clone()).or_default().extend(
+                    RouteRegistrationValidator::new()
+                        .with_locale(self.locale)
+                        .validate(uri, routes, &project_routes),
+                );
+            }
+            for (uri, handler_file) in &handler_files {
+                diagnostics.entry((*uri).clone()).or_default().extend(
+                    HandlerNameValidator::new()
+                        .with_locale(self.locale)
+                        .validate(uri, handler_file, &project_routes),
+                );
+            }
+
+            if let Some(spec_path) = OpenApiSpec::find(&sources.root) {
+                let spec_uri = Url::from_file_path(&spec_path).ok();
+                if let (Some(spec_uri), Ok(content)) =
+                    (spec_uri, std::fs::read_to_string(&spec_path))
+                {
+                    let spec = OpenApiSpec::parse(&content);
+                    let spec_name = spec_path
+                        .strip_prefix(&sources.root)
+                        .unwrap_or(&spec_path)
+                        .display()
+                        .to_string();
+                    let validator =
+                        OpenApiSpecValidator::new(&spec, &spec_name).with_locale(self.locale);
+                    for (uri, handler_file) in &handler_files {
+                        diagnostics
+                            .entry((*uri).clone())
+                            .or_default()
+                            .extend(validator.validate_routes(handler_file));
+                    }
+                    let routes = RouteScanner::new()
+                        .scan_routes(&sources.root)
+                        .unwrap_or_default();
+                    diagnostics
+                        .entry(spec_uri)
+                        .or_default()
+                        .extend(validator.validate_spec(&routes));
+                }
+            }
+
+            let jobs = JobScanner::new().scan_sources(&sources.files);
+            for (uri, diagnostic) in JobValidator::new().with_locale(self.locale).validate(&jobs) {
+                diagnostics.entry(uri).or_default().push(diagnostic);
+            }
+
+            let configs: Vec<_> = sources
+                .files
+                .iter()
+                .filter(|(_, content)| content.contains("Configurable"))
+                .flat_map(|(uri, content)| ConfigScanner::new().scan_content(uri, content))
+                .collect();
+            for (uri, diagnostic) in ConfigFieldValidator::new()
+                .with_locale(self.locale)
+                .validate(&configs, &crate_docs)
+            {
+                diagnostics.entry(uri).or_default().push(diagnostic);
+            }
+
+            if !self.config.middleware.rules.is_empty() || security_rules.is_some() {
+                let routes = MiddlewareScanner::new()
+                    .scan_middlewares(&sources.root, &self.config.middleware.rules)
+                    .unwrap_or_default();
+                for route in routes {
+                    let Ok(uri) = Url::parse(&route.location.uri) else {
+                        continue;
+                    };
+                    let file_diagnostics = diagnostics.entry(uri).or_default();
+                    file_diagnostics.extend(route.missing_diagnostics(self.locale));
+                    if let Some(rules) = &security_rules {
+                        file_diagnostics.extend(
+                            RouteSecurityValidator::new(rules)
+                                .with_locale(self.locale)
+                                .validate(&route),
+                        );
+                    }
+                }
+            }
+        }
+
+        // 依赖注入验证没有客户端可以取消，一次执行完所有批次
+        let validator =
+            DependencyInjectionValidator::new(IndexManager::new()).with_locale(self.locale);
+        let token = CancellationToken::new();
+        let mut task = DiValidationTask::new(
+            &crates,
+            IncrementalDiValidator::new(Arc::new(MacroAnalyzer::new().with_locale(self.locale))),
+        );
+        while let DiTaskStep::Batch(batch) = task.step(&validator, &self.toml_analyzer, &token) {
+            for (uri, batch_diagnostics) in batch.diagnostics {
+                diagnostics
+                    .entry(uri)
+                    .or_default()
+                    .extend(batch_diagnostics);
+            }
+        }
+
+        self.report(root, diagnostics)
+    }
+
+    /// 检查项目并把所有诊断写入基线文件，返回基线
+    pub fn update_baseline(&self, root: &Path) -> crate::Result<Baseline> {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let baseline = Baseline::from_report(&self.check_all(&root));
+        baseline.save(&root)?;
+        Ok(baseline)
+    }
+
+    /// 检查配置文件，返回解析后的文档（用于依赖注入验证）和诊断
+    fn check_toml(
+        &self,
+        sources: &CrateSources,
+        uri: &Url,
+        content: &str,
+    ) -> (Option<TomlDocument>, Vec<Diagnostic>) {
+        let pipeline = DocumentPipeline::toml(&self.toml_analyzer).with_pass(FnPass::new(
+            "job-names",
+            PassStage::CrossRef,
+            |ctx| {
+                let Some(doc) = &ctx.toml else {
+                    return;
+                };
+                if self.toml_analyzer.job_name_references(doc).is_empty() {
+                    return;
+                }
+                if let Ok(index) = JobScanner::new().build_name_index(&sources.root) {
+                    let diagnostics = self.toml_analyzer.validate_job_names(doc, &index.names());
+                    ctx.diagnostics.extend(diagnostics);
+                }
+            },
+        ));
+
+        let mut ctx = AnalysisContext::new(uri, content, &self.config);
+        pipeline.run(&mut ctx);
+        (ctx.toml, ctx.diagnostics)
+    }
+
+    /// 检查 Rust 文件，返回解析后的路由处理器文件（用于路径段拼写检查）和诊断
+    fn check_rust(&self, uri: &Url, content: &str) -> (Option<HandlerFile>, Vec<Diagnostic>) {
+        let mut ctx = AnalysisContext::new(uri, content, &self.config);
+        DocumentPipeline::rust(&self.handler_analyzer).run(&mut ctx);
+        (ctx.handlers, ctx.diagnostics)
+    }
+
+    /// 过滤被禁用的诊断并汇总报告
+    fn report(&self, root: &Path, diagnostics: BTreeMap<Url, Vec<Diagnostic>>) -> CheckReport {
+        let mut report = CheckReport::default();
+
+        for (uri, diagnostics) in diagnostics {
+            let diagnostics: Vec<_> = diagnostics
+                .into_iter()
+                .filter(|diag| match &diag.code {
+                    Some(NumberOrString::String(code)) => {
+                        !self.config.diagnostics.is_disabled(code)
+                    }
+                    _ => true,
+                })
+                .collect();
+            if diagnostics.is_empty() {
+                continue;
+            }
+
+            let path = uri
+                .to_file_path()
+                .ok()
+                .map(|path| {
+                    path.strip_prefix(root)
+                        .unwrap_or(&path)
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .unwrap_or_else(|| uri.to_string());
+
+            report.add_file(FileDiagnostics {
+                uri,
+                path,
+                diagnostics,
+            });
+        }
+
+        report.files.sort_by(|a, b| a.path.cmp(&b.path));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::validation::handler_names::DUPLICATE_HANDLER_NAME;
+    use std::fs;
+
+    fn write_project(root: &Path) {
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("config")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/main.rs"),
+            "#[get(\"/users\")]\nasync fn list(Query(page): Query<u32>) {}\n\n#[derive(Service)]\nstruct UserService {\n    #[inject(config)]\n    config: UserConfig,\n}\n\n#[auto_config(WebConfigurator)]\n#[tokio::main]\nasync fn main() {}\n",
+        )
+        .unwrap();
+        fs::write(root.join("config/app.toml"), "[web]\nport = 8080\n").unwrap();
+    }
+
+    #[test]
+    fn test_check_project() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path());
+
+        let checker = ProjectChecker::new(SchemaProvider::default(), ServerConfig::default());
+        let report = checker.check(dir.path());
+
+        assert_eq!(report.files.len(), 1);
+        assert_eq!(
+            report.files[0].path,
+            format!("src{}main.rs", std::path::MAIN_SEPARATOR)
+        );
+        let codes: Vec<_> = report.files[0]
+            .diagnostics
+            .iter()
+            .filter_map(|diag| match &diag.code {
+                Some(NumberOrString::String(code)) => Some(code.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(codes.contains(&"E018"));
+        assert!(codes.contains(&"config-not-found"));
+        assert_eq!(report.errors, 2);
+        assert_eq!(report.exit_code(), 1);
+
+        let text = report.render(OutputFormat::Text, Locale::ZhCn);
+        assert!(text.contains("main.rs:2:"));
+        assert!(text.ends_with("2 error(s), 0 warning(s) in 1 file(s)\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(OutputFormat::Json, Locale::ZhCn)).unwrap();
+        assert_eq!(json["errors"], 2);
+
+        let sarif: serde_json::Value =
+            serde_json::from_str(&report.render(OutputFormat::Sarif, Locale::ZhCn)).unwrap();
+        assert_eq!(sarif["runs"][0]["results"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_check_project_in_english() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path());
+
+        let report = ProjectChecker::new(SchemaProvider::default(), ServerConfig::default())
+            .with_locale(Locale::En)
+            .check(dir.path());
+        let sarif: serde_json::Value =
+            serde_json::from_str(&report.render(OutputFormat::Sarif, Locale::En)).unwrap();
+        let run = &sarif["runs"][0];
+        let rule = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|rule| rule["id"] == "E018")
+            .unwrap();
+        assert_eq!(
+            rule["shortDescription"]["text"],
+            "The type parameter of Query<T> must be a struct"
+        );
+        let messages: Vec<_> = run["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|result| result["message"]["text"].as_str().unwrap())
+            .collect();
+        assert!(
+            messages.iter().all(|message| message.is_ascii()),
+            "{:?}",
+            messages
+        );
+    }
+
+    #[test]
+    fn test_disabled_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path());
+
+        let mut config = ServerConfig::default();
+        config.diagnostics.disabled = ["E018", "config-not-found"]
+            .iter()
+            .map(|code| code.to_string())
+            .collect();
+
+        let report = ProjectChecker::new(SchemaProvider::default(), config).check(dir.path());
+        assert!(report.files.is_empty());
+        assert_eq!(report.exit_code(), 0);
+        assert!(report
+            .render(OutputFormat::Text, Locale::ZhCn)
+            .starts_with("0 error(s)"));
+    }
+
+    #[test]
+    fn test_baseline_suppresses_existing_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path());
+
+        let checker = ProjectChecker::new(SchemaProvider::default(), ServerConfig::default());
+        let baseline = checker.update_baseline(dir.path()).unwrap();
+        assert_eq!(baseline.len(), 2);
+        assert!(checker.check(dir.path()).files.is_empty());
+
+        // 新的诊断仍然会被报告
+        fs::write(
+            dir.path().join("src/routes.rs"),
+            "#[get(\"/items\")]\nasync fn items(Query(id): Query<i64>) {}\n",
+        )
+        .unwrap();
+        let report = checker.check(dir.path());
+        assert_eq!(report.errors, 1);
+        assert!(report.files[0].path.ends_with("routes.rs"));
+
+        let report = ProjectChecker::new(SchemaProvider::default(), ServerConfig::default())
+            .without_baseline()
+            .check(dir.path());
+        assert_eq!(report.errors, 3);
+    }
+
+    #[test]
+    fn test_check_config_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path());
+        fs::write(
+            dir.path().join("src/config.rs"),
+            "#[derive(Debug, Configurable, Deserialize)]\n#[config_prefix = \"web\"]\nstruct WebConfig {\n    port: u16,\n    host: String,\n}\n",
+        )
+        .unwrap();
+
+        let report = ProjectChecker::new(SchemaProvider::default(), ServerConfig::default())
+            .check(dir.path());
+        let paths: Vec<_> = report
+            .files
+            .iter()
+            .filter(|file| {
+                file.diagnostics.iter().any(|diag| {
+                    diag.code == Some(NumberOrString::String("config-field-missing".to_string()))
+                })
+            })
+            .map(|file| file.path.replace(std::path::MAIN_SEPARATOR, "/"))
+            .collect();
+        assert_eq!(paths, ["config/app.toml", "src/config.rs"]);
+    }
+
+    #[test]
+    fn test_check_duplicate_handler_names() {
+        let dir = tempfile::tempdir().unwrap();
+        write_project(dir.path());
+        fs::create_dir_all(dir.path().join("src/admin")).unwrap();
+        fs::write(
+            dir.path().join("src/users.rs"),
+            "#[get(\"/users\")]\nasync fn list_users() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("src/admin/users.rs"),
+            "#[get(\"/admin/users\")]\nasync fn list_users() {}\n",
+        )
+        .unwrap();
+
+        let report = ProjectChecker::new(SchemaProvider::default(), ServerConfig::default())
+            .check(dir.path());
+        let paths: Vec<_> = report
+            .files
+            .iter()
+            .filter(|file| {
+                file.diagnostics.iter().any(|diag| {
+                    diag.code == Some(NumberOrString::String(DUPLICATE_HANDLER_NAME.to_string()))
+                })
+            })
+            .map(|file| file.path.replace(std::path::MAIN_SEPARATOR, "/"))
+            .collect();
+        assert_eq!(paths, ["src/admin/users.rs", "src/users.rs"]);
+    }
+
+    #[test]
+    fn test_output_format_from_str() {
+        assert_eq!("text".parse::<OutputFormat>(), Ok(OutputFormat::Text));
+        assert_eq!("json".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+        assert_eq!("sarif".parse::<OutputFormat>(), Ok(OutputFormat::Sarif));
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+}
@@ -6,6 +6,15 @@
 //! - 诊断过滤配置
 //! - 自定义 Schema URL
 //! - 日志级别配置
+//! - 传输层消息大小限制
+//! - 路由中间件检查规则
+//! - 路由路径风格（末尾斜杠、大小写）检查
+//! - 构建脚本生成代码的索引目录
+//! - 配置文件明文密钥检查
+//! - 路由处理器复杂度提示
+//! - 悬停提示时间预算
+//! - 新建文件的模板
+//! - 大型工作空间的内存预算
 //!
 //! ## 配置文件
 //!
@@ -23,6 +32,8 @@
 //! level = "info"  # trace, debug, info, warn, error
 //! verbose = false
 //! log_file = "/tmp/spring-lsp.log"  # 可选
+//! perf_report = false  # 关闭时在日志中输出性能报告
+//! error_report = false  # 关闭时在日志中输出错误恢复统计（只记录在本地）
 //!
 //! # 补全配置
 //! [completion]
@@ -30,7 +41,7 @@
 //!
 //! # 诊断配置
 //! [diagnostics]
-//! # 禁用特定类型的诊断
+//! # 禁用特定类型的诊断（或整个规则组，如 "http-semantics"）
 //! disabled = ["deprecated_warning", "restful_style"]
 //!
 //! # Schema 配置
@@ -38,6 +49,55 @@
 //! url = "https://spring-rs.github.io/config-schema.json"
 //! # 或使用本地文件
 //! # url = "file:///path/to/schema.json"
+//!
+//! # 传输层配置
+//! [transport]
+//! max_message_size = 33554432  # 单条消息最大字节数（默认 32 MiB）
+//!
+//! # 中间件配置
+//! [middleware]
+//! # 匹配路径的路由必须应用指定的中间件（`*` 匹配一段路径，`**` 匹配任意多段）
+//! rules = [{ pattern = "/admin/**", required = "AuthMiddleware" }]
+//!
+//! # 路由路径风格配置
+//! [routes]
+//! trailing_slash = "never"  # never, always, ignore
+//! case = "lowercase"  # lowercase, ignore
+//! segment_spelling = true  # 检查拼写相近的路径段（如 /user 与 /users），默认关闭
+//! stub_handlers = true  # 报告只有 todo!()/unimplemented!() 的处理器，默认关闭
+//!
+//! # 索引配置
+//! [index]
+//! # 额外扫描的生成代码目录（相对于项目根目录，`*` 匹配一段路径）
+//! generated_dirs = ["target/debug/build/my-app-*/out"]
+//!
+//! # 配置文件明文密钥检查（默认关闭）
+//! [secrets]
+//! enabled = true
+//! severity = "warning"  # error, warning, information, hint
+//! allow = ["mail.auth.password"]  # 允许直接写入的配置项
+//!
+//! # 路由处理器复杂度提示（默认关闭）
+//! [complexity]
+//! enabled = true
+//! max_lines = 40  # 函数体最大行数
+//! max_components = 3  # 最多注入的组件数
+//!
+//! # 悬停提示配置
+//! [hover]
+//! budget_ms = 100  # 完整悬停提示的时间预算，超时返回只包含当前文档信息的简短提示
+//!
+//! # 新建文件模板（默认为 src/handlers 和 src/jobs 下的新文件填充模板）
+//! [templates]
+//! enabled = true
+//! rules = [
+//!     { dir = "src/api", template = "handler" },
+//!     { dir = "src/tasks", file = ".spring-lsp/templates/task.rs" },  # 自定义模板文件
+//! ]
+//!
+//! # 内存预算
+//! [memory]
+//! budget_mb = 1024  # 估算的内存占用超出时释放缓存，0 表示不限制
 //! ```
 //!
 //! ## 环境变量
@@ -46,7 +106,10 @@
 //! - `SPRING_LSP_LOG_LEVEL`: 日志级别
 //! - `SPRING_LSP_VERBOSE`: 启用详细日志
 //! - `SPRING_LSP_LOG_FILE`: 日志文件路径
+//! - `SPRING_LSP_PERF_REPORT`: 关闭时输出性能报告
+//! - `SPRING_LSP_ERROR_REPORT`: 关闭时输出错误恢复统计
 //! - `SPRING_LSP_SCHEMA_URL`: Schema URL
+//! - `SPRING_LSP_MAX_MESSAGE_SIZE`: 单条消息最大字节数
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -54,6 +117,9 @@ use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// 工作空间根目录下的配置文件名
+pub const WORKSPACE_CONFIG_FILE: &str = ".spring-lsp.toml";
+
 /// 服务器配置
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -66,6 +132,24 @@ pub struct ServerConfig {
     pub diagnostics: DiagnosticsConfig,
     /// Schema 配置
     pub schema: SchemaConfig,
+    /// 传输层配置
+    pub transport: TransportConfig,
+    /// 中间件配置
+    pub middleware: MiddlewareConfig,
+    /// 路由路径风格配置
+    pub routes: RoutesConfig,
+    /// 索引配置
+    pub index: IndexConfig,
+    /// 配置文件明文密钥检查配置
+    pub secrets: SecretsConfig,
+    /// 路由处理器复杂度提示配置
+    pub complexity: ComplexityConfig,
+    /// 悬停提示配置
+    pub hover: HoverConfig,
+    /// 新建文件模板配置
+    pub templates: TemplatesConfig,
+    /// 内存预算配置
+    pub memory: MemoryConfig,
 }
 
 impl ServerConfig {
@@ -88,7 +172,7 @@ impl ServerConfig {
 
         // 2. 尝试加载工作空间配置
         if let Some(workspace_root) = workspace_root {
-            let workspace_config_path = workspace_root.join(".spring-lsp.toml");
+            let workspace_config_path = workspace_root.join(WORKSPACE_CONFIG_FILE);
             if let Ok(workspace_config) = Self::load_from_file(&workspace_config_path) {
                 config = config.merge(workspace_config);
             }
@@ -119,6 +203,15 @@ impl ServerConfig {
         self.completion = self.completion.merge(other.completion);
         self.diagnostics = self.diagnostics.merge(other.diagnostics);
         self.schema = self.schema.merge(other.schema);
+        self.transport = self.transport.merge(other.transport);
+        self.middleware = self.middleware.merge(other.middleware);
+        self.routes = self.routes.merge(other.routes);
+        self.index = self.index.merge(other.index);
+        self.secrets = self.secrets.merge(other.secrets);
+        self.complexity = self.complexity.merge(other.complexity);
+        self.hover = self.hover.merge(other.hover);
+        self.templates = self.templates.merge(other.templates);
+        self.memory = self.memory.merge(other.memory);
         self
     }
 
@@ -126,6 +219,7 @@ impl ServerConfig {
     fn apply_env_overrides(mut self) -> Self {
         self.logging = self.logging.apply_env_overrides();
         self.schema = self.schema.apply_env_overrides();
+        self.transport = self.transport.apply_env_overrides();
         self
     }
 
@@ -134,6 +228,14 @@ impl ServerConfig {
         self.logging.validate()?;
         self.completion.validate()?;
         self.schema.validate()?;
+        self.transport.validate()?;
+        self.middleware.validate()?;
+        self.index.validate()?;
+        self.secrets.validate()?;
+        self.complexity.validate()?;
+        self.hover.validate()?;
+        self.templates.validate()?;
+        self.memory.validate()?;
         Ok(())
     }
 }
@@ -148,6 +250,10 @@ pub struct LoggingConfig {
     pub verbose: bool,
     /// 日志文件路径（可选）
     pub log_file: Option<PathBuf>,
+    /// 是否在服务器关闭时输出性能报告
+    pub perf_report: bool,
+    /// 是否在服务器关闭时输出错误恢复统计（解析失败、捕获的 panic、按方法统计的处理失败）
+    pub error_report: bool,
 }
 
 impl Default for LoggingConfig {
@@ -156,6 +262,8 @@ impl Default for LoggingConfig {
             level: "info".to_string(),
             verbose: false,
             log_file: None,
+            perf_report: false,
+            error_report: false,
         }
     }
 }
@@ -166,6 +274,8 @@ impl LoggingConfig {
             level: other.level,
             verbose: other.verbose,
             log_file: other.log_file.or(self.log_file),
+            perf_report: other.perf_report,
+            error_report: other.error_report,
         }
     }
 
@@ -179,6 +289,12 @@ impl LoggingConfig {
         if let Ok(log_file) = env::var("SPRING_LSP_LOG_FILE") {
             self.log_file = Some(PathBuf::from(log_file));
         }
+        if let Ok(perf_report) = env::var("SPRING_LSP_PERF_REPORT") {
+            self.perf_report = perf_report == "1" || perf_report.to_lowercase() == "true";
+        }
+        if let Ok(error_report) = env::var("SPRING_LSP_ERROR_REPORT") {
+            self.error_report = error_report == "1" || error_report.to_lowercase() == "true";
+        }
         self
     }
 
@@ -239,7 +355,7 @@ impl CompletionConfig {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct DiagnosticsConfig {
-    /// 禁用的诊断类型列表
+    /// 禁用的诊断类型列表（也可以是 `http-semantics` 这样的规则组名称）
     pub disabled: HashSet<String>,
 }
 
@@ -255,8 +371,13 @@ impl DiagnosticsConfig {
     }
 
     /// 检查诊断类型是否被禁用
+    ///
+    /// `group/rule` 形式的诊断代码在规则组 `group` 被禁用时同样视为禁用
     pub fn is_disabled(&self, diagnostic_type: &str) -> bool {
         self.disabled.contains(diagnostic_type)
+            || diagnostic_type
+                .split_once('/')
+                .is_some_and(|(group, _)| self.disabled.contains(group))
     }
 }
 
@@ -308,6 +429,405 @@ impl SchemaConfig {
     }
 }
 
+/// 传输层配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransportConfig {
+    /// 单条 JSON-RPC 消息的最大字节数，超出的消息会被丢弃
+    pub max_message_size: usize,
+}
+
+impl Default for TransportConfig {
+    fn default() -> Self {
+        Self {
+            max_message_size: crate::protocol::transport::DEFAULT_MAX_MESSAGE_SIZE,
+        }
+    }
+}
+
+impl TransportConfig {
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            max_message_size: other.max_message_size,
+        }
+    }
+
+    fn apply_env_overrides(mut self) -> Self {
+        if let Ok(size) = env::var("SPRING_LSP_MAX_MESSAGE_SIZE") {
+            match size.parse() {
+                Ok(size) => self.max_message_size = size,
+                Err(_) => tracing::warn!("Invalid SPRING_LSP_MAX_MESSAGE_SIZE: {}", size),
+            }
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_message_size == 0 {
+            return Err("Transport max_message_size must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 中间件配置
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct MiddlewareConfig {
+    /// 路由中间件检查规则
+    pub rules: Vec<MiddlewareRule>,
+}
+
+/// 路由中间件检查规则
+///
+/// 路径匹配 `pattern` 的路由必须在中间件链中包含 `required`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MiddlewareRule {
+    /// 路由路径模式（`*` 匹配一段路径，`**` 匹配任意多段）
+    pub pattern: String,
+    /// 必需的中间件名称（中间件表达式中包含该名称即视为已应用）
+    pub required: String,
+}
+
+impl MiddlewareConfig {
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            rules: if other.rules.is_empty() {
+                self.rules
+            } else {
+                other.rules
+            },
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for rule in &self.rules {
+            if rule.pattern.is_empty() || rule.required.is_empty() {
+                return Err(
+                    "Middleware rule pattern and required middleware cannot be empty".to_string(),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 路由路径末尾斜杠的约定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingSlashPolicy {
+    /// 路径不以 `/` 结尾（根路径除外）
+    #[default]
+    Never,
+    /// 路径以 `/` 结尾
+    Always,
+    /// 不检查
+    Ignore,
+}
+
+/// 路由路径大小写的约定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CasePolicy {
+    /// 路径中的字面量使用小写（路径参数名称不受影响）
+    #[default]
+    Lowercase,
+    /// 不检查
+    Ignore,
+}
+
+/// 路由路径风格配置
+///
+/// 同一文件中只有末尾斜杠或大小写不同的路由容易导致 404，
+/// 检查时按这里的约定给出修复建议
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoutesConfig {
+    /// 末尾斜杠约定
+    pub trailing_slash: TrailingSlashPolicy,
+    /// 大小写约定
+    pub case: CasePolicy,
+    /// 是否检查与项目中其他路径段拼写相近的路径段（如 `/user` 与 `/users`）
+    pub segment_spelling: bool,
+    /// 是否报告未实现的处理器（函数体只有 `todo!()`、`unimplemented!()` 或返回 501 状态码）
+    pub stub_handlers: bool,
+}
+
+impl RoutesConfig {
+    pub fn merge(self, other: Self) -> Self {
+        other
+    }
+}
+
+/// 索引配置
+///
+/// 默认只扫描 `src` 目录。构建脚本生成的路由和组件（如 `include!(concat!(env!("OUT_DIR"), ...))`）
+/// 位于 `target` 下，需要显式列出生成代码目录才会被索引
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndexConfig {
+    /// 额外扫描的生成代码目录（相对于项目根目录，`*` 匹配一段路径）
+    pub generated_dirs: Vec<String>,
+}
+
+impl IndexConfig {
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            generated_dirs: if other.generated_dirs.is_empty() {
+                self.generated_dirs
+            } else {
+                other.generated_dirs
+            },
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for dir in &self.generated_dirs {
+            if dir.is_empty() || Path::new(dir).is_absolute() {
+                return Err(format!(
+                    "Index generated_dirs entries must be relative paths: '{}'",
+                    dir
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 明文密钥诊断的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretsSeverity {
+    /// 错误
+    Error,
+    /// 警告
+    #[default]
+    Warning,
+    /// 信息
+    Information,
+    /// 提示
+    Hint,
+}
+
+/// 配置文件明文密钥检查配置
+///
+/// 默认关闭。开启后检查 `config/*.toml` 中直接写入的密码、令牌和访问密钥，
+/// 建议改用 `${ENV_VAR}` 插值
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecretsConfig {
+    /// 是否开启检查
+    pub enabled: bool,
+    /// 诊断的严重程度
+    pub severity: SecretsSeverity,
+    /// 允许直接写入的配置项（`前缀.键`，如 `mail.auth.password`）
+    pub allow: Vec<String>,
+}
+
+impl SecretsConfig {
+    pub fn merge(self, other: Self) -> Self {
+        other
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for key in &self.allow {
+            if !key.contains('.') || key.split('.').any(str::is_empty) {
+                return Err(format!(
+                    "Secrets allow entries must be dotted config keys like 'mail.auth.password': '{}'",
+                    key
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 路由处理器复杂度提示配置
+///
+/// 默认关闭。开启后在函数体过长或注入组件过多的处理器上给出提示，
+/// 建议把业务逻辑提取到 Service 中
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComplexityConfig {
+    /// 是否开启提示
+    pub enabled: bool,
+    /// 函数体最大行数
+    pub max_lines: usize,
+    /// 最多注入的组件数（`Component<T>` 参数）
+    pub max_components: usize,
+}
+
+impl Default for ComplexityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_lines: 40,
+            max_components: 3,
+        }
+    }
+}
+
+impl ComplexityConfig {
+    pub fn merge(self, other: Self) -> Self {
+        other
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_lines == 0 || self.max_components == 0 {
+            return Err(
+                "Complexity max_lines and max_components must be greater than 0".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// 悬停提示配置
+///
+/// 完整的悬停提示需要扫描项目中的其他文件，在后台线程中构建；
+/// 超出时间预算时返回只包含当前文档信息的简短提示
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HoverConfig {
+    /// 等待完整悬停提示的最长时间（毫秒）
+    pub budget_ms: u64,
+}
+
+impl Default for HoverConfig {
+    fn default() -> Self {
+        Self { budget_ms: 100 }
+    }
+}
+
+impl HoverConfig {
+    pub fn merge(self, other: Self) -> Self {
+        other
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.budget_ms == 0 {
+            return Err("Hover budget_ms must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// 时间预算
+    pub fn budget(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.budget_ms)
+    }
+}
+
+/// 内置的新建文件模板
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateKind {
+    /// 路由处理器模块
+    Handler,
+    /// 定时任务模块
+    Job,
+}
+
+/// 目录的新建文件模板
+///
+/// 在 `dir` 及其子目录中新建的空 Rust 文件使用 `file` 指定的模板文件，
+/// 没有指定时使用内置模板 `template`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateRule {
+    /// 目录（相对于项目根目录，如 `src/handlers`）
+    pub dir: String,
+    /// 内置模板
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<TemplateKind>,
+    /// 自定义模板文件（相对于项目根目录），其中的 `{module}` 替换为模块名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// 新建文件模板配置
+///
+/// 在编辑器中新建空的 Rust 文件后，按所在目录填充模块文档、导入和示例函数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TemplatesConfig {
+    /// 是否填充模板
+    pub enabled: bool,
+    /// 目录模板（多个规则匹配时使用目录最长的规则）
+    pub rules: Vec<TemplateRule>,
+}
+
+impl Default for TemplatesConfig {
+    fn default() -> Self {
+        let rule = |dir: &str, template| TemplateRule {
+            dir: dir.to_string(),
+            template: Some(template),
+            file: None,
+        };
+        Self {
+            enabled: true,
+            rules: vec![
+                rule("src/handlers", TemplateKind::Handler),
+                rule("src/jobs", TemplateKind::Job),
+            ],
+        }
+    }
+}
+
+impl TemplatesConfig {
+    pub fn merge(self, other: Self) -> Self {
+        other
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for rule in &self.rules {
+            if rule.dir.is_empty() || Path::new(&rule.dir).is_absolute() {
+                return Err(format!(
+                    "Template rule dir must be a relative path: '{}'",
+                    rule.dir
+                ));
+            }
+            if rule.template.is_none() && rule.file.is_none() {
+                return Err(format!(
+                    "Template rule for '{}' needs a template or a file",
+                    rule.dir
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 内存预算配置
+///
+/// 估算的内存占用（打开的文档、依赖注入验证保留的源文件内容和语法树）超出预算时，
+/// 先释放语法树（只保留提取的服务和诊断），仍然超出时再释放已关闭文档的内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryConfig {
+    /// 内存预算（MiB），0 表示不限制
+    pub budget_mb: u64,
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self { budget_mb: 1024 }
+    }
+}
+
+impl MemoryConfig {
+    pub fn merge(self, other: Self) -> Self {
+        other
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// 内存预算（字节），不限制时返回 `None`
+    pub fn budget_bytes(&self) -> Option<usize> {
+        (self.budget_mb > 0).then(|| (self.budget_mb as usize).saturating_mul(1024 * 1024))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,6 +853,8 @@ mod tests {
             level: "debug".to_string(),
             verbose: false,
             log_file: None,
+            perf_report: false,
+            error_report: false,
         };
         assert!(valid_config.validate().is_ok());
 
@@ -340,6 +862,8 @@ mod tests {
             level: "invalid".to_string(),
             verbose: false,
             log_file: None,
+            perf_report: false,
+            error_report: false,
         };
         assert!(invalid_config.validate().is_err());
     }
@@ -380,6 +904,161 @@ mod tests {
         assert!(invalid_protocol.validate().is_err());
     }
 
+    #[test]
+    fn test_transport_config_validation() {
+        assert!(TransportConfig::default().validate().is_ok());
+
+        let invalid_config = TransportConfig {
+            max_message_size: 0,
+        };
+        assert!(invalid_config.validate().is_err());
+
+        let config: ServerConfig = toml::from_str("[transport]\nmax_message_size = 4096").unwrap();
+        assert_eq!(config.transport.max_message_size, 4096);
+    }
+
+    #[test]
+    fn test_middleware_config() {
+        let config: ServerConfig = toml::from_str(
+            "[middleware]\nrules = [{ pattern = \"/admin/**\", required = \"AuthMiddleware\" }]",
+        )
+        .unwrap();
+        assert_eq!(config.middleware.rules[0].pattern, "/admin/**");
+        assert!(config.validate().is_ok());
+
+        let invalid_config = MiddlewareConfig {
+            rules: vec![MiddlewareRule {
+                pattern: "/admin/**".to_string(),
+                required: String::new(),
+            }],
+        };
+        assert!(invalid_config.validate().is_err());
+    }
+
+    #[test]
+    fn test_routes_config() {
+        let config = ServerConfig::default();
+        assert_eq!(config.routes.trailing_slash, TrailingSlashPolicy::Never);
+        assert_eq!(config.routes.case, CasePolicy::Lowercase);
+
+        let config: ServerConfig =
+            toml::from_str("[routes]\ntrailing_slash = \"always\"\ncase = \"ignore\"").unwrap();
+        assert_eq!(config.routes.trailing_slash, TrailingSlashPolicy::Always);
+        assert_eq!(config.routes.case, CasePolicy::Ignore);
+        assert!(toml::from_str::<ServerConfig>("[routes]\ncase = \"upper\"").is_err());
+    }
+
+    #[test]
+    fn test_index_config_validation() {
+        assert!(IndexConfig::default().validate().is_ok());
+
+        let config: ServerConfig =
+            toml::from_str("[index]\ngenerated_dirs = [\"target/*/build/app-*/out\"]").unwrap();
+        assert!(config.validate().is_ok());
+
+        let invalid = IndexConfig {
+            generated_dirs: vec!["/abs/out".to_string()],
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_secrets_config() {
+        let config = ServerConfig::default();
+        assert!(!config.secrets.enabled);
+        assert_eq!(config.secrets.severity, SecretsSeverity::Warning);
+
+        let config: ServerConfig = toml::from_str(
+            "[secrets]\nenabled = true\nseverity = \"hint\"\nallow = [\"mail.auth.password\"]",
+        )
+        .unwrap();
+        assert!(config.secrets.enabled);
+        assert_eq!(config.secrets.severity, SecretsSeverity::Hint);
+        assert!(config.validate().is_ok());
+
+        let invalid = SecretsConfig {
+            allow: vec!["password".to_string()],
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_complexity_config() {
+        let config = ServerConfig::default();
+        assert!(!config.complexity.enabled);
+        assert_eq!(config.complexity.max_lines, 40);
+
+        let config: ServerConfig =
+            toml::from_str("[complexity]\nenabled = true\nmax_components = 5").unwrap();
+        assert!(config.complexity.enabled);
+        assert_eq!(config.complexity.max_lines, 40);
+        assert_eq!(config.complexity.max_components, 5);
+        assert!(config.validate().is_ok());
+
+        let invalid = ComplexityConfig {
+            max_lines: 0,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_hover_config() {
+        assert_eq!(
+            ServerConfig::default().hover.budget(),
+            std::time::Duration::from_millis(100)
+        );
+
+        let config: ServerConfig = toml::from_str("[hover]\nbudget_ms = 300").unwrap();
+        assert_eq!(config.hover.budget_ms, 300);
+        assert!(config.validate().is_ok());
+
+        assert!(HoverConfig { budget_ms: 0 }.validate().is_err());
+    }
+
+    #[test]
+    fn test_templates_config() {
+        let defaults = ServerConfig::default().templates;
+        assert!(defaults.enabled);
+        assert_eq!(defaults.rules[1].dir, "src/jobs");
+
+        let config: ServerConfig = toml::from_str(
+            "[templates]\nrules = [{ dir = \"src/api\", template = \"handler\" }, { dir = \"src/tasks\", file = \"task.rs\" }]",
+        )
+        .unwrap();
+        assert_eq!(config.templates.rules.len(), 2);
+        assert_eq!(
+            config.templates.rules[0].template,
+            Some(TemplateKind::Handler)
+        );
+        assert!(config.validate().is_ok());
+
+        let config: ServerConfig = toml::from_str("[templates]\nenabled = false").unwrap();
+        assert!(!config.templates.enabled);
+        assert_eq!(config.templates.rules, defaults.rules);
+
+        let invalid = TemplatesConfig {
+            enabled: true,
+            rules: vec![TemplateRule {
+                dir: "src/api".to_string(),
+                template: None,
+                file: None,
+            }],
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_memory_config() {
+        let defaults = ServerConfig::default().memory;
+        assert_eq!(defaults.budget_bytes(), Some(1024 * 1024 * 1024));
+
+        let config: ServerConfig = toml::from_str("[memory]\nbudget_mb = 0").unwrap();
+        assert!(config.memory.budget_bytes().is_none());
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_diagnostics_is_disabled() {
         let mut config = DiagnosticsConfig::default();
@@ -388,6 +1067,11 @@ mod tests {
         config.disabled.insert("deprecated_warning".to_string());
         assert!(config.is_disabled("deprecated_warning"));
         assert!(!config.is_disabled("type_error"));
+
+        // 按规则组禁用
+        assert!(!config.is_disabled("http-semantics/get-with-body"));
+        config.disabled.insert("http-semantics".to_string());
+        assert!(config.is_disabled("http-semantics/get-with-body"));
     }
 
     #[test]
@@ -397,6 +1081,8 @@ mod tests {
                 level: "info".to_string(),
                 verbose: false,
                 log_file: None,
+                perf_report: false,
+                error_report: false,
             },
             completion: CompletionConfig {
                 trigger_characters: vec!["[".to_string()],
@@ -407,6 +1093,17 @@ mod tests {
             schema: SchemaConfig {
                 url: "https://default.com/schema.json".to_string(),
             },
+            transport: TransportConfig::default(),
+            middleware: MiddlewareConfig::default(),
+            routes: RoutesConfig::default(),
+            index: IndexConfig {
+                generated_dirs: vec!["target/generated".to_string()],
+            },
+            secrets: SecretsConfig::default(),
+            complexity: ComplexityConfig::default(),
+            hover: HoverConfig::default(),
+            templates: TemplatesConfig::default(),
+            memory: MemoryConfig::default(),
         };
 
         let override_config = ServerConfig {
@@ -414,6 +1111,8 @@ mod tests {
                 level: "debug".to_string(),
                 verbose: true,
                 log_file: Some(PathBuf::from("/tmp/test.log")),
+                perf_report: true,
+                error_report: true,
             },
             completion: CompletionConfig {
                 trigger_characters: vec!["[".to_string(), ".".to_string()],
@@ -428,6 +1127,38 @@ mod tests {
             schema: SchemaConfig {
                 url: "https://custom.com/schema.json".to_string(),
             },
+            transport: TransportConfig {
+                max_message_size: 1024,
+            },
+            middleware: MiddlewareConfig {
+                rules: vec![MiddlewareRule {
+                    pattern: "/admin/**".to_string(),
+                    required: "AuthMiddleware".to_string(),
+                }],
+            },
+            routes: RoutesConfig {
+                trailing_slash: TrailingSlashPolicy::Always,
+                case: CasePolicy::Ignore,
+                segment_spelling: true,
+                stub_handlers: true,
+            },
+            index: IndexConfig::default(),
+            secrets: SecretsConfig {
+                enabled: true,
+                severity: SecretsSeverity::Error,
+                allow: vec!["mail.auth.password".to_string()],
+            },
+            complexity: ComplexityConfig {
+                enabled: true,
+                max_lines: 20,
+                max_components: 2,
+            },
+            hover: HoverConfig { budget_ms: 250 },
+            templates: TemplatesConfig {
+                enabled: false,
+                rules: Vec::new(),
+            },
+            memory: MemoryConfig { budget_mb: 0 },
         };
 
         let merged = base.merge(override_config);
@@ -438,9 +1169,25 @@ mod tests {
             merged.logging.log_file,
             Some(PathBuf::from("/tmp/test.log"))
         );
+        assert!(merged.logging.perf_report);
+        assert!(merged.logging.error_report);
         assert_eq!(merged.completion.trigger_characters.len(), 2);
         assert!(merged.diagnostics.is_disabled("deprecated_warning"));
         assert_eq!(merged.schema.url, "https://custom.com/schema.json");
+        assert_eq!(merged.transport.max_message_size, 1024);
+        assert_eq!(merged.middleware.rules.len(), 1);
+        assert_eq!(merged.routes.trailing_slash, TrailingSlashPolicy::Always);
+        assert!(merged.routes.segment_spelling);
+        assert!(merged.routes.stub_handlers);
+        // 未设置的生成代码目录保留原值
+        assert_eq!(merged.index.generated_dirs, vec!["target/generated"]);
+        assert!(merged.secrets.enabled);
+        assert_eq!(merged.secrets.severity, SecretsSeverity::Error);
+        assert!(merged.complexity.enabled);
+        assert_eq!(merged.complexity.max_lines, 20);
+        assert_eq!(merged.hover.budget_ms, 250);
+        assert!(!merged.templates.enabled);
+        assert!(merged.memory.budget_bytes().is_none());
     }
 
     #[test]
@@ -538,6 +1285,8 @@ url = "file:///local/schema.json"
                 level: "invalid".to_string(),
                 verbose: false,
                 log_file: None,
+                perf_report: false,
+                error_report: false,
             },
             ..Default::default()
         };
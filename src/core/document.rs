@@ -1,12 +1,30 @@
 //! 文档管理模块
+//!
+//! 除了缓存已打开的文档，还提供 [`PositionMapper`]：在字节偏移、字符偏移和 LSP 位置之间转换。
+//! LSP 位置的列默认按 UTF-16 代码单元计算，客户端在 `general.positionEncodings` 中声明支持时
+//! 可以协商为 UTF-8（字节）或 UTF-32（字符）。所有分析器都通过它生成 `Range`，
+//! 保证包含中文注释、表情符号等多字节字符的行上诊断位置正确。
+//! 协商的编码保存在 [`DocumentManager`] 中，通过 [`Document::mapper`] 和
+//! [`SourceReader::mapper`] 创建的转换器使用它。
+//!
+//! 每个打开的文档缓存一份 [`LineIndex`]（每行起始的字节偏移），编辑时增量更新，
+//! 通过 [`Document::mapper`] 创建的转换器直接使用它，不需要重新扫描文档内容。
+
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::Arc;
 
 use dashmap::DashMap;
-use lsp_types::{TextDocumentContentChangeEvent, Url};
+use lsp_types::{
+    ClientCapabilities, Position, PositionEncodingKind, Range, TextDocumentContentChangeEvent, Url,
+};
 
 /// 文档管理器
 pub struct DocumentManager {
     /// 文档缓存（DashMap 本身就是并发安全的）
     documents: DashMap<Url, Document>,
+    /// 与客户端协商的位置编码
+    encoding: PositionEncoding,
 }
 
 /// 文档
@@ -20,23 +38,52 @@ pub struct Document {
     pub content: String,
     /// 语言 ID
     pub language_id: String,
+    /// 行索引（与 `content` 保持同步）
+    line_index: LineIndex,
+    /// 位置编码
+    encoding: PositionEncoding,
+}
+
+impl Document {
+    /// 行索引
+    pub fn line_index(&self) -> &LineIndex {
+        &self.line_index
+    }
+
+    /// 使用缓存的行索引和协商的位置编码创建位置转换器
+    pub fn mapper(&self) -> PositionMapper<'_> {
+        PositionMapper::with_line_index(&self.content, &self.line_index, self.encoding)
+    }
 }
 
 impl DocumentManager {
     /// 创建新的文档管理器
     pub fn new() -> Self {
+        Self::with_encoding(PositionEncoding::default())
+    }
+
+    /// 创建使用指定位置编码的文档管理器
+    pub fn with_encoding(encoding: PositionEncoding) -> Self {
         Self {
             documents: DashMap::new(),
+            encoding,
         }
     }
 
+    /// 位置编码
+    pub fn encoding(&self) -> PositionEncoding {
+        self.encoding
+    }
+
     /// 打开文档
     pub fn open(&self, uri: Url, version: i32, content: String, language_id: String) {
         let doc = Document {
             uri: uri.clone(),
             version,
+            line_index: LineIndex::new(&content),
             content,
             language_id,
+            encoding: self.encoding,
         };
         self.documents.insert(uri, doc);
     }
@@ -44,21 +91,28 @@ impl DocumentManager {
     /// 修改文档
     pub fn change(&self, uri: &Url, version: i32, changes: Vec<TextDocumentContentChangeEvent>) {
         if let Some(mut doc) = self.documents.get_mut(uri) {
+            let doc = &mut *doc;
             doc.version = version;
 
             // 应用修改
             for change in changes {
                 if let Some(range) = change.range {
                     // 增量修改
-                    if let Err(e) =
-                        Self::apply_incremental_change(&mut doc.content, range, &change.text)
-                    {
+                    if let Err(e) = Self::apply_incremental_change(
+                        &mut doc.content,
+                        &mut doc.line_index,
+                        self.encoding,
+                        range,
+                        &change.text,
+                    ) {
                         tracing::error!("Failed to apply incremental change: {}", e);
                         // 降级到全量更新
+                        doc.line_index = LineIndex::new(&change.text);
                         doc.content = change.text;
                     }
                 } else {
                     // 全量修改
+                    doc.line_index = LineIndex::new(&change.text);
                     doc.content = change.text;
                 }
             }
@@ -68,28 +122,24 @@ impl DocumentManager {
     /// 应用增量修改
     fn apply_incremental_change(
         content: &mut String,
+        line_index: &mut LineIndex,
+        encoding: PositionEncoding,
         range: lsp_types::Range,
         text: &str,
     ) -> Result<(), String> {
-        // 将内容按行分割
-        let lines: Vec<&str> = content.lines().collect();
-
         // 验证范围的有效性
-        let start_line = range.start.line as usize;
-        let end_line = range.end.line as usize;
-
-        if start_line > lines.len() || end_line > lines.len() {
+        let line_count = line_index.line_count();
+        if range.start.line as usize >= line_count || range.end.line as usize >= line_count {
             return Err(format!(
                 "Invalid range: start_line={}, end_line={}, total_lines={}",
-                start_line,
-                end_line,
-                lines.len()
+                range.start.line, range.end.line, line_count
             ));
         }
 
         // 计算起始和结束位置的字节偏移
-        let start_offset = Self::position_to_offset(content, range.start)?;
-        let end_offset = Self::position_to_offset(content, range.end)?;
+        let mapper = PositionMapper::with_line_index(content, line_index, encoding);
+        let start_offset = Self::mapped_offset(&mapper, range.start)?;
+        let end_offset = Self::mapped_offset(&mapper, range.end)?;
 
         if start_offset > end_offset || end_offset > content.len() {
             return Err(format!(
@@ -108,37 +158,26 @@ impl DocumentManager {
         new_content.push_str(&content[end_offset..]);
 
         *content = new_content;
+        line_index.edit(start_offset..end_offset, text);
         Ok(())
     }
 
-    /// 将 LSP Position 转换为字节偏移
+    /// 将 LSP Position 转换为字节偏移（列按 UTF-16 代码单元计算）
+    #[cfg(test)]
     fn position_to_offset(content: &str, position: lsp_types::Position) -> Result<usize, String> {
-        let mut offset = 0;
-        let mut current_line = 0;
-        let target_line = position.line as usize;
-        let target_char = position.character as usize;
-
-        for line in content.lines() {
-            if current_line == target_line {
-                // 找到目标行，计算字符偏移
-                let char_offset = Self::char_offset_to_byte_offset(line, target_char)?;
-                return Ok(offset + char_offset);
-            }
-
-            // 移动到下一行（包括换行符）
-            offset += line.len() + 1; // +1 for '\n'
-            current_line += 1;
-        }
-
-        // 如果到达文件末尾
-        if current_line == target_line && target_char == 0 {
-            return Ok(offset);
-        }
+        Self::mapped_offset(&PositionMapper::new(content), position)
+    }
 
-        Err(format!(
-            "Position out of bounds: line={}, char={}, total_lines={}",
-            target_line, target_char, current_line
-        ))
+    fn mapped_offset(
+        mapper: &PositionMapper,
+        position: lsp_types::Position,
+    ) -> Result<usize, String> {
+        mapper.offset(position).ok_or_else(|| {
+            format!(
+                "Position out of bounds: line={}, char={}",
+                position.line, position.character
+            )
+        })
     }
 
     /// 将字符偏移转换为字节偏移
@@ -175,6 +214,11 @@ impl DocumentManager {
         self.documents.get(uri).map(|doc| doc.clone())
     }
 
+    /// 获取所有已打开文档的 URI
+    pub fn uris(&self) -> Vec<Url> {
+        self.documents.iter().map(|doc| doc.key().clone()).collect()
+    }
+
     /// 获取文档的只读引用（用于快速访问）
     pub fn with_document<F, R>(&self, uri: &Url, f: F) -> Option<R>
     where
@@ -182,6 +226,22 @@ impl DocumentManager {
     {
         self.documents.get(uri).map(|doc| f(&doc))
     }
+
+    /// 打开的文档内容占用的字节数
+    pub fn memory_usage(&self) -> usize {
+        self.documents.iter().map(|doc| doc.content.len()).sum()
+    }
+
+    /// 读取文件内容：文件在编辑器中打开时使用其内容（可能包含未保存的修改），否则从磁盘读取
+    pub fn read_file(&self, path: &Path) -> std::io::Result<String> {
+        let open = Url::from_file_path(path)
+            .ok()
+            .and_then(|uri| self.with_document(&uri, |doc| doc.content.clone()));
+        match open {
+            Some(content) => Ok(content),
+            None => std::fs::read_to_string(path),
+        }
+    }
 }
 
 impl Default for DocumentManager {
@@ -190,6 +250,280 @@ impl Default for DocumentManager {
     }
 }
 
+/// 项目扫描器读取源文件的方式
+///
+/// 默认从磁盘读取；语言服务器通过 [`SourceReader::overlay`] 让编辑器中打开的文档优先，
+/// 使项目扫描的结果与编辑器中尚未保存的内容一致
+#[derive(Clone, Default)]
+pub struct SourceReader {
+    documents: Option<Arc<DocumentManager>>,
+}
+
+impl SourceReader {
+    /// 优先读取文档管理器中打开的文档
+    pub fn overlay(documents: Arc<DocumentManager>) -> Self {
+        Self {
+            documents: Some(documents),
+        }
+    }
+
+    /// 读取文件内容
+    pub fn read(&self, path: &Path) -> std::io::Result<String> {
+        match &self.documents {
+            Some(documents) => documents.read_file(path),
+            None => std::fs::read_to_string(path),
+        }
+    }
+
+    /// 位置编码（与文档管理器一致，从磁盘读取时使用 UTF-16）
+    pub fn encoding(&self) -> PositionEncoding {
+        self.documents
+            .as_ref()
+            .map(|documents| documents.encoding())
+            .unwrap_or_default()
+    }
+
+    /// 为读取的内容创建位置转换器
+    pub fn mapper<'a>(&self, content: &'a str) -> PositionMapper<'a> {
+        PositionMapper::with_encoding(content, self.encoding())
+    }
+}
+
+impl std::fmt::Debug for SourceReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceReader")
+            .field("overlay", &self.documents.is_some())
+            .finish()
+    }
+}
+
+/// 位置编码（LSP 位置中列的计量单位）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PositionEncoding {
+    /// UTF-8 字节
+    Utf8,
+    /// UTF-16 代码单元（LSP 默认）
+    #[default]
+    Utf16,
+    /// Unicode 字符
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// 按客户端声明的偏好顺序选择第一个支持的编码，未声明时使用 UTF-16
+    pub fn negotiate(capabilities: &ClientCapabilities) -> Self {
+        capabilities
+            .general
+            .as_ref()
+            .and_then(|general| general.position_encodings.as_ref())
+            .and_then(|encodings| encodings.iter().find_map(Self::from_kind))
+            .unwrap_or_default()
+    }
+
+    fn from_kind(kind: &PositionEncodingKind) -> Option<Self> {
+        match kind.as_str() {
+            "utf-8" => Some(Self::Utf8),
+            "utf-16" => Some(Self::Utf16),
+            "utf-32" => Some(Self::Utf32),
+            _ => None,
+        }
+    }
+
+    /// 服务器能力中声明的编码
+    pub fn kind(self) -> PositionEncodingKind {
+        match self {
+            Self::Utf8 => PositionEncodingKind::UTF8,
+            Self::Utf16 => PositionEncodingKind::UTF16,
+            Self::Utf32 => PositionEncodingKind::UTF32,
+        }
+    }
+
+    /// 字符占用的列数
+    pub(crate) fn width(self, ch: char) -> usize {
+        match self {
+            Self::Utf8 => ch.len_utf8(),
+            Self::Utf16 => ch.len_utf16(),
+            Self::Utf32 => 1,
+        }
+    }
+}
+
+/// 行索引：每行起始的字节偏移
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    /// 扫描内容建立行索引
+    pub fn new(content: &str) -> Self {
+        Self {
+            line_starts: std::iter::once(0)
+                .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+        }
+    }
+
+    /// 行数（以换行符结尾的内容最后有一个空行）
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// 每行起始的字节偏移
+    pub fn line_starts(&self) -> &[usize] {
+        &self.line_starts
+    }
+
+    /// 把字节范围 `range` 替换为 `text` 后更新索引，只扫描插入的文本
+    pub fn edit(&mut self, range: std::ops::Range<usize>, text: &str) {
+        // 被删除的换行符之后的行起始位置在 (start, end] 之间
+        let first = self
+            .line_starts
+            .partition_point(|&start| start <= range.start);
+        let last = self
+            .line_starts
+            .partition_point(|&start| start <= range.end);
+
+        let shift = |start: usize| start + text.len() - (range.end - range.start);
+        let inserted = text
+            .match_indices('\n')
+            .map(|(i, _)| range.start + i + 1)
+            .collect::<Vec<_>>();
+        let tail = self.line_starts[last..]
+            .iter()
+            .map(|&start| shift(start))
+            .collect::<Vec<_>>();
+
+        self.line_starts.truncate(first);
+        self.line_starts.extend(inserted);
+        self.line_starts.extend(tail);
+    }
+}
+
+/// 位置转换器
+///
+/// 在文档内容的字节偏移与 LSP 位置之间转换，并把 syn 的 `Span`（行号从 1 开始、列为字符数）
+/// 转换为 LSP `Range`
+#[derive(Debug, Clone)]
+pub struct PositionMapper<'a> {
+    content: &'a str,
+    /// 每行起始的字节偏移
+    line_starts: Cow<'a, [usize]>,
+    encoding: PositionEncoding,
+}
+
+impl<'a> PositionMapper<'a> {
+    /// 使用 UTF-16 位置编码（LSP 默认）创建转换器
+    pub fn new(content: &'a str) -> Self {
+        Self::with_encoding(content, PositionEncoding::default())
+    }
+
+    /// 使用指定的位置编码创建转换器
+    pub fn with_encoding(content: &'a str, encoding: PositionEncoding) -> Self {
+        Self {
+            content,
+            line_starts: Cow::Owned(LineIndex::new(content).line_starts),
+            encoding,
+        }
+    }
+
+    /// 使用已建立的行索引（必须与 `content` 对应）和指定的位置编码创建转换器
+    pub fn with_line_index(
+        content: &'a str,
+        line_index: &'a LineIndex,
+        encoding: PositionEncoding,
+    ) -> Self {
+        Self {
+            content,
+            line_starts: Cow::Borrowed(&line_index.line_starts),
+            encoding,
+        }
+    }
+
+    /// 文档内容
+    pub fn content(&self) -> &'a str {
+        self.content
+    }
+
+    /// 位置编码
+    pub fn encoding(&self) -> PositionEncoding {
+        self.encoding
+    }
+
+    /// 字节偏移对应的位置（超出内容长度时返回文档末尾）
+    pub fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.content.len());
+        while !self.content.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character = self.content[self.line_starts[line]..offset]
+            .chars()
+            .map(|ch| self.encoding.width(ch))
+            .sum::<usize>();
+        Position::new(line as u32, character as u32)
+    }
+
+    /// 位置对应的字节偏移
+    ///
+    /// 行号超出范围时返回 `None`；列超出行尾时返回行尾，落在多字节字符中间时返回该字符的起始位置
+    pub fn offset(&self, position: Position) -> Option<usize> {
+        let line_start = *self.line_starts.get(position.line as usize)?;
+        let line = self.line_text(position.line as usize);
+
+        let mut column = 0;
+        for (i, ch) in line.char_indices() {
+            let width = self.encoding.width(ch);
+            if column + width > position.character as usize {
+                return Some(line_start + i);
+            }
+            column += width;
+        }
+        Some(line_start + line.len())
+    }
+
+    /// 字节偏移范围对应的 `Range`
+    pub fn range(&self, offsets: std::ops::Range<usize>) -> Range {
+        Range::new(self.position(offsets.start), self.position(offsets.end))
+    }
+
+    /// syn `Span` 对应的 `Range`
+    pub fn span_range(&self, span: proc_macro2::Span) -> Range {
+        let (start, end) = (span.start(), span.end());
+        Range::new(
+            self.line_column_position(start.line, start.column),
+            self.line_column_position(end.line, end.column),
+        )
+    }
+
+    /// 行列位置（行号从 1 开始，列为字符数，即 proc_macro2 的 `LineColumn`）对应的位置
+    pub fn line_column_position(&self, line: usize, column: usize) -> Position {
+        self.position(self.line_column_offset(line, column))
+    }
+
+    /// 行列位置（行号从 1 开始，列为字符数）对应的字节偏移（超出范围时返回行尾或文档末尾）
+    pub fn line_column_offset(&self, line: usize, column: usize) -> usize {
+        let line = line.saturating_sub(1);
+        let Some(&line_start) = self.line_starts.get(line) else {
+            return self.content.len();
+        };
+        let text = self.line_text(line);
+        line_start + DocumentManager::char_offset_to_byte_offset(text, column).unwrap_or(text.len())
+    }
+
+    /// 行的内容（不含换行符）
+    fn line_text(&self, line: usize) -> &'a str {
+        let start = self.line_starts[line];
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map(|next| next - 1)
+            .unwrap_or(self.content.len());
+        &self.content[start..end]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -208,6 +542,23 @@ mod tests {
         assert_eq!(doc.language_id, "toml");
     }
 
+    #[test]
+    fn test_source_reader_prefers_open_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, "on disk").unwrap();
+
+        let manager = Arc::new(DocumentManager::new());
+        let overlay = SourceReader::overlay(Arc::clone(&manager));
+        assert_eq!(overlay.read(&path).unwrap(), "on disk");
+
+        let uri = Url::from_file_path(&path).unwrap();
+        manager.open(uri, 1, "unsaved".to_string(), "rust".to_string());
+        assert_eq!(overlay.read(&path).unwrap(), "unsaved");
+        assert_eq!(SourceReader::default().read(&path).unwrap(), "on disk");
+        assert!(overlay.read(&dir.path().join("missing.rs")).is_err());
+    }
+
     #[test]
     fn test_close_document() {
         let manager = DocumentManager::new();
@@ -535,4 +886,146 @@ mod tests {
         // 第二次修改: "x b c" -> "x y c"
         assert_eq!(doc.content, "x y c");
     }
+
+    #[test]
+    fn test_position_mapper() {
+        // "名称" 每个字符 3 字节、1 个 UTF-16 代码单元，"🚀" 4 字节、2 个 UTF-16 代码单元
+        let content = "// 名称 🚀\nlet x = 1;\n";
+        let offset = content.find("🚀").unwrap() + "🚀".len();
+
+        let utf16 = PositionMapper::with_encoding(content, PositionEncoding::Utf16);
+        assert_eq!(utf16.position(offset), Position::new(0, 8));
+        assert_eq!(utf16.offset(Position::new(0, 8)), Some(offset));
+        assert_eq!(
+            utf16.position(content.find('x').unwrap()),
+            Position::new(1, 4)
+        );
+
+        let utf8 = PositionMapper::with_encoding(content, PositionEncoding::Utf8);
+        assert_eq!(utf8.position(offset), Position::new(0, offset as u32));
+
+        let utf32 = PositionMapper::with_encoding(content, PositionEncoding::Utf32);
+        assert_eq!(utf32.position(offset), Position::new(0, 7));
+        assert_eq!(utf32.line_column_position(2, 4), Position::new(1, 4));
+
+        // 列超出行尾时截断到行尾，行号超出范围时返回 None
+        assert_eq!(utf16.offset(Position::new(1, 99)), Some(content.len() - 1));
+        assert_eq!(utf16.offset(Position::new(5, 0)), None);
+        assert_eq!(utf16.position(content.len() + 10), Position::new(2, 0));
+    }
+
+    #[test]
+    fn test_span_range() {
+        let content = "/// 获取用户\nfn 用户() {}\n";
+        let file = syn::parse_file(content).unwrap();
+        let syn::Item::Fn(item_fn) = &file.items[0] else {
+            panic!("expected fn");
+        };
+
+        let mapper = PositionMapper::with_encoding(content, PositionEncoding::Utf8);
+        assert_eq!(
+            mapper.span_range(item_fn.sig.ident.span()),
+            Range::new(Position::new(1, 3), Position::new(1, 9))
+        );
+        let mapper = PositionMapper::with_encoding(content, PositionEncoding::Utf16);
+        assert_eq!(
+            mapper.span_range(item_fn.sig.ident.span()),
+            Range::new(Position::new(1, 3), Position::new(1, 5))
+        );
+    }
+
+    #[test]
+    fn test_negotiate_position_encoding() {
+        let mut capabilities = ClientCapabilities::default();
+        assert_eq!(
+            PositionEncoding::negotiate(&capabilities),
+            PositionEncoding::Utf16
+        );
+
+        capabilities.general = Some(lsp_types::GeneralClientCapabilities {
+            position_encodings: Some(vec![
+                PositionEncodingKind::new("utf-7"),
+                PositionEncodingKind::UTF8,
+                PositionEncodingKind::UTF16,
+            ]),
+            ..Default::default()
+        });
+        let encoding = PositionEncoding::negotiate(&capabilities);
+        assert_eq!(encoding, PositionEncoding::Utf8);
+        assert_eq!(encoding.kind(), PositionEncodingKind::UTF8);
+    }
+
+    #[test]
+    fn test_line_index_edit() {
+        let mut content = String::from("a\nbb\nccc\n");
+        let mut index = LineIndex::new(&content);
+        assert_eq!(index.line_starts(), &[0, 2, 5, 9]);
+
+        for (range, text) in [(1..5, ""), (0..0, "x\ny\n"), (4..4, "中\n文"), (2..8, "")] {
+            content.replace_range(range.clone(), text);
+            index.edit(range, text);
+            assert_eq!(index, LineIndex::new(&content), "content: {:?}", content);
+        }
+    }
+
+    #[test]
+    fn test_change_updates_line_index() {
+        let manager = DocumentManager::new();
+        let uri: Url = "file:///test.rs".parse().unwrap();
+        manager.open(
+            uri.clone(),
+            1,
+            "fn a() {}\n".to_string(),
+            "rust".to_string(),
+        );
+
+        manager.change(
+            &uri,
+            2,
+            vec![TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(0, 0), Position::new(0, 0))),
+                range_length: None,
+                text: "// 注释\n".to_string(),
+            }],
+        );
+
+        let doc = manager.get(&uri).unwrap();
+        assert_eq!(doc.content, "// 注释\nfn a() {}\n");
+        assert_eq!(doc.line_index(), &LineIndex::new(&doc.content));
+        assert_eq!(
+            doc.mapper().position(doc.content.find('a').unwrap()),
+            Position::new(1, 3)
+        );
+    }
+
+    #[test]
+    fn test_document_manager_encoding() {
+        let manager = DocumentManager::with_encoding(PositionEncoding::Utf8);
+        let uri: Url = "file:///test.rs".parse().unwrap();
+        manager.open(
+            uri.clone(),
+            1,
+            "// 注释 a\n".to_string(),
+            "rust".to_string(),
+        );
+
+        // 修改的范围和文档中的位置都按字节计算
+        manager.change(
+            &uri,
+            2,
+            vec![TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(0, 10), Position::new(0, 11))),
+                range_length: None,
+                text: "b".to_string(),
+            }],
+        );
+        let doc = manager.get(&uri).unwrap();
+        assert_eq!(doc.content, "// 注释 b\n");
+        assert_eq!(doc.mapper().position(10), Position::new(0, 10));
+        assert_eq!(
+            SourceReader::overlay(Arc::new(manager)).encoding(),
+            PositionEncoding::Utf8
+        );
+        assert_eq!(SourceReader::default().encoding(), PositionEncoding::Utf16);
+    }
 }
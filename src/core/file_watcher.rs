@@ -0,0 +1,268 @@
+//! 服务器端文件监听模块
+//!
+//! 服务器需要知道磁盘上的 `Cargo.lock`、`config/*.toml`、OpenAPI 规范文件、Rust 源文件、
+//! `.spring-lsp.toml` 和 `.spring-lsp/rules.toml` 的变化：插件版本变化时重新加载 Schema，
+//! 源文件和配置文件变化时增量重新验证，服务器配置或规则文件变化时重新加载。
+//! 通常由客户端通过动态注册的 `workspace/didChangeWatchedFiles` 通知这些变化；
+//! 客户端不支持动态注册时，启用 `file-watcher` feature 后服务器使用 notify 直接监听工作空间目录，
+//! 把磁盘上的变化转换为同样的 [`DidChangeWatchedFilesParams`] 交给服务器处理。
+//!
+//! 未启用 feature 时 [`FileWatcher::start`] 始终返回 `None`。
+//! `target` 目录和隐藏目录（如 `.git`）中的变化会被忽略，工作空间根目录下的规则文件除外。
+
+use std::path::Path;
+
+use crossbeam_channel::Receiver;
+
+use crate::core::config::WORKSPACE_CONFIG_FILE;
+use crate::core::security::SECURITY_RULES_FILE;
+use lsp_types::{DidChangeWatchedFilesParams, FileChangeType, FileEvent};
+
+/// 需要监听的文件（客户端注册文件监听时使用相同的 glob）
+pub const WATCHED_GLOBS: &[&str] = &[
+    "**/Cargo.lock",
+    "**/config/*.toml",
+    "**/openapi.{yaml,yml}",
+    "**/*.rs",
+    "**/.spring-lsp.toml",
+    "**/.spring-lsp/rules.toml",
+];
+
+/// 判断工作空间中的文件是否需要监听（与 [`WATCHED_GLOBS`] 一致）
+pub fn is_watched(root: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+    if relative == Path::new(WORKSPACE_CONFIG_FILE) || relative == Path::new(SECURITY_RULES_FILE) {
+        return true;
+    }
+    let ignored = relative.parent().is_some_and(|dir| {
+        dir.components().any(|component| {
+            let name = component.as_os_str().to_string_lossy();
+            name == "target" || name.starts_with('.')
+        })
+    });
+    if ignored {
+        return false;
+    }
+
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let in_config_dir = path
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .is_some_and(|dir| dir == "config");
+    name == "Cargo.lock"
+        || name == "openapi.yaml"
+        || name == "openapi.yml"
+        || name.ends_with(".rs")
+        || (in_config_dir && name.ends_with(".toml"))
+}
+
+/// 合并同一文件的多次变化，保持文件第一次出现的顺序
+///
+/// 以最后一次变化为准；新建之后的修改仍视为新建
+pub fn merge_changes(changes: impl IntoIterator<Item = FileEvent>) -> Vec<FileEvent> {
+    let mut merged: Vec<FileEvent> = Vec::new();
+    for change in changes {
+        match merged
+            .iter_mut()
+            .find(|existing| existing.uri == change.uri)
+        {
+            Some(existing) => {
+                if !(existing.typ == FileChangeType::CREATED
+                    && change.typ == FileChangeType::CHANGED)
+                {
+                    existing.typ = change.typ;
+                }
+            }
+            None => merged.push(change),
+        }
+    }
+    merged
+}
+
+/// 服务器端文件监听器
+///
+/// 监听器在后台线程中接收文件系统事件，服务器在事件循环中从 [`FileWatcher::receiver`] 读取变化
+pub struct FileWatcher {
+    /// 变化的接收端
+    receiver: Receiver<FileEvent>,
+    /// 监听器（释放时停止监听）
+    #[cfg(feature = "file-watcher")]
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl FileWatcher {
+    /// 开始监听工作空间目录，监听失败时返回 `None`
+    #[cfg(feature = "file-watcher")]
+    pub fn start(root: &Path) -> Option<Self> {
+        use notify::{RecursiveMode, Watcher};
+
+        // 事件中的路径是规范化的绝对路径
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let watch_root = root.clone();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(e) => {
+                        tracing::debug!("File watcher error: {}", e);
+                        return;
+                    }
+                };
+                for (path, typ) in change_types(&event) {
+                    if !is_watched(&watch_root, &path) {
+                        continue;
+                    }
+                    if let Ok(uri) = lsp_types::Url::from_file_path(&path) {
+                        let _ = sender.send(FileEvent { uri, typ });
+                    }
+                }
+            })
+            .map_err(|e| tracing::warn!("Failed to create file watcher: {}", e))
+            .ok()?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .map_err(|e| tracing::warn!("Failed to watch {}: {}", root.display(), e))
+            .ok()?;
+
+        tracing::info!("Watching {} for file changes", root.display());
+        Some(Self {
+            receiver,
+            _watcher: watcher,
+        })
+    }
+
+    /// 未启用 `file-watcher` feature，不监听文件
+    #[cfg(not(feature = "file-watcher"))]
+    pub fn start(root: &Path) -> Option<Self> {
+        tracing::debug!(
+            "File watcher feature disabled, not watching {}",
+            root.display()
+        );
+        None
+    }
+
+    /// 变化的接收端
+    pub fn receiver(&self) -> &Receiver<FileEvent> {
+        &self.receiver
+    }
+
+    /// 将第一个变化和之后已经到达的变化合并为一次通知
+    pub fn collect(&self, first: FileEvent) -> DidChangeWatchedFilesParams {
+        let changes = std::iter::once(first).chain(self.receiver.try_iter());
+        DidChangeWatchedFilesParams {
+            changes: merge_changes(changes),
+        }
+    }
+}
+
+/// 文件系统事件对应的变化
+#[cfg(feature = "file-watcher")]
+fn change_types(event: &notify::Event) -> Vec<(std::path::PathBuf, FileChangeType)> {
+    use notify::event::{ModifyKind, RenameMode};
+    use notify::EventKind;
+
+    let typ = match event.kind {
+        EventKind::Create(_) => FileChangeType::CREATED,
+        EventKind::Remove(_) => FileChangeType::DELETED,
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => FileChangeType::DELETED,
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => FileChangeType::CREATED,
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            // 路径依次为重命名前和重命名后
+            let types = [FileChangeType::DELETED, FileChangeType::CREATED];
+            return event.paths.iter().cloned().zip(types).collect();
+        }
+        EventKind::Modify(ModifyKind::Name(_)) => {
+            return event
+                .paths
+                .iter()
+                .map(|path| {
+                    let typ = if path.exists() {
+                        FileChangeType::CREATED
+                    } else {
+                        FileChangeType::DELETED
+                    };
+                    (path.clone(), typ)
+                })
+                .collect();
+        }
+        EventKind::Modify(_) | EventKind::Any => FileChangeType::CHANGED,
+        EventKind::Access(_) | EventKind::Other => return Vec::new(),
+    };
+    event.paths.iter().map(|path| (path.clone(), typ)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Url;
+
+    #[test]
+    fn test_is_watched() {
+        let root = Path::new("/work");
+        for path in [
+            "/work/Cargo.lock",
+            "/work/app/config/app-dev.toml",
+            "/work/app/src/api/users.rs",
+            "/work/app/docs/openapi.yaml",
+            "/work/.spring-lsp.toml",
+            "/work/.spring-lsp/rules.toml",
+        ] {
+            assert!(is_watched(root, Path::new(path)), "{}", path);
+        }
+        for path in [
+            "/work/app/Cargo.toml",
+            "/work/app/README.md",
+            "/work/target/debug/build/app-1/out/routes.rs",
+            "/work/.git/index",
+            "/work/app/.spring-lsp.toml",
+            "/work/.spring-lsp/baseline.json",
+            "/work/app/.spring-lsp/rules.toml",
+            "/other/src/main.rs",
+        ] {
+            assert!(!is_watched(root, Path::new(path)), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_merge_changes() {
+        let event = |name: &str, typ| FileEvent {
+            uri: Url::parse(&format!("file:///work/src/{}.rs", name)).unwrap(),
+            typ,
+        };
+        let merged = merge_changes([
+            event("a", FileChangeType::CREATED),
+            event("b", FileChangeType::CHANGED),
+            event("a", FileChangeType::CHANGED),
+            event("b", FileChangeType::DELETED),
+        ]);
+        assert_eq!(
+            merged,
+            vec![
+                event("a", FileChangeType::CREATED),
+                event("b", FileChangeType::DELETED)
+            ]
+        );
+    }
+
+    #[cfg(feature = "file-watcher")]
+    #[test]
+    fn test_watch_source_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        let watcher = FileWatcher::start(dir.path()).unwrap();
+
+        std::fs::write(dir.path().join("README.md"), "# app\n").unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let first = watcher
+            .receiver()
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap();
+        assert!(first.uri.path().ends_with("src/main.rs"));
+    }
+}
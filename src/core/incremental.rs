@@ -0,0 +1,408 @@
+//! 增量计算模块
+//!
+//! 手写的查询记忆化（类似 salsa 的简化版本），用于跨文件分析：
+//! - 输入（[`Inputs`]）是按键存储的文件内容，每次内容变化都会推进全局修订号
+//! - 查询（[`Query`]）缓存派生值，并记录计算时读取了哪些输入以及这些输入当时的修订号
+//! - 再次请求时，只有读取过的输入发生变化的查询才重新计算，其余直接复用缓存
+//!
+//! 查询的计算函数通过 [`Tracker`] 读取输入，依赖由此自动记录；在计算函数中请求另一个查询
+//! （[`Query::get_tracked`]）时，内层查询的依赖会合并到外层查询中。
+//!
+//! 内存紧张时可以释放输入的内容（[`Inputs::evict`]），只保留内容的哈希：
+//! 之后设置相同的内容会恢复内容而不推进修订号，依赖它的查询仍然有效。
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// 修订号（每次输入变化加一）
+pub type Revision = u64;
+
+/// 单个输入
+struct Input {
+    /// 内容（已删除或已释放的输入为 `None`）
+    content: Option<String>,
+    /// 内容的哈希
+    hash: u64,
+    /// 内容是否已被释放（[`Inputs::evict`]）
+    evicted: bool,
+    /// 内容最近一次变化时的修订号
+    changed_at: Revision,
+}
+
+impl Input {
+    /// 输入是否存在（没有被删除）
+    fn exists(&self) -> bool {
+        self.content.is_some() || self.evicted
+    }
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 查询的输入
+pub struct Inputs<K> {
+    entries: HashMap<K, Input>,
+    revision: Revision,
+}
+
+impl<K: Eq + Hash + Clone> Inputs<K> {
+    /// 创建空的输入集合
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            revision: 0,
+        }
+    }
+
+    /// 当前修订号
+    pub fn revision(&self) -> Revision {
+        self.revision
+    }
+
+    /// 设置输入内容，内容变化时推进修订号并返回 `true`
+    ///
+    /// 内容已被释放且与释放前相同时只恢复内容
+    pub fn set(&mut self, key: K, content: &str) -> bool {
+        let hash = content_hash(content);
+        if let Some(input) = self.entries.get_mut(&key) {
+            if input.content.as_deref() == Some(content) {
+                return false;
+            }
+            if input.evicted && input.hash == hash {
+                input.content = Some(content.to_string());
+                input.evicted = false;
+                return false;
+            }
+        }
+        self.revision += 1;
+        self.entries.insert(
+            key,
+            Input {
+                content: Some(content.to_string()),
+                hash,
+                evicted: false,
+                changed_at: self.revision,
+            },
+        );
+        true
+    }
+
+    /// 删除输入，输入存在时推进修订号并返回 `true`
+    ///
+    /// 保留删除记录，读取过该输入的查询会重新计算
+    pub fn remove(&mut self, key: &K) -> bool {
+        let Some(input) = self.entries.get_mut(key) else {
+            return false;
+        };
+        if !input.exists() {
+            return false;
+        }
+        self.revision += 1;
+        input.content = None;
+        input.evicted = false;
+        input.changed_at = self.revision;
+        true
+    }
+
+    /// 只保留满足条件的输入，返回删除的输入数
+    pub fn retain(&mut self, mut keep: impl FnMut(&K) -> bool) -> usize {
+        let removed: Vec<K> = self
+            .entries
+            .iter()
+            .filter(|(key, input)| input.exists() && !keep(key))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &removed {
+            self.remove(key);
+        }
+        removed.len()
+    }
+
+    /// 释放不满足条件的输入的内容（保留哈希和修订号），返回释放的字节数
+    ///
+    /// 被释放的输入在重新设置之前读取不到内容，调用方需要在下一次计算前重新设置所有输入
+    pub fn evict(&mut self, mut keep: impl FnMut(&K) -> bool) -> usize {
+        let mut freed = 0;
+        for (key, input) in &mut self.entries {
+            if input.content.is_some() && !keep(key) {
+                freed += input.content.take().map_or(0, |content| content.len());
+                input.evicted = true;
+            }
+        }
+        freed
+    }
+
+    /// 输入内容占用的字节数
+    pub fn memory_usage(&self) -> usize {
+        self.entries
+            .values()
+            .filter_map(|input| input.content.as_ref())
+            .map(String::len)
+            .sum()
+    }
+
+    /// 输入内容（不记录依赖）
+    pub fn get(&self, key: &K) -> Option<&str> {
+        self.entries.get(key)?.content.as_deref()
+    }
+
+    /// 输入最近一次变化时的修订号（从未设置过的输入为 0）
+    fn changed_at(&self, key: &K) -> Revision {
+        self.entries.get(key).map_or(0, |input| input.changed_at)
+    }
+}
+
+impl<K: Eq + Hash + Clone> Default for Inputs<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 依赖记录器
+///
+/// 查询计算期间读取的输入都会被记录为该查询的依赖
+pub struct Tracker<'a, K> {
+    inputs: &'a Inputs<K>,
+    deps: Vec<(K, Revision)>,
+}
+
+impl<'a, K: Eq + Hash + Clone> Tracker<'a, K> {
+    /// 读取输入并记录依赖
+    pub fn read(&mut self, key: &K) -> Option<&'a str> {
+        self.deps.push((key.clone(), self.inputs.changed_at(key)));
+        self.inputs.get(key)
+    }
+}
+
+/// 缓存的查询结果
+struct Memo<K, V> {
+    value: Arc<V>,
+    /// 读取过的输入及读取时的修订号
+    deps: Vec<(K, Revision)>,
+}
+
+impl<K: Eq + Hash + Clone, V> Memo<K, V> {
+    /// 读取过的输入都没有变化
+    fn is_valid(&self, inputs: &Inputs<K>) -> bool {
+        self.deps
+            .iter()
+            .all(|(key, revision)| inputs.changed_at(key) == *revision)
+    }
+}
+
+/// 查询计算统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryStats {
+    /// 重新计算的次数
+    pub computed: u64,
+    /// 复用缓存的次数
+    pub reused: u64,
+}
+
+/// 记忆化的查询
+///
+/// `Q` 是查询键（如文件 URI），`K` 是输入键，`V` 是查询结果
+pub struct Query<Q, K, V> {
+    memos: HashMap<Q, Memo<K, V>>,
+    stats: QueryStats,
+}
+
+impl<Q: Eq + Hash + Clone, K: Eq + Hash + Clone, V> Query<Q, K, V> {
+    /// 创建空的查询
+    pub fn new() -> Self {
+        Self {
+            memos: HashMap::new(),
+            stats: QueryStats::default(),
+        }
+    }
+
+    /// 获取查询结果，依赖的输入没有变化时复用缓存
+    pub fn get(
+        &mut self,
+        inputs: &Inputs<K>,
+        key: &Q,
+        compute: impl FnOnce(&mut Tracker<K>) -> V,
+    ) -> Arc<V> {
+        self.get_with_deps(inputs, key, compute).0
+    }
+
+    /// 在另一个查询的计算函数中获取查询结果，依赖合并到外层查询
+    pub fn get_tracked(
+        &mut self,
+        tracker: &mut Tracker<K>,
+        key: &Q,
+        compute: impl FnOnce(&mut Tracker<K>) -> V,
+    ) -> Arc<V> {
+        let (value, deps) = self.get_with_deps(tracker.inputs, key, compute);
+        tracker.deps.extend(deps);
+        value
+    }
+
+    fn get_with_deps(
+        &mut self,
+        inputs: &Inputs<K>,
+        key: &Q,
+        compute: impl FnOnce(&mut Tracker<K>) -> V,
+    ) -> (Arc<V>, Vec<(K, Revision)>) {
+        if let Some(memo) = self.memos.get(key) {
+            if memo.is_valid(inputs) {
+                self.stats.reused += 1;
+                return (memo.value.clone(), memo.deps.clone());
+            }
+        }
+
+        let mut tracker = Tracker {
+            inputs,
+            deps: Vec::new(),
+        };
+        let value = Arc::new(compute(&mut tracker));
+        self.stats.computed += 1;
+        self.memos.insert(
+            key.clone(),
+            Memo {
+                value: value.clone(),
+                deps: tracker.deps.clone(),
+            },
+        );
+        (value, tracker.deps)
+    }
+
+    /// 只保留满足条件的缓存
+    pub fn retain(&mut self, mut keep: impl FnMut(&Q) -> bool) {
+        self.memos.retain(|key, _| keep(key));
+    }
+
+    /// 清空缓存（查询依赖了没有作为输入记录的状态，如组件索引，且该状态发生变化时）
+    pub fn clear(&mut self) {
+        self.memos.clear();
+    }
+
+    /// 缓存的结果数
+    pub fn len(&self) -> usize {
+        self.memos.len()
+    }
+
+    /// 是否没有缓存的结果
+    pub fn is_empty(&self) -> bool {
+        self.memos.is_empty()
+    }
+
+    /// 估算缓存的结果占用的字节数（`size` 估算单个结果）
+    pub fn memory_usage(&self, size: impl Fn(&V) -> usize) -> usize {
+        self.memos.values().map(|memo| size(&memo.value)).sum()
+    }
+
+    /// 计算统计
+    pub fn stats(&self) -> QueryStats {
+        self.stats
+    }
+}
+
+impl<Q: Eq + Hash + Clone, K: Eq + Hash + Clone, V> Default for Query<Q, K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Lengths = Query<String, String, usize>;
+
+    /// 所有文件的总长度（每个文件的长度是单独的查询）
+    fn total_length(
+        inputs: &Inputs<String>,
+        total: &mut Query<(), String, usize>,
+        lengths: &mut Lengths,
+    ) -> usize {
+        *total.get(inputs, &(), |tracker| {
+            ["a.rs", "b.rs", "c.rs"]
+                .iter()
+                .map(|file| file.to_string())
+                .map(|file| {
+                    *lengths.get_tracked(tracker, &file, |tracker| {
+                        tracker.read(&file).map_or(0, str::len)
+                    })
+                })
+                .sum()
+        })
+    }
+
+    #[test]
+    fn test_query_recomputes_only_changed_dependencies() {
+        let mut inputs = Inputs::new();
+        assert!(inputs.set("a.rs".to_string(), "a"));
+        assert!(inputs.set("b.rs".to_string(), "bb"));
+        assert!(!inputs.set("a.rs".to_string(), "a"));
+        assert_eq!(inputs.revision(), 2);
+
+        let mut total = Query::new();
+        let mut lengths = Lengths::new();
+        assert_eq!(total_length(&inputs, &mut total, &mut lengths), 3);
+        assert_eq!(
+            lengths.stats(),
+            QueryStats {
+                computed: 3,
+                reused: 0
+            }
+        );
+
+        // 没有变化时整个查询树直接复用
+        assert_eq!(total_length(&inputs, &mut total, &mut lengths), 3);
+        assert_eq!(lengths.stats().computed, 3);
+        assert_eq!(total.stats().reused, 1);
+
+        // 只有读取了变化输入的查询重新计算（包括读取了之前不存在的输入的查询）
+        inputs.set("c.rs".to_string(), "cccc");
+        assert_eq!(total_length(&inputs, &mut total, &mut lengths), 7);
+        assert_eq!(
+            lengths.stats(),
+            QueryStats {
+                computed: 4,
+                reused: 2
+            }
+        );
+
+        assert!(inputs.remove(&"b.rs".to_string()));
+        assert!(!inputs.remove(&"b.rs".to_string()));
+        assert_eq!(total_length(&inputs, &mut total, &mut lengths), 5);
+        assert_eq!(lengths.stats().computed, 5);
+
+        assert_eq!(inputs.retain(|file| file != "c.rs"), 1);
+        assert_eq!(total_length(&inputs, &mut total, &mut lengths), 1);
+    }
+
+    #[test]
+    fn test_evicted_inputs_keep_queries_valid() {
+        let mut inputs = Inputs::new();
+        inputs.set("a.rs".to_string(), "a");
+        inputs.set("b.rs".to_string(), "bb");
+        let mut total = Query::new();
+        let mut lengths = Lengths::new();
+        assert_eq!(total_length(&inputs, &mut total, &mut lengths), 3);
+        assert_eq!(inputs.memory_usage(), 3);
+
+        assert_eq!(inputs.evict(|file| file == "a.rs"), 2);
+        assert_eq!(inputs.memory_usage(), 1);
+        assert!(inputs.get(&"b.rs".to_string()).is_none());
+
+        // 恢复相同的内容不推进修订号，查询直接复用
+        let revision = inputs.revision();
+        assert!(!inputs.set("b.rs".to_string(), "bb"));
+        assert_eq!(inputs.revision(), revision);
+        assert_eq!(total_length(&inputs, &mut total, &mut lengths), 3);
+        assert_eq!(total.stats().computed, 1);
+
+        // 释放后内容变化时正常推进修订号
+        inputs.evict(|_| false);
+        assert!(inputs.set("b.rs".to_string(), "bbbb"));
+        assert!(inputs.remove(&"a.rs".to_string()));
+        assert_eq!(total_length(&inputs, &mut total, &mut lengths), 4);
+    }
+}
@@ -2,9 +2,19 @@
 //!
 //! 提供项目级别的索引管理，包括符号索引、路由索引和组件索引。
 //! 使用并发安全的数据结构支持多线程访问。
+//!
+//! 符号、路由和组件都记录完整路径（如 `crate::handlers::users::get_user`），
+//! 查找时可以使用名称（可能匹配不同模块中的多个条目）或完整路径（精确匹配）。
+//!
+//! 索引管理器还提供一致性检查（`spring.verifyIndex` 命令），
+//! 用于发现并修复指向已删除文件、越界位置或键不一致的索引条目。
 
+use crate::analysis::rust::module_path::qualified_name_for_uri;
+use crate::core::document::{DocumentManager, PositionMapper};
 use dashmap::DashMap;
-use lsp_types::{Location, Url};
+use lsp_types::{Location, Range, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 /// 符号信息
@@ -12,6 +22,8 @@ use std::sync::{Arc, RwLock};
 pub struct SymbolInfo {
     /// 符号名称
     pub name: String,
+    /// 完整路径（如 `crate::handlers::users::get_user`），文件不属于 crate 的模块树时为 `None`
+    pub qualified_name: Option<String>,
     /// 符号类型
     pub symbol_type: SymbolType,
     /// 位置
@@ -23,6 +35,10 @@ pub struct SymbolInfo {
 pub enum SymbolType {
     /// 结构体
     Struct,
+    /// 枚举
+    Enum,
+    /// 类型别名
+    TypeAlias,
     /// 函数
     Function,
     /// 常量
@@ -54,13 +70,80 @@ impl SymbolIndex {
     }
 
     /// 查找符号
+    ///
+    /// `name` 包含 `::` 时按完整路径精确匹配，否则返回所有同名符号
     pub fn find(&self, name: &str) -> Vec<SymbolInfo> {
+        let Some((_, bare)) = name.rsplit_once("::") else {
+            return self
+                .symbols
+                .get(name)
+                .map(|v| v.clone())
+                .unwrap_or_default();
+        };
         self.symbols
-            .get(name)
-            .map(|v| v.clone())
+            .get(bare)
+            .map(|symbols| {
+                symbols
+                    .iter()
+                    .filter(|symbol| symbol.qualified_name.as_deref() == Some(name))
+                    .cloned()
+                    .collect()
+            })
             .unwrap_or_default()
     }
 
+    /// 移除文档中定义的所有符号
+    pub fn remove_document(&self, uri: &Url) {
+        for mut entry in self.symbols.iter_mut() {
+            entry
+                .value_mut()
+                .retain(|symbol| symbol.location.uri != *uri);
+        }
+        self.symbols.retain(|_, symbols| !symbols.is_empty());
+    }
+
+    /// 索引文档中定义的符号（顶层条目和行内模块中的条目，位置为条目名称）
+    ///
+    /// 先移除文档之前的符号；文档无法解析时保留之前的符号
+    pub fn index_document(&self, uri: &Url, lines: &PositionMapper) {
+        let Ok(file) = syn::parse_file(lines.content()) else {
+            return;
+        };
+        self.remove_document(uri);
+        self.add_items(uri, lines, &file.items, "");
+    }
+
+    fn add_items(&self, uri: &Url, lines: &PositionMapper, items: &[syn::Item], module: &str) {
+        for item in items {
+            let (ident, symbol_type) = match item {
+                syn::Item::Struct(item) => (&item.ident, SymbolType::Struct),
+                syn::Item::Enum(item) => (&item.ident, SymbolType::Enum),
+                syn::Item::Type(item) => (&item.ident, SymbolType::TypeAlias),
+                syn::Item::Fn(item) => (&item.sig.ident, SymbolType::Function),
+                syn::Item::Const(item) => (&item.ident, SymbolType::Const),
+                syn::Item::Static(item) => (&item.ident, SymbolType::Static),
+                syn::Item::Mod(item) => {
+                    if let Some((_, items)) = &item.content {
+                        let inner = format!("{}{}::", module, item.ident);
+                        self.add_items(uri, lines, items, &inner);
+                    }
+                    (&item.ident, SymbolType::Module)
+                }
+                _ => continue,
+            };
+            let name = ident.to_string();
+            self.add(
+                name.clone(),
+                SymbolInfo {
+                    qualified_name: qualified_name_for_uri(uri, &format!("{}{}", module, name)),
+                    name,
+                    symbol_type,
+                    location: Location::new(uri.clone(), lines.span_range(ident.span())),
+                },
+            );
+        }
+    }
+
     /// 清空索引
     pub fn clear(&self) {
         self.symbols.clear();
@@ -78,12 +161,23 @@ impl Default for SymbolIndex {
 pub struct ComponentInfo {
     /// 组件名称
     pub name: String,
+    /// 定义组件的条目（Service 结构体或 `#[component]` 函数）的完整路径
+    pub qualified_name: Option<String>,
     /// 类型名称
     pub type_name: String,
     /// 位置
     pub location: Location,
     /// 所属插件
     pub plugin: Option<String>,
+    /// 注册组件的条件（`#[cfg(...)]`，如 `feature = "redis"`），无条件注册时为 `None`
+    pub cfg: Option<String>,
+}
+
+impl ComponentInfo {
+    /// 组件在索引中的键：完整路径，没有时为组件名称
+    pub fn key(&self) -> &str {
+        self.qualified_name.as_deref().unwrap_or(&self.name)
+    }
 }
 
 /// 组件索引
@@ -107,8 +201,19 @@ impl ComponentIndex {
     }
 
     /// 查找组件
+    ///
+    /// 组件以完整路径（没有时以名称）为键，按名称查找时返回任意一个同名组件
     pub fn find(&self, name: &str) -> Option<ComponentInfo> {
-        self.components.get(name).map(|v| v.clone())
+        if let Some(component) = self.components.get(name) {
+            return Some(component.clone());
+        }
+        if name.contains("::") {
+            return None;
+        }
+        self.components
+            .iter()
+            .find(|component| component.name == name)
+            .map(|component| component.clone())
     }
 
     /// 清空索引
@@ -135,6 +240,8 @@ pub struct Workspace {
 ///
 /// 管理项目级别的索引，包括符号索引、路由索引和组件索引。
 /// 使用 RwLock 保护索引结构，因为重建索引时需要整体替换。
+/// 克隆得到的索引管理器与原实例共享同一份索引。
+#[derive(Clone)]
 pub struct IndexManager {
     /// 符号索引（使用 RwLock 因为重建时需要整体替换）
     symbol_index: Arc<RwLock<SymbolIndex>>,
@@ -191,16 +298,100 @@ impl IndexManager {
     /// 增量更新索引
     ///
     /// 当单个文档发生变化时，只更新该文档相关的索引条目。
-    pub fn update(&self, uri: &Url, _content: &str) {
-        // 解析文档并更新索引
-        // 这里简化实现，实际应该解析文档并提取符号、路由和组件信息
-
+    /// 目前只更新符号索引（见 [`SymbolIndex::index_document`]）
+    pub fn update(&self, uri: &Url, lines: &PositionMapper) {
         tracing::debug!("Updating index for {}", uri);
 
-        // TODO: 实现增量更新逻辑
-        // 1. 解析文档
-        // 2. 提取符号、路由和组件
-        // 3. 更新对应的索引
+        self.symbol_index
+            .read()
+            .expect("Failed to acquire read lock on symbol index")
+            .index_document(uri, lines);
+
+        // TODO: 更新路由和组件索引
+    }
+
+    /// 文件重命名或移动后更新索引条目的位置
+    ///
+    /// 文件内容不变，只把指向 `old_uri` 的条目改为指向 `new_uri`，不需要重新扫描。
+    ///
+    /// # 返回
+    ///
+    /// 更新的条目数
+    pub fn rename_file(&self, old_uri: &Url, new_uri: &Url) -> usize {
+        let mut renamed = 0;
+
+        {
+            let index = self
+                .symbol_index
+                .read()
+                .expect("Failed to acquire read lock on symbol index");
+            for mut entry in index.symbols.iter_mut() {
+                for symbol in entry.value_mut() {
+                    if symbol.location.uri == *old_uri {
+                        symbol.location.uri = new_uri.clone();
+                        symbol.qualified_name = qualified_name_for_uri(new_uri, &symbol.name);
+                        renamed += 1;
+                    }
+                }
+            }
+        }
+
+        {
+            let mut index = self
+                .route_index
+                .write()
+                .expect("Failed to acquire write lock on route index");
+            let keys: Vec<String> = index
+                .entries()
+                .filter(|(_, route)| route.location.uri == *old_uri)
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in keys {
+                if let Some(mut route) = index.remove_route(&key) {
+                    route.location.uri = new_uri.clone();
+                    // 文件移动后处理器所在的模块随之改变
+                    route.handler_path = qualified_name_for_uri(new_uri, &route.handler);
+                    index.add_route(route);
+                    renamed += 1;
+                }
+            }
+        }
+
+        {
+            let index = self
+                .component_index
+                .read()
+                .expect("Failed to acquire read lock on component index");
+            let keys: Vec<String> = index
+                .components
+                .iter()
+                .filter(|entry| entry.location.uri == *old_uri)
+                .map(|entry| entry.key().clone())
+                .collect();
+            for key in keys {
+                if let Some((_, mut component)) = index.components.remove(&key) {
+                    // 完整路径的最后一段是定义组件的条目名称
+                    let item = component
+                        .qualified_name
+                        .as_deref()
+                        .and_then(|path| path.rsplit("::").next())
+                        .unwrap_or(&component.name)
+                        .to_string();
+                    component.location.uri = new_uri.clone();
+                    component.qualified_name = qualified_name_for_uri(new_uri, &item);
+                    index.add(component.key().to_string(), component);
+                    renamed += 1;
+                }
+            }
+        }
+
+        tracing::debug!(
+            "Renamed {} index entries from {} to {}",
+            renamed,
+            old_uri,
+            new_uri
+        );
+        renamed
     }
 
     /// 查找符号
@@ -212,6 +403,15 @@ impl IndexManager {
         index.find(name)
     }
 
+    /// 添加组件
+    pub fn add_component(&self, info: ComponentInfo) {
+        let index = self
+            .component_index
+            .read()
+            .expect("Failed to acquire read lock on component index");
+        index.add(info.key().to_string(), info);
+    }
+
     /// 查找组件
     pub fn find_component(&self, name: &str) -> Option<ComponentInfo> {
         let index = self
@@ -221,6 +421,36 @@ impl IndexManager {
         index.find(name)
     }
 
+    /// 索引是否为空（尚未构建）
+    pub fn is_empty(&self) -> bool {
+        let symbols_empty = self
+            .symbol_index
+            .read()
+            .expect("Failed to acquire read lock on symbol index")
+            .symbols
+            .is_empty();
+        let components_empty = self
+            .component_index
+            .read()
+            .expect("Failed to acquire read lock on component index")
+            .components
+            .is_empty();
+        symbols_empty && components_empty
+    }
+
+    /// 按处理器函数名或完整路径查找路由（见 [`RouteIndex::find_routes_by_handler`](crate::scanner::route::RouteIndex::find_routes_by_handler)）
+    pub fn find_routes_by_handler(&self, handler: &str) -> Vec<crate::scanner::route::Route> {
+        let index = self
+            .route_index
+            .read()
+            .expect("Failed to acquire read lock on route index");
+        index
+            .find_routes_by_handler(handler)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
     /// 获取所有路由
     pub fn get_all_routes(&self) -> Vec<crate::scanner::route::Route> {
         let index = self
@@ -230,6 +460,125 @@ impl IndexManager {
         index.all_routes().into_iter().cloned().collect()
     }
 
+    /// 验证索引一致性并修复不一致的条目
+    ///
+    /// 检查内容：
+    /// - 每个条目的 URI 对应的文档存在（已打开的文档或磁盘上的文件）
+    /// - 条目的位置范围在文档边界之内
+    /// - 路由索引的键与路由的方法和路径一致
+    ///
+    /// 指向不存在文档或越界位置的条目会被移除，键不一致的路由会被重新索引。
+    pub fn verify_and_repair(&self, documents: &DocumentManager) -> IndexVerificationReport {
+        let mut report = IndexVerificationReport::default();
+        let mut contents = DocumentContents::new(documents);
+
+        // 1. 符号索引
+        {
+            let index = self
+                .symbol_index
+                .read()
+                .expect("Failed to acquire read lock on symbol index");
+
+            for mut entry in index.symbols.iter_mut() {
+                let name = entry.key().clone();
+                entry.value_mut().retain(|symbol| {
+                    report.checked += 1;
+                    match contents.check_location(&symbol.location) {
+                        Ok(()) => true,
+                        Err(kind) => {
+                            report.push(IndexIssue::new(
+                                kind,
+                                IndexKind::Symbol,
+                                &name,
+                                &symbol.location,
+                            ));
+                            false
+                        }
+                    }
+                });
+            }
+            index.symbols.retain(|_, symbols| !symbols.is_empty());
+        }
+
+        // 2. 路由索引
+        {
+            let mut index = self
+                .route_index
+                .write()
+                .expect("Failed to acquire write lock on route index");
+
+            let mut removed = Vec::new();
+            let mut rekeyed = Vec::new();
+            for (key, route) in index.entries() {
+                report.checked += 1;
+                let name = format!("{} {}", route.method.as_str(), route.path);
+
+                if let Err(kind) = contents.check_location(&route.location) {
+                    report.push(IndexIssue::new(
+                        kind,
+                        IndexKind::Route,
+                        &name,
+                        &route.location,
+                    ));
+                    removed.push(key.clone());
+                } else if *key != crate::scanner::route::RouteIndex::route_key(route) {
+                    report.push(IndexIssue::new(
+                        IndexIssueKind::KeyMismatch,
+                        IndexKind::Route,
+                        &name,
+                        &route.location,
+                    ));
+                    rekeyed.push(key.clone());
+                }
+            }
+
+            for key in removed {
+                index.remove_route(&key);
+            }
+            for key in rekeyed {
+                if let Some(route) = index.remove_route(&key) {
+                    index.add_route(route);
+                }
+            }
+        }
+
+        // 3. 组件索引
+        {
+            let index = self
+                .component_index
+                .read()
+                .expect("Failed to acquire read lock on component index");
+
+            index.components.retain(|name, component| {
+                report.checked += 1;
+                match contents.check_location(&component.location) {
+                    Ok(()) => true,
+                    Err(kind) => {
+                        report.push(IndexIssue::new(
+                            kind,
+                            IndexKind::Component,
+                            name,
+                            &component.location,
+                        ));
+                        false
+                    }
+                }
+            });
+        }
+
+        if report.issues.is_empty() {
+            tracing::info!("Index verification passed ({} entries)", report.checked);
+        } else {
+            tracing::warn!(
+                "Index verification repaired {} of {} entries",
+                report.repaired,
+                report.checked
+            );
+        }
+
+        report
+    }
+
     /// 构建符号索引（内部方法）
     async fn build_symbol_index(_root_uri: &Url, _documents: &[(Url, String)]) -> SymbolIndex {
         // TODO: 实现符号索引构建
@@ -269,3 +618,429 @@ impl Default for IndexManager {
         Self::new()
     }
 }
+
+/// 索引一致性验证报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexVerificationReport {
+    /// 检查的条目数
+    pub checked: usize,
+    /// 修复的条目数
+    pub repaired: usize,
+    /// 发现的问题
+    pub issues: Vec<IndexIssue>,
+}
+
+impl IndexVerificationReport {
+    fn push(&mut self, issue: IndexIssue) {
+        self.repaired += 1;
+        self.issues.push(issue);
+    }
+
+    /// 索引是否一致
+    pub fn is_consistent(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// 索引问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexIssue {
+    /// 问题类型
+    pub kind: IndexIssueKind,
+    /// 所在索引
+    pub index: IndexKind,
+    /// 条目名称
+    pub name: String,
+    /// 条目所在文档
+    pub uri: String,
+    /// 采取的修复动作
+    pub action: String,
+}
+
+impl IndexIssue {
+    fn new(kind: IndexIssueKind, index: IndexKind, name: &str, location: &Location) -> Self {
+        let action = match kind {
+            IndexIssueKind::KeyMismatch => "reindexed",
+            IndexIssueKind::MissingDocument | IndexIssueKind::OutOfBounds => "removed",
+        };
+
+        Self {
+            kind,
+            index,
+            name: name.to_string(),
+            uri: location.uri.to_string(),
+            action: action.to_string(),
+        }
+    }
+}
+
+/// 索引问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IndexIssueKind {
+    /// 条目指向的文档不存在
+    MissingDocument,
+    /// 条目位置超出文档范围
+    OutOfBounds,
+    /// 索引键与条目内容不一致
+    KeyMismatch,
+}
+
+/// 索引类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IndexKind {
+    /// 符号索引
+    Symbol,
+    /// 路由索引
+    Route,
+    /// 组件索引
+    Component,
+}
+
+/// 验证时使用的文档内容缓存
+///
+/// 优先使用已打开文档的内容，其次读取磁盘文件，每个 URI 只读取一次
+struct DocumentContents<'a> {
+    documents: &'a DocumentManager,
+    cache: HashMap<Url, Option<String>>,
+}
+
+impl<'a> DocumentContents<'a> {
+    fn new(documents: &'a DocumentManager) -> Self {
+        Self {
+            documents,
+            cache: HashMap::new(),
+        }
+    }
+
+    fn content(&mut self, uri: &Url) -> Option<&str> {
+        let documents = self.documents;
+        self.cache
+            .entry(uri.clone())
+            .or_insert_with(|| {
+                documents.get(uri).map(|doc| doc.content).or_else(|| {
+                    uri.to_file_path()
+                        .ok()
+                        .and_then(|path| std::fs::read_to_string(path).ok())
+                })
+            })
+            .as_deref()
+    }
+
+    fn check_location(&mut self, location: &Location) -> Result<(), IndexIssueKind> {
+        let encoding = self.documents.encoding();
+        let content = self
+            .content(&location.uri)
+            .ok_or(IndexIssueKind::MissingDocument)?;
+
+        if range_in_bounds(
+            &PositionMapper::with_encoding(content, encoding),
+            &location.range,
+        ) {
+            Ok(())
+        } else {
+            Err(IndexIssueKind::OutOfBounds)
+        }
+    }
+}
+
+/// 检查范围是否在文档边界之内
+fn range_in_bounds(lines: &PositionMapper, range: &Range) -> bool {
+    // 列超出行尾时 offset 会截断到行尾，转换回来的位置与原位置不同
+    let position_valid = |position: &lsp_types::Position| {
+        lines
+            .offset(*position)
+            .is_some_and(|offset| lines.position(offset) == *position)
+    };
+
+    position_valid(&range.start) && position_valid(&range.end) && range.start <= range.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::route::{HttpMethod, Route};
+    use lsp_types::Position;
+
+    fn location(uri: &Url, start: (u32, u32), end: (u32, u32)) -> Location {
+        Location {
+            uri: uri.clone(),
+            range: Range {
+                start: Position::new(start.0, start.1),
+                end: Position::new(end.0, end.1),
+            },
+        }
+    }
+
+    fn open_document(uri: &Url) -> DocumentManager {
+        let documents = DocumentManager::new();
+        documents.open(
+            uri.clone(),
+            1,
+            "fn main() {}\nfn handler() {}\n".to_string(),
+            "rust".to_string(),
+        );
+        documents
+    }
+
+    #[test]
+    fn test_range_in_bounds() {
+        let lines = PositionMapper::new("abc\nde");
+        let range = |start: (u32, u32), end: (u32, u32)| Range {
+            start: Position::new(start.0, start.1),
+            end: Position::new(end.0, end.1),
+        };
+
+        assert!(range_in_bounds(&lines, &range((0, 0), (0, 3))));
+        assert!(range_in_bounds(&lines, &range((0, 1), (1, 2))));
+        assert!(!range_in_bounds(&lines, &range((0, 0), (0, 4))));
+        assert!(!range_in_bounds(&lines, &range((2, 0), (2, 0))));
+        assert!(!range_in_bounds(&lines, &range((1, 0), (0, 0))));
+    }
+
+    #[test]
+    fn test_verify_consistent_index() {
+        let uri = Url::parse("file:///project/src/main.rs").unwrap();
+        let documents = open_document(&uri);
+        let manager = IndexManager::new();
+
+        manager.symbol_index.read().unwrap().add(
+            "handler".to_string(),
+            SymbolInfo {
+                name: "handler".to_string(),
+                qualified_name: None,
+                symbol_type: SymbolType::Function,
+                location: location(&uri, (1, 3), (1, 10)),
+            },
+        );
+
+        let report = manager.verify_and_repair(&documents);
+        assert!(report.is_consistent());
+        assert_eq!(report.checked, 1);
+        assert_eq!(manager.find_symbol("handler").len(), 1);
+    }
+
+    #[test]
+    fn test_update_symbol_index() {
+        let uri = Url::parse("file:///project/src/models.rs").unwrap();
+        let manager = IndexManager::new();
+        manager.update(
+            &uri,
+            &PositionMapper::new("pub struct CreateUser {}\npub enum Role { Admin }\nmod inner {\n    pub type Id = i64;\n}\n"),
+        );
+
+        let symbols = manager.find_symbol("CreateUser");
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].symbol_type, SymbolType::Struct);
+        assert_eq!(
+            symbols[0].location.range,
+            location(&uri, (0, 11), (0, 21)).range
+        );
+        assert_eq!(
+            symbols[0].qualified_name.as_deref(),
+            Some("crate::models::CreateUser")
+        );
+        assert_eq!(manager.find_symbol("Role")[0].symbol_type, SymbolType::Enum);
+        assert_eq!(
+            manager.find_symbol("crate::models::inner::Id")[0].symbol_type,
+            SymbolType::TypeAlias
+        );
+
+        // 重新索引时移除已删除的符号；无法解析时保留之前的符号
+        manager.update(&uri, &PositionMapper::new("pub struct CreateUser {}\n"));
+        assert!(manager.find_symbol("Role").is_empty());
+        manager.update(&uri, &PositionMapper::new("pub struct CreateUser {"));
+        assert_eq!(manager.find_symbol("CreateUser").len(), 1);
+    }
+
+    #[test]
+    fn test_verify_removes_invalid_entries() {
+        let uri = Url::parse("file:///project/src/main.rs").unwrap();
+        let missing = Url::parse("file:///nonexistent/spring-lsp/deleted.rs").unwrap();
+        let documents = open_document(&uri);
+        let manager = IndexManager::new();
+
+        manager.symbol_index.read().unwrap().add(
+            "stale".to_string(),
+            SymbolInfo {
+                name: "stale".to_string(),
+                qualified_name: None,
+                symbol_type: SymbolType::Struct,
+                location: location(&missing, (0, 0), (0, 5)),
+            },
+        );
+        manager.component_index.read().unwrap().add(
+            "Broken".to_string(),
+            ComponentInfo {
+                name: "Broken".to_string(),
+                qualified_name: None,
+                type_name: "Broken".to_string(),
+                location: location(&uri, (10, 0), (10, 5)),
+                plugin: None,
+                cfg: None,
+            },
+        );
+
+        let report = manager.verify_and_repair(&documents);
+        assert_eq!(report.repaired, 2);
+        assert!(report
+            .issues
+            .iter()
+            .any(|i| i.kind == IndexIssueKind::MissingDocument && i.index == IndexKind::Symbol));
+        assert!(report
+            .issues
+            .iter()
+            .any(|i| i.kind == IndexIssueKind::OutOfBounds && i.index == IndexKind::Component));
+
+        assert!(manager.find_symbol("stale").is_empty());
+        assert!(manager.find_component("Broken").is_none());
+
+        // 修复后再次验证应该通过
+        assert!(manager.verify_and_repair(&documents).is_consistent());
+    }
+
+    #[test]
+    fn test_verify_reindexes_mismatched_route_keys() {
+        let uri = Url::parse("file:///project/src/main.rs").unwrap();
+        let documents = open_document(&uri);
+        let manager = IndexManager::new();
+
+        // 模拟路由被修改但索引键未更新
+        manager.route_index.write().unwrap().insert_raw(
+            "GET /users".to_string(),
+            Route {
+                method: HttpMethod::GET,
+                path: "/accounts".to_string(),
+                handler: "handler".to_string(),
+                handler_path: Some("crate::handler".to_string()),
+                location: location(&uri, (1, 0), (1, 15)),
+            },
+        );
+
+        let report = manager.verify_and_repair(&documents);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].kind, IndexIssueKind::KeyMismatch);
+
+        let index = manager.route_index.read().unwrap();
+        assert!(index.find_route(HttpMethod::GET, "/accounts").is_some());
+        assert!(index.find_route(HttpMethod::GET, "/users").is_none());
+    }
+
+    #[test]
+    fn test_qualified_lookups() {
+        let users = Url::parse("file:///project/src/handlers/users.rs").unwrap();
+        let orders = Url::parse("file:///project/src/handlers/orders.rs").unwrap();
+        let manager = IndexManager::new();
+
+        for uri in [&users, &orders] {
+            manager.symbol_index.read().unwrap().add(
+                "list".to_string(),
+                SymbolInfo {
+                    name: "list".to_string(),
+                    qualified_name: qualified_name_for_uri(uri, "list"),
+                    symbol_type: SymbolType::Function,
+                    location: location(uri, (1, 9), (1, 13)),
+                },
+            );
+            manager.add_component(ComponentInfo {
+                name: "Repository".to_string(),
+                qualified_name: qualified_name_for_uri(uri, "Repository"),
+                type_name: "Repository".to_string(),
+                location: location(uri, (0, 0), (0, 10)),
+                plugin: None,
+                cfg: None,
+            });
+        }
+
+        assert_eq!(manager.find_symbol("list").len(), 2);
+        let symbols = manager.find_symbol("crate::handlers::orders::list");
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].location.uri, orders);
+
+        // 不同模块中的同名组件不会相互覆盖
+        assert_eq!(manager.component_index.read().unwrap().components.len(), 2);
+        assert_eq!(
+            manager
+                .find_component("crate::handlers::users::Repository")
+                .unwrap()
+                .location
+                .uri,
+            users
+        );
+        assert!(manager.find_component("Repository").is_some());
+    }
+
+    #[test]
+    fn test_rename_file_updates_locations() {
+        let old = Url::parse("file:///project/src/handlers.rs").unwrap();
+        let new = Url::parse("file:///project/src/api/users.rs").unwrap();
+        let other = Url::parse("file:///project/src/main.rs").unwrap();
+        let manager = IndexManager::new();
+
+        manager.symbol_index.read().unwrap().add(
+            "list_users".to_string(),
+            SymbolInfo {
+                name: "list_users".to_string(),
+                qualified_name: Some("crate::handlers::list_users".to_string()),
+                symbol_type: SymbolType::Function,
+                location: location(&old, (1, 9), (1, 19)),
+            },
+        );
+        manager.route_index.write().unwrap().add_route(Route {
+            method: HttpMethod::GET,
+            path: "/users".to_string(),
+            handler: "list_users".to_string(),
+            handler_path: Some("crate::handlers::list_users".to_string()),
+            location: location(&old, (1, 0), (1, 19)),
+        });
+        manager.add_component(ComponentInfo {
+            name: "UserService".to_string(),
+            qualified_name: Some("crate::UserService".to_string()),
+            type_name: "UserService".to_string(),
+            location: location(&other, (0, 0), (0, 11)),
+            plugin: None,
+            cfg: None,
+        });
+        manager.add_component(ComponentInfo {
+            name: "UserCache".to_string(),
+            qualified_name: Some("crate::handlers::user_cache".to_string()),
+            type_name: "UserCache".to_string(),
+            location: location(&old, (3, 0), (3, 10)),
+            plugin: None,
+            cfg: None,
+        });
+
+        assert_eq!(manager.rename_file(&old, &new), 3);
+        assert_eq!(
+            manager.find_symbol("crate::api::users::list_users").len(),
+            1
+        );
+        assert!(manager
+            .find_symbol("crate::handlers::list_users")
+            .is_empty());
+        assert_eq!(
+            manager
+                .find_component("crate::api::users::user_cache")
+                .unwrap()
+                .location
+                .uri,
+            new
+        );
+        assert!(manager
+            .find_component("crate::handlers::user_cache")
+            .is_none());
+        assert_eq!(manager.find_symbol("list_users")[0].location.uri, new);
+        assert_eq!(manager.get_all_routes()[0].location.uri, new);
+        assert_eq!(
+            manager.find_routes_by_handler("crate::api::users::list_users")[0].path,
+            "/users"
+        );
+        assert_eq!(
+            manager.find_component("UserService").unwrap().location.uri,
+            other
+        );
+    }
+}
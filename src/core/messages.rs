@@ -0,0 +1,2665 @@
+//! 消息目录模块
+//!
+//! 诊断消息、快速修复标题、悬停提示、补全说明和代码透镜标题按键集中登记在 [`MESSAGES`] 中，
+//! 每条消息同时提供简体中文和英文文本。客户端在初始化选项中指定 `locale`（`"zh-CN"` 或 `"en"`），
+//! 未指定或无法识别时使用简体中文。语言由语言服务器保存，通过分析器和验证器的
+//! `with_locale` 传给生成消息的代码。
+//!
+//! 诊断消息的键与诊断代码相同；同一诊断代码有多种消息或附带快速修复时，
+//! 使用 `代码.后缀` 形式的键（如 `E020.fix`）。消息中的 `{0}`、`{1}` 等占位符按顺序替换为参数。
+
+use std::fmt::Display;
+
+/// 消息语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// 简体中文（默认）
+    #[default]
+    ZhCn,
+    /// 英文
+    En,
+}
+
+impl Locale {
+    /// 解析语言标签（不区分大小写），`en`、`en-US` 等英文标签为英文，`zh`、`zh-CN` 等为简体中文
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let tag = tag.trim().to_ascii_lowercase().replace('_', "-");
+        let language = tag.split('-').next().unwrap_or_default();
+        match language {
+            "en" => Some(Self::En),
+            "zh" => Some(Self::ZhCn),
+            _ => None,
+        }
+    }
+
+    /// 从初始化选项的 `locale` 字段读取语言
+    pub fn from_initialization_options(options: Option<&serde_json::Value>) -> Option<Self> {
+        options?.get("locale")?.as_str().and_then(Self::from_tag)
+    }
+}
+
+/// 消息目录条目
+#[derive(Debug, Clone, Copy)]
+pub struct Message {
+    /// 消息键
+    pub key: &'static str,
+    /// 简体中文文本
+    pub zh_cn: &'static str,
+    /// 英文文本
+    pub en: &'static str,
+}
+
+impl Message {
+    /// 指定语言的文本
+    pub fn text(&self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::ZhCn => self.zh_cn,
+            Locale::En => self.en,
+        }
+    }
+}
+
+/// 按指定语言生成消息
+///
+/// 目录中没有该键时返回键本身，便于发现遗漏的条目
+pub fn localized(locale: Locale, key: &str, args: &[&dyn Display]) -> String {
+    match MESSAGES.iter().find(|message| message.key == key) {
+        Some(message) => format_message(message.text(locale), args),
+        None => key.to_string(),
+    }
+}
+
+/// 诊断消息在默认语言中的文本
+///
+/// 在诊断代码对应的消息（键为代码或 `代码.后缀`）中查找与消息匹配的模板（任意语言），
+/// 提取参数后按默认语言重新生成；找不到匹配的模板时返回原消息。
+/// 用于与客户端语言无关地比较诊断（如诊断基线）
+pub fn canonical_message(code: &str, message: &str) -> String {
+    let entries = MESSAGES.iter().filter(|entry| {
+        entry.key == code
+            || entry
+                .key
+                .strip_prefix(code)
+                .is_some_and(|suffix| suffix.starts_with('.'))
+    });
+    for entry in entries {
+        for locale in [Locale::ZhCn, Locale::En] {
+            let Some(args) = match_template(entry.text(locale), message) else {
+                continue;
+            };
+            let args: Vec<&dyn Display> = args.iter().map(|arg| arg as &dyn Display).collect();
+            return format_message(entry.text(Locale::default()), &args);
+        }
+    }
+    message.to_string()
+}
+
+/// 模板中的一段
+enum Segment<'a> {
+    /// 原样出现的文本
+    Text(&'a str),
+    /// 占位符（参数下标）
+    Arg(usize),
+}
+
+/// 把模板拆分为文本和占位符（与 [`format_message`] 的规则相同）
+fn segments(template: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut index = 0;
+    while let Some(offset) = template[index..].find('{') {
+        let start = index + offset;
+        let after = &template[start + 1..];
+        let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let arg = after[..digits]
+            .parse::<usize>()
+            .ok()
+            .filter(|_| after[digits..].starts_with('}'));
+        match arg {
+            Some(arg) => {
+                if start > text_start {
+                    segments.push(Segment::Text(&template[text_start..start]));
+                }
+                segments.push(Segment::Arg(arg));
+                index = start + digits + 2;
+                text_start = index;
+            }
+            None => index = start + 1,
+        }
+    }
+    if text_start < template.len() {
+        segments.push(Segment::Text(&template[text_start..]));
+    }
+    segments
+}
+
+/// 按模板匹配消息，返回占位符对应的参数（消息与模板不匹配时返回 `None`）
+fn match_template(template: &str, message: &str) -> Option<Vec<String>> {
+    let segments = segments(template);
+    let count = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Arg(index) => Some(index + 1),
+            Segment::Text(_) => None,
+        })
+        .max()
+        .unwrap_or_default();
+    let mut args = vec![None; count];
+    match_segments(&segments, message, &mut args)
+        .then(|| args.into_iter().map(Option::unwrap_or_default).collect())
+}
+
+/// 递归匹配模板的各段，参数依次尝试每个可能的结束位置
+fn match_segments(segments: &[Segment], text: &str, args: &mut [Option<String>]) -> bool {
+    match segments.split_first() {
+        None => text.is_empty(),
+        Some((Segment::Text(literal), rest)) => text
+            .strip_prefix(literal)
+            .is_some_and(|text| match_segments(rest, text, args)),
+        Some((Segment::Arg(index), rest)) => {
+            for end in (0..=text.len()).filter(|end| text.is_char_boundary(*end)) {
+                let value = &text[..end];
+                let previous = args[*index].clone();
+                // 同一占位符多次出现时参数必须相同
+                if previous
+                    .as_deref()
+                    .is_some_and(|previous| previous != value)
+                {
+                    continue;
+                }
+                args[*index] = Some(value.to_string());
+                if match_segments(rest, &text[end..], args) {
+                    return true;
+                }
+                args[*index] = previous;
+            }
+            false
+        }
+    }
+}
+
+/// 把 `{0}`、`{1}` 等占位符替换为参数，其他大括号原样保留
+fn format_message(template: &str, args: &[&dyn Display]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let arg = after[..digits]
+            .parse::<usize>()
+            .ok()
+            .filter(|_| after[digits..].starts_with('}'))
+            .and_then(|index| args.get(index));
+        match arg {
+            Some(arg) => {
+                output.push_str(&arg.to_string());
+                rest = &after[digits + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// 消息目录
+pub const MESSAGES: &[Message] = &[
+    Message {
+        key: "undefined-section",
+        zh_cn: "配置节 '{0}' 未在 Schema 中定义",
+        en: "Section '{0}' is not defined in the schema",
+    },
+    Message {
+        key: "deprecated-property",
+        zh_cn: "配置项 '{0}' 已废弃: {1}",
+        en: "Property '{0}' is deprecated: {1}",
+    },
+    Message {
+        key: "undefined-property",
+        zh_cn: "配置项 '{0}' 未在 Schema 中定义",
+        en: "Property '{0}' is not defined in the schema",
+    },
+    Message {
+        key: "empty-var-name",
+        zh_cn: "环境变量名不能为空",
+        en: "Environment variable name must not be empty",
+    },
+    Message {
+        key: "invalid-var-name",
+        zh_cn: "环境变量名 '{0}' 不符合命名规范，建议使用大写字母、数字和下划线",
+        en: "Environment variable name '{0}' does not follow the naming convention; use uppercase letters, digits and underscores",
+    },
+    Message {
+        key: "unknown-job",
+        zh_cn: "任务 '{0}' 不存在：未找到带有 #[cron]、#[fix_delay]、#[fix_rate] 或 #[one_shot] 的同名函数{1}",
+        en: "Job '{0}' does not exist: no function with this name has #[cron], #[fix_delay], #[fix_rate] or #[one_shot]{1}",
+    },
+    Message {
+        key: "unknown-job.available",
+        zh_cn: "
+可用的任务: {0}",
+        en: "
+Available jobs: {0}",
+    },
+    Message {
+        key: "type-mismatch",
+        zh_cn: "配置项 '{0}' 的类型不匹配：期望 {1}，实际 {2}",
+        en: "Type mismatch for property '{0}': expected {1}, found {2}",
+    },
+    Message {
+        key: "invalid-enum-value",
+        zh_cn: "配置项 '{0}' 的值 '{1}' 不在允许的枚举值中：{2}",
+        en: "Value '{1}' of property '{0}' is not one of the allowed values: {2}",
+    },
+    Message {
+        key: "string-too-short",
+        zh_cn: "配置项 '{0}' 的值长度 {1} 小于最小长度 {2}",
+        en: "Value of property '{0}' has length {1}, shorter than the minimum length {2}",
+    },
+    Message {
+        key: "string-too-long",
+        zh_cn: "配置项 '{0}' 的值长度 {1} 超过最大长度 {2}",
+        en: "Value of property '{0}' has length {1}, longer than the maximum length {2}",
+    },
+    Message {
+        key: "value-too-small",
+        zh_cn: "配置项 '{0}' 的值 {1} 小于最小值 {2}",
+        en: "Value {1} of property '{0}' is less than the minimum {2}",
+    },
+    Message {
+        key: "value-too-large",
+        zh_cn: "配置项 '{0}' 的值 {1} 超过最大值 {2}",
+        en: "Value {1} of property '{0}' is greater than the maximum {2}",
+    },
+    Message {
+        key: "missing-required-property",
+        zh_cn: "缺少必需的配置项 '{0}'",
+        en: "Missing required property '{0}'",
+    },
+    Message {
+        key: "log-level.lowercase",
+        zh_cn: "日志级别必须使用小写，应为 '{0}'",
+        en: "Log levels must be lowercase, expected '{0}'",
+    },
+    Message {
+        key: "log-level.invalid",
+        zh_cn: "无效的日志级别 '{0}'，可选值: {1}",
+        en: "Invalid log level '{0}', expected one of: {1}",
+    },
+    Message {
+        key: "log-directive.unbalanced",
+        zh_cn: "指令 '{0}' 中的括号不匹配",
+        en: "Unbalanced brackets in directive '{0}'",
+    },
+    Message {
+        key: "log-directive.invalid-level",
+        zh_cn: "指令 '{0}' 中的日志级别 '{1}' 无效，可选值: {2}",
+        en: "Invalid log level '{1}' in directive '{0}', expected one of: {2}",
+    },
+    Message {
+        key: "log-directive.invalid-target",
+        zh_cn: "指令 '{0}' 中的目标 '{1}' 不是合法的模块路径",
+        en: "Target '{1}' in directive '{0}' is not a valid module path",
+    },
+    Message {
+        key: "log-directive.invalid-target-dash",
+        zh_cn: "指令 '{0}' 中的目标 '{1}' 不是合法的模块路径（crate 名称中的 '-' 在模块路径中需要写成 '_'）",
+        en: "Target '{1}' in directive '{0}' is not a valid module path ('-' in crate names must be written as '_' in module paths)",
+    },
+    Message {
+        key: "secret.aws-key",
+        zh_cn: "配置项 '{0}' 中疑似包含 AWS 访问密钥，建议改用环境变量插值，如 ${{1}}",
+        en: "'{0}' appears to contain an AWS access key; use environment variable interpolation instead, e.g. ${{1}}",
+    },
+    Message {
+        key: "secret.literal",
+        zh_cn: "配置项 '{0}' 疑似直接写入了密钥，建议改用环境变量插值，如 ${{1}}",
+        en: "'{0}' appears to contain a hard-coded secret; use environment variable interpolation instead, e.g. ${{1}}",
+    },
+    Message {
+        key: "secret.url-password",
+        zh_cn: "配置项 '{0}' 的连接地址中包含明文密码，建议改用环境变量插值，如 ${{1}}",
+        en: "The connection URL in '{0}' contains a plain-text password; use environment variable interpolation instead, e.g. ${{1}}",
+    },
+    Message {
+        key: "secret.fix",
+        zh_cn: "改用环境变量 ${{0}}",
+        en: "Use environment variable ${{0}}",
+    },
+    Message {
+        key: "url.whitespace",
+        zh_cn: "连接地址 '{0}' 包含空白字符",
+        en: "Connection URL '{0}' contains whitespace",
+    },
+    Message {
+        key: "url.missing-scheme",
+        zh_cn: "连接地址 '{0}' 缺少协议（如 postgres://、redis://、http://）",
+        en: "Connection URL '{0}' is missing a scheme (e.g. postgres://, redis://, http://)",
+    },
+    Message {
+        key: "url.invalid",
+        zh_cn: "连接地址 '{0}' 无效: {1}",
+        en: "Invalid connection URL '{0}': {1}",
+    },
+    Message {
+        key: "url.missing-host",
+        zh_cn: "连接地址 '{0}' 缺少主机",
+        en: "Connection URL '{0}' is missing a host",
+    },
+    Message {
+        key: "toml-syntax-error",
+        zh_cn: "TOML 语法错误: {0}",
+        en: "TOML syntax error: {0}",
+    },
+    Message {
+        key: "W003",
+        zh_cn: "路由路径 '{0}' 包含查询字符串 '?{1}'，路由匹配不会使用查询字符串。请使用 Query<T> 提取查询参数",
+        en: "Route path '{0}' contains the query string '?{1}', which is ignored by route matching. Use Query<T> to extract query parameters",
+    },
+    Message {
+        key: "E018",
+        zh_cn: "Query<T> 的类型参数必须是结构体，'{0}' 是基本类型。请定义一个以查询参数为字段的结构体并派生 Deserialize",
+        en: "The type parameter of Query<T> must be a struct, but '{0}' is a primitive type. Define a struct with the query parameters as fields and derive Deserialize",
+    },
+    Message {
+        key: "W004",
+        zh_cn: "路由 '{0} {1}' 与处理器 '{2}' 的路由 '{3} {4}' 冲突，同一个请求可能匹配两个路由",
+        en: "Route '{0} {1}' conflicts with route '{3} {4}' of handler '{2}'; the same request may match both routes",
+    },
+    Message {
+        key: "W007",
+        zh_cn: "处理器 '{0}' 尚未实现（{1}）",
+        en: "Handler '{0}' is not implemented yet ({1})",
+    },
+    Message {
+        key: "W005",
+        zh_cn: "路由路径 '{0}' 与 '{1}' 只有末尾斜杠不同，请求可能因为末尾斜杠返回 404",
+        en: "Route paths '{0}' and '{1}' differ only by a trailing slash; requests may return 404 because of the trailing slash",
+    },
+    Message {
+        key: "W006",
+        zh_cn: "路由路径 '{0}' 与 '{1}' 只有大小写不同，路由匹配区分大小写",
+        en: "Route paths '{0}' and '{1}' differ only in case; route matching is case-sensitive",
+    },
+    Message {
+        key: "route-style.fix",
+        zh_cn: "将路由路径改为 '{0}'",
+        en: "Change the route path to '{0}'",
+    },
+    Message {
+        key: "E020",
+        zh_cn: "'{0}' 不是有效的 HTTP 方法，允许的值: {1}",
+        en: "'{0}' is not a valid HTTP method, expected one of: {1}",
+    },
+    Message {
+        key: "E020.fix",
+        zh_cn: "将 HTTP 方法改为 '{0}'",
+        en: "Change the HTTP method to '{0}'",
+    },
+    Message {
+        key: "E016",
+        zh_cn: "Component 函数必须返回一个具体的类型，不能是 ()",
+        en: "A Component function must return a concrete type, not ()",
+    },
+    Message {
+        key: "E017",
+        zh_cn: "插件名称不能为空字符串",
+        en: "The plugin name must not be an empty string",
+    },
+    Message {
+        key: "W002",
+        zh_cn: "重复的依赖: {0} {1}",
+        en: "Duplicate dependency: {0} {1}",
+    },
+    Message {
+        key: "E001",
+        zh_cn: "字段 '{0}' 的组件名称不能为空字符串",
+        en: "The component name of field '{0}' must not be an empty string",
+    },
+    Message {
+        key: "E002",
+        zh_cn: "配置注入 (config) 不应该指定组件名称",
+        en: "Config injection (config) must not specify a component name",
+    },
+    Message {
+        key: "E002.remove-name",
+        zh_cn: "移除组件名称",
+        en: "Remove the component name",
+    },
+    Message {
+        key: "E002.inject-component",
+        zh_cn: "改为注入组件 \"{0}\"",
+        en: "Inject component \"{0}\" instead",
+    },
+    Message {
+        key: "E024",
+        zh_cn: "字段 '{0}' 有多个 #[inject] 属性，只能保留一个",
+        en: "Field '{0}' has multiple #[inject] attributes; keep only one",
+    },
+    Message {
+        key: "E025",
+        zh_cn: "#[inject] 不能用于元组结构体 '{0}' 的字段 {1}，Service 只支持命名字段",
+        en: "#[inject] cannot be used on field {1} of tuple struct '{0}'; Service only supports named fields",
+    },
+    Message {
+        key: "E026.struct",
+        zh_cn: "结构体 '{0}' 没有派生 Service，#[inject] 不会生效。请添加 #[derive(Clone, Service)]",
+        en: "Struct '{0}' does not derive Service, so #[inject] has no effect. Add #[derive(Clone, Service)]",
+    },
+    Message {
+        key: "E026.enum",
+        zh_cn: "#[inject] 不能用于枚举 '{0}'，只有派生 Service 的结构体支持依赖注入",
+        en: "#[inject] cannot be used in enum '{0}'; only structs deriving Service support dependency injection",
+    },
+    Message {
+        key: "E003",
+        zh_cn: "AutoConfig 宏必须指定配置器类型",
+        en: "The AutoConfig macro must specify a configurator type",
+    },
+    Message {
+        key: "E004",
+        zh_cn: "路由路径不能为空",
+        en: "The route path must not be empty",
+    },
+    Message {
+        key: "E005",
+        zh_cn: "路由路径必须以 '/' 开头，当前路径: '{0}'",
+        en: "The route path must start with '/', got '{0}'",
+    },
+    Message {
+        key: "E006",
+        zh_cn: "路由必须至少指定一个 HTTP 方法",
+        en: "A route must specify at least one HTTP method",
+    },
+    Message {
+        key: "E007",
+        zh_cn: "路由处理器函数名称不能为空",
+        en: "The route handler function name must not be empty",
+    },
+    Message {
+        key: "E013",
+        zh_cn: "Cron 表达式不能为空",
+        en: "The cron expression must not be empty",
+    },
+    Message {
+        key: "E030",
+        zh_cn: "未知的时区 '{0}'，时区名称应为 IANA 时区数据库中的名称（如 Asia/Shanghai）",
+        en: "Unknown time zone '{0}'; expected a name from the IANA time zone database (e.g. Asia/Shanghai)",
+    },
+    Message {
+        key: "E031",
+        zh_cn: "spring-job 无法为任务函数 '{0}' 提供 '{1}' 类型的参数，任务参数只能是 Component<T>、Config<T>、JobId、JobScheduler 或实现 FromApp 的类型",
+        en: "spring-job cannot supply a '{1}' argument to job function '{0}'; job parameters must be Component<T>, Config<T>, JobId, JobScheduler or a type implementing FromApp",
+    },
+    Message {
+        key: "W001",
+        zh_cn: "延迟秒数为 0 可能不是预期的行为",
+        en: "A delay of 0 seconds is probably not intended",
+    },
+    Message {
+        key: "E014",
+        zh_cn: "频率秒数不能为 0",
+        en: "The rate in seconds must not be 0",
+    },
+    Message {
+        key: "E015",
+        zh_cn: "Cron 表达式应该包含 6 个部分（秒 分 时 日 月 星期），当前有 {0} 个部分",
+        en: "A cron expression must have 6 fields (sec min hour day month weekday), found {0}",
+    },
+    Message {
+        key: "E008",
+        zh_cn: "路径参数不能嵌套，位置: {0}",
+        en: "Path parameters cannot be nested, at offset {0}",
+    },
+    Message {
+        key: "E009",
+        zh_cn: "路径参数缺少开括号 '{', 位置: {0}",
+        en: "Path parameter is missing an opening brace '{', at offset {0}",
+    },
+    Message {
+        key: "E010",
+        zh_cn: "路径参数名称不能为空，位置: {0}",
+        en: "Path parameter name must not be empty, at offset {0}",
+    },
+    Message {
+        key: "E011",
+        zh_cn: "路径参数名称只能包含字母、数字和下划线，当前参数: '{0}'",
+        en: "Path parameter names may only contain letters, digits and underscores, got '{0}'",
+    },
+    Message {
+        key: "E012",
+        zh_cn: "路径参数缺少闭括号 '}'",
+        en: "Path parameter is missing a closing brace '}'",
+    },
+    Message {
+        key: "E019",
+        zh_cn: "路径参数 '{0}' 的约束 '{1}' 不是合法的正则表达式: {2}",
+        en: "Constraint '{1}' of path parameter '{0}' is not a valid regular expression: {2}",
+    },
+    Message {
+        key: "E028",
+        zh_cn: "路径参数名称 '{0}' 在同一路径中重复，运行时无法正确提取路径参数",
+        en: "Path parameter name '{0}' appears more than once in the path; path extraction will not work correctly at runtime",
+    },
+    Message {
+        key: "E029",
+        zh_cn: "路径包含 {0} 个参数，超过了 {1} 个的上限，建议拆分路由或改用查询参数",
+        en: "Path declares {0} parameters, more than the limit of {1}; consider splitting the route or using query parameters",
+    },
+    Message {
+        key: "duplicate-handler-name",
+        zh_cn: "处理器 '{0}' 与 {1} 重名，按函数名查找路由时无法区分，请使用完整路径",
+        en: "Handler '{0}' has the same name as {1}; looking up routes by function name is ambiguous, use the fully-qualified path",
+    },
+    Message {
+        key: "duplicate-route-registration",
+        zh_cn: "路由 '{0} {1}' 同时通过路由宏和 Router::route 注册（{2}），axum 会因路由重叠而 panic",
+        en: "Route '{0} {1}' is registered both by a route macro and by Router::route ({2}); axum panics on overlapping routes",
+    },
+    Message {
+        key: "path-segment-spelling",
+        zh_cn: "路径段 '{0}' 与项目中更常用的 '{1}' 拼写相近（'{1}' 出现 {2} 次，'{0}' 出现 {3} 次）",
+        en: "Path segment '{0}' is spelled similarly to the more common '{1}' in this project ('{1}' appears {2} times, '{0}' appears {3} times)",
+    },
+    Message {
+        key: "unprotected-route",
+        zh_cn: "路由 {0} 匹配安全规则 '{1}'，但没有受到保护（需要{2}）",
+        en: "Route {0} matches security rule '{1}' but is not protected (requires {2})",
+    },
+    Message {
+        key: "route-prefix-convention",
+        zh_cn: "路由 {0} 不符合 '{1}' 的路由前缀约定，应以 {2} 开头",
+        en: "Route {0} violates the route prefix convention for '{1}'; it should start with {2}",
+    },
+    Message {
+        key: "unprotected-route.middleware",
+        zh_cn: "中间件 {0}",
+        en: "middleware {0}",
+    },
+    Message {
+        key: "unprotected-route.guard",
+        zh_cn: "guard 提取器 {0}",
+        en: "guard extractor {0}",
+    },
+    Message {
+        key: "unprotected-route.or",
+        zh_cn: "，或",
+        en: ", or ",
+    },
+    Message {
+        key: "E021",
+        zh_cn: "路由处理器 '{0}' 不是异步函数，#[{1}] 要求处理器是 async fn",
+        en: "Route handler '{0}' is not async; #[{1}] requires the handler to be an async fn",
+    },
+    Message {
+        key: "E022",
+        zh_cn: "任务函数 '{0}' 不是异步函数，#[{1}] 要求任务是 async fn",
+        en: "Job function '{0}' is not async; #[{1}] requires the job to be an async fn",
+    },
+    Message {
+        key: "E023",
+        zh_cn: "任务函数 '{0}' 不能有返回值，任务的返回值会被忽略，请在函数内处理结果（如记录错误日志）",
+        en: "Job function '{0}' must not return a value; the return value of a job is ignored, handle the result inside the function (e.g. log errors)",
+    },
+    Message {
+        key: "E027.multiple",
+        zh_cn: "路由处理器 '{0}' 有 {1} 个消费请求体的提取器，请求体只能读取一次，只能保留一个并放在最后",
+        en: "Route handler '{0}' has {1} extractors that consume the request body; the body can only be read once, keep one and make it the last parameter",
+    },
+    Message {
+        key: "E027",
+        zh_cn: "请求体提取器 {0} 必须是路由处理器 '{1}' 的最后一个参数（请求体只能读取一次，只有最后一个提取器可以消费请求体）",
+        en: "Body extractor {0} must be the last parameter of route handler '{1}' (the request body can only be read once, and only the last extractor may consume it)",
+    },
+    Message {
+        key: "E027.fix",
+        zh_cn: "把 {0} 移到最后",
+        en: "Move {0} to the end",
+    },
+    Message {
+        key: "make-async.fix",
+        zh_cn: "改为 async fn",
+        en: "Change to async fn",
+    },
+    Message {
+        key: "route-not-in-spec",
+        zh_cn: "路由 {0} {1} 没有在 OpenAPI 规范 {2} 中声明",
+        en: "Route {0} {1} is not declared in the OpenAPI spec {2}",
+    },
+    Message {
+        key: "spec-operation-without-handler",
+        zh_cn: "OpenAPI 操作 {0} {1} 没有对应的路由处理器",
+        en: "OpenAPI operation {0} {1} has no matching route handler",
+    },
+    Message {
+        key: "spec-operation-without-handler.with-id",
+        zh_cn: "OpenAPI 操作 {0} {1}（{2}）没有对应的路由处理器",
+        en: "OpenAPI operation {0} {1} ({2}) has no matching route handler",
+    },
+    Message {
+        key: "component-type-mismatch",
+        zh_cn: "组件 '{0}' 的类型不匹配。期望类型: {1}，实际类型: {2}",
+        en: "Type mismatch for component '{0}'. Expected: {1}, found: {2}",
+    },
+    Message {
+        key: "component-requires-cfg",
+        zh_cn: "组件 '{0}' 只在 cfg({1}) 时注册，未启用该条件时 '{2}' 无法注入它。请启用对应的 feature，或为 '{2}' 加上相同的 #[cfg]。",
+        en: "Component '{0}' is only registered under cfg({1}), so '{2}' cannot inject it when that condition is disabled. Enable the feature, or add the same #[cfg] to '{2}'.",
+    },
+    Message {
+        key: "component-type-not-found",
+        zh_cn: "组件类型 '{0}' 不存在。请确保该类型已定义。",
+        en: "Component type '{0}' does not exist. Make sure the type is defined.",
+    },
+    Message {
+        key: "component-not-registered",
+        zh_cn: "组件 '{0}' 未注册。请确保该组件已通过插件注册。",
+        en: "Component '{0}' is not registered. Make sure it is registered by a plugin.",
+    },
+    Message {
+        key: "component-not-registered.add",
+        zh_cn: "在应用入口注册组件：{0}",
+        en: "Register the component in the app builder: {0}",
+    },
+    Message {
+        key: "component-not-registered.provider",
+        zh_cn: "添加提供 {0} 的 #[component] 函数",
+        en: "Add a #[component] function providing {0}",
+    },
+    Message {
+        key: "component-available",
+        zh_cn: "\n可用的 {0} 类型组件: {1}",
+        en: "\nAvailable components of type {0}: {1}",
+    },
+    Message {
+        key: "component-name-mismatch",
+        zh_cn: "组件名称 '{0}' 的类型不匹配。期望类型: {1}，实际类型: {2}{3}",
+        en: "Type mismatch for component name '{0}'. Expected: {1}, found: {2}{3}",
+    },
+    Message {
+        key: "component-name-not-found",
+        zh_cn: "组件名称 '{0}' 不存在。{1}",
+        en: "Component name '{0}' does not exist.{1}",
+    },
+    Message {
+        key: "config-not-found.in-file",
+        zh_cn: "配置项 '{0}' 不存在。请在配置文件 {1} 中添加 [{0}] 配置节。",
+        en: "Config '{0}' does not exist. Add a [{0}] section to the config file {1}.",
+    },
+    Message {
+        key: "config-not-found",
+        zh_cn: "配置项 '{0}' 不存在。请在配置文件中添加 [{0}] 配置节。",
+        en: "Config '{0}' does not exist. Add a [{0}] section to the config file.",
+    },
+    Message {
+        key: "config-not-found.related",
+        zh_cn: "在此文件中添加 [{0}] 配置节",
+        en: "Add the [{0}] section to this file",
+    },
+    Message {
+        key: "circular-dependency",
+        zh_cn: "检测到循环依赖: {0}。建议使用 LazyComponent<T> 打破循环。",
+        en: "Circular dependency detected: {0}. Consider using LazyComponent<T> to break the cycle.",
+    },
+    Message {
+        key: "multiple-auto-config.related",
+        zh_cn: "另一个 #[auto_config] 入口",
+        en: "Another #[auto_config] entry",
+    },
+    Message {
+        key: "multiple-auto-config",
+        zh_cn: "二进制目标 '{0}' 中有 {1} 个 #[auto_config] 入口，应用只能有一个入口",
+        en: "Binary target '{0}' has {1} #[auto_config] entries; an application can only have one",
+    },
+    Message {
+        key: "missing-auto-config.routes",
+        zh_cn: "路由",
+        en: "routes",
+    },
+    Message {
+        key: "missing-auto-config.jobs",
+        zh_cn: "定时任务",
+        en: "scheduled jobs",
+    },
+    Message {
+        key: "missing-auto-config",
+        zh_cn: "crate 中有 {0} 个{1}，但没有 #[auto_config({2})] 入口注册它们",
+        en: "The crate has {0} {1}, but no #[auto_config({2})] entry registers them",
+    },
+    Message {
+        key: "missing-auto-config.related",
+        zh_cn: "未注册的{0}",
+        en: "{0} not registered",
+    },
+    Message {
+        key: "duplicate-job-name",
+        zh_cn: "任务名称 '{0}' 在项目中定义了 {1} 次，配置按函数名引用任务时无法区分，建议重命名",
+        en: "Job name '{0}' is defined {1} times in the project, so configuration cannot tell them apart by function name; consider renaming",
+    },
+    Message {
+        key: "duplicate-job-name.related",
+        zh_cn: "同名任务",
+        en: "Job with the same name",
+    },
+    Message {
+        key: "job-schedule-overlap",
+        zh_cn: "固定频率任务 '{0}' 与 {1} 都每 {2} 秒执行一次，会同时触发，建议合并任务或错开间隔",
+        en: "Fixed-rate job '{0}' and {1} all run every {2} seconds and fire at the same time; consider merging the jobs or staggering their intervals",
+    },
+    Message {
+        key: "job-schedule-overlap.related",
+        zh_cn: "间隔相同的任务",
+        en: "Job with the same interval",
+    },
+    Message {
+        key: "list.separator",
+        zh_cn: "、",
+        en: ", ",
+    },
+    Message {
+        key: "config-field-missing",
+        zh_cn: "配置节 [{0}] 中缺少必需的配置项 '{1}'，应用启动时 {2} 反序列化会失败。请添加该配置项，或将字段改为 Option<{3}> / 添加 #[serde(default)]",
+        en: "Required key '{1}' is missing from section [{0}], so deserializing {2} will fail at startup. Add the key, or change the field to Option<{3}> / add #[serde(default)]",
+    },
+    Message {
+        key: "config-field-missing.section-related",
+        zh_cn: "配置节 [{0}] 定义在这里",
+        en: "Section [{0}] is defined here",
+    },
+    Message {
+        key: "config-field-missing.section",
+        zh_cn: "配置节 [{0}] 缺少 {1} 的必需配置项，应用启动时会失败。请在此添加：{2}",
+        en: "Section [{0}] is missing required keys of {1}, so the application will fail at startup. Add them here: {2}",
+    },
+    Message {
+        key: "http-semantics/get-with-body",
+        zh_cn: "{0} 请求通常没有请求体，'{1}' 提取器在很多客户端和代理下无法收到数据。请使用 Query<T> 或改用 POST",
+        en: "{0} requests usually have no body, so the '{1}' extractor receives no data with many clients and proxies. Use Query<T> or switch to POST",
+    },
+    Message {
+        key: "http-semantics/delete-with-body",
+        zh_cn: "DELETE 处理器返回了实体内容 '{0}'，删除成功时通常返回 204 No Content（StatusCode::NO_CONTENT）",
+        en: "The DELETE handler returns an entity '{0}'; successful deletes usually return 204 No Content (StatusCode::NO_CONTENT)",
+    },
+    Message {
+        key: "handler-complexity/too-long",
+        zh_cn: "处理器 '{0}' 的函数体有 {1} 行（超过 {2} 行），建议把业务逻辑提取到 Service 中",
+        en: "Handler '{0}' has {1} lines in its body (more than {2}); consider moving the business logic into a Service",
+    },
+    Message {
+        key: "handler-complexity/too-many-components",
+        zh_cn: "处理器 '{0}' 注入了 {1} 个组件（超过 {2} 个），建议把这些组件组合到一个 Service 中再注入",
+        en: "Handler '{0}' injects {1} components (more than {2}); consider combining them into a Service and injecting that instead",
+    },
+    Message {
+        key: "missing-middleware",
+        zh_cn: "路由 {0} 匹配规则 '{1}'，但没有应用 {2}",
+        en: "Route {0} matches rule '{1}' but does not apply {2}",
+    },
+    Message {
+        key: "rename.http-method",
+        zh_cn: "'{0}' 是 HTTP 方法，不能重命名。要修改请求方法，请改用对应的路由宏或 method 参数",
+        en: "'{0}' is an HTTP method and cannot be renamed. To change the request method, use the matching route macro or method argument",
+    },
+    Message {
+        key: "rename.spring-macro",
+        zh_cn: "#[{0}] 是 spring 宏，不能重命名",
+        en: "#[{0}] is a spring macro and cannot be renamed",
+    },
+    Message {
+        key: "rename.schema-key",
+        zh_cn: "'{0}' 由插件 {1} 的配置 Schema 定义，重命名后插件将无法读取该配置",
+        en: "'{0}' is defined by the configuration schema of plugin {1}; the plugin cannot read it after renaming",
+    },
+    Message {
+        key: "rename.invalid-name",
+        zh_cn: "'{0}' 不是有效的名称",
+        en: "'{0}' is not a valid name",
+    },
+    Message {
+        key: "template.handler",
+        zh_cn: "{0} 路由处理器",
+        en: "{0} route handlers",
+    },
+    Message {
+        key: "template.job",
+        zh_cn: "{0} 定时任务",
+        en: "{0} scheduled jobs",
+    },
+    Message {
+        key: "template.apply-label",
+        zh_cn: "填充 {0} 的文件模板",
+        en: "Insert file template into {0}",
+    },
+    Message {
+        key: "hover.url",
+        zh_cn: "**连接地址**:",
+        en: "**Connection URL**:",
+    },
+    Message {
+        key: "hover.url.scheme",
+        zh_cn: "- 协议: `{0}`",
+        en: "- Scheme: `{0}`",
+    },
+    Message {
+        key: "hover.url.user",
+        zh_cn: "- 用户: `{0}`",
+        en: "- User: `{0}`",
+    },
+    Message {
+        key: "hover.url.user-with-password",
+        zh_cn: "- 用户: `{0}`（含密码）",
+        en: "- User: `{0}` (with password)",
+    },
+    Message {
+        key: "hover.url.host",
+        zh_cn: "- 主机: `{0}`",
+        en: "- Host: `{0}`",
+    },
+    Message {
+        key: "hover.url.port",
+        zh_cn: "- 端口: `{0}`",
+        en: "- Port: `{0}`",
+    },
+    Message {
+        key: "hover.url.database",
+        zh_cn: "- 数据库: `{0}`",
+        en: "- Database: `{0}`",
+    },
+    Message {
+        key: "log-level.describe.trace",
+        zh_cn: "输出所有日志，包括最详细的跟踪信息",
+        en: "Log everything, including the most detailed trace information",
+    },
+    Message {
+        key: "log-level.describe.debug",
+        zh_cn: "输出调试信息及以上级别的日志",
+        en: "Log debug information and above",
+    },
+    Message {
+        key: "log-level.describe.info",
+        zh_cn: "输出一般信息及以上级别的日志（默认）",
+        en: "Log general information and above (default)",
+    },
+    Message {
+        key: "log-level.describe.warn",
+        zh_cn: "只输出警告和错误",
+        en: "Log warnings and errors only",
+    },
+    Message {
+        key: "log-level.describe.error",
+        zh_cn: "只输出错误",
+        en: "Log errors only",
+    },
+    Message {
+        key: "log-level.describe.off",
+        zh_cn: "关闭日志",
+        en: "Disable logging",
+    },
+    Message {
+        key: "hover.section.title",
+        zh_cn: "# 配置节: `[{0}]`",
+        en: "# Section: `[{0}]`",
+    },
+    Message {
+        key: "hover.section.plugin",
+        zh_cn: "**所属插件**: `{0}`",
+        en: "**Plugin**: `{0}`",
+    },
+    Message {
+        key: "hover.section.configured-of",
+        zh_cn: "**已配置**: {0} / {1} 个配置项",
+        en: "**Configured**: {0} / {1} properties",
+    },
+    Message {
+        key: "hover.section.missing-required",
+        zh_cn: "**缺少必需配置项**: {0}",
+        en: "**Missing required properties**: {0}",
+    },
+    Message {
+        key: "hover.section.docs",
+        zh_cn: "**文档**: [{0}](https://docs.rs/{0})",
+        en: "**Documentation**: [{0}](https://docs.rs/{0})",
+    },
+    Message {
+        key: "hover.section.configured",
+        zh_cn: "**已配置**: {0} 个配置项",
+        en: "**Configured**: {0} properties",
+    },
+    Message {
+        key: "hover.section.custom",
+        zh_cn: "未在 Schema 中定义，可能是通过 `#[config_prefix]` 声明的自定义配置",
+        en: "Not defined in the schema; possibly a custom configuration declared with `#[config_prefix]`",
+    },
+    Message {
+        key: "hover.env.title",
+        zh_cn: "# 环境变量",
+        en: "# Environment variable",
+    },
+    Message {
+        key: "hover.env.name",
+        zh_cn: "**变量名**: `{0}`",
+        en: "**Name**: `{0}`",
+    },
+    Message {
+        key: "hover.default",
+        zh_cn: "**默认值**: `{0}`",
+        en: "**Default**: `{0}`",
+    },
+    Message {
+        key: "hover.current",
+        zh_cn: "**当前值**: `{0}`",
+        en: "**Current value**: `{0}`",
+    },
+    Message {
+        key: "hover.env.unset",
+        zh_cn: "**当前值**: *未设置*",
+        en: "**Current value**: *not set*",
+    },
+    Message {
+        key: "hover.env.help",
+        zh_cn: "**说明**:\n\n环境变量插值允许在配置文件中引用系统环境变量。\n\n**格式**:\n- `${VAR}` - 引用环境变量，如果未设置则报错\n- `${VAR:default}` - 引用环境变量，如果未设置则使用默认值",
+        en: "**Description**:\n\nEnvironment variable interpolation references system environment variables in configuration files.\n\n**Syntax**:\n- `${VAR}` - references the variable; an error if it is not set\n- `${VAR:default}` - references the variable; uses the default if it is not set",
+    },
+    Message {
+        key: "hover.property.title",
+        zh_cn: "# 配置项: `{0}.{1}`",
+        en: "# Property: `{0}.{1}`",
+    },
+    Message {
+        key: "hover.type",
+        zh_cn: "**类型**: {0}",
+        en: "**Type**: {0}",
+    },
+    Message {
+        key: "hover.property.required",
+        zh_cn: "**必需**: 是",
+        en: "**Required**: yes",
+    },
+    Message {
+        key: "hover.property.allowed-values",
+        zh_cn: "**允许的值**:",
+        en: "**Allowed values**:",
+    },
+    Message {
+        key: "hover.property.allowed-value",
+        zh_cn: "- `{0}`：{1}",
+        en: "- `{0}`: {1}",
+    },
+    Message {
+        key: "hover.property.range",
+        zh_cn: "**值范围**:",
+        en: "**Range**:",
+    },
+    Message {
+        key: "hover.property.min",
+        zh_cn: "- 最小值: `{0}`",
+        en: "- Minimum: `{0}`",
+    },
+    Message {
+        key: "hover.property.max",
+        zh_cn: "- 最大值: `{0}`",
+        en: "- Maximum: `{0}`",
+    },
+    Message {
+        key: "hover.property.length",
+        zh_cn: "**长度限制**:",
+        en: "**Length**:",
+    },
+    Message {
+        key: "hover.property.min-length",
+        zh_cn: "- 最小长度: `{0}`",
+        en: "- Minimum length: `{0}`",
+    },
+    Message {
+        key: "hover.property.max-length",
+        zh_cn: "- 最大长度: `{0}`",
+        en: "- Maximum length: `{0}`",
+    },
+    Message {
+        key: "hover.property.example",
+        zh_cn: "**示例**:",
+        en: "**Example**:",
+    },
+    Message {
+        key: "hover.property.deprecated",
+        zh_cn: "⚠️ **已废弃**: {0}",
+        en: "⚠️ **Deprecated**: {0}",
+    },
+    Message {
+        key: "hover.property.undefined",
+        zh_cn: "⚠️ **警告**: 此配置项未在 Schema 中定义",
+        en: "⚠️ **Warning**: this property is not defined in the schema",
+    },
+    Message {
+        key: "hover.property.footer",
+        zh_cn: "*配置节*: `[{0}]`\n*配置文件*: `config/app.toml`",
+        en: "*Section*: `[{0}]`\n*File*: `config/app.toml`",
+    },
+    Message {
+        key: "type.string",
+        zh_cn: "字符串",
+        en: "string",
+    },
+    Message {
+        key: "type.integer",
+        zh_cn: "整数",
+        en: "integer",
+    },
+    Message {
+        key: "type.float",
+        zh_cn: "浮点数",
+        en: "float",
+    },
+    Message {
+        key: "type.boolean",
+        zh_cn: "布尔值",
+        en: "boolean",
+    },
+    Message {
+        key: "type.array",
+        zh_cn: "数组",
+        en: "array",
+    },
+    Message {
+        key: "type.object",
+        zh_cn: "对象",
+        en: "object",
+    },
+    Message {
+        key: "hover.component.async",
+        zh_cn: "**异步**: 是\n\n",
+        en: "**Async**: yes\n\n",
+    },
+    Message {
+        key: "hover.component.sync",
+        zh_cn: "**异步**: 否\n\n",
+        en: "**Async**: no\n\n",
+    },
+    Message {
+        key: "hover.component.config-dependency",
+        zh_cn: "- `{0}` - 配置\n",
+        en: "- `{0}` - configuration\n",
+    },
+    Message {
+        key: "hover.component.component-dependency",
+        zh_cn: "- `{0}` - 组件\n",
+        en: "- `{0}` - component\n",
+    },
+    Message {
+        key: "macro.service",
+        zh_cn: "Service 派生宏",
+        en: "Service derive macro",
+    },
+    Message {
+        key: "macro.component",
+        zh_cn: "Component 属性宏",
+        en: "Component attribute macro",
+    },
+    Message {
+        key: "macro.inject",
+        zh_cn: "Inject 属性宏",
+        en: "Inject attribute macro",
+    },
+    Message {
+        key: "macro.auto-config",
+        zh_cn: "AutoConfig 属性宏",
+        en: "AutoConfig attribute macro",
+    },
+    Message {
+        key: "macro.route",
+        zh_cn: "路由宏",
+        en: "Route macro",
+    },
+    Message {
+        key: "macro.job",
+        zh_cn: "任务调度宏",
+        en: "Job scheduling macro",
+    },
+    Message {
+        key: "http-method.get",
+        zh_cn: "获取资源",
+        en: "Retrieve a resource",
+    },
+    Message {
+        key: "http-method.post",
+        zh_cn: "创建资源",
+        en: "Create a resource",
+    },
+    Message {
+        key: "http-method.put",
+        zh_cn: "更新资源（完整）",
+        en: "Replace a resource",
+    },
+    Message {
+        key: "http-method.delete",
+        zh_cn: "删除资源",
+        en: "Delete a resource",
+    },
+    Message {
+        key: "http-method.patch",
+        zh_cn: "更新资源（部分）",
+        en: "Partially update a resource",
+    },
+    Message {
+        key: "http-method.head",
+        zh_cn: "获取资源头信息",
+        en: "Retrieve resource headers",
+    },
+    Message {
+        key: "http-method.options",
+        zh_cn: "获取支持的方法",
+        en: "List the supported methods",
+    },
+    Message {
+        key: "http-method.connect",
+        zh_cn: "建立隧道",
+        en: "Establish a tunnel",
+    },
+    Message {
+        key: "http-method.trace",
+        zh_cn: "回显请求（诊断用）",
+        en: "Echo the request (for diagnostics)",
+    },
+    Message {
+        key: "hover.extractor.query",
+        zh_cn: "从 URL 查询字符串中反序列化参数。\n\n",
+        en: "Deserializes parameters from the URL query string.\n\n",
+    },
+    Message {
+        key: "hover.extractor.json",
+        zh_cn: "从 JSON 请求体中反序列化参数。\n\n",
+        en: "Deserializes parameters from the JSON request body.\n\n",
+    },
+    Message {
+        key: "hover.extractor.form",
+        zh_cn: "从表单请求体中反序列化参数。\n\n",
+        en: "Deserializes parameters from the form request body.\n\n",
+    },
+    Message {
+        key: "hover.extractor.param",
+        zh_cn: "- `{0}`: `{1}`\n",
+        en: "- `{0}`: `{1}`\n",
+    },
+    Message {
+        key: "hover.extractor.optional-param",
+        zh_cn: "- `{0}`: `{1}`（可选）\n",
+        en: "- `{0}`: `{1}` (optional)\n",
+    },
+    Message {
+        key: "hover.method.title",
+        zh_cn: "# HTTP 方法\n\n",
+        en: "# HTTP method\n\n",
+    },
+    Message {
+        key: "hover.method.current",
+        zh_cn: "**当前值**: `{0}` - {1}\n\n",
+        en: "**Current value**: `{0}` - {1}\n\n",
+    },
+    Message {
+        key: "completion.example",
+        zh_cn: "**示例**:",
+        en: "**Example**:",
+    },
+    Message {
+        key: "completion.plugin-name",
+        zh_cn: "插件名称",
+        en: "Plugin name",
+    },
+    Message {
+        key: "completion.plugin-name.doc",
+        zh_cn: "指定自定义的插件名称，默认使用组件类型名 + \"Plugin\"",
+        en: "Custom plugin name; defaults to the component type name + \"Plugin\"",
+    },
+    Message {
+        key: "completion.inject-component",
+        zh_cn: "注入组件",
+        en: "Inject a component",
+    },
+    Message {
+        key: "completion.inject-component.doc",
+        zh_cn: "从应用上下文中注入已注册的组件实例。",
+        en: "Injects a registered component instance from the application context.",
+    },
+    Message {
+        key: "completion.inject-named",
+        zh_cn: "注入指定名称的组件",
+        en: "Inject a named component",
+    },
+    Message {
+        key: "completion.inject-named.doc",
+        zh_cn: "使用指定名称从应用上下文中注入组件，适用于多实例场景（如多数据源）。",
+        en: "Injects the component with the given name from the application context, for multiple instances of one type (such as several data sources).",
+    },
+    Message {
+        key: "completion.inject-config",
+        zh_cn: "注入配置",
+        en: "Inject configuration",
+    },
+    Message {
+        key: "completion.inject-config.doc",
+        zh_cn: "从配置文件中加载配置项。配置项通过 `#[config_prefix]` 指定的前缀从 `config/app.toml` 中读取。",
+        en: "Loads the configuration from the configuration file, reading `config/app.toml` with the prefix given by `#[config_prefix]`.",
+    },
+    Message {
+        key: "completion.inject-component.usage",
+        zh_cn: "从应用上下文中注入已注册的组件实例。\n\n**使用方式**:\n- `#[inject(component)]` - 按类型自动查找\n- `#[inject(component = \"name\")]` - 按名称查找",
+        en: "Injects a registered component instance from the application context.\n\n**Usage**:\n- `#[inject(component)]` - look up by type\n- `#[inject(component = \"name\")]` - look up by name",
+    },
+    Message {
+        key: "completion.inject-config.usage",
+        zh_cn: "从配置文件中加载配置项。\n\n**使用方式**:\n- `#[inject(config)]` - 从 config/app.toml 加载配置",
+        en: "Loads the configuration from the configuration file.\n\n**Usage**:\n- `#[inject(config)]` - load the configuration from config/app.toml",
+    },
+    Message {
+        key: "completion.configurator.web",
+        zh_cn: "Web 路由配置器",
+        en: "Web route configurator",
+    },
+    Message {
+        key: "completion.configurator.web.doc",
+        zh_cn: "自动注册 Web 路由处理器。",
+        en: "Registers web route handlers automatically.",
+    },
+    Message {
+        key: "completion.configurator.job",
+        zh_cn: "任务调度配置器",
+        en: "Job scheduling configurator",
+    },
+    Message {
+        key: "completion.configurator.job.doc",
+        zh_cn: "自动注册定时任务。",
+        en: "Registers scheduled jobs automatically.",
+    },
+    Message {
+        key: "completion.configurator.stream",
+        zh_cn: "流处理配置器",
+        en: "Stream configurator",
+    },
+    Message {
+        key: "completion.configurator.stream.doc",
+        zh_cn: "自动注册流消费者。",
+        en: "Registers stream consumers automatically.",
+    },
+    Message {
+        key: "completion.http-method.doc",
+        zh_cn: "HTTP {0} 方法",
+        en: "HTTP {0} method",
+    },
+    Message {
+        key: "completion.path-param",
+        zh_cn: "路径参数",
+        en: "Path parameter",
+    },
+    Message {
+        key: "completion.path-param.doc",
+        zh_cn: "路径参数占位符，用于捕获 URL 中的动态部分。",
+        en: "Path parameter placeholder that captures a dynamic part of the URL.",
+    },
+    Message {
+        key: "completion.cron.hourly",
+        zh_cn: "每小时执行",
+        en: "Runs every hour",
+    },
+    Message {
+        key: "completion.cron.hourly.doc",
+        zh_cn: "Cron 表达式：每小时的第 0 分 0 秒执行",
+        en: "Cron expression: runs at minute 0, second 0 of every hour",
+    },
+    Message {
+        key: "completion.cron.daily",
+        zh_cn: "每天午夜执行",
+        en: "Runs daily at midnight",
+    },
+    Message {
+        key: "completion.cron.daily.doc",
+        zh_cn: "Cron 表达式：每天午夜 00:00:00 执行",
+        en: "Cron expression: runs every day at 00:00:00",
+    },
+    Message {
+        key: "completion.cron.every-five-minutes",
+        zh_cn: "每 5 分钟执行",
+        en: "Runs every 5 minutes",
+    },
+    Message {
+        key: "completion.cron.every-five-minutes.doc",
+        zh_cn: "Cron 表达式：每 5 分钟执行一次",
+        en: "Cron expression: runs once every 5 minutes",
+    },
+    Message {
+        key: "completion.cron-format",
+        zh_cn: "**格式**: 秒 分 时 日 月 星期",
+        en: "**Format**: sec min hour day month weekday",
+    },
+    Message {
+        key: "completion.fix-delay",
+        zh_cn: "延迟 {0} 秒",
+        en: "Delay of {0} seconds",
+    },
+    Message {
+        key: "completion.fix-delay.doc",
+        zh_cn: "任务完成后延迟 {0} 秒再次执行",
+        en: "Runs again {0} seconds after the previous run finishes",
+    },
+    Message {
+        key: "completion.delay-or-rate",
+        zh_cn: "延迟/频率 {0} 秒",
+        en: "Delay/rate of {0} seconds",
+    },
+    Message {
+        key: "completion.delay-or-rate.doc",
+        zh_cn: "延迟或频率为 {0} 秒",
+        en: "A delay or rate of {0} seconds",
+    },
+    Message {
+        key: "completion.delay-or-rate-minute",
+        zh_cn: "延迟/频率 {0} 秒（1 分钟）",
+        en: "Delay/rate of {0} seconds (1 minute)",
+    },
+    Message {
+        key: "completion.delay-or-rate-minute.doc",
+        zh_cn: "延迟或频率为 {0} 秒（1 分钟）",
+        en: "A delay or rate of {0} seconds (1 minute)",
+    },
+    Message {
+        key: "completion.fix-delay-example",
+        zh_cn: "**fix_delay 示例**:",
+        en: "**fix_delay example**:",
+    },
+    Message {
+        key: "completion.fix-rate-example",
+        zh_cn: "**fix_rate 示例**:",
+        en: "**fix_rate example**:",
+    },
+    Message {
+        key: "completion.one-shot",
+        zh_cn: "启动后延迟 {0} 秒执行一次",
+        en: "Runs once {0} seconds after startup",
+    },
+    Message {
+        key: "completion.one-shot.doc",
+        zh_cn: "应用启动后延迟 {0} 秒执行一次，之后不再执行",
+        en: "Runs once, {0} seconds after the application starts, and never again",
+    },
+    Message {
+        key: "completion.override",
+        zh_cn: "覆盖基础配置 `app.toml` 中的 `[{0}] {1}`\n\n**基础值**: `{2}`",
+        en: "Overrides `[{0}] {1}` from the base configuration `app.toml`\n\n**Base value**: `{2}`",
+    },
+    Message {
+        key: "completion.section",
+        zh_cn: "[{0}] 配置节",
+        en: "[{0}] section",
+    },
+    Message {
+        key: "completion.section.doc",
+        zh_cn: "**{0}** 插件配置节\n\n{0} 插件配置\n\n**使用方式**:\n```toml\n[{0}]\n# 配置项...\n```",
+        en: "**{0}** plugin section\n\n{0} plugin configuration\n\n**Usage**:\n```toml\n[{0}]\n# properties...\n```",
+    },
+    Message {
+        key: "completion.type",
+        zh_cn: "**类型**: `{0}`",
+        en: "**Type**: `{0}`",
+    },
+    Message {
+        key: "completion.enum-value",
+        zh_cn: "枚举值: {0}",
+        en: "Enum value: {0}",
+    },
+    Message {
+        key: "completion.enum-value.doc",
+        zh_cn: "枚举值 `{0}`",
+        en: "Enum value `{0}`",
+    },
+    Message {
+        key: "completion.log-level",
+        zh_cn: "日志级别",
+        en: "Log level",
+    },
+    Message {
+        key: "env-var.HOST",
+        zh_cn: "主机地址",
+        en: "Host address",
+    },
+    Message {
+        key: "env-var.PORT",
+        zh_cn: "端口号",
+        en: "Port number",
+    },
+    Message {
+        key: "env-var.DATABASE_URL",
+        zh_cn: "数据库连接 URL",
+        en: "Database connection URL",
+    },
+    Message {
+        key: "env-var.REDIS_URL",
+        zh_cn: "Redis 连接 URL",
+        en: "Redis connection URL",
+    },
+    Message {
+        key: "env-var.LOG_LEVEL",
+        zh_cn: "日志级别",
+        en: "Log level",
+    },
+    Message {
+        key: "env-var.ENV",
+        zh_cn: "运行环境",
+        en: "Runtime environment",
+    },
+    Message {
+        key: "env-var.DEBUG",
+        zh_cn: "调试模式",
+        en: "Debug mode",
+    },
+    Message {
+        key: "completion.env-var.doc",
+        zh_cn: "**{0}**\n\n{1}\n\n**使用方式**:\n```toml\nvalue = \"${{0}:default}\"\n```",
+        en: "**{0}**\n\n{1}\n\n**Usage**:\n```toml\nvalue = \"${{0}:default}\"\n```",
+    },
+    Message {
+        key: "completion.header",
+        zh_cn: "HTTP 头",
+        en: "HTTP header",
+    },
+    Message {
+        key: "completion.header-value",
+        zh_cn: "{0} 的常用值",
+        en: "Common value for {0}",
+    },
+    Message {
+        key: "completion.typed-header",
+        zh_cn: "类型化头：{0}",
+        en: "Typed header: {0}",
+    },
+    Message {
+        key: "completion.path-binding",
+        zh_cn: "按路由 `{0}` 中声明的顺序绑定路径参数",
+        en: "Binds the path parameters in the order declared in route `{0}`",
+    },
+    Message {
+        key: "completion.registered-component",
+        zh_cn: "注册的组件：{0}",
+        en: "Registered component: {0}",
+    },
+    Message {
+        key: "completion.default-component-name",
+        zh_cn: "按约定的默认名称：{0}",
+        en: "Conventional default name: {0}",
+    },
+    Message {
+        key: "completion.workspace-middleware",
+        zh_cn: "工作空间中的中间件",
+        en: "Middleware defined in the workspace",
+    },
+    Message {
+        key: "hover.middleware.bundled",
+        zh_cn: "{0}\n\n**模块**: `{1}::{2}`",
+        en: "{0}\n\n**Module**: `{1}::{2}`",
+    },
+    Message {
+        key: "hover.auto-config.section",
+        zh_cn: "**{0}**（{1} 个）\n\n",
+        en: "**{0}** ({1})\n\n",
+    },
+    Message {
+        key: "hover.auto-config.more",
+        zh_cn: "- …另外 {0} 个\n",
+        en: "- …and {0} more\n",
+    },
+    Message {
+        key: "hover.auto-config.routes",
+        zh_cn: "路由",
+        en: "Routes",
+    },
+    Message {
+        key: "hover.auto-config.jobs",
+        zh_cn: "定时任务",
+        en: "Scheduled jobs",
+    },
+    Message {
+        key: "hover.auto-config.listeners",
+        zh_cn: "消息流监听器",
+        en: "Stream listeners",
+    },
+    Message {
+        key: "hover.auto-config.registered",
+        zh_cn: "\n## 自动注册\n\n",
+        en: "\n## Registered automatically\n\n",
+    },
+    Message {
+        key: "hover.middleware-chain",
+        zh_cn: "# 中间件链\n\n",
+        en: "# Middleware chain\n\n",
+    },
+    Message {
+        key: "hover.middleware-chain.empty",
+        zh_cn: "未应用任何中间件。\n",
+        en: "No middleware applied.\n",
+    },
+    Message {
+        key: "hover.middleware-chain.order",
+        zh_cn: "从外到内依次执行：\n\n",
+        en: "Runs from outermost to innermost:\n\n",
+    },
+    Message {
+        key: "hover.middleware-chain.item",
+        zh_cn: "{0}. `{1}`（{2}）\n",
+        en: "{0}. `{1}` ({2})\n",
+    },
+    Message {
+        key: "hover.middleware-chain.missing",
+        zh_cn: "\n⚠️ 缺少必需的中间件 `{0}`（规则 `{1}`）\n",
+        en: "\n⚠️ Missing required middleware `{0}` (rule `{1}`)\n",
+    },
+    Message {
+        key: "hover.middleware",
+        zh_cn: "# 中间件\n\n`{0}`\n",
+        en: "# Middleware\n\n`{0}`\n",
+    },
+    Message {
+        key: "hover.middleware.definition",
+        zh_cn: "\n**定义位置**: `{0}`\n",
+        en: "\n**Defined in**: `{0}`\n",
+    },
+    Message {
+        key: "hover.middleware.no-config",
+        zh_cn: "\n未找到关联的配置结构。\n",
+        en: "\nNo associated configuration struct found.\n",
+    },
+    Message {
+        key: "hover.middleware.config",
+        zh_cn: "\n## 配置 `{0}`\n\n配置节：`[{1}]`\n\n",
+        en: "\n## Configuration `{0}`\n\nSection: `[{1}]`\n\n",
+    },
+    Message {
+        key: "hover.middleware.config-table",
+        zh_cn: "| 配置项 | 类型 | 当前值 |\n|---|---|---|\n",
+        en: "| Property | Type | Current value |\n|---|---|---|\n",
+    },
+    Message {
+        key: "hover.unset",
+        zh_cn: "*未设置*",
+        en: "*not set*",
+    },
+    Message {
+        key: "timeline.title.measured",
+        zh_cn: "**调度时间线**（任务体中的固定等待至少 {0}）",
+        en: "**Schedule timeline** (fixed waits in the job body take at least {0})",
+    },
+    Message {
+        key: "timeline.title.assumed",
+        zh_cn: "**调度时间线**（假设每次执行耗时 {0}）",
+        en: "**Schedule timeline** (assuming each run takes {0})",
+    },
+    Message {
+        key: "timeline.legend",
+        zh_cn: "`█` 执行中，`·` 等待，`↑` 开始执行\n\n",
+        en: "`█` running, `·` waiting, `↑` run starts\n\n",
+    },
+    Message {
+        key: "timeline.fix-rate",
+        zh_cn: "每隔 {0} **开始**一次执行，与上一次执行是否完成无关。\n\n",
+        en: "A run **starts** every {0}, whether or not the previous run has finished.\n\n",
+    },
+    Message {
+        key: "timeline.fix-rate.overlap",
+        zh_cn: "⚠️ 任务体中的等待至少需要 {0}，不短于 {1} 的执行频率：上一次执行完成前下一次已经到期，执行会堆积或重叠。可以改用 `#[fix_delay({2})]` 在每次执行完成后再计时。\n\n",
+        en: "⚠️ The waits in the job body take at least {0}, no shorter than the {1} rate: the next run is due before the previous one finishes, so runs pile up or overlap. Use `#[fix_delay({2})]` to start timing after each run completes.\n\n",
+    },
+    Message {
+        key: "timeline.fix-delay",
+        zh_cn: "每次执行**完成**后等待 {0} 再开始下一次，相邻两次开始的间隔为执行耗时加 {0}。\n\n",
+        en: "After each run **completes**, waits {0} before starting the next, so consecutive starts are the run time plus {0} apart.\n\n",
+    },
+    Message {
+        key: "health.route-conflicts",
+        zh_cn: "{0} 个路由冲突",
+        en: "{0} route conflicts",
+    },
+    Message {
+        key: "health.unresolved-injections",
+        zh_cn: "{0} 个未解析的注入",
+        en: "{0} unresolved injections",
+    },
+    Message {
+        key: "health.unused-config-keys",
+        zh_cn: "{0} 个未使用的配置项",
+        en: "{0} unused config keys",
+    },
+    Message {
+        key: "health.title",
+        zh_cn: "spring 项目健康：{0}",
+        en: "spring project health: {0}",
+    },
+    Message {
+        key: "health.healthy",
+        zh_cn: "spring 项目健康：没有发现问题",
+        en: "spring project health: no problems found",
+    },
+    Message {
+        key: "lens.config.unset",
+        zh_cn: "未配置（使用默认值）",
+        en: "Not configured (uses the default)",
+    },
+    Message {
+        key: "hover.origin",
+        zh_cn: "\n---\n\n**值来源**\n\n| 位置 | 生效条件 | 值 |\n|---|---|---|\n",
+        en: "\n---\n\n**Value sources**\n\n| Location | Applies when | Value |\n|---|---|---|\n",
+    },
+    Message {
+        key: "hover.origin.profile",
+        zh_cn: "激活 profile `{0}` 时（覆盖基础配置）",
+        en: "profile `{0}` is active (overrides the base configuration)",
+    },
+    Message {
+        key: "hover.origin.base",
+        zh_cn: "基础配置（未被激活的 profile 覆盖时）",
+        en: "base configuration (unless overridden by an active profile)",
+    },
+    Message {
+        key: "hover.origin.env-default",
+        zh_cn: "<br>环境变量 `{0}`，未设置时使用默认值 `{1}`",
+        en: "<br>environment variable `{0}`, defaults to `{1}` when unset",
+    },
+    Message {
+        key: "hover.origin.env-required",
+        zh_cn: "<br>环境变量 `{0}`，未设置时启动失败",
+        en: "<br>environment variable `{0}`, startup fails when unset",
+    },
+    Message {
+        key: "hover.origin.profiles-only",
+        zh_cn: "\n`app.toml` 未设置该配置项，未激活上述 profile 时使用默认值。\n",
+        en: "\n`app.toml` does not set this property; the default applies when none of the profiles above is active.\n",
+    },
+    Message {
+        key: "rename.warning.undeclared-module",
+        zh_cn: "模块 {0} 没有在父模块中声明（缺少 `mod {1};`）",
+        en: "Module {0} is not declared in its parent module (missing `mod {1};`)",
+    },
+    Message {
+        key: "rename.warning.stale-references",
+        zh_cn: "{0} 个文件仍然引用旧的模块路径 {1}（已移动到 {2}）: {3}",
+        en: "{0} files still reference the old module path {1} (moved to {2}): {3}",
+    },
+    Message {
+        key: "hover.component.title",
+        zh_cn: "# Component 属性宏\n\n",
+        en: "# Component attribute macro\n\n",
+    },
+    Message {
+        key: "hover.component.description",
+        zh_cn: "自动将组件创建函数转换为 Plugin 实现，无需手动实现 Plugin trait。\n\n",
+        en: "Turns a component factory function into a Plugin implementation, so the Plugin trait does not need to be implemented by hand.\n\n",
+    },
+    Message {
+        key: "hover.component.function",
+        zh_cn: "**函数**: `{0}`\n\n",
+        en: "**Function**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.component.type",
+        zh_cn: "**组件类型**: `{0}`\n\n",
+        en: "**Component type**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.component.plugin-name",
+        zh_cn: "**插件名称**: `\"{0}\"`\n\n",
+        en: "**Plugin name**: `\"{0}\"`\n\n",
+    },
+    Message {
+        key: "hover.component.dependencies",
+        zh_cn: "**依赖**:\n\n",
+        en: "**Dependencies**:\n\n",
+    },
+    Message {
+        key: "hover.expanded",
+        zh_cn: "**展开后的代码**:\n\n",
+        en: "**Expanded code**:\n\n",
+    },
+    Message {
+        key: "hover.service.title",
+        zh_cn: "# Service 派生宏\n\n",
+        en: "# Service derive macro\n\n",
+    },
+    Message {
+        key: "hover.service.description",
+        zh_cn: "自动为结构体实现依赖注入功能，从应用上下文中获取组件和配置。\n\n",
+        en: "Implements dependency injection for the struct, fetching components and configuration from the application context.\n\n",
+    },
+    Message {
+        key: "hover.service.struct",
+        zh_cn: "**结构体**: `{0}`\n\n",
+        en: "**Struct**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.deprecated",
+        zh_cn: "**已弃用**\n\n",
+        en: "**Deprecated**\n\n",
+    },
+    Message {
+        key: "hover.service.fields",
+        zh_cn: "**注入字段**:\n\n",
+        en: "**Injected fields**:\n\n",
+    },
+    Message {
+        key: "hover.service.named-component",
+        zh_cn: " - 注入组件 `\"{0}\"`",
+        en: " - injects component `\"{0}\"`",
+    },
+    Message {
+        key: "hover.service.component",
+        zh_cn: " - 注入组件",
+        en: " - injects a component",
+    },
+    Message {
+        key: "hover.service.config",
+        zh_cn: " - 注入配置",
+        en: " - injects configuration",
+    },
+    Message {
+        key: "hover.inject.title",
+        zh_cn: "# Inject 属性宏\n\n",
+        en: "# Inject attribute macro\n\n",
+    },
+    Message {
+        key: "hover.inject.description",
+        zh_cn: "标记字段从应用上下文中自动注入依赖。\n\n",
+        en: "Marks a field to be injected from the application context.\n\n",
+    },
+    Message {
+        key: "hover.inject.component",
+        zh_cn: "**注入类型**: 组件 (Component)\n\n",
+        en: "**Injection**: component\n\n",
+    },
+    Message {
+        key: "hover.inject.component-description",
+        zh_cn: "从应用上下文中获取已注册的组件实例。\n\n",
+        en: "Fetches a registered component instance from the application context.\n\n",
+    },
+    Message {
+        key: "hover.inject.component-name",
+        zh_cn: "**组件名称**: `\"{0}\"`\n\n",
+        en: "**Component name**: `\"{0}\"`\n\n",
+    },
+    Message {
+        key: "hover.inject.by-name",
+        zh_cn: "使用指定名称查找组件，适用于多实例场景（如多数据源）。\n\n",
+        en: "Looks the component up by name, for multiple instances of one type (such as several data sources).\n\n",
+    },
+    Message {
+        key: "hover.inject.code",
+        zh_cn: "**注入代码**:\n\n",
+        en: "**Injection code**:\n\n",
+    },
+    Message {
+        key: "hover.inject.by-type",
+        zh_cn: "使用类型自动查找组件。\n\n",
+        en: "Looks the component up by its type.\n\n",
+    },
+    Message {
+        key: "hover.inject.config",
+        zh_cn: "**注入类型**: 配置 (Config)\n\n",
+        en: "**Injection**: configuration\n\n",
+    },
+    Message {
+        key: "hover.inject.config-description",
+        zh_cn: "从配置文件中加载配置项。\n\n",
+        en: "Loads the configuration from the configuration file.\n\n",
+    },
+    Message {
+        key: "hover.inject.config-prefix",
+        zh_cn: "配置项通过 `#[config_prefix]` 指定的前缀从 `config/app.toml` 中读取。\n\n",
+        en: "The configuration is read from `config/app.toml` using the prefix given by `#[config_prefix]`.\n\n",
+    },
+    Message {
+        key: "hover.inject.example",
+        zh_cn: "\n**使用示例**:\n\n",
+        en: "\n**Example**:\n\n",
+    },
+    Message {
+        key: "hover.auto-config.title",
+        zh_cn: "# AutoConfig 属性宏\n\n",
+        en: "# AutoConfig attribute macro\n\n",
+    },
+    Message {
+        key: "hover.auto-config.description",
+        zh_cn: "自动注册配置器，在应用启动时配置路由、任务等。\n\n",
+        en: "Registers a configurator that sets up routes, jobs and so on when the application starts.\n\n",
+    },
+    Message {
+        key: "hover.auto-config.configurator",
+        zh_cn: "**配置器类型**: `{0}`\n\n",
+        en: "**Configurator**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.route.title",
+        zh_cn: "# 路由宏\n\n",
+        en: "# Route macro\n\n",
+    },
+    Message {
+        key: "hover.route.description",
+        zh_cn: "注册 HTTP 路由处理器。\n\n",
+        en: "Registers an HTTP route handler.\n\n",
+    },
+    Message {
+        key: "hover.route.path",
+        zh_cn: "**路由路径**: `{0}`\n\n",
+        en: "**Path**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.route.methods",
+        zh_cn: "**HTTP 方法**: {0}\n\n",
+        en: "**HTTP methods**: {0}\n\n",
+    },
+    Message {
+        key: "hover.route.middlewares",
+        zh_cn: "**中间件**: {0}\n\n",
+        en: "**Middlewares**: {0}\n\n",
+    },
+    Message {
+        key: "hover.route.handler",
+        zh_cn: "**处理器函数**: `{0}`\n\n",
+        en: "**Handler**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.route.span",
+        zh_cn: "**追踪 span**: `{0}`（`{1}`）\n\n",
+        en: "**Tracing span**: `{0}` (`{1}`)\n\n",
+    },
+    Message {
+        key: "hover.route.span-fields",
+        zh_cn: "**记录字段**: {0}\n\n",
+        en: "**Recorded fields**: {0}\n\n",
+    },
+    Message {
+        key: "hover.job.title",
+        zh_cn: "# 任务调度宏\n\n",
+        en: "# Job scheduling macro\n\n",
+    },
+    Message {
+        key: "hover.job.cron",
+        zh_cn: "定时任务，使用 Cron 表达式指定执行时间。\n\n",
+        en: "Scheduled job; a cron expression specifies when it runs.\n\n",
+    },
+    Message {
+        key: "hover.job.expression",
+        zh_cn: "**Cron 表达式**: `{0}`\n\n",
+        en: "**Cron expression**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.job.cron-format",
+        zh_cn: "**格式**: `秒 分 时 日 月 星期`\n\n",
+        en: "**Format**: `sec min hour day month weekday`\n\n",
+    },
+    Message {
+        key: "hover.job.timezone",
+        zh_cn: "**时区**: `{0}`\n\n",
+        en: "**Time zone**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.job.fix-delay",
+        zh_cn: "固定延迟任务，任务完成后延迟指定秒数再次执行。\n\n",
+        en: "Fixed-delay job; runs again the given number of seconds after the previous run finishes.\n\n",
+    },
+    Message {
+        key: "hover.job.delay",
+        zh_cn: "**延迟秒数**: `{0}`\n\n",
+        en: "**Delay (seconds)**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.job.fix-rate",
+        zh_cn: "固定频率任务，每隔指定秒数执行一次。\n\n",
+        en: "Fixed-rate job; runs every given number of seconds.\n\n",
+    },
+    Message {
+        key: "hover.job.rate",
+        zh_cn: "**频率秒数**: `{0}`\n\n",
+        en: "**Rate (seconds)**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.job.one-shot",
+        zh_cn: "一次性任务，应用启动后延迟指定秒数执行一次，之后不再执行。\n\n",
+        en: "One-shot job; runs once, the given number of seconds after the application starts.\n\n",
+    },
+    Message {
+        key: "expand.begin",
+        zh_cn: "// >>> {0}展开\n",
+        en: "// >>> {0} expansion\n",
+    },
+    Message {
+        key: "expand.end",
+        zh_cn: "\n// <<< {0}展开结束\n",
+        en: "\n// <<< end of {0} expansion\n",
+    },
+    Message {
+        key: "expand.original",
+        zh_cn: "// 原始定义\n",
+        en: "// Original definition\n",
+    },
+    Message {
+        key: "expand.generated",
+        zh_cn: "// 展开后的代码\n",
+        en: "// Expanded code\n",
+    },
+    Message {
+        key: "expand.service.build",
+        zh_cn: "    /// 从应用上下文构建服务实例\n",
+        en: "    /// Builds the service from the application context\n",
+    },
+    Message {
+        key: "expand.service.manual-init",
+        zh_cn: "        let {0} = Default::default(); // 需要手动初始化\n",
+        en: "        let {0} = Default::default(); // must be initialized manually\n",
+    },
+    Message {
+        key: "expand.inject.title",
+        zh_cn: "// Inject 属性展开\n",
+        en: "// Inject attribute expansion\n",
+    },
+    Message {
+        key: "expand.inject.description",
+        zh_cn: "// 这个字段将在运行时从应用上下文中注入\n",
+        en: "// This field is injected from the application context at runtime\n",
+    },
+    Message {
+        key: "expand.inject.component",
+        zh_cn: "// 注入类型: 组件\n",
+        en: "// Injection: component\n",
+    },
+    Message {
+        key: "expand.inject.component-name",
+        zh_cn: "// 组件名称: \"{0}\"\n",
+        en: "// Component name: \"{0}\"\n",
+    },
+    Message {
+        key: "expand.inject.named-code",
+        zh_cn: "// 注入代码: app.get_component::<T>(\"{0}\")\n",
+        en: "// Injection code: app.get_component::<T>(\"{0}\")\n",
+    },
+    Message {
+        key: "expand.inject.component-code",
+        zh_cn: "// 注入代码: app.get_component::<T>()\n",
+        en: "// Injection code: app.get_component::<T>()\n",
+    },
+    Message {
+        key: "expand.inject.config",
+        zh_cn: "// 注入类型: 配置\n",
+        en: "// Injection: configuration\n",
+    },
+    Message {
+        key: "expand.inject.config-code",
+        zh_cn: "// 注入代码: app.get_config::<T>()\n",
+        en: "// Injection code: app.get_config::<T>()\n",
+    },
+    Message {
+        key: "expand.auto-config.title",
+        zh_cn: "// AutoConfig 宏展开\n",
+        en: "// AutoConfig macro expansion\n",
+    },
+    Message {
+        key: "expand.auto-config.configurator",
+        zh_cn: "// 配置器类型: {0}\n",
+        en: "// Configurator: {0}\n",
+    },
+    Message {
+        key: "expand.auto-config.description",
+        zh_cn: "// 这个函数将在应用启动时自动注册配置\n",
+        en: "// This function is registered as a configurator when the application starts\n",
+    },
+    Message {
+        key: "expand.generated-heading",
+        zh_cn: "// 展开后的代码:\n",
+        en: "// Expanded code:\n",
+    },
+    Message {
+        key: "expand.original-body",
+        zh_cn: "//     // ... 原函数体\n",
+        en: "//     // ... original function body\n",
+    },
+    Message {
+        key: "expand.route.title",
+        zh_cn: "// 路由宏展开\n",
+        en: "// Route macro expansion\n",
+    },
+    Message {
+        key: "expand.route.path",
+        zh_cn: "// 路由路径: {0}\n",
+        en: "// Path: {0}\n",
+    },
+    Message {
+        key: "expand.route.methods",
+        zh_cn: "// HTTP 方法: {0}\n",
+        en: "// HTTP methods: {0}\n",
+    },
+    Message {
+        key: "expand.route.middlewares",
+        zh_cn: "// 中间件: {0}\n",
+        en: "// Middlewares: {0}\n",
+    },
+    Message {
+        key: "expand.route.apply-middlewares",
+        zh_cn: "// 应用中间件:\n",
+        en: "// Applied middlewares:\n",
+    },
+    Message {
+        key: "expand.job.title",
+        zh_cn: "// 任务调度宏展开\n",
+        en: "// Job scheduling macro expansion\n",
+    },
+    Message {
+        key: "expand.job.cron",
+        zh_cn: "// 任务类型: Cron\n",
+        en: "// Job type: Cron\n",
+    },
+    Message {
+        key: "expand.job.expression",
+        zh_cn: "// Cron 表达式: {0}\n",
+        en: "// Cron expression: {0}\n",
+    },
+    Message {
+        key: "expand.job.timezone",
+        zh_cn: "// 时区: {0}\n",
+        en: "// Time zone: {0}\n",
+    },
+    Message {
+        key: "expand.job.body",
+        zh_cn: "//         // 任务函数体\n",
+        en: "//         // job function body\n",
+    },
+    Message {
+        key: "expand.job.fix-delay",
+        zh_cn: "// 任务类型: FixDelay\n",
+        en: "// Job type: FixDelay\n",
+    },
+    Message {
+        key: "expand.job.delay",
+        zh_cn: "// 延迟秒数: {0}\n",
+        en: "// Delay (seconds): {0}\n",
+    },
+    Message {
+        key: "expand.job.fix-delay-description",
+        zh_cn: "// 说明: 任务完成后延迟指定秒数再次执行\n",
+        en: "// Runs again the given number of seconds after the previous run finishes\n",
+    },
+    Message {
+        key: "expand.job.fix-rate",
+        zh_cn: "// 任务类型: FixRate\n",
+        en: "// Job type: FixRate\n",
+    },
+    Message {
+        key: "expand.job.rate",
+        zh_cn: "// 频率秒数: {0}\n",
+        en: "// Rate (seconds): {0}\n",
+    },
+    Message {
+        key: "expand.job.fix-rate-description",
+        zh_cn: "// 说明: 每隔指定秒数执行一次任务\n",
+        en: "// Runs every given number of seconds\n",
+    },
+    Message {
+        key: "expand.job.one-shot",
+        zh_cn: "// 任务类型: OneShot\n",
+        en: "// Job type: OneShot\n",
+    },
+    Message {
+        key: "expand.job.one-shot-description",
+        zh_cn: "// 说明: 应用启动后延迟指定秒数执行一次任务\n",
+        en: "// Runs once, the given number of seconds after the application starts\n",
+    },
+    Message {
+        key: "hover.extractor.title",
+        zh_cn: "# {0} 提取器\n\n",
+        en: "# {0} extractor\n\n",
+    },
+    Message {
+        key: "hover.extractor.type",
+        zh_cn: "**参数类型**: `{0}`\n\n",
+        en: "**Parameter type**: `{0}`\n\n",
+    },
+    Message {
+        key: "hover.extractor.query-params",
+        zh_cn: "**查询参数**:\n",
+        en: "**Query parameters**:\n",
+    },
+    Message {
+        key: "hover.extractor.example",
+        zh_cn: "\n**示例**: `?{0}`\n\n",
+        en: "\n**Example**: `?{0}`\n\n",
+    },
+    Message {
+        key: "hover.extractor.shape",
+        zh_cn: "**序列化结构**:\n\n```jsonc\n",
+        en: "**Serialized shape**:\n\n```jsonc\n",
+    },
+    Message {
+        key: "hover.extractor.no-fields",
+        zh_cn: "结构体没有命名字段，不会提取任何参数。\n",
+        en: "The struct has no named fields, so no parameters are extracted.\n",
+    },
+    Message {
+        key: "hover.extractor.primitive",
+        zh_cn: "⚠️ 基本类型无法从查询字符串反序列化，请使用结构体。\n",
+        en: "⚠️ Primitive types cannot be deserialized from a query string; use a struct.\n",
+    },
+    Message {
+        key: "hover.extractor.not-found",
+        zh_cn: "未在工作空间中找到结构体 `{0}` 的定义。\n",
+        en: "No definition of struct `{0}` was found in the workspace.\n",
+    },
+    Message {
+        key: "hover.method.invalid",
+        zh_cn: "⚠️ `{0}` 不是有效的 HTTP 方法，最接近的是 `{1}`。\n\n",
+        en: "⚠️ `{0}` is not a valid HTTP method; the closest is `{1}`.\n\n",
+    },
+    Message {
+        key: "hover.method.allowed",
+        zh_cn: "**允许的值**（不区分大小写）:\n",
+        en: "**Allowed values** (case-insensitive):\n",
+    },
+    Message {
+        key: "rule.E001",
+        zh_cn: "Service 字段的组件名称不能为空字符串",
+        en: "The component name of a Service field must not be an empty string",
+    },
+    Message {
+        key: "rule.E002",
+        zh_cn: "配置注入 (config) 不应该指定组件名称",
+        en: "A config injection must not specify a component name",
+    },
+    Message {
+        key: "rule.E003",
+        zh_cn: "AutoConfig 宏必须指定配置器类型",
+        en: "The AutoConfig macro must specify a configurator type",
+    },
+    Message {
+        key: "rule.E004",
+        zh_cn: "路由路径不能为空",
+        en: "The route path must not be empty",
+    },
+    Message {
+        key: "rule.E005",
+        zh_cn: "路由路径必须以 '/' 开头",
+        en: "The route path must start with '/'",
+    },
+    Message {
+        key: "rule.E006",
+        zh_cn: "路由必须至少指定一个 HTTP 方法",
+        en: "A route must specify at least one HTTP method",
+    },
+    Message {
+        key: "rule.E007",
+        zh_cn: "路由处理器函数名称不能为空",
+        en: "The route handler function name must not be empty",
+    },
+    Message {
+        key: "rule.E008",
+        zh_cn: "路径参数不能嵌套",
+        en: "Path parameters must not be nested",
+    },
+    Message {
+        key: "rule.E009",
+        zh_cn: "路径参数缺少开括号 '{'",
+        en: "A path parameter is missing its opening brace '{'",
+    },
+    Message {
+        key: "rule.E010",
+        zh_cn: "路径参数名称不能为空",
+        en: "The path parameter name must not be empty",
+    },
+    Message {
+        key: "rule.E011",
+        zh_cn: "路径参数名称只能包含字母、数字和下划线",
+        en: "Path parameter names may only contain letters, digits and underscores",
+    },
+    Message {
+        key: "rule.E012",
+        zh_cn: "路径参数缺少闭括号 '}'",
+        en: "A path parameter is missing its closing brace '}'",
+    },
+    Message {
+        key: "rule.E013",
+        zh_cn: "Cron 表达式不能为空",
+        en: "The cron expression must not be empty",
+    },
+    Message {
+        key: "rule.E014",
+        zh_cn: "频率秒数不能为 0",
+        en: "The fixed rate in seconds must not be 0",
+    },
+    Message {
+        key: "rule.E015",
+        zh_cn: "Cron 表达式应该包含 6 个部分（秒 分 时 日 月 星期）",
+        en: "A cron expression should have 6 fields (second minute hour day month weekday)",
+    },
+    Message {
+        key: "rule.E016",
+        zh_cn: "Component 函数必须返回一个具体的类型，不能是 ()",
+        en: "A Component function must return a concrete type, not ()",
+    },
+    Message {
+        key: "rule.E017",
+        zh_cn: "插件名称不能为空字符串",
+        en: "The plugin name must not be an empty string",
+    },
+    Message {
+        key: "rule.E018",
+        zh_cn: "Query<T> 的类型参数必须是结构体",
+        en: "The type parameter of Query<T> must be a struct",
+    },
+    Message {
+        key: "rule.E019",
+        zh_cn: "路径参数的约束不是合法的正则表达式",
+        en: "The path parameter constraint is not a valid regular expression",
+    },
+    Message {
+        key: "rule.E020",
+        zh_cn: "路由宏的 method 参数不是有效的 HTTP 方法",
+        en: "The method argument of the route macro is not a valid HTTP method",
+    },
+    Message {
+        key: "rule.E021",
+        zh_cn: "路由处理器不是异步函数",
+        en: "The route handler is not an async function",
+    },
+    Message {
+        key: "rule.E022",
+        zh_cn: "任务函数不是异步函数",
+        en: "The job function is not an async function",
+    },
+    Message {
+        key: "rule.E023",
+        zh_cn: "任务函数有返回值",
+        en: "The job function returns a value",
+    },
+    Message {
+        key: "rule.E024",
+        zh_cn: "同一字段上有多个 #[inject] 属性",
+        en: "A field has more than one #[inject] attribute",
+    },
+    Message {
+        key: "rule.E025",
+        zh_cn: "#[inject] 用于元组结构体字段",
+        en: "#[inject] is used on a tuple struct field",
+    },
+    Message {
+        key: "rule.E026",
+        zh_cn: "#[inject] 用于没有派生 Service 的结构体或枚举",
+        en: "#[inject] is used on a struct or enum that does not derive Service",
+    },
+    Message {
+        key: "rule.E027",
+        zh_cn: "消费请求体的提取器不是路由处理器的最后一个参数",
+        en: "An extractor that consumes the request body is not the last handler parameter",
+    },
+    Message {
+        key: "rule.E028",
+        zh_cn: "路径参数名称在同一路径中重复",
+        en: "A path parameter name is repeated in the same path",
+    },
+    Message {
+        key: "rule.E029",
+        zh_cn: "路径参数数量过多",
+        en: "The path has too many parameters",
+    },
+    Message {
+        key: "rule.E030",
+        zh_cn: "Cron 任务的时区不在时区数据库中",
+        en: "The cron job time zone is not in the time zone database",
+    },
+    Message {
+        key: "rule.E031",
+        zh_cn: "任务函数的参数无法由 spring-job 提供",
+        en: "The job function parameter cannot be provided by spring-job",
+    },
+    Message {
+        key: "rule.W001",
+        zh_cn: "延迟秒数为 0 可能不是预期的行为",
+        en: "A fixed delay of 0 seconds is probably not intended",
+    },
+    Message {
+        key: "rule.W002",
+        zh_cn: "重复的依赖",
+        en: "Duplicate dependency",
+    },
+    Message {
+        key: "rule.W003",
+        zh_cn: "路由路径包含查询字符串，路由匹配不会使用查询字符串",
+        en: "The route path contains a query string, which route matching ignores",
+    },
+    Message {
+        key: "rule.W004",
+        zh_cn: "同一个请求可能匹配多个路由",
+        en: "The same request may match more than one route",
+    },
+    Message {
+        key: "rule.W005",
+        zh_cn: "同一文件中的路由只有末尾斜杠不同",
+        en: "Routes in the same file differ only by a trailing slash",
+    },
+    Message {
+        key: "rule.W006",
+        zh_cn: "同一文件中的路由只有大小写不同",
+        en: "Routes in the same file differ only by case",
+    },
+    Message {
+        key: "rule.W007",
+        zh_cn: "处理器尚未实现（函数体只有 todo!()、unimplemented!() 或返回 501 状态码）",
+        en: "The handler is not implemented yet (the body is only todo!(), unimplemented!() or returns a 501 status)",
+    },
+    Message {
+        key: "rule.http-semantics/get-with-body",
+        zh_cn: "GET/HEAD 处理器读取了请求体",
+        en: "A GET/HEAD handler reads the request body",
+    },
+    Message {
+        key: "rule.http-semantics/delete-with-body",
+        zh_cn: "DELETE 处理器返回了实体内容",
+        en: "A DELETE handler returns an entity body",
+    },
+    Message {
+        key: "rule.handler-complexity/too-long",
+        zh_cn: "处理器函数体行数超过配置的阈值",
+        en: "The handler body has more lines than the configured threshold",
+    },
+    Message {
+        key: "rule.handler-complexity/too-many-components",
+        zh_cn: "处理器注入的组件数超过配置的阈值",
+        en: "The handler injects more components than the configured threshold",
+    },
+    Message {
+        key: "rule.parse_error",
+        zh_cn: "配置文件不是合法的 TOML",
+        en: "The config file is not valid TOML",
+    },
+    Message {
+        key: "rule.undefined-section",
+        zh_cn: "配置节未在 Schema 中定义",
+        en: "The config section is not defined in the schema",
+    },
+    Message {
+        key: "rule.undefined-property",
+        zh_cn: "配置项未在 Schema 中定义",
+        en: "The property is not defined in the schema",
+    },
+    Message {
+        key: "rule.deprecated-property",
+        zh_cn: "配置项已废弃",
+        en: "The property is deprecated",
+    },
+    Message {
+        key: "rule.missing-required-property",
+        zh_cn: "缺少必需的配置项",
+        en: "A required property is missing",
+    },
+    Message {
+        key: "rule.type-mismatch",
+        zh_cn: "配置值类型与 Schema 不匹配",
+        en: "The value type does not match the schema",
+    },
+    Message {
+        key: "rule.invalid-enum-value",
+        zh_cn: "配置值不在允许的枚举值中",
+        en: "The value is not one of the allowed enum values",
+    },
+    Message {
+        key: "rule.string-too-short",
+        zh_cn: "字符串长度小于最小长度",
+        en: "The string is shorter than the minimum length",
+    },
+    Message {
+        key: "rule.string-too-long",
+        zh_cn: "字符串长度超过最大长度",
+        en: "The string is longer than the maximum length",
+    },
+    Message {
+        key: "rule.value-too-small",
+        zh_cn: "数值小于最小值",
+        en: "The number is less than the minimum",
+    },
+    Message {
+        key: "rule.value-too-large",
+        zh_cn: "数值超过最大值",
+        en: "The number is greater than the maximum",
+    },
+    Message {
+        key: "rule.empty-var-name",
+        zh_cn: "环境变量插值的变量名为空",
+        en: "The environment variable name in an interpolation is empty",
+    },
+    Message {
+        key: "rule.invalid-var-name",
+        zh_cn: "环境变量插值的变量名不合法",
+        en: "The environment variable name in an interpolation is invalid",
+    },
+    Message {
+        key: "rule.invalid-log-level",
+        zh_cn: "日志级别不是合法的 tracing 级别",
+        en: "The log level is not a valid tracing level",
+    },
+    Message {
+        key: "rule.invalid-log-filter",
+        zh_cn: "override_filter 不是合法的 env-filter 指令",
+        en: "override_filter is not a valid env-filter directive",
+    },
+    Message {
+        key: "rule.invalid-url",
+        zh_cn: "连接地址缺少协议、包含空白字符或无法解析",
+        en: "The connection URL has no scheme, contains whitespace or cannot be parsed",
+    },
+    Message {
+        key: "rule.hardcoded-secret",
+        zh_cn: "配置文件中疑似直接写入了密码、令牌或访问密钥",
+        en: "The config file appears to contain a plain-text password, token or access key",
+    },
+    Message {
+        key: "rule.unknown-job",
+        zh_cn: "配置引用了不存在的任务",
+        en: "The config references a job that does not exist",
+    },
+    Message {
+        key: "rule.duplicate-job-name",
+        zh_cn: "定时任务名称在项目中重复定义",
+        en: "The job name is defined more than once in the project",
+    },
+    Message {
+        key: "rule.job-schedule-overlap",
+        zh_cn: "同名或同一文件中的固定频率任务使用相同的极短间隔",
+        en: "Fixed-rate jobs with the same name or in the same file use the same very short interval",
+    },
+    Message {
+        key: "rule.component-type-mismatch",
+        zh_cn: "注入的组件类型与注册的类型不匹配",
+        en: "The injected component type does not match the registered type",
+    },
+    Message {
+        key: "rule.component-type-not-found",
+        zh_cn: "注入的组件类型未注册",
+        en: "The injected component type is not registered",
+    },
+    Message {
+        key: "rule.component-not-registered",
+        zh_cn: "注入的组件未注册",
+        en: "The injected component is not registered",
+    },
+    Message {
+        key: "rule.component-requires-cfg",
+        zh_cn: "注入的组件只在某个 cfg 条件下注册",
+        en: "The injected component is only registered under a cfg condition",
+    },
+    Message {
+        key: "rule.component-name-mismatch",
+        zh_cn: "组件名称与注册的组件不一致",
+        en: "The component name does not match the registered component",
+    },
+    Message {
+        key: "rule.component-name-not-found",
+        zh_cn: "找不到指定名称的组件",
+        en: "No component with the given name was found",
+    },
+    Message {
+        key: "rule.config-not-found",
+        zh_cn: "注入的配置在配置文件中不存在",
+        en: "The injected config is not present in the config file",
+    },
+    Message {
+        key: "rule.config-field-missing",
+        zh_cn: "配置结构体的必需字段在配置文件中缺少",
+        en: "A required field of the config struct is missing from the config file",
+    },
+    Message {
+        key: "rule.circular-dependency",
+        zh_cn: "服务之间存在循环依赖",
+        en: "Services depend on each other in a cycle",
+    },
+    Message {
+        key: "rule.multiple-auto-config",
+        zh_cn: "同一个二进制目标中有多个 #[auto_config] 入口",
+        en: "A binary target has more than one #[auto_config] entry point",
+    },
+    Message {
+        key: "rule.missing-auto-config",
+        zh_cn: "路由或任务没有 #[auto_config] 入口注册",
+        en: "Routes or jobs are not registered by any #[auto_config] entry point",
+    },
+    Message {
+        key: "rule.unprotected-route",
+        zh_cn: "路由匹配安全规则，但没有应用要求的中间件或 guard 提取器",
+        en: "The route matches a security rule but does not apply the required middleware or guard extractor",
+    },
+    Message {
+        key: "rule.route-prefix-convention",
+        zh_cn: "路由不符合所在目录或模块的路由前缀约定",
+        en: "The route does not follow the route prefix convention of its directory or module",
+    },
+    Message {
+        key: "rule.duplicate-handler-name",
+        zh_cn: "路由处理器与其他模块中的处理器重名",
+        en: "The route handler has the same name as a handler in another module",
+    },
+    Message {
+        key: "rule.duplicate-route-registration",
+        zh_cn: "同一个方法和路径同时通过路由宏和 Router::route 注册",
+        en: "The same method and path are registered by both a route macro and Router::route",
+    },
+    Message {
+        key: "rule.path-segment-spelling",
+        zh_cn: "路径段与项目中更常用的路径段拼写相近",
+        en: "A path segment is spelled similarly to a more common segment in the project",
+    },
+    Message {
+        key: "rule.missing-middleware",
+        zh_cn: "路由缺少中间件规则要求的中间件",
+        en: "The route is missing a middleware required by the middleware rules",
+    },
+    Message {
+        key: "rule.route-not-in-spec",
+        zh_cn: "路由没有在 OpenAPI 规范文件中声明",
+        en: "The route is not declared in the OpenAPI spec file",
+    },
+    Message {
+        key: "rule.spec-operation-without-handler",
+        zh_cn: "OpenAPI 规范中的操作没有对应的路由处理器",
+        en: "An operation in the OpenAPI spec has no matching route handler",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn placeholders(text: &str) -> HashSet<String> {
+        regex::Regex::new(r"\{\d+\}")
+            .unwrap()
+            .find_iter(text)
+            .map(|m| m.as_str().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_catalog_is_consistent() {
+        let mut keys = HashSet::new();
+        for message in MESSAGES {
+            assert!(keys.insert(message.key), "重复的消息键: {}", message.key);
+            assert_eq!(
+                placeholders(message.zh_cn),
+                placeholders(message.en),
+                "消息 {} 的占位符不一致",
+                message.key
+            );
+        }
+    }
+
+    #[test]
+    fn test_localized() {
+        assert_eq!(
+            localized(Locale::ZhCn, "E005", &[&"users"]),
+            "路由路径必须以 '/' 开头，当前路径: 'users'"
+        );
+        assert_eq!(
+            localized(Locale::En, "E005", &[&"users"]),
+            "The route path must start with '/', got 'users'"
+        );
+        // 字面量大括号和多次出现的占位符
+        assert_eq!(
+            localized(Locale::En, "secret.fix", &[&"DB_PASSWORD"]),
+            "Use environment variable ${DB_PASSWORD}"
+        );
+        assert_eq!(
+            localized(Locale::ZhCn, "E012", &[]),
+            "路径参数缺少闭括号 '}'"
+        );
+        assert_eq!(localized(Locale::En, "no-such-key", &[]), "no-such-key");
+    }
+
+    #[test]
+    fn test_canonical_message() {
+        let zh_cn = localized(Locale::ZhCn, "E005", &[&"users"]);
+        let en = localized(Locale::En, "E005", &[&"users"]);
+        assert_eq!(canonical_message("E005", &en), zh_cn);
+        assert_eq!(canonical_message("E005", &zh_cn), zh_cn);
+
+        // 多个参数和没有参数的消息
+        let en = localized(
+            Locale::En,
+            "deprecated-property",
+            &[&"web.port", &"use 'server.port'"],
+        );
+        assert_eq!(
+            canonical_message("deprecated-property", &en),
+            "配置项 'web.port' 已废弃: use 'server.port'"
+        );
+        assert_eq!(
+            canonical_message("E012", &localized(Locale::En, "E012", &[])),
+            localized(Locale::ZhCn, "E012", &[])
+        );
+
+        // 不匹配任何模板的消息原样返回
+        assert_eq!(
+            canonical_message("E005", "custom message"),
+            "custom message"
+        );
+        assert_eq!(canonical_message("X999", "other"), "other");
+    }
+
+    #[test]
+    fn test_locale_from_initialization_options() {
+        let options = serde_json::json!({ "locale": "en-US" });
+        assert_eq!(
+            Locale::from_initialization_options(Some(&options)),
+            Some(Locale::En)
+        );
+        let options = serde_json::json!({ "locale": "zh_CN" });
+        assert_eq!(
+            Locale::from_initialization_options(Some(&options)),
+            Some(Locale::ZhCn)
+        );
+        let options = serde_json::json!({ "locale": "fr" });
+        assert_eq!(Locale::from_initialization_options(Some(&options)), None);
+        assert_eq!(Locale::from_initialization_options(None), None);
+    }
+}
@@ -0,0 +1,443 @@
+//! 性能剖析模块
+//!
+//! 启用 `profiling` feature 后，扫描器、分析器、流水线步骤和请求处理函数会创建 `tracing` span，
+//! 日志系统额外安装 [`ProfileLayer`]。执行 `spring.profile.start` 命令开始记录，
+//! `spring.profile.stop` 命令停止记录并写入两个文件，便于在用户机器上排查性能问题：
+//!
+//! - 折叠栈文件（`spring-lsp-<时间戳>.folded`，每行为 `帧;帧;帧 耗时`，耗时为自身耗时的微秒数），
+//!   可以用 `inferno-flamegraph` 或 `flamegraph.pl` 生成火焰图；
+//! - pprof 格式的文件（`spring-lsp-<时间戳>.pb`，未压缩的 `profile.proto`，样本值为自身耗时的纳秒数），
+//!   可以用 `go tool pprof` 查看。
+//!
+//! 数据来自 span 的计时，不是按 CPU 时钟采样的结果：只包含创建了 span 的函数，
+//! 耗时为 span 进入期间的墙钟时间。未启用 feature 时不创建 span，也不安装记录层，命令不可用。
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tracing::span;
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// 命令：开始记录性能剖析数据
+pub const PROFILE_START_COMMAND: &str = "spring.profile.start";
+
+/// 命令：停止记录并写入折叠栈和 pprof 文件（可选参数为输出目录，默认为系统临时目录）
+pub const PROFILE_STOP_COMMAND: &str = "spring.profile.stop";
+
+/// 正在进行的记录
+struct Recording {
+    started: Instant,
+    /// 开始记录的系统时间（pprof 文件中的采集时间）
+    started_at: SystemTime,
+    /// 调用栈 -> 自身耗时
+    stacks: HashMap<String, Duration>,
+}
+
+/// 全局的记录状态（[`ProfileLayer`] 在所有线程上写入）
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+/// 停止记录的结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileReport {
+    /// 折叠栈文件路径
+    pub path: PathBuf,
+    /// pprof 文件路径
+    pub pprof_path: PathBuf,
+    /// 记录的不同调用栈数
+    pub stacks: usize,
+    /// 记录时长（毫秒）
+    pub duration_ms: u64,
+}
+
+/// 开始记录，已经在记录时丢弃之前的数据重新开始
+pub fn start() {
+    let mut recording = RECORDING.lock().unwrap_or_else(|e| e.into_inner());
+    *recording = Some(Recording {
+        started: Instant::now(),
+        started_at: SystemTime::now(),
+        stacks: HashMap::new(),
+    });
+    tracing::info!("Profiling started");
+}
+
+/// 是否正在记录
+pub fn is_recording() -> bool {
+    RECORDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some()
+}
+
+/// 停止记录并在 `dir` 中写入折叠栈文件和 pprof 文件，没有在记录时返回错误
+pub fn stop(dir: &Path) -> Result<ProfileReport, String> {
+    let recording = RECORDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+        .ok_or_else(|| "Profiling is not running".to_string())?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let duration = recording.started.elapsed();
+    let path = dir.join(format!("spring-lsp-{}.folded", timestamp));
+    let pprof_path = dir.join(format!("spring-lsp-{}.pb", timestamp));
+    for (path, content) in [
+        (&path, folded(&recording.stacks).into_bytes()),
+        (&pprof_path, pprof(&recording, duration)),
+    ] {
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+
+    let report = ProfileReport {
+        path,
+        pprof_path,
+        stacks: recording.stacks.len(),
+        duration_ms: duration.as_millis() as u64,
+    };
+    tracing::info!(
+        "Profiling stopped, wrote {} stacks to {}",
+        report.stacks,
+        report.path.display()
+    );
+    Ok(report)
+}
+
+/// 折叠栈格式的文本（按调用栈排序，耗时为微秒）
+fn folded(stacks: &HashMap<String, Duration>) -> String {
+    let mut lines: Vec<_> = stacks.iter().collect();
+    lines.sort_by(|a, b| a.0.cmp(b.0));
+    let mut output = String::new();
+    for (stack, elapsed) in lines {
+        let _ = writeln!(output, "{} {}", stack, elapsed.as_micros());
+    }
+    output
+}
+
+/// pprof 格式（`profile.proto`）的文件内容
+///
+/// 每个帧对应一个函数和一个位置（编号相同），每个调用栈对应一个样本（位置从叶子帧开始），
+/// 样本值为自身耗时的纳秒数
+fn pprof(recording: &Recording, duration: Duration) -> Vec<u8> {
+    // 字符串表的第一项必须是空字符串
+    let mut strings: Vec<&str> = vec![""];
+    let mut string_ids: HashMap<&str, u64> = HashMap::new();
+    let mut intern = |value| {
+        *string_ids.entry(value).or_insert_with(|| {
+            strings.push(value);
+            strings.len() as u64 - 1
+        })
+    };
+    let sample_type = (intern("wall"), intern("nanoseconds"));
+
+    let mut stacks: Vec<_> = recording.stacks.iter().collect();
+    stacks.sort_by(|a, b| a.0.cmp(b.0));
+    let mut frames: Vec<&str> = Vec::new();
+    let mut frame_ids: HashMap<&str, u64> = HashMap::new();
+    let samples: Vec<(Vec<u64>, u64)> = stacks
+        .iter()
+        .map(|(stack, elapsed)| {
+            let locations = stack
+                .rsplit(';')
+                .map(|frame| {
+                    *frame_ids.entry(frame).or_insert_with(|| {
+                        frames.push(frame);
+                        frames.len() as u64
+                    })
+                })
+                .collect();
+            (locations, elapsed.as_nanos() as u64)
+        })
+        .collect();
+    let names: Vec<u64> = frames.iter().map(|frame| intern(frame)).collect();
+
+    let value_type = |proto: &mut Proto| {
+        proto.uint(1, sample_type.0);
+        proto.uint(2, sample_type.1);
+    };
+    let mut profile = Proto::default();
+    profile.message(1, value_type);
+    for (locations, value) in &samples {
+        profile.message(2, |sample| {
+            sample.packed(1, locations);
+            sample.packed(2, &[*value]);
+        });
+    }
+    for id in 1..=frames.len() as u64 {
+        profile.message(4, |location| {
+            location.uint(1, id);
+            location.message(4, |line| line.uint(1, id));
+        });
+    }
+    for (id, name) in (1..).zip(&names) {
+        profile.message(5, |function| {
+            function.uint(1, id);
+            function.uint(2, *name);
+            function.uint(3, *name);
+        });
+    }
+    for value in &strings {
+        profile.bytes(6, value.as_bytes());
+    }
+    let started_at = recording
+        .started_at
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    profile.uint(9, started_at.as_nanos() as u64);
+    profile.uint(10, duration.as_nanos() as u64);
+    profile.message(11, value_type);
+    profile.uint(12, 1);
+    profile.0
+}
+
+/// protobuf 编码（只支持 pprof 文件用到的 varint 和长度前缀字段）
+#[derive(Default)]
+struct Proto(Vec<u8>);
+
+impl Proto {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    /// varint 字段（值为 0 时省略）
+    fn uint(&mut self, field: u32, value: u64) {
+        if value != 0 {
+            self.varint(u64::from(field) << 3);
+            self.varint(value);
+        }
+    }
+
+    /// 长度前缀字段
+    fn bytes(&mut self, field: u32, value: &[u8]) {
+        self.varint(u64::from(field) << 3 | 2);
+        self.varint(value.len() as u64);
+        self.0.extend_from_slice(value);
+    }
+
+    /// 打包编码的 varint 数组
+    fn packed(&mut self, field: u32, values: &[u64]) {
+        let mut packed = Proto::default();
+        for value in values {
+            packed.varint(*value);
+        }
+        self.bytes(field, &packed.0);
+    }
+
+    /// 嵌套消息
+    fn message(&mut self, field: u32, build: impl FnOnce(&mut Proto)) {
+        let mut message = Proto::default();
+        build(&mut message);
+        self.bytes(field, &message.0);
+    }
+}
+
+/// span 的计时信息，保存在 span 的扩展数据中
+struct SpanTiming {
+    /// 从根 span 到当前 span 的调用栈
+    stack: String,
+    /// 最近一次进入的时间
+    entered: Option<Instant>,
+    /// 累计耗时
+    busy: Duration,
+    /// 子 span 的累计耗时
+    children: Duration,
+}
+
+/// 记录 span 耗时的 `tracing` 层
+///
+/// 帧名称为 span 名称，span 带有 `name` 字段时追加其值（如 `request:textDocument/hover`）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProfileLayer;
+
+impl<S> Layer<S> for ProfileLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = NameVisitor(None);
+        attrs.record(&mut visitor);
+        let frame = match visitor.0 {
+            Some(name) => format!("{}:{}", span.name(), name),
+            None => span.name().to_string(),
+        };
+        // 折叠栈格式中分号分隔帧、空格分隔耗时
+        let frame = frame.replace([';', ' '], "_");
+        let stack = match span
+            .parent()
+            .and_then(|parent| Some(parent.extensions().get::<SpanTiming>()?.stack.clone()))
+        {
+            Some(parent) => format!("{};{}", parent, frame),
+            None => frame,
+        };
+        span.extensions_mut().insert(SpanTiming {
+            stack,
+            entered: None,
+            busy: Duration::ZERO,
+            children: Duration::ZERO,
+        });
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+                timing.entered = Some(Instant::now());
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+                if let Some(entered) = timing.entered.take() {
+                    timing.busy += entered.elapsed();
+                }
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(timing) = span.extensions_mut().remove::<SpanTiming>() else {
+            return;
+        };
+        if let Some(parent) = span.parent() {
+            if let Some(parent_timing) = parent.extensions_mut().get_mut::<SpanTiming>() {
+                parent_timing.children += timing.busy;
+            }
+        }
+
+        let mut recording = RECORDING.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(recording) = recording.as_mut() {
+            *recording.stacks.entry(timing.stack).or_default() +=
+                timing.busy.saturating_sub(timing.children);
+        }
+    }
+}
+
+/// 读取 span 的 `name` 字段
+struct NameVisitor(Option<String>);
+
+impl tracing::field::Visit for NameVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "name" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "name" {
+            self.0 = Some(format!("{:?}", value).trim_matches('"').to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_record_folded_stacks() {
+        let subscriber = tracing_subscriber::registry().with(ProfileLayer);
+        let dir = tempfile::tempdir().unwrap();
+
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(stop(dir.path()).is_err());
+            start();
+            assert!(is_recording());
+            {
+                let _request =
+                    tracing::trace_span!("request", name = "textDocument/hover").entered();
+                let _scan = tracing::trace_span!("scan routes").entered();
+                std::thread::sleep(Duration::from_millis(2));
+            }
+            let report = stop(dir.path()).unwrap();
+            assert!(!is_recording());
+            assert_eq!(report.stacks, 2);
+
+            let content = std::fs::read_to_string(&report.path).unwrap();
+            let lines: Vec<&str> = content.lines().collect();
+            assert_eq!(lines.len(), 2);
+            assert!(lines[0].starts_with("request:textDocument/hover "));
+            let (stack, micros) = lines[1].rsplit_once(' ').unwrap();
+            assert_eq!(stack, "request:textDocument/hover;scan_routes");
+            assert!(micros.parse::<u64>().unwrap() >= 2000);
+
+            let fields = top_level_fields(&std::fs::read(&report.pprof_path).unwrap());
+            let count = |number| fields.iter().filter(|(field, _)| *field == number).count();
+            // 两个样本、两个位置、两个函数
+            assert_eq!((count(2), count(4), count(5)), (2, 2, 2));
+            let strings: Vec<&[u8]> = fields
+                .iter()
+                .filter(|(field, _)| *field == 6)
+                .map(|(_, value)| value.as_slice())
+                .collect();
+            assert_eq!(strings[0], b"");
+            assert!(strings.contains(&b"scan_routes".as_slice()));
+            assert!(strings.contains(&b"nanoseconds".as_slice()));
+        });
+    }
+
+    #[test]
+    fn test_proto_encoding() {
+        let mut proto = Proto::default();
+        proto.uint(1, 300);
+        proto.uint(2, 0);
+        proto.packed(3, &[1, 2]);
+        proto.message(4, |message| message.bytes(1, b"ab"));
+        assert_eq!(
+            proto.0,
+            [0x08, 0xac, 0x02, 0x1a, 0x02, 0x01, 0x02, 0x22, 0x04, 0x0a, 0x02, b'a', b'b']
+        );
+    }
+
+    /// 解析消息的顶层字段（长度前缀字段的值为内容，varint 字段的值为空）
+    fn top_level_fields(mut bytes: &[u8]) -> Vec<(u64, Vec<u8>)> {
+        fn varint(bytes: &mut &[u8]) -> u64 {
+            let mut value = 0;
+            for shift in (0..).step_by(7) {
+                let byte = bytes[0];
+                *bytes = &bytes[1..];
+                value |= u64::from(byte & 0x7f) << shift;
+                if byte < 0x80 {
+                    break;
+                }
+            }
+            value
+        }
+        let mut fields = Vec::new();
+        while !bytes.is_empty() {
+            let key = varint(&mut bytes);
+            let value = if key & 7 == 2 {
+                let len = varint(&mut bytes) as usize;
+                let (value, rest) = bytes.split_at(len);
+                bytes = rest;
+                value.to_vec()
+            } else {
+                varint(&mut bytes);
+                Vec::new()
+            };
+            fields.push((key >> 3, value));
+        }
+        fields
+    }
+}
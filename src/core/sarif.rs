@@ -0,0 +1,897 @@
+//! SARIF 输出模块
+//!
+//! 将项目检查报告序列化为 [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! 日志，便于接入 GitHub Code Scanning 等工具。
+//!
+//! 每个诊断代码对应一条规则（`tool.driver.rules`），规则的名称来自 [`RULES`] 中的元数据，
+//! 描述按输出语言从消息目录中的 `rule.代码` 条目生成；未登记的代码使用诊断代码本身作为描述。
+//! [`RULES`] 同时是 `spring/listRules` 请求返回的规则目录的来源，
+//! 每条规则的文档位于 `docs/rules.md`。
+
+use std::collections::BTreeSet;
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+use serde::Serialize;
+
+use crate::core::check::CheckReport;
+use crate::core::messages::{localized, Locale};
+
+/// SARIF 规范版本
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// SARIF JSON Schema 地址
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// 相对路径的基准目录标识（检查根目录）
+pub const SRCROOT: &str = "%SRCROOT%";
+
+/// 没有诊断代码时使用的规则 ID
+const FALLBACK_RULE_ID: &str = "spring-lsp";
+
+/// 诊断规则元数据
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleMetadata {
+    /// 规则 ID（诊断代码）
+    pub id: &'static str,
+    /// 规则名称（PascalCase）
+    pub name: &'static str,
+    /// 默认诊断级别
+    pub severity: DiagnosticSeverity,
+    /// 诊断是否附带快速修复
+    pub fixable: bool,
+}
+
+impl RuleMetadata {
+    /// 指定语言的规则描述（消息目录中的 `rule.规则 ID` 条目）
+    pub fn description(&self, locale: Locale) -> String {
+        localized(locale, &format!("rule.{}", self.id), &[])
+    }
+
+    /// 规则文档地址（`docs/rules.md` 中以规则 ID 为标题的章节）
+    pub fn docs_url(&self) -> String {
+        // GitHub 标题锚点：小写，去掉除 `-`、`_` 以外的标点
+        let anchor: String = self
+            .id
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+        format!(
+            "{}/blob/main/docs/rules.md#{}",
+            env!("CARGO_PKG_REPOSITORY"),
+            anchor
+        )
+    }
+}
+
+/// 已知的诊断规则
+pub const RULES: &[RuleMetadata] = &[
+    RuleMetadata {
+        id: "E001",
+        name: "EmptyComponentName",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E002",
+        name: "ConfigInjectWithName",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: true,
+    },
+    RuleMetadata {
+        id: "E003",
+        name: "MissingAutoConfigType",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E004",
+        name: "EmptyRoutePath",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E005",
+        name: "RoutePathMissingSlash",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E006",
+        name: "MissingHttpMethod",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E007",
+        name: "EmptyHandlerName",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E008",
+        name: "NestedPathParameter",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E009",
+        name: "PathParameterMissingOpenBrace",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E010",
+        name: "EmptyPathParameter",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E011",
+        name: "InvalidPathParameterName",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E012",
+        name: "PathParameterMissingCloseBrace",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E013",
+        name: "EmptyCronExpression",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E014",
+        name: "ZeroFixRate",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E015",
+        name: "InvalidCronExpression",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E016",
+        name: "ComponentReturnsUnit",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E017",
+        name: "EmptyPluginName",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E018",
+        name: "PrimitiveQueryExtractor",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E019",
+        name: "InvalidPathParameterConstraint",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E020",
+        name: "InvalidHttpMethod",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: true,
+    },
+    RuleMetadata {
+        id: "E021",
+        name: "NonAsyncRouteHandler",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: true,
+    },
+    RuleMetadata {
+        id: "E022",
+        name: "NonAsyncJob",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: true,
+    },
+    RuleMetadata {
+        id: "E023",
+        name: "JobReturnsValue",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E024",
+        name: "DuplicateInjectAttribute",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E025",
+        name: "InjectOnTupleField",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E026",
+        name: "InjectOutsideService",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E027",
+        name: "BodyExtractorNotLast",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: true,
+    },
+    RuleMetadata {
+        id: "E028",
+        name: "DuplicatePathParameter",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E029",
+        name: "TooManyPathParameters",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E030",
+        name: "UnknownCronTimezone",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "E031",
+        name: "UnsupportedJobParameter",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "W001",
+        name: "ZeroFixDelay",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "W002",
+        name: "DuplicateDependency",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "W003",
+        name: "QueryStringInRoutePath",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "W004",
+        name: "RouteConflict",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "W005",
+        name: "TrailingSlashMismatch",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: true,
+    },
+    RuleMetadata {
+        id: "W006",
+        name: "RouteCaseMismatch",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: true,
+    },
+    RuleMetadata {
+        id: "W007",
+        name: "StubHandler",
+        severity: DiagnosticSeverity::INFORMATION,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "http-semantics/get-with-body",
+        name: "GetWithBody",
+        severity: DiagnosticSeverity::INFORMATION,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "http-semantics/delete-with-body",
+        name: "DeleteWithBody",
+        severity: DiagnosticSeverity::INFORMATION,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "handler-complexity/too-long",
+        name: "HandlerTooLong",
+        severity: DiagnosticSeverity::INFORMATION,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "handler-complexity/too-many-components",
+        name: "HandlerTooManyComponents",
+        severity: DiagnosticSeverity::INFORMATION,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "parse_error",
+        name: "TomlParseError",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "undefined-section",
+        name: "UndefinedSection",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "undefined-property",
+        name: "UndefinedProperty",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "deprecated-property",
+        name: "DeprecatedProperty",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "missing-required-property",
+        name: "MissingRequiredProperty",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "type-mismatch",
+        name: "TypeMismatch",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "invalid-enum-value",
+        name: "InvalidEnumValue",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "string-too-short",
+        name: "StringTooShort",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "string-too-long",
+        name: "StringTooLong",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "value-too-small",
+        name: "ValueTooSmall",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "value-too-large",
+        name: "ValueTooLarge",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "empty-var-name",
+        name: "EmptyEnvVarName",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "invalid-var-name",
+        name: "InvalidEnvVarName",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "invalid-log-level",
+        name: "InvalidLogLevel",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "invalid-log-filter",
+        name: "InvalidLogFilter",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "invalid-url",
+        name: "InvalidUrl",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "hardcoded-secret",
+        name: "HardcodedSecret",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: true,
+    },
+    RuleMetadata {
+        id: "unknown-job",
+        name: "UnknownJob",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "duplicate-job-name",
+        name: "DuplicateJobName",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "job-schedule-overlap",
+        name: "JobScheduleOverlap",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "component-type-mismatch",
+        name: "ComponentTypeMismatch",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "component-type-not-found",
+        name: "ComponentTypeNotFound",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "component-not-registered",
+        name: "ComponentNotRegistered",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: true,
+    },
+    RuleMetadata {
+        id: "component-requires-cfg",
+        name: "ComponentRequiresCfg",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "component-name-mismatch",
+        name: "ComponentNameMismatch",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "component-name-not-found",
+        name: "ComponentNameNotFound",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "config-not-found",
+        name: "ConfigNotFound",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "config-field-missing",
+        name: "ConfigFieldMissing",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "circular-dependency",
+        name: "CircularDependency",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "multiple-auto-config",
+        name: "MultipleAutoConfig",
+        severity: DiagnosticSeverity::ERROR,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "missing-auto-config",
+        name: "MissingAutoConfig",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "unprotected-route",
+        name: "UnprotectedRoute",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "route-prefix-convention",
+        name: "RoutePrefixConvention",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "duplicate-handler-name",
+        name: "DuplicateHandlerName",
+        severity: DiagnosticSeverity::INFORMATION,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "duplicate-route-registration",
+        name: "DuplicateRouteRegistration",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "path-segment-spelling",
+        name: "PathSegmentSpelling",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: true,
+    },
+    RuleMetadata {
+        id: "missing-middleware",
+        name: "MissingMiddleware",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "route-not-in-spec",
+        name: "RouteNotInSpec",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+    RuleMetadata {
+        id: "spec-operation-without-handler",
+        name: "SpecOperationWithoutHandler",
+        severity: DiagnosticSeverity::WARNING,
+        fixable: false,
+    },
+];
+
+/// 查找诊断代码对应的规则元数据
+pub fn find_rule(id: &str) -> Option<&'static RuleMetadata> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+/// SARIF 日志（顶层对象）
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<SarifRun>,
+}
+
+/// 一次工具运行
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+}
+
+/// 工具信息
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+/// 工具驱动（名称、版本和规则列表）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub information_uri: &'static str,
+    pub rules: Vec<SarifRule>,
+}
+
+/// 规则描述
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'static str>,
+    pub short_description: SarifMessage,
+    pub default_configuration: SarifConfiguration,
+}
+
+/// 规则的默认配置
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifConfiguration {
+    pub level: &'static str,
+}
+
+/// 文本消息
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+/// 单条结果（对应一个诊断）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: &'static str,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+}
+
+/// 结果位置
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+/// 物理位置（文件和区域）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+/// 文件位置，路径相对于 [`SRCROOT`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+    pub uri_base_id: &'static str,
+}
+
+/// 区域，行列号从 1 开始
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+/// 将检查报告转换为 SARIF 日志
+///
+/// 规则列表只包含报告中出现过的诊断代码，按代码排序，规则描述使用 `locale` 指定的语言
+pub fn to_sarif(report: &CheckReport, locale: Locale) -> SarifLog {
+    let rule_ids: Vec<String> = report
+        .files
+        .iter()
+        .flat_map(|file| file.diagnostics.iter())
+        .map(rule_id)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let rules = rule_ids
+        .iter()
+        .map(|id| {
+            let metadata = find_rule(id);
+            SarifRule {
+                id: id.clone(),
+                name: metadata.map(|rule| rule.name),
+                short_description: SarifMessage {
+                    text: metadata
+                        .map(|rule| rule.description(locale))
+                        .unwrap_or_else(|| id.clone()),
+                },
+                default_configuration: SarifConfiguration {
+                    level: default_level(id),
+                },
+            }
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for file in &report.files {
+        // SARIF 的 URI 使用 '/' 分隔
+        let uri = file.path.replace('\\', "/");
+        for diagnostic in &file.diagnostics {
+            let id = rule_id(diagnostic);
+            let rule_index = rule_ids.iter().position(|rule| *rule == id).unwrap_or(0);
+            results.push(SarifResult {
+                rule_id: id,
+                rule_index,
+                level: level(diagnostic.severity),
+                message: SarifMessage {
+                    text: diagnostic.message.clone(),
+                },
+                locations: vec![SarifLocation {
+                    physical_location: SarifPhysicalLocation {
+                        artifact_location: SarifArtifactLocation {
+                            uri: uri.clone(),
+                            uri_base_id: SRCROOT,
+                        },
+                        region: SarifRegion {
+                            start_line: diagnostic.range.start.line + 1,
+                            start_column: diagnostic.range.start.character + 1,
+                            end_line: diagnostic.range.end.line + 1,
+                            end_column: diagnostic.range.end.character + 1,
+                        },
+                    },
+                }],
+            });
+        }
+    }
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "spring-lsp",
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: env!("CARGO_PKG_REPOSITORY"),
+                    rules,
+                },
+            },
+            results,
+        }],
+    }
+}
+
+/// 诊断对应的规则 ID
+fn rule_id(diagnostic: &Diagnostic) -> String {
+    match &diagnostic.code {
+        Some(NumberOrString::String(code)) => code.clone(),
+        Some(NumberOrString::Number(code)) => code.to_string(),
+        None => FALLBACK_RULE_ID.to_string(),
+    }
+}
+
+/// 诊断级别对应的 SARIF 级别
+fn level(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        Some(DiagnosticSeverity::INFORMATION) | Some(DiagnosticSeverity::HINT) => "note",
+        _ => "error",
+    }
+}
+
+/// 规则的默认级别：已登记的规则使用元数据中的级别；
+/// 未登记的代码中 `W` 开头的为警告，`http-semantics` 和 `handler-complexity` 规则组为提示，其余为错误
+fn default_level(id: &str) -> &'static str {
+    if let Some(rule) = find_rule(id) {
+        level(Some(rule.severity))
+    } else if id.starts_with("http-semantics/") || id.starts_with("handler-complexity/") {
+        "note"
+    } else if id.starts_with('W') {
+        "warning"
+    } else {
+        "error"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::check::FileDiagnostics;
+    use lsp_types::{Position, Range, Url};
+
+    fn diagnostic(code: &str, severity: DiagnosticSeverity, line: u32) -> Diagnostic {
+        Diagnostic {
+            range: Range {
+                start: Position::new(line, 4),
+                end: Position::new(line, 10),
+            },
+            severity: Some(severity),
+            code: Some(NumberOrString::String(code.to_string())),
+            source: Some("spring-lsp".to_string()),
+            message: format!("{} message", code),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_to_sarif() {
+        let report = CheckReport {
+            files: vec![FileDiagnostics {
+                uri: Url::parse("file:///project/src/main.rs").unwrap(),
+                path: "src\\main.rs".to_string(),
+                diagnostics: vec![
+                    diagnostic("W003", DiagnosticSeverity::WARNING, 0),
+                    diagnostic("E018", DiagnosticSeverity::ERROR, 2),
+                    diagnostic("custom-code", DiagnosticSeverity::HINT, 3),
+                ],
+            }],
+            errors: 1,
+            warnings: 1,
+        };
+
+        let json = serde_json::to_value(to_sarif(&report, Locale::ZhCn)).unwrap();
+        assert_eq!(json["version"], "2.1.0");
+        assert_eq!(json["$schema"], SARIF_SCHEMA);
+
+        let run = &json["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "spring-lsp");
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        let ids: Vec<_> = rules
+            .iter()
+            .map(|rule| rule["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["E018", "W003", "custom-code"]);
+        assert_eq!(rules[0]["name"], "PrimitiveQueryExtractor");
+        assert_eq!(
+            rules[0]["shortDescription"]["text"],
+            "Query<T> 的类型参数必须是结构体"
+        );
+        assert_eq!(rules[1]["defaultConfiguration"]["level"], "warning");
+        assert_eq!(rules[2]["shortDescription"]["text"], "custom-code");
+        assert!(rules[2].get("name").is_none());
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["ruleId"], "W003");
+        assert_eq!(results[0]["ruleIndex"], 1);
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[1]["message"]["text"], "E018 message");
+        assert_eq!(results[2]["level"], "note");
+
+        let location = &results[1]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(location["artifactLocation"]["uriBaseId"], SRCROOT);
+        assert_eq!(location["region"]["startLine"], 3);
+        assert_eq!(location["region"]["startColumn"], 5);
+        assert_eq!(location["region"]["endColumn"], 11);
+
+        let json = serde_json::to_value(to_sarif(&report, Locale::En)).unwrap();
+        let rules = json["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        assert_eq!(
+            rules[0]["shortDescription"]["text"],
+            "The type parameter of Query<T> must be a struct"
+        );
+        assert_eq!(rules[2]["shortDescription"]["text"], "custom-code");
+    }
+
+    #[test]
+    fn test_rule_metadata_ids_are_unique() {
+        let ids: BTreeSet<_> = RULES.iter().map(|rule| rule.id).collect();
+        assert_eq!(ids.len(), RULES.len());
+        assert_eq!(find_rule("E005").unwrap().name, "RoutePathMissingSlash");
+        assert!(find_rule("E999").is_none());
+    }
+
+    #[test]
+    fn test_rules_have_descriptions() {
+        for rule in RULES {
+            for locale in [Locale::ZhCn, Locale::En] {
+                assert_ne!(
+                    rule.description(locale),
+                    format!("rule.{}", rule.id),
+                    "{} has no description in the message catalog",
+                    rule.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rules_are_documented() {
+        let docs = include_str!("../../docs/rules.md");
+        for rule in RULES {
+            assert!(
+                docs.contains(&format!("\n## {}\n", rule.id)),
+                "{} is missing from docs/rules.md",
+                rule.id
+            );
+        }
+        assert_eq!(
+            find_rule("http-semantics/get-with-body").unwrap().docs_url(),
+            "https://github.com/spring-rs/spring-lsp/blob/main/docs/rules.md#http-semanticsget-with-body"
+        );
+        assert_eq!(default_level("W007"), "note");
+        assert_eq!(default_level("route-prefix-convention"), "warning");
+    }
+}
@@ -1,8 +1,7 @@
 //! 配置 Schema 管理模块
 
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// 配置 Schema
 ///
@@ -64,6 +63,12 @@ pub struct PropertySchema {
     /// 示例代码（可选）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<String>,
+    /// 字符串格式（可选，如 `uri`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// 取值的说明（键为取值，如日志级别 `debug` 的含义）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub value_docs: BTreeMap<String, String>,
 }
 
 /// 类型信息
@@ -137,7 +142,7 @@ pub enum Value {
 /// 管理配置 Schema，提供配置项元数据查询
 #[derive(Clone)]
 pub struct SchemaProvider {
-    /// Schema 数据（加载后不会改变，直接拥有即可）
+    /// Schema 数据（热重载时由服务器克隆后整体替换，直接拥有即可）
     schema: ConfigSchema,
 }
 
@@ -173,6 +178,13 @@ impl SchemaProvider {
         }
     }
 
+    /// 重新获取最新的 Schema（不使用备用 Schema）
+    ///
+    /// 用于插件版本变化后的热重载，加载失败时由调用方决定是否保留当前 Schema
+    pub async fn fetch_schema() -> anyhow::Result<ConfigSchema> {
+        Self::load_from_url(Self::SCHEMA_URL).await
+    }
+
     /// 从指定 URL 加载 Schema
     async fn load_from_url(url: &str) -> anyhow::Result<ConfigSchema> {
         let response = reqwest::get(url).await?;
@@ -190,46 +202,11 @@ impl SchemaProvider {
 
     /// 创建内置备用 Schema
     ///
-    /// 包含常见的 spring-rs 插件配置
+    /// 包含官方 spring-rs 插件的配置定义，参见 [`crate::core::bundled_schema`]
     fn create_fallback_schema() -> ConfigSchema {
-        let mut plugins = HashMap::new();
-
-        // Web 插件配置
-        let web_schema = json!({
-            "type": "object",
-            "properties": {
-                "host": {
-                    "type": "string",
-                    "description": "Web server host address",
-                    "default": "0.0.0.0"
-                },
-                "port": {
-                    "type": "integer",
-                    "description": "Web server port",
-                    "default": 8080,
-                    "minimum": 1,
-                    "maximum": 65535
-                }
-            }
-        });
-        plugins.insert("web".to_string(), web_schema);
-
-        // Redis 插件配置
-        let redis_schema = json!({
-            "type": "object",
-            "properties": {
-                "url": {
-                    "type": "string",
-                    "description": "Redis connection URL",
-                    "default": "redis://localhost:6379"
-                }
-            }
-        });
-        plugins.insert("redis".to_string(), redis_schema);
-
         ConfigSchema {
             schema_type: "object".to_string(),
-            plugins,
+            plugins: crate::core::bundled_schema::plugins(),
         }
     }
 
@@ -270,6 +247,312 @@ impl SchemaProvider {
     }
 }
 
+impl SchemaProvider {
+    /// 获取配置属性的 Schema
+    ///
+    /// 从插件的 JSON Schema 中解析属性的类型、描述、默认值、枚举值和取值范围；
+    /// 属性未定义或类型无法识别（如 `$ref` 引用）时返回 `None`
+    pub fn property_schema(&self, prefix: &str, property: &str) -> Option<PropertySchema> {
+        let plugin_schema = self.schema.plugins.get(prefix)?;
+        let value = plugin_schema.get("properties")?.get(property)?;
+        let required = plugin_schema
+            .get("required")
+            .and_then(|required| required.as_array())
+            .is_some_and(|required| required.iter().any(|name| name == property));
+
+        PropertySchema::from_json(property, value, required)
+    }
+
+    /// 配置项取值的说明
+    ///
+    /// Schema 没有提供时使用内置 Schema 中的说明（远程 Schema 通常只有配置项级别的文档）
+    pub fn value_doc(&self, prefix: &str, property: &str, value: &str) -> Option<String> {
+        let documented = |schema: Option<PropertySchema>| {
+            schema.and_then(|schema| schema.value_docs.get(value).cloned())
+        };
+        documented(self.property_schema(prefix, property)).or_else(|| {
+            let plugins = crate::core::bundled_schema::plugins();
+            let value = plugins.get(prefix)?.get("properties")?.get(property)?;
+            documented(PropertySchema::from_json(property, value, false))
+        })
+    }
+}
+
+impl PropertySchema {
+    /// 从 JSON Schema 属性定义解析
+    pub fn from_json(name: &str, value: &serde_json::Value, required: bool) -> Option<Self> {
+        let deprecated = match value.get("deprecated") {
+            Some(serde_json::Value::Bool(true)) => Some(
+                value
+                    .get("deprecationMessage")
+                    .and_then(|message| message.as_str())
+                    .unwrap_or("该配置项已废弃")
+                    .to_string(),
+            ),
+            Some(serde_json::Value::String(message)) => Some(message.clone()),
+            _ => None,
+        };
+        let example = value
+            .get("examples")
+            .and_then(|examples| examples.get(0))
+            .or_else(|| value.get("example"))
+            .map(|example| format!("{} = {}", name, toml_literal(example)));
+
+        Some(Self {
+            name: name.to_string(),
+            type_info: TypeInfo::from_json(value)?,
+            description: value
+                .get("description")
+                .and_then(|description| description.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            default: value
+                .get("default")
+                .and_then(|default| serde_json::from_value(default.clone()).ok()),
+            required,
+            deprecated,
+            example,
+            format: value
+                .get("format")
+                .and_then(|format| format.as_str())
+                .map(str::to_string),
+            value_docs: value_docs(value),
+        })
+    }
+}
+
+/// 属性定义中取值的说明
+///
+/// 支持两种写法：与 `enum` 一一对应的 `enumDescriptions`，
+/// 以及 `oneOf`/`anyOf` 中带 `description` 的 `const`（或只有一个值的 `enum`）
+fn value_docs(value: &serde_json::Value) -> BTreeMap<String, String> {
+    let mut docs = BTreeMap::new();
+    if let (Some(values), Some(descriptions)) = (
+        value.get("enum").and_then(|values| values.as_array()),
+        value
+            .get("enumDescriptions")
+            .and_then(|descriptions| descriptions.as_array()),
+    ) {
+        for (value, description) in values.iter().zip(descriptions) {
+            if let (Some(value), Some(description)) = (value.as_str(), description.as_str()) {
+                docs.insert(value.to_string(), description.to_string());
+            }
+        }
+    }
+
+    let variants = ["oneOf", "anyOf"]
+        .iter()
+        .filter_map(|key| value.get(key).and_then(|variants| variants.as_array()))
+        .flatten();
+    for variant in variants {
+        let constant = variant.get("const").or_else(|| match variant.get("enum") {
+            Some(serde_json::Value::Array(values)) if values.len() == 1 => values.first(),
+            _ => None,
+        });
+        if let (Some(constant), Some(description)) = (
+            constant.and_then(|constant| constant.as_str()),
+            variant
+                .get("description")
+                .and_then(|description| description.as_str()),
+        ) {
+            docs.insert(constant.to_string(), description.to_string());
+        }
+    }
+    docs
+}
+
+impl TypeInfo {
+    /// 从 JSON Schema 的类型定义解析
+    ///
+    /// `type` 为数组时（如 `["integer", "null"]`）使用第一个非 `null` 类型
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let type_name = match value.get("type")? {
+            serde_json::Value::String(type_name) => type_name.as_str(),
+            serde_json::Value::Array(types) => types
+                .iter()
+                .filter_map(|t| t.as_str())
+                .find(|t| *t != "null")?,
+            _ => return None,
+        };
+        let usize_of = |key: &str| value.get(key).and_then(|v| v.as_u64()).map(|v| v as usize);
+
+        Some(match type_name {
+            "string" => TypeInfo::String {
+                enum_values: value.get("enum").and_then(|values| {
+                    values.as_array().map(|values| {
+                        values
+                            .iter()
+                            .filter_map(|v| v.as_str().map(str::to_string))
+                            .collect()
+                    })
+                }),
+                min_length: usize_of("minLength"),
+                max_length: usize_of("maxLength"),
+            },
+            "integer" => TypeInfo::Integer {
+                min: value
+                    .get("minimum")
+                    .and_then(|v| v.as_f64())
+                    .map(|v| v as i64),
+                max: value
+                    .get("maximum")
+                    .and_then(|v| v.as_f64())
+                    .map(|v| v as i64),
+            },
+            "number" => TypeInfo::Float {
+                min: value.get("minimum").and_then(|v| v.as_f64()),
+                max: value.get("maximum").and_then(|v| v.as_f64()),
+            },
+            "boolean" => TypeInfo::Boolean,
+            "array" => TypeInfo::Array {
+                item_type: Box::new(value.get("items").and_then(Self::from_json).unwrap_or(
+                    TypeInfo::String {
+                        enum_values: None,
+                        min_length: None,
+                        max_length: None,
+                    },
+                )),
+            },
+            "object" => TypeInfo::Object {
+                properties: value
+                    .get("properties")
+                    .and_then(|properties| properties.as_object())
+                    .map(|properties| {
+                        properties
+                            .iter()
+                            .filter_map(|(key, property)| {
+                                PropertySchema::from_json(key, property, false)
+                                    .map(|schema| (key.clone(), schema))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            },
+            _ => return None,
+        })
+    }
+}
+
+/// JSON 值对应的 TOML 字面量
+fn toml_literal(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => format!("{:?}", s),
+        other => other.to_string(),
+    }
+}
+
+impl SchemaProvider {
+    /// 使用新的 Schema 更新受影响插件的配置定义
+    ///
+    /// 只替换 `crates` 对应插件的 Schema（`spring-web` 对应 `web`），
+    /// 核心 crate `spring` 变化时更新所有插件。新 Schema 中不存在的插件保持不变。
+    ///
+    /// # 返回
+    ///
+    /// 实际发生变化的配置前缀列表（已排序）
+    pub fn reload_plugins(&mut self, fresh: &ConfigSchema, crates: &[String]) -> Vec<String> {
+        let prefixes: BTreeSet<String> = if crates.iter().any(|name| name == CORE_CRATE) {
+            fresh.plugins.keys().cloned().collect()
+        } else {
+            crates
+                .iter()
+                .filter_map(|name| plugin_prefix(name))
+                .map(str::to_string)
+                .collect()
+        };
+
+        let mut updated = Vec::new();
+        for prefix in prefixes {
+            let Some(schema) = fresh.plugins.get(&prefix) else {
+                continue;
+            };
+            if self.schema.plugins.get(&prefix) != Some(schema) {
+                self.schema.plugins.insert(prefix.clone(), schema.clone());
+                updated.push(prefix);
+            }
+        }
+
+        updated
+    }
+}
+
+/// spring-rs 核心 crate 名称
+const CORE_CRATE: &str = "spring";
+
+/// 根据插件 crate 名称获取配置前缀
+///
+/// 例如 `spring-web` -> `web`，`spring-sea-orm` -> `sea-orm`
+pub fn plugin_prefix(crate_name: &str) -> Option<&str> {
+    crate_name
+        .strip_prefix("spring-")
+        .filter(|prefix| !prefix.is_empty())
+}
+
+/// Cargo.lock 中记录的 spring 相关 crate 版本
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginVersions {
+    /// crate 名称 -> 版本（同名多版本时以逗号连接）
+    versions: BTreeMap<String, String>,
+}
+
+impl PluginVersions {
+    /// 从 Cargo.lock 内容中解析 spring 相关 crate 的版本
+    ///
+    /// 解析失败时返回空集合
+    pub fn from_lock_file(content: &str) -> Self {
+        let mut versions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        let Ok(lock) = content.parse::<toml::Table>() else {
+            return Self::default();
+        };
+        let packages = lock
+            .get("package")
+            .and_then(|packages| packages.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        for package in &packages {
+            let name = package.get("name").and_then(|name| name.as_str());
+            let version = package.get("version").and_then(|version| version.as_str());
+            if let (Some(name), Some(version)) = (name, version) {
+                if name == CORE_CRATE || name.starts_with("spring-") {
+                    versions
+                        .entry(name.to_string())
+                        .or_default()
+                        .push(version.to_string());
+                }
+            }
+        }
+
+        Self {
+            versions: versions
+                .into_iter()
+                .map(|(name, mut list)| {
+                    list.sort();
+                    (name, list.join(","))
+                })
+                .collect(),
+        }
+    }
+
+    /// 获取 crate 的版本
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.versions.get(name).map(String::as_str)
+    }
+
+    /// 对比两个版本快照，返回新增、删除或版本变化的 crate 名称（已排序）
+    pub fn changed(&self, other: &Self) -> Vec<String> {
+        self.versions
+            .keys()
+            .chain(other.versions.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|name| self.versions.get(*name) != other.versions.get(*name))
+            .cloned()
+            .collect()
+    }
+}
+
 impl Default for SchemaProvider {
     fn default() -> Self {
         Self::with_fallback_schema()
@@ -284,3 +567,148 @@ impl SchemaProvider {
         Self { schema }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const LOCK: &str = r#"
+version = 3
+
+[[package]]
+name = "spring"
+version = "0.4.0"
+
+[[package]]
+name = "spring-web"
+version = "0.4.1"
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+"#;
+
+    #[test]
+    fn test_plugin_versions_from_lock_file() {
+        let versions = PluginVersions::from_lock_file(LOCK);
+        assert_eq!(versions.get("spring"), Some("0.4.0"));
+        assert_eq!(versions.get("spring-web"), Some("0.4.1"));
+        assert_eq!(versions.get("serde"), None);
+
+        let updated = PluginVersions::from_lock_file(&LOCK.replace("0.4.1", "0.4.2"));
+        assert_eq!(versions.changed(&updated), vec!["spring-web".to_string()]);
+        assert!(versions.changed(&versions).is_empty());
+
+        // 无效的 Cargo.lock 视为没有依赖
+        assert_eq!(
+            PluginVersions::from_lock_file("not toml ["),
+            PluginVersions::default()
+        );
+    }
+
+    #[test]
+    fn test_reload_plugins() {
+        let mut provider = SchemaProvider::default();
+        let mut fresh = SchemaProvider::create_fallback_schema();
+        fresh.plugins.insert(
+            "web".to_string(),
+            json!({ "type": "object", "properties": { "graceful": { "type": "boolean" } } }),
+        );
+        fresh.plugins.insert(
+            "redis".to_string(),
+            json!({ "type": "object", "properties": {} }),
+        );
+
+        // 只更新受影响的插件
+        let updated = provider.reload_plugins(&fresh, &["spring-web".to_string()]);
+        assert_eq!(updated, vec!["web".to_string()]);
+        assert!(provider.has_property("web", "graceful"));
+        assert!(provider.has_property("redis", "uri"));
+
+        // 核心 crate 变化时更新所有插件
+        let updated = provider.reload_plugins(&fresh, &["spring".to_string()]);
+        assert_eq!(updated, vec!["redis".to_string()]);
+        assert!(!provider.has_property("redis", "url"));
+    }
+
+    #[test]
+    fn test_property_schema_from_json() {
+        let provider = SchemaProvider::default();
+
+        let port = provider.property_schema("mail", "port").unwrap();
+        assert!(matches!(
+            port.type_info,
+            TypeInfo::Integer {
+                min: Some(1),
+                max: Some(65535)
+            }
+        ));
+        assert_eq!(port.default, Some(Value::Integer(465)));
+
+        let uri = provider.property_schema("sqlx", "uri").unwrap();
+        assert!(uri.required);
+        assert!(uri.example.unwrap().starts_with("uri = \"postgres://"));
+
+        let auth = provider.property_schema("mail", "auth").unwrap();
+        let TypeInfo::Object { properties } = auth.type_info else {
+            panic!("expected object");
+        };
+        assert!(properties.contains_key("password"));
+
+        // 远程 Schema 中的可空类型
+        let nullable = PropertySchema::from_json(
+            "timeout",
+            &json!({ "type": ["integer", "null"], "deprecated": true }),
+            false,
+        )
+        .unwrap();
+        assert!(matches!(nullable.type_info, TypeInfo::Integer { .. }));
+        assert!(nullable.deprecated.is_some());
+        assert!(PropertySchema::from_json("x", &json!({ "$ref": "#/x" }), false).is_none());
+    }
+
+    #[test]
+    fn test_value_docs() {
+        let parallel = PropertySchema::from_json(
+            "mode",
+            &json!({
+                "type": "string",
+                "enum": ["fast", "safe"],
+                "enumDescriptions": ["跳过校验", "完整校验"]
+            }),
+            false,
+        )
+        .unwrap();
+        assert_eq!(parallel.value_docs["safe"], "完整校验");
+
+        // schemars 为带文档注释的枚举变体生成的写法
+        let variants = PropertySchema::from_json(
+            "mode",
+            &json!({
+                "type": "string",
+                "oneOf": [
+                    { "const": "fast", "description": "跳过校验" },
+                    { "enum": ["safe"], "description": "完整校验" },
+                    { "enum": ["a", "b"], "description": "多个值" }
+                ]
+            }),
+            false,
+        )
+        .unwrap();
+        assert_eq!(variants.value_docs.len(), 2);
+        assert_eq!(variants.value_docs["fast"], "跳过校验");
+
+        // 内置 Schema 补充远程 Schema 缺少的取值说明
+        let mut provider = SchemaProvider::new();
+        provider.schema.plugins.insert(
+            "logger".to_string(),
+            json!({ "type": "object", "properties": { "level": { "type": "string" } } }),
+        );
+        assert!(provider
+            .value_doc("logger", "level", "debug")
+            .unwrap()
+            .contains("调试"));
+        assert!(provider.value_doc("logger", "level", "verbose").is_none());
+    }
+}
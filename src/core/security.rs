@@ -0,0 +1,176 @@
+//! 路由安全规则模块
+//!
+//! 在 `.spring-lsp/rules.toml` 中声明哪些路径需要认证或授权保护：
+//!
+//! ```toml
+//! [[security]]
+//! pattern = "/admin/**"
+//! middleware = ["AuthLayer"]
+//! guards = ["AdminUser"]
+//! ```
+//!
+//! 路径匹配 `pattern` 的路由，只要生效的中间件链中包含 `middleware` 中的任意一个，
+//! 或者处理器参数中有 `guards` 中的任意一个提取器（如 `AdminUser`、`Claims`），
+//! 即视为已受保护。未受保护的路由由 [`crate::analysis::validation::RouteSecurityValidator`] 报告。
+//!
+//! 与 `.spring-lsp.toml` 中的 `[middleware] rules` 不同，安全规则可以用 guard 提取器满足，
+//! 并且和基线一样随项目提交，供团队共享。
+//!
+//! 同一文件还可以声明路由前缀约定：目录或模块文件中的路由必须以指定前缀开头，
+//! 违反约定的路由由 [`crate::analysis::validation::RoutePrefixValidator`] 报告：
+//!
+//! ```toml
+//! [[prefix]]
+//! path = "src/handlers/admin"
+//! prefix = "/admin"
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+/// 安全规则文件相对于项目根目录的路径
+pub const SECURITY_RULES_FILE: &str = ".spring-lsp/rules.toml";
+
+/// 路由安全规则
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityRule {
+    /// 路由路径模式（`*` 匹配一段路径，`**` 匹配任意多段）
+    pub pattern: String,
+    /// 满足规则的中间件名称（中间件表达式中包含该名称即视为已应用）
+    #[serde(default)]
+    pub middleware: Vec<String>,
+    /// 满足规则的 guard 提取器类型名（处理器参数类型中包含该名称即视为已应用）
+    #[serde(default)]
+    pub guards: Vec<String>,
+}
+
+/// 路由前缀约定
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrefixRule {
+    /// 目录或模块文件（相对于项目根目录，不含扩展名，如 `src/handlers/admin`）
+    pub path: String,
+    /// 其中的路由必须使用的路径前缀
+    pub prefix: String,
+}
+
+/// 安全规则文件
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityRules {
+    /// 路由安全规则
+    #[serde(default)]
+    pub security: Vec<SecurityRule>,
+    /// 路由前缀约定
+    #[serde(default, rename = "prefix")]
+    pub prefixes: Vec<PrefixRule>,
+}
+
+impl SecurityRules {
+    /// 安全规则文件的完整路径
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(SECURITY_RULES_FILE)
+    }
+
+    /// 从项目根目录加载安全规则
+    ///
+    /// 规则文件不存在或没有规则时返回 `None`
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let path = Self::path(root);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let rules: SecurityRules = toml::from_str(&content)
+            .map_err(|e| Error::Config(format!("Invalid rules file {}: {}", path.display(), e)))?;
+        rules
+            .validate()
+            .map_err(|e| Error::Config(format!("Invalid rules file {}: {}", path.display(), e)))?;
+
+        Ok((!rules.security.is_empty() || !rules.prefixes.is_empty()).then_some(rules))
+    }
+
+    /// 验证规则
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        for rule in &self.security {
+            if !rule.pattern.starts_with('/') {
+                return Err(format!(
+                    "Security rule pattern must start with '/': '{}'",
+                    rule.pattern
+                ));
+            }
+            if rule.middleware.is_empty() && rule.guards.is_empty() {
+                return Err(format!(
+                    "Security rule '{}' must list middleware or guards",
+                    rule.pattern
+                ));
+            }
+        }
+        for rule in &self.prefixes {
+            if rule.path.trim().is_empty() {
+                return Err(format!(
+                    "Prefix rule for '{}' must have a path",
+                    rule.prefix
+                ));
+            }
+            if !rule.prefix.starts_with('/') {
+                return Err(format!(
+                    "Prefix rule for '{}' must start with '/': '{}'",
+                    rule.path, rule.prefix
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_security_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(SecurityRules::load(dir.path()).unwrap().is_none());
+
+        let path = SecurityRules::path(dir.path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            "[[security]]\npattern = \"/admin/**\"\nmiddleware = [\"AuthLayer\"]\nguards = [\"AdminUser\"]\n",
+        )
+        .unwrap();
+        let rules = SecurityRules::load(dir.path()).unwrap().unwrap();
+        assert_eq!(rules.security.len(), 1);
+        assert_eq!(rules.security[0].guards, vec!["AdminUser"]);
+
+        std::fs::write(&path, "[[security]]\npattern = \"/admin/**\"\n").unwrap();
+        assert!(SecurityRules::load(dir.path()).is_err());
+
+        std::fs::write(
+            &path,
+            "[[security]]\npattern = \"admin\"\nguards = [\"User\"]\n",
+        )
+        .unwrap();
+        assert!(SecurityRules::load(dir.path()).is_err());
+
+        // 只有路由前缀约定
+        std::fs::write(
+            &path,
+            "[[prefix]]\npath = \"src/handlers/admin\"\nprefix = \"/admin\"\n",
+        )
+        .unwrap();
+        let rules = SecurityRules::load(dir.path()).unwrap().unwrap();
+        assert!(rules.security.is_empty());
+        assert_eq!(rules.prefixes[0].prefix, "/admin");
+
+        std::fs::write(
+            &path,
+            "[[prefix]]\npath = \"src/admin\"\nprefix = \"admin\"\n",
+        )
+        .unwrap();
+        assert!(SecurityRules::load(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,398 @@
+//! 工作空间编辑构建模块
+//!
+//! 所有跨文件的编辑（重命名、整理路由、生成配置等）都通过 [`WorkspaceEditBuilder`]
+//! 构建，而不是直接拼装 `WorkspaceEdit`。构建器负责：
+//! - 校验编辑基于的文档版本，避免把过期的编辑应用到已修改的文档上
+//! - 处理编辑器中未打开的文件（检查文件存在并校验编辑范围）
+//! - 需要新建文件时使用 `CreateFile` 资源操作；客户端不支持时返回错误，
+//!   而不是让编辑被客户端静默丢弃
+
+use crate::core::document::DocumentManager;
+use lsp_types::{
+    ClientCapabilities, CreateFile, CreateFileOptions, DocumentChangeOperation, DocumentChanges,
+    OneOf, OptionalVersionedTextDocumentIdentifier, Position, Range, ResourceOp,
+    ResourceOperationKind, TextDocumentEdit, TextEdit, Url, WorkspaceEdit,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// 构建工作空间编辑时的错误
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WorkspaceEditError {
+    #[error("Document {uri} has changed (expected version {expected}, current version {actual})")]
+    VersionMismatch {
+        uri: Url,
+        expected: i32,
+        actual: i32,
+    },
+
+    #[error("File not found: {0}")]
+    FileNotFound(Url),
+
+    #[error("File already exists: {0}")]
+    FileExists(Url),
+
+    #[error("Edit range {range:?} is outside of document {uri}")]
+    OutOfBounds { uri: Url, range: Range },
+
+    #[error("Overlapping edits in document {0}")]
+    OverlappingEdits(Url),
+
+    #[error("Client does not support creating files, cannot create {0}")]
+    CreateFileUnsupported(Url),
+}
+
+/// 客户端对工作空间编辑的支持情况
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkspaceEditSupport {
+    /// 是否支持 `documentChanges`（带版本的文档编辑）
+    pub document_changes: bool,
+    /// 是否支持 `CreateFile` 资源操作
+    pub create_files: bool,
+}
+
+impl WorkspaceEditSupport {
+    /// 从客户端能力中读取工作空间编辑支持情况
+    pub fn from_capabilities(capabilities: &ClientCapabilities) -> Self {
+        let workspace_edit = capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.workspace_edit.as_ref());
+
+        let document_changes = workspace_edit
+            .and_then(|edit| edit.document_changes)
+            .unwrap_or(false);
+        let create_files = document_changes
+            && workspace_edit
+                .and_then(|edit| edit.resource_operations.as_ref())
+                .is_some_and(|ops| ops.contains(&ResourceOperationKind::Create));
+
+        Self {
+            document_changes,
+            create_files,
+        }
+    }
+}
+
+/// 单个文件的编辑
+#[derive(Debug, Default)]
+struct FileEdits {
+    /// 编辑基于的文档版本（文档未在编辑器中打开时为 `None`）
+    version: Option<i32>,
+    /// 文本编辑
+    edits: Vec<TextEdit>,
+}
+
+/// 工作空间编辑构建器
+pub struct WorkspaceEditBuilder<'a> {
+    /// 文档管理器，用于读取已打开文档的版本和内容
+    documents: &'a DocumentManager,
+    /// 需要新建的文件（按添加顺序）
+    creates: Vec<Url>,
+    /// 按文件分组的文本编辑
+    files: BTreeMap<Url, FileEdits>,
+}
+
+impl<'a> WorkspaceEditBuilder<'a> {
+    /// 创建新的构建器
+    pub fn new(documents: &'a DocumentManager) -> Self {
+        Self {
+            documents,
+            creates: Vec::new(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// 添加文本编辑
+    ///
+    /// # 参数
+    ///
+    /// * `uri` - 文档 URI
+    /// * `expected_version` - 编辑基于的文档版本；文档已打开且版本不同时返回错误
+    /// * `edit` - 文本编辑
+    pub fn edit(
+        &mut self,
+        uri: &Url,
+        expected_version: Option<i32>,
+        edit: TextEdit,
+    ) -> Result<(), WorkspaceEditError> {
+        let version = match self.documents.get(uri) {
+            Some(doc) => {
+                if let Some(expected) = expected_version {
+                    if expected != doc.version {
+                        return Err(WorkspaceEditError::VersionMismatch {
+                            uri: uri.clone(),
+                            expected,
+                            actual: doc.version,
+                        });
+                    }
+                }
+                check_bounds(uri, &doc.content, edit.range)?;
+                Some(doc.version)
+            }
+            // 本次编辑中新建的文件，内容为空
+            None if self.creates.contains(uri) => {
+                check_bounds(uri, "", edit.range)?;
+                None
+            }
+            None => {
+                let content = uri
+                    .to_file_path()
+                    .ok()
+                    .and_then(|path| std::fs::read_to_string(path).ok())
+                    .ok_or_else(|| WorkspaceEditError::FileNotFound(uri.clone()))?;
+                check_bounds(uri, &content, edit.range)?;
+                None
+            }
+        };
+
+        let file = self.files.entry(uri.clone()).or_default();
+        file.version = version;
+        file.edits.push(edit);
+        Ok(())
+    }
+
+    /// 新建文件并写入内容
+    ///
+    /// 文件已存在（在磁盘上或已在编辑器中打开）时返回错误
+    pub fn create_file(&mut self, uri: &Url, content: String) -> Result<(), WorkspaceEditError> {
+        let exists_on_disk = uri.to_file_path().is_ok_and(|path| path.exists());
+        if exists_on_disk || self.documents.get(uri).is_some() || self.creates.contains(uri) {
+            return Err(WorkspaceEditError::FileExists(uri.clone()));
+        }
+
+        self.creates.push(uri.clone());
+        if !content.is_empty() {
+            let start = Position::new(0, 0);
+            self.edit(
+                uri,
+                None,
+                TextEdit {
+                    range: Range::new(start, start),
+                    new_text: content,
+                },
+            )?;
+        }
+        Ok(())
+    }
+
+    /// 构建 `WorkspaceEdit`
+    ///
+    /// 客户端支持 `documentChanges` 时生成带版本的文档编辑和资源操作，
+    /// 否则生成 `changes` 映射。需要新建文件但客户端不支持时返回错误。
+    pub fn build(self, support: WorkspaceEditSupport) -> Result<WorkspaceEdit, WorkspaceEditError> {
+        if let Some(uri) = self.creates.first() {
+            if !support.create_files {
+                return Err(WorkspaceEditError::CreateFileUnsupported(uri.clone()));
+            }
+        }
+
+        for (uri, file) in &self.files {
+            check_overlaps(uri, &file.edits)?;
+        }
+
+        if !support.document_changes {
+            let changes: HashMap<Url, Vec<TextEdit>> = self
+                .files
+                .into_iter()
+                .map(|(uri, file)| (uri, file.edits))
+                .collect();
+            return Ok(WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            });
+        }
+
+        // 先新建文件，再应用文本编辑
+        let mut operations: Vec<DocumentChangeOperation> = self
+            .creates
+            .into_iter()
+            .map(|uri| {
+                DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                    uri,
+                    options: Some(CreateFileOptions {
+                        overwrite: Some(false),
+                        ignore_if_exists: Some(false),
+                    }),
+                    annotation_id: None,
+                }))
+            })
+            .collect();
+
+        operations.extend(self.files.into_iter().map(|(uri, file)| {
+            DocumentChangeOperation::Edit(TextDocumentEdit {
+                text_document: OptionalVersionedTextDocumentIdentifier {
+                    uri,
+                    version: file.version,
+                },
+                edits: file.edits.into_iter().map(OneOf::Left).collect(),
+            })
+        }));
+
+        Ok(WorkspaceEdit {
+            document_changes: Some(DocumentChanges::Operations(operations)),
+            ..Default::default()
+        })
+    }
+}
+
+/// 检查编辑范围是否在文档内
+fn check_bounds(uri: &Url, content: &str, range: Range) -> Result<(), WorkspaceEditError> {
+    let line_count = content.split('\n').count() as u32;
+    if range.start > range.end || range.end.line >= line_count {
+        return Err(WorkspaceEditError::OutOfBounds {
+            uri: uri.clone(),
+            range,
+        });
+    }
+    Ok(())
+}
+
+/// 检查同一文档中的编辑是否重叠
+fn check_overlaps(uri: &Url, edits: &[TextEdit]) -> Result<(), WorkspaceEditError> {
+    let mut ranges: Vec<Range> = edits.iter().map(|edit| edit.range).collect();
+    ranges.sort_by_key(|range| (range.start, range.end));
+
+    for pair in ranges.windows(2) {
+        if pair[0].end > pair[1].start {
+            return Err(WorkspaceEditError::OverlappingEdits(uri.clone()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_edit(line: u32, text: &str) -> TextEdit {
+        TextEdit {
+            range: Range::new(Position::new(line, 0), Position::new(line, 0)),
+            new_text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_open_document_versions() {
+        let documents = DocumentManager::new();
+        let uri = Url::parse("file:///project/src/main.rs").unwrap();
+        documents.open(uri.clone(), 3, "fn main() {}\n".into(), "rust".into());
+
+        let mut builder = WorkspaceEditBuilder::new(&documents);
+        let error = builder
+            .edit(&uri, Some(2), text_edit(0, "// x\n"))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            WorkspaceEditError::VersionMismatch {
+                expected: 2,
+                actual: 3,
+                ..
+            }
+        ));
+
+        builder.edit(&uri, Some(3), text_edit(0, "// x\n")).unwrap();
+        let edit = builder
+            .build(WorkspaceEditSupport {
+                document_changes: true,
+                create_files: false,
+            })
+            .unwrap();
+        let Some(DocumentChanges::Operations(operations)) = edit.document_changes else {
+            panic!("Expected document change operations");
+        };
+        assert!(matches!(
+            &operations[0],
+            DocumentChangeOperation::Edit(edit) if edit.text_document.version == Some(3)
+        ));
+    }
+
+    #[test]
+    fn test_unopened_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("app.toml");
+        std::fs::write(&existing, "[web]\nport = 8080\n").unwrap();
+        let existing = Url::from_file_path(existing).unwrap();
+        let missing = Url::from_file_path(dir.path().join("missing.toml")).unwrap();
+
+        let documents = DocumentManager::new();
+        let mut builder = WorkspaceEditBuilder::new(&documents);
+        builder
+            .edit(&existing, None, text_edit(1, "host = \"0.0.0.0\"\n"))
+            .unwrap();
+        assert_eq!(
+            builder.edit(&missing, None, text_edit(0, "")).unwrap_err(),
+            WorkspaceEditError::FileNotFound(missing.clone())
+        );
+        assert!(matches!(
+            builder
+                .edit(&existing, None, text_edit(10, ""))
+                .unwrap_err(),
+            WorkspaceEditError::OutOfBounds { .. }
+        ));
+
+        let edit = builder.build(WorkspaceEditSupport::default()).unwrap();
+        assert_eq!(edit.changes.unwrap()[&existing].len(), 1);
+    }
+
+    #[test]
+    fn test_create_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let uri = Url::from_file_path(dir.path().join("app-dev.toml")).unwrap();
+        let documents = DocumentManager::new();
+
+        // 客户端不支持新建文件时报错，而不是静默丢弃编辑
+        let mut builder = WorkspaceEditBuilder::new(&documents);
+        builder.create_file(&uri, "[web]\n".to_string()).unwrap();
+        assert_eq!(
+            builder.build(WorkspaceEditSupport::default()).unwrap_err(),
+            WorkspaceEditError::CreateFileUnsupported(uri.clone())
+        );
+
+        let mut builder = WorkspaceEditBuilder::new(&documents);
+        builder.create_file(&uri, "[web]\n".to_string()).unwrap();
+        assert_eq!(
+            builder.create_file(&uri, String::new()).unwrap_err(),
+            WorkspaceEditError::FileExists(uri.clone())
+        );
+        let edit = builder
+            .build(WorkspaceEditSupport {
+                document_changes: true,
+                create_files: true,
+            })
+            .unwrap();
+        let Some(DocumentChanges::Operations(operations)) = edit.document_changes else {
+            panic!("Expected document change operations");
+        };
+        assert_eq!(operations.len(), 2);
+        assert!(matches!(
+            operations[0],
+            DocumentChangeOperation::Op(ResourceOp::Create(_))
+        ));
+    }
+
+    #[test]
+    fn test_overlapping_edits() {
+        let documents = DocumentManager::new();
+        let uri = Url::parse("file:///project/src/main.rs").unwrap();
+        documents.open(uri.clone(), 1, "fn main() {}\n".into(), "rust".into());
+
+        let range = Range::new(Position::new(0, 0), Position::new(0, 5));
+        let mut builder = WorkspaceEditBuilder::new(&documents);
+        for _ in 0..2 {
+            builder
+                .edit(
+                    &uri,
+                    None,
+                    TextEdit {
+                        range,
+                        new_text: String::new(),
+                    },
+                )
+                .unwrap();
+        }
+        assert_eq!(
+            builder.build(WorkspaceEditSupport::default()).unwrap_err(),
+            WorkspaceEditError::OverlappingEdits(uri)
+        );
+    }
+}
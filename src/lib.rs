@@ -20,26 +20,49 @@
 //!
 //! ```text
 //! spring-lsp/
+//! ├── api.rs             # 嵌入式分析 API（不通过 LSP 复用分析引擎）
 //! ├── protocol/          # LSP 协议层
 //! │   ├── server.rs      # LSP 服务器核心
 //! │   ├── handlers/      # 请求处理器
+//! │   ├── hover.rs       # 悬停提示构建（带时间预算）
+//! │   ├── partial.rs     # 部分结果流式传输
+//! │   ├── publisher.rs   # 诊断分批限速发布
+//! │   ├── transport.rs   # 消息帧传输层
 //! │   └── types.rs       # 协议类型定义
 //! ├── analysis/          # 分析引擎层
 //! │   ├── toml/          # TOML 分析
 //! │   ├── rust/          # Rust 代码分析
 //! │   ├── completion/    # 补全引擎
 //! │   ├── diagnostic/    # 诊断引擎
+//! │   ├── item_cache.rs  # 按顶层函数缓存的诊断（增量重新验证）
+//! │   ├── openapi.rs     # OpenAPI 规范读取
+//! │   ├── pipeline.rs    # 单文档分析流水线
 //! │   └── validation/    # 验证引擎
 //! ├── scanner/           # 扫描器层
 //! │   ├── component.rs   # 组件扫描
 //! │   ├── route.rs       # 路由扫描
+//! │   ├── generated.rs   # 生成代码目录扫描（可选）
+//! │   ├── middleware.rs  # 中间件链扫描
 //! │   ├── job.rs         # 任务扫描
 //! │   ├── plugin.rs      # 插件扫描
+//! │   ├── project_cache.rs # 按文件缓存的项目扫描结果（跨文件诊断）
+//! │   ├── request_sample.rs # 路由请求示例生成
+//! │   ├── stats.rs       # 工作空间统计
+//! │   ├── stream.rs      # 消息流监听器扫描
 //! │   └── config.rs      # 配置扫描
 //! ├── core/              # 核心层
+//! │   ├── baseline.rs    # 诊断基线（只报告新问题）
+//! │   ├── check.rs       # 无客户端的项目检查（CI）
+//! │   ├── sarif.rs       # 检查报告的 SARIF 输出
+//! │   ├── security.rs    # 路由安全规则（.spring-lsp/rules.toml）
 //! │   ├── document.rs    # 文档管理
+//! │   ├── file_watcher.rs # 服务器端文件监听（file-watcher feature）
+//! │   ├── incremental.rs # 增量计算（查询记忆化和依赖跟踪）
 //! │   ├── index.rs       # 符号索引
+//! │   ├── messages.rs    # 诊断消息目录（简体中文 / 英文）
 //! │   ├── schema.rs      # Schema 管理
+//! │   ├── bundled_schema.rs # 内置插件 Schema（离线备用）
+//! │   ├── workspace_edit.rs # 工作空间编辑构建
 //! │   └── config.rs      # 配置管理
 //! └── utils/             # 工具层
 //!     ├── error.rs       # 错误定义
@@ -47,14 +70,24 @@
 //!     └── status.rs      # 状态管理
 //! ```
 
+// ============================================================================
+// 嵌入式分析 API (Public API)
+// ============================================================================
+pub mod api;
+
 // ============================================================================
 // 协议层 (Protocol Layer)
 // ============================================================================
 pub mod protocol {
     //! LSP 协议处理模块
 
+    pub mod extensions;
     pub mod handlers;
+    pub mod hover;
+    pub mod partial;
+    pub mod publisher;
     pub mod server;
+    pub mod transport;
     pub mod types;
 
     pub use server::LspServer;
@@ -68,6 +101,9 @@ pub mod analysis {
 
     pub mod completion;
     pub mod diagnostic;
+    pub mod item_cache;
+    pub mod openapi;
+    pub mod pipeline;
     pub mod rust;
     pub mod toml;
     pub mod validation;
@@ -84,15 +120,23 @@ pub mod scanner {
 
     pub mod component;
     pub mod config;
+    pub mod generated;
     pub mod job;
+    pub mod middleware;
     pub mod plugin;
+    pub mod project_cache;
+    pub mod request_sample;
     pub mod route;
+    pub mod stats;
+    pub mod stream;
 
     pub use component::ComponentScanner;
     pub use config::ConfigScanner;
+    pub use generated::GeneratedCodeCache;
     pub use job::JobScanner;
     pub use plugin::PluginScanner;
     pub use route::RouteScanner;
+    pub use stream::StreamScanner;
 }
 
 // ============================================================================
@@ -101,10 +145,20 @@ pub mod scanner {
 pub mod core {
     //! 核心功能模块
 
+    pub mod baseline;
+    pub mod bundled_schema;
+    pub mod check;
     pub mod config;
     pub mod document;
+    pub mod file_watcher;
+    pub mod incremental;
     pub mod index;
+    pub mod messages;
+    pub mod profiling;
+    pub mod sarif;
     pub mod schema;
+    pub mod security;
+    pub mod workspace_edit;
 
     pub use document::DocumentManager;
     pub use index::SymbolIndex;
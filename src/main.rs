@@ -1,6 +1,22 @@
 use anyhow::Result;
+use spring_lsp::core::baseline::Baseline;
+use spring_lsp::core::check::{OutputFormat, ProjectChecker};
+use spring_lsp::core::config::ServerConfig;
+use spring_lsp::core::messages::Locale;
+use spring_lsp::core::schema::SchemaProvider;
 use spring_lsp::protocol::LspServer;
 use spring_lsp::utils::init_logging;
+use std::path::PathBuf;
+
+/// 命令行用法
+const USAGE: &str = "Usage:
+    spring-lsp                                          Start the language server (stdio)
+    spring-lsp check <path> [--format text|json|sarif]  Check a project without an LSP client
+
+Check options:
+    --locale zh-CN|en  Language of diagnostic messages and rule descriptions (default: zh-CN)
+    --no-baseline      Report diagnostics recorded in .spring-lsp/baseline.json
+    --update-baseline  Record all current diagnostics in .spring-lsp/baseline.json";
 
 fn main() -> Result<()> {
     // 初始化日志系统
@@ -10,6 +26,20 @@ fn main() -> Result<()> {
     // - SPRING_LSP_LOG_FILE: 日志文件路径（可选）
     init_logging().expect("Failed to initialize logging system");
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => {}
+        Some("check") => std::process::exit(check(&args[1..])),
+        Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        Some(other) => {
+            eprintln!("Unknown argument: {}\n\n{}", other, USAGE);
+            std::process::exit(2);
+        }
+    }
+
     tracing::info!("Starting spring-lsp language server");
 
     // 启动 LSP 服务器
@@ -19,3 +49,100 @@ fn main() -> Result<()> {
     tracing::info!("spring-lsp language server stopped");
     Ok(())
 }
+
+/// 无客户端模式：检查项目并输出诊断，返回退出码
+///
+/// 退出码：0 表示没有错误，1 表示存在错误级诊断，2 表示参数无效。
+/// 使用 `--update-baseline` 时只重新生成基线，成功时退出码为 0
+fn check(args: &[String]) -> i32 {
+    let mut path = None;
+    let mut format = OutputFormat::default();
+    let mut locale = Locale::default();
+    let mut use_baseline = true;
+    let mut update_baseline = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => match args.next().map(|value| value.parse()) {
+                Some(Ok(value)) => format = value,
+                Some(Err(e)) => {
+                    eprintln!("{}\n\n{}", e, USAGE);
+                    return 2;
+                }
+                None => {
+                    eprintln!("--format requires a value\n\n{}", USAGE);
+                    return 2;
+                }
+            },
+            "--locale" => match args.next() {
+                Some(tag) => match Locale::from_tag(tag) {
+                    Some(value) => locale = value,
+                    None => {
+                        eprintln!("Unknown locale: {}\n\n{}", tag, USAGE);
+                        return 2;
+                    }
+                },
+                None => {
+                    eprintln!("--locale requires a value\n\n{}", USAGE);
+                    return 2;
+                }
+            },
+            "--no-baseline" => use_baseline = false,
+            "--update-baseline" => update_baseline = true,
+            _ if path.is_none() => path = Some(PathBuf::from(arg)),
+            other => {
+                eprintln!("Unexpected argument: {}\n\n{}", other, USAGE);
+                return 2;
+            }
+        }
+    }
+
+    let Some(path) = path else {
+        eprintln!("Missing project path\n\n{}", USAGE);
+        return 2;
+    };
+    if !path.is_dir() {
+        eprintln!("Not a directory: {}", path.display());
+        return 2;
+    }
+
+    // 与服务器一样优先加载远程 Schema，失败时使用内置 Schema
+    let schema_provider = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime
+            .block_on(SchemaProvider::load())
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load schema from URL: {}, using fallback", e);
+                SchemaProvider::default()
+            }),
+        Err(_) => SchemaProvider::default(),
+    };
+
+    let config = ServerConfig::load(Some(&path));
+    let mut checker = ProjectChecker::new(schema_provider, config).with_locale(locale);
+
+    if update_baseline {
+        return match checker.update_baseline(&path) {
+            Ok(baseline) => {
+                println!(
+                    "Recorded {} diagnostic(s) in {}",
+                    baseline.len(),
+                    Baseline::path(&path).display()
+                );
+                0
+            }
+            Err(e) => {
+                eprintln!("Failed to update baseline: {}", e);
+                1
+            }
+        };
+    }
+
+    if !use_baseline {
+        checker = checker.without_baseline();
+    }
+    let report = checker.check(&path);
+
+    print!("{}", report.render(format, locale));
+    report.exit_code()
+}
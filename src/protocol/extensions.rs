@@ -0,0 +1,94 @@
+//! 自定义协议扩展模块
+//!
+//! 服务器在标准 LSP 之外提供 `spring/*` 自定义请求。`initialize` 响应的
+//! `capabilities.experimental.springLsp` 列出这些请求及其版本，编辑器扩展据此判断功能是否可用，
+//! 不需要先发送请求再捕获 `MethodNotFound` 错误：
+//!
+//! ```json
+//! { "springLsp": { "protocolVersion": 1, "requests": { "spring/routes": 1, "spring/jobs": 1 } } }
+//! ```
+//!
+//! 请求的参数或响应发生不兼容的变化时增加该请求的版本；
+//! 能力块本身的结构发生变化时增加 [`PROTOCOL_VERSION`]。
+
+use serde_json::{json, Map, Value};
+
+/// 自定义协议（能力块结构）的版本
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// `experimental` 能力中自定义协议的键
+pub const EXPERIMENTAL_KEY: &str = "springLsp";
+
+/// 服务器状态查询
+pub const STATUS_REQUEST: &str = "spring-lsp/status";
+/// 按方法统计的请求延迟和缓存命中率
+pub const PERF_REPORT_REQUEST: &str = "spring/perfReport";
+/// 组件列表
+pub const COMPONENTS_REQUEST: &str = "spring/components";
+/// 路由列表
+pub const ROUTES_REQUEST: &str = "spring/routes";
+/// 路由的中间件链
+pub const MIDDLEWARES_REQUEST: &str = "spring/middlewares";
+/// 路由的请求示例
+pub const REQUEST_SAMPLE_REQUEST: &str = "spring/requestSample";
+/// 展开整个文件中的宏
+pub const EXPAND_ALL_MACROS_REQUEST: &str = "spring/expandAllMacros";
+/// 任务列表
+pub const JOBS_REQUEST: &str = "spring/jobs";
+/// 插件列表
+pub const PLUGINS_REQUEST: &str = "spring/plugins";
+/// 配置列表
+pub const CONFIGURATIONS_REQUEST: &str = "spring/configurations";
+/// 工作空间统计
+pub const STATS_REQUEST: &str = "spring/stats";
+/// 项目级依赖注入验证
+pub const VALIDATE_DEPENDENCIES_REQUEST: &str = "spring/validateDependencies";
+/// 诊断规则目录
+pub const LIST_RULES_REQUEST: &str = "spring/listRules";
+
+/// 服务器支持的自定义请求及其版本
+pub const CUSTOM_REQUESTS: &[(&str, u32)] = &[
+    (STATUS_REQUEST, 1),
+    (PERF_REPORT_REQUEST, 1),
+    (COMPONENTS_REQUEST, 1),
+    // 版本 2：支持排序、方法过滤和分页，响应包含路由总数
+    (ROUTES_REQUEST, 2),
+    (MIDDLEWARES_REQUEST, 1),
+    (REQUEST_SAMPLE_REQUEST, 1),
+    (EXPAND_ALL_MACROS_REQUEST, 1),
+    (JOBS_REQUEST, 1),
+    (PLUGINS_REQUEST, 1),
+    (CONFIGURATIONS_REQUEST, 1),
+    (STATS_REQUEST, 1),
+    (VALIDATE_DEPENDENCIES_REQUEST, 1),
+    (LIST_RULES_REQUEST, 1),
+];
+
+/// `initialize` 响应中的 `experimental` 能力
+pub fn experimental_capabilities() -> Value {
+    let requests: Map<String, Value> = CUSTOM_REQUESTS
+        .iter()
+        .map(|(method, version)| (method.to_string(), json!(version)))
+        .collect();
+    json!({
+        EXPERIMENTAL_KEY: {
+            "protocolVersion": PROTOCOL_VERSION,
+            "requests": requests,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_experimental_capabilities() {
+        let capabilities = experimental_capabilities();
+        let extension = &capabilities[EXPERIMENTAL_KEY];
+        assert_eq!(extension["protocolVersion"], PROTOCOL_VERSION);
+        let requests = extension["requests"].as_object().unwrap();
+        assert_eq!(requests.len(), CUSTOM_REQUESTS.len());
+        assert_eq!(requests[ROUTES_REQUEST], 2);
+    }
+}
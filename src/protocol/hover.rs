@@ -0,0 +1,753 @@
+//! 悬停提示构建模块
+//!
+//! Rust 文档的悬停提示需要格式化宏展开、扫描 crate 中的其他文件和查询配置，
+//! 在大型项目中可能耗时较长。服务器在后台线程（[`HoverWorker`]）中构建完整的悬停提示
+//! （[`HoverScope::Workspace`]），同时在分发线程上构建只依赖当前文档的简短提示（[`HoverScope::Document`]）；
+//! 完整提示未在时间预算（`[hover] budget_ms`）内完成时返回简短提示，避免阻塞编辑器。
+//!
+//! 所有悬停请求共用一个后台线程。超时的任务通过取消令牌通知后台线程，扫描项目文件的循环
+//! 在处理每个文件前检查令牌并放弃构建，后台线程随即可以处理下一个请求。
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::analysis::completion::middlewares;
+use crate::analysis::rust::handler_analyzer::HandlerAnalyzer;
+use crate::analysis::rust::macro_analyzer::{MacroAnalyzer, SpringMacro};
+use crate::analysis::validation::CancellationToken;
+use crate::core::config::MiddlewareRule;
+use crate::core::document::{Document, DocumentManager, SourceReader};
+use crate::core::messages::{localized, Locale};
+use crate::protocol::server::{
+    crate_rust_sources, find_project_root, read_app_config, route_location_range, route_middlewares,
+};
+
+/// AutoConfig 悬停提示中每类注册项最多列出的条目数
+const AUTO_CONFIG_HOVER_LIMIT: usize = 10;
+
+/// 指向源代码位置的 Markdown 链接
+fn location_link(location: &crate::protocol::types::LocationResponse) -> String {
+    format!("{}#L{}", location.uri, location.range.start.line + 1)
+}
+
+/// AutoConfig 悬停提示中的一类注册项，超出上限的条目只显示数量
+fn auto_config_section(title: &str, items: Vec<String>, locale: Locale) -> String {
+    let mut section = localized(locale, "hover.auto-config.section", &[&title, &items.len()]);
+    for item in items.iter().take(AUTO_CONFIG_HOVER_LIMIT) {
+        section.push_str(&format!("- {}\n", item));
+    }
+    if items.len() > AUTO_CONFIG_HOVER_LIMIT {
+        section.push_str(&localized(
+            locale,
+            "hover.auto-config.more",
+            &[&(items.len() - AUTO_CONFIG_HOVER_LIMIT)],
+        ));
+    }
+    section.push('\n');
+    section
+}
+
+/// 悬停提示的信息范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoverScope {
+    /// 只使用当前文档的内容（不扫描项目中的其他文件）
+    Document,
+    /// 使用整个项目的信息（其他文件中的类型、中间件链、自动注册的路由等）
+    Workspace,
+}
+
+/// 构建悬停提示所需的服务器状态快照，可以发送到其他线程
+#[derive(Clone)]
+pub struct HoverContext {
+    /// 工作空间根目录
+    pub workspace_path: Option<PathBuf>,
+    /// 文档管理器（读取编辑器中打开的内容）
+    pub document_manager: Arc<DocumentManager>,
+    /// 路由处理器分析器
+    pub handler_analyzer: Arc<HandlerAnalyzer>,
+    /// 宏分析器
+    pub macro_analyzer: Arc<MacroAnalyzer>,
+    /// 中间件规则
+    pub middleware_rules: Vec<MiddlewareRule>,
+    /// 取消令牌（超出时间预算时取消，扫描项目文件时检查）
+    pub cancellation: CancellationToken,
+}
+
+/// 提交给后台线程的完整悬停提示任务
+struct HoverJob {
+    context: HoverContext,
+    document: Document,
+    position: lsp_types::Position,
+    /// 返回结果的通道（构建时 panic 则不发送，接收端看到通道断开）
+    reply: Sender<Option<lsp_types::Hover>>,
+}
+
+/// 构建完整悬停提示的后台线程
+///
+/// 任务队列的容量为 1：后台线程还在处理上一个任务（尚未察觉取消）且已有任务排队时不再接受新任务，
+/// 调用方直接返回简短提示。服务器释放时队列关闭，后台线程随之退出
+pub struct HoverWorker {
+    sender: Sender<HoverJob>,
+}
+
+impl HoverWorker {
+    /// 启动后台线程
+    pub fn spawn() -> std::io::Result<Self> {
+        let (sender, receiver) = crossbeam_channel::bounded::<HoverJob>(1);
+        std::thread::Builder::new()
+            .name("spring-lsp-hover".to_string())
+            .spawn(move || {
+                for job in receiver {
+                    if job.context.cancellation.is_cancelled() {
+                        continue;
+                    }
+                    let hover = panic::catch_unwind(AssertUnwindSafe(|| {
+                        job.context
+                            .hover(&job.document, job.position, HoverScope::Workspace)
+                    }));
+                    if let Ok(hover) = hover {
+                        let _ = job.reply.send(hover);
+                    }
+                }
+            })?;
+        Ok(Self { sender })
+    }
+
+    /// 提交完整悬停提示任务，返回接收结果的通道；队列已满时返回 `None`
+    pub fn submit(
+        &self,
+        context: HoverContext,
+        document: Document,
+        position: lsp_types::Position,
+    ) -> Option<Receiver<Option<lsp_types::Hover>>> {
+        let (reply, receiver) = crossbeam_channel::bounded(1);
+        self.sender
+            .try_send(HoverJob {
+                context,
+                document,
+                position,
+                reply,
+            })
+            .ok()?;
+        Some(receiver)
+    }
+}
+
+impl HoverContext {
+    /// 构建 Rust 文档中指定位置的悬停提示
+    ///
+    /// 依次尝试：处理器提取器、路由中间件链、中间件配置、Service 注入字段、任务调度宏、AutoConfig 自动注册项。
+    /// [`HoverScope::Document`] 下跳过需要扫描项目的中间件提示，其余提示只使用当前文档中的信息。
+    pub fn hover(
+        &self,
+        document: &Document,
+        position: lsp_types::Position,
+        scope: HoverScope,
+    ) -> Option<lsp_types::Hover> {
+        let mut handler_file = self
+            .handler_analyzer
+            .analyze_lines(&document.mapper())
+            .ok()?;
+        if self.cancellation.is_cancelled() {
+            return None;
+        }
+        // 提取器的类型参数可能定义在 crate 的其他文件中
+        if scope == HoverScope::Workspace && handler_file.param_at(position).is_some() {
+            for (name, info) in self.crate_structs(&document.uri) {
+                handler_file.structs.entry(name).or_insert(info);
+            }
+        }
+
+        let hover = self.handler_analyzer.hover(&handler_file, position);
+        match scope {
+            HoverScope::Document => hover,
+            HoverScope::Workspace => hover
+                .or_else(|| self.middleware_hover(&document.uri, &document.content, position))
+                .or_else(|| {
+                    self.middleware_config_hover(&document.uri, &document.content, position)
+                }),
+        }
+        .or_else(|| self.service_hover(document, position, scope))
+        .or_else(|| self.job_hover(document, position))
+        .or_else(|| self.auto_config_hover(document, position, scope))
+    }
+
+    /// 悬停提示使用的语言（与宏分析器一致）
+    fn locale(&self) -> Locale {
+        self.macro_analyzer.locale()
+    }
+
+    fn project_root_for(&self, uri: &lsp_types::Url) -> Option<PathBuf> {
+        find_project_root(uri, self.workspace_path.as_deref())
+    }
+
+    /// 项目扫描器读取源文件的方式：优先使用编辑器中打开的内容
+    fn sources(&self) -> SourceReader {
+        SourceReader::overlay(Arc::clone(&self.document_manager))
+    }
+
+    fn crate_sources(&self, uri: &lsp_types::Url) -> Vec<(lsp_types::Url, String)> {
+        match self.project_root_for(uri) {
+            Some(root) => crate_rust_sources(&self.document_manager, &root),
+            None => Vec::new(),
+        }
+    }
+
+    fn document_route_middlewares(
+        &self,
+        uri: &lsp_types::Url,
+        content: &str,
+    ) -> Vec<crate::scanner::middleware::RouteMiddlewares> {
+        route_middlewares(
+            &self.document_manager,
+            uri,
+            content,
+            self.project_root_for(uri).as_deref(),
+            &self.middleware_rules,
+        )
+    }
+
+    fn app_config_table(&self, project_root: &Path) -> Option<toml::Table> {
+        read_app_config(&self.document_manager, project_root)
+    }
+
+    /// 文档所属 crate 中其他文件定义的结构体（键为结构体名）
+    fn crate_structs(
+        &self,
+        uri: &lsp_types::Url,
+    ) -> HashMap<String, crate::analysis::rust::handler_analyzer::StructInfo> {
+        self.crate_sources(uri)
+            .into_iter()
+            .take_while(|_| !self.cancellation.is_cancelled())
+            .filter(|(source_uri, _)| source_uri != uri)
+            .filter_map(|(_, content)| self.handler_analyzer.analyze(&content).ok())
+            .flat_map(|file| file.structs)
+            .collect()
+    }
+
+    /// 在路由处理器上显示生效的中间件链
+    fn middleware_hover(
+        &self,
+        uri: &lsp_types::Url,
+        content: &str,
+        position: lsp_types::Position,
+    ) -> Option<lsp_types::Hover> {
+        let route = self
+            .document_route_middlewares(uri, content)
+            .into_iter()
+            .find(|route| {
+                let range = route_location_range(&route.location);
+                position >= range.start && position <= range.end
+            })?;
+
+        let locale = self.locale();
+        let mut hover = localized(locale, "hover.middleware-chain", &[]);
+        hover.push_str(&format!(
+            "`{} {}` → `{}`
+
+",
+            route.methods.join(", "),
+            route.path,
+            route.handler
+        ));
+
+        if route.middlewares.is_empty() {
+            hover.push_str(&localized(locale, "hover.middleware-chain.empty", &[]));
+        } else {
+            hover.push_str(&localized(locale, "hover.middleware-chain.order", &[]));
+            for (i, middleware) in route.middlewares.iter().enumerate() {
+                hover.push_str(&localized(
+                    locale,
+                    "hover.middleware-chain.item",
+                    &[&(i + 1), &middleware.name, &middleware.source.as_str()],
+                ));
+            }
+        }
+
+        for rule in &route.missing {
+            hover.push_str(&localized(
+                locale,
+                "hover.middleware-chain.missing",
+                &[&rule.required, &rule.pattern],
+            ));
+        }
+
+        Some(lsp_types::Hover {
+            contents: lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: hover,
+            }),
+            range: Some(route_location_range(&route.location)),
+        })
+    }
+
+    /// 在 `#[middlewares(...)]` 中的中间件上显示关联的配置结构及其当前值
+    fn middleware_config_hover(
+        &self,
+        uri: &lsp_types::Url,
+        content: &str,
+        position: lsp_types::Position,
+    ) -> Option<lsp_types::Hover> {
+        use crate::scanner::config::ConfigScanner;
+        use crate::scanner::middleware::MiddlewareScanner;
+
+        let scanner = MiddlewareScanner::new().with_sources(self.sources());
+        let reference = scanner
+            .middleware_references(content)
+            .ok()?
+            .into_iter()
+            .find(|reference| {
+                position >= reference.range.start && position <= reference.range.end
+            })?;
+
+        let project_root = self.project_root_for(uri)?;
+        let configurations = ConfigScanner::new()
+            .with_sources(self.sources())
+            .scan_configurations(&project_root)
+            .unwrap_or_default();
+        let configs = scanner.find_middleware_configs(
+            &project_root,
+            Some((uri, content)),
+            &reference,
+            &configurations,
+        );
+
+        let locale = self.locale();
+        let mut hover = localized(locale, "hover.middleware", &[&reference.name]);
+        // 工作空间中定义的中间件优先于同名的内置中间件
+        let definition = scanner
+            .workspace_middlewares(&self.crate_sources(uri))
+            .into_iter()
+            .find(|definition| reference.idents.contains(&definition.name));
+        let bundled = middlewares::find_bundled_middleware(&reference.idents);
+        match (&definition, bundled) {
+            (Some(definition), _) => {
+                if let Some(summary) = &definition.summary {
+                    hover.push_str(&format!("\n{}\n", summary));
+                }
+                hover.push_str(&localized(
+                    locale,
+                    "hover.middleware.definition",
+                    &[&definition.uri.path()],
+                ));
+            }
+            (None, Some(bundled)) => {
+                hover.push_str(&format!(
+                    "\n{}\n",
+                    middlewares::bundled_middleware_docs(bundled, locale)
+                ));
+            }
+            (None, None) => {}
+        }
+        if configs.is_empty() && bundled.is_none() {
+            hover.push_str(&localized(locale, "hover.middleware.no-config", &[]));
+        }
+
+        let app_config = self.app_config_table(&project_root);
+        for config in &configs {
+            let section = app_config
+                .as_ref()
+                .and_then(|table| table.get(&config.prefix))
+                .and_then(|value| value.as_table());
+
+            hover.push_str(&localized(
+                locale,
+                "hover.middleware.config",
+                &[&config.name, &config.prefix],
+            ));
+            hover.push_str(&localized(locale, "hover.middleware.config-table", &[]));
+            for field in &config.fields {
+                let value = match section.and_then(|section| section.get(&field.name)) {
+                    Some(value) => format!("`{}`", value),
+                    None => localized(locale, "hover.unset", &[]),
+                };
+                hover.push_str(&format!(
+                    "| `{}` | `{}` | {} |\n",
+                    field.name, field.type_name, value
+                ));
+            }
+        }
+
+        Some(lsp_types::Hover {
+            contents: lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: hover,
+            }),
+            range: Some(reference.range),
+        })
+    }
+
+    /// 在 `#[derive(Service)]` 结构体上显示注入字段，字段类型定义在工作空间中时附上文档摘要
+    fn service_hover(
+        &self,
+        document: &Document,
+        position: lsp_types::Position,
+        scope: HoverScope,
+    ) -> Option<lsp_types::Hover> {
+        use crate::analysis::validation::di_task::CrateSources;
+
+        let doc = self
+            .macro_analyzer
+            .parse(document.uri.clone(), document.content.clone())
+            .ok()?;
+        let doc = self
+            .macro_analyzer
+            .extract_macros_with_index(doc, document.line_index())
+            .ok()?;
+        let service = doc
+            .macros
+            .iter()
+            .find_map(|spring_macro| match spring_macro {
+                SpringMacro::DeriveService(service)
+                    if position >= service.range.start && position <= service.range.end =>
+                {
+                    Some(service)
+                }
+                _ => None,
+            })?;
+
+        // 当前文档优先，其次是工作空间中其他文件（磁盘上的内容）
+        let mut type_docs = self.macro_analyzer.type_doc_summaries(&document.content);
+        let missing = service
+            .fields
+            .iter()
+            .any(|field| !type_docs.contains_key(&field.type_name));
+        if let Some(root) = (missing && scope == HoverScope::Workspace)
+            .then(|| {
+                self.workspace_path
+                    .clone()
+                    .or_else(|| self.project_root_for(&document.uri))
+            })
+            .flatten()
+        {
+            for sources in CrateSources::discover(&root) {
+                for (uri, content) in sources.files.iter().filter(|(uri, _)| *uri != document.uri) {
+                    if self.cancellation.is_cancelled() {
+                        return None;
+                    }
+                    let content = self
+                        .document_manager
+                        .with_document(uri, |doc| doc.content.clone())
+                        .unwrap_or_else(|| content.clone());
+                    for (name, summary) in self.macro_analyzer.type_doc_summaries(&content) {
+                        type_docs.entry(name).or_insert(summary);
+                    }
+                }
+            }
+        }
+
+        Some(lsp_types::Hover {
+            contents: lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: self.macro_analyzer.hover_service(service, &type_docs),
+            }),
+            range: Some(service.range),
+        })
+    }
+
+    /// 在任务调度宏上显示调度说明，`#[fix_delay]` / `#[fix_rate]` 附带按任务体估算耗时的调度时间线
+    fn job_hover(
+        &self,
+        document: &Document,
+        position: lsp_types::Position,
+    ) -> Option<lsp_types::Hover> {
+        use crate::analysis::rust::job_timeline::min_body_duration;
+        use crate::analysis::rust::macro_analyzer::JobMacro;
+        use syn::spanned::Spanned;
+
+        let doc = self
+            .macro_analyzer
+            .parse(document.uri.clone(), document.content.clone())
+            .ok()?;
+        let doc = self
+            .macro_analyzer
+            .extract_macros_with_index(doc, document.line_index())
+            .ok()?;
+        let (job, range) = doc
+            .macros
+            .iter()
+            .find_map(|spring_macro| match spring_macro {
+                SpringMacro::Job(job) => {
+                    let range = match job {
+                        JobMacro::Cron { range, .. }
+                        | JobMacro::FixDelay { range, .. }
+                        | JobMacro::FixRate { range, .. }
+                        | JobMacro::OneShot { range, .. } => *range,
+                    };
+                    (position >= range.start && position <= range.end).then_some((job, range))
+                }
+                _ => None,
+            })?;
+
+        // 任务宏所在的函数（宏的起始位置与属性一致）
+        let mapper = document.mapper();
+        let min_duration = syn::parse_file(&document.content)
+            .ok()?
+            .items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Fn(item_fn)
+                    if item_fn
+                        .attrs
+                        .iter()
+                        .any(|attr| mapper.span_range(attr.span()).start == range.start) =>
+                {
+                    Some(item_fn)
+                }
+                _ => None,
+            })
+            .and_then(min_body_duration);
+
+        Some(lsp_types::Hover {
+            contents: lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: self.macro_analyzer.hover_job(job, min_duration),
+            }),
+            range: Some(range),
+        })
+    }
+
+    /// 在 `#[auto_config(...)]` 上列出配置器将自动注册的路由、任务和消息流监听器
+    fn auto_config_hover(
+        &self,
+        document: &Document,
+        position: lsp_types::Position,
+        scope: HoverScope,
+    ) -> Option<lsp_types::Hover> {
+        use crate::scanner::job::JobScanner;
+        use crate::scanner::route::RouteScanner;
+        use crate::scanner::stream::StreamScanner;
+
+        let doc = self
+            .macro_analyzer
+            .parse(document.uri.clone(), document.content.clone())
+            .ok()?;
+        let doc = self
+            .macro_analyzer
+            .extract_macros_with_index(doc, document.line_index())
+            .ok()?;
+        let auto_config = doc
+            .macros
+            .iter()
+            .find_map(|spring_macro| match spring_macro {
+                SpringMacro::AutoConfig(auto_config)
+                    if position >= auto_config.range.start && position <= auto_config.range.end =>
+                {
+                    Some(auto_config)
+                }
+                _ => None,
+            })?;
+
+        let mut hover = self
+            .macro_analyzer
+            .hover_macro(&SpringMacro::AutoConfig(auto_config.clone()));
+
+        let project_root = match scope {
+            HoverScope::Document => None,
+            HoverScope::Workspace => self.project_root_for(&document.uri),
+        };
+        let locale = self.locale();
+        let mut sections = Vec::new();
+        for configurator in auto_config.configurators() {
+            let Some(project_root) = project_root.as_deref() else {
+                break;
+            };
+            if self.cancellation.is_cancelled() {
+                return None;
+            }
+
+            match configurator {
+                "WebConfigurator" => {
+                    let scanner = RouteScanner::new().with_sources(self.sources());
+                    let routes: Vec<_> = scanner
+                        .routes(project_root)
+                        .into_iter()
+                        .flatten()
+                        .take_while(|_| !self.cancellation.is_cancelled())
+                        .collect();
+                    sections.push(auto_config_section(
+                        &localized(locale, "hover.auto-config.routes", &[]),
+                        routes
+                            .iter()
+                            .map(|route| {
+                                format!(
+                                    "[`{} {}`]({}) → `{}`",
+                                    route.method,
+                                    route.path,
+                                    location_link(&route.location),
+                                    route.handler
+                                )
+                            })
+                            .collect(),
+                        locale,
+                    ));
+                }
+                "JobConfigurator" => {
+                    let jobs = JobScanner::new()
+                        .with_sources(self.sources())
+                        .scan_jobs(project_root)
+                        .unwrap_or_default();
+                    sections.push(auto_config_section(
+                        &localized(locale, "hover.auto-config.jobs", &[]),
+                        jobs.iter()
+                            .map(|job| {
+                                format!(
+                                    "[`{}`]({})（{}）",
+                                    job.name,
+                                    location_link(&job.location),
+                                    job.schedule
+                                )
+                            })
+                            .collect(),
+                        locale,
+                    ));
+                }
+                "StreamConfigurator" => {
+                    let listeners = StreamScanner::new()
+                        .with_sources(self.sources())
+                        .scan_streams(project_root)
+                        .unwrap_or_default();
+                    sections.push(auto_config_section(
+                        &localized(locale, "hover.auto-config.listeners", &[]),
+                        listeners
+                            .iter()
+                            .map(|listener| {
+                                format!(
+                                    "[`{}`]({}) ← {}",
+                                    listener.handler,
+                                    location_link(&listener.location),
+                                    listener
+                                        .topics
+                                        .iter()
+                                        .map(|topic| format!("`{}`", topic))
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                )
+                            })
+                            .collect(),
+                        locale,
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        if self.cancellation.is_cancelled() {
+            return None;
+        }
+        if !sections.is_empty() {
+            hover.push_str(&localized(locale, "hover.auto-config.registered", &[]));
+            for section in sections {
+                hover.push_str(&section);
+            }
+        }
+
+        Some(lsp_types::Hover {
+            contents: lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: hover,
+            }),
+            range: Some(auto_config.range),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hover_text(hover: lsp_types::Hover) -> String {
+        match hover.contents {
+            lsp_types::HoverContents::Markup(content) => content.value,
+            other => panic!("Expected markup hover, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_document_scope_skips_workspace_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/repo.rs"),
+            "/// 用户数据访问\n#[derive(Clone)]\npub struct UserRepo;\n",
+        )
+        .unwrap();
+        let main_rs = "#[derive(Clone, Service)]\nstruct UserService {\n    #[inject(component)]\n    repo: UserRepo,\n}\n";
+
+        let uri = lsp_types::Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+        let document_manager = Arc::new(DocumentManager::new());
+        document_manager.open(uri.clone(), 1, main_rs.to_string(), "rust".to_string());
+        let document = document_manager.get(&uri).unwrap();
+        let context = HoverContext {
+            workspace_path: None,
+            document_manager,
+            handler_analyzer: Arc::new(HandlerAnalyzer::new()),
+            macro_analyzer: Arc::new(MacroAnalyzer::new()),
+            middleware_rules: Vec::new(),
+            cancellation: CancellationToken::new(),
+        };
+        let position = lsp_types::Position::new(1, 9);
+
+        let quick = hover_text(
+            context
+                .hover(&document, position, HoverScope::Document)
+                .unwrap(),
+        );
+        assert!(quick.contains("UserRepo"));
+        assert!(!quick.contains("用户数据访问"));
+
+        let full = hover_text(
+            context
+                .hover(&document, position, HoverScope::Workspace)
+                .unwrap(),
+        );
+        assert!(full.contains("用户数据访问"));
+
+        // 后台线程构建同样的完整提示
+        let worker = HoverWorker::spawn().unwrap();
+        let receiver = worker
+            .submit(context.clone(), document.clone(), position)
+            .unwrap();
+        let from_worker = hover_text(receiver.recv().unwrap().unwrap());
+        assert_eq!(from_worker, full);
+
+        // 取消后不再扫描项目文件
+        context.cancellation.cancel();
+        let cancelled = context.hover(&document, position, HoverScope::Workspace);
+        assert!(cancelled.is_none_or(|hover| !hover_text(hover).contains("用户数据访问")));
+    }
+
+    #[test]
+    fn test_fix_rate_timeline_hover() {
+        let uri = lsp_types::Url::parse("file:///app/src/jobs.rs").unwrap();
+        let source = "#[fix_rate(5)]\nasync fn sync() {\n    tokio::time::sleep(Duration::from_secs(8)).await;\n}\n";
+        let document_manager = Arc::new(DocumentManager::new());
+        document_manager.open(uri.clone(), 1, source.to_string(), "rust".to_string());
+        let document = document_manager.get(&uri).unwrap();
+        let context = HoverContext {
+            workspace_path: None,
+            document_manager,
+            handler_analyzer: Arc::new(HandlerAnalyzer::new()),
+            macro_analyzer: Arc::new(MacroAnalyzer::new()),
+            middleware_rules: Vec::new(),
+            cancellation: CancellationToken::new(),
+        };
+
+        let hover = context
+            .hover(
+                &document,
+                lsp_types::Position::new(0, 3),
+                HoverScope::Document,
+            )
+            .unwrap();
+        assert_eq!(hover.range.unwrap().start, lsp_types::Position::new(0, 0));
+        let text = hover_text(hover);
+        assert!(text.contains("**调度时间线**（任务体中的固定等待至少 8s）"));
+        assert!(text.contains("⚠️ 任务体中的等待至少需要 8s，不短于 5s 的执行频率"));
+    }
+}
@@ -0,0 +1,209 @@
+//! 部分结果（partial result）流式传输模块
+//!
+//! 实现 LSP 的 `partialResultToken` 支持：客户端在请求参数中提供令牌时，
+//! 大型结果集通过 `$/progress` 通知分块发送，最终响应只包含空结果。
+//! 这样可以避免在大型工作空间中序列化和传输一个巨大的响应。
+
+use crossbeam_channel::Sender;
+use lsp_server::{Message, Notification};
+use lsp_types::ProgressToken;
+use serde::Serialize;
+
+use crate::{Error, Result};
+
+/// 每个部分结果通知包含的默认条目数
+pub const DEFAULT_CHUNK_SIZE: usize = 100;
+
+/// 部分结果通知的方法名
+const PROGRESS_METHOD: &str = "$/progress";
+
+/// 从请求参数中读取 `partialResultToken`
+pub fn partial_result_token(params: &serde_json::Value) -> Option<ProgressToken> {
+    params
+        .get("partialResultToken")
+        .and_then(|token| serde_json::from_value(token.clone()).ok())
+}
+
+/// 部分结果流
+///
+/// 缓冲推入的结果，每满一个分块就通过 `$/progress` 通知发送给客户端。
+/// 结果默认以数组形式发送；对于以对象包装列表的自定义请求（如 `spring/routes`
+/// 的 `{ "routes": [...] }`），使用 [`PartialResultStream::with_field`] 指定字段名。
+pub struct PartialResultStream<'a, T: Serialize> {
+    /// 消息发送端
+    sender: &'a Sender<Message>,
+    /// 客户端提供的部分结果令牌
+    token: ProgressToken,
+    /// 每个分块的条目数
+    chunk_size: usize,
+    /// 包装结果列表的字段名
+    field: Option<&'static str>,
+    /// 待发送的结果
+    buffer: Vec<T>,
+    /// 已发送的分块数
+    chunks_sent: usize,
+    /// 已发送的条目数
+    items_sent: usize,
+}
+
+impl<'a, T: Serialize> PartialResultStream<'a, T> {
+    /// 创建部分结果流
+    pub fn new(sender: &'a Sender<Message>, token: ProgressToken, chunk_size: usize) -> Self {
+        Self {
+            sender,
+            token,
+            chunk_size: chunk_size.max(1),
+            field: None,
+            buffer: Vec::new(),
+            chunks_sent: 0,
+            items_sent: 0,
+        }
+    }
+
+    /// 将每个分块包装为 `{ field: [...] }` 形式的对象
+    pub fn with_field(mut self, field: &'static str) -> Self {
+        self.field = Some(field);
+        self
+    }
+
+    /// 推入一个结果，分块已满时立即发送
+    pub fn push(&mut self, item: T) -> Result<()> {
+        self.buffer.push(item);
+        if self.buffer.len() >= self.chunk_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// 推入多个结果
+    pub fn extend(&mut self, items: impl IntoIterator<Item = T>) -> Result<()> {
+        for item in items {
+            self.push(item)?;
+        }
+        Ok(())
+    }
+
+    /// 发送缓冲区中剩余的结果
+    pub fn flush(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        let chunk = std::mem::take(&mut self.buffer);
+        let count = chunk.len();
+        let value = self.wrap(serde_json::to_value(chunk)?);
+
+        let notification = Notification::new(
+            PROGRESS_METHOD.to_string(),
+            serde_json::json!({ "token": self.token, "value": value }),
+        );
+        self.sender
+            .send(Message::Notification(notification))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        self.chunks_sent += 1;
+        self.items_sent += count;
+        Ok(())
+    }
+
+    /// 结束流式传输，返回最终响应应使用的空结果
+    pub fn finish(mut self) -> Result<serde_json::Value> {
+        self.flush()?;
+        tracing::debug!(
+            "Streamed {} partial results in {} chunks",
+            self.items_sent,
+            self.chunks_sent
+        );
+        Ok(self.wrap(serde_json::Value::Array(Vec::new())))
+    }
+
+    /// 已发送的条目数
+    pub fn items_sent(&self) -> usize {
+        self.items_sent
+    }
+
+    /// 按字段名包装结果列表
+    fn wrap(&self, list: serde_json::Value) -> serde_json::Value {
+        match self.field {
+            Some(field) => serde_json::json!({ field: list }),
+            None => list,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::NumberOrString;
+
+    fn progress_values(receiver: &crossbeam_channel::Receiver<Message>) -> Vec<serde_json::Value> {
+        receiver
+            .try_iter()
+            .map(|msg| match msg {
+                Message::Notification(not) => {
+                    assert_eq!(not.method, PROGRESS_METHOD);
+                    assert_eq!(not.params["token"], "search-1");
+                    not.params["value"].clone()
+                }
+                other => panic!("Expected notification, got {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_partial_result_token() {
+        let params = serde_json::json!({ "query": "", "partialResultToken": "abc" });
+        assert_eq!(
+            partial_result_token(&params),
+            Some(NumberOrString::String("abc".to_string()))
+        );
+
+        let params = serde_json::json!({ "query": "", "partialResultToken": 7 });
+        assert_eq!(
+            partial_result_token(&params),
+            Some(NumberOrString::Number(7))
+        );
+
+        assert_eq!(
+            partial_result_token(&serde_json::json!({ "query": "" })),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stream_in_chunks() {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let token = NumberOrString::String("search-1".to_string());
+
+        let mut stream = PartialResultStream::new(&sender, token, 2);
+        stream.extend(1..=5).unwrap();
+        assert_eq!(stream.items_sent(), 4);
+        let result = stream.finish().unwrap();
+
+        assert_eq!(result, serde_json::json!([]));
+        assert_eq!(
+            progress_values(&receiver),
+            vec![
+                serde_json::json!([1, 2]),
+                serde_json::json!([3, 4]),
+                serde_json::json!([5]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_with_field() {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let token = NumberOrString::String("search-1".to_string());
+
+        let mut stream = PartialResultStream::new(&sender, token, 10).with_field("routes");
+        stream.push("/users").unwrap();
+        let result = stream.finish().unwrap();
+
+        assert_eq!(result, serde_json::json!({ "routes": [] }));
+        assert_eq!(
+            progress_values(&receiver),
+            vec![serde_json::json!({ "routes": ["/users"] })]
+        );
+    }
+}
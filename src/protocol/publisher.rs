@@ -0,0 +1,176 @@
+//! 诊断发布队列模块
+//!
+//! 工作空间级的验证（如 `spring/validateDependencies`）可能一次产生数千个文件的诊断，
+//! 同时发送数千条 `textDocument/publishDiagnostics` 通知会让部分客户端卡顿。
+//! 需要发布的文件先放入队列，再按固定间隔分批发布：
+//! - 每批最多发布 [`DEFAULT_BATCH_SIZE`] 个文件，两批之间至少间隔 [`DEFAULT_INTERVAL`]
+//! - 编辑器中打开的文件排在未打开的文件之前
+//! - 同一文件在发布前多次入队只发布一次，发布时读取诊断引擎中的最新诊断
+
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use lsp_types::Url;
+
+/// 每批发布的默认文件数
+pub const DEFAULT_BATCH_SIZE: usize = 50;
+
+/// 两批之间的默认间隔
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 限速的诊断发布队列
+///
+/// 队列只记录需要发布的文件，诊断内容由调用方在发布时从诊断引擎读取
+#[derive(Debug)]
+pub struct DiagnosticPublisher {
+    /// 打开的文件
+    open: VecDeque<Url>,
+    /// 未打开的文件
+    background: VecDeque<Url>,
+    /// 已在队列中的文件
+    queued: HashSet<Url>,
+    /// 每批最多发布的文件数
+    batch_size: usize,
+    /// 两批之间的最小间隔
+    interval: Duration,
+    /// 上一批的发布时间
+    last_batch: Option<Instant>,
+}
+
+impl DiagnosticPublisher {
+    /// 创建发布队列
+    pub fn new(batch_size: usize, interval: Duration) -> Self {
+        Self {
+            open: VecDeque::new(),
+            background: VecDeque::new(),
+            queued: HashSet::new(),
+            batch_size: batch_size.max(1),
+            interval,
+            last_batch: None,
+        }
+    }
+
+    /// 将文件加入队列，已在队列中的未打开文件变为打开时提前
+    pub fn enqueue(&mut self, uri: Url, open: bool) {
+        if !self.queued.insert(uri.clone()) {
+            if open {
+                if let Some(index) = self.background.iter().position(|queued| *queued == uri) {
+                    self.background.remove(index);
+                    self.open.push_back(uri);
+                }
+            }
+            return;
+        }
+
+        if open {
+            self.open.push_back(uri);
+        } else {
+            self.background.push_back(uri);
+        }
+    }
+
+    /// 从队列中移除文件（文件的诊断已经直接发布）
+    pub fn discard(&mut self, uri: &Url) {
+        if self.queued.remove(uri) {
+            self.open.retain(|queued| queued != uri);
+            self.background.retain(|queued| queued != uri);
+        }
+    }
+
+    /// 队列是否为空
+    pub fn is_empty(&self) -> bool {
+        self.queued.is_empty()
+    }
+
+    /// 队列中的文件数
+    pub fn len(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// 距离下一批可以发布还需要等待的时间
+    pub fn wait_time(&self, now: Instant) -> Duration {
+        self.last_batch
+            .map(|last| (last + self.interval).saturating_duration_since(now))
+            .unwrap_or_default()
+    }
+
+    /// 取出下一批需要发布的文件，距离上一批不足间隔时返回空列表
+    pub fn next_batch(&mut self, now: Instant) -> Vec<Url> {
+        if self.is_empty() || !self.wait_time(now).is_zero() {
+            return Vec::new();
+        }
+
+        let mut batch = Vec::new();
+        while batch.len() < self.batch_size {
+            let Some(uri) = self
+                .open
+                .pop_front()
+                .or_else(|| self.background.pop_front())
+            else {
+                break;
+            };
+            self.queued.remove(&uri);
+            batch.push(uri);
+        }
+        self.last_batch = Some(now);
+        batch
+    }
+}
+
+impl Default for DiagnosticPublisher {
+    fn default() -> Self {
+        Self::new(DEFAULT_BATCH_SIZE, DEFAULT_INTERVAL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uri(name: &str) -> Url {
+        Url::parse(&format!("file:///app/src/{}.rs", name)).unwrap()
+    }
+
+    #[test]
+    fn test_batches_are_rate_limited() {
+        let mut publisher = DiagnosticPublisher::new(2, Duration::from_millis(100));
+        for name in ["a", "b", "c"] {
+            publisher.enqueue(uri(name), false);
+        }
+        publisher.enqueue(uri("a"), false);
+        assert_eq!(publisher.len(), 3);
+
+        let start = Instant::now();
+        assert_eq!(publisher.next_batch(start), vec![uri("a"), uri("b")]);
+        assert!(publisher
+            .next_batch(start + Duration::from_millis(50))
+            .is_empty());
+        assert_eq!(
+            publisher.wait_time(start + Duration::from_millis(50)),
+            Duration::from_millis(50)
+        );
+        assert_eq!(
+            publisher.next_batch(start + Duration::from_millis(100)),
+            vec![uri("c")]
+        );
+        assert!(publisher.is_empty());
+    }
+
+    #[test]
+    fn test_open_files_are_published_first() {
+        let mut publisher = DiagnosticPublisher::new(2, Duration::from_millis(100));
+        publisher.enqueue(uri("a"), false);
+        publisher.enqueue(uri("b"), false);
+        publisher.enqueue(uri("c"), true);
+        // 已在队列中的文件被打开后提前
+        publisher.enqueue(uri("b"), true);
+        publisher.enqueue(uri("d"), false);
+        publisher.discard(&uri("d"));
+
+        assert_eq!(
+            publisher.next_batch(Instant::now()),
+            vec![uri("c"), uri("b")]
+        );
+        assert_eq!(publisher.len(), 1);
+    }
+}
@@ -17,13 +17,14 @@
 //!
 //! ### 悬停提示 (Hover)
 //! - 配置项：显示类型、文档、默认值
-//! - 宏：显示宏展开后的代码
+//! - 宏：显示宏展开后的代码，`#[auto_config]` 额外列出将自动注册的路由、任务和消息流监听器
 //! - 路由：显示完整路径和 HTTP 方法
 //! - 环境变量：显示当前值（如果可用）
 //!
 //! ### 定义跳转 (Go to Definition)
 //! - 路由路径：跳转到处理器函数定义
 //! - 组件注入：跳转到组件定义
+//! - 路由路径与 OpenAPI 规范文件（`openapi.yaml`）中的操作互相跳转
 //!
 //! ### 文档符号 (Document Symbols)
 //! - 显示文档中的所有路由
@@ -33,39 +34,421 @@
 //! - 全局搜索路由
 //! - 全局搜索组件
 //!
+//! ### 文档颜色 (Document Color)
+//! - Schema 中声明为颜色（`"format": "color"`）的配置值：显示色块并支持取色器修改
+//!
+//! ### 文件操作 (File Operations)
+//! - 重命名或移动 Rust 文件前：更新 `crate::` 开头的模块路径引用
+//! - 重命名或移动 Rust 文件后：更新索引位置、检查模块声明并重新发布受影响文档的诊断
+//!
 //! ### 诊断 (Diagnostics)
 //! - 配置验证：类型检查、必需项检查、废弃警告
 //! - 路由验证：路径语法、参数类型、冲突检测
 //! - 依赖注入验证：组件存在性、循环依赖检测
+//! - OpenAPI 规范对照：规范中缺少的路由、没有处理器的规范操作
+//! - 配置文件修改后，只重新验证依赖被修改的配置节的 Rust 文件
 //!
 //! ## LSP 协议版本
 //!
 //! 本实现遵循 LSP 3.17 规范。
 
-use crate::analysis::completion::CompletionEngine;
-use crate::analysis::diagnostic::DiagnosticEngine;
+use crate::analysis::completion::{component_names, middlewares, snippet, CompletionEngine};
+use crate::analysis::diagnostic::{DiagnosticEngine, ListRulesResponse, QuickFix};
+use crate::analysis::item_cache::ItemDiagnosticsCache;
+use crate::analysis::openapi::OpenApiSpec;
+use crate::analysis::pipeline::{
+    AnalysisContext, AnalysisPipeline, DocumentPipeline, FnPass, PassStage,
+};
+use crate::analysis::rust::file_template;
+use crate::analysis::rust::handler_analyzer::{ExtractorKind, HandlerAnalyzer};
 use crate::analysis::rust::macro_analyzer::MacroAnalyzer;
-use crate::analysis::toml::toml_analyzer::TomlAnalyzer;
-use crate::core::config::ServerConfig;
-use crate::core::document::DocumentManager;
-use crate::core::index::IndexManager;
-use crate::core::schema::SchemaProvider;
+use crate::analysis::rust::module_path;
+use crate::analysis::rust::rename::{self, RenameTarget};
+use crate::analysis::rust::route_organizer::RouteOrganizer;
+use crate::analysis::rust::router_calls::{router_routes_at, RouterRoute};
+use crate::analysis::rust::type_definition::extractor_target;
+use crate::analysis::toml::color_value;
+use crate::analysis::toml::toml_analyzer::{TomlAnalyzer, TomlSyntaxError};
+use crate::analysis::validation::component_registration::MissingComponent;
+use crate::analysis::validation::di_task::{
+    CancellationToken, CrateSources, DiTaskStep, DiValidationTask, ValidateDependenciesRequest,
+    ValidateDependenciesResponse,
+};
+use crate::analysis::validation::di_validator::CONFIG_NOT_FOUND;
+use crate::analysis::validation::{
+    ConfigFieldValidator, DependencyInjectionValidator, HandlerNameValidator,
+    IncrementalDiValidator, JobValidator, OpenApiSpecValidator, RoutePrefixValidator,
+    RouteRegistrationValidator, RouteSecurityValidator,
+};
+use crate::core::baseline::Baseline;
+use crate::core::check::ProjectChecker;
+use crate::core::config::{MiddlewareRule, ServerConfig, WORKSPACE_CONFIG_FILE};
+use crate::core::document::{
+    Document, DocumentManager, PositionEncoding, PositionMapper, SourceReader,
+};
+use crate::core::file_watcher::{FileWatcher, WATCHED_GLOBS};
+use crate::core::index::{IndexManager, SymbolType};
+use crate::core::messages::{localized, Locale};
+#[cfg(feature = "profiling")]
+use crate::core::profiling;
+use crate::core::schema::{ConfigSchema, PluginVersions, SchemaProvider};
+use crate::core::security::SecurityRules;
+use crate::core::workspace_edit::{WorkspaceEditBuilder, WorkspaceEditError, WorkspaceEditSupport};
+use crate::protocol::extensions;
+use crate::protocol::hover::{HoverContext, HoverScope, HoverWorker};
+use crate::protocol::partial::{partial_result_token, PartialResultStream, DEFAULT_CHUNK_SIZE};
+use crate::protocol::publisher::DiagnosticPublisher;
+use crate::protocol::transport;
+use crate::scanner::component::{injected_type_at, provider_functions};
+use crate::scanner::config::{
+    config_file_profile, config_value_sources, ConfigScanner, ConfigValueSource,
+};
+use crate::scanner::generated::GeneratedCodeCache;
+use crate::scanner::project_cache::ProjectScanCache;
 use crate::scanner::route::RouteNavigator;
+use crate::scanner::stats::WorkspaceStats;
+use crate::scanner::stats::{config_key_stats, HealthCategory, ProjectHealth};
 use crate::utils::error::{ErrorHandler, RecoveryAction};
-use crate::utils::status::ServerStatus;
+use crate::utils::status::{CacheStats, MemoryUsage, ServerStatus};
 use crate::{Error, Result};
 use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
 use lsp_types::{
     notification::{
-        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Exit, Notification as _,
+        Cancel, DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidCreateFiles,
+        DidOpenTextDocument, DidRenameFiles, Exit, Notification as _,
+    },
+    request::{
+        ApplyWorkspaceEdit, CodeActionRequest, CodeLensRequest, ColorPresentationRequest,
+        Completion, DocumentColor, DocumentSymbolRequest, ExecuteCommand, GotoDefinition,
+        GotoTypeDefinition, HoverRequest, PrepareRenameRequest, RegisterCapability, Rename,
+        Request as _, ShowDocument, WillRenameFiles,
     },
-    request::{Completion, DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as _},
-    CompletionParams, CompletionResponse, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
-    DidOpenTextDocumentParams, DocumentSymbolParams, DocumentSymbolResponse, GotoDefinitionParams,
-    GotoDefinitionResponse, HoverParams, InitializeParams, InitializeResult, ServerCapabilities,
+    CompletionParams, CompletionResponse, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentSymbolParams,
+    DocumentSymbolResponse, FileChangeType, GotoDefinitionParams, GotoDefinitionResponse,
+    HoverParams, InitializeParams, InitializeResult, RenameFilesParams, ServerCapabilities,
     ServerInfo,
 };
+use std::cell::OnceCell;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Instant;
+
+/// 命令：验证并修复索引一致性
+pub const VERIFY_INDEX_COMMAND: &str = "spring.verifyIndex";
+
+/// 命令：整理文件中的路由处理器（参数为文件 URI）
+pub const ORGANIZE_ROUTES_COMMAND: &str = "spring.organizeRoutes";
+
+/// 命令：检查工作空间并重新生成诊断基线（`.spring-lsp/baseline.json`）
+pub const UPDATE_BASELINE_COMMAND: &str = "spring.updateBaseline";
+
+/// 命令：在编辑器中打开配置值所在的配置文件（参数为配置文件 URI 和值的位置范围）
+pub const SHOW_CONFIG_VALUE_COMMAND: &str = "spring.showConfigValue";
+
+/// 命令：打开项目健康摘要中某类问题的位置（参数为 `Cargo.toml` 的 URI 和问题类别，
+/// 返回该类别的所有位置，并请求客户端打开第一个位置）
+pub const SHOW_HEALTH_REPORT_COMMAND: &str = "spring.showHealthReport";
+
+/// 客户端 `window/showDocument` 请求的 ID
+const SHOW_DOCUMENT_REQUEST: &str = "spring-lsp/showDocument";
+
+/// 填充新建文件模板的 `workspace/applyEdit` 请求的 ID 前缀（后接文件 URI）
+const APPLY_TEMPLATE_REQUEST: &str = "spring-lsp/applyTemplate";
+
+/// 配置值代码透镜中显示的最大字符数
+const CONFIG_LENS_VALUE_LIMIT: usize = 40;
+
+/// 服务器支持的 `workspace/executeCommand` 命令列表
+const SUPPORTED_COMMANDS: &[&str] = &[
+    VERIFY_INDEX_COMMAND,
+    ORGANIZE_ROUTES_COMMAND,
+    UPDATE_BASELINE_COMMAND,
+    SHOW_CONFIG_VALUE_COMMAND,
+    SHOW_HEALTH_REPORT_COMMAND,
+    #[cfg(feature = "profiling")]
+    profiling::PROFILE_START_COMMAND,
+    #[cfg(feature = "profiling")]
+    profiling::PROFILE_STOP_COMMAND,
+];
+
+/// 补全缓存在性能报告中的名称
+const COMPLETION_CACHE: &str = "completion";
+
+/// 宏缓存在性能报告中的名称
+const MACRO_CACHE: &str = "macro";
+
+/// 条目级诊断缓存在性能报告中的名称（每个复用或重新检查的顶层函数记一次）
+const ITEM_CACHE: &str = "item-diagnostics";
+
+/// 用于检测插件版本变化的锁文件名
+const CARGO_LOCK: &str = "Cargo.lock";
+
+/// 文件监听的注册 ID
+const WATCH_FILES_REGISTRATION: &str = "spring-lsp/watchFiles";
+
+/// 正在运行的项目级依赖注入验证
+struct DependencyValidationRun {
+    /// spring/validateDependencies 请求 ID，任务完成或取消时响应
+    id: RequestId,
+    /// 验证任务
+    task: DiValidationTask,
+    /// 取消令牌（收到 `$/cancelRequest` 时取消）
+    token: CancellationToken,
+    /// 依赖注入验证器
+    validator: DependencyInjectionValidator,
+    /// 已发布的诊断数
+    diagnostics: usize,
+}
+
+/// 将扫描结果中的位置转换为 LSP Range
+pub(crate) fn route_location_range(
+    location: &crate::protocol::types::LocationResponse,
+) -> lsp_types::Range {
+    lsp_types::Range {
+        start: lsp_types::Position {
+            line: location.range.start.line,
+            character: location.range.start.character,
+        },
+        end: lsp_types::Position {
+            line: location.range.end.line,
+            character: location.range.end.character,
+        },
+    }
+}
+
+/// 判断文档是否为 OpenAPI 规范文件
+fn is_openapi_spec(uri: &lsp_types::Url) -> bool {
+    uri.to_file_path()
+        .is_ok_and(|path| OpenApiSpec::is_spec_file(&path))
+}
+
+/// 查找文档所属的项目根目录
+///
+/// 从文档所在目录向上查找包含 `Cargo.toml` 和 `src` 的目录，找不到时使用工作空间根目录
+pub(crate) fn find_project_root(
+    uri: &lsp_types::Url,
+    workspace_path: Option<&Path>,
+) -> Option<PathBuf> {
+    uri.to_file_path()
+        .ok()
+        .and_then(|path| {
+            path.ancestors()
+                .skip(1)
+                .find(|dir| dir.join("Cargo.toml").is_file() && dir.join("src").is_dir())
+                .map(|dir| dir.to_path_buf())
+        })
+        .or_else(|| workspace_path.map(Path::to_path_buf))
+}
+
+/// panic 负载中的消息（`panic!` 的参数为字符串时）
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// 读取文件内容（优先使用编辑器中打开的内容）
+pub(crate) fn read_file_content(documents: &DocumentManager, path: &Path) -> Option<String> {
+    documents.read_file(path).ok()
+}
+
+/// 项目 `src` 目录中的所有 Rust 文件（优先使用编辑器中打开的内容）
+pub(crate) fn crate_rust_sources(
+    documents: &DocumentManager,
+    project_root: &Path,
+) -> Vec<(lsp_types::Url, String)> {
+    walkdir::WalkDir::new(project_root.join("src"))
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|entry| {
+            let uri = lsp_types::Url::from_file_path(entry.path()).ok()?;
+            let content = read_file_content(documents, entry.path())?;
+            Some((uri, content))
+        })
+        .collect()
+}
+
+/// 读取项目的 `config/app.toml`（优先使用编辑器中打开的内容）
+pub(crate) fn read_app_config(
+    documents: &DocumentManager,
+    project_root: &Path,
+) -> Option<toml::Table> {
+    read_file_content(documents, &project_root.join("config").join("app.toml"))?
+        .parse()
+        .ok()
+}
+
+/// 分析文档中每个路由生效的中间件链
+///
+/// 路由器级中间件来自文档本身和所属项目（`project_root`）的其他文件（优先使用编辑器中打开的内容）
+pub(crate) fn route_middlewares(
+    documents: &Arc<DocumentManager>,
+    uri: &lsp_types::Url,
+    content: &str,
+    project_root: Option<&Path>,
+    rules: &[MiddlewareRule],
+) -> Vec<crate::scanner::middleware::RouteMiddlewares> {
+    use crate::scanner::middleware::{MiddlewareInfo, MiddlewareScanner, MiddlewareSource};
+
+    let scanner =
+        MiddlewareScanner::new().with_sources(SourceReader::overlay(Arc::clone(documents)));
+    let Ok(file) = scanner.analyze_file(uri, content) else {
+        return vec![];
+    };
+
+    let mut router_layers = project_root
+        .and_then(|root| scanner.scan_router_layers(root, Some(uri)).ok())
+        .unwrap_or_default();
+    router_layers.extend(file.router_layers);
+
+    file.routes
+        .into_iter()
+        .map(|mut route| {
+            route.middlewares.splice(
+                0..0,
+                router_layers.iter().map(|name| MiddlewareInfo {
+                    name: name.clone(),
+                    source: MiddlewareSource::Router,
+                }),
+            );
+            route.apply_rules(rules);
+            route
+        })
+        .collect()
+}
+
+/// 未实现处理器的代码透镜标题（如 `stub · GET /users`）
+fn stub_lens_title(handler: &crate::analysis::rust::handler_analyzer::HandlerInfo) -> String {
+    let routes = handler
+        .routes
+        .iter()
+        .map(|route| format!("{} {}", route.methods.join("|"), route.path))
+        .collect::<Vec<_>>();
+    format!("stub · {}", routes.join(", "))
+}
+
+/// 配置值代码透镜的标题（如 `= 8080 · app.toml`），过长的值被截断
+fn config_lens_title(source: &ConfigValueSource) -> String {
+    let value = if source.value.chars().count() > CONFIG_LENS_VALUE_LIMIT {
+        let truncated: String = source.value.chars().take(CONFIG_LENS_VALUE_LIMIT).collect();
+        format!("{}…", truncated)
+    } else {
+        source.value.clone()
+    };
+    format!("= {} · {}", value, source.file_name())
+}
+
+/// 配置项悬停提示中的值来源：每个定义了该配置项的配置文件一行，包括文件和行号、
+/// 生效条件（基础配置或 profile 覆盖）和值中引用的环境变量及其默认值
+fn config_origin_markdown(sources: &[ConfigValueSource], locale: Locale) -> String {
+    let mut markdown = localized(locale, "hover.origin", &[]);
+    for source in sources {
+        let condition = match &source.profile {
+            Some(profile) => localized(locale, "hover.origin.profile", &[profile]),
+            None => localized(locale, "hover.origin.base", &[]),
+        };
+        let mut value = format!("`{}`", source.value.replace('|', "\\|"));
+        for env_var in &source.env_vars {
+            value.push_str(&match &env_var.default {
+                Some(default) => localized(
+                    locale,
+                    "hover.origin.env-default",
+                    &[&env_var.name, &default.replace('|', "\\|")],
+                ),
+                None => localized(locale, "hover.origin.env-required", &[&env_var.name]),
+            });
+        }
+        markdown.push_str(&format!(
+            "| [{file}:{line}]({uri}#L{line}) | {condition} | {value} |\n",
+            file = source.file_name(),
+            line = source.line(),
+            uri = source.uri,
+        ));
+    }
+    if sources.iter().all(|source| source.profile.is_some()) {
+        markdown.push_str(&localized(locale, "hover.origin.profiles-only", &[]));
+    }
+    markdown
+}
+
+/// 读取 Cargo.lock 中的插件版本，文件不可读时视为没有依赖
+fn read_plugin_versions(path: &Path) -> PluginVersions {
+    std::fs::read_to_string(path)
+        .map(|content| PluginVersions::from_lock_file(&content))
+        .unwrap_or_default()
+}
+
+/// 文件重命名引起的模块路径变化
+struct ModuleRename {
+    /// 重命名前的文件 URI
+    old_uri: lsp_types::Url,
+    /// 重命名后的文件 URI
+    new_uri: lsp_types::Url,
+    /// 重命名前的模块路径
+    old_module: Vec<String>,
+    /// 重命名后的模块路径
+    new_module: Vec<String>,
+}
+
+/// 找出改变了模块路径的 Rust 文件重命名（按旧模块路径从长到短排序）
+fn module_renames(files: &[lsp_types::FileRename]) -> Vec<ModuleRename> {
+    let mut renames: Vec<ModuleRename> = files
+        .iter()
+        .filter_map(|file| {
+            let old_uri = lsp_types::Url::parse(&file.old_uri).ok()?;
+            let new_uri = lsp_types::Url::parse(&file.new_uri).ok()?;
+            let old_module = module_path::module_path(&old_uri.to_file_path().ok()?)?;
+            let new_module = module_path::module_path(&new_uri.to_file_path().ok()?)?;
+            (!old_module.is_empty() && !new_module.is_empty() && old_module != new_module)
+                .then_some(ModuleRename {
+                    old_uri,
+                    new_uri,
+                    old_module,
+                    new_module,
+                })
+        })
+        .collect();
+
+    // 先处理更长的路径，`crate::a::b` 的引用不会被 `crate::a` 的重命名覆盖
+    renames.sort_by_key(|rename| std::cmp::Reverse(rename.old_module.len()));
+    renames
+}
+
+/// 匹配 Rust 文件的文件操作过滤器
+fn rust_file_operation_options() -> lsp_types::FileOperationRegistrationOptions {
+    use lsp_types::{FileOperationFilter, FileOperationPattern, FileOperationPatternKind};
+
+    lsp_types::FileOperationRegistrationOptions {
+        filters: vec![FileOperationFilter {
+            scheme: Some("file".to_string()),
+            pattern: FileOperationPattern {
+                glob: "**/*.rs".to_string(),
+                matches: Some(FileOperationPatternKind::File),
+                options: None,
+            },
+        }],
+    }
+}
+
+/// 过滤诊断基线中已记录的诊断，没有基线或工作空间时原样返回
+fn apply_baseline(
+    baseline: Option<&Baseline>,
+    workspace_path: Option<&Path>,
+    uri: &lsp_types::Url,
+    diagnostics: Vec<lsp_types::Diagnostic>,
+) -> Vec<lsp_types::Diagnostic> {
+    match (baseline, workspace_path) {
+        (Some(baseline), Some(root)) => baseline.filter_uri(root, uri, diagnostics),
+        _ => diagnostics,
+    }
+}
 
 /// 服务器状态
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -88,6 +471,10 @@ pub struct LspServer {
     pub workspace_path: Option<std::path::PathBuf>,
     /// 文档管理器
     pub document_manager: Arc<DocumentManager>,
+    /// 与客户端协商的位置编码
+    position_encoding: PositionEncoding,
+    /// 客户端在初始化选项中指定的消息语言
+    locale: Locale,
     /// 错误处理器
     error_handler: ErrorHandler,
     /// 服务器配置
@@ -100,6 +487,8 @@ pub struct LspServer {
     pub toml_analyzer: Arc<TomlAnalyzer>,
     /// 宏分析器
     pub macro_analyzer: Arc<MacroAnalyzer>,
+    /// 路由处理器分析器
+    pub handler_analyzer: Arc<HandlerAnalyzer>,
     /// 路由导航器
     pub route_navigator: Arc<RouteNavigator>,
     /// 补全引擎
@@ -108,6 +497,48 @@ pub struct LspServer {
     pub diagnostic_engine: Arc<DiagnosticEngine>,
     /// 索引管理器
     pub index_manager: Arc<IndexManager>,
+    /// 生成代码目录的扫描缓存（`[index] generated_dirs`）
+    generated_code: GeneratedCodeCache,
+    /// 各项目 Cargo.lock 中的 spring 插件版本快照（用于 Schema 热重载）
+    plugin_versions: HashMap<PathBuf, PluginVersions>,
+    /// 已经把源文件加入符号索引的项目根目录
+    symbol_roots: HashSet<PathBuf>,
+    /// 客户端是否支持动态注册文件监听
+    watched_files_registration: bool,
+    /// 服务器端文件监听器（客户端不支持动态注册文件监听时使用）
+    file_watcher: Option<FileWatcher>,
+    /// 客户端对工作空间编辑的支持情况
+    workspace_edit_support: WorkspaceEditSupport,
+    /// 客户端是否支持 `workspace/applyEdit`（新建文件模板）
+    apply_edit_support: bool,
+    /// 客户端是否支持代码片段格式的补全（不支持时补全项转换为纯文本）
+    snippet_support: bool,
+    /// 正在运行的依赖注入验证任务
+    dependency_validation: Option<DependencyValidationRun>,
+    /// 最近一次依赖注入验证的诊断（文档重新分析时合并发布）
+    dependency_diagnostics: HashMap<lsp_types::Url, Vec<lsp_types::Diagnostic>>,
+    /// 工作空间级验证的诊断发布队列（分批限速发布，打开的文件优先）
+    diagnostic_publisher: DiagnosticPublisher,
+    /// 是否运行过依赖注入验证（配置文件修改时只在运行过后重新计算配置注入诊断）
+    dependencies_validated: bool,
+    /// 最近一次依赖注入验证的应用路径（之后编辑 Rust 文件时增量重新验证）
+    dependency_root: Option<std::path::PathBuf>,
+    /// 增量依赖注入验证器（缓存每个文件的分析结果）
+    incremental_di: IncrementalDiValidator,
+    /// 打开的配置文件最近一次解析成功的内容（用于找出修改过的配置节）
+    config_tables: HashMap<lsp_types::Url, toml::Table>,
+    /// 工作空间的诊断基线，基线中已记录的诊断不会发布
+    baseline: Option<Baseline>,
+    /// 工作空间的路由安全规则（`.spring-lsp/rules.toml`）
+    security_rules: Option<SecurityRules>,
+    /// 每个应用最近一次 spring/stats 请求的统计（用于计算变化趋势）
+    workspace_stats: HashMap<PathBuf, WorkspaceStats>,
+    /// 打开的 Rust 文件按顶层函数缓存的诊断（编辑时只重新检查变化的函数）
+    item_diagnostics: ItemDiagnosticsCache,
+    /// 跨文件诊断使用的项目扫描结果（按文件缓存，编辑时只更新修改的文件）
+    project_scans: ProjectScanCache,
+    /// 构建完整悬停提示的后台线程（无法启动时在分发线程上构建）
+    hover_worker: Option<HoverWorker>,
 }
 
 impl LspServer {
@@ -117,10 +548,13 @@ impl LspServer {
     pub fn start() -> Result<Self> {
         tracing::info!("Starting spring-lsp server");
 
-        // 通过标准输入输出创建 LSP 连接
-        let (connection, _io_threads) = Connection::stdio();
+        // 加载默认配置（在初始化时会从客户端获取工作空间路径并重新加载）
+        let config = ServerConfig::load(None);
 
-        Self::new_with_connection(connection)
+        // 通过标准输入输出创建 LSP 连接（使用容错的消息帧传输层）
+        let (connection, _io_threads) = transport::stdio(config.transport.max_message_size);
+
+        Self::new_with_connection(connection, config)
     }
 
     /// 为测试创建 LSP 服务器（不使用 stdio 连接）
@@ -129,14 +563,11 @@ impl LspServer {
         // 我们不会实际使用这个连接发送消息
         let (connection, _io_threads) = Connection::memory();
 
-        Self::new_with_connection(connection)
+        Self::new_with_connection(connection, ServerConfig::load(None))
     }
 
-    /// 使用给定连接创建服务器实例
-    fn new_with_connection(connection: Connection) -> Result<Self> {
-        // 加载默认配置（在初始化时会从客户端获取工作空间路径并重新加载）
-        let config = ServerConfig::load(None);
-
+    /// 使用给定连接和配置创建服务器实例
+    fn new_with_connection(connection: Connection, config: ServerConfig) -> Result<Self> {
         // 验证配置
         if let Err(e) = config.validate() {
             tracing::error!("Invalid configuration: {}", e);
@@ -173,6 +604,7 @@ impl LspServer {
 
         // 3. 宏分析器
         let macro_analyzer = Arc::new(MacroAnalyzer::new());
+        let handler_analyzer = Arc::new(HandlerAnalyzer::new());
 
         // 4. 路由导航器
         let route_navigator = Arc::new(RouteNavigator::new());
@@ -186,23 +618,60 @@ impl LspServer {
         // 7. 索引管理器
         let index_manager = Arc::new(IndexManager::new());
 
+        // 8. 增量依赖注入验证器（与服务器共享宏分析器的缓存）
+        let incremental_di = IncrementalDiValidator::new(macro_analyzer.clone());
+
+        // 9. 项目扫描缓存（优先使用编辑器中打开的内容）
+        let document_manager = Arc::new(DocumentManager::new());
+        let project_scans =
+            ProjectScanCache::new(SourceReader::overlay(Arc::clone(&document_manager)));
+
+        // 10. 悬停提示后台线程
+        let hover_worker = HoverWorker::spawn()
+            .map_err(|e| tracing::warn!("Failed to spawn hover thread: {}", e))
+            .ok();
+
         tracing::info!("All components initialized successfully");
 
         Ok(Self {
             connection,
             state: ServerState::Uninitialized,
             workspace_path: None,
-            document_manager: Arc::new(DocumentManager::new()),
+            document_manager,
+            position_encoding: PositionEncoding::default(),
+            locale: Locale::default(),
             error_handler: ErrorHandler::new(verbose),
             config,
             status: ServerStatus::new(),
             schema_provider,
             toml_analyzer,
             macro_analyzer,
+            handler_analyzer,
             route_navigator,
             completion_engine,
             diagnostic_engine,
             index_manager,
+            generated_code: GeneratedCodeCache::new(),
+            plugin_versions: HashMap::new(),
+            symbol_roots: HashSet::new(),
+            watched_files_registration: false,
+            file_watcher: None,
+            workspace_edit_support: WorkspaceEditSupport::default(),
+            apply_edit_support: false,
+            snippet_support: false,
+            dependency_validation: None,
+            dependency_diagnostics: HashMap::new(),
+            diagnostic_publisher: DiagnosticPublisher::default(),
+            dependencies_validated: false,
+            dependency_root: None,
+            incremental_di,
+            config_tables: HashMap::new(),
+            baseline: None,
+            security_rules: None,
+            workspace_stats: HashMap::new(),
+            item_diagnostics: ItemDiagnosticsCache::new(),
+            project_scans,
+            hover_worker,
         })
     }
 
@@ -242,6 +711,17 @@ impl LspServer {
         self.state = ServerState::Initialized;
         tracing::info!("LSP server initialized successfully");
 
+        // 监听 Cargo.lock、配置文件和源文件：插件版本变化时热重载 Schema，文件变化时增量重新验证
+        if let Err(e) = self.register_file_watchers() {
+            tracing::warn!("Failed to register file watchers: {}", e);
+        }
+        // 客户端不支持动态注册时由服务器监听（需要启用 file-watcher feature）
+        if !self.watched_files_registration {
+            if let Some(root) = &self.workspace_path {
+                self.file_watcher = FileWatcher::start(root);
+            }
+        }
+
         Ok(())
     }
 
@@ -259,33 +739,19 @@ impl LspServer {
             }
 
             // 接收消息
-            let msg = match self.connection.receiver.recv() {
+            let msg = match self.next_message() {
                 Ok(msg) => msg,
                 Err(e) => {
+                    // 通道断开意味着传输层已停止（客户端关闭了输入流），重试没有意义
                     let error = Error::MessageReceive(e.to_string());
-                    let result = self.error_handler.handle(&error);
-
-                    match result.action {
-                        RecoveryAction::RetryConnection => {
-                            tracing::info!("Attempting to recover connection...");
-                            // 短暂等待后继续
-                            std::thread::sleep(std::time::Duration::from_millis(100));
-                            continue;
-                        }
-                        RecoveryAction::Abort => {
-                            tracing::error!("Fatal error receiving message, shutting down");
-                            break;
-                        }
-                        _ => {
-                            tracing::warn!("Unexpected recovery action for message receive error");
-                            break;
-                        }
-                    }
+                    self.status.record_error();
+                    tracing::error!("Client connection closed: {}", error);
+                    break;
                 }
             };
 
-            // 处理消息，捕获错误以保持服务器运行
-            if let Err(e) = self.handle_message(msg) {
+            // 处理消息，捕获错误和 panic 以保持服务器运行
+            if let Err(e) = self.handle_message_guarded(msg) {
                 // 记录错误
                 self.status.record_error();
 
@@ -317,6 +783,107 @@ impl LspServer {
         Ok(())
     }
 
+    /// 接收下一条消息
+    ///
+    /// 有正在运行的依赖注入验证任务时不阻塞等待：没有消息就推进一个批次。
+    /// 任务在 crate 之间让出控制权，期间到达的请求（包括取消请求）能及时处理。
+    /// 诊断发布队列不为空时，最多等待到下一批可以发布的时间
+    fn next_message(&mut self) -> std::result::Result<Message, crossbeam_channel::RecvError> {
+        while self.dependency_validation.is_some() || !self.diagnostic_publisher.is_empty() {
+            match self.connection.receiver.try_recv() {
+                Ok(msg) => return Ok(msg),
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    return Err(crossbeam_channel::RecvError)
+                }
+                Err(crossbeam_channel::TryRecvError::Empty) => {}
+            }
+
+            if self.dependency_validation.is_some() {
+                if let Err(e) = self.step_dependency_validation() {
+                    self.status.record_error();
+                    tracing::error!("Dependency validation failed: {}", e);
+                }
+            } else {
+                let wait = self.diagnostic_publisher.wait_time(Instant::now());
+                match self.connection.receiver.recv_timeout(wait) {
+                    Ok(msg) => return Ok(msg),
+                    Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                        return Err(crossbeam_channel::RecvError)
+                    }
+                    Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
+                }
+            }
+
+            if let Err(e) = self.flush_diagnostics() {
+                self.status.record_error();
+                tracing::error!("Failed to publish diagnostics: {}", e);
+            }
+        }
+
+        let Some(watcher) = &self.file_watcher else {
+            return self.connection.receiver.recv();
+        };
+        // 服务器端监听到的文件变化转换为 didChangeWatchedFiles 通知
+        crossbeam_channel::select! {
+            recv(self.connection.receiver) -> msg => msg,
+            recv(watcher.receiver()) -> change => match change {
+                Ok(change) => Ok(Message::Notification(Notification::new(
+                    DidChangeWatchedFiles::METHOD.to_string(),
+                    watcher.collect(change),
+                ))),
+                Err(_) => {
+                    tracing::warn!("File watcher stopped");
+                    self.file_watcher = None;
+                    self.connection.receiver.recv()
+                }
+            },
+        }
+    }
+
+    /// 发布诊断队列中到期的一批文件
+    fn flush_diagnostics(&mut self) -> Result<()> {
+        for uri in self.diagnostic_publisher.next_batch(Instant::now()) {
+            self.diagnostic_engine.publish(&self.connection, &uri)?;
+        }
+        Ok(())
+    }
+
+    /// 将文件的诊断加入发布队列
+    fn queue_diagnostics(&mut self, uri: lsp_types::Url) {
+        let open = self.document_manager.with_document(&uri, |_| ()).is_some();
+        self.diagnostic_publisher.enqueue(uri, open);
+    }
+
+    /// 处理单个消息并捕获处理过程中的 panic
+    ///
+    /// panic 记录到错误恢复统计中；请求返回 `InternalError`，避免客户端一直等待响应
+    fn handle_message_guarded(&mut self, msg: Message) -> Result<()> {
+        let (method, request_id) = match &msg {
+            Message::Request(req) => (req.method.clone(), Some(req.id.clone())),
+            Message::Notification(not) => (not.method.clone(), None),
+            Message::Response(_) => return self.handle_message(msg),
+        };
+
+        let payload = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            self.handle_message(msg)
+        })) {
+            Ok(result) => return result,
+            Err(payload) => payload,
+        };
+        let message = panic_message(payload.as_ref());
+        self.status.record_panic();
+        self.status.record_method_error(&method);
+        tracing::error!("Handler for {} panicked: {}", method, message);
+        match request_id {
+            Some(id) => self.send_error_response(
+                id,
+                lsp_server::ErrorCode::InternalError as i32,
+                format!("Internal error while handling {}: {}", method, message),
+            ),
+            None => Ok(()),
+        }
+    }
+
     /// 处理单个消息
     fn handle_message(&mut self, msg: Message) -> Result<()> {
         match msg {
@@ -344,30 +911,71 @@ impl LspServer {
             return Ok(());
         }
 
+        // 保留请求 ID，用于在参数无效时返回错误响应
+        let id = req.id.clone();
+        let method = req.method.clone();
+        let start = std::time::Instant::now();
+        #[cfg(feature = "profiling")]
+        let _span = tracing::trace_span!("request", name = %method).entered();
+
         // 根据请求方法分发
-        match req.method.as_str() {
+        let result = match req.method.as_str() {
             // 智能补全请求
             Completion::METHOD => self.handle_completion(req),
             // 悬停提示请求
             HoverRequest::METHOD => self.handle_hover(req),
             // 定义跳转请求
             GotoDefinition::METHOD => self.handle_goto_definition(req),
+            // 类型定义跳转请求
+            GotoTypeDefinition::METHOD => self.handle_goto_type_definition(req),
             // 文档符号请求
             DocumentSymbolRequest::METHOD => self.handle_document_symbol(req),
+            // 代码透镜请求（未实现的处理器）
+            CodeLensRequest::METHOD => self.handle_code_lens(req),
+            // 代码操作请求（快速修复）
+            CodeActionRequest::METHOD => self.handle_code_action(req),
+            // 颜色配置值的色块
+            DocumentColor::METHOD => self.handle_document_color(req),
+            // 颜色配置值的可选写法
+            ColorPresentationRequest::METHOD => self.handle_color_presentation(req),
             // 工作空间符号请求
             "workspace/symbol" => self.handle_workspace_symbol(req),
+            // 执行命令请求
+            ExecuteCommand::METHOD => self.handle_execute_command(req),
+            // 重命名前检查光标处的 spring 构造能否重命名
+            PrepareRenameRequest::METHOD => self.handle_prepare_rename(req),
+            // 重命名路径参数和组件名称
+            Rename::METHOD => self.handle_rename(req),
+            // 文件重命名前更新模块路径引用
+            WillRenameFiles::METHOD => self.handle_will_rename_files(req),
             // 状态查询请求
-            "spring-lsp/status" => self.handle_status_query(req),
+            extensions::STATUS_REQUEST => self.handle_status_query(req),
+            // 自定义请求：获取性能报告
+            extensions::PERF_REPORT_REQUEST => self.handle_perf_report_request(req),
             // 自定义请求：获取组件列表
-            "spring/components" => self.handle_components_request(req),
+            extensions::COMPONENTS_REQUEST => self.handle_components_request(req),
             // 自定义请求：获取路由列表
-            "spring/routes" => self.handle_routes_request(req),
+            extensions::ROUTES_REQUEST => self.handle_routes_request(req),
+            // 中间件链查询
+            extensions::MIDDLEWARES_REQUEST => self.handle_middlewares_request(req),
+            // 自定义请求：生成路由的请求示例
+            extensions::REQUEST_SAMPLE_REQUEST => self.handle_request_sample_request(req),
+            // 自定义请求：展开整个文件中的宏
+            extensions::EXPAND_ALL_MACROS_REQUEST => self.handle_expand_all_macros_request(req),
             // 自定义请求：获取任务列表
-            "spring/jobs" => self.handle_jobs_request(req),
+            extensions::JOBS_REQUEST => self.handle_jobs_request(req),
             // 自定义请求：获取插件列表
-            "spring/plugins" => self.handle_plugins_request(req),
+            extensions::PLUGINS_REQUEST => self.handle_plugins_request(req),
             // 自定义请求：获取配置列表
-            "spring/configurations" => self.handle_configurations_request(req),
+            extensions::CONFIGURATIONS_REQUEST => self.handle_configurations_request(req),
+            // 自定义请求：获取工作空间统计
+            extensions::STATS_REQUEST => self.handle_stats_request(req),
+            // 项目级依赖注入验证（分批执行，可取消）
+            extensions::VALIDATE_DEPENDENCIES_REQUEST => {
+                self.handle_validate_dependencies_request(req)
+            }
+            // 诊断规则目录
+            extensions::LIST_RULES_REQUEST => self.handle_list_rules_request(req),
             _ => {
                 tracing::warn!("Unhandled request method: {}", req.method);
                 // 返回方法未实现错误
@@ -377,15 +985,60 @@ impl LspServer {
                     format!("Method not found: {}", req.method),
                 )
             }
-        }?;
+        };
+
+        if result.is_err() {
+            self.status.record_method_error(&method);
+        }
+        let result = match result {
+            // 参数反序列化失败时返回 InvalidParams，避免客户端一直等待响应
+            Err(Error::Json(e)) => {
+                tracing::warn!("Invalid params for request {:?}: {}", id, e);
+                self.status.record_error();
+                self.send_error_response(
+                    id,
+                    lsp_server::ErrorCode::InvalidParams as i32,
+                    format!("Invalid params: {}", e),
+                )
+            }
+            result => result,
+        };
 
-        Ok(())
+        self.status.record_latency(&method, start.elapsed());
+
+        result
     }
 
     /// 处理通知
     fn handle_notification(&mut self, not: Notification) -> Result<()> {
         tracing::debug!("Received notification: {}", not.method);
 
+        let method = not.method.clone();
+        let start = std::time::Instant::now();
+        #[cfg(feature = "profiling")]
+        let _span = tracing::trace_span!("notification", name = %method).entered();
+
+        let result = self.dispatch_notification(not);
+        if result.is_err() {
+            self.status.record_method_error(&method);
+        }
+        let result = match result {
+            // 通知没有响应，参数无效时只记录日志，不能让畸形消息终止服务器
+            Err(Error::Json(e)) => {
+                tracing::warn!("Invalid params for notification {}: {}", method, e);
+                self.status.record_error();
+                Ok(())
+            }
+            result => result,
+        };
+
+        self.status.record_latency(&method, start.elapsed());
+
+        result
+    }
+
+    /// 根据方法分发通知
+    fn dispatch_notification(&mut self, not: Notification) -> Result<()> {
         match not.method.as_str() {
             DidOpenTextDocument::METHOD => {
                 let params: DidOpenTextDocumentParams = serde_json::from_value(not.params)?;
@@ -399,6 +1052,22 @@ impl LspServer {
                 let params: DidCloseTextDocumentParams = serde_json::from_value(not.params)?;
                 self.handle_did_close(params)?;
             }
+            DidChangeWatchedFiles::METHOD => {
+                let params: DidChangeWatchedFilesParams = serde_json::from_value(not.params)?;
+                self.handle_did_change_watched_files(params)?;
+            }
+            DidCreateFiles::METHOD => {
+                let params: lsp_types::CreateFilesParams = serde_json::from_value(not.params)?;
+                self.handle_did_create_files(params)?;
+            }
+            DidRenameFiles::METHOD => {
+                let params: RenameFilesParams = serde_json::from_value(not.params)?;
+                self.handle_did_rename_files(params)?;
+            }
+            Cancel::METHOD => {
+                let params: lsp_types::CancelParams = serde_json::from_value(not.params)?;
+                self.handle_cancel_request(params);
+            }
             Exit::METHOD => {
                 tracing::info!("Received exit notification");
                 self.state = ServerState::ShuttingDown;
@@ -422,6 +1091,8 @@ impl LspServer {
             doc.text,
             doc.language_id.clone(),
         );
+        self.project_scans.update(&doc.uri);
+        self.sync_dependency_input(&doc.uri);
 
         // 更新状态
         self.status.increment_document_count();
@@ -429,6 +1100,9 @@ impl LspServer {
         // 触发文档分析和诊断
         self.analyze_document(&doc.uri, &doc.language_id)?;
 
+        // 记录配置文件的内容，之后的修改与它比较
+        self.changed_config_sections(&doc.uri);
+
         Ok(())
     }
 
@@ -440,12 +1114,21 @@ impl LspServer {
 
         self.document_manager
             .change(&uri, version, params.content_changes);
+        self.completion_engine.invalidate(&uri);
+        self.project_scans.update(&uri);
+        self.sync_dependency_input(&uri);
+
+        // 运行过依赖注入验证时，只重新验证修改过的 Rust 文件影响的部分
+        self.revalidate_dependencies_incrementally(&uri)?;
 
         // 触发增量分析和诊断
         if let Some(doc) = self.document_manager.get(&uri) {
             self.analyze_document(&uri, &doc.language_id)?;
         }
 
+        // 配置文件修改后重新验证依赖修改过的配置节的 Rust 文件
+        self.revalidate_config_dependents(&uri)?;
+
         Ok(())
     }
 
@@ -455,6 +1138,11 @@ impl LspServer {
         tracing::info!("Document closed: {}", uri);
 
         self.document_manager.close(&uri);
+        self.completion_engine.invalidate(&uri);
+        self.project_scans.update(&uri);
+        self.sync_dependency_input(&uri);
+        self.config_tables.remove(&uri);
+        self.item_diagnostics.remove(&uri);
 
         // 更新状态
         self.status.decrement_document_count();
@@ -466,230 +1154,339 @@ impl LspServer {
         Ok(())
     }
 
-    /// 处理智能补全请求
-    fn handle_completion(&mut self, req: Request) -> Result<()> {
-        tracing::debug!("Handling completion request");
-
-        let params: CompletionParams = serde_json::from_value(req.params)?;
-        self.status.record_completion();
+    /// Rust 文件修改后增量重新验证依赖注入
+    ///
+    /// 只在运行过项目级验证且没有正在运行的验证任务时执行：内容没有变化的文件复用缓存的分析结果，
+    /// 只重新发布诊断发生变化的文件（修改的文件本身由调用方重新分析）
+    fn revalidate_dependencies_incrementally(&mut self, uri: &lsp_types::Url) -> Result<()> {
+        if self.dependency_validation.is_some() || self.dependency_root.is_none() {
+            return Ok(());
+        }
+        let is_rust_file = uri
+            .to_file_path()
+            .is_ok_and(|path| path.extension().is_some_and(|ext| ext == "rs"));
+        if !is_rust_file {
+            return Ok(());
+        }
 
-        let response = self.document_manager.with_document(
-            &params.text_document_position.text_document.uri,
-            |doc| {
-                // 根据文件类型选择补全策略
-                match doc.language_id.as_str() {
-                    "toml" => {
-                        if let Ok(toml_doc) = self.toml_analyzer.parse(&doc.content) {
-                            self.completion_engine.complete_toml_document(
-                                &toml_doc,
-                                params.text_document_position.position,
-                            )
-                        } else {
-                            vec![]
-                        }
-                    }
-                    "rust" => {
-                        // TODO: 实现 Rust 补全
-                        vec![]
-                    }
-                    _ => vec![],
-                }
-            },
-        );
+        self.update_dependency_diagnostics(Some(uri))
+    }
 
-        let result = match response {
-            Some(completions) => serde_json::to_value(CompletionResponse::Array(completions))?,
-            None => serde_json::Value::Null,
+    /// 把文件的最新内容交给依赖注入验证（正在运行的验证任务或增量验证器）
+    ///
+    /// 优先使用编辑器中打开的内容，未打开时从磁盘读取，文件不存在时从验证中移除。
+    /// 只处理验证过的 crate 中的源文件和配置文件，返回文件是否属于这些 crate
+    fn sync_dependency_input(&mut self, uri: &lsp_types::Url) -> bool {
+        let content = self
+            .document_manager
+            .with_document(uri, |doc| doc.content.clone())
+            .or_else(|| std::fs::read_to_string(uri.to_file_path().ok()?).ok());
+        let incremental = match &mut self.dependency_validation {
+            Some(run) => run.task.incremental_mut(),
+            None => &mut self.incremental_di,
         };
+        incremental.set_file(uri, content.as_deref())
+    }
 
-        let response = Response {
-            id: req.id,
-            result: Some(result),
-            error: None,
-        };
+    /// 增量重新计算项目的依赖注入诊断，重新发布诊断发生变化的文件
+    ///
+    /// 增量验证器中的文件列表和内容由文件变化通知更新（[`Self::sync_dependency_input`]），
+    /// 这里不重新遍历工作空间。`edited` 是编辑器中修改的文件，由调用方重新分析，这里不重复发布
+    fn update_dependency_diagnostics(&mut self, edited: Option<&lsp_types::Url>) -> Result<()> {
+        let validator = DependencyInjectionValidator::new((*self.index_manager).clone())
+            .with_locale(self.locale);
+        let diagnostics: HashMap<_, Vec<_>> = self
+            .incremental_di
+            .validate(&validator, &self.toml_analyzer)
+            .into_iter()
+            .map(|(file, diagnostics)| {
+                let diagnostics: Vec<_> = diagnostics
+                    .into_iter()
+                    .filter(|diag| match &diag.code {
+                        Some(lsp_types::NumberOrString::String(code)) => {
+                            !self.config.diagnostics.is_disabled(code)
+                        }
+                        _ => true,
+                    })
+                    .collect();
+                (file, diagnostics)
+            })
+            .filter(|(_, diagnostics)| !diagnostics.is_empty())
+            .collect();
+        tracing::debug!(
+            "Incremental dependency validation after editing {:?} ({:?})",
+            edited.map(|uri| uri.as_str()),
+            self.incremental_di.stats()
+        );
 
-        self.connection
-            .sender
-            .send(Message::Response(response))
-            .map_err(|e| Error::MessageSend(e.to_string()))?;
+        let changed: BTreeSet<_> = diagnostics
+            .keys()
+            .chain(self.dependency_diagnostics.keys())
+            .filter(|file| diagnostics.get(*file) != self.dependency_diagnostics.get(*file))
+            .filter(|file| Some(*file) != edited)
+            .cloned()
+            .collect();
+        self.dependency_diagnostics = diagnostics;
+        for file in changed {
+            self.republish_diagnostics(&file)?;
+        }
 
+        self.enforce_memory_budget();
         Ok(())
     }
 
-    /// 处理悬停提示请求
-    fn handle_hover(&mut self, req: Request) -> Result<()> {
-        tracing::debug!("Handling hover request");
-
-        let params: HoverParams = serde_json::from_value(req.params)?;
-        self.status.record_hover();
+    /// 估算的内存占用
+    fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            documents: self.document_manager.memory_usage(),
+            sources: self.incremental_di.source_bytes(),
+            parsed: self.incremental_di.parsed_bytes(),
+            budget: self.config.memory.budget_bytes().unwrap_or(0),
+        }
+    }
 
-        let response = self.document_manager.with_document(
-            &params.text_document_position_params.text_document.uri,
-            |doc| {
-                // 根据文件类型选择分析器
-                match doc.language_id.as_str() {
-                    "toml" => {
-                        if let Ok(toml_doc) = self.toml_analyzer.parse(&doc.content) {
-                            self.toml_analyzer
-                                .hover(&toml_doc, params.text_document_position_params.position)
-                        } else {
-                            None
-                        }
-                    }
-                    "rust" => {
-                        // TODO: 实现 Rust 悬停提示
-                        None
-                    }
-                    _ => None,
-                }
-            },
-        );
+    /// 估算的内存占用超出配置的预算时释放缓存
+    fn enforce_memory_budget(&mut self) {
+        if let Some(budget) = self.config.memory.budget_bytes() {
+            self.evict_caches(budget);
+        }
+    }
 
-        let result = match response {
-            Some(Some(hover)) => serde_json::to_value(hover)?,
-            _ => serde_json::Value::Null,
+    /// 按顺序释放缓存，直到内存占用不超过 `budget`：
+    /// 1. 依赖注入验证保留的解析结果（提取的服务和诊断仍然保留）
+    /// 2. 已关闭文档的内容和宏提取缓存
+    fn evict_caches(&mut self, budget: usize) {
+        let usage = MemoryUsage {
+            budget,
+            ..self.memory_usage()
         };
+        if !usage.exceeds_budget() {
+            return;
+        }
 
-        let response = Response {
-            id: req.id,
-            result: Some(result),
-            error: None,
+        let files = self.incremental_di.evict_parsed();
+        tracing::info!(
+            "Memory usage {} bytes exceeds budget {} bytes: evicted parsed syntax of {} files ({} bytes)",
+            usage.total(),
+            budget,
+            files,
+            usage.parsed
+        );
+        let usage = MemoryUsage {
+            budget,
+            ..self.memory_usage()
         };
+        if !usage.exceeds_budget() {
+            return;
+        }
 
-        self.connection
-            .sender
-            .send(Message::Response(response))
-            .map_err(|e| Error::MessageSend(e.to_string()))?;
-
-        Ok(())
+        let open: HashSet<lsp_types::Url> = self.document_manager.uris().into_iter().collect();
+        let freed = self.incremental_di.evict_sources(|uri| open.contains(uri));
+        let macros = self.macro_analyzer.cache_stats().entries;
+        self.macro_analyzer.clear_cache();
+        tracing::info!(
+            "Evicted content of closed documents ({} bytes) and {} cached macro extractions",
+            freed,
+            macros
+        );
+        let usage = MemoryUsage {
+            budget,
+            ..self.memory_usage()
+        };
+        if usage.exceeds_budget() {
+            tracing::warn!(
+                "Memory usage {} bytes still exceeds budget {} bytes after eviction",
+                usage.total(),
+                budget
+            );
+        }
     }
 
-    /// 处理定义跳转请求
-    fn handle_goto_definition(&mut self, req: Request) -> Result<()> {
-        tracing::debug!("Handling goto definition request");
-
-        let _params: GotoDefinitionParams = serde_json::from_value(req.params)?;
+    /// 比较配置文件与上一次解析成功的内容，返回修改过（包括新增和删除）的顶层配置节
+    ///
+    /// 只处理 `config` 目录中的 TOML 文件；内容无法解析时保留上一次的内容，返回空列表
+    fn changed_config_sections(&mut self, uri: &lsp_types::Url) -> Vec<String> {
+        let is_config_file = uri.to_file_path().is_ok_and(|path| {
+            path.extension().is_some_and(|ext| ext == "toml")
+                && path
+                    .parent()
+                    .and_then(|dir| dir.file_name())
+                    .is_some_and(|name| name == "config")
+        });
+        if !is_config_file {
+            return Vec::new();
+        }
 
-        // TODO: 实现定义跳转逻辑
-        let result = GotoDefinitionResponse::Array(vec![]);
+        let Some(Ok(table)) = self
+            .document_manager
+            .with_document(uri, |doc| doc.content.parse::<toml::Table>())
+        else {
+            return Vec::new();
+        };
 
-        let response = Response {
-            id: req.id,
-            result: Some(serde_json::to_value(result)?),
-            error: None,
+        let Some(previous) = self.config_tables.insert(uri.clone(), table) else {
+            return Vec::new();
         };
+        let current = &self.config_tables[uri];
+        previous
+            .keys()
+            .chain(current.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|key| previous.get(*key) != current.get(*key))
+            .cloned()
+            .collect()
+    }
 
-        self.connection
-            .sender
-            .send(Message::Response(response))
-            .map_err(|e| Error::MessageSend(e.to_string()))?;
+    /// 配置文件修改后重新验证受影响的 Rust 文件
+    ///
+    /// 通过配置前缀到配置结构体的反向索引找到映射到修改过的配置节的结构体，
+    /// 重新发布定义这些结构体的文件和注入了它们的服务所在文件的诊断（不重新验证整个项目）。
+    /// 运行过依赖注入验证时，同时重新计算这些服务的配置注入诊断
+    fn revalidate_config_dependents(&mut self, uri: &lsp_types::Url) -> Result<()> {
+        use crate::scanner::config::ConfigScanner;
 
-        Ok(())
-    }
+        let changed = self.changed_config_sections(uri);
+        if changed.is_empty() {
+            return Ok(());
+        }
 
-    /// 分析文档并生成诊断
-    pub fn analyze_document(&mut self, uri: &lsp_types::Url, language_id: &str) -> Result<()> {
-        tracing::debug!("Analyzing document: {} ({})", uri, language_id);
+        let files = self.crate_sources(uri);
+        let index = ConfigScanner::new().build_prefix_index(&files);
+        let structs: Vec<_> = changed
+            .iter()
+            .flat_map(|prefix| index.structs(prefix))
+            .collect();
+        if structs.is_empty() {
+            return Ok(());
+        }
+        tracing::debug!(
+            "Config sections {:?} changed in {}, revalidating {} config struct(s)",
+            changed,
+            uri,
+            structs.len()
+        );
 
-        // 清除旧的诊断
-        self.diagnostic_engine.clear(uri);
+        // 注入了这些配置的服务（先按名称过滤文件，避免解析无关的文件）
+        let names: HashSet<&str> = structs.iter().map(|s| s.name.as_str()).collect();
+        let rust_docs: Vec<_> = files
+            .iter()
+            .filter(|(_, content)| names.iter().any(|name| content.contains(name)))
+            .filter_map(|(file, content)| {
+                let doc = self
+                    .macro_analyzer
+                    .parse(file.clone(), content.clone())
+                    .ok()?;
+                self.macro_analyzer.extract_macros(doc).ok()
+            })
+            .collect();
+        let validator = DependencyInjectionValidator::new((*self.index_manager).clone())
+            .with_locale(self.locale);
+        let services = validator.extract_services(&rust_docs);
+        let dependents: BTreeSet<_> = services
+            .values()
+            .filter(|service| service.config_types().any(|ty| names.contains(ty)))
+            .map(|service| service.location.uri.clone())
+            .collect();
 
-        let diagnostics = self
-            .document_manager
-            .with_document(uri, |doc| {
-                match language_id {
-                    "toml" => {
-                        // TOML 文档分析
-                        match self.toml_analyzer.parse(&doc.content) {
-                            Ok(toml_doc) => {
-                                let mut diagnostics = Vec::new();
-
-                                // 配置验证
-                                let validation_diagnostics = self.toml_analyzer.validate(&toml_doc);
-                                diagnostics.extend(validation_diagnostics);
-
-                                diagnostics
-                            }
-                            Err(e) => {
-                                // 解析错误 - 显示详细错误信息
-                                tracing::error!("TOML parse error: {}", e);
-                                vec![lsp_types::Diagnostic {
-                                    range: lsp_types::Range {
-                                        start: lsp_types::Position {
-                                            line: 0,
-                                            character: 0,
-                                        },
-                                        end: lsp_types::Position {
-                                            line: 0,
-                                            character: 0,
-                                        },
-                                    },
-                                    severity: Some(lsp_types::DiagnosticSeverity::ERROR),
-                                    code: Some(lsp_types::NumberOrString::String(
-                                        "parse_error".to_string(),
-                                    )),
-                                    code_description: None,
-                                    source: Some("spring-lsp".to_string()),
-                                    message: format!("TOML parse error: {}", e),
-                                    related_information: None,
-                                    tags: None,
-                                    data: None,
-                                }]
-                            }
-                        }
-                    }
-                    "rust" => {
-                        // Rust 文档分析
-                        // TODO: 实现完整的 Rust 分析
-                        vec![]
-                    }
-                    _ => {
-                        tracing::debug!("Unsupported language: {}", language_id);
-                        vec![]
-                    }
+        if self.dependencies_validated && !dependents.is_empty() {
+            let is_config_not_found = |diag: &lsp_types::Diagnostic| {
+                diag.code
+                    == Some(lsp_types::NumberOrString::String(
+                        CONFIG_NOT_FOUND.to_string(),
+                    ))
+            };
+            // 修改的配置文件可能还没有保存到磁盘
+            let mut toml_docs = self.crate_config_docs(uri);
+            if !toml_docs.iter().any(|(config_uri, _)| config_uri == uri) {
+                if let Some(Ok(doc)) = self
+                    .document_manager
+                    .with_document(uri, |doc| self.toml_analyzer.parse(&doc.content))
+                {
+                    toml_docs.push((uri.clone(), doc));
                 }
-            })
-            .unwrap_or_default();
+            }
 
-        // 过滤被禁用的诊断
-        let filtered_diagnostics: Vec<_> = diagnostics
-            .into_iter()
-            .filter(|diag| {
-                if let Some(lsp_types::NumberOrString::String(code)) = &diag.code {
-                    !self.config.diagnostics.is_disabled(code)
-                } else {
-                    true
+            for file in &dependents {
+                let mut diagnostics = self.dependency_diagnostics.remove(file).unwrap_or_default();
+                diagnostics.retain(|diag| !is_config_not_found(diag));
+                if !self.config.diagnostics.is_disabled(CONFIG_NOT_FOUND) {
+                    diagnostics.extend(
+                        services
+                            .values()
+                            .filter(|service| &service.location.uri == file)
+                            .flat_map(|service| validator.validate_service(service, &toml_docs))
+                            .filter(is_config_not_found),
+                    );
                 }
-            })
-            .collect();
-
-        // 添加诊断
-        for diagnostic in filtered_diagnostics {
-            self.diagnostic_engine.add(uri.clone(), diagnostic);
+                if !diagnostics.is_empty() {
+                    self.dependency_diagnostics
+                        .insert(file.clone(), diagnostics);
+                }
+            }
         }
 
-        // 发布诊断
-        let _ = self.diagnostic_engine.publish(&self.connection, uri);
-        self.status.record_diagnostic();
+        let affected: BTreeSet<_> = structs
+            .iter()
+            .map(|s| s.uri.clone())
+            .chain(dependents)
+            .collect();
+        for file in &affected {
+            self.republish_diagnostics(file)?;
+        }
 
         Ok(())
     }
 
-    /// 处理状态查询请求
+    /// 光标处的 spring 构造能否重命名（参见 [`rename::prepare_rename`]）
     ///
-    /// 返回服务器的运行状态和性能指标
-    fn handle_status_query(&self, req: Request) -> Result<()> {
-        tracing::debug!("Handling status query request");
+    /// TOML 文档中 Schema 定义的配置节和配置项不能重命名，其余 TOML 位置返回 `None`
+    fn rename_target(
+        &self,
+        uri: &lsp_types::Url,
+        position: lsp_types::Position,
+    ) -> Option<std::result::Result<RenameTarget, String>> {
+        self.document_manager
+            .with_document(uri, |doc| match doc.language_id.as_str() {
+                "rust" => rename::prepare_rename(&doc.mapper(), position, self.locale),
+                "toml" => {
+                    let (toml_doc, _) = self.toml_analyzer.parse_recovering(&doc.content);
+                    self.toml_analyzer
+                        .rename_rejection(&toml_doc, position)
+                        .map(Err)
+                }
+                _ => None,
+            })
+            .flatten()
+    }
 
-        let metrics = self.status.get_metrics();
-        let result = serde_json::to_value(metrics)?;
+    /// 处理 textDocument/prepareRename 请求
+    ///
+    /// 可以重命名时返回名称的范围；HTTP 方法、spring 宏、Schema 定义的配置键等
+    /// 不能重命名时返回带有原因的错误；其他位置返回 null，由 rust-analyzer 等处理
+    fn handle_prepare_rename(&self, req: Request) -> Result<()> {
+        let params: lsp_types::TextDocumentPositionParams = serde_json::from_value(req.params)?;
+
+        let result = match self.rename_target(&params.text_document.uri, params.position) {
+            Some(Err(reason)) => {
+                return self.send_error_response(
+                    req.id,
+                    lsp_server::ErrorCode::RequestFailed as i32,
+                    reason,
+                );
+            }
+            Some(Ok(target)) => Some(lsp_types::PrepareRenameResponse::RangeWithPlaceholder {
+                range: target.range(),
+                placeholder: target.name().to_string(),
+            }),
+            None => None,
+        };
 
         let response = Response {
             id: req.id,
-            result: Some(result),
+            result: Some(serde_json::to_value(result)?),
             error: None,
         };
-
         self.connection
             .sender
             .send(Message::Response(response))
@@ -698,52 +1495,50 @@ impl LspServer {
         Ok(())
     }
 
-    /// 处理 spring/routes 请求
+    /// 处理 textDocument/rename 请求
     ///
-    /// 扫描项目中的所有路由并返回路由列表
-    fn handle_routes_request(&self, req: Request) -> Result<()> {
-        tracing::info!("Handling spring/routes request");
-
-        use crate::scanner::route::{RouteScanner, RoutesRequest, RoutesResponse};
-
-        // 解析请求参数
-        let params: RoutesRequest = serde_json::from_value(req.params)?;
-        let project_path = std::path::Path::new(&params.app_path);
-
-        tracing::info!("Scanning routes in: {:?}", project_path);
-
-        // 创建路由扫描器
-        let scanner = RouteScanner::new();
-
-        // 扫描路由
-        let routes = match scanner.scan_routes(project_path) {
-            Ok(routes) => {
-                tracing::info!("Successfully scanned {} routes", routes.len());
-                routes
+    /// 路径参数在所在处理器中重命名（路由路径和处理器中的绑定）；
+    /// 组件名称在整个 crate 的 `#[inject(component = "...")]` 中重命名
+    fn handle_rename(&self, req: Request) -> Result<()> {
+        let params: lsp_types::RenameParams = serde_json::from_value(req.params)?;
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        let edit = match self.rename_target(&uri, position) {
+            Some(Ok(target)) => {
+                if let Err(reason) = target.validate_name(&params.new_name, self.locale) {
+                    return self.send_error_response(
+                        req.id,
+                        lsp_server::ErrorCode::InvalidParams as i32,
+                        reason,
+                    );
+                }
+                match self.rename_edit(&uri, &target, &params.new_name) {
+                    Ok(edit) => Some(edit),
+                    Err(e) => {
+                        return self.send_error_response(
+                            req.id,
+                            lsp_server::ErrorCode::RequestFailed as i32,
+                            e.to_string(),
+                        );
+                    }
+                }
             }
-            Err(e) => {
-                tracing::error!("Failed to scan routes: {}", e);
-                // 返回空列表而不是错误
-                Vec::new()
+            Some(Err(reason)) => {
+                return self.send_error_response(
+                    req.id,
+                    lsp_server::ErrorCode::RequestFailed as i32,
+                    reason,
+                );
             }
+            None => None,
         };
 
-        // 构建响应
-        let response_data = RoutesResponse { routes };
-
-        tracing::info!(
-            "Sending response with {} routes",
-            response_data.routes.len()
-        );
-
-        let result = serde_json::to_value(response_data)?;
-
         let response = Response {
             id: req.id,
-            result: Some(result),
+            result: Some(serde_json::to_value(edit)?),
             error: None,
         };
-
         self.connection
             .sender
             .send(Message::Response(response))
@@ -752,52 +1547,66 @@ impl LspServer {
         Ok(())
     }
 
-    /// 处理 spring/components 请求
-    ///
-    /// 扫描项目中的所有组件并返回组件列表
-    fn handle_components_request(&self, req: Request) -> Result<()> {
-        tracing::info!("Handling spring/components request");
-
-        use crate::scanner::component::{ComponentScanner, ComponentsRequest, ComponentsResponse};
-
-        // 解析请求参数
-        let params: ComponentsRequest = serde_json::from_value(req.params)?;
-        let project_path = std::path::Path::new(&params.app_path);
-
-        tracing::info!("Scanning components in: {:?}", project_path);
-        tracing::info!("Project path exists: {}", project_path.exists());
-        tracing::info!("Project path is dir: {}", project_path.is_dir());
+    /// 构建重命名的工作空间编辑
+    fn rename_edit(
+        &self,
+        uri: &lsp_types::Url,
+        target: &RenameTarget,
+        new_name: &str,
+    ) -> std::result::Result<lsp_types::WorkspaceEdit, WorkspaceEditError> {
+        let mut builder = WorkspaceEditBuilder::new(&self.document_manager);
+        match target {
+            RenameTarget::PathParam { name, handler, .. } => {
+                let content = self
+                    .document_manager
+                    .with_document(uri, |doc| doc.content.clone())
+                    .unwrap_or_default();
+                for edit in
+                    rename::path_param_edits(&self.mapper(&content), *handler, name, new_name)
+                {
+                    builder.edit(uri, None, edit)?;
+                }
+            }
+            RenameTarget::ComponentName { name, .. } => {
+                // 文档不在项目中时只重命名当前文档
+                let mut sources = self.crate_sources(uri);
+                if !sources.iter().any(|(source, _)| source == uri) {
+                    if let Some(content) = self
+                        .document_manager
+                        .with_document(uri, |doc| doc.content.clone())
+                    {
+                        sources.push((uri.clone(), content));
+                    }
+                }
+                for (source, content) in sources {
+                    for edit in rename::component_name_edits(&self.mapper(&content), name, new_name)
+                    {
+                        builder.edit(&source, None, edit)?;
+                    }
+                }
+            }
+        }
+        builder.build(self.workspace_edit_support)
+    }
 
-        // 创建组件扫描器
-        let scanner = ComponentScanner::new();
+    /// 处理 workspace/willRenameFiles 请求
+    ///
+    /// 重命名或移动 Rust 文件会改变它的模块路径，返回把 crate 中 `crate::旧路径`
+    /// 引用改为 `crate::新路径` 的工作空间编辑；没有需要更新的引用时返回 null
+    fn handle_will_rename_files(&self, req: Request) -> Result<()> {
+        let params: RenameFilesParams = serde_json::from_value(req.params)?;
 
-        // 扫描组件
-        let components = match scanner.scan_components(project_path) {
-            Ok(components) => {
-                tracing::info!("Successfully scanned {} components", components.len());
-                components
-            }
+        let edit = match self.module_rename_edit(&module_renames(&params.files)) {
+            Ok(edit) => edit,
             Err(e) => {
-                tracing::error!("Failed to scan components: {}", e);
-                tracing::error!("Error details: {:?}", e);
-                // 返回空列表而不是错误
-                Vec::new()
+                tracing::warn!("Failed to update module references: {}", e);
+                None
             }
         };
 
-        // 构建响应
-        let response_data = ComponentsResponse { components };
-
-        tracing::info!(
-            "Sending response with {} components",
-            response_data.components.len()
-        );
-
-        let result = serde_json::to_value(response_data)?;
-
         let response = Response {
             id: req.id,
-            result: Some(result),
+            result: Some(serde_json::to_value(edit)?),
             error: None,
         };
 
@@ -809,187 +1618,635 @@ impl LspServer {
         Ok(())
     }
 
-    /// 处理 spring/jobs 请求
+    /// 处理 workspace/didCreateFiles 通知
     ///
-    /// 扫描项目中的所有定时任务并返回任务列表
-    fn handle_jobs_request(&self, req: Request) -> Result<()> {
-        tracing::info!("Handling spring/jobs request");
-
-        use crate::scanner::job::{JobScanner, JobsRequest, JobsResponse};
-
-        // 解析请求参数
-        let params: JobsRequest = serde_json::from_value(req.params)?;
-        let project_path = std::path::Path::new(&params.app_path);
-
-        tracing::info!("Scanning jobs in: {:?}", project_path);
-
-        // 创建任务扫描器
-        let scanner = JobScanner::new();
-
-        // 扫描任务
-        let jobs = match scanner.scan_jobs(project_path) {
-            Ok(jobs) => {
-                tracing::info!("Successfully scanned {} jobs", jobs.len());
-                jobs
-            }
-            Err(e) => {
-                tracing::error!("Failed to scan jobs: {}", e);
-                // 返回空列表而不是错误
-                Vec::new()
+    /// 在 `[templates]` 配置的目录中新建空的 Rust 文件时，通过 `workspace/applyEdit`
+    /// 请求客户端填充模板（模块文档、导入和示例函数），参见 [`file_template`]。
+    /// `willCreateFiles` 返回的编辑在文件创建之前应用，不能修改新文件的内容，因此在文件创建后填充
+    fn handle_did_create_files(&mut self, params: lsp_types::CreateFilesParams) -> Result<()> {
+        for file in &params.files {
+            if let Ok(uri) = lsp_types::Url::parse(&file.uri) {
+                self.project_scans.update(&uri);
+                self.sync_dependency_input(&uri);
             }
-        };
-
-        // 构建响应
-        let response_data = JobsResponse { jobs };
+        }
+        if !self.apply_edit_support {
+            return Ok(());
+        }
 
-        tracing::info!("Sending response with {} jobs", response_data.jobs.len());
+        for file in params.files {
+            let Ok(uri) = lsp_types::Url::parse(&file.uri) else {
+                continue;
+            };
+            let Some(edit) = self.template_edit(&uri) else {
+                continue;
+            };
+            let name = uri
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .unwrap_or_default()
+                .to_string();
+            let params = lsp_types::ApplyWorkspaceEditParams {
+                label: Some(localized(self.locale, "template.apply-label", &[&name])),
+                edit,
+            };
+            let request = Request::new(
+                RequestId::from(format!("{}:{}", APPLY_TEMPLATE_REQUEST, uri)),
+                ApplyWorkspaceEdit::METHOD.to_string(),
+                params,
+            );
+            self.connection
+                .sender
+                .send(Message::Request(request))
+                .map_err(|e| Error::MessageSend(e.to_string()))?;
+        }
 
-        let result = serde_json::to_value(response_data)?;
+        Ok(())
+    }
 
-        let response = Response {
-            id: req.id,
-            result: Some(result),
-            error: None,
+    /// 在新建的空文件中插入模板的工作空间编辑，文件不为空或没有适用的模板时返回 `None`
+    fn template_edit(&self, uri: &lsp_types::Url) -> Option<lsp_types::WorkspaceEdit> {
+        let path = uri.to_file_path().ok()?;
+        let content = match self.document_manager.get(uri) {
+            Some(doc) => doc.content,
+            None => std::fs::read_to_string(&path).ok()?,
         };
+        if !content.trim().is_empty() {
+            return None;
+        }
+        let root = self.project_root_for(uri)?;
+        let template =
+            file_template::template_for(&self.config.templates, &root, &path, self.locale)?;
+
+        let start = lsp_types::Position::new(0, 0);
+        let mut builder = WorkspaceEditBuilder::new(&self.document_manager);
+        builder
+            .edit(
+                uri,
+                None,
+                lsp_types::TextEdit {
+                    range: lsp_types::Range::new(start, start),
+                    new_text: template,
+                },
+            )
+            .ok()?;
+        builder.build(self.workspace_edit_support).ok()
+    }
 
-        self.connection
-            .sender
-            .send(Message::Response(response))
-            .map_err(|e| Error::MessageSend(e.to_string()))?;
+    /// 构建更新模块路径引用的工作空间编辑
+    fn module_rename_edit(
+        &self,
+        renames: &[ModuleRename],
+    ) -> std::result::Result<Option<lsp_types::WorkspaceEdit>, String> {
+        let mut builder = WorkspaceEditBuilder::new(&self.document_manager);
+        let mut edited: HashMap<lsp_types::Url, Vec<lsp_types::Range>> = HashMap::new();
+
+        for rename in renames {
+            for (uri, content) in self.crate_sources(&rename.old_uri) {
+                for edit in module_path::rename_edits(
+                    &self.mapper(&content),
+                    &rename.old_module,
+                    &rename.new_module,
+                ) {
+                    let ranges = edited.entry(uri.clone()).or_default();
+                    if ranges
+                        .iter()
+                        .any(|range| range.start < edit.range.end && edit.range.start < range.end)
+                    {
+                        continue;
+                    }
+                    ranges.push(edit.range);
+                    builder.edit(&uri, None, edit).map_err(|e| e.to_string())?;
+                }
+            }
+        }
 
-        Ok(())
+        if edited.is_empty() {
+            return Ok(None);
+        }
+        builder
+            .build(self.workspace_edit_support)
+            .map(Some)
+            .map_err(|e| e.to_string())
     }
 
-    /// 处理 spring/plugins 请求
+    /// 处理 workspace/didRenameFiles 通知
     ///
-    /// 扫描项目中的所有插件并返回插件列表
-    fn handle_plugins_request(&self, req: Request) -> Result<()> {
-        tracing::info!("Handling spring/plugins request");
-
-        use crate::scanner::plugin::{PluginScanner, PluginsRequest, PluginsResponse};
-
-        // 解析请求参数
-        let params: PluginsRequest = serde_json::from_value(req.params)?;
-        let project_path = std::path::Path::new(&params.app_path);
+    /// 文件内容没有变化，只更新索引中的位置并迁移诊断，不重新扫描项目。
+    /// 模块路径改变时检查新的父模块是否声明了该模块、是否还有引用旧路径的代码，
+    /// 并重新发布受影响的已打开文档的诊断
+    pub fn handle_did_rename_files(&mut self, params: RenameFilesParams) -> Result<()> {
+        let mut affected = Vec::new();
+
+        for file in &params.files {
+            let (Ok(old_uri), Ok(new_uri)) = (
+                lsp_types::Url::parse(&file.old_uri),
+                lsp_types::Url::parse(&file.new_uri),
+            ) else {
+                continue;
+            };
+            self.project_scans.update(&old_uri);
+            self.project_scans.update(&new_uri);
+            self.sync_dependency_input(&old_uri);
+            self.sync_dependency_input(&new_uri);
+
+            let renamed = self.index_manager.rename_file(&old_uri, &new_uri);
+            tracing::info!(
+                "File renamed: {} -> {} ({} index entries updated)",
+                old_uri,
+                new_uri,
+                renamed
+            );
 
-        tracing::info!("Scanning plugins in: {:?}", project_path);
+            if let Some(diagnostics) = self.dependency_diagnostics.remove(&old_uri) {
+                self.dependency_diagnostics
+                    .insert(new_uri.clone(), diagnostics);
+            }
+            self.completion_engine.invalidate(&old_uri);
+            self.diagnostic_engine.clear(&old_uri);
+            self.diagnostic_engine.publish(&self.connection, &old_uri)?;
+            affected.push(new_uri);
+        }
 
-        // 创建插件扫描器
-        let scanner = PluginScanner::new();
+        let renames = module_renames(&params.files);
+        let mut warnings = Vec::new();
+        for rename in &renames {
+            warnings.extend(self.check_module_rename(rename));
+        }
+        if !warnings.is_empty() {
+            self.show_message(lsp_types::MessageType::WARNING, warnings.join("\n"))?;
+        }
 
-        // 扫描插件
-        let plugins = match scanner.scan_plugins(project_path) {
-            Ok(plugins) => {
-                tracing::info!("Successfully scanned {} plugins", plugins.len());
-                plugins
+        // 引用了旧模块路径的已打开文档
+        for uri in self.document_manager.uris() {
+            let references_old = self
+                .document_manager
+                .with_document(&uri, |doc| {
+                    renames.iter().any(|rename| {
+                        !module_path::find_references(&doc.mapper(), &rename.old_module).is_empty()
+                    })
+                })
+                .unwrap_or(false);
+            if references_old && !affected.contains(&uri) {
+                affected.push(uri);
             }
-            Err(e) => {
-                tracing::error!("Failed to scan plugins: {}", e);
-                // 返回空列表而不是错误
-                Vec::new()
+        }
+
+        for uri in affected {
+            if self.document_manager.get(&uri).is_some() {
+                self.refresh_diagnostics(&uri)?;
             }
-        };
+        }
 
-        // 构建响应
-        let response_data = PluginsResponse { plugins };
+        Ok(())
+    }
 
-        tracing::info!(
-            "Sending response with {} plugins",
-            response_data.plugins.len()
-        );
+    /// 检查模块路径变化后的模块声明和旧路径引用，返回警告消息
+    fn check_module_rename(&self, rename: &ModuleRename) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let new_path = module_path::display_path(&rename.new_module);
+        let old_path = module_path::display_path(&rename.old_module);
 
-        let result = serde_json::to_value(response_data)?;
+        if let Some(src_dir) = self
+            .project_root_for(&rename.new_uri)
+            .map(|r| r.join("src"))
+        {
+            let name = rename
+                .new_module
+                .last()
+                .map(String::as_str)
+                .unwrap_or_default();
+            let parents: Vec<_> = module_path::parent_module_files(&src_dir, &rename.new_module)
+                .into_iter()
+                .filter(|path| path.is_file())
+                .collect();
+            let declared = parents.iter().any(|path| {
+                self.file_content(path)
+                    .is_some_and(|content| module_path::declares_module(&content, name))
+            });
+            if !declared {
+                warnings.push(localized(
+                    self.locale,
+                    "rename.warning.undeclared-module",
+                    &[&new_path, &name],
+                ));
+            }
+        }
 
-        let response = Response {
-            id: req.id,
-            result: Some(result),
-            error: None,
-        };
+        let stale: Vec<_> = self
+            .crate_sources(&rename.new_uri)
+            .into_iter()
+            .filter(|(_, content)| {
+                !module_path::find_references(&self.mapper(content), &rename.old_module).is_empty()
+            })
+            .map(|(uri, _)| uri.to_string())
+            .collect();
+        if !stale.is_empty() {
+            warnings.push(localized(
+                self.locale,
+                "rename.warning.stale-references",
+                &[&stale.len(), &old_path, &new_path, &stale.join(", ")],
+            ));
+        }
 
-        self.connection
-            .sender
-            .send(Message::Response(response))
-            .map_err(|e| Error::MessageSend(e.to_string()))?;
+        warnings
+    }
 
-        Ok(())
+    /// 项目扫描器读取源文件的方式：优先使用编辑器中打开的内容
+    fn sources(&self) -> SourceReader {
+        SourceReader::overlay(Arc::clone(&self.document_manager))
     }
 
-    /// 处理 spring/configurations 请求
+    /// 使用协商的位置编码为未打开的文件内容创建位置转换器
+    fn mapper<'a>(&self, content: &'a str) -> PositionMapper<'a> {
+        PositionMapper::with_encoding(content, self.position_encoding)
+    }
+
+    /// 使用与客户端协商的位置编码
     ///
-    /// 扫描项目中的所有配置结构并返回配置列表
-    fn handle_configurations_request(&self, req: Request) -> Result<()> {
-        tracing::debug!("Handling spring/configurations request");
+    /// 文档管理器和计算位置的分析器都持有编码，在 `initialize` 时（还没有打开的文档）一起替换，
+    /// 依赖它们的缓存随之重建
+    fn set_position_encoding(&mut self, encoding: PositionEncoding) {
+        if encoding == self.position_encoding {
+            return;
+        }
+        self.position_encoding = encoding;
+        self.document_manager = Arc::new(DocumentManager::with_encoding(encoding));
+        self.project_scans = ProjectScanCache::new(self.sources());
+        self.rebuild_analyzers();
+    }
 
-        use crate::scanner::config::{
-            ConfigScanner, ConfigurationsRequest, ConfigurationsResponse,
-        };
+    /// 使用客户端指定的消息语言
+    ///
+    /// 生成消息的分析器和补全引擎都持有语言，在 `initialize` 时一起替换
+    fn set_locale(&mut self, locale: Locale) {
+        if locale == self.locale {
+            return;
+        }
+        self.locale = locale;
+        self.rebuild_analyzers();
+    }
 
-        // 解析请求参数
-        let params: ConfigurationsRequest = serde_json::from_value(req.params)?;
-        let project_path = std::path::Path::new(&params.app_path);
+    /// 按当前的位置编码和消息语言重建分析器
+    fn rebuild_analyzers(&mut self) {
+        self.toml_analyzer = Arc::new(
+            TomlAnalyzer::new((*self.schema_provider).clone())
+                .with_encoding(self.position_encoding)
+                .with_locale(self.locale),
+        );
+        self.macro_analyzer = Arc::new(
+            MacroAnalyzer::new()
+                .with_encoding(self.position_encoding)
+                .with_locale(self.locale),
+        );
+        self.handler_analyzer = Arc::new(
+            HandlerAnalyzer::new()
+                .with_encoding(self.position_encoding)
+                .with_locale(self.locale),
+        );
+        self.completion_engine = Arc::new(
+            CompletionEngine::new((*self.schema_provider).clone()).with_locale(self.locale),
+        );
+        self.incremental_di
+            .set_macro_analyzer(self.macro_analyzer.clone());
+    }
 
-        // 创建配置扫描器
-        let scanner = ConfigScanner::new();
+    /// 文档所属 crate 的 `src` 目录中的所有 Rust 文件（优先使用编辑器中打开的内容）
+    fn crate_sources(&self, uri: &lsp_types::Url) -> Vec<(lsp_types::Url, String)> {
+        match self.project_root_for(uri) {
+            Some(root) => crate_rust_sources(&self.document_manager, &root),
+            None => Vec::new(),
+        }
+    }
 
-        // 扫描配置
-        let configurations = match scanner.scan_configurations(project_path) {
-            Ok(configurations) => configurations,
-            Err(e) => {
-                tracing::error!("Failed to scan configurations: {}", e);
-                // 返回空列表而不是错误
-                Vec::new()
-            }
+    /// 文档所属 crate 的 `config` 目录中的配置文件（优先使用编辑器中打开的内容）
+    fn crate_config_docs(
+        &self,
+        uri: &lsp_types::Url,
+    ) -> Vec<(
+        lsp_types::Url,
+        crate::analysis::toml::toml_analyzer::TomlDocument,
+    )> {
+        match self.project_root_for(uri) {
+            Some(root) => self.project_scans.config_docs(&root, &self.toml_analyzer),
+            None => Vec::new(),
+        }
+    }
+
+    /// profile 配置文件（如 `config/app-prod.toml`）对应的基础配置 `config/app.toml`
+    ///
+    /// 文档不是 profile 配置文件或基础配置无法解析时返回 `None`
+    fn profile_base_config(
+        &self,
+        uri: &lsp_types::Url,
+    ) -> Option<crate::analysis::toml::toml_analyzer::TomlDocument> {
+        let path = uri.to_file_path().ok()?;
+        let profile = path
+            .file_name()?
+            .to_str()?
+            .strip_prefix("app-")?
+            .strip_suffix(".toml")?;
+        if profile.is_empty() {
+            return None;
+        }
+        let content = self.file_content(&path.with_file_name("app.toml"))?;
+        self.toml_analyzer.parse(&content).ok()
+    }
+
+    /// 读取文件内容（优先使用编辑器中打开的内容）
+    fn file_content(&self, path: &Path) -> Option<String> {
+        read_file_content(&self.document_manager, path)
+    }
+
+    /// 向客户端注册 Cargo.lock 文件监听
+    ///
+    /// 只在客户端支持 `workspace/didChangeWatchedFiles` 动态注册时发送
+    fn register_file_watchers(&self) -> Result<()> {
+        use lsp_types::{
+            DidChangeWatchedFilesRegistrationOptions, FileSystemWatcher, GlobPattern, Registration,
+            RegistrationParams,
         };
 
-        // 构建响应
-        let response_data = ConfigurationsResponse { configurations };
-        let result = serde_json::to_value(response_data)?;
+        if !self.watched_files_registration {
+            return Ok(());
+        }
 
-        let response = Response {
-            id: req.id,
-            result: Some(result),
-            error: None,
+        let options = DidChangeWatchedFilesRegistrationOptions {
+            watchers: WATCHED_GLOBS
+                .iter()
+                .map(|glob| FileSystemWatcher {
+                    glob_pattern: GlobPattern::String(glob.to_string()),
+                    kind: None,
+                })
+                .collect(),
+        };
+        let params = RegistrationParams {
+            registrations: vec![Registration {
+                id: WATCH_FILES_REGISTRATION.to_string(),
+                method: DidChangeWatchedFiles::METHOD.to_string(),
+                register_options: Some(serde_json::to_value(options)?),
+            }],
         };
 
+        let request = Request::new(
+            RequestId::from(WATCH_FILES_REGISTRATION.to_string()),
+            RegisterCapability::METHOD.to_string(),
+            params,
+        );
         self.connection
             .sender
-            .send(Message::Response(response))
+            .send(Message::Request(request))
             .map_err(|e| Error::MessageSend(e.to_string()))?;
 
         Ok(())
     }
 
-    /// 处理 textDocument/documentSymbol 请求
+    /// 处理文件变化通知
     ///
-    /// 提取文档中的符号（配置节、属性、函数、结构体等）用于大纲视图
-    fn handle_document_symbol(&self, req: Request) -> Result<()> {
-        tracing::debug!("Handling textDocument/documentSymbol request");
+    /// Cargo.lock 中 spring 插件版本变化时，重新加载受影响插件的 Schema
+    /// 并重新验证所有打开的 TOML 文档。
+    /// 编辑器中未打开的源文件、配置文件或 OpenAPI 规范文件在磁盘上变化时，
+    /// 增量重新验证依赖注入并重新分析打开的文档（打开的文件以编辑器中的内容为准）。
+    /// 工作空间的 `.spring-lsp.toml` 变化时重新加载服务器配置，
+    /// `.spring-lsp/rules.toml` 变化时重新加载路由安全规则
+    pub fn handle_did_change_watched_files(
+        &mut self,
+        params: DidChangeWatchedFilesParams,
+    ) -> Result<()> {
+        let mut changed = Vec::new();
+        let mut disk_changes = false;
+
+        for change in params.changes {
+            let Ok(path) = change.uri.to_file_path() else {
+                continue;
+            };
+            if self
+                .workspace_path
+                .as_ref()
+                .is_some_and(|root| path == root.join(WORKSPACE_CONFIG_FILE))
+            {
+                disk_changes |= self.reload_config();
+                continue;
+            }
+            if self
+                .workspace_path
+                .as_ref()
+                .is_some_and(|root| path == SecurityRules::path(root))
+            {
+                disk_changes |= self.reload_security_rules();
+                continue;
+            }
+            self.project_scans.update(&change.uri);
+            self.sync_dependency_input(&change.uri);
+            if path.file_name().and_then(|name| name.to_str()) != Some(CARGO_LOCK) {
+                let is_open = self
+                    .document_manager
+                    .with_document(&change.uri, |_| ())
+                    .is_some();
+                disk_changes |= !is_open;
+                continue;
+            }
 
-        let params: DocumentSymbolParams = serde_json::from_value(req.params)?;
-        let uri = &params.text_document.uri;
+            let versions = if change.typ == FileChangeType::DELETED {
+                PluginVersions::default()
+            } else {
+                read_plugin_versions(&path)
+            };
+            let previous = self
+                .plugin_versions
+                .insert(path, versions.clone())
+                .unwrap_or_default();
+            changed.extend(previous.changed(&versions));
+        }
 
-        let symbols = self.document_manager.with_document(uri, |doc| {
+        if disk_changes {
+            self.reindex_disk_changes()?;
+        }
+
+        changed.sort();
+        changed.dedup();
+        if changed.is_empty() {
+            return Ok(());
+        }
+
+        tracing::info!("Spring plugin versions changed: {:?}", changed);
+
+        let runtime = tokio::runtime::Runtime::new()
+            .map_err(|e| Error::SchemaLoad(format!("Failed to create tokio runtime: {}", e)))?;
+        match runtime.block_on(SchemaProvider::fetch_schema()) {
+            Ok(fresh) => {
+                self.apply_schema_update(&fresh, &changed)?;
+            }
+            Err(e) => {
+                // 保留当前 Schema，下次版本变化时再尝试
+                tracing::warn!("Failed to reload schema: {}, keeping current schema", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 重新加载服务器配置，返回是否使用了新的配置
+    ///
+    /// 新的配置无效时保留当前配置。按函数缓存的诊断依赖配置（如占位处理器和复杂度检查），
+    /// 配置变化后清空缓存
+    fn reload_config(&mut self) -> bool {
+        let config = ServerConfig::load(self.workspace_path.as_deref());
+        if let Err(e) = config.validate() {
+            tracing::warn!(
+                "Invalid configuration: {}, keeping current configuration",
+                e
+            );
+            return false;
+        }
+        tracing::info!("Configuration reloaded");
+        self.config = config;
+        self.item_diagnostics.clear();
+        true
+    }
+
+    /// 重新加载路由安全规则（`.spring-lsp/rules.toml`），返回是否使用了新的规则
+    ///
+    /// 同一文件中的路由前缀约定（`[[prefix]]`）一起重新加载。
+    /// 规则文件无效时保留当前规则，与 [`Self::reload_config`] 一致
+    fn reload_security_rules(&mut self) -> bool {
+        let Some(root) = &self.workspace_path else {
+            return false;
+        };
+        match SecurityRules::load(root) {
+            Ok(rules) => {
+                tracing::info!("Security rules reloaded");
+                self.security_rules = rules;
+                true
+            }
+            Err(e) => {
+                tracing::warn!("{}, keeping current security rules", e);
+                false
+            }
+        }
+    }
+
+    /// 磁盘上的文件变化后重新验证
+    ///
+    /// 运行过依赖注入验证时增量重新验证（内容没有变化的文件复用缓存），然后重新分析打开的文档
+    fn reindex_disk_changes(&mut self) -> Result<()> {
+        if self.dependency_validation.is_none() && self.dependency_root.is_some() {
+            self.update_dependency_diagnostics(None)?;
+        }
+
+        for uri in self.document_manager.uris() {
+            if let Some(language_id) = self
+                .document_manager
+                .with_document(&uri, |doc| doc.language_id.clone())
+            {
+                self.analyze_document(&uri, &language_id)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 使用新的 Schema 更新受影响的插件，并重新验证打开的 TOML 文档
+    ///
+    /// # 返回
+    ///
+    /// 实际更新的配置前缀列表
+    pub fn apply_schema_update(
+        &mut self,
+        fresh: &ConfigSchema,
+        crates: &[String],
+    ) -> Result<Vec<String>> {
+        let mut provider = (*self.schema_provider).clone();
+        let updated = provider.reload_plugins(fresh, crates);
+        if updated.is_empty() {
+            return Ok(updated);
+        }
+
+        tracing::info!("Reloaded schema for plugins: {:?}", updated);
+
+        // 依赖 Schema 的组件持有 Schema 的副本，需要一起替换（补全缓存随之失效）
+        self.toml_analyzer = Arc::new(
+            TomlAnalyzer::new(provider.clone())
+                .with_encoding(self.position_encoding)
+                .with_locale(self.locale),
+        );
+        self.completion_engine =
+            Arc::new(CompletionEngine::new(provider.clone()).with_locale(self.locale));
+        self.schema_provider = Arc::new(provider);
+
+        for uri in self.document_manager.uris() {
+            let is_toml = self
+                .document_manager
+                .with_document(&uri, |doc| doc.language_id == "toml")
+                .unwrap_or(false);
+            if is_toml {
+                self.analyze_document(&uri, "toml")?;
+            }
+        }
+
+        Ok(updated)
+    }
+
+    /// 处理智能补全请求
+    fn handle_completion(&mut self, req: Request) -> Result<()> {
+        tracing::debug!("Handling completion request");
+
+        let params: CompletionParams = serde_json::from_value(req.params)?;
+        self.status.record_completion();
+
+        let uri = &params.text_document_position.text_document.uri;
+        let base_config = self.profile_base_config(uri);
+        let response = self.document_manager.with_document(uri, |doc| {
+            // 根据文件类型选择补全策略
             match doc.language_id.as_str() {
                 "toml" => {
-                    // TOML 文档符号提取
-                    self.extract_toml_symbols(&doc.content)
+                    // 正在输入的配置通常有语法错误，使用容错解析
+                    let (toml_doc, _) = self.toml_analyzer.parse_recovering(&doc.content);
+                    let position = params.text_document_position.position;
+                    let completions = self.completion_engine.complete_toml_cached(
+                        uri,
+                        doc.version,
+                        &toml_doc,
+                        position,
+                    );
+                    if completions.cache_hit {
+                        self.status.record_cache_hit(COMPLETION_CACHE);
+                    } else {
+                        self.status.record_cache_miss(COMPLETION_CACHE);
+                    }
+                    let mut items = completions.items;
+                    if let Some(base) = &base_config {
+                        self.completion_engine
+                            .add_profile_overrides(&mut items, &toml_doc, base, position);
+                    }
+                    items
                 }
                 "rust" => {
-                    // Rust 文档符号提取
-                    self.extract_rust_symbols(&doc.content)
-                }
-                _ => {
-                    tracing::debug!(
-                        "Unsupported language for document symbols: {}",
-                        doc.language_id
-                    );
-                    vec![]
+                    let position = params.text_document_position.position;
+                    let lines = doc.mapper();
+                    let mut items = self.completion_engine.complete_rust(&lines, position);
+                    // #[middlewares(...)] 中补全工作空间中的中间件和内置中间件
+                    if middlewares::in_middlewares_attr(&lines, position) {
+                        let workspace = crate::scanner::middleware::MiddlewareScanner::new()
+                            .workspace_middlewares(&self.crate_sources(uri));
+                        items.extend(middlewares::complete_middlewares(&workspace, self.locale));
+                    }
+                    // #[inject(component = "...")] 中补全注册的组件名称和按约定的默认名称
+                    if component_names::in_component_name(&lines, position) {
+                        let components = self.project_components(uri);
+                        items = component_names::complete_component_names(&components, self.locale);
+                    }
+                    items
                 }
+                _ => vec![],
             }
         });
 
-        let result = match symbols {
-            Some(symbols) => serde_json::to_value(DocumentSymbolResponse::Nested(symbols))?,
+        let result = match response {
+            Some(mut completions) => {
+                snippet::adapt_completions(&mut completions, self.snippet_support);
+                serde_json::to_value(CompletionResponse::Array(completions))?
+            }
             None => serde_json::Value::Null,
         };
 
@@ -1007,49 +2264,34 @@ impl LspServer {
         Ok(())
     }
 
-    /// 处理 workspace/symbol 请求
-    ///
-    /// 在整个工作空间中搜索符号（组件、路由、配置等）
-    fn handle_workspace_symbol(&self, req: Request) -> Result<()> {
-        tracing::debug!("Handling workspace/symbol request");
-
-        // 解析请求参数
-        let params: serde_json::Value = req.params;
-        let query = params
-            .get("query")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_lowercase();
-
-        tracing::debug!("Workspace symbol query: '{}'", query);
-
-        let mut symbols: Vec<lsp_types::SymbolInformation> = vec![];
-
-        // 从 workspace_path 获取工作空间路径
-        if let Some(workspace_path) = &self.workspace_path {
-            // 搜索组件
-            if let Ok(component_symbols) = self.search_component_symbols(workspace_path, &query) {
-                symbols.extend(component_symbols);
-            }
+    /// 处理悬停提示请求
+    fn handle_hover(&mut self, req: Request) -> Result<()> {
+        tracing::debug!("Handling hover request");
 
-            // 搜索路由
-            if let Ok(route_symbols) = self.search_route_symbols(workspace_path, &query) {
-                symbols.extend(route_symbols);
-            }
+        let params: HoverParams = serde_json::from_value(req.params)?;
+        self.status.record_hover();
 
-            // 搜索配置
-            if let Ok(config_symbols) = self.search_config_symbols(workspace_path, &query) {
-                symbols.extend(config_symbols);
+        let position = params.text_document_position_params.position;
+        let document = self
+            .document_manager
+            .get(&params.text_document_position_params.text_document.uri);
+
+        // 根据文件类型选择分析器
+        let hover = match document {
+            Some(doc) if doc.language_id == "toml" => {
+                let (toml_doc, _) = self.toml_analyzer.parse_recovering(&doc.content);
+                self.toml_analyzer
+                    .hover(&toml_doc, position)
+                    .map(|hover| self.with_config_origin(&doc.uri, &toml_doc, position, hover))
             }
-        }
-
-        tracing::debug!(
-            "Found {} workspace symbols matching '{}'",
-            symbols.len(),
-            query
-        );
+            Some(doc) if doc.language_id == "rust" => self.rust_hover(doc, position),
+            _ => None,
+        };
 
-        let result = serde_json::to_value(symbols)?;
+        let result = match hover {
+            Some(hover) => serde_json::to_value(hover)?,
+            None => serde_json::Value::Null,
+        };
 
         let response = Response {
             id: req.id,
@@ -1065,591 +2307,6037 @@ impl LspServer {
         Ok(())
     }
 
-    /// 搜索组件符号
-    fn search_component_symbols(
+    /// 在配置项的悬停提示后附加值来源（`app.toml` 和 profile 配置文件中的位置）
+    ///
+    /// 只处理 `app.toml` 和 `app-<profile>.toml`，光标不在配置值上时原样返回
+    fn with_config_origin(
         &self,
-        workspace_path: &std::path::Path,
-        query: &str,
-    ) -> Result<Vec<lsp_types::SymbolInformation>> {
-        use crate::scanner::component::{ComponentScanner, ComponentSource};
-        use lsp_types::{Location, Position, Range, SymbolInformation, SymbolKind, Url};
-
-        let scanner = ComponentScanner::new();
-        let components = scanner
-            .scan_components(workspace_path)
-            .map_err(|e| Error::Other(anyhow::anyhow!("Failed to scan components: {}", e)))?;
-
-        let mut symbols = Vec::new();
-
-        for component in components {
-            // 过滤：如果有查询字符串，检查组件名称是否匹配
-            if !query.is_empty() && !component.name.to_lowercase().contains(query) {
-                continue;
-            }
-
-            // 转换 LocationResponse 到 lsp_types::Location
-            let uri = Url::parse(&component.location.uri)
-                .map_err(|e| Error::Other(anyhow::anyhow!("Invalid URI: {}", e)))?;
-
-            let range = Range {
-                start: Position {
-                    line: component.location.range.start.line,
-                    character: component.location.range.start.character,
-                },
-                end: Position {
-                    line: component.location.range.end.line,
-                    character: component.location.range.end.character,
-                },
-            };
-
-            // 根据组件来源使用不同的图标
-            // #[component] 使用 METHOD (symbol-method)
-            // #[derive(Service)] 使用 CLASS (symbol-class)
-            let kind = match component.source {
-                ComponentSource::Component => SymbolKind::METHOD,
-                ComponentSource::Service => SymbolKind::CLASS,
-            };
-
-            #[allow(deprecated)]
-            symbols.push(SymbolInformation {
-                name: component.name.clone(),
-                kind,
-                tags: None,
-                deprecated: None,
-                location: Location { uri, range },
-                container_name: Some(format!("Component ({})", component.type_name)),
+        uri: &lsp_types::Url,
+        toml_doc: &crate::analysis::toml::toml_analyzer::TomlDocument,
+        position: lsp_types::Position,
+        mut hover: lsp_types::Hover,
+    ) -> lsp_types::Hover {
+        if config_file_profile(uri).is_none() {
+            return hover;
+        }
+        let property = toml_doc
+            .config_sections
+            .iter()
+            .find_map(|(prefix, section)| {
+                section
+                    .properties
+                    .iter()
+                    .find(|(_, property)| {
+                        property.range.start <= position && position <= property.range.end
+                    })
+                    .map(|(key, _)| (prefix, key))
             });
+        let Some((prefix, key)) = property else {
+            return hover;
+        };
+        let sources = config_value_sources(prefix, key, &self.crate_config_docs(uri));
+        if sources.is_empty() {
+            return hover;
         }
-
-        Ok(symbols)
+        if let lsp_types::HoverContents::Markup(content) = &mut hover.contents {
+            content
+                .value
+                .push_str(&config_origin_markdown(&sources, self.locale));
+        }
+        hover
     }
 
-    /// 搜索路由符号
-    fn search_route_symbols(
+    /// 构建 Rust 文档的悬停提示
+    ///
+    /// 完整提示在悬停提示后台线程中构建，分发线程同时构建只依赖当前文档的简短提示；
+    /// 完整提示未在时间预算内完成时返回简短提示并取消后台任务。
+    /// 后台线程忙于上一个任务且已有任务排队时直接返回简短提示
+    fn rust_hover(
         &self,
-        workspace_path: &std::path::Path,
-        query: &str,
-    ) -> Result<Vec<lsp_types::SymbolInformation>> {
-        use crate::scanner::route::RouteScanner;
-        use lsp_types::{Location, Position, Range, SymbolInformation, SymbolKind, Url};
+        document: Document,
+        position: lsp_types::Position,
+    ) -> Option<lsp_types::Hover> {
+        let context = self.hover_context();
+        let start = Instant::now();
+        let Some(worker) = &self.hover_worker else {
+            return context.hover(&document, position, HoverScope::Workspace);
+        };
+        let Some(receiver) = worker.submit(context.clone(), document.clone(), position) else {
+            tracing::debug!(
+                "Hover thread is busy, returning document-only hover for {}",
+                document.uri
+            );
+            return context.hover(&document, position, HoverScope::Document);
+        };
 
-        let scanner = RouteScanner::new();
-        let routes = scanner
-            .scan_routes(workspace_path)
-            .map_err(|e| Error::Other(anyhow::anyhow!("Failed to scan routes: {}", e)))?;
+        let quick = context.hover(&document, position, HoverScope::Document);
+        let remaining = self.config.hover.budget().saturating_sub(start.elapsed());
+        match receiver.recv_timeout(remaining) {
+            Ok(hover) => hover,
+            // 后台线程没有发送结果（构建提示时 panic）
+            Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                self.status.record_panic();
+                self.status.record_method_error(HoverRequest::METHOD);
+                tracing::error!("Hover for {} panicked on the hover thread", document.uri);
+                quick
+            }
+            Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
+                context.cancellation.cancel();
+                tracing::debug!(
+                    "Hover for {} exceeded the {}ms budget, returning document-only hover",
+                    document.uri,
+                    self.config.hover.budget_ms
+                );
+                quick
+            }
+        }
+    }
 
-        let mut symbols = Vec::new();
+    /// 悬停提示构建所需的服务器状态快照
+    fn hover_context(&self) -> HoverContext {
+        HoverContext {
+            workspace_path: self.workspace_path.clone(),
+            document_manager: Arc::clone(&self.document_manager),
+            handler_analyzer: Arc::clone(&self.handler_analyzer),
+            macro_analyzer: Arc::clone(&self.macro_analyzer),
+            middleware_rules: self.config.middleware.rules.clone(),
+            cancellation: CancellationToken::new(),
+        }
+    }
 
-        for route in routes {
-            // 构建搜索文本：方法 + 路径
-            let search_text = format!("{} {}", route.method, route.path).to_lowercase();
+    /// 处理定义跳转请求
+    fn handle_goto_definition(&mut self, req: Request) -> Result<()> {
+        tracing::debug!("Handling goto definition request");
 
-            // 过滤：如果有查询字符串，检查路由是否匹配
-            if !query.is_empty() && !search_text.contains(query) {
-                continue;
-            }
+        let params: GotoDefinitionParams = serde_json::from_value(req.params)?;
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
 
-            // 转换 LocationResponse 到 lsp_types::Location
-            let uri = Url::parse(&route.location.uri)
-                .map_err(|e| Error::Other(anyhow::anyhow!("Invalid URI: {}", e)))?;
-
-            let range = Range {
-                start: Position {
-                    line: route.location.range.start.line,
-                    character: route.location.range.start.character,
-                },
-                end: Position {
-                    line: route.location.range.end.line,
-                    character: route.location.range.end.character,
-                },
+        // `.route("路径", get(处理器))` 的路径 -> 处理器函数（可能在其他文件中）
+        let router_routes = self
+            .document_manager
+            .with_document(&uri, |doc| {
+                if doc.language_id == "rust" {
+                    router_routes_at(&doc.mapper(), position)
+                } else {
+                    Vec::new()
+                }
+            })
+            .unwrap_or_default();
+        if !router_routes.is_empty() {
+            let locations = self.router_handler_locations(&uri, &router_routes);
+            let response = Response {
+                id: req.id,
+                result: Some(serde_json::to_value(GotoDefinitionResponse::Array(
+                    locations,
+                ))?),
+                error: None,
             };
-
-            #[allow(deprecated)]
-            symbols.push(SymbolInformation {
-                name: format!("{} {}", route.method, route.path),
-                kind: SymbolKind::FUNCTION,
-                tags: None,
-                deprecated: None,
-                location: Location { uri, range },
-                container_name: Some(format!("Route ({})", route.handler)),
-            });
+            return self
+                .connection
+                .sender
+                .send(Message::Response(response))
+                .map_err(|e| Error::MessageSend(e.to_string()));
         }
 
-        Ok(symbols)
-    }
-
-    /// 搜索配置符号
-    fn search_config_symbols(
-        &self,
-        workspace_path: &std::path::Path,
-        query: &str,
-    ) -> Result<Vec<lsp_types::SymbolInformation>> {
-        use crate::scanner::config::ConfigScanner;
-        use lsp_types::{SymbolInformation, SymbolKind};
+        let locations = self
+            .document_manager
+            .with_document(&uri, |doc| match doc.language_id.as_str() {
+                "toml" => {
+                    // 配置中引用的任务名称 -> 任务函数
+                    let (toml_doc, _) = self.toml_analyzer.parse_recovering(&doc.content);
+                    match self
+                        .toml_analyzer
+                        .job_name_reference_at(&toml_doc, position)
+                    {
+                        Some(reference) => self
+                            .job_name_index_for(&uri)
+                            .map(|index| index.find(&reference.name))
+                            .unwrap_or_default(),
+                        None => vec![],
+                    }
+                }
+                "rust" => {
+                    // 注入的组件类型 -> 提供它的 #[component] 函数
+                    if let Some(type_name) = injected_type_at(&doc.mapper(), position) {
+                        let components = self.project_components(&uri);
+                        let providers: Vec<_> = provider_functions(&components, &type_name)
+                            .into_iter()
+                            .filter_map(|component| {
+                                let uri = lsp_types::Url::parse(&component.location.uri).ok()?;
+                                Some(lsp_types::Location::new(
+                                    uri,
+                                    route_location_range(&component.location),
+                                ))
+                            })
+                            .collect();
+                        if !providers.is_empty() {
+                            return providers;
+                        }
+                    }
 
-        let scanner = ConfigScanner::new();
-        let configs = scanner
-            .scan_configurations(workspace_path)
-            .map_err(|e| Error::Other(anyhow::anyhow!("Failed to scan configurations: {}", e)))?;
+                    // 路由路径 -> OpenAPI 规范中的操作
+                    let Ok(handler_file) = self.handler_analyzer.analyze(&doc.content) else {
+                        return vec![];
+                    };
+                    let Some(route) = handler_file.route_at(position) else {
+                        return vec![];
+                    };
+                    let Some((spec_uri, _, spec)) = self.openapi_spec_for(&uri) else {
+                        return vec![];
+                    };
+                    route
+                        .methods
+                        .iter()
+                        .filter_map(|method| spec.operation(method, &route.path))
+                        .map(|operation| {
+                            lsp_types::Location::new(spec_uri.clone(), operation.range)
+                        })
+                        .collect()
+                }
+                // OpenAPI 规范中的操作 -> 路由处理器
+                "yaml" if is_openapi_spec(&uri) => {
+                    let spec = OpenApiSpec::parse(&doc.content);
+                    let Some(operation) = spec.operation_at(position) else {
+                        return vec![];
+                    };
+                    self.project_routes(&uri)
+                        .iter()
+                        .filter(|route| operation.matches(&route.method, &route.path))
+                        .filter_map(|route| {
+                            let uri = lsp_types::Url::parse(&route.location.uri).ok()?;
+                            Some(lsp_types::Location::new(
+                                uri,
+                                route_location_range(&route.location),
+                            ))
+                        })
+                        .collect()
+                }
+                _ => vec![],
+            })
+            .unwrap_or_default();
 
-        let mut symbols = Vec::new();
+        let result = GotoDefinitionResponse::Array(locations);
 
-        for config in configs {
-            // 过滤：如果有查询字符串，检查配置名称是否匹配
-            if !query.is_empty() && !config.name.to_lowercase().contains(query) {
-                continue;
-            }
+        let response = Response {
+            id: req.id,
+            result: Some(serde_json::to_value(result)?),
+            error: None,
+        };
 
-            // config.location 已经是 Option<lsp_types::Location>
-            if let Some(location) = config.location {
-                #[allow(deprecated)]
-                symbols.push(SymbolInformation {
-                    name: config.name.clone(),
-                    kind: SymbolKind::STRUCT,
-                    tags: None,
-                    deprecated: None,
-                    location,
-                    container_name: Some(format!("Config [{}]", config.prefix)),
-                });
-            }
-        }
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
 
-        Ok(symbols)
+        Ok(())
     }
 
-    /// 提取 TOML 文档符号
-    fn extract_toml_symbols(&self, content: &str) -> Vec<lsp_types::DocumentSymbol> {
-        use lsp_types::{DocumentSymbol, Position, Range, SymbolKind};
+    /// 处理类型定义跳转请求
+    ///
+    /// 从处理器参数的提取器跳转到泛型参数的类型定义（`Json<CreateUser>` -> `CreateUser`），
+    /// `Component<T>` 的类型不在项目中定义时跳转到提供它的 `#[component]` 函数
+    fn handle_goto_type_definition(&mut self, req: Request) -> Result<()> {
+        tracing::debug!("Handling goto type definition request");
 
-        let mut symbols = Vec::new();
+        let params: GotoDefinitionParams = serde_json::from_value(req.params)?;
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
 
-        // 使用 taplo 解析 TOML
-        let parse_result = taplo::parser::parse(content);
-        let root = parse_result.into_dom();
+        let target = self
+            .document_manager
+            .with_document(&uri, |doc| {
+                if doc.language_id != "rust" {
+                    return None;
+                }
+                let file = self.handler_analyzer.analyze(&doc.content).ok()?;
+                extractor_target(&file, position)
+            })
+            .flatten();
+
+        let mut locations = Vec::new();
+        if let Some(target) = target {
+            self.index_project_symbols(&uri);
+            locations = self
+                .index_manager
+                .find_symbol(&target.type_name)
+                .into_iter()
+                .filter(|symbol| {
+                    matches!(
+                        symbol.symbol_type,
+                        SymbolType::Struct | SymbolType::Enum | SymbolType::TypeAlias
+                    )
+                })
+                .map(|symbol| symbol.location)
+                .collect();
+            if locations.is_empty() && target.kind == ExtractorKind::Component {
+                let components = self.project_components(&uri);
+                locations = provider_functions(&components, &target.type_name)
+                    .into_iter()
+                    .filter_map(|component| {
+                        let uri = lsp_types::Url::parse(&component.location.uri).ok()?;
+                        Some(lsp_types::Location::new(
+                            uri,
+                            route_location_range(&component.location),
+                        ))
+                    })
+                    .collect();
+            }
+        }
 
-        // 检查根节点是否为表
-        if let taplo::dom::Node::Table(table) = root {
-            let entries = table.entries();
-            let entries_arc = entries.get();
+        let result = GotoDefinitionResponse::Array(locations);
+        let response = Response {
+            id: req.id,
+            result: Some(serde_json::to_value(result)?),
+            error: None,
+        };
 
-            // 遍历顶层表
-            for (key, value) in entries_arc.iter() {
-                let key_str = key.value().to_string();
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
 
-                // 获取键的位置信息（简化版，使用默认位置）
-                let key_range = Range {
-                    start: Position {
-                        line: 0,
-                        character: 0,
-                    },
-                    end: Position {
-                        line: 0,
-                        character: key_str.len() as u32,
-                    },
-                };
+        Ok(())
+    }
 
-                match value {
-                    taplo::dom::Node::Table(inner_table) => {
-                        // 配置节（表）
-                        let mut children = Vec::new();
+    /// 通过 `.route(...)` 注册的路由的处理器函数定义
+    ///
+    /// 在项目的符号索引中按函数名查找；同名函数有多个时优先使用完整路径匹配的函数
+    fn router_handler_locations(
+        &mut self,
+        uri: &lsp_types::Url,
+        routes: &[RouterRoute],
+    ) -> Vec<lsp_types::Location> {
+        self.index_project_symbols(uri);
+        let file = uri.to_file_path().ok();
+        let mut locations = Vec::new();
+        for route in routes {
+            let candidates: Vec<_> = self
+                .index_manager
+                .find_symbol(&route.handler)
+                .into_iter()
+                .filter(|symbol| symbol.symbol_type == SymbolType::Function)
+                .collect();
+            let qualified = file
+                .as_deref()
+                .and_then(|file| route.qualified_handler(file));
+            let exact: Vec<_> = candidates
+                .iter()
+                .filter(|symbol| qualified.is_some() && symbol.qualified_name == qualified)
+                .collect();
+            let matched = if exact.is_empty() {
+                candidates.iter().collect()
+            } else {
+                exact
+            };
+            for symbol in matched {
+                if !locations.contains(&symbol.location) {
+                    locations.push(symbol.location.clone());
+                }
+            }
+        }
+        locations
+    }
 
-                        // 提取表中的属性
-                        let inner_entries = inner_table.entries();
-                        let inner_entries_arc = inner_entries.get();
-                        for (prop_key, _prop_value) in inner_entries_arc.iter() {
-                            let prop_key_str = prop_key.value().to_string();
+    /// 把文档所属项目的源文件加入符号索引
+    ///
+    /// 每个项目只从磁盘读取一次，之后只重新索引打开的 Rust 文档（使用编辑器中的最新内容）
+    fn index_project_symbols(&mut self, uri: &lsp_types::Url) {
+        if let Some(root) = self.project_root_for(uri) {
+            if self.symbol_roots.insert(root.clone()) {
+                for sources in CrateSources::discover(&root) {
+                    for (file_uri, content) in &sources.files {
+                        self.index_manager.update(file_uri, &self.mapper(content));
+                    }
+                }
+            }
+        }
+        for open_uri in self.document_manager.uris() {
+            if let Some(doc) = self.document_manager.get(&open_uri) {
+                if doc.language_id == "rust" {
+                    self.index_manager.update(&open_uri, &doc.mapper());
+                }
+            }
+        }
+    }
 
-                            let prop_symbol = DocumentSymbol {
-                                name: prop_key_str.clone(),
-                                detail: Some("Property".to_string()),
-                                kind: SymbolKind::PROPERTY,
-                                tags: None,
-                                #[allow(deprecated)]
-                                deprecated: None,
-                                range: key_range,
-                                selection_range: key_range,
-                                children: None,
-                            };
-                            children.push(prop_symbol);
-                        }
+    /// 分析文档并生成诊断
+    pub fn analyze_document(&mut self, uri: &lsp_types::Url, language_id: &str) -> Result<()> {
+        tracing::debug!("Analyzing document: {} ({})", uri, language_id);
 
-                        let symbol = DocumentSymbol {
-                            name: key_str.clone(),
-                            detail: Some("Configuration section".to_string()),
-                            kind: SymbolKind::MODULE,
-                            tags: None,
-                            #[allow(deprecated)]
-                            deprecated: None,
-                            range: key_range,
-                            selection_range: key_range,
-                            children: if children.is_empty() {
-                                None
-                            } else {
-                                Some(children)
-                            },
-                        };
-                        symbols.push(symbol);
+        // 清除旧的诊断
+        self.diagnostic_engine.clear(uri);
+
+        let diagnostics = self
+            .document_manager
+            .with_document(uri, |doc| {
+                let pipeline = match language_id {
+                    "toml" => self.toml_pipeline(),
+                    "rust" => self.rust_pipeline(),
+                    "yaml" if is_openapi_spec(uri) => self.openapi_pipeline(),
+                    _ => {
+                        tracing::debug!("Unsupported language: {}", language_id);
+                        return vec![];
                     }
-                    taplo::dom::Node::Array(_) => {
-                        // 数组
-                        let symbol = DocumentSymbol {
-                            name: key_str.clone(),
-                            detail: Some("Array".to_string()),
-                            kind: SymbolKind::ARRAY,
-                            tags: None,
-                            #[allow(deprecated)]
-                            deprecated: None,
-                            range: key_range,
-                            selection_range: key_range,
-                            children: None,
-                        };
-                        symbols.push(symbol);
+                };
+
+                let mut ctx = AnalysisContext::with_lines(uri, doc.mapper(), &self.config);
+                if language_id == "rust" {
+                    let count = pipeline.run_cached(&mut ctx, &self.item_diagnostics);
+                    for _ in 0..count.reused {
+                        self.status.record_cache_hit(ITEM_CACHE);
                     }
-                    _ => {
-                        // 其他值类型（字符串、数字、布尔等）
-                        let symbol = DocumentSymbol {
-                            name: key_str.clone(),
-                            detail: Some("Property".to_string()),
-                            kind: SymbolKind::PROPERTY,
-                            tags: None,
-                            #[allow(deprecated)]
-                            deprecated: None,
-                            range: key_range,
-                            selection_range: key_range,
-                            children: None,
-                        };
-                        symbols.push(symbol);
+                    for _ in 0..count.analyzed {
+                        self.status.record_cache_miss(ITEM_CACHE);
                     }
+                } else {
+                    pipeline.run(&mut ctx);
                 }
-            }
-        }
+                let parse_failed = match language_id {
+                    "rust" => ctx.handlers.is_none(),
+                    "toml" => ctx.diagnostics.iter().any(|diagnostic| {
+                        diagnostic.code
+                            == Some(lsp_types::NumberOrString::String(
+                                TomlSyntaxError::CODE.to_string(),
+                            ))
+                    }),
+                    _ => false,
+                };
+                if parse_failed {
+                    self.status.record_parse_failure(language_id);
+                }
+                for timing in &ctx.timings {
+                    self.status
+                        .record_latency(&format!("analysis/{}", timing.name), timing.elapsed);
+                }
+                ctx.diagnostics
+            })
+            .unwrap_or_default();
 
-        symbols
-    }
+        // 过滤被禁用的诊断
+        let filtered_diagnostics: Vec<_> = diagnostics
+            .into_iter()
+            .filter(|diag| {
+                if let Some(lsp_types::NumberOrString::String(code)) = &diag.code {
+                    !self.config.diagnostics.is_disabled(code)
+                } else {
+                    true
+                }
+            })
+            .collect();
 
-    /// 提取 Rust 文档符号
-    fn extract_rust_symbols(&self, _content: &str) -> Vec<lsp_types::DocumentSymbol> {
-        use lsp_types::{DocumentSymbol, Range, SymbolKind};
+        // 合并最近一次依赖注入验证的诊断
+        let mut filtered_diagnostics = filtered_diagnostics;
+        if let Some(dependency_diagnostics) = self.dependency_diagnostics.get(uri) {
+            filtered_diagnostics.extend(dependency_diagnostics.iter().cloned());
+        }
 
-        let mut symbols = Vec::new();
+        // 过滤基线中已记录的诊断
+        let filtered_diagnostics = apply_baseline(
+            self.baseline.as_ref(),
+            self.workspace_path.as_deref(),
+            uri,
+            filtered_diagnostics,
+        );
 
-        // 使用 syn 解析 Rust 代码
-        let syntax = match syn::parse_file(_content) {
-            Ok(syntax) => syntax,
-            Err(e) => {
-                tracing::warn!("Failed to parse Rust file: {}", e);
-                return symbols;
-            }
-        };
+        // 添加诊断
+        for diagnostic in filtered_diagnostics {
+            self.diagnostic_engine.add(uri.clone(), diagnostic);
+        }
 
-        // 默认范围（因为 proc_macro2::Span 不提供行列信息）
-        let default_range = Range::default();
+        // 发布诊断（已包含队列中等待发布的内容）
+        let _ = self.diagnostic_engine.publish(&self.connection, uri);
+        self.diagnostic_publisher.discard(uri);
+        self.status.record_diagnostic();
 
-        // 遍历顶层项
-        for item in syntax.items {
-            match item {
-                syn::Item::Fn(item_fn) => {
-                    // 函数
-                    let name = item_fn.sig.ident.to_string();
+        Ok(())
+    }
 
-                    let symbol = DocumentSymbol {
-                        name: name.clone(),
-                        detail: Some(format!("fn {}", name)),
-                        kind: SymbolKind::FUNCTION,
-                        tags: None,
-                        #[allow(deprecated)]
-                        deprecated: None,
-                        range: default_range,
-                        selection_range: default_range,
-                        children: None,
-                    };
-                    symbols.push(symbol);
+    /// 配置文件的分析流水线（在内置步骤之后检查任务名称引用和配置结构体的必需字段）
+    fn toml_pipeline(&self) -> DocumentPipeline<'_> {
+        DocumentPipeline::toml(&self.toml_analyzer)
+            .with_pass(FnPass::new("job-names", PassStage::CrossRef, |ctx| {
+                // 只在配置引用了任务时扫描项目
+                let Some(doc) = &ctx.toml else {
+                    return;
+                };
+                if self.toml_analyzer.job_name_references(doc).is_empty() {
+                    return;
                 }
-                syn::Item::Struct(item_struct) => {
-                    // 结构体
-                    let name = item_struct.ident.to_string();
-
-                    let symbol = DocumentSymbol {
-                        name: name.clone(),
-                        detail: Some(format!("struct {}", name)),
-                        kind: SymbolKind::STRUCT,
-                        tags: None,
-                        #[allow(deprecated)]
-                        deprecated: None,
-                        range: default_range,
-                        selection_range: default_range,
-                        children: None,
-                    };
-                    symbols.push(symbol);
+                if let Some(index) = self.job_name_index_for(ctx.uri) {
+                    let diagnostics = self.toml_analyzer.validate_job_names(doc, &index.names());
+                    ctx.diagnostics.extend(diagnostics);
                 }
-                syn::Item::Enum(item_enum) => {
-                    // 枚举
-                    let name = item_enum.ident.to_string();
-
-                    let symbol = DocumentSymbol {
-                        name: name.clone(),
-                        detail: Some(format!("enum {}", name)),
-                        kind: SymbolKind::ENUM,
-                        tags: None,
-                        #[allow(deprecated)]
-                        deprecated: None,
-                        range: default_range,
-                        selection_range: default_range,
-                        children: None,
-                    };
-                    symbols.push(symbol);
+            }))
+            .with_pass(FnPass::new("config-fields", PassStage::CrossRef, |ctx| {
+                // 只检查 app.toml 和 profile 配置文件
+                let Some(doc) = &ctx.toml else {
+                    return;
+                };
+                if config_file_profile(ctx.uri).is_none() {
+                    return;
                 }
-                syn::Item::Trait(item_trait) => {
-                    // Trait
-                    let name = item_trait.ident.to_string();
+                let Some(root) = self.project_root_for(ctx.uri) else {
+                    return;
+                };
+                let configs = self.project_scans.configuration_structs(&root);
+                if configs.is_empty() {
+                    return;
+                }
+                // 修改的配置文件可能还没有保存到磁盘
+                let mut docs = self.crate_config_docs(ctx.uri);
+                if !docs.iter().any(|(config_uri, _)| config_uri == ctx.uri) {
+                    docs.push((ctx.uri.clone(), doc.clone()));
+                }
+                let diagnostics = ConfigFieldValidator::new()
+                    .with_locale(self.locale)
+                    .validate(&configs, &docs)
+                    .into_iter()
+                    .filter(|(uri, _)| uri == ctx.uri)
+                    .map(|(_, diagnostic)| diagnostic);
+                ctx.diagnostics.extend(diagnostics);
+            }))
+    }
 
-                    let symbol = DocumentSymbol {
-                        name: name.clone(),
-                        detail: Some(format!("trait {}", name)),
-                        kind: SymbolKind::INTERFACE,
-                        tags: None,
-                        #[allow(deprecated)]
-                        deprecated: None,
-                        range: default_range,
-                        selection_range: default_range,
-                        children: None,
-                    };
-                    symbols.push(symbol);
+    /// OpenAPI 规范文件的分析流水线（检查规范中的操作是否都有路由处理器）
+    fn openapi_pipeline(&self) -> DocumentPipeline<'_> {
+        DocumentPipeline::new().with_pass(FnPass::new("openapi-spec", PassStage::CrossRef, |ctx| {
+            let spec = OpenApiSpec::parse(ctx.content);
+            let spec_name = self.openapi_spec_name(ctx.uri);
+            let routes = self.project_routes(ctx.uri);
+            let diagnostics = OpenApiSpecValidator::new(&spec, &spec_name)
+                .with_locale(self.locale)
+                .validate_spec(&routes);
+            ctx.diagnostics.extend(diagnostics);
+        }))
+    }
+
+    /// Rust 文件的分析流水线（在内置步骤之后检查中间件规则、路由安全、处理器重名、路径段拼写、定时任务、
+    /// 配置结构体的必需字段和 OpenAPI 规范对照）
+    fn rust_pipeline(&self) -> DocumentPipeline<'_> {
+        // 处理器重名和路由重复注册检查共用同一份项目路由
+        let project_routes = Rc::new(OnceCell::new());
+        let shared_routes = Rc::clone(&project_routes);
+        DocumentPipeline::rust(&self.handler_analyzer)
+            .with_pass(FnPass::new(
+                "middleware-rules",
+                PassStage::CrossRef,
+                |ctx| {
+                    // 只在配置了规则时扫描项目
+                    if ctx.handlers.is_some() && !ctx.config.middleware.rules.is_empty() {
+                        let diagnostics = self.middleware_diagnostics(ctx.uri, ctx.content);
+                        ctx.diagnostics.extend(diagnostics);
+                    }
+                },
+            ))
+            .with_pass(FnPass::new("route-prefix", PassStage::Validate, |ctx| {
+                // 只在规则文件声明了路由前缀约定时检查
+                let (Some(rules), Some(root), Some(file)) =
+                    (&self.security_rules, &self.workspace_path, &ctx.handlers)
+                else {
+                    return;
+                };
+                let diagnostics = RoutePrefixValidator::new(rules)
+                    .with_locale(self.locale)
+                    .validate(root, ctx.uri, file);
+                ctx.diagnostics.extend(diagnostics);
+            }))
+            .with_pass(FnPass::new("route-security", PassStage::CrossRef, |ctx| {
+                // 只在存在安全规则时扫描项目
+                let Some(rules) = &self.security_rules else {
+                    return;
+                };
+                if ctx.handlers.is_none() {
+                    return;
                 }
-                syn::Item::Impl(item_impl) => {
-                    // Impl 块
-                    if let Some((_, path, _)) = &item_impl.trait_ {
-                        let name = quote::quote!(#path).to_string();
-                        let symbol = DocumentSymbol {
-                            name: format!("impl {}", name),
-                            detail: Some("Implementation".to_string()),
-                            kind: SymbolKind::CLASS,
-                            tags: None,
-                            #[allow(deprecated)]
-                            deprecated: None,
-                            range: default_range,
-                            selection_range: default_range,
-                            children: None,
-                        };
-                        symbols.push(symbol);
-                    } else if let syn::Type::Path(type_path) = &*item_impl.self_ty {
-                        let name = quote::quote!(#type_path).to_string();
-                        let symbol = DocumentSymbol {
-                            name: format!("impl {}", name),
-                            detail: Some("Implementation".to_string()),
-                            kind: SymbolKind::CLASS,
-                            tags: None,
-                            #[allow(deprecated)]
-                            deprecated: None,
-                            range: default_range,
-                            selection_range: default_range,
-                            children: None,
-                        };
-                        symbols.push(symbol);
+                let validator = RouteSecurityValidator::new(rules).with_locale(self.locale);
+                let diagnostics: Vec<_> = self
+                    .document_route_middlewares(ctx.uri, ctx.content)
+                    .iter()
+                    .flat_map(|route| validator.validate(route))
+                    .collect();
+                ctx.diagnostics.extend(diagnostics);
+            }))
+            .with_pass(FnPass::new(
+                "handler-names",
+                PassStage::CrossRef,
+                move |ctx| {
+                    // 只在文档定义了路由处理器时扫描项目
+                    let Some(file) = &ctx.handlers else {
+                        return;
+                    };
+                    if file
+                        .handlers
+                        .iter()
+                        .all(|handler| handler.routes.is_empty())
+                    {
+                        return;
+                    }
+                    let routes = project_routes.get_or_init(|| self.project_routes(ctx.uri));
+                    let diagnostics = HandlerNameValidator::new()
+                        .with_locale(self.locale)
+                        .validate(ctx.uri, file, routes);
+                    ctx.diagnostics.extend(diagnostics);
+                },
+            ))
+            .with_pass(FnPass::new(
+                "route-registration",
+                PassStage::CrossRef,
+                move |ctx| {
+                    // 只在文档声明或注册了路由时扫描项目
+                    let Ok(path) = ctx.uri.to_file_path() else {
+                        return;
+                    };
+                    let document_routes = crate::scanner::route::RouteScanner::new()
+                        .with_sources(self.sources())
+                        .scan_file(&path, ctx.content.to_string());
+                    if document_routes.is_empty() {
+                        return;
+                    }
+                    let routes = shared_routes.get_or_init(|| self.project_routes(ctx.uri));
+                    let diagnostics = RouteRegistrationValidator::new()
+                        .with_locale(self.locale)
+                        .validate(ctx.uri, &document_routes, routes);
+                    ctx.diagnostics.extend(diagnostics);
+                },
+            ))
+            .with_pass(FnPass::new(
+                "segment-spelling",
+                PassStage::CrossRef,
+                |ctx| {
+                    // 只在启用时扫描项目
+                    if !ctx.config.routes.segment_spelling {
+                        return;
+                    }
+                    if let Some(file) = &ctx.handlers {
+                        let diagnostics = self.segment_spelling_diagnostics(ctx.uri, file);
+                        ctx.diagnostics.extend(diagnostics);
                     }
+                },
+            ))
+            .with_pass(FnPass::new("job-schedule", PassStage::CrossRef, |ctx| {
+                // 只在文档定义了任务时扫描项目
+                let scanner = crate::scanner::job::JobScanner::new();
+                let document = [(ctx.uri.clone(), ctx.content.to_string())];
+                if scanner.scan_sources(&document).is_empty() {
+                    return;
                 }
-                _ => {
-                    // 其他项暂不处理
+                let Some(root) = self.project_root_for(ctx.uri) else {
+                    return;
+                };
+                let jobs = self.project_scans.jobs(&root);
+                let diagnostics = JobValidator::new()
+                    .with_locale(self.locale)
+                    .validate(&jobs)
+                    .into_iter()
+                    .filter(|(uri, _)| uri == ctx.uri)
+                    .map(|(_, diagnostic)| diagnostic);
+                ctx.diagnostics.extend(diagnostics);
+            }))
+            .with_pass(FnPass::new("config-fields", PassStage::CrossRef, |ctx| {
+                // 只在文档定义了配置结构体时读取配置文件
+                let configs = ConfigScanner::new()
+                    .with_sources(self.sources())
+                    .scan_content(ctx.uri, ctx.content);
+                if configs.is_empty() {
+                    return;
                 }
+                let docs = self.crate_config_docs(ctx.uri);
+                let diagnostics = ConfigFieldValidator::new()
+                    .with_locale(self.locale)
+                    .validate(&configs, &docs)
+                    .into_iter()
+                    .filter(|(uri, _)| uri == ctx.uri)
+                    .map(|(_, diagnostic)| diagnostic);
+                ctx.diagnostics.extend(diagnostics);
+            }))
+            .with_pass(FnPass::new("openapi-spec", PassStage::CrossRef, |ctx| {
+                // 只在项目中存在规范文件时检查
+                let Some(file) = &ctx.handlers else {
+                    return;
+                };
+                if let Some((_, spec_name, spec)) = self.openapi_spec_for(ctx.uri) {
+                    let diagnostics = OpenApiSpecValidator::new(&spec, &spec_name)
+                        .with_locale(self.locale)
+                        .validate_routes(file);
+                    ctx.diagnostics.extend(diagnostics);
+                }
+            }))
+    }
+
+    /// 文档所属项目的 OpenAPI 规范文件（URI、相对于项目根目录的名称和解析结果）
+    ///
+    /// 规范文件在编辑器中打开时使用编辑器中的内容
+    fn openapi_spec_for(
+        &self,
+        uri: &lsp_types::Url,
+    ) -> Option<(lsp_types::Url, String, OpenApiSpec)> {
+        let spec_path = OpenApiSpec::find(&self.project_root_for(uri)?)?;
+        let spec_uri = lsp_types::Url::from_file_path(&spec_path).ok()?;
+        let content = self.file_content(&spec_path)?;
+        let spec_name = self.openapi_spec_name(&spec_uri);
+        Some((spec_uri, spec_name, OpenApiSpec::parse(&content)))
+    }
+
+    /// 规范文件相对于项目根目录的名称（如 `docs/openapi.yaml`）
+    fn openapi_spec_name(&self, spec_uri: &lsp_types::Url) -> String {
+        let Ok(path) = spec_uri.to_file_path() else {
+            return spec_uri.to_string();
+        };
+        self.project_root_for(spec_uri)
+            .and_then(|root| path.strip_prefix(root).ok().map(Path::to_path_buf))
+            .unwrap_or_else(|| path.clone())
+            .display()
+            .to_string()
+    }
+
+    /// 文档所属项目中的所有路由（按文件缓存，只重新扫描修改过的文件）
+    fn project_routes(
+        &self,
+        uri: &lsp_types::Url,
+    ) -> Vec<crate::scanner::route::RouteInfoResponse> {
+        self.project_root_for(uri)
+            .map(|root| self.project_scans.routes(&root))
+            .unwrap_or_default()
+    }
+
+    /// 文档所属项目中的组件（包括生成代码中的组件）
+    fn project_components(
+        &self,
+        uri: &lsp_types::Url,
+    ) -> Vec<crate::scanner::component::ComponentInfoResponse> {
+        let Some(root) = self.project_root_for(uri) else {
+            return Vec::new();
+        };
+        let mut components = crate::scanner::component::ComponentScanner::new()
+            .with_sources(self.sources())
+            .scan_components(&root)
+            .unwrap_or_default();
+        components.extend(
+            self.generated_code
+                .components(&root, &self.config.index.generated_dirs),
+        );
+        components
+    }
+
+    /// 查找文档所属的项目根目录
+    ///
+    /// 从文档所在目录向上查找包含 `Cargo.toml` 和 `src` 的目录，找不到时使用工作空间根目录
+    fn project_root_for(&self, uri: &lsp_types::Url) -> Option<std::path::PathBuf> {
+        find_project_root(uri, self.workspace_path.as_deref())
+    }
+
+    /// 构建文档所属项目的任务名称索引
+    fn job_name_index_for(
+        &self,
+        uri: &lsp_types::Url,
+    ) -> Option<crate::scanner::job::JobNameIndex> {
+        let project_root = self.project_root_for(uri)?;
+
+        match crate::scanner::job::JobScanner::new()
+            .with_sources(self.sources())
+            .build_name_index(&project_root)
+        {
+            Ok(index) => Some(index),
+            Err(e) => {
+                tracing::debug!("Failed to build job name index: {}", e);
+                None
             }
         }
+    }
 
-        symbols
+    /// 分析文档中每个路由生效的中间件链
+    ///
+    /// 路由器级中间件来自文档本身和所属项目的其他文件
+    fn document_route_middlewares(
+        &self,
+        uri: &lsp_types::Url,
+        content: &str,
+    ) -> Vec<crate::scanner::middleware::RouteMiddlewares> {
+        route_middlewares(
+            &self.document_manager,
+            uri,
+            content,
+            self.project_root_for(uri).as_deref(),
+            &self.config.middleware.rules,
+        )
     }
 
-    /// 处理初始化请求
+    /// 生成缺少必需中间件的诊断
+    fn middleware_diagnostics(
+        &self,
+        uri: &lsp_types::Url,
+        content: &str,
+    ) -> Vec<lsp_types::Diagnostic> {
+        self.document_route_middlewares(uri, content)
+            .iter()
+            .flat_map(|route| route.missing_diagnostics(self.locale))
+            .collect()
+    }
+
+    /// 按项目的路径段统计检查文档中的路由
     ///
-    /// 声明服务器支持的所有能力，包括：
-    /// - 文档同步（增量更新）
-    /// - 智能补全（TOML 配置、宏参数、环境变量）
-    /// - 悬停提示（配置文档、宏展开、路由信息）
-    /// - 诊断（配置验证、路由验证、依赖注入验证）
-    /// - 定义跳转（路由导航）
-    /// - 文档符号（路由列表）
-    pub fn handle_initialize(&mut self, params: InitializeParams) -> Result<InitializeResult> {
-        use lsp_types::{
-            CompletionOptions, HoverProviderCapability, OneOf, TextDocumentSyncCapability,
-            TextDocumentSyncKind, TextDocumentSyncOptions, WorkDoneProgressOptions,
+    /// 其他文件的路由从磁盘扫描，当前文档使用编辑器中的内容
+    fn segment_spelling_diagnostics(
+        &self,
+        uri: &lsp_types::Url,
+        handler_file: &crate::analysis::rust::handler_analyzer::HandlerFile,
+    ) -> Vec<lsp_types::Diagnostic> {
+        use crate::analysis::validation::{SegmentStats, SegmentVocabularyValidator};
+        use crate::scanner::route::RouteScanner;
+
+        let project_routes = self
+            .project_root_for(uri)
+            .and_then(|root| {
+                RouteScanner::new()
+                    .with_sources(self.sources())
+                    .scan_routes(&root)
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        let mut stats = SegmentStats::from_paths(
+            project_routes
+                .iter()
+                .filter(|route| route.location.uri != uri.as_str())
+                .map(|route| route.path.as_str()),
+        );
+        for route in handler_file.handlers.iter().flat_map(|h| h.routes.iter()) {
+            stats.add_path(&route.path);
+        }
+
+        SegmentVocabularyValidator::new()
+            .with_locale(self.locale)
+            .validate(handler_file, &stats)
+    }
+
+    /// 读取项目的 `config/app.toml`（优先使用编辑器中打开的内容）
+    fn app_config_table(&self, project_root: &Path) -> Option<toml::Table> {
+        read_app_config(&self.document_manager, project_root)
+    }
+
+    /// 处理状态查询请求
+    ///
+    /// 返回服务器的运行状态和性能指标
+    fn handle_status_query(&self, req: Request) -> Result<()> {
+        tracing::debug!("Handling status query request");
+
+        let mut metrics = self.status.get_metrics();
+        metrics.memory = self.memory_usage();
+        let result = serde_json::to_value(metrics)?;
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
         };
 
-        // 如果客户端提供了工作空间路径，重新加载配置
-        #[allow(deprecated)]
-        if let Some(root_uri) = params.root_uri {
-            if let Ok(workspace_path) = root_uri.to_file_path() {
-                tracing::info!(
-                    "Loading configuration from workspace: {}",
-                    workspace_path.display()
-                );
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
 
-                // 存储 workspace_path
-                self.workspace_path = Some(workspace_path.clone());
+        Ok(())
+    }
 
-                self.config = ServerConfig::load(Some(&workspace_path));
+    /// 处理 workspace/executeCommand 请求
+    fn handle_execute_command(&mut self, req: Request) -> Result<()> {
+        let params: lsp_types::ExecuteCommandParams = serde_json::from_value(req.params)?;
+        tracing::debug!("Executing command: {}", params.command);
 
-                // 验证配置
-                if let Err(e) = self.config.validate() {
-                    tracing::error!("Invalid configuration: {}", e);
-                    return Err(Error::Config(e));
+        let result = match params.command.as_str() {
+            VERIFY_INDEX_COMMAND => {
+                let report = self.index_manager.verify_and_repair(&self.document_manager);
+                serde_json::to_value(report)?
+            }
+            ORGANIZE_ROUTES_COMMAND => {
+                let uri = params
+                    .arguments
+                    .first()
+                    .and_then(|arg| serde_json::from_value::<lsp_types::Url>(arg.clone()).ok());
+                let Some(uri) = uri else {
+                    return self.send_error_response(
+                        req.id,
+                        lsp_server::ErrorCode::InvalidParams as i32,
+                        format!(
+                            "{} expects a document URI argument",
+                            ORGANIZE_ROUTES_COMMAND
+                        ),
+                    );
+                };
+
+                match self.organize_routes(&uri) {
+                    Ok(edit) => serde_json::to_value(edit)?,
+                    Err(message) => {
+                        return self.send_error_response(
+                            req.id,
+                            lsp_server::ErrorCode::InvalidParams as i32,
+                            message,
+                        );
+                    }
                 }
+            }
+            SHOW_CONFIG_VALUE_COMMAND => {
+                let mut arguments = params.arguments.iter();
+                let uri = arguments
+                    .next()
+                    .and_then(|arg| serde_json::from_value::<lsp_types::Url>(arg.clone()).ok());
+                let range = arguments
+                    .next()
+                    .and_then(|arg| serde_json::from_value::<lsp_types::Range>(arg.clone()).ok());
+                let (Some(uri), Some(range)) = (uri, range) else {
+                    return self.send_error_response(
+                        req.id,
+                        lsp_server::ErrorCode::InvalidParams as i32,
+                        format!(
+                            "{} expects a document URI and a range argument",
+                            SHOW_CONFIG_VALUE_COMMAND
+                        ),
+                    );
+                };
 
-                tracing::info!("Configuration loaded successfully");
-                tracing::debug!(
-                    "Trigger characters: {:?}",
-                    self.config.completion.trigger_characters
-                );
-                tracing::debug!("Schema URL: {}", self.config.schema.url);
-                tracing::debug!(
-                    "Disabled diagnostics: {:?}",
-                    self.config.diagnostics.disabled
+                self.show_document(uri, range)?;
+                serde_json::Value::Null
+            }
+            SHOW_HEALTH_REPORT_COMMAND => {
+                let mut arguments = params.arguments.iter();
+                let uri = arguments
+                    .next()
+                    .and_then(|arg| serde_json::from_value::<lsp_types::Url>(arg.clone()).ok());
+                let category = arguments
+                    .next()
+                    .and_then(|arg| serde_json::from_value::<HealthCategory>(arg.clone()).ok());
+                let root = uri.as_ref().and_then(|uri| self.manifest_project_root(uri));
+                let (Some(root), Some(category)) = (root, category) else {
+                    return self.send_error_response(
+                        req.id,
+                        lsp_server::ErrorCode::InvalidParams as i32,
+                        format!(
+                            "{} expects a Cargo.toml URI and a health category argument",
+                            SHOW_HEALTH_REPORT_COMMAND
+                        ),
+                    );
+                };
+
+                let health = self.project_health(&root);
+                let locations = health.locations(category);
+                if let Some(first) = locations.first() {
+                    self.show_document(first.uri.clone(), first.range)?;
+                }
+                serde_json::to_value(locations)?
+            }
+            #[cfg(feature = "profiling")]
+            profiling::PROFILE_START_COMMAND => {
+                profiling::start();
+                serde_json::Value::Null
+            }
+            #[cfg(feature = "profiling")]
+            profiling::PROFILE_STOP_COMMAND => {
+                // 可选参数为输出目录
+                let dir = params
+                    .arguments
+                    .first()
+                    .and_then(|arg| arg.as_str())
+                    .map(PathBuf::from)
+                    .unwrap_or_else(std::env::temp_dir);
+                match profiling::stop(&dir) {
+                    Ok(report) => serde_json::to_value(report)?,
+                    Err(message) => {
+                        return self.send_error_response(
+                            req.id,
+                            lsp_server::ErrorCode::RequestFailed as i32,
+                            message,
+                        );
+                    }
+                }
+            }
+            UPDATE_BASELINE_COMMAND => match self.update_baseline() {
+                Ok(result) => result,
+                Err(message) => {
+                    return self.send_error_response(
+                        req.id,
+                        lsp_server::ErrorCode::RequestFailed as i32,
+                        message,
+                    );
+                }
+            },
+            _ => {
+                return self.send_error_response(
+                    req.id,
+                    lsp_server::ErrorCode::InvalidParams as i32,
+                    format!("Unknown command: {}", params.command),
                 );
             }
+        };
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 检查工作空间磁盘上的文件并重新生成诊断基线
+    ///
+    /// 生成后立即应用新基线，重新发布打开的文档和依赖注入验证涉及的文件的诊断
+    fn update_baseline(&mut self) -> std::result::Result<serde_json::Value, String> {
+        let root = self
+            .workspace_path
+            .clone()
+            .ok_or_else(|| "No workspace folder is open".to_string())?;
+
+        let checker = ProjectChecker::new((*self.schema_provider).clone(), self.config.clone());
+        let baseline = checker
+            .update_baseline(&root)
+            .map_err(|e| format!("Failed to update baseline: {}", e))?;
+        let count = baseline.len();
+        tracing::info!("Updated baseline with {} diagnostics", count);
+        self.baseline = Some(baseline);
+
+        let mut uris = self.document_manager.uris();
+        uris.extend(self.dependency_diagnostics.keys().cloned());
+        uris.sort();
+        uris.dedup();
+        for uri in uris {
+            if let Err(e) = self.republish_diagnostics(&uri) {
+                tracing::warn!("Failed to republish diagnostics for {}: {}", uri, e);
+            }
         }
 
-        Ok(InitializeResult {
-            capabilities: ServerCapabilities {
-                // 文档同步能力 - 支持增量更新
-                text_document_sync: Some(TextDocumentSyncCapability::Options(
-                    TextDocumentSyncOptions {
-                        open_close: Some(true),
-                        change: Some(TextDocumentSyncKind::INCREMENTAL),
-                        will_save: None,
-                        will_save_wait_until: None,
-                        save: None,
-                    },
-                )),
+        Ok(serde_json::json!({
+            "path": Baseline::path(&root),
+            "diagnostics": count,
+        }))
+    }
 
-                // 智能补全能力
-                // 支持 TOML 配置项、宏参数、环境变量补全
-                // 使用配置文件中的触发字符
-                completion_provider: Some(CompletionOptions {
-                    resolve_provider: Some(true),
-                    trigger_characters: Some(self.config.completion.trigger_characters.clone()),
+    /// 重新发布文档的诊断，包括未打开文件的依赖注入诊断
+    fn republish_diagnostics(&mut self, uri: &lsp_types::Url) -> Result<()> {
+        if self.document_manager.get(uri).is_some() {
+            return self.refresh_diagnostics(uri);
+        }
+
+        self.diagnostic_engine.clear(uri);
+        let diagnostics = self
+            .dependency_diagnostics
+            .get(uri)
+            .cloned()
+            .unwrap_or_default();
+        for diagnostic in apply_baseline(
+            self.baseline.as_ref(),
+            self.workspace_path.as_deref(),
+            uri,
+            diagnostics,
+        ) {
+            self.diagnostic_engine.add(uri.clone(), diagnostic);
+        }
+        self.diagnostic_engine.publish(&self.connection, uri)
+    }
+
+    /// 整理文件中的路由处理器，返回需要应用的 WorkspaceEdit
+    ///
+    /// 优先使用编辑器中打开的文档内容，否则读取磁盘文件；文件已经整理好时返回 `None`
+    fn organize_routes(
+        &self,
+        uri: &lsp_types::Url,
+    ) -> std::result::Result<Option<lsp_types::WorkspaceEdit>, String> {
+        let (content, version) = match self.document_manager.get(uri) {
+            Some(doc) => (doc.content, Some(doc.version)),
+            None => {
+                let path = uri
+                    .to_file_path()
+                    .map_err(|_| format!("Not a file URI: {}", uri))?;
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                (content, None)
+            }
+        };
+
+        let Some(edit) = RouteOrganizer::new()
+            .organize(&self.mapper(&content))
+            .map_err(|e| format!("Failed to parse {}: {}", uri, e))?
+        else {
+            return Ok(None);
+        };
+
+        let mut builder = WorkspaceEditBuilder::new(&self.document_manager);
+        builder
+            .edit(
+                uri,
+                version,
+                lsp_types::TextEdit {
+                    range: edit.range,
+                    new_text: edit.new_text,
+                },
+            )
+            .map_err(|e| e.to_string())?;
+        builder
+            .build(self.workspace_edit_support)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    }
+
+    /// 处理 spring/perfReport 请求
+    ///
+    /// 返回按方法统计的请求延迟直方图和缓存命中率
+    fn handle_perf_report_request(&self, req: Request) -> Result<()> {
+        tracing::debug!("Handling perf report request");
+
+        let mut report = self.status.get_perf_report();
+        let macro_cache = self.macro_analyzer.cache_stats();
+        report.caches.push(CacheStats {
+            name: MACRO_CACHE.to_string(),
+            hits: macro_cache.hits,
+            misses: macro_cache.misses,
+            hit_rate: macro_cache.hit_rate(),
+        });
+        let result = serde_json::to_value(report)?;
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 spring/routes 请求
+    ///
+    /// 扫描项目中的所有路由并返回路由列表
+    fn handle_routes_request(&self, req: Request) -> Result<()> {
+        tracing::info!("Handling spring/routes request");
+
+        use crate::scanner::route::{RouteScanner, RoutesRequest};
+
+        // 解析请求参数
+        let params: RoutesRequest = serde_json::from_value(req.params)?;
+        let project_path = std::path::Path::new(&params.app_path);
+
+        tracing::info!("Scanning routes in: {:?}", project_path);
+
+        // 创建路由扫描器
+        let scanner = RouteScanner::new().with_sources(self.sources());
+
+        // 逐个文件扫描路由
+        let scanned = match scanner.routes(project_path) {
+            Ok(routes) => Some(routes),
+            Err(e) => {
+                tracing::error!("Failed to scan routes: {}", e);
+                // 返回空列表而不是错误
+                None
+            }
+        };
+        let routes = scanned.into_iter().flatten().chain(
+            self.generated_code
+                .routes(project_path, &self.config.index.generated_dirs),
+        );
+
+        let result = match params.partial_result_token.clone() {
+            // 客户端支持部分结果：边扫描边分块发送路由列表，总数在最终响应中返回
+            Some(token) => {
+                let mut stream =
+                    PartialResultStream::new(&self.connection.sender, token, DEFAULT_CHUNK_SIZE)
+                        .with_field("routes");
+                let total = params.for_each_in_page(routes, |route| stream.push(route))?;
+                tracing::info!("Streamed {} routes", stream.items_sent());
+                let mut result = stream.finish()?;
+                result["total"] = serde_json::json!(total);
+                result
+            }
+            None => {
+                // 构建响应
+                let response_data = params.page(routes);
+
+                tracing::info!(
+                    "Sending response with {} routes",
+                    response_data.routes.len()
+                );
+
+                serde_json::to_value(response_data)?
+            }
+        };
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 spring/middlewares 请求
+    ///
+    /// 扫描项目中的所有路由，返回每个路由生效的中间件链和未满足的检查规则
+    fn handle_middlewares_request(&self, req: Request) -> Result<()> {
+        tracing::info!("Handling spring/middlewares request");
+
+        use crate::scanner::middleware::{
+            MiddlewareScanner, MiddlewaresRequest, MiddlewaresResponse,
+        };
+
+        let params: MiddlewaresRequest = serde_json::from_value(req.params)?;
+        let project_path = std::path::Path::new(&params.app_path);
+
+        let routes = match MiddlewareScanner::new()
+            .with_sources(self.sources())
+            .scan_middlewares(project_path, &self.config.middleware.rules)
+        {
+            Ok(routes) => routes,
+            Err(e) => {
+                tracing::error!("Failed to scan middlewares: {}", e);
+                // 返回空列表而不是错误
+                Vec::new()
+            }
+        };
+
+        let result = serde_json::to_value(MiddlewaresResponse { routes })?;
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 spring/stats 请求
+    ///
+    /// 汇总路由、组件、任务、配置项和诊断的数量，并返回与上一次请求相比的变化
+    fn handle_stats_request(&mut self, req: Request) -> Result<()> {
+        tracing::info!("Handling spring/stats request");
+
+        use crate::scanner::stats::{StatsRequest, StatsResponse};
+        use crate::scanner::{ComponentScanner, ConfigScanner, JobScanner, RouteScanner};
+
+        let params: StatsRequest = serde_json::from_value(req.params)?;
+        let project_path = Path::new(&params.app_path);
+        let generated_dirs = &self.config.index.generated_dirs;
+
+        let mut routes = RouteScanner::new()
+            .with_sources(self.sources())
+            .scan_routes(project_path)
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to scan routes: {}", e);
+                Vec::new()
+            });
+        routes.extend(self.generated_code.routes(project_path, generated_dirs));
+        let mut components = ComponentScanner::new()
+            .with_sources(self.sources())
+            .scan_components(project_path)
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to scan components: {}", e);
+                Vec::new()
+            });
+        components.extend(self.generated_code.components(project_path, generated_dirs));
+        let jobs = JobScanner::new()
+            .with_sources(self.sources())
+            .scan_jobs(project_path)
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to scan jobs: {}", e);
+                Vec::new()
+            });
+        let configurations = ConfigScanner::new()
+            .with_sources(self.sources())
+            .scan_configurations(project_path)
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to scan configurations: {}", e);
+                Vec::new()
+            });
+        let diagnostics = self.diagnostic_engine.counts_by_code(|uri| {
+            uri.to_file_path()
+                .is_ok_and(|path| path.starts_with(project_path))
+        });
+
+        let stats = WorkspaceStats::collect(
+            &routes,
+            &components,
+            &jobs,
+            &configurations,
+            self.app_config_table(project_path).as_ref(),
+            &self.schema_provider,
+            diagnostics,
+        );
+        let trends = self
+            .workspace_stats
+            .get(project_path)
+            .map(|previous| stats.trends(previous));
+        self.workspace_stats
+            .insert(project_path.to_path_buf(), stats.clone());
+
+        let response = Response {
+            id: req.id,
+            result: Some(serde_json::to_value(StatsResponse { stats, trends })?),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 spring/requestSample 请求
+    ///
+    /// 为指定方法和路径的路由生成 curl、HTTPie 和 reqwest 请求示例，
+    /// 未指定服务地址时使用 `config/app.toml` 中 `[web]` 配置的端口。找不到路由时返回 `null`
+    fn handle_request_sample_request(&self, req: Request) -> Result<()> {
+        tracing::info!("Handling spring/requestSample request");
+
+        use crate::scanner::request_sample::{RequestSampleGenerator, RequestSampleRequest};
+
+        let params: RequestSampleRequest = serde_json::from_value(req.params)?;
+        let project_path = Path::new(&params.app_path);
+        let base_url = params.base_url.clone().unwrap_or_else(|| {
+            let port = self
+                .app_config_table(project_path)
+                .as_ref()
+                .and_then(|table| table.get("web"))
+                .and_then(|web| web.get("port"))
+                .and_then(|port| port.as_integer())
+                .unwrap_or(8080);
+            format!("http://localhost:{}", port)
+        });
+
+        let sample = match RequestSampleGenerator::new()
+            .with_sources(self.sources())
+            .generate(project_path, &params.method, &params.path, &base_url)
+        {
+            Ok(sample) => sample,
+            Err(e) => {
+                tracing::error!("Failed to generate request sample: {}", e);
+                None
+            }
+        };
+
+        let response = Response {
+            id: req.id,
+            result: Some(serde_json::to_value(sample)?),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 spring/expandAllMacros 请求
+    ///
+    /// 返回把文件中所有 spring-rs 宏的展开代码插入到对应结构体或函数之后的文件内容，
+    /// 其余代码保持不变。文件未打开时从磁盘读取；不是 Rust 文件或无法解析时返回 `null`
+    fn handle_expand_all_macros_request(&self, req: Request) -> Result<()> {
+        tracing::info!("Handling spring/expandAllMacros request");
+
+        use crate::analysis::rust::macro_analyzer::{
+            ExpandAllMacrosRequest, ExpandAllMacrosResponse,
+        };
+
+        let params: ExpandAllMacrosRequest = serde_json::from_value(req.params)?;
+        let uri = params.text_document.uri;
+        let content = match uri.to_file_path() {
+            Ok(path) if path.extension().is_some_and(|ext| ext == "rs") => self.file_content(&path),
+            _ => None,
+        };
+
+        let expanded = content.and_then(|content| {
+            let doc = self.macro_analyzer.parse(uri.clone(), content).ok()?;
+            let doc = self.macro_analyzer.extract_macros(doc).ok()?;
+            match self.macro_analyzer.expand_document(&doc) {
+                Ok((content, expanded)) => Some(ExpandAllMacrosResponse { content, expanded }),
+                Err(e) => {
+                    tracing::error!("Failed to expand macros in {}: {}", uri, e);
+                    None
+                }
+            }
+        });
+
+        let response = Response {
+            id: req.id,
+            result: Some(serde_json::to_value(expanded)?),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 spring/validateDependencies 请求
+    ///
+    /// 创建项目级依赖注入验证任务，任务在事件循环空闲时逐个 crate 执行，
+    /// 每个批次完成后立即发布诊断，全部完成后才响应请求。
+    /// 新的验证请求会取消仍在运行的旧任务
+    fn handle_validate_dependencies_request(&mut self, req: Request) -> Result<()> {
+        tracing::info!("Handling spring/validateDependencies request");
+
+        let params: ValidateDependenciesRequest = serde_json::from_value(req.params)?;
+        let mut crates = CrateSources::discover(Path::new(&params.app_path));
+        for sources in &mut crates {
+            sources.overlay(&self.document_manager);
+        }
+        self.dependency_root = Some(Path::new(&params.app_path).to_path_buf());
+
+        // 任务在增量验证器上执行（内容没有变化的文件复用解析结果），完成后交还给编辑时的增量验证
+        let mut incremental = match self.dependency_validation.take() {
+            Some(previous) => {
+                previous.token.cancel();
+                self.send_error_response(
+                    previous.id,
+                    lsp_server::ErrorCode::RequestCanceled as i32,
+                    "Superseded by a new dependency validation".to_string(),
+                )?;
+                previous.task.into_incremental()
+            }
+            None => std::mem::replace(
+                &mut self.incremental_di,
+                IncrementalDiValidator::new(self.macro_analyzer.clone()),
+            ),
+        };
+        // 组件索引可能已经变化，重新计算所有注入诊断
+        incremental.invalidate();
+
+        // 清除上一次验证的诊断：打开的文档重新分析，未打开的文档排队发布空列表
+        let stale: Vec<_> = self
+            .dependency_diagnostics
+            .drain()
+            .map(|(uri, _)| uri)
+            .collect();
+        for uri in stale {
+            if self.document_manager.with_document(&uri, |_| ()).is_some() {
+                self.refresh_diagnostics(&uri)?;
+            } else {
+                self.diagnostic_engine.clear(&uri);
+                self.queue_diagnostics(uri);
+            }
+        }
+
+        self.dependencies_validated = true;
+        self.dependency_validation = Some(DependencyValidationRun {
+            id: req.id,
+            task: DiValidationTask::new(&crates, incremental),
+            token: CancellationToken::new(),
+            validator: DependencyInjectionValidator::new((*self.index_manager).clone())
+                .with_locale(self.locale),
+            diagnostics: 0,
+        });
+
+        Ok(())
+    }
+
+    /// 处理 `$/cancelRequest` 通知
+    ///
+    /// 只有依赖注入验证是长时间运行的请求，其他请求在收到取消通知前已经响应
+    fn handle_cancel_request(&mut self, params: lsp_types::CancelParams) {
+        let id: RequestId = match params.id {
+            lsp_types::NumberOrString::Number(id) => id.into(),
+            lsp_types::NumberOrString::String(id) => id.into(),
+        };
+
+        if let Some(run) = &self.dependency_validation {
+            if run.id == id {
+                tracing::info!("Cancelling dependency validation {:?}", id);
+                run.token.cancel();
+            }
+        }
+    }
+
+    /// 推进依赖注入验证任务一个批次
+    ///
+    /// 发布该批次的诊断；任务被取消或完成时响应请求并结束任务
+    fn step_dependency_validation(&mut self) -> Result<()> {
+        let Some(run) = self.dependency_validation.as_mut() else {
+            return Ok(());
+        };
+
+        match run
+            .task
+            .step(&run.validator, &self.toml_analyzer, &run.token)
+        {
+            DiTaskStep::Batch(batch) => {
+                tracing::debug!(
+                    "Dependency validation batch {:?} ({}/{} crates)",
+                    batch.scope,
+                    run.task.crates_done(),
+                    run.task.crates_total()
+                );
+
+                for (uri, diagnostics) in batch.diagnostics {
+                    let diagnostics: Vec<_> = diagnostics
+                        .into_iter()
+                        .filter(|diag| match &diag.code {
+                            Some(lsp_types::NumberOrString::String(code)) => {
+                                !self.config.diagnostics.is_disabled(code)
+                            }
+                            _ => true,
+                        })
+                        .collect();
+                    if diagnostics.is_empty() {
+                        continue;
+                    }
+
+                    // 保存完整的诊断，基线只影响发布的内容
+                    let published = apply_baseline(
+                        self.baseline.as_ref(),
+                        self.workspace_path.as_deref(),
+                        &uri,
+                        diagnostics.clone(),
+                    );
+                    self.dependency_diagnostics
+                        .entry(uri.clone())
+                        .or_default()
+                        .extend(diagnostics);
+                    if published.is_empty() {
+                        continue;
+                    }
+
+                    run.diagnostics += published.len();
+                    for diagnostic in published {
+                        self.diagnostic_engine.add(uri.clone(), diagnostic);
+                    }
+                    let open = self.document_manager.with_document(&uri, |_| ()).is_some();
+                    self.diagnostic_publisher.enqueue(uri, open);
+                }
+
+                self.flush_diagnostics()
+            }
+            DiTaskStep::Cancelled => {
+                let run = self
+                    .dependency_validation
+                    .take()
+                    .expect("validation is running");
+                tracing::info!(
+                    "Dependency validation cancelled after {}/{} crates",
+                    run.task.crates_done(),
+                    run.task.crates_total()
+                );
+                self.incremental_di = run.task.into_incremental();
+                self.send_error_response(
+                    run.id,
+                    lsp_server::ErrorCode::RequestCanceled as i32,
+                    "Dependency validation cancelled".to_string(),
+                )
+            }
+            DiTaskStep::Completed => {
+                let run = self
+                    .dependency_validation
+                    .take()
+                    .expect("validation is running");
+                let result = serde_json::to_value(ValidateDependenciesResponse {
+                    crates: run.task.crates_total(),
+                    diagnostics: run.diagnostics,
+                })?;
+                self.incremental_di = run.task.into_incremental();
+
+                self.connection
+                    .sender
+                    .send(Message::Response(Response {
+                        id: run.id,
+                        result: Some(result),
+                        error: None,
+                    }))
+                    .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+                Ok(())
+            }
+        }
+    }
+
+    /// 重新发布文档的诊断
+    ///
+    /// 打开的文档重新分析，未打开的文档发布空列表以清除诊断
+    fn refresh_diagnostics(&mut self, uri: &lsp_types::Url) -> Result<()> {
+        let language_id = self
+            .document_manager
+            .with_document(uri, |doc| doc.language_id.clone());
+
+        match language_id {
+            Some(language_id) => self.analyze_document(uri, &language_id),
+            None => {
+                self.diagnostic_engine.clear(uri);
+                self.diagnostic_engine.publish(&self.connection, uri)
+            }
+        }
+    }
+
+    /// 处理 spring/components 请求
+    ///
+    /// 扫描项目中的所有组件并返回组件列表
+    fn handle_components_request(&self, req: Request) -> Result<()> {
+        tracing::info!("Handling spring/components request");
+
+        use crate::scanner::component::{ComponentScanner, ComponentsRequest, ComponentsResponse};
+
+        // 解析请求参数
+        let params: ComponentsRequest = serde_json::from_value(req.params)?;
+        let project_path = std::path::Path::new(&params.app_path);
+
+        tracing::info!("Scanning components in: {:?}", project_path);
+        tracing::info!("Project path exists: {}", project_path.exists());
+        tracing::info!("Project path is dir: {}", project_path.is_dir());
+
+        // 创建组件扫描器
+        let scanner = ComponentScanner::new().with_sources(self.sources());
+
+        // 扫描组件
+        let mut components = match scanner.scan_components(project_path) {
+            Ok(components) => {
+                tracing::info!("Successfully scanned {} components", components.len());
+                components
+            }
+            Err(e) => {
+                tracing::error!("Failed to scan components: {}", e);
+                tracing::error!("Error details: {:?}", e);
+                // 返回空列表而不是错误
+                Vec::new()
+            }
+        };
+        components.extend(
+            self.generated_code
+                .components(project_path, &self.config.index.generated_dirs),
+        );
+
+        // 构建响应
+        let response_data = ComponentsResponse { components };
+
+        tracing::info!(
+            "Sending response with {} components",
+            response_data.components.len()
+        );
+
+        let result = serde_json::to_value(response_data)?;
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 spring/jobs 请求
+    ///
+    /// 扫描项目中的所有定时任务并返回任务列表
+    fn handle_jobs_request(&self, req: Request) -> Result<()> {
+        tracing::info!("Handling spring/jobs request");
+
+        use crate::scanner::job::{JobScanner, JobsRequest, JobsResponse};
+
+        // 解析请求参数
+        let params: JobsRequest = serde_json::from_value(req.params)?;
+        let project_path = std::path::Path::new(&params.app_path);
+
+        tracing::info!("Scanning jobs in: {:?}", project_path);
+
+        // 创建任务扫描器
+        let scanner = JobScanner::new().with_sources(self.sources());
+
+        // 扫描任务
+        let jobs = match scanner.scan_jobs(project_path) {
+            Ok(jobs) => {
+                tracing::info!("Successfully scanned {} jobs", jobs.len());
+                jobs
+            }
+            Err(e) => {
+                tracing::error!("Failed to scan jobs: {}", e);
+                // 返回空列表而不是错误
+                Vec::new()
+            }
+        };
+
+        // 构建响应
+        let response_data = JobsResponse { jobs };
+
+        tracing::info!("Sending response with {} jobs", response_data.jobs.len());
+
+        let result = serde_json::to_value(response_data)?;
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 spring/listRules 请求
+    ///
+    /// 返回所有诊断规则（ID、描述、默认级别、是否可快速修复、文档地址）及其在当前配置下是否启用，
+    /// 供编辑器扩展构建规则配置界面
+    fn handle_list_rules_request(&self, req: Request) -> Result<()> {
+        tracing::debug!("Handling spring/listRules request");
+
+        let result = ListRulesResponse {
+            rules: DiagnosticEngine::rule_catalog(&self.config.diagnostics, self.locale),
+        };
+        let response = Response {
+            id: req.id,
+            result: Some(serde_json::to_value(result)?),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 spring/plugins 请求
+    ///
+    /// 扫描项目中的所有插件并返回插件列表
+    fn handle_plugins_request(&self, req: Request) -> Result<()> {
+        tracing::info!("Handling spring/plugins request");
+
+        use crate::scanner::plugin::{PluginScanner, PluginsRequest, PluginsResponse};
+
+        // 解析请求参数
+        let params: PluginsRequest = serde_json::from_value(req.params)?;
+        let project_path = std::path::Path::new(&params.app_path);
+
+        tracing::info!("Scanning plugins in: {:?}", project_path);
+
+        // 创建插件扫描器
+        let scanner = PluginScanner::new().with_sources(self.sources());
+
+        // 扫描插件
+        let plugins = match scanner.scan_plugins(project_path) {
+            Ok(plugins) => {
+                tracing::info!("Successfully scanned {} plugins", plugins.len());
+                plugins
+            }
+            Err(e) => {
+                tracing::error!("Failed to scan plugins: {}", e);
+                // 返回空列表而不是错误
+                Vec::new()
+            }
+        };
+
+        // 构建响应
+        let response_data = PluginsResponse { plugins };
+
+        tracing::info!(
+            "Sending response with {} plugins",
+            response_data.plugins.len()
+        );
+
+        let result = serde_json::to_value(response_data)?;
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 spring/configurations 请求
+    ///
+    /// 扫描项目中的所有配置结构并返回配置列表
+    fn handle_configurations_request(&self, req: Request) -> Result<()> {
+        tracing::debug!("Handling spring/configurations request");
+
+        use crate::scanner::config::{
+            ConfigScanner, ConfigurationsRequest, ConfigurationsResponse,
+        };
+
+        // 解析请求参数
+        let params: ConfigurationsRequest = serde_json::from_value(req.params)?;
+        let project_path = std::path::Path::new(&params.app_path);
+
+        // 创建配置扫描器
+        let scanner = ConfigScanner::new().with_sources(self.sources());
+
+        // 扫描配置
+        let configurations = match scanner.scan_configurations(project_path) {
+            Ok(configurations) => configurations,
+            Err(e) => {
+                tracing::error!("Failed to scan configurations: {}", e);
+                // 返回空列表而不是错误
+                Vec::new()
+            }
+        };
+
+        // 构建响应
+        let response_data = ConfigurationsResponse { configurations };
+        let result = serde_json::to_value(response_data)?;
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理 textDocument/codeAction 请求
+    ///
+    /// 为请求中附带快速修复（`Diagnostic::data`）的诊断生成 quickfix 代码操作，
+    /// 附带多个快速修复时第一个标记为首选
+    fn handle_code_action(&self, req: Request) -> Result<()> {
+        tracing::debug!("Handling textDocument/codeAction request");
+
+        let params: lsp_types::CodeActionParams = serde_json::from_value(req.params)?;
+        let uri = &params.text_document.uri;
+        let version = self.document_manager.get(uri).map(|doc| doc.version);
+
+        let mut actions = Vec::new();
+        for diagnostic in &params.context.diagnostics {
+            if diagnostic.source.as_deref() != Some("spring-lsp") {
+                continue;
+            }
+            for (index, fix) in QuickFix::all_from_diagnostic(diagnostic)
+                .into_iter()
+                .enumerate()
+            {
+                let mut builder = WorkspaceEditBuilder::new(&self.document_manager);
+                let edit = builder
+                    .edit(
+                        uri,
+                        version,
+                        lsp_types::TextEdit {
+                            range: fix.range.unwrap_or(diagnostic.range),
+                            new_text: fix.new_text,
+                        },
+                    )
+                    .ok()
+                    .and_then(|_| builder.build(self.workspace_edit_support).ok());
+                let Some(edit) = edit else {
+                    continue;
+                };
+
+                actions.push(lsp_types::CodeActionOrCommand::CodeAction(
+                    lsp_types::CodeAction {
+                        title: fix.title,
+                        kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+                        diagnostics: Some(vec![diagnostic.clone()]),
+                        edit: Some(edit),
+                        is_preferred: Some(index == 0),
+                        ..Default::default()
+                    },
+                ));
+            }
+            if let Some(missing) = MissingComponent::from_diagnostic(diagnostic) {
+                actions.extend(self.register_component_actions(uri, version, diagnostic, &missing));
+            }
+        }
+
+        let response = Response {
+            id: req.id,
+            result: Some(serde_json::to_value(actions)?),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 注册缺失组件的代码操作：在应用入口调用 `add_component`，或添加提供组件的函数
+    fn register_component_actions(
+        &self,
+        uri: &lsp_types::Url,
+        version: Option<i32>,
+        diagnostic: &lsp_types::Diagnostic,
+        missing: &MissingComponent,
+    ) -> Vec<lsp_types::CodeActionOrCommand> {
+        let mut edits = Vec::new();
+        if let Some((entry, edit)) =
+            missing.add_component_edit(&self.crate_sources(uri), self.position_encoding)
+        {
+            let entry_version = if &entry == uri { version } else { None };
+            let call = edit.new_text.trim_start().to_string();
+            edits.push((
+                localized(self.locale, "component-not-registered.add", &[&call]),
+                entry,
+                entry_version,
+                edit,
+            ));
+        }
+        if let Some(edit) = self
+            .document_manager
+            .with_document(uri, |doc| missing.provider_edit(&doc.mapper()))
+        {
+            edits.push((
+                localized(
+                    self.locale,
+                    "component-not-registered.provider",
+                    &[&missing.component_type],
+                ),
+                uri.clone(),
+                version,
+                edit,
+            ));
+        }
+
+        edits
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, (title, target, target_version, edit))| {
+                let mut builder = WorkspaceEditBuilder::new(&self.document_manager);
+                builder.edit(&target, target_version, edit).ok()?;
+                let edit = builder.build(self.workspace_edit_support).ok()?;
+                Some(lsp_types::CodeActionOrCommand::CodeAction(
+                    lsp_types::CodeAction {
+                        title,
+                        kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+                        diagnostics: Some(vec![diagnostic.clone()]),
+                        edit: Some(edit),
+                        is_preferred: Some(index == 0),
+                        ..Default::default()
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// 处理 textDocument/documentSymbol 请求
+    ///
+    /// 提取文档中的符号（配置节、属性、函数、结构体等）用于大纲视图
+    fn handle_document_symbol(&self, req: Request) -> Result<()> {
+        tracing::debug!("Handling textDocument/documentSymbol request");
+
+        let params: DocumentSymbolParams = serde_json::from_value(req.params)?;
+        let uri = &params.text_document.uri;
+
+        let symbols = self.document_manager.with_document(uri, |doc| {
+            match doc.language_id.as_str() {
+                "toml" => {
+                    // TOML 文档符号提取
+                    self.extract_toml_symbols(&doc.content)
+                }
+                "rust" => {
+                    // Rust 文档符号提取
+                    self.extract_rust_symbols(&doc.content)
+                }
+                _ => {
+                    tracing::debug!(
+                        "Unsupported language for document symbols: {}",
+                        doc.language_id
+                    );
+                    vec![]
+                }
+            }
+        });
+
+        let result = match symbols {
+            Some(symbols) => serde_json::to_value(DocumentSymbolResponse::Nested(symbols))?,
+            None => serde_json::Value::Null,
+        };
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理文档颜色请求
+    ///
+    /// 为 TOML 配置中 Schema 声明为颜色（`"format": "color"`）的配置值显示色块
+    fn handle_document_color(&self, req: Request) -> Result<()> {
+        tracing::debug!("Handling textDocument/documentColor request");
+
+        let params: lsp_types::DocumentColorParams = serde_json::from_value(req.params)?;
+        let colors = self
+            .document_manager
+            .with_document(&params.text_document.uri, |doc| {
+                if doc.language_id != "toml" {
+                    return Vec::new();
+                }
+                let (toml_doc, _) = self.toml_analyzer.parse_recovering(&doc.content);
+                color_value::document_colors(&toml_doc, &self.schema_provider)
+            })
+            .unwrap_or_default();
+
+        let response = Response {
+            id: req.id,
+            result: Some(serde_json::to_value(colors)?),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理颜色表示请求
+    ///
+    /// 用户在编辑器的取色器中选择颜色后，返回可替换到配置值中的写法
+    fn handle_color_presentation(&self, req: Request) -> Result<()> {
+        tracing::debug!("Handling textDocument/colorPresentation request");
+
+        let params: lsp_types::ColorPresentationParams = serde_json::from_value(req.params)?;
+        let presentations = color_value::color_presentations(params.color);
+
+        let response = Response {
+            id: req.id,
+            result: Some(serde_json::to_value(presentations)?),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 处理代码透镜请求
+    ///
+    /// 在未实现的路由处理器（函数体只有 `todo!()`、`unimplemented!()` 等）上显示 stub 标记，
+    /// 在配置结构体（`#[derive(Configurable)]`）的字段上显示配置文件中的当前值
+    fn handle_code_lens(&self, req: Request) -> Result<()> {
+        tracing::debug!("Handling textDocument/codeLens request");
+
+        let params: lsp_types::CodeLensParams = serde_json::from_value(req.params)?;
+        let uri = &params.text_document.uri;
+        let document = self.document_manager.with_document(uri, |doc| {
+            if doc.language_id != "rust" {
+                return None;
+            }
+            let lenses: Vec<lsp_types::CodeLens> = self
+                .handler_analyzer
+                .analyze_lines(&doc.mapper())
+                .map(|file| {
+                    file.handlers
+                        .iter()
+                        .filter(|handler| handler.stub)
+                        .map(|handler| lsp_types::CodeLens {
+                            range: handler.range,
+                            command: Some(lsp_types::Command {
+                                title: stub_lens_title(handler),
+                                command: String::new(),
+                                arguments: None,
+                            }),
+                            data: None,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some((lenses, doc.content.clone()))
+        });
+        let lenses = match document.flatten() {
+            Some((mut lenses, content)) => {
+                lenses.extend(self.config_value_lenses(uri, &content));
+                lenses
+            }
+            None => match self.manifest_project_root(uri) {
+                Some(root) => self.health_lenses(&root),
+                None => Vec::new(),
+            },
+        };
+
+        let response = Response {
+            id: req.id,
+            result: Some(serde_json::to_value(lenses)?),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// `Cargo.toml` 所在的项目根目录，文档不是项目的 `Cargo.toml` 时返回 `None`
+    fn manifest_project_root(&self, uri: &lsp_types::Url) -> Option<PathBuf> {
+        let path = uri.to_file_path().ok()?;
+        if path.file_name()? != "Cargo.toml" {
+            return None;
+        }
+        let root = path.parent()?;
+        (self.project_root_for(uri).as_deref() == Some(root)).then(|| root.to_path_buf())
+    }
+
+    /// 项目健康摘要
+    ///
+    /// 路由冲突和无法解析的注入来自已发布的诊断（已分析的文档），
+    /// 未使用的配置项来自 `config/app.toml` 与项目中的配置结构体和插件 Schema 的比较
+    fn project_health(&self, root: &Path) -> ProjectHealth {
+        let in_project =
+            |uri: &lsp_types::Url| uri.to_file_path().is_ok_and(|path| path.starts_with(root));
+        let mut health = ProjectHealth {
+            route_conflicts: self.diagnostic_engine.locations_with_codes(
+                in_project,
+                HealthCategory::RouteConflicts.diagnostic_codes(),
+            ),
+            unresolved_injections: self.diagnostic_engine.locations_with_codes(
+                in_project,
+                HealthCategory::UnresolvedInjections.diagnostic_codes(),
+            ),
+            unused_config_keys: Vec::new(),
+        };
+
+        let app_path = root.join("config/app.toml");
+        let app_doc = self
+            .file_content(&app_path)
+            .and_then(|content| self.toml_analyzer.parse(&content).ok());
+        let app_uri = lsp_types::Url::from_file_path(&app_path).ok();
+        if let (Some(app_doc), Some(app_uri)) = (app_doc, app_uri) {
+            let configurations = ConfigScanner::new()
+                .with_sources(self.sources())
+                .scan_configurations(root)
+                .unwrap_or_default();
+            let stats = config_key_stats(
+                self.app_config_table(root).as_ref(),
+                &configurations,
+                &self.schema_provider,
+            );
+            health.unused_config_keys = stats
+                .unused
+                .iter()
+                .filter_map(|name| {
+                    let (prefix, key) = name.split_once('.')?;
+                    let property = app_doc.config_sections.get(prefix)?.properties.get(key)?;
+                    Some(lsp_types::Location::new(app_uri.clone(), property.range))
+                })
+                .collect();
+        }
+        health
+    }
+
+    /// 项目 `Cargo.toml` 第一行上的健康摘要代码透镜
+    ///
+    /// 每个类别一个透镜，有问题的类别点击后打开问题位置；没有任何问题时只显示一个透镜
+    fn health_lenses(&self, root: &Path) -> Vec<lsp_types::CodeLens> {
+        let health = self.project_health(root);
+        let range = lsp_types::Range::default();
+        if health.is_healthy() {
+            return vec![lsp_types::CodeLens {
+                range,
+                command: Some(lsp_types::Command {
+                    title: localized(self.locale, "health.healthy", &[]),
+                    command: String::new(),
+                    arguments: None,
+                }),
+                data: None,
+            }];
+        }
+
+        let Ok(manifest) = lsp_types::Url::from_file_path(root.join("Cargo.toml")) else {
+            return Vec::new();
+        };
+        HealthCategory::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, category)| {
+                let count = health.locations(*category).len();
+                let mut title = category.describe(count, self.locale);
+                if index == 0 {
+                    title = localized(self.locale, "health.title", &[&title]);
+                }
+                let (command, arguments) = if count > 0 {
+                    (
+                        SHOW_HEALTH_REPORT_COMMAND.to_string(),
+                        Some(vec![
+                            serde_json::json!(manifest),
+                            serde_json::json!(category),
+                        ]),
+                    )
+                } else {
+                    (String::new(), None)
+                };
+                lsp_types::CodeLens {
+                    range,
+                    command: Some(lsp_types::Command {
+                        title,
+                        command,
+                        arguments,
+                    }),
+                    data: None,
+                }
+            })
+            .collect()
+    }
+
+    /// 配置结构体字段上的配置值代码透镜
+    ///
+    /// 每个定义了该配置项的配置文件（`app.toml` 和 profile 配置文件）一个透镜，点击后跳转到配置值；
+    /// 没有配置文件定义该配置项时显示使用默认值
+    fn config_value_lenses(&self, uri: &lsp_types::Url, content: &str) -> Vec<lsp_types::CodeLens> {
+        let configs = ConfigScanner::new()
+            .with_sources(self.sources())
+            .scan_content(uri, content);
+        if configs.is_empty() {
+            return Vec::new();
+        }
+        let docs = self.crate_config_docs(uri);
+
+        let mut lenses = Vec::new();
+        for config in &configs {
+            for field in &config.fields {
+                let sources = config_value_sources(&config.prefix, &field.name, &docs);
+                if sources.is_empty() {
+                    lenses.push(lsp_types::CodeLens {
+                        range: field.range,
+                        command: Some(lsp_types::Command {
+                            title: localized(self.locale, "lens.config.unset", &[]),
+                            command: String::new(),
+                            arguments: None,
+                        }),
+                        data: None,
+                    });
+                }
+                for source in sources {
+                    lenses.push(lsp_types::CodeLens {
+                        range: field.range,
+                        command: Some(lsp_types::Command {
+                            title: config_lens_title(&source),
+                            command: SHOW_CONFIG_VALUE_COMMAND.to_string(),
+                            arguments: Some(vec![
+                                serde_json::json!(source.uri),
+                                serde_json::json!(source.range),
+                            ]),
+                        }),
+                        data: None,
+                    });
+                }
+            }
+        }
+        lenses
+    }
+
+    /// 请求客户端打开文档并选中指定范围（`window/showDocument`）
+    fn show_document(&self, uri: lsp_types::Url, range: lsp_types::Range) -> Result<()> {
+        let params = lsp_types::ShowDocumentParams {
+            uri,
+            external: Some(false),
+            take_focus: Some(true),
+            selection: Some(range),
+        };
+        let request = Request::new(
+            RequestId::from(SHOW_DOCUMENT_REQUEST.to_string()),
+            ShowDocument::METHOD.to_string(),
+            params,
+        );
+        self.connection
+            .sender
+            .send(Message::Request(request))
+            .map_err(|e| Error::MessageSend(e.to_string()))
+    }
+
+    /// 处理 workspace/symbol 请求
+    ///
+    /// 在整个工作空间中搜索符号（组件、路由、配置等）
+    fn handle_workspace_symbol(&self, req: Request) -> Result<()> {
+        tracing::debug!("Handling workspace/symbol request");
+
+        // 解析请求参数
+        let params: serde_json::Value = req.params;
+        let query = params
+            .get("query")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        tracing::debug!("Workspace symbol query: '{}'", query);
+
+        // 客户端提供部分结果令牌时，搜索过程中每满一个分块就发送
+        let mut stream = partial_result_token(&params).map(|token| {
+            PartialResultStream::new(&self.connection.sender, token, DEFAULT_CHUNK_SIZE)
+        });
+        let mut symbols: Vec<lsp_types::SymbolInformation> = vec![];
+
+        // 从 workspace_path 获取工作空间路径
+        if let Some(workspace_path) = &self.workspace_path {
+            let mut emit = |symbol| match stream.as_mut() {
+                Some(stream) => stream.push(symbol),
+                None => {
+                    symbols.push(symbol);
+                    Ok(())
+                }
+            };
+            // 搜索组件
+            self.search_component_symbols(workspace_path, &query, &mut emit)?;
+            // 搜索路由
+            self.search_route_symbols(workspace_path, &query, &mut emit)?;
+            // 搜索配置
+            self.search_config_symbols(workspace_path, &query, &mut emit)?;
+        }
+
+        let result = match stream {
+            Some(stream) => {
+                tracing::debug!(
+                    "Streamed {} workspace symbols matching '{}'",
+                    stream.items_sent(),
+                    query
+                );
+                stream.finish()?
+            }
+            None => {
+                tracing::debug!(
+                    "Found {} workspace symbols matching '{}'",
+                    symbols.len(),
+                    query
+                );
+                serde_json::to_value(symbols)?
+            }
+        };
+
+        let response = Response {
+            id: req.id,
+            result: Some(result),
+            error: None,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 搜索组件符号，匹配的符号边扫描边交给 `emit`
+    fn search_component_symbols(
+        &self,
+        workspace_path: &std::path::Path,
+        query: &str,
+        emit: &mut dyn FnMut(lsp_types::SymbolInformation) -> Result<()>,
+    ) -> Result<()> {
+        use crate::scanner::component::{ComponentScanner, ComponentSource};
+        use lsp_types::{Location, Position, Range, SymbolInformation, SymbolKind, SymbolTag, Url};
+
+        let scanner = ComponentScanner::new().with_sources(self.sources());
+        let components = scanner.components(workspace_path).chain(
+            self.generated_code
+                .components(workspace_path, &self.config.index.generated_dirs),
+        );
+
+        for component in components {
+            // 过滤：如果有查询字符串，检查组件名称或完整路径是否匹配
+            let qualified = component.qualified_name.as_deref().unwrap_or_default();
+            if !query.is_empty()
+                && !component.name.to_lowercase().contains(query)
+                && !qualified.to_lowercase().contains(query)
+            {
+                continue;
+            }
+
+            // 转换 LocationResponse 到 lsp_types::Location
+            let uri = match Url::parse(&component.location.uri) {
+                Ok(uri) => uri,
+                Err(e) => {
+                    tracing::warn!("Invalid URI {}: {}", component.location.uri, e);
+                    continue;
+                }
+            };
+
+            let range = Range {
+                start: Position {
+                    line: component.location.range.start.line,
+                    character: component.location.range.start.character,
+                },
+                end: Position {
+                    line: component.location.range.end.line,
+                    character: component.location.range.end.character,
+                },
+            };
+
+            // 根据组件来源使用不同的图标
+            // #[component] 使用 METHOD (symbol-method)
+            // #[derive(Service)] 使用 CLASS (symbol-class)
+            let kind = match component.source {
+                ComponentSource::Component => SymbolKind::METHOD,
+                ComponentSource::Service => SymbolKind::CLASS,
+            };
+
+            #[allow(deprecated)]
+            emit(SymbolInformation {
+                name: component.name.clone(),
+                kind,
+                tags: component.deprecated.then(|| vec![SymbolTag::DEPRECATED]),
+                deprecated: None,
+                location: Location { uri, range },
+                container_name: Some(format!(
+                    "Component ({})",
+                    component
+                        .qualified_name
+                        .as_deref()
+                        .unwrap_or(&component.type_name)
+                )),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// 搜索路由符号，匹配的符号边扫描边交给 `emit`
+    fn search_route_symbols(
+        &self,
+        workspace_path: &std::path::Path,
+        query: &str,
+        emit: &mut dyn FnMut(lsp_types::SymbolInformation) -> Result<()>,
+    ) -> Result<()> {
+        use crate::scanner::route::RouteScanner;
+        use lsp_types::{Location, Position, Range, SymbolInformation, SymbolKind, SymbolTag, Url};
+
+        let scanner = RouteScanner::new().with_sources(self.sources());
+        let scanned = match scanner.routes(workspace_path) {
+            Ok(routes) => Some(routes),
+            Err(e) => {
+                tracing::debug!("Failed to scan routes: {}", e);
+                None
+            }
+        };
+        let routes = scanned.into_iter().flatten().chain(
+            self.generated_code
+                .routes(workspace_path, &self.config.index.generated_dirs),
+        );
+
+        for route in routes {
+            // 构建搜索文本：方法 + 路径，以及处理器的完整路径
+            let handler = route.handler_path.as_deref().unwrap_or(&route.handler);
+            let search_text = format!("{} {}", route.method, route.path).to_lowercase();
+
+            // 过滤：如果有查询字符串，检查路由或处理器是否匹配
+            if !query.is_empty()
+                && !search_text.contains(query)
+                && !handler.to_lowercase().contains(query)
+            {
+                continue;
+            }
+
+            // 转换 LocationResponse 到 lsp_types::Location
+            let uri = match Url::parse(&route.location.uri) {
+                Ok(uri) => uri,
+                Err(e) => {
+                    tracing::warn!("Invalid URI {}: {}", route.location.uri, e);
+                    continue;
+                }
+            };
+
+            let range = Range {
+                start: Position {
+                    line: route.location.range.start.line,
+                    character: route.location.range.start.character,
+                },
+                end: Position {
+                    line: route.location.range.end.line,
+                    character: route.location.range.end.character,
+                },
+            };
+
+            #[allow(deprecated)]
+            emit(SymbolInformation {
+                name: format!("{} {}", route.method, route.path),
+                kind: SymbolKind::FUNCTION,
+                tags: route.deprecated.then(|| vec![SymbolTag::DEPRECATED]),
+                deprecated: None,
+                location: Location { uri, range },
+                container_name: Some(match &route.cfg {
+                    Some(cfg) => format!("Route ({}) · cfg({})", handler, cfg),
+                    None => format!("Route ({})", handler),
+                }),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// 搜索配置符号，匹配的符号边扫描边交给 `emit`
+    fn search_config_symbols(
+        &self,
+        workspace_path: &std::path::Path,
+        query: &str,
+        emit: &mut dyn FnMut(lsp_types::SymbolInformation) -> Result<()>,
+    ) -> Result<()> {
+        use crate::scanner::config::ConfigScanner;
+        use lsp_types::{SymbolInformation, SymbolKind};
+
+        let scanner = ConfigScanner::new().with_sources(self.sources());
+
+        for config in scanner.configurations(workspace_path) {
+            // 过滤：如果有查询字符串，检查配置名称是否匹配
+            if !query.is_empty() && !config.name.to_lowercase().contains(query) {
+                continue;
+            }
+
+            // config.location 已经是 Option<lsp_types::Location>
+            if let Some(location) = config.location {
+                #[allow(deprecated)]
+                emit(SymbolInformation {
+                    name: config.name.clone(),
+                    kind: SymbolKind::STRUCT,
+                    tags: None,
+                    deprecated: None,
+                    location,
+                    container_name: Some(format!("Config [{}]", config.prefix)),
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 提取 TOML 文档符号
+    fn extract_toml_symbols(&self, content: &str) -> Vec<lsp_types::DocumentSymbol> {
+        use lsp_types::{DocumentSymbol, SymbolKind};
+
+        let mut symbols = Vec::new();
+        let mapper = self.mapper(content);
+        // 语法树中的字节范围按协商的位置编码转换，多段范围（如表头和表中的条目）合并为一个
+        let span = |ranges: &mut dyn Iterator<Item = taplo::rowan::TextRange>| {
+            ranges
+                .map(|range| usize::from(range.start())..usize::from(range.end()))
+                .reduce(|a, b| a.start.min(b.start)..a.end.max(b.end))
+                .map(|range| mapper.range(range))
+        };
+
+        // 使用 taplo 解析 TOML
+        let parse_result = taplo::parser::parse(content);
+        let root = parse_result.into_dom();
+
+        // 检查根节点是否为表
+        if let taplo::dom::Node::Table(table) = root {
+            let entries = table.entries();
+            let entries_arc = entries.get();
+
+            // 遍历顶层表
+            for (key, value) in entries_arc.iter() {
+                let key_str = key.value().to_string();
+
+                // 键的位置作为选择范围，符号范围覆盖键和值
+                let key_range = span(&mut key.text_ranges()).unwrap_or_default();
+                let range =
+                    span(&mut key.text_ranges().chain(value.text_ranges())).unwrap_or(key_range);
+
+                match value {
+                    taplo::dom::Node::Table(inner_table) => {
+                        // 配置节（表）
+                        let mut children = Vec::new();
+
+                        // 提取表中的属性
+                        let inner_entries = inner_table.entries();
+                        let inner_entries_arc = inner_entries.get();
+                        for (prop_key, prop_value) in inner_entries_arc.iter() {
+                            let prop_key_str = prop_key.value().to_string();
+                            let prop_key_range =
+                                span(&mut prop_key.text_ranges()).unwrap_or(key_range);
+                            let prop_range =
+                                span(&mut prop_key.text_ranges().chain(prop_value.text_ranges()))
+                                    .unwrap_or(prop_key_range);
+
+                            let prop_symbol = DocumentSymbol {
+                                name: prop_key_str.clone(),
+                                detail: Some("Property".to_string()),
+                                kind: SymbolKind::PROPERTY,
+                                tags: None,
+                                #[allow(deprecated)]
+                                deprecated: None,
+                                range: prop_range,
+                                selection_range: prop_key_range,
+                                children: None,
+                            };
+                            children.push(prop_symbol);
+                        }
+
+                        let symbol = DocumentSymbol {
+                            name: key_str.clone(),
+                            detail: Some("Configuration section".to_string()),
+                            kind: SymbolKind::MODULE,
+                            tags: None,
+                            #[allow(deprecated)]
+                            deprecated: None,
+                            range,
+                            selection_range: key_range,
+                            children: if children.is_empty() {
+                                None
+                            } else {
+                                Some(children)
+                            },
+                        };
+                        symbols.push(symbol);
+                    }
+                    taplo::dom::Node::Array(_) => {
+                        // 数组
+                        let symbol = DocumentSymbol {
+                            name: key_str.clone(),
+                            detail: Some("Array".to_string()),
+                            kind: SymbolKind::ARRAY,
+                            tags: None,
+                            #[allow(deprecated)]
+                            deprecated: None,
+                            range,
+                            selection_range: key_range,
+                            children: None,
+                        };
+                        symbols.push(symbol);
+                    }
+                    _ => {
+                        // 其他值类型（字符串、数字、布尔等）
+                        let symbol = DocumentSymbol {
+                            name: key_str.clone(),
+                            detail: Some("Property".to_string()),
+                            kind: SymbolKind::PROPERTY,
+                            tags: None,
+                            #[allow(deprecated)]
+                            deprecated: None,
+                            range,
+                            selection_range: key_range,
+                            children: None,
+                        };
+                        symbols.push(symbol);
+                    }
+                }
+            }
+        }
+
+        symbols
+    }
+
+    /// 提取 Rust 文档符号
+    fn extract_rust_symbols(&self, _content: &str) -> Vec<lsp_types::DocumentSymbol> {
+        use crate::analysis::rust::macro_analyzer::is_deprecated;
+        use lsp_types::{DocumentSymbol, Range, SymbolKind, SymbolTag};
+
+        let mut symbols = Vec::new();
+        let deprecated_tags =
+            |attrs: &[syn::Attribute]| is_deprecated(attrs).then(|| vec![SymbolTag::DEPRECATED]);
+
+        // 使用 syn 解析 Rust 代码
+        let syntax = match syn::parse_file(_content) {
+            Ok(syntax) => syntax,
+            Err(e) => {
+                tracing::warn!("Failed to parse Rust file: {}", e);
+                return symbols;
+            }
+        };
+
+        // 默认范围（因为 proc_macro2::Span 不提供行列信息）
+        let default_range = Range::default();
+
+        // 遍历顶层项
+        for item in syntax.items {
+            match item {
+                syn::Item::Fn(item_fn) => {
+                    // 函数
+                    let name = item_fn.sig.ident.to_string();
+
+                    let symbol = DocumentSymbol {
+                        name: name.clone(),
+                        detail: Some(format!("fn {}", name)),
+                        kind: SymbolKind::FUNCTION,
+                        tags: deprecated_tags(&item_fn.attrs),
+                        #[allow(deprecated)]
+                        deprecated: None,
+                        range: default_range,
+                        selection_range: default_range,
+                        children: None,
+                    };
+                    symbols.push(symbol);
+                }
+                syn::Item::Struct(item_struct) => {
+                    // 结构体
+                    let name = item_struct.ident.to_string();
+
+                    let symbol = DocumentSymbol {
+                        name: name.clone(),
+                        detail: Some(format!("struct {}", name)),
+                        kind: SymbolKind::STRUCT,
+                        tags: deprecated_tags(&item_struct.attrs),
+                        #[allow(deprecated)]
+                        deprecated: None,
+                        range: default_range,
+                        selection_range: default_range,
+                        children: None,
+                    };
+                    symbols.push(symbol);
+                }
+                syn::Item::Enum(item_enum) => {
+                    // 枚举
+                    let name = item_enum.ident.to_string();
+
+                    let symbol = DocumentSymbol {
+                        name: name.clone(),
+                        detail: Some(format!("enum {}", name)),
+                        kind: SymbolKind::ENUM,
+                        tags: deprecated_tags(&item_enum.attrs),
+                        #[allow(deprecated)]
+                        deprecated: None,
+                        range: default_range,
+                        selection_range: default_range,
+                        children: None,
+                    };
+                    symbols.push(symbol);
+                }
+                syn::Item::Trait(item_trait) => {
+                    // Trait
+                    let name = item_trait.ident.to_string();
+
+                    let symbol = DocumentSymbol {
+                        name: name.clone(),
+                        detail: Some(format!("trait {}", name)),
+                        kind: SymbolKind::INTERFACE,
+                        tags: deprecated_tags(&item_trait.attrs),
+                        #[allow(deprecated)]
+                        deprecated: None,
+                        range: default_range,
+                        selection_range: default_range,
+                        children: None,
+                    };
+                    symbols.push(symbol);
+                }
+                syn::Item::Impl(item_impl) => {
+                    // Impl 块
+                    if let Some((_, path, _)) = &item_impl.trait_ {
+                        let name = quote::quote!(#path).to_string();
+                        let symbol = DocumentSymbol {
+                            name: format!("impl {}", name),
+                            detail: Some("Implementation".to_string()),
+                            kind: SymbolKind::CLASS,
+                            tags: None,
+                            #[allow(deprecated)]
+                            deprecated: None,
+                            range: default_range,
+                            selection_range: default_range,
+                            children: None,
+                        };
+                        symbols.push(symbol);
+                    } else if let syn::Type::Path(type_path) = &*item_impl.self_ty {
+                        let name = quote::quote!(#type_path).to_string();
+                        let symbol = DocumentSymbol {
+                            name: format!("impl {}", name),
+                            detail: Some("Implementation".to_string()),
+                            kind: SymbolKind::CLASS,
+                            tags: None,
+                            #[allow(deprecated)]
+                            deprecated: None,
+                            range: default_range,
+                            selection_range: default_range,
+                            children: None,
+                        };
+                        symbols.push(symbol);
+                    }
+                }
+                _ => {
+                    // 其他项暂不处理
+                }
+            }
+        }
+
+        symbols
+    }
+
+    /// 处理初始化请求
+    ///
+    /// 声明服务器支持的所有能力，包括：
+    /// - 文档同步（增量更新）
+    /// - 智能补全（TOML 配置、宏参数、环境变量）
+    /// - 悬停提示（配置文档、宏展开、路由信息）
+    /// - 诊断（配置验证、路由验证、依赖注入验证）
+    /// - 定义跳转（路由导航）
+    /// - 文档符号（路由列表）
+    /// - 文件操作（重命名 Rust 文件时更新模块路径引用）
+    pub fn handle_initialize(&mut self, params: InitializeParams) -> Result<InitializeResult> {
+        use lsp_types::{
+            CodeActionKind, CodeActionOptions, CodeActionProviderCapability, CompletionOptions,
+            ExecuteCommandOptions, HoverProviderCapability, OneOf, TextDocumentSyncCapability,
+            TextDocumentSyncKind, TextDocumentSyncOptions, WorkDoneProgressOptions,
+            WorkspaceFileOperationsServerCapabilities, WorkspaceServerCapabilities,
+        };
+
+        self.watched_files_registration = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.did_change_watched_files.as_ref())
+            .and_then(|watched| watched.dynamic_registration)
+            .unwrap_or(false);
+
+        self.workspace_edit_support = WorkspaceEditSupport::from_capabilities(&params.capabilities);
+        self.apply_edit_support = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.apply_edit)
+            .unwrap_or(false);
+        self.snippet_support = snippet::supports_snippets(&params.capabilities);
+        let position_encoding = PositionEncoding::negotiate(&params.capabilities);
+        self.set_position_encoding(position_encoding);
+        self.set_locale(
+            Locale::from_initialization_options(params.initialization_options.as_ref())
+                .unwrap_or_default(),
+        );
+
+        // 如果客户端提供了工作空间路径，重新加载配置
+        #[allow(deprecated)]
+        if let Some(root_uri) = params.root_uri {
+            if let Ok(workspace_path) = root_uri.to_file_path() {
+                tracing::info!(
+                    "Loading configuration from workspace: {}",
+                    workspace_path.display()
+                );
+
+                // 存储 workspace_path
+                self.workspace_path = Some(workspace_path.clone());
+
+                // 加载诊断基线
+                self.baseline = match Baseline::load(&workspace_path) {
+                    Ok(baseline) => baseline,
+                    Err(e) => {
+                        tracing::warn!("Ignoring baseline: {}", e);
+                        None
+                    }
+                };
+
+                // 加载路由安全规则
+                self.security_rules = match SecurityRules::load(&workspace_path) {
+                    Ok(rules) => rules,
+                    Err(e) => {
+                        tracing::warn!("Ignoring security rules: {}", e);
+                        None
+                    }
+                };
+
+                // 记录 Cargo.lock 中的插件版本，用于检测后续的版本变化
+                let lock_path = workspace_path.join(CARGO_LOCK);
+                if lock_path.exists() {
+                    let versions = read_plugin_versions(&lock_path);
+                    self.plugin_versions.insert(lock_path, versions);
+                }
+
+                self.config = ServerConfig::load(Some(&workspace_path));
+
+                // 验证配置
+                if let Err(e) = self.config.validate() {
+                    tracing::error!("Invalid configuration: {}", e);
+                    return Err(Error::Config(e));
+                }
+
+                tracing::info!("Configuration loaded successfully");
+                tracing::debug!(
+                    "Trigger characters: {:?}",
+                    self.config.completion.trigger_characters
+                );
+                tracing::debug!("Schema URL: {}", self.config.schema.url);
+                tracing::debug!(
+                    "Disabled diagnostics: {:?}",
+                    self.config.diagnostics.disabled
+                );
+            }
+        }
+
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                // 位置编码（列的计量单位）
+                position_encoding: Some(position_encoding.kind()),
+                // 文档同步能力 - 支持增量更新
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        change: Some(TextDocumentSyncKind::INCREMENTAL),
+                        will_save: None,
+                        will_save_wait_until: None,
+                        save: None,
+                    },
+                )),
+
+                // 智能补全能力
+                // 支持 TOML 配置项、宏参数、环境变量补全
+                // 使用配置文件中的触发字符
+                completion_provider: Some(CompletionOptions {
+                    resolve_provider: Some(true),
+                    trigger_characters: Some(self.config.completion.trigger_characters.clone()),
                     all_commit_characters: None,
                     work_done_progress_options: WorkDoneProgressOptions {
                         work_done_progress: None,
                     },
-                    completion_item: None,
+                    completion_item: None,
+                }),
+
+                // 悬停提示能力
+                // 支持配置文档、宏展开、路由信息显示
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+
+                // 定义跳转能力
+                // 支持路由路径跳转到处理器函数
+                definition_provider: Some(OneOf::Left(true)),
+
+                // 类型定义跳转能力
+                // 支持从提取器跳转到泛型参数的类型定义
+                type_definition_provider: Some(
+                    lsp_types::TypeDefinitionProviderCapability::Simple(true),
+                ),
+
+                // 文档符号能力
+                // 支持显示文档中的所有路由
+                document_symbol_provider: Some(OneOf::Left(true)),
+
+                // 代码透镜能力
+                // 标记未实现的路由处理器
+                code_lens_provider: Some(lsp_types::CodeLensOptions {
+                    resolve_provider: Some(false),
+                }),
+
+                // 工作空间符号能力
+                // 支持全局搜索路由和组件
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+
+                // 命令执行能力
+                // 支持索引一致性检查等维护命令
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: SUPPORTED_COMMANDS.iter().map(|c| c.to_string()).collect(),
+                    work_done_progress_options: WorkDoneProgressOptions {
+                        work_done_progress: None,
+                    },
+                }),
+
+                // 诊断能力（通过 publishDiagnostics 通知发送）
+                // 支持配置验证、路由验证、依赖注入验证
+
+                // 代码操作能力
+                // 为附带修复建议的诊断提供快速修复
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                        ..Default::default()
+                    },
+                )),
+
+                // 颜色能力
+                // 为 Schema 中声明为颜色的配置值显示色块
+                color_provider: Some(lsp_types::ColorProviderCapability::Simple(true)),
+
+                // 文件操作能力
+                // 重命名或移动 Rust 文件时更新模块路径引用和索引，新建 Rust 文件时填充模板
+                workspace: Some(WorkspaceServerCapabilities {
+                    workspace_folders: None,
+                    file_operations: Some(WorkspaceFileOperationsServerCapabilities {
+                        did_create: Some(rust_file_operation_options()),
+                        will_rename: Some(rust_file_operation_options()),
+                        did_rename: Some(rust_file_operation_options()),
+                        ..Default::default()
+                    }),
+                }),
+
+                // 格式化能力（未来支持 TOML 格式化）
+                // document_formatting_provider: Some(OneOf::Left(true)),
+
+                // 重命名能力
+                // 支持重命名路径参数和组件名称，拒绝重命名 HTTP 方法、spring 宏和 Schema 定义的配置键
+                rename_provider: Some(OneOf::Right(lsp_types::RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: WorkDoneProgressOptions {
+                        work_done_progress: None,
+                    },
+                })),
+
+                // 自定义请求及其版本，供编辑器扩展判断功能是否可用
+                experimental: Some(extensions::experimental_capabilities()),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: "spring-lsp".to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    /// 发送错误响应
+    fn send_error_response(&self, id: RequestId, code: i32, message: String) -> Result<()> {
+        let response = Response {
+            id,
+            result: None,
+            error: Some(lsp_server::ResponseError {
+                code,
+                message,
+                data: None,
+            }),
+        };
+
+        self.connection
+            .sender
+            .send(Message::Response(response))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 向客户端发送错误通知
+    ///
+    /// 使用 window/showMessage 通知向客户端显示错误消息
+    fn notify_client_error(&self, error: &Error) -> Result<()> {
+        use lsp_types::MessageType;
+
+        let message_type = match error.severity() {
+            crate::error::ErrorSeverity::Error => MessageType::ERROR,
+            crate::error::ErrorSeverity::Warning => MessageType::WARNING,
+            crate::error::ErrorSeverity::Info => MessageType::INFO,
+        };
+
+        self.show_message(message_type, error.to_string())
+    }
+
+    /// 使用 window/showMessage 通知向客户端显示消息
+    fn show_message(&self, typ: lsp_types::MessageType, message: String) -> Result<()> {
+        let params = lsp_types::ShowMessageParams { typ, message };
+
+        let notification = Notification {
+            method: "window/showMessage".to_string(),
+            params: serde_json::to_value(params)?,
+        };
+
+        self.connection
+            .sender
+            .send(Message::Notification(notification))
+            .map_err(|e| Error::MessageSend(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 优雅关闭服务器
+    pub fn shutdown(&mut self) -> Result<()> {
+        tracing::info!("Shutting down spring-lsp server");
+
+        // 输出性能报告，便于附加到性能问题报告中
+        if self.config.logging.perf_report {
+            tracing::info!("{}", self.status.get_perf_report().format());
+        }
+        // 输出错误恢复统计，便于发现和报告系统性的问题
+        if self.config.logging.error_report {
+            let report = self.status.get_error_report();
+            if !report.is_empty() {
+                tracing::info!("{}", report.format());
+            }
+        }
+
+        // 清理资源
+        tracing::debug!("Clearing all diagnostics...");
+        // TODO: 清理所有文档的诊断
+        // 需要实现 DocumentManager::get_all_uris() 方法
+
+        tracing::debug!("Clearing document cache...");
+        // 清理文档缓存（DocumentManager 会自动清理）
+
+        tracing::debug!("Clearing indexes...");
+        // 索引管理器会自动清理
+
+        tracing::info!("Server shutdown complete");
+        Ok(())
+    }
+}
+
+impl Default for LspServer {
+    fn default() -> Self {
+        Self::start().expect("Failed to start LSP server")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{
+        ClientCapabilities, ClientInfo, InitializeParams, TextDocumentItem, Url,
+        VersionedTextDocumentIdentifier, WorkDoneProgressParams,
+    };
+
+    /// 创建服务器，并返回可用于模拟客户端的另一端连接
+    fn server_with_client() -> (LspServer, Connection) {
+        let (server_conn, client_conn) = Connection::memory();
+        let mut config = ServerConfig::default();
+        // 测试断言完整的悬停提示，避免在较慢的环境中退化为简短提示
+        config.hover.budget_ms = 60_000;
+        let server = LspServer::new_with_connection(server_conn, config).unwrap();
+        (server, client_conn)
+    }
+
+    /// 接收服务器发出的错误响应
+    fn recv_error(client: &Connection) -> lsp_server::ResponseError {
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => resp.error.expect("expected error response"),
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试未知方法返回 MethodNotFound
+    #[test]
+    fn test_unknown_method_returns_method_not_found() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let req = Request::new(RequestId::from(1), "unknown/method".to_string(), ());
+        server.handle_message(Message::Request(req)).unwrap();
+
+        let error = recv_error(&client);
+        assert_eq!(error.code, lsp_server::ErrorCode::MethodNotFound as i32);
+        assert!(error.message.contains("unknown/method"));
+    }
+
+    /// 测试无效参数返回 InvalidParams 而不是静默丢弃请求
+    #[test]
+    fn test_invalid_params_returns_error_response() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let req = Request::new(
+            RequestId::from(2),
+            HoverRequest::METHOD.to_string(),
+            serde_json::json!({ "garbage": true }),
+        );
+        assert!(server.handle_message(Message::Request(req)).is_ok());
+
+        let error = recv_error(&client);
+        assert_eq!(error.code, lsp_server::ErrorCode::InvalidParams as i32);
+    }
+
+    /// 测试 spring/perfReport 返回已记录的请求延迟
+    #[test]
+    fn test_perf_report_request() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let req = Request::new(RequestId::from(1), "unknown/method".to_string(), ());
+        server.handle_message(Message::Request(req)).unwrap();
+        let _ = recv_error(&client);
+
+        let req = Request::new(RequestId::from(2), "spring/perfReport".to_string(), ());
+        server.handle_message(Message::Request(req)).unwrap();
+
+        let resp = match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => resp,
+            other => panic!("Expected response, got {:?}", other),
+        };
+        let report = resp.result.unwrap();
+        let methods = report["methods"].as_array().unwrap();
+        assert!(methods.iter().any(|m| m["method"] == "unknown/method"));
+        let caches = report["caches"].as_array().unwrap();
+        assert!(caches.iter().any(|c| c["name"] == MACRO_CACHE));
+    }
+
+    /// 测试编辑 Rust 文件时只重新检查发生变化的函数
+    #[test]
+    fn test_did_change_reuses_unchanged_fn_diagnostics() {
+        let (mut server, _client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::parse("file:///app/src/routes.rs").unwrap();
+        let content = "#[get(\"/items\")]\nfn items() {}\n\nfn helper() {}\n";
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: content.to_string(),
+                },
+            })
+            .unwrap();
+        let before = server.diagnostic_engine.get(&uri);
+        assert!(!before.is_empty());
+
+        server
+            .handle_did_change(DidChangeTextDocumentParams {
+                text_document: lsp_types::VersionedTextDocumentIdentifier {
+                    uri: uri.clone(),
+                    version: 2,
+                },
+                content_changes: vec![lsp_types::TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: format!("\n{}", content.replace("helper() {}", "helper() { 1; }")),
+                }],
+            })
+            .unwrap();
+        // 未修改的处理器的诊断复用并平移到新位置
+        let after = server.diagnostic_engine.get(&uri);
+        assert_eq!(after.len(), before.len());
+        assert_eq!(after[0].range.start.line, before[0].range.start.line + 1);
+
+        let report = server.status.get_perf_report();
+        let cache = report
+            .caches
+            .iter()
+            .find(|cache| cache.name == ITEM_CACHE)
+            .unwrap();
+        assert_eq!((cache.hits, cache.misses), (1, 3));
+    }
+
+    /// 测试 TOML 文档符号的范围来自语法树，列号按协商的位置编码计算
+    #[test]
+    fn test_toml_symbol_ranges() {
+        let mut server = LspServer::new_for_test().unwrap();
+        let content = "# 应用配置\n[web]\nport = 8080\n\n[\"数据库\"]\n\"名称\" = \"orders\"\n";
+        let range = |start: (u32, u32), end: (u32, u32)| {
+            lsp_types::Range::new(
+                lsp_types::Position::new(start.0, start.1),
+                lsp_types::Position::new(end.0, end.1),
+            )
+        };
+
+        let symbols = server.extract_toml_symbols(content);
+        assert_eq!(symbols[0].name, "web");
+        assert_eq!(symbols[0].selection_range, range((1, 1), (1, 4)));
+        let port = &symbols[0].children.as_ref().unwrap()[0];
+        assert_eq!(port.selection_range, range((2, 0), (2, 4)));
+        assert_eq!(port.range, range((2, 0), (2, 11)));
+
+        let database = &symbols[1];
+        assert_eq!(database.name, "数据库");
+        assert_eq!(database.selection_range, range((4, 1), (4, 6)));
+        assert_eq!(database.range, range((4, 0), (5, 15)));
+        let name = &database.children.as_ref().unwrap()[0];
+        assert_eq!(name.name, "名称");
+        assert_eq!(name.selection_range, range((5, 0), (5, 4)));
+
+        // UTF-8 编码下列号按字节计算
+        server.set_position_encoding(PositionEncoding::Utf8);
+        let symbols = server.extract_toml_symbols(content);
+        assert_eq!(symbols[1].selection_range, range((4, 1), (4, 12)));
+        let name = &symbols[1].children.as_ref().unwrap()[0];
+        assert_eq!(name.selection_range, range((5, 0), (5, 8)));
+    }
+
+    /// 测试 .spring-lsp.toml 变化后重新加载配置，不复用按旧配置缓存的诊断
+    #[test]
+    fn test_workspace_config_change_reloads_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut server, _client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.workspace_path = Some(dir.path().to_path_buf());
+
+        let uri = Url::from_file_path(dir.path().join("src/routes.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: "#[get(\"/items\")]\nasync fn items() {\n    todo!()\n}\n".to_string(),
+                },
+            })
+            .unwrap();
+        let is_stub = |diagnostic: &lsp_types::Diagnostic| {
+            diagnostic.code == Some(lsp_types::NumberOrString::String("W007".to_string()))
+        };
+        assert!(!server.diagnostic_engine.get(&uri).iter().any(is_stub));
+
+        let config_path = dir.path().join(WORKSPACE_CONFIG_FILE);
+        std::fs::write(&config_path, "[routes]\nstub_handlers = true\n").unwrap();
+        let not = Notification::new(
+            DidChangeWatchedFiles::METHOD.to_string(),
+            serde_json::json!({
+                "changes": [{ "uri": Url::from_file_path(&config_path).unwrap(), "type": 1 }]
+            }),
+        );
+        server.handle_message(Message::Notification(not)).unwrap();
+
+        assert!(server.config.routes.stub_handlers);
+        assert!(server.diagnostic_engine.get(&uri).iter().any(is_stub));
+    }
+
+    /// 测试 .spring-lsp/rules.toml 变化后重新加载安全规则，无效的规则文件保留当前规则
+    #[test]
+    fn test_security_rules_change_reloads_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join(".spring-lsp")).unwrap();
+        let main_rs = root.join("src/main.rs");
+        let content = "#[get(\"/admin/users\")]\nasync fn users() {}\n";
+        std::fs::write(&main_rs, content).unwrap();
+
+        let (mut server, _client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.workspace_path = Some(root.clone());
+        let uri = Url::from_file_path(&main_rs).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: content.to_string(),
+                },
+            })
+            .unwrap();
+        let unprotected = |server: &LspServer| {
+            server.diagnostic_engine.get(&uri).iter().any(|diagnostic| {
+                diagnostic.code
+                    == Some(lsp_types::NumberOrString::String(
+                        "unprotected-route".to_string(),
+                    ))
+            })
+        };
+        assert!(!unprotected(&server));
+
+        let rules_path = SecurityRules::path(&root);
+        let rules_changed = |server: &mut LspServer| {
+            let not = Notification::new(
+                DidChangeWatchedFiles::METHOD.to_string(),
+                serde_json::json!({
+                    "changes": [{ "uri": Url::from_file_path(&rules_path).unwrap(), "type": 2 }]
+                }),
+            );
+            server.handle_message(Message::Notification(not)).unwrap();
+        };
+        std::fs::write(
+            &rules_path,
+            "[[security]]\npattern = \"/admin/**\"\nmiddleware = [\"AuthLayer\"]\n",
+        )
+        .unwrap();
+        rules_changed(&mut server);
+        assert!(unprotected(&server));
+
+        // 无效的规则文件不会清除当前规则
+        std::fs::write(&rules_path, "[[security]]\npattern = \"admin\"\n").unwrap();
+        rules_changed(&mut server);
+        assert_eq!(server.security_rules.as_ref().unwrap().security.len(), 1);
+        assert!(unprotected(&server));
+
+        std::fs::remove_file(&rules_path).unwrap();
+        rules_changed(&mut server);
+        assert!(server.security_rules.is_none());
+        assert!(!unprotected(&server));
+    }
+
+    /// 测试 .spring-lsp/rules.toml 变化后路由前缀约定随安全规则一起重新加载
+    #[test]
+    fn test_security_rules_change_reloads_prefixes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(root.join("src/handlers")).unwrap();
+        std::fs::create_dir_all(root.join(".spring-lsp")).unwrap();
+        let admin_rs = root.join("src/handlers/admin.rs");
+        let content = "#[get(\"/users\")]\nasync fn users() {}\n";
+        std::fs::write(&admin_rs, content).unwrap();
+
+        let (mut server, _client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.workspace_path = Some(root.clone());
+        let uri = Url::from_file_path(&admin_rs).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: content.to_string(),
+                },
+            })
+            .unwrap();
+        let violates_prefix = |server: &LspServer| {
+            server.diagnostic_engine.get(&uri).iter().any(|diagnostic| {
+                diagnostic.code
+                    == Some(lsp_types::NumberOrString::String(
+                        "route-prefix-convention".to_string(),
+                    ))
+            })
+        };
+        assert!(!violates_prefix(&server));
+
+        let rules_path = SecurityRules::path(&root);
+        std::fs::write(
+            &rules_path,
+            "[[prefix]]\npath = \"src/handlers/admin\"\nprefix = \"/admin\"\n",
+        )
+        .unwrap();
+        let not = Notification::new(
+            DidChangeWatchedFiles::METHOD.to_string(),
+            serde_json::json!({
+                "changes": [{ "uri": Url::from_file_path(&rules_path).unwrap(), "type": 1 }]
+            }),
+        );
+        server.handle_message(Message::Notification(not)).unwrap();
+
+        assert_eq!(server.security_rules.as_ref().unwrap().prefixes.len(), 1);
+        assert!(violates_prefix(&server));
+    }
+
+    /// 测试 spring.verifyIndex 命令返回验证报告
+    #[test]
+    fn test_verify_index_command() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let req = Request::new(
+            RequestId::from(1),
+            ExecuteCommand::METHOD.to_string(),
+            serde_json::json!({ "command": VERIFY_INDEX_COMMAND, "arguments": [] }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let report = resp.result.unwrap();
+                assert_eq!(report["checked"], 0);
+                assert!(report["issues"].as_array().unwrap().is_empty());
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+
+        let req = Request::new(
+            RequestId::from(2),
+            ExecuteCommand::METHOD.to_string(),
+            serde_json::json!({ "command": "spring.unknown" }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        let error = recv_error(&client);
+        assert_eq!(error.code, lsp_server::ErrorCode::InvalidParams as i32);
+    }
+
+    /// 测试 spring.updateBaseline 命令生成基线后，已有诊断不再发布
+    #[test]
+    fn test_update_baseline_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let content = "#[get(\"/users\")]\nasync fn list(Query(page): Query<u32>) {}\n\n#[auto_config(WebConfigurator)]\n#[tokio::main]\nasync fn main() {}\n";
+        std::fs::write(root.join("src/main.rs"), content).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.workspace_path = Some(root.clone());
+
+        let uri = Url::from_file_path(root.join("src/main.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: content.to_string(),
+                },
+            })
+            .unwrap();
+        assert_eq!(server.diagnostic_engine.get(&uri).len(), 1);
+        while client.receiver.try_recv().is_ok() {}
+
+        let req = Request::new(
+            RequestId::from(1),
+            ExecuteCommand::METHOD.to_string(),
+            serde_json::json!({ "command": UPDATE_BASELINE_COMMAND, "arguments": [] }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        let mut response = None;
+        while let Ok(message) = client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+        {
+            if let Message::Response(resp) = message {
+                response = Some(resp);
+                break;
+            }
+        }
+        let result = response.unwrap().result.unwrap();
+        assert_eq!(result["diagnostics"], 1);
+        assert!(root.join(crate::core::baseline::BASELINE_FILE).is_file());
+        assert!(server.diagnostic_engine.get(&uri).is_empty());
+
+        // 新增的诊断仍然发布
+        let changed = format!(
+            "{}#[get(\"/items\")]\nasync fn items(Query(id): Query<i64>) {{}}\n",
+            content
+        );
+        server
+            .handle_did_change(DidChangeTextDocumentParams {
+                text_document: lsp_types::VersionedTextDocumentIdentifier {
+                    uri: uri.clone(),
+                    version: 2,
+                },
+                content_changes: vec![lsp_types::TextDocumentContentChangeEvent {
+                    range: None,
+                    range_length: None,
+                    text: changed,
+                }],
+            })
+            .unwrap();
+        let diagnostics = server.diagnostic_engine.get(&uri);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("i64"));
+    }
+
+    /// 测试从配置中的任务名称跳转到任务函数，并对未知任务发出警告
+    #[test]
+    fn test_job_name_navigation_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(
+            dir.path().join("src/main.rs"),
+            "#[cron(\"0 0 * * * *\")]\nasync fn hourly_cleanup() {}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::from_file_path(dir.path().join("config/app.toml")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "toml".to_string(),
+                    version: 1,
+                    text: "[job]\ndisabled = [\"hourly_cleanup\", \"nightly\"]\n".to_string(),
+                },
+            })
+            .unwrap();
+
+        // 未知任务产生警告
+        let diagnostics = server.diagnostic_engine.get(&uri);
+        let unknown: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.code == Some(lsp_types::NumberOrString::String("unknown-job".into())))
+            .collect();
+        assert_eq!(unknown.len(), 1);
+        assert!(unknown[0].message.contains("nightly"));
+
+        // 丢弃 publishDiagnostics 通知
+        while client.receiver.try_recv().is_ok() {}
+
+        let req = Request::new(
+            RequestId::from(1),
+            GotoDefinition::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "position": { "line": 1, "character": 15 },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let locations: Vec<lsp_types::Location> =
+                    serde_json::from_value(resp.result.unwrap()).unwrap();
+                assert_eq!(locations.len(), 1);
+                assert!(locations[0].uri.path().ends_with("src/main.rs"));
+                assert_eq!(locations[0].range.start.line, 0);
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试提供 partialResultToken 时工作空间符号通过 $/progress 分块发送
+    #[test]
+    fn test_workspace_symbol_partial_results() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/main.rs"),
+            "#[get(\"/users\")]\nasync fn list_users() {}\n\n#[post(\"/users\")]\nasync fn create_user() {}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.workspace_path = Some(dir.path().to_path_buf());
+
+        let req = Request::new(
+            RequestId::from(1),
+            "workspace/symbol".to_string(),
+            serde_json::json!({ "query": "user", "partialResultToken": "ws-1" }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        let mut streamed = 0;
+        loop {
+            match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Notification(not) => {
+                    assert_eq!(not.method, "$/progress");
+                    assert_eq!(not.params["token"], "ws-1");
+                    streamed += not.params["value"].as_array().unwrap().len();
+                }
+                Message::Response(resp) => {
+                    // 最终响应不再重复包含已发送的结果
+                    assert_eq!(resp.result, Some(serde_json::json!([])));
+                    break;
+                }
+                other => panic!("Unexpected message: {:?}", other),
+            }
+        }
+        assert!(streamed >= 2);
+    }
+
+    /// 测试工作空间符号使用完整路径区分不同模块中的同名条目
+    #[test]
+    fn test_workspace_symbol_qualified_names() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src/handlers")).unwrap();
+        std::fs::write(
+            dir.path().join("src/handlers/users.rs"),
+            "#[get(\"/users\")]\nasync fn list() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/handlers/orders.rs"),
+            "#[get(\"/orders\")]\nasync fn list() {}\n\n#[derive(Clone, Service)]\nstruct OrderService {}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.workspace_path = Some(dir.path().to_path_buf());
+
+        let req = Request::new(
+            RequestId::from(1),
+            "workspace/symbol".to_string(),
+            serde_json::json!({ "query": "orders::" }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        let Message::Response(resp) = client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        else {
+            panic!("Expected response");
+        };
+        let symbols: Vec<lsp_types::SymbolInformation> =
+            serde_json::from_value(resp.result.unwrap()).unwrap();
+        let mut containers: Vec<_> = symbols
+            .iter()
+            .map(|symbol| symbol.container_name.clone().unwrap())
+            .collect();
+        containers.sort();
+        assert_eq!(
+            containers,
+            vec![
+                "Component (crate::handlers::orders::OrderService)",
+                "Route (crate::handlers::orders::list)",
+            ]
+        );
+    }
+
+    /// 测试插件 Schema 更新后重新验证打开的 TOML 文档
+    #[test]
+    fn test_schema_update_revalidates_open_documents() {
+        let (mut server, _client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::parse("file:///project/config/app.toml").unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "toml".to_string(),
+                    version: 1,
+                    text: "[web]\nzz_hot_reload = true\n".to_string(),
+                },
+            })
+            .unwrap();
+        let undefined_property = |server: &LspServer| {
+            server.diagnostic_engine.get(&uri).iter().any(|d| {
+                d.code
+                    == Some(lsp_types::NumberOrString::String(
+                        "undefined-property".into(),
+                    ))
+            })
+        };
+        assert!(undefined_property(&server));
+
+        let mut fresh = ConfigSchema {
+            schema_type: "object".to_string(),
+            plugins: std::collections::HashMap::new(),
+        };
+        fresh.plugins.insert(
+            "web".to_string(),
+            serde_json::json!({
+                "type": "object",
+                "properties": { "zz_hot_reload": { "type": "boolean" } }
+            }),
+        );
+
+        // 无关插件的版本变化不会触发更新
+        let updated = server
+            .apply_schema_update(&fresh, &["spring-redis".to_string()])
+            .unwrap();
+        assert!(updated.is_empty());
+        assert!(undefined_property(&server));
+
+        let updated = server
+            .apply_schema_update(&fresh, &["spring-web".to_string()])
+            .unwrap();
+        assert_eq!(updated, vec!["web".to_string()]);
+        assert!(server.schema_provider.has_property("web", "zz_hot_reload"));
+        assert!(!undefined_property(&server));
+    }
+
+    /// 测试移动处理器文件时更新模块路径引用和索引
+    #[test]
+    fn test_rename_handler_file() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(src.join("api")).unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::write(
+            src.join("main.rs"),
+            "mod api;\nmod handlers;\nuse crate::handlers::list;\n",
+        )
+        .unwrap();
+        std::fs::write(src.join("api.rs"), "").unwrap();
+        std::fs::write(
+            src.join("handlers.rs"),
+            "#[get(\"/users\")]\nasync fn list() {}\n",
+        )
+        .unwrap();
+
+        let old_uri = Url::from_file_path(src.join("handlers.rs")).unwrap();
+        let new_uri = Url::from_file_path(src.join("api/users.rs")).unwrap();
+        let main_uri = Url::from_file_path(src.join("main.rs")).unwrap();
+        let params = serde_json::json!({
+            "files": [{ "oldUri": old_uri, "newUri": new_uri }]
+        });
+
+        let req = Request::new(
+            RequestId::from(1),
+            WillRenameFiles::METHOD.to_string(),
+            params.clone(),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let edit: lsp_types::WorkspaceEdit =
+                    serde_json::from_value(resp.result.unwrap()).unwrap();
+                let edits = &edit.changes.unwrap()[&main_uri];
+                assert_eq!(edits.len(), 1);
+                assert_eq!(edits[0].new_text, "crate::api::users");
+                assert_eq!(edits[0].range.start, lsp_types::Position::new(2, 4));
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+
+        // 客户端移动文件，但没有更新引用，也没有在 api.rs 中声明 users 模块
+        std::fs::rename(src.join("handlers.rs"), src.join("api/users.rs")).unwrap();
+        server
+            .handle_message(Message::Notification(Notification::new(
+                DidRenameFiles::METHOD.to_string(),
+                params,
+            )))
+            .unwrap();
+
+        let mut warning = None;
+        while let Ok(msg) = client
+            .receiver
+            .recv_timeout(std::time::Duration::from_millis(500))
+        {
+            if let Message::Notification(not) = msg {
+                if not.method == "window/showMessage" {
+                    warning = Some(not.params["message"].as_str().unwrap().to_string());
+                }
+            }
+        }
+        let warning = warning.expect("expected rename warning");
+        assert!(warning.contains("mod users;"));
+        assert!(warning.contains("crate::handlers"));
+        assert!(warning.contains(main_uri.as_str()));
+    }
+
+    /// 测试 spring.organizeRoutes 命令返回整理路由的 WorkspaceEdit
+    #[test]
+    fn test_organize_routes_command() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::parse("file:///project/src/handlers.rs").unwrap();
+        server.document_manager.open(
+            uri.clone(),
+            1,
+            "#[post(\"/users\")]\nasync fn create() {}\n\n#[get(\"/users\")]\nasync fn list() {}\n"
+                .to_string(),
+            "rust".to_string(),
+        );
+
+        let req = Request::new(
+            RequestId::from(1),
+            ExecuteCommand::METHOD.to_string(),
+            serde_json::json!({ "command": ORGANIZE_ROUTES_COMMAND, "arguments": [uri] }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let edit: lsp_types::WorkspaceEdit =
+                    serde_json::from_value(resp.result.unwrap()).unwrap();
+                let edits = &edit.changes.unwrap()[&uri];
+                assert_eq!(edits.len(), 1);
+                assert!(edits[0]
+                    .new_text
+                    .starts_with("#[get(\"/users\")]\nasync fn list()"));
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+
+        // 缺少 URI 参数
+        let req = Request::new(
+            RequestId::from(2),
+            ExecuteCommand::METHOD.to_string(),
+            serde_json::json!({ "command": ORGANIZE_ROUTES_COMMAND }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        let error = recv_error(&client);
+        assert_eq!(error.code, lsp_server::ErrorCode::InvalidParams as i32);
+    }
+
+    /// 测试只有末尾斜杠不同的路由产生诊断，并通过代码操作修复
+    #[test]
+    fn test_route_style_quick_fix() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::parse("file:///project/src/handlers.rs").unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: "#[get(\"/users\")]\nasync fn list() {}\n\n#[post(\"/users/\")]\nasync fn create() {}\n"
+                        .to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let diagnostics = server.diagnostic_engine.get(&uri);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(lsp_types::NumberOrString::String("W005".into()))
+        );
+
+        let req = Request::new(
+            RequestId::from(1),
+            CodeActionRequest::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "range": diagnostics[0].range,
+                "context": { "diagnostics": diagnostics },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let actions: Vec<lsp_types::CodeActionOrCommand> =
+                    serde_json::from_value(resp.result.unwrap()).unwrap();
+                assert_eq!(actions.len(), 1);
+                let lsp_types::CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+                    panic!("Expected code action");
+                };
+                assert_eq!(action.kind, Some(lsp_types::CodeActionKind::QUICKFIX));
+                let edit = action.edit.clone().unwrap();
+                let edits = &edit.changes.unwrap()[&uri];
+                assert_eq!(edits[0].new_text, "\"/users\"");
+                assert_eq!(edits[0].range.start.line, 3);
+                assert_eq!(edits[0].range.start.character, 7);
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试指定了名称的配置注入提供移除名称和改为组件注入两个代码操作
+    #[test]
+    fn test_config_inject_with_name_quick_fixes() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::parse("file:///project/src/services.rs").unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: "#[derive(Clone, Service)]\nstruct UserService {\n    #[inject(config = \"primary\")]\n    db: ConnectPool,\n}\n"
+                        .to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let diagnostics = server.diagnostic_engine.get(&uri);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(lsp_types::NumberOrString::String("E002".into()))
+        );
+
+        let req = Request::new(
+            RequestId::from(1),
+            CodeActionRequest::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "range": diagnostics[0].range,
+                "context": { "diagnostics": diagnostics },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let actions: Vec<lsp_types::CodeActionOrCommand> =
+                    serde_json::from_value(resp.result.unwrap()).unwrap();
+                let actions: Vec<_> = actions
+                    .into_iter()
+                    .map(|action| match action {
+                        lsp_types::CodeActionOrCommand::CodeAction(action) => action,
+                        other => panic!("Expected code action, got {:?}", other),
+                    })
+                    .collect();
+                assert_eq!(actions.len(), 2);
+                assert_eq!(actions[0].is_preferred, Some(true));
+                assert_eq!(actions[1].is_preferred, Some(false));
+
+                let new_texts: Vec<_> = actions
+                    .iter()
+                    .map(|action| {
+                        let edits = &action.edit.clone().unwrap().changes.unwrap()[&uri];
+                        assert_eq!(edits[0].range.start, lsp_types::Position::new(2, 4));
+                        assert_eq!(edits[0].range.end, lsp_types::Position::new(2, 33));
+                        edits[0].new_text.clone()
+                    })
+                    .collect();
+                assert_eq!(
+                    new_texts,
+                    vec!["#[inject(config)]", "#[inject(component = \"primary\")]"]
+                );
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试未注册组件的诊断提供在应用入口注册和添加提供函数两个代码操作
+    #[test]
+    fn test_register_component_quick_fixes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        let main_rs = "#[tokio::main]\nasync fn main() {\n    App::new()\n        .add_plugin(WebPlugin)\n        .run()\n        .await\n}\n";
+        std::fs::write(dir.path().join("src/main.rs"), main_rs).unwrap();
+        let services_rs = "#[derive(Clone, Service)]\nstruct UserService {\n    #[inject(component)]\n    repo: UserRepo,\n}\n";
+        std::fs::write(dir.path().join("src/services.rs"), services_rs).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::from_file_path(dir.path().join("src/services.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: services_rs.to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let mut diagnostic = lsp_types::Diagnostic {
+            range: lsp_types::Range::new(
+                lsp_types::Position::new(3, 4),
+                lsp_types::Position::new(3, 18),
+            ),
+            code: Some(lsp_types::NumberOrString::String(
+                "component-not-registered".to_string(),
+            )),
+            source: Some("spring-lsp".to_string()),
+            ..Default::default()
+        };
+        MissingComponent {
+            component_type: "UserRepo".to_string(),
+        }
+        .attach(&mut diagnostic);
+
+        let req = Request::new(
+            RequestId::from(1),
+            CodeActionRequest::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "range": diagnostic.range,
+                "context": { "diagnostics": [diagnostic] },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let actions: Vec<lsp_types::CodeActionOrCommand> =
+                    serde_json::from_value(resp.result.unwrap()).unwrap();
+                let actions: Vec<_> = actions
+                    .into_iter()
+                    .map(|action| match action {
+                        lsp_types::CodeActionOrCommand::CodeAction(action) => action,
+                        other => panic!("Expected code action, got {:?}", other),
+                    })
+                    .collect();
+                assert_eq!(actions.len(), 2);
+
+                let main = Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+                assert_eq!(
+                    actions[0].title,
+                    "在应用入口注册组件：.add_component(UserRepo::new())"
+                );
+                assert_eq!(actions[0].is_preferred, Some(true));
+                let changes = actions[0].edit.clone().unwrap().changes.unwrap();
+                let edit = &changes[&main][0];
+                assert_eq!(edit.range.start, lsp_types::Position::new(2, 14));
+                assert_eq!(edit.new_text, "\n        .add_component(UserRepo::new())");
+
+                assert_eq!(actions[1].title, "添加提供 UserRepo 的 #[component] 函数");
+                let changes = actions[1].edit.clone().unwrap().changes.unwrap();
+                let edit = &changes[&uri][0];
+                assert_eq!(edit.range.start, lsp_types::Position::new(5, 0));
+                assert!(edit.new_text.contains("fn create_user_repo() -> UserRepo"));
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试 spring/listRules 返回规则目录并标记被禁用的规则，规则描述使用客户端的语言
+    #[test]
+    fn test_list_rules_request() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.locale = Locale::En;
+        server.config.diagnostics.disabled = ["http-semantics".to_string()].into_iter().collect();
+
+        let req = Request::new(
+            RequestId::from(1),
+            extensions::LIST_RULES_REQUEST.to_string(),
+            serde_json::Value::Null,
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        let result = match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => resp.result.unwrap(),
+            other => panic!("Expected response, got {:?}", other),
+        };
+
+        let rules = result["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), crate::core::sarif::RULES.len());
+        let rule = |id: &str| rules.iter().find(|rule| rule["id"] == id).unwrap();
+        assert_eq!(rule("E020")["defaultSeverity"], "error");
+        assert_eq!(rule("E020")["fixable"], true);
+        assert!(rule("E020")["docsUrl"]
+            .as_str()
+            .unwrap()
+            .ends_with("docs/rules.md#e020"));
+        assert_eq!(
+            rule("route-prefix-convention")["defaultSeverity"],
+            "warning"
+        );
+        assert_eq!(rule("route-prefix-convention")["enabled"], true);
+        assert_eq!(rule("http-semantics/get-with-body")["enabled"], false);
+        assert_eq!(
+            rule("E005")["description"],
+            "The route path must start with '/'"
+        );
+    }
+
+    /// 测试 spring/stats 的统计结果和两次请求之间的变化趋势
+    #[test]
+    fn test_stats_request() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src/api")).unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(
+            dir.path().join("config/app.toml"),
+            "[web]\nport = 8080\ntimeout = 5\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/api/users.rs"),
+            "#[get(\"/users\")]\nasync fn list_users() {}\n\n#[post(\"/users\")]\nasync fn create_user() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/jobs.rs"),
+            "#[cron(\"0 0 * * * *\")]\nasync fn cleanup() {}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let mut request_stats = |id: i32| {
+            let req = Request::new(
+                RequestId::from(id),
+                "spring/stats".to_string(),
+                serde_json::json!({ "appPath": dir.path() }),
+            );
+            server.handle_message(Message::Request(req)).unwrap();
+            match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Response(resp) => resp.result.unwrap(),
+                other => panic!("Expected response, got {:?}", other),
+            }
+        };
+
+        let first = request_stats(1);
+        assert_eq!(first["routes"], 2);
+        assert_eq!(first["routesByModule"]["crate::api::users"], 2);
+        assert_eq!(first["jobs"], 1);
+        assert_eq!(first["configKeys"]["used"], 1);
+        assert_eq!(
+            first["configKeys"]["unused"],
+            serde_json::json!(["web.timeout"])
+        );
+        assert!(first.get("trends").is_none());
+
+        std::fs::write(
+            dir.path().join("src/api/orders.rs"),
+            "#[get(\"/orders\")]\nasync fn list_orders() {}\n",
+        )
+        .unwrap();
+        let second = request_stats(2);
+        assert_eq!(second["routes"], 3);
+        assert_eq!(second["trends"], serde_json::json!({ "routes": 1 }));
+    }
+
+    /// 测试 spring/requestSample 使用 app.toml 中的端口生成请求示例
+    #[test]
+    fn test_request_sample_request() {
+        use crate::scanner::request_sample::RequestSample;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(dir.path().join("config/app.toml"), "[web]\nport = 3000\n").unwrap();
+        std::fs::write(
+            dir.path().join("src/users.rs"),
+            "#[derive(Deserialize)]\nstruct NewUser {\n    name: String,\n}\n\n\
+             #[put(\"/users/{id}\")]\nasync fn update_user(Path(id): Path<i64>, Json(user): Json<NewUser>) {}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let request = |id: i32, path: &str| {
+            Request::new(
+                RequestId::from(id),
+                "spring/requestSample".to_string(),
+                serde_json::json!({
+                    "appPath": dir.path(),
+                    "method": "put",
+                    "path": path,
+                }),
+            )
+        };
+
+        server
+            .handle_message(Message::Request(request(1, "/users/{id}")))
+            .unwrap();
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let sample: RequestSample = serde_json::from_value(resp.result.unwrap()).unwrap();
+                assert_eq!(sample.method, "PUT");
+                assert_eq!(sample.url, "http://localhost:3000/users/1");
+                assert_eq!(sample.body.as_deref(), Some(r#"{"name": "string"}"#));
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+
+        // 找不到路由时返回 null
+        server
+            .handle_message(Message::Request(request(2, "/missing")))
+            .unwrap();
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => assert_eq!(resp.result, Some(serde_json::Value::Null)),
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试 spring/expandAllMacros 展开未打开的文件中的所有宏
+    #[test]
+    fn test_expand_all_macros_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jobs.rs");
+        std::fs::write(
+            &path,
+            "#[cron(\"0 0 * * * *\")]\nasync fn hourly() {}\n\n#[fix_rate(10)]\nasync fn poll() {}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let request = |id: i32, uri: Url| {
+            Request::new(
+                RequestId::from(id),
+                "spring/expandAllMacros".to_string(),
+                serde_json::json!({ "textDocument": { "uri": uri } }),
+            )
+        };
+        let mut response = |req: Request| {
+            server.handle_message(Message::Request(req)).unwrap();
+            match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Response(resp) => resp.result.unwrap(),
+                other => panic!("Expected response, got {:?}", other),
+            }
+        };
+
+        let result = response(request(1, Url::from_file_path(&path).unwrap()));
+        assert_eq!(result["expanded"], 2);
+        let content = result["content"].as_str().unwrap();
+        assert!(content.starts_with(
+            "#[cron(\"0 0 * * * *\")]\nasync fn hourly() {}\n// >>> 任务调度宏展开\n"
+        ));
+        assert!(content.contains("//     FixRateJob::new(10, || async {"));
+
+        // 不是 Rust 文件时返回 null
+        let toml = dir.path().join("app.toml");
+        std::fs::write(&toml, "[web]\n").unwrap();
+        let result = response(request(2, Url::from_file_path(&toml).unwrap()));
+        assert_eq!(result, serde_json::Value::Null);
+    }
+
+    /// 测试路由中间件链的悬停提示和缺少必需中间件的诊断
+    #[test]
+    fn test_middleware_hover_and_rules() {
+        use crate::core::config::MiddlewareRule;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/app.rs"),
+            "fn router() -> Router {\n    Router::new().layer(TraceLayer::new_for_http())\n}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.config.middleware.rules = vec![MiddlewareRule {
+            pattern: "/admin/**".to_string(),
+            required: "AuthMiddleware".to_string(),
+        }];
+
+        let uri = Url::from_file_path(dir.path().join("src/admin.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: "#[get(\"/admin/users\")]\nasync fn list_users() {}\n".to_string(),
+                },
+            })
+            .unwrap();
+
+        let diagnostics = server.diagnostic_engine.get(&uri);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            Some(lsp_types::NumberOrString::String(
+                "missing-middleware".into()
+            ))
+        );
+        assert!(diagnostics[0].message.contains("AuthMiddleware"));
+
+        // 丢弃 publishDiagnostics 通知
+        while client.receiver.try_recv().is_ok() {}
+
+        let req = Request::new(
+            RequestId::from(1),
+            HoverRequest::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "position": { "line": 1, "character": 12 },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let hover: lsp_types::Hover = serde_json::from_value(resp.result.unwrap()).unwrap();
+                let lsp_types::HoverContents::Markup(content) = hover.contents else {
+                    panic!("Expected markup hover");
+                };
+                assert!(content
+                    .value
+                    .contains("`TraceLayer::new_for_http()`（router）"));
+                assert!(content.value.contains("缺少必需的中间件 `AuthMiddleware`"));
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试中间件悬停显示关联配置的当前值
+    #[test]
+    fn test_middleware_config_hover() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(
+            dir.path().join("src/limit.rs"),
+            "#[derive(Configurable, Deserialize)]\n#[config_prefix = \"rate-limit\"]\nstruct RateLimitConfig {\n    per_second: u32,\n    burst: u32,\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("config/app.toml"),
+            "[rate-limit]\nper_second = 10\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: "#[middlewares(RateLimitLayer::new())]\n#[get(\"/\")]\nasync fn index() {}\n"
+                        .to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let req = Request::new(
+            RequestId::from(1),
+            HoverRequest::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "position": { "line": 0, "character": 16 },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let hover: lsp_types::Hover = serde_json::from_value(resp.result.unwrap()).unwrap();
+                let lsp_types::HoverContents::Markup(content) = hover.contents else {
+                    panic!("Expected markup hover");
+                };
+                assert!(content.value.contains("`RateLimitLayer::new()`"));
+                assert!(content.value.contains("配置节：`[rate-limit]`"));
+                assert!(content.value.contains("| `per_second` | `u32` | `10` |"));
+                assert!(content.value.contains("| `burst` | `u32` | *未设置* |"));
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试 `#[middlewares(...)]` 中补全工作空间中的中间件和内置中间件，并在内置中间件上显示说明
+    #[test]
+    fn test_middlewares_attr_completion_and_hover() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/auth.rs"),
+            "/// 校验 JWT 令牌\npub async fn auth(req: Request, next: Next) -> Response {\n    next.run(req).await\n}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let uri = Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+        server.document_manager.open(
+            uri.clone(),
+            1,
+            "#[middlewares(TraceLayer::new_for_http(), )]\n#[get(\"/\")]\nasync fn index() {}\n"
+                .to_string(),
+            "rust".to_string(),
+        );
+
+        let mut send = |method: &str, character: u32| {
+            let req = Request::new(
+                RequestId::from(1),
+                method.to_string(),
+                serde_json::json!({
+                    "textDocument": { "uri": uri },
+                    "position": { "line": 0, "character": character },
+                }),
+            );
+            server.handle_message(Message::Request(req)).unwrap();
+            match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Response(resp) => resp.result.unwrap(),
+                other => panic!("Expected response, got {:?}", other),
+            }
+        };
+
+        let items: Vec<lsp_types::CompletionItem> =
+            serde_json::from_value(send(Completion::METHOD, 42)).unwrap();
+        assert_eq!(items[0].label, "auth");
+        assert_eq!(
+            items[0].insert_text.as_deref(),
+            Some("middleware::from_fn(auth)")
+        );
+        assert!(items.iter().any(|item| item.label == "CorsLayer"));
+
+        let hover: lsp_types::Hover =
+            serde_json::from_value(send(HoverRequest::METHOD, 16)).unwrap();
+        let lsp_types::HoverContents::Markup(content) = hover.contents else {
+            panic!("Expected markup hover");
+        };
+        assert!(content.value.contains("`tower_http::trace::TraceLayer`"));
+        assert!(!content.value.contains("未找到关联的配置结构"));
+    }
+
+    /// 测试 `#[inject(component = "...")]` 中补全注册的组件名称和按约定的默认名称
+    #[test]
+    fn test_inject_component_name_completion() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/repo.rs"),
+            "#[component]\nfn user_repo() -> UserRepo {\n    UserRepo::default()\n}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let uri = Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+        server.document_manager.open(
+            uri.clone(),
+            1,
+            "#[derive(Clone, Service)]\nstruct Users {\n    #[inject(component = \"\n    repo: UserRepo,\n}\n"
+                .to_string(),
+            "rust".to_string(),
+        );
+
+        let req = Request::new(
+            RequestId::from(1),
+            Completion::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "position": { "line": 2, "character": 26 },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        let items: Vec<lsp_types::CompletionItem> = match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => serde_json::from_value(resp.result.unwrap()).unwrap(),
+            other => panic!("Expected response, got {:?}", other),
+        };
+        let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["UserRepo", "user_repo"]);
+        assert!(items[0].sort_text < items[1].sort_text);
+    }
+
+    /// 测试 profile 配置文件补全基础配置中尚未覆盖的配置项
+    #[test]
+    fn test_profile_override_completion() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(
+            dir.path().join("config/app.toml"),
+            "[web]\nhost = \"0.0.0.0\"\nport = 8080\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::from_file_path(dir.path().join("config/app-prod.toml")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "toml".to_string(),
+                    version: 1,
+                    text: "[web]\nhost = \"10.0.0.1\"".to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let req = Request::new(
+            RequestId::from(1),
+            Completion::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "position": { "line": 1, "character": 0 },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let items: Vec<lsp_types::CompletionItem> =
+                    serde_json::from_value(resp.result.unwrap()).unwrap();
+                assert_eq!(items[0].label, "port");
+                assert_eq!(items[0].detail.as_deref(), Some("base value: 8080"));
+                assert!(!items.iter().any(|item| item.label == "host"));
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试 AutoConfig 悬停列出将自动注册的路由、任务和消息流监听器
+    #[test]
+    fn test_auto_config_hover() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/handlers.rs"),
+            "#[get(\"/users\")]\nasync fn list_users() {}\n\n#[cron(\"0 0 * * * *\")]\nasync fn cleanup() {}\n\n#[stream_listener(\"orders\")]\nasync fn on_order() {}\n",
+        )
+        .unwrap();
+        let main_rs = "#[auto_config(WebConfigurator, spring_job::JobConfigurator)]\n#[tokio::main]\nasync fn main() {}\n";
+        std::fs::write(dir.path().join("src/main.rs"), main_rs).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: main_rs.to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let req = Request::new(
+            RequestId::from(1),
+            HoverRequest::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "position": { "line": 0, "character": 5 },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let hover: lsp_types::Hover = serde_json::from_value(resp.result.unwrap()).unwrap();
+                let lsp_types::HoverContents::Markup(content) = hover.contents else {
+                    panic!("Expected markup hover");
+                };
+                let handlers = Url::from_file_path(dir.path().join("src/handlers.rs")).unwrap();
+                assert!(content.value.contains("## 自动注册"));
+                assert!(content.value.contains("**路由**（1 个）"));
+                assert!(content
+                    .value
+                    .contains(&format!("[`GET /users`]({}#L2) → `list_users`", handlers)));
+                assert!(content.value.contains("**定时任务**（1 个）"));
+                assert!(content.value.contains("[`cleanup`]"));
+                // 未使用 StreamConfigurator，不列出消息流监听器
+                assert!(!content.value.contains("消息流监听器"));
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试 Service 悬停在字段后附上工作空间中类型的文档摘要
+    #[test]
+    fn test_service_hover() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/repo.rs"),
+            "/// 用户数据访问\n#[derive(Clone)]\npub struct UserRepo;\n",
+        )
+        .unwrap();
+        let main_rs = "#[derive(Clone, Service)]\nstruct UserService {\n    #[inject(component)]\n    repo: UserRepo,\n}\n";
+        std::fs::write(dir.path().join("src/main.rs"), main_rs).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: main_rs.to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let req = Request::new(
+            RequestId::from(1),
+            HoverRequest::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "position": { "line": 1, "character": 9 },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let hover: lsp_types::Hover = serde_json::from_value(resp.result.unwrap()).unwrap();
+                let lsp_types::HoverContents::Markup(content) = hover.contents else {
+                    panic!("Expected markup hover");
+                };
+                assert!(content.value.contains("# Service 派生宏"));
+                assert!(content
+                    .value
+                    .contains("`repo`: `UserRepo` - 注入组件 — 用户数据访问"));
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试提取器悬停显示其他文件中定义的结构体形状
+    #[test]
+    fn test_extractor_hover_cross_file_struct() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/dto.rs"),
+            "#[derive(Deserialize)]\npub struct NewUser {\n    #[serde(rename = \"userName\")]\n    name: String,\n    age: Option<u8>,\n}\n",
+        )
+        .unwrap();
+        let main_rs = "#[post(\"/users\")]\nasync fn create(Json(user): Json<NewUser>) {}\n";
+        std::fs::write(dir.path().join("src/main.rs"), main_rs).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: main_rs.to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let req = Request::new(
+            RequestId::from(1),
+            HoverRequest::METHOD.to_string(),
+            serde_json::json!({
+                "textDocument": { "uri": uri },
+                "position": { "line": 1, "character": 30 },
+            }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let hover: lsp_types::Hover = serde_json::from_value(resp.result.unwrap()).unwrap();
+                let lsp_types::HoverContents::Markup(content) = hover.contents else {
+                    panic!("Expected markup hover");
+                };
+                assert!(content.value.contains("# Json 提取器"));
+                assert!(content
+                    .value
+                    .contains("\"userName\": string,  // name: String"));
+                assert!(content
+                    .value
+                    .contains("\"age\"?: number | null  // age: Option<u8>"));
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试路由与 OpenAPI 规范文件的对照诊断和互相跳转
+    #[test]
+    fn test_openapi_spec_cross_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let main_rs = "#[get(\"/users/{id}\")]\nasync fn get_user() {}\n\n#[post(\"/users\")]\nasync fn create_user() {}\n";
+        std::fs::write(root.join("src/main.rs"), main_rs).unwrap();
+        let spec = "openapi: 3.0.3\npaths:\n  /users/{userId}:\n    get:\n      operationId: getUser\n    delete:\n      operationId: deleteUser\n";
+        std::fs::write(root.join("openapi.yaml"), spec).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let main_uri = Url::from_file_path(root.join("src/main.rs")).unwrap();
+        let spec_uri = Url::from_file_path(root.join("openapi.yaml")).unwrap();
+        for (uri, language_id, text) in [(&main_uri, "rust", main_rs), (&spec_uri, "yaml", spec)] {
+            server
+                .handle_did_open(DidOpenTextDocumentParams {
+                    text_document: TextDocumentItem {
+                        uri: uri.clone(),
+                        language_id: language_id.to_string(),
+                        version: 1,
+                        text: text.to_string(),
+                    },
+                })
+                .unwrap();
+        }
+        while client.receiver.try_recv().is_ok() {}
+
+        let messages = |uri: &Url, code: &str| -> Vec<String> {
+            server
+                .diagnostic_engine
+                .get(uri)
+                .into_iter()
+                .filter(|d| d.code == Some(lsp_types::NumberOrString::String(code.to_string())))
+                .map(|d| d.message)
+                .collect()
+        };
+        assert_eq!(
+            messages(&main_uri, "route-not-in-spec"),
+            vec!["路由 POST /users 没有在 OpenAPI 规范 openapi.yaml 中声明"]
+        );
+        assert_eq!(
+            messages(&spec_uri, "spec-operation-without-handler"),
+            vec!["OpenAPI 操作 DELETE /users/{userId}（deleteUser）没有对应的路由处理器"]
+        );
+
+        let mut goto = |uri: &Url, line: u32, character: u32| -> Vec<lsp_types::Location> {
+            let req = Request::new(
+                RequestId::from(1),
+                GotoDefinition::METHOD.to_string(),
+                serde_json::json!({
+                    "textDocument": { "uri": uri },
+                    "position": { "line": line, "character": character },
+                }),
+            );
+            server.handle_message(Message::Request(req)).unwrap();
+            match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Response(resp) => serde_json::from_value(resp.result.unwrap()).unwrap(),
+                other => panic!("Expected response, got {:?}", other),
+            }
+        };
+
+        // 路由路径 -> 规范中的操作
+        let locations = goto(&main_uri, 0, 10);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].uri, spec_uri);
+        assert_eq!(locations[0].range.start, lsp_types::Position::new(3, 4));
+
+        // 规范中的操作 -> 路由处理器
+        let locations = goto(&spec_uri, 3, 5);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].uri, main_uri);
+        assert!(goto(&spec_uri, 5, 5).is_empty());
+    }
+
+    /// 测试在注入的组件类型上跳转到提供它的 #[component] 函数
+    #[test]
+    fn test_goto_component_provider() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::write(
+            root.join("src/providers.rs"),
+            "#[component]\nasync fn create_client(Config(config): Config<RedisConfig>) -> Result<RedisClient> {\n    todo!()\n}\n",
+        )
+        .unwrap();
+        let main_rs =
+            "#[get(\"/cache\")]\nasync fn cache(Component(client): Component<RedisClient>) {}\n";
+        std::fs::write(root.join("src/main.rs"), main_rs).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let main_uri = Url::from_file_path(root.join("src/main.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: main_uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: main_rs.to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let mut goto = |line: u32, character: u32| -> Vec<lsp_types::Location> {
+            let req = Request::new(
+                RequestId::from(1),
+                GotoDefinition::METHOD.to_string(),
+                serde_json::json!({
+                    "textDocument": { "uri": main_uri },
+                    "position": { "line": line, "character": character },
+                }),
+            );
+            server.handle_message(Message::Request(req)).unwrap();
+            match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Response(resp) => serde_json::from_value(resp.result.unwrap()).unwrap(),
+                other => panic!("Expected response, got {:?}", other),
+            }
+        };
+
+        let locations = goto(1, 50);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(
+            locations[0].uri,
+            Url::from_file_path(root.join("src/providers.rs")).unwrap()
+        );
+        assert_eq!(locations[0].range.start, lsp_types::Position::new(1, 9));
+        assert!(goto(1, 20).is_empty());
+    }
+
+    /// 测试从提取器跳转到泛型参数的类型定义
+    #[test]
+    fn test_goto_extractor_type_definition() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::write(
+            root.join("src/models.rs"),
+            "#[derive(Deserialize)]\npub struct CreateUser {\n    name: String,\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("src/providers.rs"),
+            "#[component]\nfn create_pool() -> ConnectPool {\n    todo!()\n}\n",
+        )
+        .unwrap();
+        let main_rs = "#[post(\"/users\")]\nasync fn create(\n    Component(db): Component<ConnectPool>,\n    Json(body): Json<CreateUser>,\n) {}\n";
+        std::fs::write(root.join("src/main.rs"), main_rs).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let main_uri = Url::from_file_path(root.join("src/main.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: main_uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: main_rs.to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let mut goto = |line: u32, character: u32| -> Vec<lsp_types::Location> {
+            let req = Request::new(
+                RequestId::from(1),
+                GotoTypeDefinition::METHOD.to_string(),
+                serde_json::json!({
+                    "textDocument": { "uri": main_uri },
+                    "position": { "line": line, "character": character },
+                }),
+            );
+            server.handle_message(Message::Request(req)).unwrap();
+            match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Response(resp) => serde_json::from_value(resp.result.unwrap()).unwrap(),
+                other => panic!("Expected response, got {:?}", other),
+            }
+        };
+
+        // Json<CreateUser> -> 结构体定义
+        let locations = goto(3, 20);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(
+            locations[0].uri,
+            Url::from_file_path(root.join("src/models.rs")).unwrap()
+        );
+        assert_eq!(locations[0].range.start, lsp_types::Position::new(1, 11));
+
+        // Component<ConnectPool>：类型不在项目中定义，跳转到提供它的函数
+        let locations = goto(2, 20);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(
+            locations[0].uri,
+            Url::from_file_path(root.join("src/providers.rs")).unwrap()
+        );
+
+        // 参数名上没有类型定义
+        assert!(goto(3, 9).is_empty());
+    }
+
+    /// 测试从 `.route(...)` 注册的路径跳转到其他文件中的处理器函数
+    #[test]
+    fn test_goto_router_registration_handler() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/handlers")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::write(
+            root.join("src/handlers/mod.rs"),
+            "pub async fn list_users() {}\n",
+        )
+        .unwrap();
+        // 同名函数：优先使用完整路径匹配的函数
+        std::fs::write(root.join("src/admin.rs"), "pub async fn list_users() {}\n").unwrap();
+        let main_rs = "fn router() -> Router {\n    Router::new()\n        .route(\"/users\", get(handlers::list_users))\n        .route(\"/health\", get(|| async {}))\n}\n";
+        std::fs::write(root.join("src/main.rs"), main_rs).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let main_uri = Url::from_file_path(root.join("src/main.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: main_uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: main_rs.to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let mut goto = |line: u32, character: u32| -> Vec<lsp_types::Location> {
+            let req = Request::new(
+                RequestId::from(1),
+                GotoDefinition::METHOD.to_string(),
+                serde_json::json!({
+                    "textDocument": { "uri": main_uri },
+                    "position": { "line": line, "character": character },
+                }),
+            );
+            server.handle_message(Message::Request(req)).unwrap();
+            match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Response(resp) => serde_json::from_value(resp.result.unwrap()).unwrap(),
+                other => panic!("Expected response, got {:?}", other),
+            }
+        };
+
+        let locations = goto(2, 18);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(
+            locations[0].uri,
+            Url::from_file_path(root.join("src/handlers/mod.rs")).unwrap()
+        );
+        assert_eq!(locations[0].range.start, lsp_types::Position::new(0, 13));
+
+        // 闭包处理器无法定位
+        assert!(goto(3, 18).is_empty());
+    }
+
+    /// 测试重命名 spring 构造：拒绝 HTTP 方法，重命名路径参数和组件名称
+    #[test]
+    fn test_prepare_rename_and_rename() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::write(
+            root.join("src/jobs.rs"),
+            "#[derive(Clone, Service)]\nstruct Cleanup {\n    #[inject(component = \"primary\")]\n    db: Db,\n}\n",
+        )
+        .unwrap();
+        let main_rs = "#[get(\"/users/{id}\")]\nasync fn user(Path(id): Path<i64>) -> String {\n    id.to_string()\n}\n\n#[derive(Clone, Service)]\nstruct Users {\n    #[inject(component = \"primary\")]\n    db: Db,\n}\n";
+        std::fs::write(root.join("src/main.rs"), main_rs).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let main_uri = Url::from_file_path(root.join("src/main.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: main_uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: main_rs.to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let mut request = |method: &str, params: serde_json::Value| -> Response {
+            let req = Request::new(RequestId::from(1), method.to_string(), params);
+            server.handle_message(Message::Request(req)).unwrap();
+            match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Response(resp) => resp,
+                other => panic!("Expected response, got {:?}", other),
+            }
+        };
+        let position = |line: u32, character: u32| {
+            serde_json::json!({
+                "textDocument": { "uri": main_uri },
+                "position": { "line": line, "character": character },
+            })
+        };
+
+        // HTTP 方法宏不能重命名
+        let resp = request(PrepareRenameRequest::METHOD, position(0, 3));
+        let error = resp.error.unwrap();
+        assert_eq!(error.code, lsp_server::ErrorCode::RequestFailed as i32);
+        assert!(error.message.contains("'get'"));
+
+        // 路径参数返回名称的范围
+        let resp = request(PrepareRenameRequest::METHOD, position(0, 16));
+        let result: lsp_types::PrepareRenameResponse =
+            serde_json::from_value(resp.result.unwrap()).unwrap();
+        assert_eq!(
+            result,
+            lsp_types::PrepareRenameResponse::RangeWithPlaceholder {
+                range: lsp_types::Range::new(
+                    lsp_types::Position::new(0, 15),
+                    lsp_types::Position::new(0, 17)
+                ),
+                placeholder: "id".to_string(),
+            }
+        );
+
+        // 重命名路径参数：路由路径、参数绑定和函数体
+        let mut params = position(0, 16);
+        params["newName"] = serde_json::json!("user_id");
+        let resp = request(Rename::METHOD, params);
+        let edit: lsp_types::WorkspaceEdit = serde_json::from_value(resp.result.unwrap()).unwrap();
+        assert_eq!(edit.changes.unwrap()[&main_uri].len(), 3);
+
+        // 无效的路径参数名称
+        let mut params = position(0, 16);
+        params["newName"] = serde_json::json!("user-id");
+        let resp = request(Rename::METHOD, params);
+        assert_eq!(
+            resp.error.unwrap().code,
+            lsp_server::ErrorCode::InvalidParams as i32
+        );
+
+        // 重命名组件名称：项目中所有引用该名称的注入
+        let mut params = position(7, 28);
+        params["newName"] = serde_json::json!("main");
+        let resp = request(Rename::METHOD, params);
+        let edit: lsp_types::WorkspaceEdit = serde_json::from_value(resp.result.unwrap()).unwrap();
+        let changes = edit.changes.unwrap();
+        assert_eq!(changes.len(), 2);
+        let jobs_uri = Url::from_file_path(root.join("src/jobs.rs")).unwrap();
+        assert_eq!(changes[&jobs_uri][0].new_text, "main");
+        assert_eq!(
+            changes[&jobs_uri][0].range.start,
+            lsp_types::Position::new(2, 26)
+        );
+
+        // 不是 spring 构造时返回 null
+        let resp = request(PrepareRenameRequest::METHOD, position(2, 8));
+        assert_eq!(resp.result, Some(serde_json::Value::Null));
+    }
+
+    /// 测试未实现的处理器显示 stub 代码透镜
+    #[test]
+    fn test_stub_code_lens() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let uri = Url::parse("file:///test/src/main.rs").unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: "#[get(\"/users\")]\nasync fn list_users() {\n    todo!()\n}\n\n#[get(\"/health\")]\nasync fn health() -> &'static str {\n    \"ok\"\n}\n".to_string(),
+                },
+            })
+            .unwrap();
+        while client.receiver.try_recv().is_ok() {}
+
+        let req = Request::new(
+            RequestId::from(1),
+            CodeLensRequest::METHOD.to_string(),
+            serde_json::json!({ "textDocument": { "uri": uri } }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let lenses: Vec<lsp_types::CodeLens> =
+                    serde_json::from_value(resp.result.unwrap()).unwrap();
+                assert_eq!(lenses.len(), 1);
+                assert_eq!(
+                    lenses[0].command.as_ref().unwrap().title,
+                    "stub · GET /users"
+                );
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试配置项的悬停提示列出值来自哪个配置文件的哪一行，以及环境变量的默认值
+    #[test]
+    fn test_config_hover_shows_value_origin() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(
+            dir.path().join("config/app.toml"),
+            "[web]\nhost = \"127.0.0.1\"\nport = 8080\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("config/app-prod.toml"),
+            "[web]\nport = \"${PORT:80}\"\nworkers = 8\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let uri = Url::from_file_path(dir.path().join("config/app-prod.toml")).unwrap();
+        server.document_manager.open(
+            uri.clone(),
+            1,
+            "[web]\nport = \"${PORT:80}\"\nworkers = 8\n".to_string(),
+            "toml".to_string(),
+        );
+
+        let mut hover = |line: u32, character: u32| {
+            let req = Request::new(
+                RequestId::from(1),
+                HoverRequest::METHOD.to_string(),
+                serde_json::json!({
+                    "textDocument": { "uri": uri },
+                    "position": { "line": line, "character": character },
                 }),
+            );
+            server.handle_message(Message::Request(req)).unwrap();
+            let hover: lsp_types::Hover = match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Response(resp) => serde_json::from_value(resp.result.unwrap()).unwrap(),
+                other => panic!("Expected response, got {:?}", other),
+            };
+            match hover.contents {
+                lsp_types::HoverContents::Markup(content) => content.value,
+                other => panic!("Expected markup hover, got {:?}", other),
+            }
+        };
+
+        let port = hover(1, 10);
+        assert!(port.contains("**值来源**"));
+        let base = port.find("[app.toml:3]").unwrap();
+        let profile = port.find("[app-prod.toml:2]").unwrap();
+        // 基础配置在 profile 覆盖之前
+        assert!(base < profile);
+        assert!(port.contains("激活 profile `prod` 时（覆盖基础配置）"));
+        assert!(port.contains("环境变量 `PORT`，未设置时使用默认值 `80`"));
+        assert!(port.contains("app.toml#L3)"));
+
+        let workers = hover(2, 10);
+        assert!(workers.contains("[app-prod.toml:3]"));
+        assert!(workers.contains("`app.toml` 未设置该配置项"));
+    }
+
+    /// 测试项目 `Cargo.toml` 上的健康摘要代码透镜，点击后打开问题位置
+    #[test]
+    fn test_project_health_code_lens() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(dir.path().join("config/app.toml"), "[shop]\nlegacy = 1\n").unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let manifest = Url::from_file_path(dir.path().join("Cargo.toml")).unwrap();
+        let main_rs = Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+        let conflict = lsp_types::Range::new(
+            lsp_types::Position::new(4, 6),
+            lsp_types::Position::new(4, 14),
+        );
+        for code in ["W004", "component-not-registered", "E001"] {
+            server.diagnostic_engine.add(
+                main_rs.clone(),
+                lsp_types::Diagnostic {
+                    range: conflict,
+                    code: Some(lsp_types::NumberOrString::String(code.to_string())),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let mut send = |id: i32, method: &str, params: serde_json::Value| {
+            let req = Request::new(RequestId::from(id), method.to_string(), params);
+            server.handle_message(Message::Request(req)).unwrap();
+        };
+        let recv = || {
+            client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+        };
+
+        send(
+            1,
+            CodeLensRequest::METHOD,
+            serde_json::json!({ "textDocument": { "uri": manifest } }),
+        );
+        let lenses: Vec<lsp_types::CodeLens> = match recv() {
+            Message::Response(resp) => serde_json::from_value(resp.result.unwrap()).unwrap(),
+            other => panic!("Expected response, got {:?}", other),
+        };
+        let titles: Vec<_> = lenses
+            .iter()
+            .map(|lens| lens.command.as_ref().unwrap().title.as_str())
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                "spring 项目健康：1 个路由冲突",
+                "1 个未解析的注入",
+                "1 个未使用的配置项"
+            ]
+        );
+
+        let command = lenses[2].command.clone().unwrap();
+        assert_eq!(command.command, SHOW_HEALTH_REPORT_COMMAND);
+        send(
+            2,
+            ExecuteCommand::METHOD,
+            serde_json::json!({ "command": command.command, "arguments": command.arguments }),
+        );
+        match recv() {
+            Message::Request(req) => {
+                let params: lsp_types::ShowDocumentParams =
+                    serde_json::from_value(req.params).unwrap();
+                assert!(params.uri.path().ends_with("config/app.toml"));
+                assert_eq!(params.selection.unwrap().start.line, 1);
+            }
+            other => panic!("Expected showDocument request, got {:?}", other),
+        }
+        let locations: Vec<lsp_types::Location> = match recv() {
+            Message::Response(resp) => serde_json::from_value(resp.result.unwrap()).unwrap(),
+            other => panic!("Expected response, got {:?}", other),
+        };
+        assert_eq!(locations.len(), 1);
 
-                // 悬停提示能力
-                // 支持配置文档、宏展开、路由信息显示
-                hover_provider: Some(HoverProviderCapability::Simple(true)),
+        // 其他文件（包括依赖中的 Cargo.toml）没有健康摘要
+        send(
+            3,
+            CodeLensRequest::METHOD,
+            serde_json::json!({ "textDocument": { "uri": Url::from_file_path(dir.path().join("vendor/Cargo.toml")).unwrap() } }),
+        );
+        match recv() {
+            Message::Response(resp) => assert_eq!(resp.result.unwrap(), serde_json::json!([])),
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
 
-                // 定义跳转能力
-                // 支持路由路径跳转到处理器函数
-                definition_provider: Some(OneOf::Left(true)),
+    /// 测试配置结构体字段显示配置文件中的值，点击后请求客户端打开配置文件
+    #[test]
+    fn test_config_value_code_lens() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(
+            dir.path().join("config/app.toml"),
+            "[shop]\ncurrency = \"EUR\"\nlimit = 10\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("config/app-prod.toml"),
+            "[shop]\nlimit = 100\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let uri = Url::from_file_path(dir.path().join("src/config.rs")).unwrap();
+        server.document_manager.open(
+            uri.clone(),
+            1,
+            "#[derive(Configurable, Deserialize)]\n#[config_prefix = \"shop\"]\nstruct ShopConfig {\n    currency: String,\n    limit: u32,\n    timeout: Option<u64>,\n}\n".to_string(),
+            "rust".to_string(),
+        );
 
-                // 文档符号能力
-                // 支持显示文档中的所有路由
-                document_symbol_provider: Some(OneOf::Left(true)),
+        let req = Request::new(
+            RequestId::from(1),
+            CodeLensRequest::METHOD.to_string(),
+            serde_json::json!({ "textDocument": { "uri": uri } }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        let lenses: Vec<lsp_types::CodeLens> = match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => serde_json::from_value(resp.result.unwrap()).unwrap(),
+            other => panic!("Expected response, got {:?}", other),
+        };
+        let titles: Vec<(u32, &str)> = lenses
+            .iter()
+            .map(|lens| {
+                (
+                    lens.range.start.line,
+                    lens.command.as_ref().unwrap().title.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                (3, "= \"EUR\" · app.toml"),
+                (4, "= 10 · app.toml"),
+                (4, "= 100 · app-prod.toml"),
+                (5, "未配置（使用默认值）"),
+            ]
+        );
 
-                // 工作空间符号能力
-                // 支持全局搜索路由和组件
-                workspace_symbol_provider: Some(OneOf::Left(true)),
+        let command = lenses[2].command.clone().unwrap();
+        assert_eq!(command.command, SHOW_CONFIG_VALUE_COMMAND);
+        let req = Request::new(
+            RequestId::from(2),
+            ExecuteCommand::METHOD.to_string(),
+            serde_json::json!({ "command": command.command, "arguments": command.arguments }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Request(req) => {
+                assert_eq!(req.method, ShowDocument::METHOD);
+                let params: lsp_types::ShowDocumentParams =
+                    serde_json::from_value(req.params).unwrap();
+                assert!(params.uri.path().ends_with("config/app-prod.toml"));
+                assert_eq!(
+                    params.selection,
+                    Some(lsp_types::Range::new(
+                        lsp_types::Position::new(1, 8),
+                        lsp_types::Position::new(1, 11)
+                    ))
+                );
+            }
+            other => panic!("Expected showDocument request, got {:?}", other),
+        }
+    }
 
-                // 诊断能力（通过 publishDiagnostics 通知发送）
-                // 支持配置验证、路由验证、依赖注入验证
+    /// 测试配置结构体的必需字段缺少时同时在字段和配置节上报告错误
+    #[test]
+    fn test_config_field_missing_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        let source = "#[derive(Configurable, Deserialize)]\n#[config_prefix = \"shop\"]\nstruct ShopConfig {\n    currency: String,\n    #[serde(default)]\n    limit: u32,\n}\n";
+        std::fs::write(dir.path().join("src/config.rs"), source).unwrap();
+        std::fs::write(dir.path().join("config/app.toml"), "[shop]\nlimit = 10\n").unwrap();
+
+        let (mut server, _client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let is_missing = |d: &&lsp_types::Diagnostic| {
+            d.code
+                == Some(lsp_types::NumberOrString::String(
+                    "config-field-missing".into(),
+                ))
+        };
 
-                // 代码操作能力（未来支持快速修复）
-                // code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        let toml_uri = Url::from_file_path(dir.path().join("config/app.toml")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: toml_uri.clone(),
+                    language_id: "toml".to_string(),
+                    version: 1,
+                    text: "[shop]\nlimit = 10\n".to_string(),
+                },
+            })
+            .unwrap();
+        let diagnostics = server.diagnostic_engine.get(&toml_uri);
+        let missing: Vec<_> = diagnostics.iter().filter(is_missing).collect();
+        assert_eq!(missing.len(), 1);
+        assert!(missing[0].message.contains("currency"));
+        assert_eq!(missing[0].range.start.line, 0);
+
+        let rust_uri = Url::from_file_path(dir.path().join("src/config.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: rust_uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: source.to_string(),
+                },
+            })
+            .unwrap();
+        let diagnostics = server.diagnostic_engine.get(&rust_uri);
+        let missing: Vec<_> = diagnostics.iter().filter(is_missing).collect();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].range.start.line, 3);
+        assert_eq!(
+            missing[0].severity,
+            Some(lsp_types::DiagnosticSeverity::ERROR)
+        );
+    }
 
-                // 格式化能力（未来支持 TOML 格式化）
-                // document_formatting_provider: Some(OneOf::Left(true)),
+    /// 测试不同模块中的同名处理器给出提示
+    #[test]
+    fn test_duplicate_handler_name_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src/api")).unwrap();
+        let source = "#[get(\"/users\")]\nasync fn list() {}\n";
+        std::fs::write(dir.path().join("src/api/users.rs"), source).unwrap();
+        std::fs::write(
+            dir.path().join("src/api/orders.rs"),
+            "#[get(\"/orders\")]\nasync fn list() {}\n",
+        )
+        .unwrap();
+
+        let (mut server, _client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let uri = Url::from_file_path(dir.path().join("src/api/users.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: source.to_string(),
+                },
+            })
+            .unwrap();
+
+        let diagnostics = server.diagnostic_engine.get(&uri);
+        let duplicate = diagnostics
+            .iter()
+            .find(|d| {
+                d.code
+                    == Some(lsp_types::NumberOrString::String(
+                        "duplicate-handler-name".into(),
+                    ))
+            })
+            .unwrap();
+        assert_eq!(
+            duplicate.severity,
+            Some(lsp_types::DiagnosticSeverity::INFORMATION)
+        );
+        assert!(duplicate.message.contains("crate::api::orders::list"));
+    }
 
-                // 重命名能力（未来支持配置项重命名）
-                // rename_provider: Some(OneOf::Left(true)),
-                ..Default::default()
-            },
-            server_info: Some(ServerInfo {
-                name: "spring-lsp".to_string(),
-                version: Some(env!("CARGO_PKG_VERSION").to_string()),
-            }),
+    /// 测试在 handlers 目录中新建空文件后请求客户端填充模板
+    #[test]
+    fn test_did_create_files_applies_template() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/handlers")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::write(root.join("src/handlers/users.rs"), "").unwrap();
+        std::fs::write(root.join("src/handlers/orders.rs"), "// 已有内容\n").unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.apply_edit_support = true;
+
+        let users = Url::from_file_path(root.join("src/handlers/users.rs")).unwrap();
+        let files = [
+            "src/handlers/users.rs",
+            "src/handlers/orders.rs",
+            "src/lib.rs",
+        ]
+        .iter()
+        .map(|file| lsp_types::FileCreate {
+            uri: Url::from_file_path(root.join(file)).unwrap().to_string(),
         })
+        .collect();
+        server
+            .handle_message(Message::Notification(Notification::new(
+                DidCreateFiles::METHOD.to_string(),
+                lsp_types::CreateFilesParams { files },
+            )))
+            .unwrap();
+
+        let mut requests = Vec::new();
+        while let Ok(msg) = client
+            .receiver
+            .recv_timeout(std::time::Duration::from_millis(200))
+        {
+            if let Message::Request(req) = msg {
+                requests.push(req);
+            }
+        }
+        // 只有空的 handlers/users.rs 使用模板
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, ApplyWorkspaceEdit::METHOD);
+        let params: lsp_types::ApplyWorkspaceEditParams =
+            serde_json::from_value(requests[0].params.clone()).unwrap();
+        let edits = &params.edit.changes.unwrap()[&users];
+        assert!(edits[0].new_text.contains("#[get(\"/users\")]"));
     }
 
-    /// 发送错误响应
-    fn send_error_response(&self, id: RequestId, code: i32, message: String) -> Result<()> {
-        let response = Response {
-            id,
-            result: None,
-            error: Some(lsp_server::ResponseError {
-                code,
-                message,
-                data: None,
-            }),
+    /// 测试路由宏与 `Router::route` 重复注册同一路由时给出警告，且不误报处理器重名
+    #[test]
+    fn test_duplicate_route_registration_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        let source = "#[get(\"/users\")]\nasync fn list() {}\n";
+        std::fs::write(dir.path().join("src/users.rs"), source).unwrap();
+        std::fs::write(
+            dir.path().join("src/main.rs"),
+            "fn router() -> Router {\n    Router::new().route(\"/users/\", get(users::list))\n}\n",
+        )
+        .unwrap();
+
+        let (mut server, _client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let uri = Url::from_file_path(dir.path().join("src/users.rs")).unwrap();
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: uri.clone(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: source.to_string(),
+                },
+            })
+            .unwrap();
+
+        let diagnostics = server.diagnostic_engine.get(&uri);
+        let code = |code: &str| Some(lsp_types::NumberOrString::String(code.into()));
+        let duplicate = diagnostics
+            .iter()
+            .find(|d| d.code == code("duplicate-route-registration"))
+            .unwrap();
+        assert_eq!(
+            duplicate.severity,
+            Some(lsp_types::DiagnosticSeverity::WARNING)
+        );
+        assert!(duplicate.message.contains("crate::users::list"));
+        assert!(!diagnostics
+            .iter()
+            .any(|d| d.code == code("duplicate-handler-name")));
+    }
+
+    /// 测试修改配置节后只重新验证依赖它的 Rust 文件
+    #[test]
+    fn test_config_edit_revalidates_dependents() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("config")).unwrap();
+        std::fs::write(
+            dir.path().join("src/main.rs"),
+            "#[derive(Configurable, Deserialize)]\n#[config_prefix = \"user\"]\nstruct UserConfig {\n    name: String,\n}\n\n#[derive(Clone, Service)]\nstruct UserService {\n    #[inject(config)]\n    config: UserConfig,\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("src/web.rs"), "fn router() {}\n").unwrap();
+        let main_uri = Url::from_file_path(dir.path().join("src/main.rs")).unwrap();
+        let config_uri = Url::from_file_path(dir.path().join("config/app.toml")).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.dependencies_validated = true;
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: config_uri.clone(),
+                    language_id: "toml".to_string(),
+                    version: 1,
+                    text: "[user]\nname = \"admin\"\n".to_string(),
+                },
+            })
+            .unwrap();
+
+        let mut edit = |version: i32, text: &str| -> Vec<lsp_types::PublishDiagnosticsParams> {
+            while client.receiver.try_recv().is_ok() {}
+            server
+                .handle_did_change(DidChangeTextDocumentParams {
+                    text_document: VersionedTextDocumentIdentifier {
+                        uri: config_uri.clone(),
+                        version,
+                    },
+                    content_changes: vec![lsp_types::TextDocumentContentChangeEvent {
+                        range: None,
+                        range_length: None,
+                        text: text.to_string(),
+                    }],
+                })
+                .unwrap();
+            client
+                .receiver
+                .try_iter()
+                .filter_map(|message| match message {
+                    Message::Notification(not)
+                        if not.method == "textDocument/publishDiagnostics" =>
+                    {
+                        serde_json::from_value(not.params).ok()
+                    }
+                    _ => None,
+                })
+                .filter(|params: &lsp_types::PublishDiagnosticsParams| params.uri != config_uri)
+                .collect()
         };
 
-        self.connection
-            .sender
-            .send(Message::Response(response))
-            .map_err(|e| Error::MessageSend(e.to_string()))?;
+        // 删除 [user] 配置节：只重新验证 main.rs
+        let published = edit(2, "[web]\nport = 8080\n");
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].uri, main_uri);
+        assert_eq!(published[0].diagnostics.len(), 1);
+        assert_eq!(
+            published[0].diagnostics[0].code,
+            Some(lsp_types::NumberOrString::String(
+                CONFIG_NOT_FOUND.to_string()
+            ))
+        );
 
-        Ok(())
+        // 只修改 [web]：没有 Rust 文件需要重新验证
+        assert!(edit(3, "[web]\nport = 9090\n").is_empty());
+
+        // 恢复 [user]：配置注入诊断被清除
+        let published = edit(4, "[web]\nport = 9090\n\n[user]\nname = \"admin\"\n");
+        assert_eq!(published.len(), 1);
+        assert!(published[0].diagnostics.is_empty());
     }
 
-    /// 向客户端发送错误通知
-    ///
-    /// 使用 window/showMessage 通知向客户端显示错误消息
-    fn notify_client_error(&self, error: &Error) -> Result<()> {
-        use lsp_types::{MessageType, ShowMessageParams};
+    /// 测试依赖注入验证分批发布诊断，并响应取消请求
+    #[test]
+    fn test_validate_dependencies_batches_and_cancel() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["app", "orders"] {
+            let crate_root = dir.path().join(name);
+            std::fs::create_dir_all(crate_root.join("src")).unwrap();
+            std::fs::write(
+                crate_root.join("Cargo.toml"),
+                format!("[package]\nname = \"{}\"\n", name),
+            )
+            .unwrap();
+        }
+        std::fs::write(
+            dir.path().join("app/src/lib.rs"),
+            "#[derive(Service)]\nstruct UserService {\n    #[inject(config)]\n    config: UserConfig,\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("orders/src/lib.rs"),
+            "#[derive(Service)]\nstruct OrderService {\n    #[inject(config)]\n    config: OrderConfig,\n}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let app_path = dir.path().to_string_lossy().to_string();
 
-        let message_type = match error.severity() {
-            crate::error::ErrorSeverity::Error => MessageType::ERROR,
-            crate::error::ErrorSeverity::Warning => MessageType::WARNING,
-            crate::error::ErrorSeverity::Info => MessageType::INFO,
-        };
+        let req = Request::new(
+            RequestId::from(1),
+            "spring/validateDependencies".to_string(),
+            serde_json::json!({ "appPath": app_path }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        assert!(client.receiver.try_recv().is_err());
+
+        // 第一个批次只验证 app crate，立即发布该 crate 的诊断
+        server.step_dependency_validation().unwrap();
+        match client.receiver.try_recv().unwrap() {
+            Message::Notification(not) => {
+                let params: lsp_types::PublishDiagnosticsParams =
+                    serde_json::from_value(not.params).unwrap();
+                assert!(params.uri.path().ends_with("app/src/lib.rs"));
+                assert_eq!(params.diagnostics.len(), 1);
+            }
+            other => panic!("Expected notification, got {:?}", other),
+        }
+        assert!(client.receiver.try_recv().is_err());
+
+        // 取消后以 RequestCanceled 响应，剩余的 crate 不再验证
+        let not = Notification::new(Cancel::METHOD.to_string(), serde_json::json!({ "id": 1 }));
+        server.handle_message(Message::Notification(not)).unwrap();
+        server.step_dependency_validation().unwrap();
+        assert_eq!(
+            recv_error(&client).code,
+            lsp_server::ErrorCode::RequestCanceled as i32
+        );
+        assert!(server.dependency_validation.is_none());
 
-        let params = ShowMessageParams {
-            typ: message_type,
-            message: error.to_string(),
-        };
+        // 重新验证会清除旧诊断，并在全部批次完成后响应
+        let req = Request::new(
+            RequestId::from(2),
+            "spring/validateDependencies".to_string(),
+            serde_json::json!({ "appPath": app_path }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        while server.dependency_validation.is_some() {
+            server.step_dependency_validation().unwrap();
+        }
 
-        let notification = Notification {
-            method: "window/showMessage".to_string(),
-            params: serde_json::to_value(params)?,
-        };
+        let response = client
+            .receiver
+            .try_iter()
+            .find_map(|msg| match msg {
+                Message::Response(resp) => Some(resp),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(response.id, RequestId::from(2));
+        assert_eq!(
+            response.result.unwrap(),
+            serde_json::json!({ "crates": 2, "diagnostics": 2 })
+        );
+        assert_eq!(server.dependency_diagnostics.len(), 2);
+    }
 
-        self.connection
-            .sender
-            .send(Message::Notification(notification))
-            .map_err(|e| Error::MessageSend(e.to_string()))?;
+    /// 测试工作空间级验证的诊断分批限速发布，打开的文件优先
+    #[test]
+    fn test_dependency_diagnostics_are_throttled() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["app", "orders", "users"] {
+            let crate_root = dir.path().join(name);
+            std::fs::create_dir_all(crate_root.join("src")).unwrap();
+            std::fs::write(
+                crate_root.join("Cargo.toml"),
+                format!("[package]\nname = \"{}\"\n", name),
+            )
+            .unwrap();
+            std::fs::write(
+                crate_root.join("src/lib.rs"),
+                "#[derive(Service)]\nstruct Svc {\n    #[inject(config)]\n    config: MissingConfig,\n}\n",
+            )
+            .unwrap();
+        }
 
-        Ok(())
-    }
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.diagnostic_publisher =
+            DiagnosticPublisher::new(1, std::time::Duration::from_secs(60));
 
-    /// 优雅关闭服务器
-    pub fn shutdown(&mut self) -> Result<()> {
-        tracing::info!("Shutting down spring-lsp server");
+        let req = Request::new(
+            RequestId::from(1),
+            "spring/validateDependencies".to_string(),
+            serde_json::json!({ "appPath": dir.path().to_string_lossy() }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        while server.dependency_validation.is_some() {
+            server.step_dependency_validation().unwrap();
+        }
 
-        // 清理资源
-        tracing::debug!("Clearing all diagnostics...");
-        // TODO: 清理所有文档的诊断
-        // 需要实现 DocumentManager::get_all_uris() 方法
+        // 第一批立即发布，其余文件等待下一批
+        let published: Vec<_> = client
+            .receiver
+            .try_iter()
+            .filter_map(|msg| match msg {
+                Message::Notification(not) => Some(not),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(published.len(), 1);
+        assert_eq!(server.diagnostic_publisher.len(), 2);
 
-        tracing::debug!("Clearing document cache...");
-        // 清理文档缓存（DocumentManager 会自动清理）
+        // 打开的文件排在队列前面
+        let users_uri = Url::from_file_path(dir.path().join("users/src/lib.rs")).unwrap();
+        server
+            .document_manager
+            .open(users_uri.clone(), 1, String::new(), "rust".to_string());
+        server.diagnostic_publisher.enqueue(users_uri.clone(), true);
+        let batch = server
+            .diagnostic_publisher
+            .next_batch(std::time::Instant::now() + std::time::Duration::from_secs(60));
+        assert_eq!(batch, vec![users_uri]);
+    }
 
-        tracing::debug!("Clearing indexes...");
-        // 索引管理器会自动清理
+    /// 测试磁盘上未打开的文件变化后增量重新验证依赖注入
+    #[test]
+    fn test_watched_source_change_revalidates_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        let lib_rs = dir.path().join("src/lib.rs");
+        std::fs::write(
+            &lib_rs,
+            "#[derive(Service)]\nstruct UserService {\n    #[inject(config)]\n    config: UserConfig,\n}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let req = Request::new(
+            RequestId::from(1),
+            "spring/validateDependencies".to_string(),
+            serde_json::json!({ "appPath": dir.path().to_string_lossy() }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        while server.dependency_validation.is_some() {
+            server.step_dependency_validation().unwrap();
+        }
+        let lib_uri = Url::from_file_path(&lib_rs).unwrap();
+        assert!(server.dependency_diagnostics.contains_key(&lib_uri));
+        while client.receiver.try_recv().is_ok() {}
+
+        // 修复后的文件只在磁盘上变化（编辑器中没有打开）
+        std::fs::write(&lib_rs, "#[derive(Service)]\nstruct UserService;\n").unwrap();
+        let not = Notification::new(
+            DidChangeWatchedFiles::METHOD.to_string(),
+            serde_json::json!({ "changes": [{ "uri": lib_uri, "type": 2 }] }),
+        );
+        server.handle_message(Message::Notification(not)).unwrap();
+
+        assert!(server.dependency_diagnostics.is_empty());
+        let cleared = client.receiver.try_iter().any(|msg| match msg {
+            Message::Notification(not) => {
+                let params: lsp_types::PublishDiagnosticsParams =
+                    serde_json::from_value(not.params).unwrap();
+                params.uri == lib_uri && params.diagnostics.is_empty()
+            }
+            _ => false,
+        });
+        assert!(cleared);
+    }
 
-        tracing::info!("Server shutdown complete");
-        Ok(())
+    /// 测试完整验证之后新建的文件加入增量验证，其它文件复用完整验证的解析结果
+    #[test]
+    fn test_created_source_joins_incremental_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(
+            dir.path().join("src/user.rs"),
+            "#[derive(Service)]\nstruct UserService;\n",
+        )
+        .unwrap();
+
+        let (mut server, _client) = server_with_client();
+        server.state = ServerState::Initialized;
+        let req = Request::new(
+            RequestId::from(1),
+            "spring/validateDependencies".to_string(),
+            serde_json::json!({ "appPath": dir.path().to_string_lossy() }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        while server.dependency_validation.is_some() {
+            server.step_dependency_validation().unwrap();
+        }
+        assert!(server.dependency_diagnostics.is_empty());
+        assert_eq!(server.incremental_di.stats().computed, 2);
+
+        let mail_rs = dir.path().join("src/mail.rs");
+        std::fs::write(
+            &mail_rs,
+            "#[derive(Service)]\nstruct MailService {\n    #[inject(config)]\n    config: MailConfig,\n}\n",
+        )
+        .unwrap();
+        let mail_uri = Url::from_file_path(&mail_rs).unwrap();
+        let not = Notification::new(
+            DidChangeWatchedFiles::METHOD.to_string(),
+            serde_json::json!({ "changes": [{ "uri": mail_uri, "type": 1 }] }),
+        );
+        server.handle_message(Message::Notification(not)).unwrap();
+
+        assert_eq!(server.dependency_diagnostics[&mail_uri].len(), 1);
+        // 只解析新建的文件
+        assert_eq!(server.incremental_di.stats().computed, 3);
     }
-}
 
-impl Default for LspServer {
-    fn default() -> Self {
-        Self::start().expect("Failed to start LSP server")
+    /// 测试无效的通知参数不会导致服务器退出
+    #[test]
+    fn test_invalid_notification_params_are_ignored() {
+        let (mut server, _client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        let not = Notification::new(
+            DidOpenTextDocument::METHOD.to_string(),
+            serde_json::json!({ "textDocument": 42 }),
+        );
+        assert!(server.handle_message(Message::Notification(not)).is_ok());
+        assert_eq!(server.state, ServerState::Initialized);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use lsp_types::{
-        ClientCapabilities, ClientInfo, InitializeParams, TextDocumentItem, Url,
-        VersionedTextDocumentIdentifier, WorkDoneProgressParams,
-    };
+    /// 测试客户端断开后事件循环退出而不是无限重试
+    #[test]
+    fn test_event_loop_stops_on_disconnect() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        drop(client);
+
+        assert!(server.event_loop().is_ok());
+    }
 
     /// 测试服务器状态转换
     #[test]
@@ -1757,6 +8445,100 @@ mod tests {
         assert!(server.document_manager.get(&uri).is_none());
     }
 
+    /// 测试协商的位置编码只影响协商它的服务器
+    #[test]
+    fn test_negotiated_position_encoding_is_per_server() {
+        let content = "/* 中文 */ #[get(\"/users\")]\nasync fn list_users() {}\n";
+        let path_column = |server: &LspServer| {
+            let file = server.handler_analyzer.analyze(content).unwrap();
+            file.handlers[0].routes[0].range.start.character
+        };
+
+        let mut utf8 = LspServer::new_for_test().unwrap();
+        let capabilities = ClientCapabilities {
+            general: Some(lsp_types::GeneralClientCapabilities {
+                position_encodings: Some(vec![lsp_types::PositionEncodingKind::UTF8]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let result = utf8
+            .handle_initialize(InitializeParams {
+                capabilities,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            result.capabilities.position_encoding,
+            Some(lsp_types::PositionEncodingKind::UTF8)
+        );
+        let mut utf16 = LspServer::new_for_test().unwrap();
+        utf16
+            .handle_initialize(InitializeParams::default())
+            .unwrap();
+
+        assert_eq!(path_column(&utf8), 19);
+        assert_eq!(path_column(&utf16), 15);
+
+        // 打开的文档使用所在服务器协商的编码
+        let uri = Url::parse("file:///app/src/main.rs").unwrap();
+        let attr = content.find('#').unwrap();
+        for server in [&utf8, &utf16] {
+            server
+                .document_manager
+                .open(uri.clone(), 1, content.to_string(), "rust".to_string());
+        }
+        let position = |server: &LspServer| {
+            let doc = server.document_manager.get(&uri).unwrap();
+            doc.mapper().position(attr)
+        };
+        assert_eq!(position(&utf8), lsp_types::Position::new(0, 13));
+        assert_eq!(position(&utf16), lsp_types::Position::new(0, 9));
+    }
+
+    #[test]
+    fn test_initialization_locale_is_per_server() {
+        let content = "#[fix_rate(5)]\nasync fn sync() {\n    tokio::time::sleep(Duration::from_secs(8)).await;\n}\n";
+        let uri = Url::parse("file:///app/src/jobs.rs").unwrap();
+        let hover_text = |server: &LspServer| {
+            server
+                .document_manager
+                .open(uri.clone(), 1, content.to_string(), "rust".to_string());
+            let doc = server.document_manager.get(&uri).unwrap();
+            match server
+                .rust_hover(doc, lsp_types::Position::new(0, 3))
+                .unwrap()
+                .contents
+            {
+                lsp_types::HoverContents::Markup(content) => content.value,
+                other => panic!("unexpected hover contents: {:?}", other),
+            }
+        };
+
+        let mut english = LspServer::new_for_test().unwrap();
+        english
+            .handle_initialize(InitializeParams {
+                initialization_options: Some(serde_json::json!({ "locale": "en" })),
+                ..Default::default()
+            })
+            .unwrap();
+        let mut chinese = LspServer::new_for_test().unwrap();
+        chinese
+            .handle_initialize(InitializeParams::default())
+            .unwrap();
+
+        let text = hover_text(&english);
+        assert!(
+            text.contains("**Schedule timeline** (fixed waits in the job body take at least 8s)")
+        );
+        assert!(!text.contains("调度时间线"));
+        assert!(hover_text(&chinese).contains("**调度时间线**（任务体中的固定等待至少 8s）"));
+
+        // 其他生成消息的分析器使用同一语言
+        assert_eq!(english.toml_analyzer.locale(), Locale::En);
+        assert_eq!(chinese.handler_analyzer.locale(), Locale::ZhCn);
+    }
+
     /// 测试初始化响应
     #[test]
     fn test_initialize_response() {
@@ -1784,6 +8566,9 @@ mod tests {
 
         let result = server.handle_initialize(params).unwrap();
 
+        // 客户端没有声明代码片段支持，补全项使用纯文本
+        assert!(!server.snippet_support);
+
         // 验证服务器信息
         assert!(result.server_info.is_some());
         let server_info = result.server_info.unwrap();
@@ -1822,12 +8607,66 @@ mod tests {
 
         // 验证定义跳转能力
         assert!(capabilities.definition_provider.is_some());
+        assert!(capabilities.type_definition_provider.is_some());
+        assert_eq!(
+            capabilities.rename_provider,
+            Some(lsp_types::OneOf::Right(lsp_types::RenameOptions {
+                prepare_provider: Some(true),
+                work_done_progress_options: lsp_types::WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+            }))
+        );
 
         // 验证文档符号能力
         assert!(capabilities.document_symbol_provider.is_some());
 
+        // 验证代码透镜能力
+        assert!(capabilities.code_lens_provider.is_some());
+
         // 验证工作空间符号能力
         assert!(capabilities.workspace_symbol_provider.is_some());
+
+        // 验证颜色能力
+        assert!(capabilities.color_provider.is_some());
+
+        // 验证自定义请求列表
+        let experimental = capabilities.experimental.unwrap();
+        assert_eq!(
+            experimental[extensions::EXPERIMENTAL_KEY]["requests"][extensions::ROUTES_REQUEST],
+            2
+        );
+    }
+
+    /// 测试 initialize 响应中列出的自定义请求都能被分发
+    #[test]
+    fn test_advertised_requests_are_dispatched() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+
+        for (i, (method, _)) in extensions::CUSTOM_REQUESTS.iter().enumerate() {
+            let id = RequestId::from(i as i32 + 1);
+            let req = Request::new(id.clone(), method.to_string(), serde_json::json!({}));
+            server.handle_message(Message::Request(req)).unwrap();
+
+            // 跳过处理过程中发送的通知，直到收到该请求的响应
+            let resp = loop {
+                match client
+                    .receiver
+                    .recv_timeout(std::time::Duration::from_secs(5))
+                    .unwrap()
+                {
+                    Message::Response(resp) if resp.id == id => break resp,
+                    _ => continue,
+                }
+            };
+            assert!(
+                resp.error.as_ref().map(|error| error.code)
+                    != Some(lsp_server::ErrorCode::MethodNotFound as i32),
+                "{} is advertised but not dispatched",
+                method
+            );
+        }
     }
 
     /// 测试错误恢复
@@ -1857,4 +8696,161 @@ mod tests {
         // 验证文档仍然不存在
         assert!(server.document_manager.get(&uri).is_none());
     }
+
+    /// 测试项目扫描优先使用编辑器中打开的文档（包括尚未保存的修改）
+    #[test]
+    fn test_project_scan_prefers_unsaved_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        let jobs_rs = dir.path().join("src/jobs.rs");
+        std::fs::write(
+            &jobs_rs,
+            "#[cron(\"0 0 * * * *\")]\nasync fn cleanup() {}\n",
+        )
+        .unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        // 编辑器中把 cleanup 重命名为 purge，尚未保存
+        server.document_manager.open(
+            lsp_types::Url::from_file_path(&jobs_rs).unwrap(),
+            2,
+            "#[cron(\"0 0 * * * *\")]\nasync fn purge() {}\n".to_string(),
+            "rust".to_string(),
+        );
+
+        let req = Request::new(
+            RequestId::from(1),
+            extensions::JOBS_REQUEST.to_string(),
+            serde_json::json!({ "appPath": dir.path() }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let result = resp.result.unwrap();
+                let names: Vec<_> = result["jobs"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|job| job["name"].as_str().unwrap())
+                    .collect();
+                assert_eq!(names, vec!["purge"]);
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试内存占用超出预算时释放解析结果和已关闭文档的内容，并通过状态查询报告内存占用
+    #[test]
+    fn test_memory_budget_eviction() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        let main_rs = "fn main() {}\n";
+        let mail_rs = "#[derive(Clone, Service)]\nstruct MailService {}\n";
+        std::fs::write(dir.path().join("src/main.rs"), main_rs).unwrap();
+        std::fs::write(dir.path().join("src/mail.rs"), mail_rs).unwrap();
+
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server.document_manager.open(
+            lsp_types::Url::from_file_path(dir.path().join("src/main.rs")).unwrap(),
+            1,
+            main_rs.to_string(),
+            "rust".to_string(),
+        );
+        let mut crates = CrateSources::discover(dir.path());
+        crates[0].overlay(&server.document_manager);
+        server.incremental_di.load(&crates);
+        server.update_dependency_diagnostics(None).unwrap();
+
+        let usage = server.memory_usage();
+        assert_eq!(usage.documents, main_rs.len());
+        assert_eq!(usage.sources, main_rs.len() + mail_rs.len());
+        assert!(usage.parsed > 0);
+        assert_eq!(usage.budget, 1024 * 1024 * 1024);
+
+        // 只保留打开的文档的内容
+        server.evict_caches(1);
+        let usage = server.memory_usage();
+        assert_eq!(usage.parsed, 0);
+        assert_eq!(usage.sources, main_rs.len());
+
+        let req = Request::new(
+            RequestId::from(1),
+            extensions::STATUS_REQUEST.to_string(),
+            serde_json::json!({}),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        match client
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap()
+        {
+            Message::Response(resp) => {
+                let memory = &resp.result.unwrap()["memory"];
+                assert_eq!(memory["sources"], main_rs.len());
+                assert_eq!(memory["parsed"], 0);
+            }
+            other => panic!("Expected response, got {:?}", other),
+        }
+    }
+
+    /// 测试错误恢复统计：解析失败和处理失败的请求出现在状态查询中
+    #[test]
+    fn test_error_report_in_status() {
+        let (mut server, client) = server_with_client();
+        server.state = ServerState::Initialized;
+        server
+            .handle_did_open(DidOpenTextDocumentParams {
+                text_document: lsp_types::TextDocumentItem {
+                    uri: lsp_types::Url::parse("file:///app/src/broken.rs").unwrap(),
+                    language_id: "rust".to_string(),
+                    version: 1,
+                    text: "fn broken( {".to_string(),
+                },
+            })
+            .unwrap();
+
+        // 参数无效的请求
+        let req = Request::new(
+            RequestId::from(1),
+            HoverRequest::METHOD.to_string(),
+            serde_json::json!({ "invalid": true }),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+
+        let req = Request::new(
+            RequestId::from(2),
+            extensions::STATUS_REQUEST.to_string(),
+            serde_json::json!({}),
+        );
+        server.handle_message(Message::Request(req)).unwrap();
+        let status = loop {
+            match client
+                .receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .unwrap()
+            {
+                Message::Response(resp) if resp.id == RequestId::from(2) => {
+                    break resp.result.unwrap();
+                }
+                _ => continue,
+            }
+        };
+        let errors = &status["errors"];
+        assert_eq!(errors["panics"], 0);
+        assert_eq!(errors["parseFailures"]["rust"], 1);
+        assert_eq!(errors["errorsByMethod"][HoverRequest::METHOD], 1);
+
+        assert_eq!(panic_message(&"boom"), "boom");
+        assert_eq!(panic_message(&"boom".to_string()), "boom");
+        assert_eq!(panic_message(&42), "unknown panic");
+    }
 }
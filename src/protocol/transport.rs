@@ -0,0 +1,602 @@
+//! JSON-RPC 消息帧传输层
+//!
+//! 本模块替代 `lsp_server::Connection::stdio()` 自带的读写线程，
+//! 对不规范的客户端输入进行容错处理：
+//!
+//! - **畸形头部**：无法识别的头部行会被跳过，不会中断连接
+//! - **消息大小限制**：超过 `max_message_size` 的消息体会被丢弃，并返回错误响应
+//! - **部分读取**：消息体按 `Content-Length` 完整读取，底层读取被拆分时也能正确重组
+//! - **无效 JSON**：按 JSON-RPC 规范返回 `ParseError`（id 为 null）
+//!
+//! ## 消息格式
+//!
+//! ```text
+//! Content-Length: <字节数>\r\n
+//! Content-Type: application/vscode-jsonrpc; charset=utf-8\r\n   (可选)
+//! \r\n
+//! <JSON 消息体>
+//! ```
+
+use crossbeam_channel::{bounded, Receiver, Sender};
+use lsp_server::{Connection, Message};
+use std::io::{self, BufRead, Read, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// 默认的最大消息大小（32 MiB）
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 32 * 1024 * 1024;
+
+/// 单个头部行的最大长度
+const MAX_HEADER_LINE_LENGTH: usize = 8 * 1024;
+
+/// JSON-RPC 解析错误码
+const PARSE_ERROR_CODE: i32 = -32700;
+
+/// JSON-RPC 无效请求错误码
+const INVALID_REQUEST_CODE: i32 = -32600;
+
+/// 消息帧错误
+#[derive(Debug, thiserror::Error)]
+pub enum FramingError {
+    /// 头部中缺少 Content-Length
+    #[error("Missing Content-Length header")]
+    MissingContentLength,
+
+    /// 头部格式错误
+    #[error("Malformed header: {0}")]
+    MalformedHeader(String),
+
+    /// 消息体超过大小限制
+    #[error("Payload too large: {size} bytes exceeds limit of {max} bytes")]
+    PayloadTooLarge { size: usize, max: usize },
+
+    /// 消息体不是合法的 JSON-RPC 消息
+    #[error("Invalid JSON-RPC payload: {0}")]
+    InvalidPayload(String),
+
+    /// 输入流在消息中途结束
+    #[error("Unexpected end of stream")]
+    UnexpectedEof,
+
+    /// I/O 错误
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl FramingError {
+    /// 判断错误发生后是否可以继续读取下一条消息
+    ///
+    /// 流结束和 I/O 错误之后无法再同步消息边界，其他错误只影响当前消息。
+    pub fn is_recoverable(&self) -> bool {
+        !matches!(self, FramingError::UnexpectedEof | FramingError::Io(_))
+    }
+}
+
+/// 读取一个完整的消息帧，返回消息体字节
+///
+/// 输入流在消息边界处结束时返回 `Ok(None)`。
+pub fn read_frame<R: BufRead>(
+    reader: &mut R,
+    max_message_size: usize,
+) -> Result<Option<Vec<u8>>, FramingError> {
+    let mut content_length: Option<usize> = None;
+    let mut saw_header = false;
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        let read = read_header_line(reader, &mut line)?;
+        if read == 0 {
+            return if saw_header {
+                Err(FramingError::UnexpectedEof)
+            } else {
+                Ok(None)
+            };
+        }
+
+        let text = String::from_utf8_lossy(&line);
+        let text = text.trim();
+
+        if text.is_empty() {
+            if saw_header {
+                break;
+            }
+            // 消息之间多余的空行直接忽略
+            continue;
+        }
+
+        // 上一条消息的残留数据可能与下一个头部粘在同一行，从 Content-Length 处重新同步
+        let header = match find_case_insensitive(text, "content-length:") {
+            Some(index) => &text[index..],
+            None => text,
+        };
+
+        let Some((name, value)) = header.split_once(':') else {
+            tracing::warn!("Skipping malformed header line: {:?}", truncate(text));
+            continue;
+        };
+
+        saw_header = true;
+
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            let length = value.trim().parse::<usize>().map_err(|_| {
+                FramingError::MalformedHeader(format!(
+                    "invalid Content-Length value: {:?}",
+                    truncate(value.trim())
+                ))
+            })?;
+
+            if content_length.is_some_and(|existing| existing != length) {
+                return Err(FramingError::MalformedHeader(
+                    "conflicting Content-Length headers".to_string(),
+                ));
+            }
+            content_length = Some(length);
+        } else if !name.trim().eq_ignore_ascii_case("content-type") {
+            tracing::debug!("Ignoring unknown header: {}", name.trim());
+        }
+    }
+
+    let size = content_length.ok_or(FramingError::MissingContentLength)?;
+
+    if size > max_message_size {
+        // 丢弃整个消息体，保持后续消息边界同步
+        let discarded = io::copy(&mut reader.by_ref().take(size as u64), &mut io::sink())?;
+        if discarded < size as u64 {
+            return Err(FramingError::UnexpectedEof);
+        }
+        return Err(FramingError::PayloadTooLarge {
+            size,
+            max: max_message_size,
+        });
+    }
+
+    let mut body = vec![0u8; size];
+    reader.read_exact(&mut body).map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof => FramingError::UnexpectedEof,
+        _ => FramingError::Io(e),
+    })?;
+
+    Ok(Some(body))
+}
+
+/// 读取并解析一条 JSON-RPC 消息
+///
+/// 输入流在消息边界处结束时返回 `Ok(None)`。
+pub fn read_message<R: BufRead>(
+    reader: &mut R,
+    max_message_size: usize,
+) -> Result<Option<Message>, FramingError> {
+    let Some(body) = read_frame(reader, max_message_size)? else {
+        return Ok(None);
+    };
+
+    serde_json::from_slice::<Message>(&body)
+        .map(Some)
+        .map_err(|e| FramingError::InvalidPayload(e.to_string()))
+}
+
+/// 写入一条 JSON-RPC 消息
+pub fn write_message<W: Write>(writer: &mut W, message: Message) -> io::Result<()> {
+    message.write(writer)
+}
+
+/// 写入一条 id 为 null 的错误响应
+///
+/// 当消息无法解析、无法得知请求 id 时使用（JSON-RPC 2.0 第 5 节）。
+pub fn write_error_response<W: Write>(writer: &mut W, code: i32, message: &str) -> io::Result<()> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": {
+            "code": code,
+            "message": message,
+        },
+    })
+    .to_string();
+
+    write!(writer, "Content-Length: {}\r\n\r\n", body.len())?;
+    writer.write_all(body.as_bytes())?;
+    writer.flush()
+}
+
+/// 传输层 I/O 线程
+pub struct IoThreads {
+    reader: thread::JoinHandle<io::Result<()>>,
+    writer: thread::JoinHandle<io::Result<()>>,
+}
+
+impl IoThreads {
+    /// 等待读写线程结束
+    pub fn join(self) -> io::Result<()> {
+        match self.reader.join() {
+            Ok(result) => result?,
+            Err(e) => std::panic::resume_unwind(e),
+        }
+        match self.writer.join() {
+            Ok(result) => result,
+            Err(e) => std::panic::resume_unwind(e),
+        }
+    }
+}
+
+/// 通过标准输入输出创建 LSP 连接
+pub fn stdio(max_message_size: usize) -> (Connection, IoThreads) {
+    spawn(
+        io::BufReader::new(io::stdin()),
+        io::stdout(),
+        max_message_size,
+    )
+}
+
+/// 在任意读写流上创建 LSP 连接
+///
+/// 读线程负责消息帧解析和容错，写线程负责发送服务器消息。
+pub fn spawn<R, W>(reader: R, writer: W, max_message_size: usize) -> (Connection, IoThreads)
+where
+    R: BufRead + Send + 'static,
+    W: Write + Send + 'static,
+{
+    let writer = Arc::new(Mutex::new(writer));
+
+    let (reader_sender, reader_receiver) = bounded::<Message>(0);
+    let reader_thread = {
+        let writer = Arc::clone(&writer);
+        thread::Builder::new()
+            .name("LspServerReader".to_string())
+            .spawn(move || reader_loop(reader, writer, reader_sender, max_message_size))
+            .expect("failed to spawn reader thread")
+    };
+
+    let (writer_sender, writer_receiver) = bounded::<Message>(0);
+    let writer_thread = thread::Builder::new()
+        .name("LspServerWriter".to_string())
+        .spawn(move || writer_loop(writer, writer_receiver))
+        .expect("failed to spawn writer thread");
+
+    (
+        Connection {
+            sender: writer_sender,
+            receiver: reader_receiver,
+        },
+        IoThreads {
+            reader: reader_thread,
+            writer: writer_thread,
+        },
+    )
+}
+
+/// 读线程主循环
+fn reader_loop<R, W>(
+    mut reader: R,
+    writer: Arc<Mutex<W>>,
+    sender: Sender<Message>,
+    max_message_size: usize,
+) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    loop {
+        let message = match read_message(&mut reader, max_message_size) {
+            Ok(Some(message)) => message,
+            Ok(None) => {
+                tracing::info!("Client closed the input stream");
+                return Ok(());
+            }
+            Err(e) if e.is_recoverable() => {
+                tracing::warn!("Discarding invalid message: {}", e);
+                let reply = match &e {
+                    FramingError::InvalidPayload(_) => Some(PARSE_ERROR_CODE),
+                    FramingError::PayloadTooLarge { .. } => Some(INVALID_REQUEST_CODE),
+                    _ => None,
+                };
+                if let Some(code) = reply {
+                    let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+                    write_error_response(&mut *writer, code, &e.to_string())?;
+                }
+                continue;
+            }
+            Err(FramingError::Io(e)) => return Err(e),
+            Err(e) => {
+                tracing::warn!("Transport stopped: {}", e);
+                return Ok(());
+            }
+        };
+
+        let is_exit = matches!(&message, Message::Notification(n) if n.method == "exit");
+
+        if sender.send(message).is_err() {
+            // 服务器已停止接收消息
+            return Ok(());
+        }
+
+        if is_exit {
+            return Ok(());
+        }
+    }
+}
+
+/// 写线程主循环
+fn writer_loop<W: Write>(writer: Arc<Mutex<W>>, receiver: Receiver<Message>) -> io::Result<()> {
+    for message in receiver {
+        let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+        write_message(&mut *writer, message)?;
+    }
+    Ok(())
+}
+
+/// 读取一个头部行（包含换行符），超长部分会被丢弃
+fn read_header_line<R: BufRead>(reader: &mut R, line: &mut Vec<u8>) -> io::Result<usize> {
+    let read = reader
+        .by_ref()
+        .take(MAX_HEADER_LINE_LENGTH as u64)
+        .read_until(b'\n', line)?;
+
+    if read == MAX_HEADER_LINE_LENGTH && !line.ends_with(b"\n") {
+        // 跳过超长行的剩余部分
+        let mut rest = Vec::new();
+        let skipped = reader.read_until(b'\n', &mut rest)?;
+        tracing::warn!(
+            "Header line exceeds {} bytes, skipped {} bytes",
+            MAX_HEADER_LINE_LENGTH,
+            read + skipped
+        );
+        line.clear();
+        line.extend_from_slice(b"<oversized header line>\n");
+    }
+
+    Ok(read)
+}
+
+/// 不区分大小写地查找子串位置
+fn find_case_insensitive(haystack: &str, needle: &str) -> Option<usize> {
+    haystack.to_ascii_lowercase().find(needle)
+}
+
+/// 截断过长的文本，用于日志和错误消息
+fn truncate(text: &str) -> String {
+    const LIMIT: usize = 64;
+    match text.char_indices().nth(LIMIT) {
+        Some((index, _)) => format!("{}...", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Cursor};
+    use std::time::Duration;
+
+    /// 模拟每次只返回少量字节的客户端
+    struct TrickleReader {
+        data: Vec<u8>,
+        position: usize,
+        chunk: usize,
+    }
+
+    impl Read for TrickleReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let remaining = self.data.len() - self.position;
+            let n = remaining.min(self.chunk).min(buf.len());
+            buf[..n].copy_from_slice(&self.data[self.position..self.position + n]);
+            self.position += n;
+            Ok(n)
+        }
+    }
+
+    /// 共享的输出缓冲区
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn frame(body: &str) -> String {
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+
+    const INITIALIZED: &str = r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#;
+
+    fn read_all(input: &str, max: usize) -> Vec<Result<Option<Message>, FramingError>> {
+        let mut reader = Cursor::new(input.as_bytes().to_vec());
+        let mut results = Vec::new();
+        loop {
+            let result = read_message(&mut reader, max);
+            let stop =
+                matches!(result, Ok(None)) || matches!(&result, Err(e) if !e.is_recoverable());
+            results.push(result);
+            if stop {
+                break;
+            }
+        }
+        results
+    }
+
+    #[test]
+    fn test_read_well_formed_message() {
+        let results = read_all(&frame(INITIALIZED), DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(
+            &results[0],
+            Ok(Some(Message::Notification(n))) if n.method == "initialized"
+        ));
+        assert!(matches!(results[1], Ok(None)));
+    }
+
+    #[test]
+    fn test_content_type_and_case_insensitive_headers() {
+        let input = format!(
+            "content-length: {}\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{}",
+            INITIALIZED.len(),
+            INITIALIZED
+        );
+        let results = read_all(&input, DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(results[0], Ok(Some(Message::Notification(_)))));
+    }
+
+    #[test]
+    fn test_malformed_header_lines_are_skipped() {
+        let input = format!(
+            "\r\nthis is not a header\r\nX-Custom: 1\r\nContent-Length: {}\r\n\r\n{}",
+            INITIALIZED.len(),
+            INITIALIZED
+        );
+        let results = read_all(&input, DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(results[0], Ok(Some(Message::Notification(_)))));
+        assert!(matches!(results[1], Ok(None)));
+    }
+
+    #[test]
+    fn test_invalid_content_length() {
+        let input = format!("Content-Length: abc\r\n\r\n{}", frame(INITIALIZED));
+        let results = read_all(&input, DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(results[0], Err(FramingError::MalformedHeader(_))));
+        // 随后的合法消息仍然可以读取
+        assert!(matches!(results[1], Ok(Some(Message::Notification(_)))));
+    }
+
+    #[test]
+    fn test_conflicting_content_length() {
+        let input = "Content-Length: 10\r\nContent-Length: 20\r\n\r\n";
+        let results = read_all(input, DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(results[0], Err(FramingError::MalformedHeader(_))));
+    }
+
+    #[test]
+    fn test_missing_content_length() {
+        let input = format!("Content-Type: text/plain\r\n\r\n{}", frame(INITIALIZED));
+        let results = read_all(&input, DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(
+            results[0],
+            Err(FramingError::MissingContentLength)
+        ));
+        assert!(matches!(results[1], Ok(Some(Message::Notification(_)))));
+    }
+
+    #[test]
+    fn test_oversized_payload_is_discarded() {
+        let large = format!(
+            r#"{{"jsonrpc":"2.0","method":"initialized","params":{{"pad":"{}"}}}}"#,
+            "x".repeat(256)
+        );
+        let input = format!("{}{}", frame(&large), frame(INITIALIZED));
+        let results = read_all(&input, 128);
+
+        assert!(matches!(
+            results[0],
+            Err(FramingError::PayloadTooLarge { max: 128, .. })
+        ));
+        assert!(matches!(results[1], Ok(Some(Message::Notification(_)))));
+    }
+
+    #[test]
+    fn test_invalid_json_payload() {
+        let input = format!("{}{}", frame("{not json"), frame(INITIALIZED));
+        let results = read_all(&input, DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(results[0], Err(FramingError::InvalidPayload(_))));
+        assert!(results[0].as_ref().unwrap_err().is_recoverable());
+        assert!(matches!(results[1], Ok(Some(Message::Notification(_)))));
+    }
+
+    #[test]
+    fn test_truncated_body() {
+        let input = "Content-Length: 100\r\n\r\n{\"jsonrpc\"";
+        let results = read_all(input, DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(results[0], Err(FramingError::UnexpectedEof)));
+        assert!(!results[0].as_ref().unwrap_err().is_recoverable());
+    }
+
+    #[test]
+    fn test_truncated_headers() {
+        let results = read_all("Content-Length: 10\r\n", DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(results[0], Err(FramingError::UnexpectedEof)));
+    }
+
+    #[test]
+    fn test_oversized_header_line() {
+        let input = format!(
+            "X-Garbage: {}\r\n{}",
+            "a".repeat(MAX_HEADER_LINE_LENGTH * 2),
+            frame(INITIALIZED)
+        );
+        let results = read_all(&input, DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(results[0], Ok(Some(Message::Notification(_)))));
+    }
+
+    #[test]
+    fn test_resync_after_garbage_glued_to_header() {
+        let input = format!("garbage bytes{}", frame(INITIALIZED));
+        let results = read_all(&input, DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(matches!(results[0], Ok(Some(Message::Notification(_)))));
+    }
+
+    #[test]
+    fn test_partial_reads() {
+        let input = format!("{}{}", frame(INITIALIZED), frame(INITIALIZED));
+        let mut reader = BufReader::with_capacity(
+            4,
+            TrickleReader {
+                data: input.into_bytes(),
+                position: 0,
+                chunk: 3,
+            },
+        );
+
+        for _ in 0..2 {
+            let message = read_message(&mut reader, DEFAULT_MAX_MESSAGE_SIZE).unwrap();
+            assert!(matches!(message, Some(Message::Notification(_))));
+        }
+        assert!(read_message(&mut reader, DEFAULT_MAX_MESSAGE_SIZE)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_write_error_response() {
+        let mut output = Vec::new();
+        write_error_response(&mut output, PARSE_ERROR_CODE, "bad").unwrap();
+
+        let mut reader = Cursor::new(output);
+        let body = read_frame(&mut reader, DEFAULT_MAX_MESSAGE_SIZE)
+            .unwrap()
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(value["id"].is_null());
+        assert_eq!(value["error"]["code"], PARSE_ERROR_CODE);
+    }
+
+    #[test]
+    fn test_connection_survives_misbehaving_client() {
+        let input = format!(
+            "{}{}{}",
+            frame("{broken"),
+            "Content-Length: nope\r\n\r\n",
+            frame(INITIALIZED)
+        );
+        let output = SharedBuffer::default();
+        let (connection, io_threads) = spawn(Cursor::new(input.into_bytes()), output.clone(), 1024);
+
+        let message = connection
+            .receiver
+            .recv_timeout(Duration::from_secs(5))
+            .unwrap();
+        assert!(matches!(message, Message::Notification(n) if n.method == "initialized"));
+
+        drop(connection);
+        io_threads.join().unwrap();
+
+        // 无效 JSON 触发 ParseError 响应
+        let written = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        assert!(written.contains("-32700"));
+        assert!(written.contains("\"id\":null"));
+    }
+}
@@ -2,19 +2,25 @@
 //!
 //! 扫描项目中的所有组件定义：
 //! - 带有 #[derive(Service)] 的结构体
-//! - 带有 #[component] 的函数
+//! - 带有 #[component] 的函数（提供函数，返回类型即提供的组件类型，`Result<T>` 提供 `T`）
+//!
+//! 在注入的类型（`#[inject(component)]` 字段的类型、`Component<T>` / `LazyComponent<T>` 参数中的 `T`）
+//! 上跳转到定义时，没有同名 Service 结构体的组件跳转到提供它的 `#[component]` 函数。
 
 use crate::analysis::rust::macro_analyzer::{MacroAnalyzer, SpringMacro};
+use crate::analysis::rust::module_path::qualified_name;
+use crate::core::document::{PositionMapper, SourceReader};
 use crate::protocol::types::{LocationResponse, PositionResponse, RangeResponse};
-use lsp_types::Url;
+use lsp_types::{Position, Url};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 /// 组件扫描器
 pub struct ComponentScanner {
     macro_analyzer: MacroAnalyzer,
+    sources: SourceReader,
 }
 
 impl ComponentScanner {
@@ -22,9 +28,17 @@ impl ComponentScanner {
     pub fn new() -> Self {
         Self {
             macro_analyzer: MacroAnalyzer::new(),
+            sources: SourceReader::default(),
         }
     }
 
+    /// 使用指定的方式读取源文件（语言服务器中优先读取编辑器中打开的文档）
+    pub fn with_sources(mut self, sources: SourceReader) -> Self {
+        self.macro_analyzer = MacroAnalyzer::new().with_encoding(sources.encoding());
+        self.sources = sources;
+        self
+    }
+
     /// 扫描项目中的所有组件
     ///
     /// # Arguments
@@ -38,204 +52,191 @@ impl ComponentScanner {
         &self,
         project_path: &Path,
     ) -> Result<Vec<ComponentInfoResponse>, ScanError> {
-        tracing::info!("Starting component scan in: {:?}", project_path);
-
-        let mut components = Vec::new();
+        let components: Vec<_> = self.components(project_path).collect();
+        tracing::info!("Total components found: {}", components.len());
+        Ok(components)
+    }
 
-        // 检查是否直接是一个 spring-rs 项目（有 src 目录）
+    /// 逐个文件产出项目中的组件
+    ///
+    /// 项目目录有 `src` 目录时扫描这个项目，否则视为 workspace 根目录，扫描其中所有 spring-rs 项目。
+    /// 组件在遍历文件的过程中产出，调用方不必等整个项目扫描完成（如分块发送部分结果）
+    pub fn components<'a>(
+        &'a self,
+        project_path: &Path,
+    ) -> impl Iterator<Item = ComponentInfoResponse> + 'a {
+        tracing::info!("Starting component scan in: {:?}", project_path);
         let src_path = project_path.join("src");
-
-        if src_path.exists() && src_path.is_dir() {
-            // 直接扫描这个项目
+        let src_dirs = if src_path.is_dir() {
             tracing::info!("Found src directory, scanning single project");
-            components.extend(self.scan_single_project(project_path)?);
+            vec![src_path]
         } else {
             // 可能是 workspace 根目录，递归查找所有 spring-rs 项目
             tracing::info!("No src directory found, searching for spring-rs projects in workspace");
-            components.extend(self.scan_workspace(project_path)?);
-        }
+            self.workspace_src_dirs(project_path)
+        };
+        src_dirs
+            .into_iter()
+            .flat_map(move |src_dir| self.components_in(&src_dir))
+    }
 
-        tracing::info!("Total components found: {}", components.len());
-        Ok(components)
+    /// 扫描目录下所有 Rust 文件中的组件
+    ///
+    /// 用于 `src` 目录和构建脚本生成的代码目录
+    pub fn scan_dir(&self, dir: &Path) -> Vec<ComponentInfoResponse> {
+        self.components_in(dir).collect()
     }
 
-    /// 扫描单个项目
-    fn scan_single_project(
-        &self,
-        project_path: &Path,
-    ) -> Result<Vec<ComponentInfoResponse>, ScanError> {
-        let mut components = Vec::new();
+    /// 逐个文件产出目录下所有 Rust 文件中的组件
+    fn components_in<'a>(&'a self, dir: &Path) -> impl Iterator<Item = ComponentInfoResponse> + 'a {
+        WalkDir::new(dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+            .flat_map(move |entry| self.scan_file(entry.path()))
+    }
 
-        // 查找 src 目录
-        let src_path = project_path.join("src");
-        tracing::info!("Looking for src directory: {:?}", src_path);
+    /// 扫描单个 Rust 文件中的组件，无法读取或解析时返回空列表
+    fn scan_file(&self, file_path: &Path) -> Vec<ComponentInfoResponse> {
+        tracing::info!("Scanning file: {:?}", file_path);
 
-        if !src_path.exists() {
-            tracing::error!("src directory not found at: {:?}", src_path);
-            return Err(ScanError::InvalidProject(
-                "src directory not found".to_string(),
-            ));
-        }
+        // 读取文件内容
+        let content = match self.sources.read(file_path) {
+            Ok(content) => {
+                tracing::info!("Successfully read file, size: {} bytes", content.len());
+                content
+            }
+            Err(e) => {
+                tracing::warn!("Failed to read file {:?}: {}", file_path, e);
+                return Vec::new();
+            }
+        };
 
-        tracing::info!("Found src directory, starting file scan...");
-        let mut file_count = 0;
-        let mut parsed_count = 0;
-        let mut macro_count = 0;
+        // 解析文件
+        let file_url = match Url::from_file_path(file_path) {
+            Ok(url) => {
+                tracing::info!("Converted to URL: {}", url);
+                url
+            }
+            Err(_) => {
+                tracing::warn!("Failed to convert path to URL: {:?}", file_path);
+                return Vec::new();
+            }
+        };
 
-        // 遍历所有 Rust 文件
-        for entry in WalkDir::new(&src_path)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().map_or(false, |ext| ext == "rs"))
-        {
-            file_count += 1;
-            let file_path = entry.path();
-            tracing::info!("Scanning file {}: {:?}", file_count, file_path);
-
-            // 读取文件内容
-            let content = match fs::read_to_string(file_path) {
-                Ok(content) => {
-                    tracing::info!("Successfully read file, size: {} bytes", content.len());
-                    content
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to read file {:?}: {}", file_path, e);
-                    continue;
-                }
-            };
+        let rust_doc = match self.macro_analyzer.parse(file_url.clone(), content) {
+            Ok(doc) => {
+                tracing::info!("Successfully parsed file");
+                doc
+            }
+            Err(e) => {
+                tracing::warn!("Failed to parse file {:?}: {}", file_path, e);
+                return Vec::new();
+            }
+        };
 
-            // 解析文件
-            let file_url = match Url::from_file_path(file_path) {
-                Ok(url) => {
-                    tracing::info!("Converted to URL: {}", url);
-                    url
-                }
-                Err(_) => {
-                    tracing::warn!("Failed to convert path to URL: {:?}", file_path);
-                    continue;
-                }
-            };
+        // 提取宏信息
+        let rust_doc = match self.macro_analyzer.extract_macros(rust_doc) {
+            Ok(doc) => {
+                tracing::info!("Extracted {} macros from file", doc.macros.len());
+                doc
+            }
+            Err(e) => {
+                tracing::warn!("Failed to extract macros from {:?}: {}", file_path, e);
+                return Vec::new();
+            }
+        };
 
-            let rust_doc = match self.macro_analyzer.parse(file_url.clone(), content) {
-                Ok(doc) => {
-                    parsed_count += 1;
-                    tracing::info!("Successfully parsed file");
-                    doc
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to parse file {:?}: {}", file_path, e);
-                    continue;
-                }
-            };
-
-            // 提取宏信息
-            let rust_doc = match self.macro_analyzer.extract_macros(rust_doc) {
-                Ok(doc) => {
-                    tracing::info!("Extracted {} macros from file", doc.macros.len());
-                    macro_count += doc.macros.len();
-                    doc
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to extract macros from {:?}: {}", file_path, e);
-                    continue;
-                }
-            };
-
-            // 提取组件信息
-            for spring_macro in &rust_doc.macros {
-                match spring_macro {
-                    // 处理 #[derive(Service)] 宏
-                    SpringMacro::DeriveService(service_macro) => {
-                        tracing::info!(
-                            "Found Service component: {} in {:?}",
-                            service_macro.struct_name,
-                            file_path
-                        );
-
-                        components.push(ComponentInfoResponse {
-                            name: service_macro.struct_name.clone(),
-                            type_name: service_macro.struct_name.clone(),
-                            scope: ComponentScope::Singleton, // spring-rs 默认是单例
-                            source: ComponentSource::Service,
-                            dependencies: service_macro
-                                .fields
-                                .iter()
-                                .filter_map(|field| {
-                                    // 只包含带有 inject 标注的字段
-                                    field.inject.as_ref().map(|_| field.type_name.clone())
-                                })
-                                .collect(),
-                            location: LocationResponse {
-                                uri: file_url.to_string(),
-                                range: RangeResponse {
-                                    start: PositionResponse {
-                                        line: service_macro.range.start.line,
-                                        character: service_macro.range.start.character,
-                                    },
-                                    end: PositionResponse {
-                                        line: service_macro.range.end.line,
-                                        character: service_macro.range.end.character,
-                                    },
+        // 提取组件信息
+        let mut components = Vec::new();
+        for spring_macro in &rust_doc.macros {
+            match spring_macro {
+                // 处理 #[derive(Service)] 宏
+                SpringMacro::DeriveService(service_macro) => {
+                    tracing::info!(
+                        "Found Service component: {} in {:?}",
+                        service_macro.struct_name,
+                        file_path
+                    );
+
+                    components.push(ComponentInfoResponse {
+                        name: service_macro.struct_name.clone(),
+                        qualified_name: qualified_name(file_path, &service_macro.struct_name),
+                        type_name: service_macro.struct_name.clone(),
+                        scope: ComponentScope::Singleton, // spring-rs 默认是单例
+                        source: ComponentSource::Service,
+                        dependencies: service_macro
+                            .fields
+                            .iter()
+                            .filter_map(|field| {
+                                // 只包含带有 inject 标注的字段
+                                field.inject.as_ref().map(|_| field.type_name.clone())
+                            })
+                            .collect(),
+                        deprecated: service_macro.deprecated,
+                        cfg: service_macro.cfg.clone(),
+                        location: LocationResponse {
+                            uri: file_url.to_string(),
+                            range: RangeResponse {
+                                start: PositionResponse {
+                                    line: service_macro.range.start.line,
+                                    character: service_macro.range.start.character,
+                                },
+                                end: PositionResponse {
+                                    line: service_macro.range.end.line,
+                                    character: service_macro.range.end.character,
                                 },
                             },
-                        });
-                    }
-                    // 处理 #[component] 宏
-                    SpringMacro::Component(component_macro) => {
-                        tracing::info!(
-                            "Found Component function: {} -> {} in {:?}",
-                            component_macro.function_name,
-                            component_macro.component_type,
-                            file_path
-                        );
-
-                        components.push(ComponentInfoResponse {
-                            name: component_macro.component_type.clone(),
-                            type_name: component_macro.component_type.clone(),
-                            scope: ComponentScope::Singleton, // spring-rs 默认是单例
-                            source: ComponentSource::Component,
-                            dependencies: component_macro
-                                .dependencies
-                                .iter()
-                                .map(|dep| dep.type_name.clone())
-                                .collect(),
-                            location: LocationResponse {
-                                uri: file_url.to_string(),
-                                range: RangeResponse {
-                                    start: PositionResponse {
-                                        line: component_macro.range.start.line,
-                                        character: component_macro.range.start.character,
-                                    },
-                                    end: PositionResponse {
-                                        line: component_macro.range.end.line,
-                                        character: component_macro.range.end.character,
-                                    },
+                        },
+                    });
+                }
+                // 处理 #[component] 宏
+                SpringMacro::Component(component_macro) => {
+                    tracing::info!(
+                        "Found Component function: {} -> {} in {:?}",
+                        component_macro.function_name,
+                        component_macro.component_type,
+                        file_path
+                    );
+
+                    components.push(ComponentInfoResponse {
+                        name: component_macro.component_type.clone(),
+                        qualified_name: qualified_name(file_path, &component_macro.function_name),
+                        type_name: component_macro.component_type.clone(),
+                        scope: ComponentScope::Singleton, // spring-rs 默认是单例
+                        source: ComponentSource::Component,
+                        dependencies: component_macro
+                            .dependencies
+                            .iter()
+                            .map(|dep| dep.type_name.clone())
+                            .collect(),
+                        deprecated: false,
+                        cfg: component_macro.cfg.clone(),
+                        location: LocationResponse {
+                            uri: file_url.to_string(),
+                            range: RangeResponse {
+                                start: PositionResponse {
+                                    line: component_macro.range.start.line,
+                                    character: component_macro.range.start.character,
+                                },
+                                end: PositionResponse {
+                                    line: component_macro.range.end.line,
+                                    character: component_macro.range.end.character,
                                 },
                             },
-                        });
-                    }
-                    _ => {}
+                        },
+                    });
                 }
+                _ => {}
             }
         }
 
-        tracing::info!(
-            "Scanned {} Rust files, parsed {} files, found {} macros, extracted {} components",
-            file_count,
-            parsed_count,
-            macro_count,
-            components.len()
-        );
-        Ok(components)
+        components
     }
 
-    /// 扫描 workspace 中的所有 spring-rs 项目
-    fn scan_workspace(
-        &self,
-        workspace_path: &Path,
-    ) -> Result<Vec<ComponentInfoResponse>, ScanError> {
-        let mut all_components = Vec::new();
-        let mut project_count = 0;
+    /// workspace 中所有 spring-rs 项目的 `src` 目录
+    fn workspace_src_dirs(&self, workspace_path: &Path) -> Vec<PathBuf> {
+        let mut src_dirs = Vec::new();
 
         // 递归查找所有包含 Cargo.toml 的目录
         for entry in WalkDir::new(workspace_path)
@@ -259,22 +260,11 @@ impl ComponentScanner {
             }
 
             tracing::info!("Found spring-rs project: {:?}", project_dir);
-            project_count += 1;
-
-            // 扫描这个项目
-            match self.scan_single_project(project_dir) {
-                Ok(components) => {
-                    tracing::info!("Found {} components in {:?}", components.len(), project_dir);
-                    all_components.extend(components);
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to scan project {:?}: {}", project_dir, e);
-                }
-            }
+            src_dirs.push(src_dir);
         }
 
-        tracing::info!("Scanned {} spring-rs projects in workspace", project_count);
-        Ok(all_components)
+        tracing::info!("Found {} spring-rs projects in workspace", src_dirs.len());
+        src_dirs
     }
 
     /// 检查是否是 spring-rs 项目
@@ -325,6 +315,14 @@ pub enum ComponentSource {
 pub struct ComponentInfoResponse {
     /// 组件名称
     pub name: String,
+    /// 定义组件的条目（Service 结构体或 `#[component]` 函数）的完整路径，
+    /// 如 `crate::services::UserService`，文件不属于 crate 的模块树时为 `None`
+    #[serde(
+        rename = "qualifiedName",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub qualified_name: Option<String>,
     /// 组件类型名
     #[serde(rename = "typeName")]
     pub type_name: String,
@@ -334,6 +332,12 @@ pub struct ComponentInfoResponse {
     pub source: ComponentSource,
     /// 依赖列表
     pub dependencies: Vec<String>,
+    /// 是否标注了 `#[deprecated]`
+    #[serde(default)]
+    pub deprecated: bool,
+    /// 注册组件的条件（`#[cfg(...)]`，如 `feature = "redis"`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg: Option<String>,
     /// 源代码位置
     pub location: LocationResponse,
 }
@@ -366,6 +370,126 @@ pub enum ScanError {
     NoComponents,
 }
 
+/// 注入类型对应的 `#[component]` 提供函数
+///
+/// 按类型名的最后一段比较（`redis::Client` 与 `Client` 相同）。
+/// 存在同名的 `#[derive(Service)]` 结构体时返回空列表，由 rust-analyzer 跳转到结构体定义
+pub fn provider_functions<'a>(
+    components: &'a [ComponentInfoResponse],
+    type_name: &str,
+) -> Vec<&'a ComponentInfoResponse> {
+    let type_name = base_type_name(type_name);
+    let providers = components
+        .iter()
+        .filter(|component| base_type_name(&component.type_name) == type_name);
+    if providers
+        .clone()
+        .any(|component| matches!(component.source, ComponentSource::Service))
+    {
+        return Vec::new();
+    }
+    providers
+        .filter(|component| matches!(component.source, ComponentSource::Component))
+        .collect()
+}
+
+/// 类型名的最后一段，去掉泛型参数（`crate::db::Pool<Postgres>` -> `Pool`）
+fn base_type_name(type_name: &str) -> &str {
+    let name = type_name.split('<').next().unwrap_or_default();
+    name.rsplit("::").next().unwrap_or_default().trim()
+}
+
+/// 光标处的注入类型
+///
+/// 光标位于 `#[inject(component)]` 字段的类型上，或 `Component<T>` / `LazyComponent<T>` 参数的 `T` 上时
+/// 返回类型名，无法解析时返回 `None`
+pub fn injected_type_at(lines: &PositionMapper, position: Position) -> Option<String> {
+    let file = syn::parse_file(lines.content()).ok()?;
+    let mut types = Vec::new();
+    collect_injected_types(&file.items, &mut types);
+    types.into_iter().find_map(|ty| {
+        let range = lines.span_range(syn::spanned::Spanned::span(ty));
+        (position >= range.start && position <= range.end).then(|| type_path_name(ty))?
+    })
+}
+
+/// 收集文件中所有注入的类型（包括行内模块和 impl 块中的函数）
+fn collect_injected_types<'a>(items: &'a [syn::Item], types: &mut Vec<&'a syn::Type>) {
+    let collect_inputs = |sig: &'a syn::Signature, types: &mut Vec<&'a syn::Type>| {
+        for input in &sig.inputs {
+            if let syn::FnArg::Typed(pat_type) = input {
+                types.extend(component_argument(&pat_type.ty));
+            }
+        }
+    };
+
+    for item in items {
+        match item {
+            syn::Item::Struct(item_struct) => types.extend(
+                item_struct
+                    .fields
+                    .iter()
+                    .filter(|field| field.attrs.iter().any(is_component_inject))
+                    .map(|field| &field.ty),
+            ),
+            syn::Item::Fn(item_fn) => collect_inputs(&item_fn.sig, types),
+            syn::Item::Impl(item_impl) => {
+                for impl_item in &item_impl.items {
+                    if let syn::ImplItem::Fn(impl_fn) = impl_item {
+                        collect_inputs(&impl_fn.sig, types);
+                    }
+                }
+            }
+            syn::Item::Mod(item_mod) => {
+                if let Some((_, items)) = &item_mod.content {
+                    collect_injected_types(items, types);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `#[inject(component)]` 或 `#[inject(component = "name")]`
+fn is_component_inject(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("inject")
+        && attr
+            .meta
+            .require_list()
+            .is_ok_and(|list| list.tokens.to_string().contains("component"))
+}
+
+/// `Component<T>` / `LazyComponent<T>` 中的 `T`
+fn component_argument(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Component" && segment.ident != "LazyComponent" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// 类型路径的最后一段（`Arc<Db>` 这类包装类型返回 `Arc`）
+fn type_path_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        syn::Type::Reference(reference) => type_path_name(&reference.elem),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -383,4 +507,81 @@ mod tests {
         // 验证默认扫描器创建成功
         assert!(true);
     }
+
+    fn component(type_name: &str, source: ComponentSource) -> ComponentInfoResponse {
+        let position = PositionResponse {
+            line: 0,
+            character: 0,
+        };
+        ComponentInfoResponse {
+            name: type_name.to_string(),
+            qualified_name: None,
+            type_name: type_name.to_string(),
+            scope: ComponentScope::Singleton,
+            source,
+            dependencies: Vec::new(),
+            deprecated: false,
+            cfg: None,
+            location: LocationResponse {
+                uri: "file:///app/src/lib.rs".to_string(),
+                range: RangeResponse {
+                    start: position.clone(),
+                    end: position,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_injected_type_at() {
+        let content = r#"#[derive(Clone, Service)]
+struct UserService {
+    #[inject(component)]
+    db: ConnectPool,
+    #[inject(config)]
+    config: UserConfig,
+}
+
+#[get("/users")]
+async fn list_users(Component(client): Component<redis::Client>) {}
+
+mod jobs {
+    #[cron("0 0 * * * *")]
+    async fn cleanup(Component(db): LazyComponent<ConnectPool>) {}
+}
+"#;
+        let lines = PositionMapper::new(content);
+        assert_eq!(
+            injected_type_at(&lines, Position::new(3, 10)).as_deref(),
+            Some("ConnectPool")
+        );
+        // 配置注入和字段名不是注入的组件类型
+        assert_eq!(injected_type_at(&lines, Position::new(5, 14)), None);
+        assert_eq!(injected_type_at(&lines, Position::new(3, 5)), None);
+        assert_eq!(
+            injected_type_at(&lines, Position::new(9, 60)).as_deref(),
+            Some("Client")
+        );
+        assert_eq!(injected_type_at(&lines, Position::new(9, 45)), None);
+        assert_eq!(
+            injected_type_at(&lines, Position::new(13, 55)).as_deref(),
+            Some("ConnectPool")
+        );
+    }
+
+    #[test]
+    fn test_provider_functions() {
+        let components = vec![
+            component("redis::Client", ComponentSource::Component),
+            component("UserService", ComponentSource::Service),
+            component("UserService", ComponentSource::Component),
+        ];
+
+        let providers = provider_functions(&components, "Client");
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].type_name, "redis::Client");
+        // 存在 Service 结构体时由 rust-analyzer 跳转
+        assert!(provider_functions(&components, "UserService").is_empty());
+        assert!(provider_functions(&components, "ConnectPool").is_empty());
+    }
 }
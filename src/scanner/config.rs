@@ -1,10 +1,16 @@
 //! 配置扫描器模块
 //!
-//! 扫描项目中所有带有 `#[derive(Configurable)]` 的配置结构体
+//! 扫描项目中所有带有 `#[derive(Configurable)]` 的配置结构体，
+//! 并构建配置前缀到配置结构体的反向索引（配置文件修改时据此找到受影响的 Rust 文件）。
+//! 反过来，配置结构体的字段也可以在 `config/app.toml` 及 profile 配置文件（如 `app-prod.toml`）中
+//! 找到当前的配置值（见 [`config_value_sources`]）
 
+use crate::analysis::toml::toml_analyzer::{EnvVarReference, TomlDocument};
+use crate::core::document::{PositionMapper, SourceReader};
 use crate::Result;
-use lsp_types::{Location, Position, Range, Url};
+use lsp_types::{Location, Range, Url};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 use syn::spanned::Spanned;
 use walkdir::WalkDir;
@@ -51,15 +57,172 @@ pub struct ConfigField {
     /// 描述（从文档注释提取）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// 字段名在源文件中的位置范围
+    #[serde(skip)]
+    pub range: Range,
+    /// 配置文件中缺少该字段时是否使用默认值（字段或结构体上有 `#[serde(default)]`，或字段被跳过）
+    #[serde(skip)]
+    pub has_default: bool,
+}
+
+impl ConfigField {
+    /// 配置文件中必须提供的字段（既不是 `Option<T>`，也没有默认值），缺少时应用启动时反序列化失败
+    pub fn is_required(&self) -> bool {
+        !self.optional && !self.has_default
+    }
+}
+
+/// 反向索引中的配置结构体
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigStructRef {
+    /// 结构体名称
+    pub name: String,
+    /// 定义结构体的文件
+    pub uri: Url,
+}
+
+/// 配置前缀到配置结构体的反向索引
+#[derive(Debug, Clone, Default)]
+pub struct ConfigPrefixIndex {
+    structs: HashMap<String, Vec<ConfigStructRef>>,
+}
+
+impl ConfigPrefixIndex {
+    /// 映射到配置前缀（即 `app.toml` 中的顶层表）的配置结构体
+    pub fn structs(&self, prefix: &str) -> &[ConfigStructRef] {
+        self.structs.get(prefix).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// 配置项在配置文件中的值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigValueSource {
+    /// 配置文件
+    pub uri: Url,
+    /// 配置文件对应的 profile（`app-prod.toml` 为 `prod`，`app.toml` 为 `None`）
+    pub profile: Option<String>,
+    /// 值的原文（多行的值合并为一行）
+    pub value: String,
+    /// 值在配置文件中的位置范围
+    pub range: Range,
+    /// 值中引用的环境变量（`${VAR}` 或 `${VAR:default}`）
+    pub env_vars: Vec<EnvVarReference>,
+}
+
+impl ConfigValueSource {
+    /// 配置文件名（`app.toml` 或 `app-<profile>.toml`）
+    pub fn file_name(&self) -> String {
+        match &self.profile {
+            Some(profile) => format!("app-{}.toml", profile),
+            None => "app.toml".to_string(),
+        }
+    }
+
+    /// 值所在的行号（从 1 开始）
+    pub fn line(&self) -> u32 {
+        self.range.start.line + 1
+    }
+}
+
+/// 查找配置项 `[prefix] key` 在各配置文件中的值
+///
+/// 只读取 `app.toml` 和 `app-<profile>.toml`，基础配置排在 profile 配置之前，profile 按名称排序
+pub fn config_value_sources(
+    prefix: &str,
+    key: &str,
+    docs: &[(Url, TomlDocument)],
+) -> Vec<ConfigValueSource> {
+    let mut sources: Vec<ConfigValueSource> = docs
+        .iter()
+        .filter_map(|(uri, doc)| {
+            let profile = config_file_profile(uri)?;
+            let property = doc.config_sections.get(prefix)?.properties.get(key)?;
+            let lines = doc.mapper();
+            let start = lines.offset(property.range.start)?;
+            let end = lines.offset(property.range.end)?;
+            let value = doc.content.get(start..end)?;
+            let env_vars = env_var_references(&lines, start, value);
+            Some(ConfigValueSource {
+                uri: uri.clone(),
+                profile,
+                value: value.split_whitespace().collect::<Vec<_>>().join(" "),
+                range: property.range,
+                env_vars,
+            })
+        })
+        .collect();
+    sources.sort_by(|a, b| a.profile.cmp(&b.profile));
+    sources
+}
+
+/// 配置值原文（从 `start` 字节偏移开始）中的环境变量引用
+///
+/// 解析 TOML 时只记录引号外的引用，而 `"${VAR:default}"` 形式的引用位于字符串中，因此直接扫描值的原文
+fn env_var_references(lines: &PositionMapper, start: usize, value: &str) -> Vec<EnvVarReference> {
+    let mut references = Vec::new();
+    let mut rest = value;
+    while let Some(open) = rest.find("${") {
+        let Some(close) = rest[open..].find('}') else {
+            break;
+        };
+        let (name, default) = match rest[open + 2..open + close].split_once(':') {
+            Some((name, default)) => (name, Some(default.to_string())),
+            None => (&rest[open + 2..open + close], None),
+        };
+        let offset = start + (value.len() - rest.len()) + open;
+        references.push(EnvVarReference {
+            name: name.to_string(),
+            default,
+            range: lines.range(offset..offset + close + 1),
+        });
+        rest = &rest[open + close + 1..];
+    }
+    references
+}
+
+/// 配置文件对应的 profile，不是 `app.toml` 或 `app-<profile>.toml` 时返回 `None`
+pub(crate) fn config_file_profile(uri: &Url) -> Option<Option<String>> {
+    let path = uri.to_file_path().ok()?;
+    let stem = path.file_name()?.to_str()?.strip_suffix(".toml")?;
+    if stem == "app" {
+        return Some(None);
+    }
+    let profile = stem.strip_prefix("app-")?;
+    (!profile.is_empty()).then(|| Some(profile.to_string()))
+}
+
+/// `#[serde(...)]` 中是否有给定名称的参数（如 `default` 或 `default = "path"`）
+fn has_serde_flag(attrs: &[syn::Attribute], names: &[&str]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .filter_map(|attr| {
+            attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+            )
+            .ok()
+        })
+        .flatten()
+        .any(|meta| names.iter().any(|name| meta.path().is_ident(name)))
 }
 
 /// 配置扫描器
-pub struct ConfigScanner;
+pub struct ConfigScanner {
+    sources: SourceReader,
+}
 
 impl ConfigScanner {
     /// 创建新的配置扫描器
     pub fn new() -> Self {
-        Self
+        Self {
+            sources: SourceReader::default(),
+        }
+    }
+
+    /// 使用指定的方式读取源文件（语言服务器中优先读取编辑器中打开的文档）
+    pub fn with_sources(mut self, sources: SourceReader) -> Self {
+        self.sources = sources;
+        self
     }
 
     /// 扫描项目中的所有配置结构
@@ -71,61 +234,102 @@ impl ConfigScanner {
     /// # Returns
     ///
     /// 返回找到的所有配置结构列表
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
     pub fn scan_configurations(&self, project_path: &Path) -> Result<Vec<ConfigurationStruct>> {
         tracing::info!("Scanning configurations in: {:?}", project_path);
 
-        let mut configurations = Vec::new();
+        let configurations: Vec<_> = self.configurations(project_path).collect();
 
+        tracing::info!("Found {} configuration structs", configurations.len());
+        Ok(configurations)
+    }
+
+    /// 逐个文件产出项目中的配置结构
+    ///
+    /// 配置结构在遍历文件的过程中产出，调用方不必等整个项目扫描完成（如分块发送部分结果）
+    pub fn configurations<'a>(
+        &'a self,
+        project_path: &Path,
+    ) -> impl Iterator<Item = ConfigurationStruct> + 'a {
         // 遍历项目中的所有 Rust 文件
-        for entry in WalkDir::new(project_path)
+        WalkDir::new(project_path)
             .follow_links(true)
             .into_iter()
             .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
+            // 跳过 target 目录，只处理 .rs 文件
+            .filter(|e| {
+                let path = e.path();
+                !path.components().any(|c| c.as_os_str() == "target")
+                    && path.extension().and_then(|s| s.to_str()) == Some("rs")
+            })
+            .flat_map(move |entry| self.scan_file(entry.path()))
+    }
 
-            // 跳过 target 目录
-            if path.components().any(|c| c.as_os_str() == "target") {
-                continue;
+    /// 提取单个文件中的配置结构，无法读取或解析时返回空列表
+    fn scan_file(&self, path: &Path) -> Vec<ConfigurationStruct> {
+        // 读取文件内容
+        let content = match self.sources.read(path) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("Failed to read file {:?}: {}", path, e);
+                return Vec::new();
             }
+        };
 
-            // 只处理 .rs 文件
-            if path.extension().and_then(|s| s.to_str()) != Some("rs") {
-                continue;
+        // 解析文件
+        let syntax_tree = match syn::parse_file(&content) {
+            Ok(tree) => tree,
+            Err(e) => {
+                tracing::debug!("Failed to parse file {:?}: {}", path, e);
+                return Vec::new();
             }
+        };
 
-            // 读取文件内容
-            let content = match std::fs::read_to_string(path) {
-                Ok(content) => content,
-                Err(e) => {
-                    tracing::warn!("Failed to read file {:?}: {}", path, e);
-                    continue;
-                }
-            };
-
-            // 解析文件
-            let syntax_tree = match syn::parse_file(&content) {
-                Ok(tree) => tree,
-                Err(e) => {
-                    tracing::debug!("Failed to parse file {:?}: {}", path, e);
-                    continue;
-                }
-            };
+        // 提取配置结构
+        let lines = self.sources.mapper(&content);
+        self.extract_configurations_from_file(&syntax_tree, &lines, path)
+            .unwrap_or_default()
+    }
 
-            // 提取配置结构
-            if let Ok(file_configs) = self.extract_configurations_from_file(&syntax_tree, path) {
-                configurations.extend(file_configs);
+    /// 提取单个文件中的配置结构（文件内容可以是编辑器中尚未保存的内容）
+    ///
+    /// 无法解析的文件返回空列表
+    pub fn scan_content(&self, uri: &Url, content: &str) -> Vec<ConfigurationStruct> {
+        let (Ok(syntax_tree), Ok(path)) = (syn::parse_file(content), uri.to_file_path()) else {
+            return Vec::new();
+        };
+        let lines = self.sources.mapper(content);
+        self.extract_configurations_from_file(&syntax_tree, &lines, &path)
+            .unwrap_or_default()
+    }
+
+    /// 从给定的 Rust 文件构建配置前缀的反向索引
+    ///
+    /// 文件内容由调用方提供（可以是编辑器中尚未保存的内容），无法解析的文件直接跳过
+    pub fn build_prefix_index(&self, files: &[(Url, String)]) -> ConfigPrefixIndex {
+        let mut index = ConfigPrefixIndex::default();
+
+        for (uri, content) in files {
+            for config in self.scan_content(uri, content) {
+                index
+                    .structs
+                    .entry(config.prefix)
+                    .or_default()
+                    .push(ConfigStructRef {
+                        name: config.name,
+                        uri: uri.clone(),
+                    });
             }
         }
 
-        tracing::info!("Found {} configuration structs", configurations.len());
-        Ok(configurations)
+        index
     }
 
     /// 从单个文件中提取配置结构
     fn extract_configurations_from_file(
         &self,
         syntax_tree: &syn::File,
+        lines: &PositionMapper,
         file_path: &Path,
     ) -> Result<Vec<ConfigurationStruct>> {
         let mut configurations = Vec::new();
@@ -135,7 +339,8 @@ impl ConfigScanner {
             if let syn::Item::Struct(item_struct) = item {
                 // 检查是否有 Configurable derive
                 if self.has_configurable_derive(item_struct) {
-                    if let Some(config) = self.extract_configuration_struct(item_struct, file_path)
+                    if let Some(config) =
+                        self.extract_configuration_struct(item_struct, lines, file_path)
                     {
                         configurations.push(config);
                     }
@@ -165,19 +370,25 @@ impl ConfigScanner {
     fn extract_configuration_struct(
         &self,
         item_struct: &syn::ItemStruct,
+        lines: &PositionMapper,
         file_path: &Path,
     ) -> Option<ConfigurationStruct> {
         // 提取配置前缀
         let prefix = self.extract_config_prefix(item_struct)?;
 
-        // 提取字段
-        let fields = self.extract_fields(&item_struct.fields);
+        // 提取字段（结构体上的 `#[serde(default)]` 对所有字段生效）
+        let mut fields = self.extract_fields(&item_struct.fields, lines);
+        if has_serde_flag(&item_struct.attrs, &["default"]) {
+            for field in &mut fields {
+                field.has_default = true;
+            }
+        }
 
         // 提取文档注释
         let _doc_comment = self.extract_doc_comment(&item_struct.attrs);
 
         // 构建位置信息
-        let location = self.build_location(item_struct, file_path);
+        let location = self.build_location(item_struct, lines, file_path);
 
         Some(ConfigurationStruct {
             name: item_struct.ident.to_string(),
@@ -205,7 +416,7 @@ impl ConfigScanner {
     }
 
     /// 提取结构体字段
-    fn extract_fields(&self, fields: &syn::Fields) -> Vec<ConfigField> {
+    fn extract_fields(&self, fields: &syn::Fields, lines: &PositionMapper) -> Vec<ConfigField> {
         let mut result = Vec::new();
 
         if let syn::Fields::Named(fields_named) = fields {
@@ -225,6 +436,11 @@ impl ConfigScanner {
                         type_name,
                         optional,
                         description,
+                        range: lines.span_range(ident.span()),
+                        has_default: has_serde_flag(
+                            &field.attrs,
+                            &["default", "skip", "skip_deserializing"],
+                        ),
                     });
                 }
             }
@@ -317,7 +533,12 @@ impl ConfigScanner {
     }
 
     /// 构建位置信息
-    fn build_location(&self, item_struct: &syn::ItemStruct, file_path: &Path) -> Option<Location> {
+    fn build_location(
+        &self,
+        item_struct: &syn::ItemStruct,
+        lines: &PositionMapper,
+        file_path: &Path,
+    ) -> Option<Location> {
         // 将文件路径转换为 URI
         let uri = match Url::from_file_path(file_path) {
             Ok(uri) => uri,
@@ -327,22 +548,8 @@ impl ConfigScanner {
             }
         };
 
-        // 获取结构体的 span
-        let span = item_struct.span();
-        let start = span.start();
-        let end = span.end();
-
-        // 构建 Range
-        let range = Range {
-            start: Position {
-                line: start.line.saturating_sub(1) as u32, // syn 的行号从 1 开始
-                character: start.column as u32,
-            },
-            end: Position {
-                line: end.line.saturating_sub(1) as u32,
-                character: end.column as u32,
-            },
-        };
+        // 将结构体的 span 转换为 Range
+        let range = lines.span_range(item_struct.span());
 
         Some(Location { uri, range })
     }
@@ -380,6 +587,72 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_build_prefix_index() {
+        let uri = Url::parse("file:///app/src/config.rs").unwrap();
+        let files = vec![
+            (
+                uri.clone(),
+                "#[derive(Configurable, Deserialize)]\n#[config_prefix = \"rate-limit\"]\nstruct RateLimitConfig {\n    per_second: u32,\n}\n\nstruct Plain;\n".to_string(),
+            ),
+            (
+                Url::parse("file:///app/src/broken.rs").unwrap(),
+                "struct {".to_string(),
+            ),
+        ];
+
+        let index = ConfigScanner::new().build_prefix_index(&files);
+        assert_eq!(
+            index.structs("rate-limit"),
+            &[ConfigStructRef {
+                name: "RateLimitConfig".to_string(),
+                uri,
+            }]
+        );
+        assert!(index.structs("web").is_empty());
+    }
+
+    #[test]
+    fn test_config_value_sources() {
+        use crate::analysis::toml::toml_analyzer::TomlAnalyzer;
+        use crate::schema::SchemaProvider;
+
+        let analyzer = TomlAnalyzer::new(SchemaProvider::default());
+        let doc =
+            |uri: &str, content: &str| (Url::parse(uri).unwrap(), analyzer.parse(content).unwrap());
+        let docs = vec![
+            doc(
+                "file:///app/config/app-prod.toml",
+                "[web]\nport = 80\nhost = \"${HOST:0.0.0.0}\"\n",
+            ),
+            doc(
+                "file:///app/config/app.toml",
+                "[web]\nport = 8080\nhosts = [\n  \"a\",\n  \"b\",\n]\n",
+            ),
+            doc("file:///app/config/other.toml", "[web]\nport = 1\n"),
+        ];
+
+        let sources = config_value_sources("web", "port", &docs);
+        let values: Vec<_> = sources
+            .iter()
+            .map(|source| (source.profile.as_deref(), source.value.as_str()))
+            .collect();
+        assert_eq!(values, vec![(None, "8080"), (Some("prod"), "80")]);
+        assert_eq!(sources[0].range.start, lsp_types::Position::new(1, 7));
+        assert_eq!(sources[0].line(), 2);
+        assert_eq!(sources[1].file_name(), "app-prod.toml");
+        assert!(sources[1].env_vars.is_empty());
+
+        let host = config_value_sources("web", "host", &docs);
+        assert_eq!(host[0].env_vars.len(), 1);
+        assert_eq!(host[0].env_vars[0].name, "HOST");
+        assert_eq!(host[0].env_vars[0].default.as_deref(), Some("0.0.0.0"));
+
+        let hosts = config_value_sources("web", "hosts", &docs);
+        assert_eq!(hosts[0].value, "[ \"a\", \"b\", ]");
+        assert!(config_value_sources("web", "timeout", &docs).is_empty());
+    }
+
     #[test]
     fn test_extract_fields() {
         let code = r#"
@@ -397,7 +670,7 @@ mod tests {
         let scanner = ConfigScanner::new();
 
         if let syn::Item::Struct(item_struct) = &syntax_tree.items[0] {
-            let fields = scanner.extract_fields(&item_struct.fields);
+            let fields = scanner.extract_fields(&item_struct.fields, &PositionMapper::new(code));
 
             assert_eq!(fields.len(), 3);
 
@@ -413,6 +686,7 @@ mod tests {
             assert_eq!(fields[2].name, "timeout");
             assert_eq!(fields[2].type_name, "Option<u64>");
             assert!(fields[2].optional);
+            assert_eq!(fields[2].range.start, lsp_types::Position::new(7, 16));
         } else {
             panic!("Expected struct item");
         }
@@ -0,0 +1,226 @@
+//! 生成代码扫描模块
+//!
+//! 构建脚本可以在 `OUT_DIR` 中生成路由处理器和组件，再通过 `include!` 引入源代码。
+//! 这些文件不在 `src` 目录下，默认不会被索引；在 `[index] generated_dirs` 中列出后，
+//! 路由和组件的导航、搜索会同时包含生成的代码。
+//!
+//! `OUT_DIR` 的路径包含构建哈希（如 `target/debug/build/my-app-1a2b3c/out`），
+//! 因此目录模式中的 `*` 可以匹配一段路径。
+//!
+//! 生成代码目录通常很大且只在构建后变化，扫描结果按目录缓存，缓存键是目录中
+//! 所有 Rust 文件的路径、大小和修改时间的哈希，目录内容变化后自动重新扫描。
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use walkdir::WalkDir;
+
+use super::component::{ComponentInfoResponse, ComponentScanner};
+use super::route::{RouteInfoResponse, RouteScanner};
+
+/// 展开目录模式，返回存在的目录（已排序）
+///
+/// 模式相对于 `root`，`*` 匹配一段路径中的任意字符
+pub fn resolve_dirs(root: &Path, patterns: &[String]) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    for pattern in patterns {
+        let mut candidates = vec![root.to_path_buf()];
+        for segment in pattern.split(['/', '\\']).filter(|s| !s.is_empty()) {
+            candidates = candidates
+                .iter()
+                .flat_map(|dir| expand_segment(dir, segment))
+                .collect();
+        }
+        dirs.extend(candidates.into_iter().filter(|dir| dir.is_dir()));
+    }
+
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+/// 展开一段路径模式
+fn expand_segment(dir: &Path, segment: &str) -> Vec<PathBuf> {
+    if !segment.contains('*') {
+        return vec![dir.join(segment)];
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| wildcard_match(segment, &entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// `*` 通配符匹配
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<_> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// 目录中所有 Rust 文件的路径、大小和修改时间的哈希
+pub fn dir_hash(dir: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    for entry in WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+    {
+        entry.path().hash(&mut hasher);
+        if let Ok(metadata) = entry.metadata() {
+            metadata.len().hash(&mut hasher);
+            if let Ok(modified) = metadata.modified() {
+                modified.hash(&mut hasher);
+            }
+        }
+    }
+
+    hasher.finish()
+}
+
+/// 按目录哈希缓存的扫描结果
+struct CachedScan<T> {
+    entries: Mutex<HashMap<PathBuf, (u64, Vec<T>)>>,
+}
+
+impl<T: Clone> CachedScan<T> {
+    fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 目录哈希未变化时返回缓存的结果，否则重新扫描
+    fn get_or_scan(&self, dir: &Path, scan: impl FnOnce(&Path) -> Vec<T>) -> Vec<T> {
+        let hash = dir_hash(dir);
+        let mut entries = self.entries.lock().unwrap();
+        if let Some((cached_hash, items)) = entries.get(dir) {
+            if *cached_hash == hash {
+                return items.clone();
+            }
+        }
+
+        let items = scan(dir);
+        entries.insert(dir.to_path_buf(), (hash, items.clone()));
+        items
+    }
+}
+
+/// 生成代码的扫描缓存
+pub struct GeneratedCodeCache {
+    routes: CachedScan<RouteInfoResponse>,
+    components: CachedScan<ComponentInfoResponse>,
+}
+
+impl GeneratedCodeCache {
+    /// 创建空缓存
+    pub fn new() -> Self {
+        Self {
+            routes: CachedScan::new(),
+            components: CachedScan::new(),
+        }
+    }
+
+    /// 生成代码目录中的路由
+    pub fn routes(&self, root: &Path, patterns: &[String]) -> Vec<RouteInfoResponse> {
+        let scanner = RouteScanner::new();
+        resolve_dirs(root, patterns)
+            .iter()
+            .flat_map(|dir| self.routes.get_or_scan(dir, |dir| scanner.scan_dir(dir)))
+            .collect()
+    }
+
+    /// 生成代码目录中的组件
+    pub fn components(&self, root: &Path, patterns: &[String]) -> Vec<ComponentInfoResponse> {
+        let scanner = ComponentScanner::new();
+        resolve_dirs(root, patterns)
+            .iter()
+            .flat_map(|dir| {
+                self.components
+                    .get_or_scan(dir, |dir| scanner.scan_dir(dir))
+            })
+            .collect()
+    }
+}
+
+impl Default for GeneratedCodeCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_resolve_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let build = dir.path().join("target/debug/build");
+        fs::create_dir_all(build.join("app-1a2b/out")).unwrap();
+        fs::create_dir_all(build.join("app-3c4d")).unwrap();
+        fs::create_dir_all(build.join("other-5e6f/out")).unwrap();
+
+        let dirs = resolve_dirs(dir.path(), &["target/*/build/app-*/out".to_string()]);
+        assert_eq!(dirs, vec![build.join("app-1a2b/out")]);
+
+        assert!(wildcard_match("app-*", "app-1a2b"));
+        assert!(wildcard_match("*-*", "app-1a2b"));
+        assert!(!wildcard_match("app-*", "other-1a2b"));
+        assert!(!wildcard_match("app", "app-1a2b"));
+    }
+
+    #[test]
+    fn test_generated_routes_cached_by_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("target/generated");
+        fs::create_dir_all(&out).unwrap();
+        fs::write(
+            out.join("routes.rs"),
+            "#[get(\"/generated\")]\nasync fn generated() {}\n",
+        )
+        .unwrap();
+
+        let cache = GeneratedCodeCache::new();
+        let patterns = vec!["target/generated".to_string()];
+        let routes = cache.routes(dir.path(), &patterns);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].path, "/generated");
+        let hash = dir_hash(&out);
+        assert_eq!(cache.routes(dir.path(), &patterns).len(), 1);
+
+        // 目录内容变化后哈希改变，重新扫描
+        fs::write(
+            out.join("more.rs"),
+            "#[post(\"/generated\")]\nasync fn create() {}\n",
+        )
+        .unwrap();
+        assert_ne!(dir_hash(&out), hash);
+        assert_eq!(cache.routes(dir.path(), &patterns).len(), 2);
+    }
+}
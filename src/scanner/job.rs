@@ -1,18 +1,25 @@
 //! 任务扫描器模块
 //!
-//! 扫描项目中的所有定时任务定义（带有 #[cron], #[fix_delay], #[fix_rate] 的函数）
+//! 扫描项目中的所有定时任务定义（带有 #[cron], #[fix_delay], #[fix_rate], #[one_shot] 的函数）
 
 use crate::analysis::rust::macro_analyzer::{JobMacro, MacroAnalyzer, SpringMacro};
+use crate::core::document::PositionMapper;
+use crate::core::document::SourceReader;
 use crate::protocol::types::{LocationResponse, PositionResponse, RangeResponse};
-use lsp_types::Url;
+use lsp_types::{Location, Url};
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::collections::HashMap;
 use std::path::Path;
+use syn::spanned::Spanned;
 use walkdir::WalkDir;
 
+/// 定时任务宏名称
+pub(crate) const JOB_ATTRIBUTES: &[&str] = &["cron", "fix_delay", "fix_rate", "one_shot"];
+
 /// 任务扫描器
 pub struct JobScanner {
     macro_analyzer: MacroAnalyzer,
+    sources: SourceReader,
 }
 
 impl JobScanner {
@@ -20,9 +27,17 @@ impl JobScanner {
     pub fn new() -> Self {
         Self {
             macro_analyzer: MacroAnalyzer::new(),
+            sources: SourceReader::default(),
         }
     }
 
+    /// 使用指定的方式读取源文件（语言服务器中优先读取编辑器中打开的文档）
+    pub fn with_sources(mut self, sources: SourceReader) -> Self {
+        self.macro_analyzer = MacroAnalyzer::new().with_encoding(sources.encoding());
+        self.sources = sources;
+        self
+    }
+
     /// 扫描项目中的所有任务
     ///
     /// # Arguments
@@ -32,6 +47,7 @@ impl JobScanner {
     /// # Returns
     ///
     /// 返回扫描到的所有任务信息
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
     pub fn scan_jobs(&self, project_path: &Path) -> Result<Vec<JobInfoResponse>, ScanError> {
         let mut jobs = Vec::new();
 
@@ -52,7 +68,7 @@ impl JobScanner {
             let file_path = entry.path();
 
             // 读取文件内容
-            let content = match fs::read_to_string(file_path) {
+            let content = match self.sources.read(file_path) {
                 Ok(content) => content,
                 Err(e) => {
                     tracing::warn!("Failed to read file {:?}: {}", file_path, e);
@@ -69,65 +85,179 @@ impl JobScanner {
                 }
             };
 
-            let rust_doc = match self.macro_analyzer.parse(file_url.clone(), content) {
-                Ok(doc) => doc,
-                Err(e) => {
-                    tracing::warn!("Failed to parse file {:?}: {}", file_path, e);
-                    continue;
-                }
-            };
+            jobs.extend(self.scan_file(&file_url, content));
+        }
 
-            // 提取宏信息
-            let rust_doc = match self.macro_analyzer.extract_macros(rust_doc) {
-                Ok(doc) => doc,
-                Err(e) => {
-                    tracing::warn!("Failed to extract macros from {:?}: {}", file_path, e);
-                    continue;
-                }
-            };
+        Ok(jobs)
+    }
+
+    /// 扫描给定的 Rust 文件中的任务（文件内容由调用方提供，可以是编辑器中尚未保存的内容）
+    pub fn scan_sources(&self, files: &[(Url, String)]) -> Vec<JobInfoResponse> {
+        files
+            .iter()
+            .flat_map(|(uri, content)| self.scan_file(uri, content.clone()))
+            .collect()
+    }
 
-            // 提取任务信息
-            for spring_macro in &rust_doc.macros {
-                if let SpringMacro::Job(job_macro) = spring_macro {
-                    let (job_type, schedule) = match job_macro {
-                        JobMacro::Cron { expression, .. } => (JobType::Cron, expression.clone()),
-                        JobMacro::FixDelay { seconds, .. } => {
-                            (JobType::FixDelay, format!("{} seconds", seconds))
-                        }
-                        JobMacro::FixRate { seconds, .. } => {
-                            (JobType::FixRate, format!("{} seconds", seconds))
-                        }
-                    };
-
-                    let range = match job_macro {
-                        JobMacro::Cron { range, .. }
-                        | JobMacro::FixDelay { range, .. }
-                        | JobMacro::FixRate { range, .. } => range,
-                    };
-
-                    jobs.push(JobInfoResponse {
-                        name: "job_function".to_string(), // TODO: 从函数名提取
-                        job_type,
-                        schedule,
-                        location: LocationResponse {
-                            uri: file_url.to_string(),
-                            range: RangeResponse {
-                                start: PositionResponse {
-                                    line: range.start.line,
-                                    character: range.start.character,
-                                },
-                                end: PositionResponse {
-                                    line: range.end.line,
-                                    character: range.end.character,
-                                },
+    /// 扫描单个文件中的任务，无法解析的文件返回空列表
+    fn scan_file(&self, file_url: &Url, content: String) -> Vec<JobInfoResponse> {
+        let rust_doc = match self.macro_analyzer.parse(file_url.clone(), content) {
+            Ok(doc) => doc,
+            Err(e) => {
+                tracing::warn!("Failed to parse file {}: {}", file_url, e);
+                return Vec::new();
+            }
+        };
+
+        // 任务宏位置 -> 函数名
+        let function_names = job_function_names(&self.sources.mapper(&rust_doc.content));
+
+        // 提取宏信息
+        let rust_doc = match self.macro_analyzer.extract_macros(rust_doc) {
+            Ok(doc) => doc,
+            Err(e) => {
+                tracing::warn!("Failed to extract macros from {}: {}", file_url, e);
+                return Vec::new();
+            }
+        };
+
+        // 提取任务信息
+        let mut jobs = Vec::new();
+        for spring_macro in &rust_doc.macros {
+            if let SpringMacro::Job(job_macro) = spring_macro {
+                let (job_type, schedule, seconds) = match job_macro {
+                    JobMacro::Cron { expression, .. } => (JobType::Cron, expression.clone(), None),
+                    JobMacro::FixDelay { seconds, .. } => (
+                        JobType::FixDelay,
+                        format!("{} seconds", seconds),
+                        Some(*seconds),
+                    ),
+                    JobMacro::FixRate { seconds, .. } => (
+                        JobType::FixRate,
+                        format!("{} seconds", seconds),
+                        Some(*seconds),
+                    ),
+                    JobMacro::OneShot { seconds, .. } => (
+                        JobType::OneShot,
+                        format!("once after {} seconds", seconds),
+                        Some(*seconds),
+                    ),
+                };
+
+                let range = match job_macro {
+                    JobMacro::Cron { range, .. }
+                    | JobMacro::FixDelay { range, .. }
+                    | JobMacro::FixRate { range, .. }
+                    | JobMacro::OneShot { range, .. } => range,
+                };
+
+                let name = function_names
+                    .get(&(range.start.line, range.start.character))
+                    .cloned()
+                    .unwrap_or_else(|| "job_function".to_string());
+
+                jobs.push(JobInfoResponse {
+                    name,
+                    job_type,
+                    schedule,
+                    seconds,
+                    timezone: match job_macro {
+                        JobMacro::Cron { timezone, .. } => timezone.clone(),
+                        _ => None,
+                    },
+                    location: LocationResponse {
+                        uri: file_url.to_string(),
+                        range: RangeResponse {
+                            start: PositionResponse {
+                                line: range.start.line,
+                                character: range.start.character,
+                            },
+                            end: PositionResponse {
+                                line: range.end.line,
+                                character: range.end.character,
                             },
                         },
-                    });
+                    },
+                });
+            }
+        }
+
+        jobs
+    }
+
+    /// 构建任务名称索引
+    ///
+    /// 用于将配置文件中引用的任务名称（如 `[job] disabled = ["hourly_cleanup"]`）
+    /// 解析到对应的任务函数
+    pub fn build_name_index(&self, project_path: &Path) -> Result<JobNameIndex, ScanError> {
+        Ok(JobNameIndex::from_jobs(&self.scan_jobs(project_path)?))
+    }
+}
+
+/// 查找带有任务宏的函数，返回宏位置（行、列）到函数名的映射
+fn job_function_names(lines: &PositionMapper) -> HashMap<(u32, u32), String> {
+    let mut names = HashMap::new();
+
+    let Ok(file) = syn::parse_file(lines.content()) else {
+        return names;
+    };
+
+    for item in &file.items {
+        if let syn::Item::Fn(item_fn) = item {
+            for attr in &item_fn.attrs {
+                if JOB_ATTRIBUTES.iter().any(|name| attr.path().is_ident(name)) {
+                    let start = lines.span_range(attr.span()).start;
+                    names.insert((start.line, start.character), item_fn.sig.ident.to_string());
                 }
             }
         }
+    }
 
-        Ok(jobs)
+    names
+}
+
+/// 任务名称索引
+///
+/// 任务名称为函数名，同名函数可能出现在不同模块中，因此一个名称可对应多个位置
+#[derive(Debug, Clone, Default)]
+pub struct JobNameIndex {
+    jobs: HashMap<String, Vec<Location>>,
+}
+
+impl JobNameIndex {
+    /// 从扫描结果构建索引
+    pub fn from_jobs(jobs: &[JobInfoResponse]) -> Self {
+        let mut index = Self::default();
+
+        for job in jobs {
+            let Some(location) = job.lsp_location() else {
+                continue;
+            };
+            index
+                .jobs
+                .entry(job.name.clone())
+                .or_default()
+                .push(location);
+        }
+
+        index
+    }
+
+    /// 查找任务定义位置
+    pub fn find(&self, name: &str) -> Vec<Location> {
+        self.jobs.get(name).cloned().unwrap_or_default()
+    }
+
+    /// 任务是否存在
+    pub fn contains(&self, name: &str) -> bool {
+        self.jobs.contains_key(name)
+    }
+
+    /// 所有任务名称（已排序）
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.jobs.keys().map(|s| s.as_str()).collect();
+        names.sort();
+        names
     }
 }
 
@@ -138,7 +268,7 @@ impl Default for JobScanner {
 }
 
 /// 任务类型
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum JobType {
     /// Cron 表达式任务
     Cron,
@@ -146,6 +276,8 @@ pub enum JobType {
     FixDelay,
     /// 固定频率任务
     FixRate,
+    /// 一次性任务
+    OneShot,
 }
 
 /// 任务信息响应（用于 JSON 序列化）
@@ -158,10 +290,31 @@ pub struct JobInfoResponse {
     pub job_type: JobType,
     /// 调度表达式
     pub schedule: String,
+    /// Cron 任务的时区（未指定时为 UTC）
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub timezone: Option<String>,
+    /// 固定延迟、固定频率任务的间隔秒数和一次性任务的延迟秒数（不序列化，调度信息见 `schedule`）
+    #[serde(skip)]
+    pub seconds: Option<u64>,
     /// 源代码位置
     pub location: LocationResponse,
 }
 
+impl JobInfoResponse {
+    /// 任务宏的 LSP 位置，URI 无效时返回 `None`
+    pub fn lsp_location(&self) -> Option<Location> {
+        let uri = Url::parse(&self.location.uri).ok()?;
+        let range = &self.location.range;
+        Some(Location {
+            uri,
+            range: lsp_types::Range {
+                start: lsp_types::Position::new(range.start.line, range.start.character),
+                end: lsp_types::Position::new(range.end.line, range.end.character),
+            },
+        })
+    }
+}
+
 /// spring/jobs 请求参数
 #[derive(Debug, Deserialize)]
 pub struct JobsRequest {
@@ -193,6 +346,7 @@ pub enum ScanError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
 
     #[test]
     fn test_job_scanner_new() {
@@ -207,4 +361,37 @@ mod tests {
         // 验证默认扫描器创建成功
         assert!(true);
     }
+
+    #[test]
+    fn test_scan_jobs_uses_function_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("jobs.rs"),
+            r#"
+#[cron("0 0 * * * *")]
+async fn hourly_cleanup() {}
+
+#[fix_delay(10)]
+async fn refresh_cache() {}
+
+#[one_shot(3)]
+async fn warm_up() {}
+"#,
+        )
+        .unwrap();
+
+        let scanner = JobScanner::new();
+        let index = scanner.build_name_index(dir.path()).unwrap();
+
+        assert_eq!(
+            index.names(),
+            vec!["hourly_cleanup", "refresh_cache", "warm_up"]
+        );
+        let locations = index.find("hourly_cleanup");
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].range.start.line, 1);
+        assert!(!index.contains("missing_job"));
+    }
 }
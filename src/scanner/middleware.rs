@@ -0,0 +1,958 @@
+//! 中间件扫描器模块
+//!
+//! 分析每个路由实际生效的中间件链，包括：
+//! - 路由级中间件：处理器函数上的 `#[middlewares(...)]`
+//! - 模块级中间件：`mod` 上的 `#[middlewares(...)]`，作用于模块内的所有路由
+//! - 路由器级中间件：`Router` 上的 `.layer(...)` / `.route_layer(...)` 调用
+//!
+//! 路由器在运行时才组装，无法静态确定 `.layer` 作用于哪些路由，
+//! 因此项目中的路由器级中间件视为作用于所有路由。
+
+use crate::analysis::rust::handler_analyzer::{route_path_and_methods, ROUTE_ATTRIBUTES};
+use crate::core::config::MiddlewareRule;
+use crate::core::document::{PositionMapper, SourceReader};
+use crate::core::messages::{localized, Locale};
+use crate::protocol::types::{LocationResponse, PositionResponse, RangeResponse};
+use crate::scanner::config::ConfigurationStruct;
+use lsp_types::Url;
+use proc_macro2::TokenTree;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use walkdir::WalkDir;
+
+use super::route::ScanError;
+
+/// 路由器级中间件的方法名
+const LAYER_METHODS: &[&str] = &["layer", "route_layer"];
+
+/// 中间件类型名的常见后缀，去掉后缀再加上 `Config` 即为约定的配置结构名
+const MIDDLEWARE_SUFFIXES: &[&str] = &["Layer", "Middleware", "Service"];
+
+/// 中间件来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MiddlewareSource {
+    /// `Router` 上的 `.layer(...)` 调用
+    Router,
+    /// 模块上的 `#[middlewares(...)]`
+    Module,
+    /// 处理器上的 `#[middlewares(...)]`
+    Route,
+}
+
+impl MiddlewareSource {
+    /// 来源的显示名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MiddlewareSource::Router => "router",
+            MiddlewareSource::Module => "module",
+            MiddlewareSource::Route => "route",
+        }
+    }
+}
+
+/// 中间件信息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MiddlewareInfo {
+    /// 中间件表达式，如 `middleware::from_fn(auth)`
+    pub name: String,
+    /// 中间件来源
+    pub source: MiddlewareSource,
+}
+
+/// 路由及其生效的中间件链
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteMiddlewares {
+    /// HTTP 方法列表
+    pub methods: Vec<String>,
+    /// 路径模式
+    pub path: String,
+    /// 处理器函数名
+    pub handler: String,
+    /// 源代码位置（处理器函数名）
+    pub location: LocationResponse,
+    /// 中间件链（从外到内：路由器级、模块级、路由级）
+    pub middlewares: Vec<MiddlewareInfo>,
+    /// 处理器参数的类型（去掉空白，如 `Extension<AdminUser>`），用于检查 guard 提取器
+    #[serde(default)]
+    pub extractors: Vec<String>,
+    /// 未满足的检查规则
+    #[serde(default)]
+    pub missing: Vec<MiddlewareRule>,
+}
+
+impl RouteMiddlewares {
+    /// 按规则检查中间件链，记录未满足的规则
+    pub fn apply_rules(&mut self, rules: &[MiddlewareRule]) {
+        self.missing = rules
+            .iter()
+            .filter(|rule| path_matches(&rule.pattern, &self.path))
+            .filter(|rule| {
+                !self
+                    .middlewares
+                    .iter()
+                    .any(|middleware| middleware.name.contains(&rule.required))
+            })
+            .cloned()
+            .collect();
+    }
+
+    /// 为未满足的检查规则生成诊断（位于处理器函数名上）
+    pub fn missing_diagnostics(&self, locale: Locale) -> Vec<lsp_types::Diagnostic> {
+        let range = &self.location.range;
+        let range = lsp_types::Range {
+            start: lsp_types::Position::new(range.start.line, range.start.character),
+            end: lsp_types::Position::new(range.end.line, range.end.character),
+        };
+
+        self.missing
+            .iter()
+            .map(|rule| lsp_types::Diagnostic {
+                range,
+                severity: Some(lsp_types::DiagnosticSeverity::WARNING),
+                code: Some(lsp_types::NumberOrString::String(
+                    "missing-middleware".to_string(),
+                )),
+                source: Some("spring-lsp".to_string()),
+                message: localized(
+                    locale,
+                    "missing-middleware",
+                    &[&self.path, &rule.pattern, &rule.required],
+                ),
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
+/// 单个文件的中间件分析结果
+#[derive(Debug, Clone, Default)]
+pub struct FileMiddlewares {
+    /// 文件中的路由（中间件链只包含路由级和模块级中间件）
+    pub routes: Vec<RouteMiddlewares>,
+    /// 文件中的路由器级中间件
+    pub router_layers: Vec<String>,
+}
+
+/// `#[middlewares(...)]` 中引用的单个中间件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MiddlewareReference {
+    /// 中间件表达式
+    pub name: String,
+    /// 表达式在文档中的范围
+    pub range: lsp_types::Range,
+    /// 表达式中出现的标识符，用于关联中间件函数、类型和配置结构
+    pub idents: Vec<String>,
+}
+
+/// 工作空间中定义的中间件种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiddlewareKind {
+    /// 实现了 `Layer<S>` 的类型
+    Layer,
+    /// 接收 `Next` 参数的中间件函数（通过 `middleware::from_fn` 使用）
+    Function,
+}
+
+/// 工作空间中定义的中间件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MiddlewareDefinition {
+    /// 类型名或函数名
+    pub name: String,
+    /// 中间件种类
+    pub kind: MiddlewareKind,
+    /// 文档注释摘要（第一行）
+    pub summary: Option<String>,
+    /// 定义所在的文件
+    pub uri: Url,
+}
+
+impl MiddlewareDefinition {
+    /// 在 `#[middlewares(...)]` 中使用该中间件的写法
+    pub fn usage(&self) -> String {
+        match self.kind {
+            MiddlewareKind::Layer => self.name.clone(),
+            MiddlewareKind::Function => format!("middleware::from_fn({})", self.name),
+        }
+    }
+}
+
+/// spring/middlewares 请求参数
+#[derive(Debug, Deserialize)]
+pub struct MiddlewaresRequest {
+    /// 应用路径
+    #[serde(rename = "appPath")]
+    pub app_path: String,
+}
+
+/// spring/middlewares 响应
+#[derive(Debug, Serialize)]
+pub struct MiddlewaresResponse {
+    /// 路由列表
+    pub routes: Vec<RouteMiddlewares>,
+}
+
+/// 中间件扫描器
+#[derive(Debug, Clone, Default)]
+pub struct MiddlewareScanner {
+    sources: SourceReader,
+}
+
+impl MiddlewareScanner {
+    /// 创建新的中间件扫描器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的方式读取源文件（语言服务器中优先读取编辑器中打开的文档）
+    pub fn with_sources(mut self, sources: SourceReader) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// 扫描项目中所有路由的中间件链
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - 项目根目录路径
+    /// * `rules` - 中间件检查规则
+    pub fn scan_middlewares(
+        &self,
+        project_path: &Path,
+        rules: &[MiddlewareRule],
+    ) -> Result<Vec<RouteMiddlewares>, ScanError> {
+        let mut routes = Vec::new();
+        let mut router_layers = Vec::new();
+
+        for (file_url, content) in rust_files(project_path, &self.sources)? {
+            match self.analyze_file(&file_url, &content) {
+                Ok(file) => {
+                    routes.extend(file.routes);
+                    router_layers.extend(file.router_layers);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to parse file {}: {}", file_url, e);
+                }
+            }
+        }
+
+        for route in &mut routes {
+            route.middlewares.splice(
+                0..0,
+                router_layers.iter().map(|name| MiddlewareInfo {
+                    name: name.clone(),
+                    source: MiddlewareSource::Router,
+                }),
+            );
+            route.apply_rules(rules);
+        }
+
+        Ok(routes)
+    }
+
+    /// 扫描项目中的路由器级中间件
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - 项目根目录路径
+    /// * `exclude` - 跳过的文件（通常是编辑器中已打开、内容尚未保存的文件）
+    pub fn scan_router_layers(
+        &self,
+        project_path: &Path,
+        exclude: Option<&Url>,
+    ) -> Result<Vec<String>, ScanError> {
+        let mut layers = Vec::new();
+
+        for (file_url, content) in rust_files(project_path, &self.sources)? {
+            if exclude == Some(&file_url) {
+                continue;
+            }
+            if let Ok(file) = self.analyze_file(&file_url, &content) {
+                layers.extend(file.router_layers);
+            }
+        }
+
+        Ok(layers)
+    }
+
+    /// 分析单个文件中的路由和中间件
+    pub fn analyze_file(&self, uri: &Url, content: &str) -> Result<FileMiddlewares, syn::Error> {
+        let file = syn::parse_file(content)?;
+        let lines = self.sources.mapper(content);
+        let mut result = FileMiddlewares::default();
+
+        self.analyze_items(uri, &lines, &file.items, &[], &mut result);
+
+        Ok(result)
+    }
+
+    /// 提取文档中所有 `#[middlewares(...)]` 引用的中间件
+    pub fn middleware_references(
+        &self,
+        content: &str,
+    ) -> Result<Vec<MiddlewareReference>, syn::Error> {
+        fn collect(
+            items: &[syn::Item],
+            lines: &PositionMapper,
+            references: &mut Vec<MiddlewareReference>,
+        ) {
+            for item in items {
+                match item {
+                    syn::Item::Fn(item_fn) => attr_references(&item_fn.attrs, lines, references),
+                    syn::Item::Mod(item_mod) => {
+                        attr_references(&item_mod.attrs, lines, references);
+                        if let Some((_, items)) = &item_mod.content {
+                            collect(items, lines, references);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let file = syn::parse_file(content)?;
+        let mut references = Vec::new();
+        collect(&file.items, &self.sources.mapper(content), &mut references);
+        Ok(references)
+    }
+
+    /// 查找给定文件中定义的中间件：实现了 `Layer<S>` 的类型和接收 `Next` 参数的函数
+    ///
+    /// 文件内容由调用方提供（可以是编辑器中尚未保存的内容），无法解析的文件直接跳过
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
+    pub fn workspace_middlewares(&self, files: &[(Url, String)]) -> Vec<MiddlewareDefinition> {
+        fn collect(
+            uri: &Url,
+            items: &[syn::Item],
+            summaries: &HashMap<String, Option<String>>,
+            definitions: &mut Vec<MiddlewareDefinition>,
+        ) {
+            for item in items {
+                match item {
+                    syn::Item::Impl(item_impl) => {
+                        let is_layer = item_impl.trait_.as_ref().is_some_and(|(_, path, _)| {
+                            path.segments
+                                .last()
+                                .is_some_and(|segment| segment.ident == "Layer")
+                        });
+                        let syn::Type::Path(self_ty) = item_impl.self_ty.as_ref() else {
+                            continue;
+                        };
+                        let Some(segment) = self_ty.path.segments.last() else {
+                            continue;
+                        };
+                        if is_layer {
+                            let name = segment.ident.to_string();
+                            definitions.push(MiddlewareDefinition {
+                                summary: summaries.get(&name).cloned().flatten(),
+                                name,
+                                kind: MiddlewareKind::Layer,
+                                uri: uri.clone(),
+                            });
+                        }
+                    }
+                    syn::Item::Fn(item_fn) => {
+                        let takes_next = item_fn.sig.inputs.iter().any(|input| {
+                            matches!(input, syn::FnArg::Typed(pat_type)
+                                if matches!(pat_type.ty.as_ref(), syn::Type::Path(path)
+                                    if path.path.segments.last().is_some_and(|segment| segment.ident == "Next")))
+                        });
+                        if takes_next {
+                            definitions.push(MiddlewareDefinition {
+                                name: item_fn.sig.ident.to_string(),
+                                kind: MiddlewareKind::Function,
+                                summary: doc_summary(&item_fn.attrs),
+                                uri: uri.clone(),
+                            });
+                        }
+                    }
+                    syn::Item::Mod(item_mod) => {
+                        if let Some((_, items)) = &item_mod.content {
+                            collect(uri, items, summaries, definitions);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut definitions = Vec::new();
+        for (uri, content) in files {
+            let Ok(file) = syn::parse_file(content) else {
+                continue;
+            };
+            let summaries: HashMap<String, Option<String>> = file
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    syn::Item::Struct(item) => {
+                        Some((item.ident.to_string(), doc_summary(&item.attrs)))
+                    }
+                    _ => None,
+                })
+                .collect();
+            collect(uri, &file.items, &summaries, &mut definitions);
+        }
+        definitions.dedup_by(|a, b| a.name == b.name && a.kind == b.kind);
+        definitions
+    }
+
+    /// 查找中间件关联的配置结构
+    ///
+    /// 关联方式：
+    /// - 中间件函数的参数类型或中间件结构体的字段类型中引用了配置结构（如 `Config<RateLimitConfig>`）
+    /// - 命名约定：`RateLimitLayer` / `rate_limit` 对应 `RateLimitConfig`
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - 项目根目录路径
+    /// * `current` - 编辑器中打开的文档（优先于磁盘上的内容）
+    /// * `reference` - 中间件引用
+    /// * `configurations` - 项目中的配置结构
+    pub fn find_middleware_configs(
+        &self,
+        project_path: &Path,
+        current: Option<(&Url, &str)>,
+        reference: &MiddlewareReference,
+        configurations: &[ConfigurationStruct],
+    ) -> Vec<ConfigurationStruct> {
+        let idents: HashSet<&str> = reference.idents.iter().map(String::as_str).collect();
+
+        let mut related: HashSet<String> = reference
+            .idents
+            .iter()
+            .flat_map(|ident| {
+                let pascal = to_pascal_case(ident);
+                let stem = MIDDLEWARE_SUFFIXES
+                    .iter()
+                    .find_map(|suffix| pascal.strip_suffix(suffix))
+                    .unwrap_or(&pascal)
+                    .to_string();
+                [ident.clone(), format!("{}Config", stem)]
+            })
+            .collect();
+
+        let mut files = rust_files(project_path, &self.sources).unwrap_or_default();
+        if let Some((uri, content)) = current {
+            files.retain(|(file_url, _)| file_url != uri);
+            files.push((uri.clone(), content.to_string()));
+        }
+
+        for (_, content) in files {
+            if let Ok(file) = syn::parse_file(&content) {
+                collect_related_types(&file.items, &idents, &mut related);
+            }
+        }
+
+        configurations
+            .iter()
+            .filter(|config| related.contains(&config.name))
+            .cloned()
+            .collect()
+    }
+
+    /// 递归分析条目，`inherited` 为外层模块的中间件
+    fn analyze_items(
+        &self,
+        uri: &Url,
+        lines: &PositionMapper,
+        items: &[syn::Item],
+        inherited: &[String],
+        result: &mut FileMiddlewares,
+    ) {
+        for item in items {
+            match item {
+                syn::Item::Fn(item_fn) => {
+                    match self.extract_route(uri, lines, item_fn, inherited) {
+                        Some(route) => result.routes.push(route),
+                        None => collect_layers(item_tokens(item), &mut result.router_layers),
+                    }
+                }
+                syn::Item::Mod(item_mod) => {
+                    if let Some((_, items)) = &item_mod.content {
+                        let mut middlewares = inherited.to_vec();
+                        middlewares.extend(attr_middlewares(&item_mod.attrs));
+                        self.analyze_items(uri, lines, items, &middlewares, result);
+                    }
+                }
+                _ => collect_layers(item_tokens(item), &mut result.router_layers),
+            }
+        }
+    }
+
+    /// 提取路由处理器及其模块级和路由级中间件
+    fn extract_route(
+        &self,
+        uri: &Url,
+        lines: &PositionMapper,
+        item_fn: &syn::ItemFn,
+        inherited: &[String],
+    ) -> Option<RouteMiddlewares> {
+        let attr = item_fn.attrs.iter().find(|attr| {
+            ROUTE_ATTRIBUTES
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        })?;
+        let (path, methods) = route_path_and_methods(attr);
+
+        let middlewares = inherited
+            .iter()
+            .map(|name| MiddlewareInfo {
+                name: name.clone(),
+                source: MiddlewareSource::Module,
+            })
+            .chain(
+                attr_middlewares(&item_fn.attrs)
+                    .into_iter()
+                    .map(|name| MiddlewareInfo {
+                        name,
+                        source: MiddlewareSource::Route,
+                    }),
+            )
+            .collect();
+
+        let extractors = item_fn
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                syn::FnArg::Typed(pat_type) => {
+                    let ty = &pat_type.ty;
+                    Some(quote::quote!(#ty).to_string().replace(' ', ""))
+                }
+                syn::FnArg::Receiver(_) => None,
+            })
+            .collect();
+
+        let range = lines.span_range(item_fn.sig.ident.span());
+
+        Some(RouteMiddlewares {
+            methods,
+            path: path.unwrap_or_default(),
+            handler: item_fn.sig.ident.to_string(),
+            location: LocationResponse {
+                uri: uri.to_string(),
+                range: RangeResponse {
+                    start: PositionResponse {
+                        line: range.start.line,
+                        character: range.start.character,
+                    },
+                    end: PositionResponse {
+                        line: range.end.line,
+                        character: range.end.character,
+                    },
+                },
+            },
+            middlewares,
+            extractors,
+            missing: Vec::new(),
+        })
+    }
+}
+
+/// 判断路由路径是否匹配模式
+///
+/// `*` 匹配一段路径，`**` 匹配任意多段（包括零段）
+pub fn path_matches(pattern: &str, path: &str) -> bool {
+    fn matches(pattern: &[&str], path: &[&str]) -> bool {
+        match (pattern.first(), path.first()) {
+            (None, None) => true,
+            (Some(&"**"), _) => {
+                matches(&pattern[1..], path) || (!path.is_empty() && matches(pattern, &path[1..]))
+            }
+            (Some(&"*"), Some(_)) => matches(&pattern[1..], &path[1..]),
+            (Some(p), Some(s)) if p == s => matches(&pattern[1..], &path[1..]),
+            _ => false,
+        }
+    }
+
+    let segments = |s: &'_ str| -> Vec<String> {
+        s.split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let pattern = segments(pattern);
+    let path = segments(path);
+    matches(
+        &pattern.iter().map(String::as_str).collect::<Vec<_>>(),
+        &path.iter().map(String::as_str).collect::<Vec<_>>(),
+    )
+}
+
+/// 读取项目 src 目录中的所有 Rust 文件
+pub(crate) fn rust_files(
+    project_path: &Path,
+    sources: &SourceReader,
+) -> Result<Vec<(Url, String)>, ScanError> {
+    let src_path = project_path.join("src");
+    if !src_path.exists() {
+        return Err(ScanError::InvalidProject(
+            "src directory not found".to_string(),
+        ));
+    }
+
+    Ok(WalkDir::new(&src_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|entry| {
+            let content = sources.read(entry.path()).ok()?;
+            let url = Url::from_file_path(entry.path()).ok()?;
+            Some((url, content))
+        })
+        .collect())
+}
+
+/// 提取属性中的 `#[middlewares(...)]` 列表
+fn attr_middlewares(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("middlewares"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flat_map(|args| {
+            args.into_iter()
+                .map(|arg| compact_tokens(quote::quote!(#arg)))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// 提取属性中 `#[middlewares(...)]` 的每个中间件及其位置
+fn attr_references(
+    attrs: &[syn::Attribute],
+    lines: &PositionMapper,
+    references: &mut Vec<MiddlewareReference>,
+) {
+    let args = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("middlewares"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated)
+                .ok()
+        });
+
+    for arg in args.flatten() {
+        let tokens = quote::quote!(#arg);
+        let mut idents = Vec::new();
+        collect_idents(tokens.clone(), &mut idents);
+
+        references.push(MiddlewareReference {
+            name: compact_tokens(tokens),
+            range: lines.span_range(arg.span()),
+            idents,
+        });
+    }
+}
+
+/// 文档注释的第一行
+fn doc_summary(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .find_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }),
+                ..
+            }) => Some(lit.value().trim().to_string()),
+            _ => None,
+        })
+        .filter(|summary| !summary.is_empty())
+}
+
+/// 收集 token 流中的所有标识符
+fn collect_idents(tokens: proc_macro2::TokenStream, idents: &mut Vec<String>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => idents.push(ident.to_string()),
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
+
+/// 收集中间件函数参数和中间件结构体字段的类型中出现的标识符
+fn collect_related_types(
+    items: &[syn::Item],
+    idents: &HashSet<&str>,
+    related: &mut HashSet<String>,
+) {
+    fn add_type(ty: &syn::Type, related: &mut HashSet<String>) {
+        let mut type_idents = Vec::new();
+        collect_idents(quote::quote!(#ty), &mut type_idents);
+        related.extend(type_idents);
+    }
+
+    for item in items {
+        match item {
+            syn::Item::Fn(item_fn) if idents.contains(item_fn.sig.ident.to_string().as_str()) => {
+                for input in &item_fn.sig.inputs {
+                    if let syn::FnArg::Typed(pat_type) = input {
+                        add_type(&pat_type.ty, related);
+                    }
+                }
+            }
+            syn::Item::Struct(item_struct)
+                if idents.contains(item_struct.ident.to_string().as_str()) =>
+            {
+                for field in &item_struct.fields {
+                    add_type(&field.ty, related);
+                }
+            }
+            syn::Item::Mod(item_mod) => {
+                if let Some((_, items)) = &item_mod.content {
+                    collect_related_types(items, idents, related);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 将 snake_case 标识符转换为 PascalCase，已是 PascalCase 的标识符保持不变
+fn to_pascal_case(ident: &str) -> String {
+    ident
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// 获取条目的 token 流
+fn item_tokens(item: &syn::Item) -> proc_macro2::TokenStream {
+    quote::quote!(#item)
+}
+
+/// 在 token 流中查找 `.layer(...)` 调用
+fn collect_layers(tokens: proc_macro2::TokenStream, layers: &mut Vec<String>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if let TokenTree::Group(group) = token {
+            collect_layers(group.stream(), layers);
+        }
+
+        let is_layer_call = matches!(token, TokenTree::Punct(p) if p.as_char() == '.')
+            && matches!(tokens.get(i + 1), Some(TokenTree::Ident(ident)) if LAYER_METHODS.iter().any(|m| ident == m));
+        if !is_layer_call {
+            continue;
+        }
+        if let Some(TokenTree::Group(args)) = tokens.get(i + 2) {
+            if args.delimiter() == proc_macro2::Delimiter::Parenthesis {
+                layers.push(compact_tokens(args.stream()));
+            }
+        }
+    }
+}
+
+/// 将 token 流转换为紧凑的字符串表示
+fn compact_tokens(tokens: proc_macro2::TokenStream) -> String {
+    tokens
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" (", "(")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(" ,", ",")
+        .replace(" . ", ".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const SOURCE: &str = r#"
+#[middlewares(middleware::from_fn(auth_middleware))]
+mod admin {
+    #[get("/admin/users")]
+    async fn list_users() {}
+
+    #[middlewares(TimeoutLayer::new(Duration::from_secs(5)))]
+    #[post("/admin/users")]
+    async fn create_user() {}
+}
+
+#[get("/admin/stats")]
+async fn stats() {}
+
+fn router() -> Router {
+    Router::new().layer(TraceLayer::new_for_http())
+}
+"#;
+
+    #[test]
+    fn test_analyze_file() {
+        let uri = Url::parse("file:///project/src/main.rs").unwrap();
+        let file = MiddlewareScanner::new().analyze_file(&uri, SOURCE).unwrap();
+
+        assert_eq!(file.router_layers, vec!["TraceLayer::new_for_http()"]);
+        assert_eq!(file.routes.len(), 3);
+
+        let create = &file.routes[1];
+        assert_eq!(create.handler, "create_user");
+        assert_eq!(create.methods, vec!["POST".to_string()]);
+        assert_eq!(
+            create.middlewares,
+            vec![
+                MiddlewareInfo {
+                    name: "middleware::from_fn(auth_middleware)".to_string(),
+                    source: MiddlewareSource::Module,
+                },
+                MiddlewareInfo {
+                    name: "TimeoutLayer::new(Duration::from_secs(5))".to_string(),
+                    source: MiddlewareSource::Route,
+                },
+            ]
+        );
+        assert!(file.routes[2].middlewares.is_empty());
+    }
+
+    #[test]
+    fn test_apply_rules() {
+        let uri = Url::parse("file:///project/src/main.rs").unwrap();
+        let mut file = MiddlewareScanner::new().analyze_file(&uri, SOURCE).unwrap();
+        let rules = vec![MiddlewareRule {
+            pattern: "/admin/**".to_string(),
+            required: "auth_middleware".to_string(),
+        }];
+
+        for route in &mut file.routes {
+            route.apply_rules(&rules);
+        }
+        assert!(file.routes[0].missing.is_empty());
+        assert_eq!(file.routes[2].missing, rules);
+    }
+
+    #[test]
+    fn test_middleware_references() {
+        let references = MiddlewareScanner::new()
+            .middleware_references(SOURCE)
+            .unwrap();
+
+        let names: Vec<_> = references.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "middleware::from_fn(auth_middleware)",
+                "TimeoutLayer::new(Duration::from_secs(5))"
+            ]
+        );
+        assert_eq!(references[0].range.start, lsp_types::Position::new(1, 14));
+        assert_eq!(references[0].range.end, lsp_types::Position::new(1, 50));
+        assert!(references[1].idents.contains(&"TimeoutLayer".to_string()));
+    }
+
+    #[test]
+    fn test_workspace_middlewares() {
+        let uri = Url::parse("file:///project/src/middleware.rs").unwrap();
+        let content = r#"
+/// 按租户限流
+pub struct TenantLimitLayer;
+
+impl<S> tower::Layer<S> for TenantLimitLayer {
+    type Service = TenantLimit<S>;
+    fn layer(&self, inner: S) -> Self::Service { TenantLimit(inner) }
+}
+
+/// 校验 JWT 令牌
+async fn auth(req: Request, next: Next) -> Response {
+    next.run(req).await
+}
+
+async fn helper(req: Request) {}
+"#;
+        let definitions = MiddlewareScanner::new().workspace_middlewares(&[
+            (uri.clone(), content.to_string()),
+            (uri.clone(), "fn broken(".to_string()),
+        ]);
+
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(definitions[0].name, "TenantLimitLayer");
+        assert_eq!(definitions[0].kind, MiddlewareKind::Layer);
+        assert_eq!(definitions[0].summary.as_deref(), Some("按租户限流"));
+        assert_eq!(definitions[0].usage(), "TenantLimitLayer");
+        assert_eq!(definitions[1].usage(), "middleware::from_fn(auth)");
+        assert_eq!(definitions[1].summary.as_deref(), Some("校验 JWT 令牌"));
+    }
+
+    #[test]
+    fn test_find_middleware_configs() {
+        use crate::scanner::config::ConfigScanner;
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        let content = r#"
+#[derive(Configurable, Deserialize)]
+#[config_prefix = "rate-limit"]
+struct RateLimitConfig {
+    per_second: u32,
+}
+
+#[derive(Configurable, Deserialize)]
+#[config_prefix = "jwt"]
+struct JwtConfig {
+    secret: String,
+}
+
+#[derive(Configurable, Deserialize)]
+#[config_prefix = "timeout"]
+struct TimeoutConfig {
+    secs: u64,
+}
+
+async fn auth(Config(jwt): Config<JwtConfig>, req: Request, next: Next) -> Response {
+    next.run(req).await
+}
+
+#[middlewares(middleware::from_fn(auth), RateLimitLayer::new())]
+#[get("/")]
+async fn index() {}
+"#;
+        fs::write(dir.path().join("src/main.rs"), content).unwrap();
+
+        let scanner = MiddlewareScanner::new();
+        let configurations = ConfigScanner::new()
+            .scan_configurations(dir.path())
+            .unwrap();
+        let references = scanner.middleware_references(content).unwrap();
+
+        let names = |reference: &MiddlewareReference| -> Vec<String> {
+            scanner
+                .find_middleware_configs(dir.path(), None, reference, &configurations)
+                .into_iter()
+                .map(|config| config.name)
+                .collect()
+        };
+        assert_eq!(names(&references[0]), vec!["JwtConfig"]);
+        assert_eq!(names(&references[1]), vec!["RateLimitConfig"]);
+    }
+
+    #[test]
+    fn test_path_matches() {
+        assert!(path_matches("/admin/**", "/admin"));
+        assert!(path_matches("/admin/**", "/admin/users/{id}"));
+        assert!(path_matches("/api/*/users", "/api/v1/users"));
+        assert!(!path_matches("/api/*/users", "/api/v1/v2/users"));
+        assert!(!path_matches("/admin/**", "/public/admin"));
+        assert!(path_matches("/**", "/"));
+    }
+}
@@ -2,20 +2,30 @@
 //!
 //! 扫描项目中的所有插件注册（.add_plugin() 调用）
 
+use crate::core::document::SourceReader;
 use crate::protocol::types::{LocationResponse, PositionResponse, RangeResponse};
 
 use lsp_types::Url;
 use serde::{Deserialize, Serialize};
-use std::fs;
 use std::path::Path;
 
 /// 插件扫描器
-pub struct PluginScanner;
+pub struct PluginScanner {
+    sources: SourceReader,
+}
 
 impl PluginScanner {
     /// 创建新的插件扫描器
     pub fn new() -> Self {
-        Self
+        Self {
+            sources: SourceReader::default(),
+        }
+    }
+
+    /// 使用指定的方式读取源文件（语言服务器中优先读取编辑器中打开的文档）
+    pub fn with_sources(mut self, sources: SourceReader) -> Self {
+        self.sources = sources;
+        self
     }
 
     /// 扫描项目中的所有插件
@@ -27,6 +37,7 @@ impl PluginScanner {
     /// # Returns
     ///
     /// 返回扫描到的所有插件信息
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
     pub fn scan_plugins(&self, project_path: &Path) -> Result<Vec<PluginInfoResponse>, ScanError> {
         let mut plugins = Vec::new();
 
@@ -54,12 +65,13 @@ impl PluginScanner {
         file_path: &Path,
         plugins: &mut Vec<PluginInfoResponse>,
     ) -> Result<(), ScanError> {
-        let content = fs::read_to_string(file_path)?;
+        let content = self.sources.read(file_path)?;
         let file_url = Url::from_file_path(file_path)
             .map_err(|_| ScanError::InvalidProject("Failed to convert path to URL".to_string()))?;
 
         // 简单的文本搜索 .add_plugin() 调用
         // TODO: 使用 syn 进行更精确的 AST 分析
+        let lines = self.sources.mapper(&content);
         for (line_num, line) in content.lines().enumerate() {
             if line.contains(".add_plugin(") {
                 // 提取插件类型名
@@ -77,7 +89,9 @@ impl PluginScanner {
                                 },
                                 end: PositionResponse {
                                     line: line_num as u32,
-                                    character: line.len() as u32,
+                                    character: lines
+                                        .line_column_position(line_num + 1, line.chars().count())
+                                        .character,
                                 },
                             },
                         },
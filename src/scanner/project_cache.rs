@@ -0,0 +1,341 @@
+//! 项目扫描缓存模块
+//!
+//! 诊断流水线的跨文件检查需要项目中的所有路由、任务、配置结构体和配置文件。
+//! 每次按键都遍历磁盘并解析整个项目代价太高，这里按项目缓存文件列表，
+//! 按文件缓存扫描结果（基于 [`crate::core::incremental`] 的查询记忆化）：
+//! 文件列表只在第一次使用时从磁盘建立，之后编辑、保存或磁盘变化只更新对应的文件，
+//! 请求结果时只重新扫描内容发生变化的文件。
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use lsp_types::Url;
+use walkdir::WalkDir;
+
+use crate::analysis::toml::toml_analyzer::{TomlAnalyzer, TomlDocument};
+use crate::core::document::SourceReader;
+use crate::core::incremental::{Inputs, Query, Tracker};
+use crate::scanner::config::{ConfigScanner, ConfigurationStruct};
+use crate::scanner::job::{JobInfoResponse, JobScanner};
+use crate::scanner::route::{RouteInfoResponse, RouteScanner};
+
+/// 项目中的文件
+#[derive(Debug, Default)]
+struct ProjectFiles {
+    /// `src` 目录中的 Rust 文件
+    sources: BTreeSet<Url>,
+    /// `config` 目录中的配置文件
+    config_files: BTreeSet<Url>,
+}
+
+/// 按文件缓存的扫描结果
+type FileQuery<V> = Query<Url, Url, Vec<V>>;
+
+/// 缓存的状态
+struct ScanState {
+    /// 文件内容
+    inputs: Inputs<Url>,
+    /// 已加载的项目（键为项目根目录）
+    projects: HashMap<PathBuf, ProjectFiles>,
+    /// 每个文件中的路由
+    routes: FileQuery<RouteInfoResponse>,
+    /// 每个文件中的任务
+    jobs: FileQuery<JobInfoResponse>,
+    /// 每个文件中的配置结构体
+    configs: FileQuery<ConfigurationStruct>,
+    /// 每个配置文件的解析结果（无法解析时为 `None`）
+    config_docs: Query<Url, Url, Option<TomlDocument>>,
+}
+
+/// 按项目缓存的扫描结果
+pub struct ProjectScanCache {
+    /// 读取文件的方式（优先使用编辑器中打开的内容）
+    sources: SourceReader,
+    state: Mutex<ScanState>,
+}
+
+impl ProjectScanCache {
+    /// 创建空的缓存
+    pub fn new(sources: SourceReader) -> Self {
+        Self {
+            sources,
+            state: Mutex::new(ScanState {
+                inputs: Inputs::new(),
+                projects: HashMap::new(),
+                routes: Query::new(),
+                jobs: Query::new(),
+                configs: Query::new(),
+                config_docs: Query::new(),
+            }),
+        }
+    }
+
+    /// 项目中的所有路由
+    pub fn routes(&self, root: &Path) -> Vec<RouteInfoResponse> {
+        let scanner = RouteScanner::new().with_sources(self.sources.clone());
+        self.collect_sources(
+            root,
+            |state| (&state.inputs, &mut state.routes),
+            |uri, content| {
+                uri.to_file_path()
+                    .map(|path| scanner.scan_file(&path, content.to_string()))
+                    .unwrap_or_default()
+            },
+        )
+    }
+
+    /// 项目中的所有任务
+    pub fn jobs(&self, root: &Path) -> Vec<JobInfoResponse> {
+        let scanner = JobScanner::new().with_sources(self.sources.clone());
+        self.collect_sources(
+            root,
+            |state| (&state.inputs, &mut state.jobs),
+            |uri, content| scanner.scan_sources(&[(uri.clone(), content.to_string())]),
+        )
+    }
+
+    /// 项目中的所有配置结构体
+    pub fn configuration_structs(&self, root: &Path) -> Vec<ConfigurationStruct> {
+        let scanner = ConfigScanner::new().with_sources(self.sources.clone());
+        self.collect_sources(
+            root,
+            |state| (&state.inputs, &mut state.configs),
+            |uri, content| {
+                if content.contains("Configurable") {
+                    scanner.scan_content(uri, content)
+                } else {
+                    Vec::new()
+                }
+            },
+        )
+    }
+
+    /// 项目 `config` 目录中可以解析的配置文件（按文件名排序）
+    pub fn config_docs(&self, root: &Path, analyzer: &TomlAnalyzer) -> Vec<(Url, TomlDocument)> {
+        let mut state = self.state.lock().unwrap();
+        self.load(&mut state, root);
+        let ScanState {
+            inputs,
+            projects,
+            config_docs,
+            ..
+        } = &mut *state;
+        projects[root]
+            .config_files
+            .iter()
+            .filter_map(|uri| {
+                let doc = config_docs.get(inputs, uri, |tracker| {
+                    analyzer.parse(tracker.read(uri)?).ok()
+                });
+                Some((uri.clone(), (*doc).clone()?))
+            })
+            .collect()
+    }
+
+    /// 文件在编辑器或磁盘上发生变化后更新缓存
+    ///
+    /// 只处理已加载项目中的文件；文件无法读取（已删除）时从项目中移除
+    pub fn update(&self, uri: &Url) {
+        let Ok(path) = uri.to_file_path() else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap();
+        let ScanState {
+            inputs, projects, ..
+        } = &mut *state;
+        for (root, files) in projects.iter_mut() {
+            let list = if is_source_file(root, &path) {
+                &mut files.sources
+            } else if is_config_file(root, &path) {
+                &mut files.config_files
+            } else {
+                continue;
+            };
+            match self.sources.read(&path) {
+                Ok(content) => {
+                    inputs.set(uri.clone(), &content);
+                    list.insert(uri.clone());
+                }
+                Err(_) => {
+                    inputs.remove(uri);
+                    list.remove(uri);
+                }
+            }
+        }
+    }
+
+    /// 汇总项目中每个 Rust 文件的查询结果
+    fn collect_sources<V: Clone>(
+        &self,
+        root: &Path,
+        query: fn(&mut ScanState) -> (&Inputs<Url>, &mut FileQuery<V>),
+        scan: impl Fn(&Url, &str) -> Vec<V>,
+    ) -> Vec<V> {
+        let mut state = self.state.lock().unwrap();
+        self.load(&mut state, root);
+        let uris: Vec<Url> = state.projects[root].sources.iter().cloned().collect();
+        let (inputs, query) = query(&mut state);
+        let values: Vec<Arc<Vec<V>>> = uris
+            .iter()
+            .map(|uri| {
+                query.get(inputs, uri, |tracker: &mut Tracker<Url>| {
+                    tracker
+                        .read(uri)
+                        .map(|content| scan(uri, content))
+                        .unwrap_or_default()
+                })
+            })
+            .collect();
+        values
+            .iter()
+            .flat_map(|items| items.iter().cloned())
+            .collect()
+    }
+
+    /// 第一次使用项目时从磁盘建立文件列表
+    fn load(&self, state: &mut ScanState, root: &Path) {
+        if state.projects.contains_key(root) {
+            return;
+        }
+        let mut files = ProjectFiles::default();
+        for (dir, extension, max_depth, list) in [
+            (root.join("src"), "rs", usize::MAX, &mut files.sources),
+            (root.join("config"), "toml", 1, &mut files.config_files),
+        ] {
+            for entry in WalkDir::new(dir)
+                .max_depth(max_depth)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().extension().is_some_and(|ext| ext == extension))
+            {
+                let (Ok(uri), Ok(content)) = (
+                    Url::from_file_path(entry.path()),
+                    self.sources.read(entry.path()),
+                ) else {
+                    continue;
+                };
+                state.inputs.set(uri.clone(), &content);
+                list.insert(uri);
+            }
+        }
+        state.projects.insert(root.to_path_buf(), files);
+    }
+}
+
+impl std::fmt::Debug for ProjectScanCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("ProjectScanCache")
+            .field("projects", &state.projects.len())
+            .finish()
+    }
+}
+
+/// 是否为项目 `src` 目录中的 Rust 文件
+fn is_source_file(root: &Path, path: &Path) -> bool {
+    path.starts_with(root.join("src")) && path.extension().is_some_and(|ext| ext == "rs")
+}
+
+/// 是否为项目 `config` 目录中的配置文件
+fn is_config_file(root: &Path, path: &Path) -> bool {
+    path.parent() == Some(&root.join("config")) && path.extension().is_some_and(|ext| ext == "toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::document::DocumentManager;
+    use crate::schema::SchemaProvider;
+    use std::fs;
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::create_dir_all(dir.path().join("config")).unwrap();
+        fs::write(
+            dir.path().join("src/main.rs"),
+            "#[get(\"/users\")]\nasync fn list_users() {}\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("config/app.toml"), "[web]\nport = 8080\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_scans_project_once_and_updates_changed_files() {
+        let dir = project();
+        let documents = Arc::new(DocumentManager::new());
+        let cache = ProjectScanCache::new(SourceReader::overlay(Arc::clone(&documents)));
+
+        let routes = cache.routes(dir.path());
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].handler, "list_users");
+
+        // 未通知的磁盘变化不会触发重新扫描
+        let main = dir.path().join("src/main.rs");
+        fs::write(&main, "#[post(\"/users\")]\nasync fn create_user() {}\n").unwrap();
+        assert_eq!(cache.routes(dir.path())[0].handler, "list_users");
+
+        // 编辑器中的修改只重新扫描修改的文件
+        let uri = Url::from_file_path(&main).unwrap();
+        documents.open(
+            uri.clone(),
+            1,
+            "#[get(\"/users\")]\nasync fn all_users() {}\n".to_string(),
+            "rust".to_string(),
+        );
+        cache.update(&uri);
+        assert_eq!(cache.routes(dir.path())[0].handler, "all_users");
+
+        // 新建的文件加入项目，删除的文件从项目中移除
+        let other = dir.path().join("src/admin.rs");
+        fs::write(&other, "#[get(\"/admin\")]\nasync fn admin() {}\n").unwrap();
+        let other_uri = Url::from_file_path(&other).unwrap();
+        cache.update(&other_uri);
+        assert_eq!(cache.routes(dir.path()).len(), 2);
+        fs::remove_file(&other).unwrap();
+        cache.update(&other_uri);
+        assert_eq!(cache.routes(dir.path()).len(), 1);
+    }
+
+    #[test]
+    fn test_jobs() {
+        let dir = project();
+        let cache = ProjectScanCache::new(SourceReader::default());
+        assert!(cache.jobs(dir.path()).is_empty());
+
+        let jobs = dir.path().join("src/jobs.rs");
+        fs::write(&jobs, "#[cron(\"0 0 * * * *\")]\nasync fn cleanup() {}\n").unwrap();
+        cache.update(&Url::from_file_path(&jobs).unwrap());
+        let found = cache.jobs(dir.path());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "cleanup");
+    }
+
+    #[test]
+    fn test_configuration_structs_and_config_docs() {
+        let dir = project();
+        let cache = ProjectScanCache::new(SourceReader::default());
+        let analyzer = TomlAnalyzer::new(SchemaProvider::new());
+
+        let config = dir.path().join("src/config.rs");
+        fs::write(
+            &config,
+            "#[derive(Configurable, Deserialize)]\n#[config_prefix = \"web\"]\nstruct WebConfig {\n    port: u16,\n}\n",
+        )
+        .unwrap();
+        cache.update(&Url::from_file_path(&config).unwrap());
+        assert_eq!(cache.configuration_structs(dir.path()).len(), 1);
+
+        let docs = cache.config_docs(dir.path(), &analyzer);
+        assert_eq!(docs.len(), 1);
+        assert!(docs[0].1.config_sections.contains_key("web"));
+
+        // 无法解析的配置文件被跳过
+        let app = dir.path().join("config/app.toml");
+        fs::write(&app, "[web\n").unwrap();
+        cache.update(&Url::from_file_path(&app).unwrap());
+        assert!(cache.config_docs(dir.path(), &analyzer).is_empty());
+    }
+}
@@ -0,0 +1,499 @@
+//! 请求示例生成模块
+//!
+//! 为指定的路由生成可直接运行的请求示例（curl 命令、HTTPie 命令和 reqwest 代码片段）：
+//! - 路径参数替换为示例值：`Path<T>` 中声明为整数的参数使用 `1`，其他参数使用参数名
+//! - `Query<T>` 结构体中的必填字段拼接为查询字符串
+//! - `Json<T>` 结构体的字段（使用 serde 序列化后的名称）生成 JSON 请求体，
+//!   嵌套的结构体在项目中定义时同样展开
+
+use crate::analysis::rust::handler_analyzer::{
+    ExtractorKind, HandlerAnalyzer, HandlerInfo, StructInfo,
+};
+use crate::analysis::rust::route_path::PathSegment;
+use crate::core::document::SourceReader;
+use crate::scanner::middleware::rust_files;
+use crate::scanner::route::{PathPattern, ScanError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 请求体中嵌套结构体的最大展开深度
+const MAX_DEPTH: usize = 3;
+
+/// 整数类型（路径参数示例值为 `1`）
+const INTEGER_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// spring/requestSample 请求参数
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestSampleRequest {
+    /// 应用路径
+    pub app_path: String,
+    /// HTTP 方法（不区分大小写）
+    pub method: String,
+    /// 路由路径模式（与 spring/routes 返回的 `path` 相同）
+    pub path: String,
+    /// 服务地址，默认使用 `config/app.toml` 中 `[web]` 的端口（`http://localhost:8080`）
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+/// 路由的请求示例
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestSample {
+    /// HTTP 方法（大写）
+    pub method: String,
+    /// 替换路径参数后的完整 URL
+    pub url: String,
+    /// JSON 请求体（处理器没有 `Json<T>` 参数时为 `None`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// curl 命令
+    pub curl: String,
+    /// HTTPie 命令
+    pub httpie: String,
+    /// reqwest 代码片段
+    pub reqwest: String,
+}
+
+/// 请求体中的字段
+struct BodyField {
+    /// 序列化后的字段名
+    name: String,
+    /// 字段值的 JSON 文本
+    json: String,
+    /// 字段值是否为字符串（HTTPie 中使用 `=`，否则使用 `:=`）
+    is_string: bool,
+}
+
+/// 请求示例生成器
+#[derive(Debug, Clone, Default)]
+pub struct RequestSampleGenerator {
+    handler_analyzer: HandlerAnalyzer,
+    sources: SourceReader,
+}
+
+impl RequestSampleGenerator {
+    /// 创建新的请求示例生成器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的方式读取源文件（语言服务器中优先读取编辑器中打开的文档）
+    pub fn with_sources(mut self, sources: SourceReader) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// 为项目中的路由生成请求示例
+    ///
+    /// 项目中没有匹配方法和路径的路由处理器时返回 `Ok(None)`
+    pub fn generate(
+        &self,
+        project_path: &Path,
+        method: &str,
+        path: &str,
+        base_url: &str,
+    ) -> Result<Option<RequestSample>, ScanError> {
+        let method = method.to_uppercase();
+        let mut handler = None;
+        let mut structs = HashMap::new();
+
+        for (_, content) in rust_files(project_path, &self.sources)? {
+            let Ok(file) = self.handler_analyzer.analyze(&content) else {
+                continue;
+            };
+            if handler.is_none() {
+                handler = file.handlers.into_iter().find(|handler| {
+                    handler
+                        .routes
+                        .iter()
+                        .any(|route| route.path == path && route.methods.contains(&method))
+                });
+            }
+            structs.extend(file.structs);
+        }
+
+        Ok(handler.map(|handler| self.sample(&handler, &method, path, base_url, &structs)))
+    }
+
+    /// 根据处理器的提取器生成请求示例
+    pub fn sample(
+        &self,
+        handler: &HandlerInfo,
+        method: &str,
+        path: &str,
+        base_url: &str,
+        structs: &HashMap<String, StructInfo>,
+    ) -> RequestSample {
+        let inner_type = |kind: ExtractorKind| {
+            handler
+                .params
+                .iter()
+                .find(|param| param.kind == kind)
+                .and_then(|param| param.inner_type.as_deref())
+        };
+
+        let mut url = format!(
+            "{}{}",
+            base_url.trim_end_matches('/'),
+            sample_path(path, inner_type(ExtractorKind::Path), structs)
+        );
+        if let Some(query) = inner_type(ExtractorKind::Query)
+            .and_then(|ty| structs.get(base_name(ty)))
+            .map(sample_query)
+            .filter(|query| !query.is_empty())
+        {
+            url.push('?');
+            url.push_str(&query);
+        }
+
+        let body = inner_type(ExtractorKind::Json).map(|ty| body_fields(ty, structs));
+        let body_json = body.as_ref().map(|fields| object_json(fields));
+
+        RequestSample {
+            method: method.to_string(),
+            curl: curl_command(method, &url, body_json.as_deref()),
+            httpie: httpie_command(method, &url, body.as_deref()),
+            reqwest: reqwest_snippet(method, &url, body_json.as_deref()),
+            url,
+            body: body_json,
+        }
+    }
+}
+
+/// 替换路径参数后的路径
+fn sample_path(
+    path: &str,
+    path_type: Option<&str>,
+    structs: &HashMap<String, StructInfo>,
+) -> String {
+    let pattern = PathPattern::parse(path);
+    let route_path = pattern.route_path();
+    let names = route_path.param_names();
+    let param_type = |name: &str| param_type(name, &names, path_type, structs);
+
+    let segments: Vec<String> = route_path
+        .segments
+        .iter()
+        .map(|segment| match segment {
+            PathSegment::Literal(literal) => literal.clone(),
+            PathSegment::Param { name, .. } | PathSegment::Wildcard(name) => {
+                path_value(name, param_type(name).as_deref())
+            }
+            PathSegment::Mixed(raw) => {
+                substitute_params(raw, |name| path_value(name, param_type(name).as_deref()))
+            }
+        })
+        .collect();
+
+    let mut sample = format!("/{}", segments.join("/"));
+    if pattern.has_trailing_slash() {
+        sample.push('/');
+    }
+    sample
+}
+
+/// 路径参数的类型
+///
+/// `Path<T>` 的类型参数可以是单个类型、元组（按参数顺序对应）或结构体（按字段名对应）
+fn param_type(
+    name: &str,
+    names: &[&str],
+    path_type: Option<&str>,
+    structs: &HashMap<String, StructInfo>,
+) -> Option<String> {
+    let path_type = path_type?.trim();
+    if let Some(elements) = path_type
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let index = names.iter().position(|param| *param == name)?;
+        return elements
+            .split(',')
+            .map(str::trim)
+            .filter(|element| !element.is_empty())
+            .nth(index)
+            .map(str::to_string);
+    }
+    if let Some(info) = structs.get(base_name(path_type)) {
+        return info
+            .fields
+            .iter()
+            .find(|field| field.serialized_name == name)
+            .map(|field| field.type_name.clone());
+    }
+    (names.len() == 1).then(|| path_type.to_string())
+}
+
+/// 替换路径段中的 `{name}` 参数
+fn substitute_params(raw: &str, value: impl Fn(&str) -> String) -> String {
+    let mut result = String::new();
+    let mut rest = raw;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let param = &rest[start + 1..start + end];
+        let name = param
+            .split(':')
+            .next()
+            .unwrap_or(param)
+            .trim_start_matches('*');
+        result.push_str(&value(name));
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 路径参数的示例值
+fn path_value(name: &str, type_name: Option<&str>) -> String {
+    match type_name.map(base_name) {
+        Some(ty) if INTEGER_TYPES.contains(&ty) => "1".to_string(),
+        Some("f32" | "f64") => "1.0".to_string(),
+        Some("bool") => "true".to_string(),
+        Some("Uuid") => "00000000-0000-0000-0000-000000000000".to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// `Query<T>` 结构体必填字段组成的查询字符串
+fn sample_query(info: &StructInfo) -> String {
+    info.fields
+        .iter()
+        .filter(|field| !field.optional)
+        .map(|field| {
+            format!(
+                "{}={}",
+                field.serialized_name,
+                path_value(&field.serialized_name, Some(&field.type_name))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// 请求体类型的字段
+///
+/// 类型不是项目中定义的结构体时没有字段（请求体为 `{}`）
+fn body_fields(type_name: &str, structs: &HashMap<String, StructInfo>) -> Vec<BodyField> {
+    let Some(info) = structs.get(base_name(type_name)) else {
+        return Vec::new();
+    };
+    info.fields
+        .iter()
+        .map(|field| {
+            let json = sample_json(&field.type_name, structs, 1);
+            BodyField {
+                name: field.serialized_name.clone(),
+                is_string: json.starts_with('"'),
+                json,
+            }
+        })
+        .collect()
+}
+
+/// 类型的 JSON 示例值
+fn sample_json(type_name: &str, structs: &HashMap<String, StructInfo>, depth: usize) -> String {
+    match base_name(type_name) {
+        ty if INTEGER_TYPES.contains(&ty) => "0".to_string(),
+        "f32" | "f64" => "0.0".to_string(),
+        "bool" => "false".to_string(),
+        "String" | "str" | "char" => "\"string\"".to_string(),
+        "Uuid" => "\"00000000-0000-0000-0000-000000000000\"".to_string(),
+        "Option" => "null".to_string(),
+        "Vec" | "HashSet" | "BTreeSet" | "VecDeque" => "[]".to_string(),
+        "HashMap" | "BTreeMap" | "Value" => "{}".to_string(),
+        name => match structs.get(name) {
+            Some(info) if depth < MAX_DEPTH => {
+                let fields: Vec<BodyField> = info
+                    .fields
+                    .iter()
+                    .map(|field| BodyField {
+                        name: field.serialized_name.clone(),
+                        json: sample_json(&field.type_name, structs, depth + 1),
+                        is_string: false,
+                    })
+                    .collect();
+                object_json(&fields)
+            }
+            Some(_) => "{}".to_string(),
+            None => "null".to_string(),
+        },
+    }
+}
+
+/// 字段组成的 JSON 对象文本（保持字段声明顺序）
+fn object_json(fields: &[BodyField]) -> String {
+    let members: Vec<String> = fields
+        .iter()
+        .map(|field| format!("\"{}\": {}", field.name, field.json))
+        .collect();
+    format!("{{{}}}", members.join(", "))
+}
+
+/// 类型的基础名称：去掉引用、模块路径和泛型参数
+fn base_name(type_name: &str) -> &str {
+    let trimmed = type_name.trim().trim_start_matches('&').trim();
+    let without_generics = trimmed.split('<').next().unwrap_or(trimmed);
+    without_generics
+        .rsplit("::")
+        .next()
+        .unwrap_or(without_generics)
+        .trim()
+}
+
+/// 单引号包裹的 shell 参数
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn curl_command(method: &str, url: &str, body: Option<&str>) -> String {
+    let mut command = String::from("curl");
+    if method != "GET" || body.is_some() {
+        command.push_str(&format!(" -X {}", method));
+    }
+    command.push_str(&format!(" {}", shell_quote(url)));
+    if let Some(body) = body {
+        command.push_str(" -H 'Content-Type: application/json'");
+        command.push_str(&format!(" -d {}", shell_quote(body)));
+    }
+    command
+}
+
+fn httpie_command(method: &str, url: &str, body: Option<&[BodyField]>) -> String {
+    let mut command = format!("http {} {}", method, shell_quote(url));
+    for field in body.unwrap_or_default() {
+        let item = if field.is_string {
+            format!("{}={}", field.name, field.json.trim_matches('"'))
+        } else {
+            format!("{}:={}", field.name, field.json)
+        };
+        command.push(' ');
+        command.push_str(&shell_quote(&item));
+    }
+    command
+}
+
+fn reqwest_snippet(method: &str, url: &str, body: Option<&str>) -> String {
+    let request = match method {
+        "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" => {
+            format!(".{}({:?})", method.to_lowercase(), url)
+        }
+        other => format!(".request(reqwest::Method::{}, {:?})", other, url),
+    };
+
+    let mut snippet = format!("let response = reqwest::Client::new()\n    {}\n", request);
+    if let Some(body) = body {
+        snippet.push_str(&format!("    .json(&serde_json::json!({}))\n", body));
+    }
+    snippet.push_str("    .send()\n    .await?;");
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateOrder {
+    customer_name: String,
+    quantity: u32,
+    note: Option<String>,
+    address: Address,
+}
+
+#[derive(Deserialize)]
+struct Address {
+    city: String,
+}
+
+#[derive(Deserialize)]
+struct Paging {
+    page: u32,
+    size: Option<u32>,
+}
+
+#[post("/shops/{shop}/orders/{id}")]
+async fn create_order(
+    Path((shop, id)): Path<(String, u64)>,
+    Query(paging): Query<Paging>,
+    Json(order): Json<CreateOrder>,
+) -> Json<Order> {}
+
+#[get("/files/{name}.json")]
+async fn file(Path(name): Path<String>) {}
+"#;
+
+    fn generate(method: &str, path: &str) -> RequestSample {
+        let file = HandlerAnalyzer::new().analyze(SOURCE).unwrap();
+        let handler = file
+            .handlers
+            .iter()
+            .find(|handler| handler.routes.iter().any(|route| route.path == path))
+            .unwrap();
+        RequestSampleGenerator::new().sample(
+            handler,
+            method,
+            path,
+            "http://localhost:8080/",
+            &file.structs,
+        )
+    }
+
+    #[test]
+    fn test_request_sample() {
+        let sample = generate("POST", "/shops/{shop}/orders/{id}");
+
+        assert_eq!(
+            sample.url,
+            "http://localhost:8080/shops/shop/orders/1?page=1"
+        );
+        assert_eq!(
+            sample.body.as_deref(),
+            Some(
+                r#"{"customerName": "string", "quantity": 0, "note": null, "address": {"city": "string"}}"#
+            )
+        );
+        assert_eq!(
+            sample.curl,
+            format!(
+                "curl -X POST '{}' -H 'Content-Type: application/json' -d '{}'",
+                sample.url,
+                sample.body.as_deref().unwrap()
+            )
+        );
+        assert_eq!(
+            sample.httpie,
+            format!(
+                "http POST '{}' 'customerName=string' 'quantity:=0' 'note:=null' 'address:={{\"city\": \"string\"}}'",
+                sample.url
+            )
+        );
+        assert!(sample
+            .reqwest
+            .contains(".post(\"http://localhost:8080/shops/shop/orders/1?page=1\")"));
+        assert!(sample.reqwest.contains(".json(&serde_json::json!({"));
+    }
+
+    #[test]
+    fn test_request_sample_without_body() {
+        let sample = generate("GET", "/files/{name}.json");
+
+        assert_eq!(sample.url, "http://localhost:8080/files/name.json");
+        assert!(sample.body.is_none());
+        assert_eq!(sample.curl, "curl 'http://localhost:8080/files/name.json'");
+        assert_eq!(
+            sample.httpie,
+            "http GET 'http://localhost:8080/files/name.json'"
+        );
+        assert!(!sample.reqwest.contains(".json("));
+    }
+}
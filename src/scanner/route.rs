@@ -1,18 +1,27 @@
 //! 路由扫描器模块
 //!
-//! 扫描项目中的所有路由定义
+//! 扫描项目中的所有路由定义，并提供各模块共用的路由路径模式 [`PathPattern`]
+//! （规范化、比较、冲突检测和匹配具体 URL）
 
+use crate::analysis::rust::instrument::InstrumentInfo;
 use crate::analysis::rust::macro_analyzer::{MacroAnalyzer, SpringMacro};
+use crate::analysis::rust::module_path::qualified_name;
+use crate::analysis::rust::route_path::{
+    constraint_matches, parse_route_path, PathSegment, RoutePath,
+};
+use crate::analysis::rust::router_calls::router_routes;
+use crate::core::document::SourceReader;
 use crate::protocol::types::{LocationResponse, PositionResponse, RangeResponse};
 use lsp_types::Url;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::convert::Infallible;
 use std::path::Path;
 use walkdir::WalkDir;
 
 /// 路由扫描器
 pub struct RouteScanner {
     macro_analyzer: MacroAnalyzer,
+    sources: SourceReader,
 }
 
 impl RouteScanner {
@@ -20,9 +29,17 @@ impl RouteScanner {
     pub fn new() -> Self {
         Self {
             macro_analyzer: MacroAnalyzer::new(),
+            sources: SourceReader::default(),
         }
     }
 
+    /// 使用指定的方式读取源文件（语言服务器中优先读取编辑器中打开的文档）
+    pub fn with_sources(mut self, sources: SourceReader) -> Self {
+        self.macro_analyzer = MacroAnalyzer::new().with_encoding(sources.encoding());
+        self.sources = sources;
+        self
+    }
+
     /// 扫描项目中的所有路由
     ///
     /// # Arguments
@@ -32,9 +49,19 @@ impl RouteScanner {
     /// # Returns
     ///
     /// 返回扫描到的所有路由信息
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
     pub fn scan_routes(&self, project_path: &Path) -> Result<Vec<RouteInfoResponse>, ScanError> {
-        let mut routes = Vec::new();
+        Ok(self.routes(project_path)?.collect())
+    }
 
+    /// 逐个文件产出项目中的路由
+    ///
+    /// 路由在遍历文件的过程中产出，调用方不必等整个项目扫描完成（如分块发送部分结果）；
+    /// 项目没有 `src` 目录时返回错误
+    pub fn routes<'a>(
+        &'a self,
+        project_path: &Path,
+    ) -> Result<impl Iterator<Item = RouteInfoResponse> + 'a, ScanError> {
         // 查找 src 目录
         let src_path = project_path.join("src");
         if !src_path.exists() {
@@ -43,79 +70,133 @@ impl RouteScanner {
             ));
         }
 
-        // 遍历所有 Rust 文件
-        for entry in WalkDir::new(&src_path)
+        Ok(self.routes_in(&src_path))
+    }
+
+    /// 扫描目录下所有 Rust 文件中的路由
+    ///
+    /// 用于 `src` 目录和构建脚本生成的代码目录
+    pub fn scan_dir(&self, dir: &Path) -> Vec<RouteInfoResponse> {
+        self.routes_in(dir).collect()
+    }
+
+    /// 逐个文件产出目录下所有 Rust 文件中的路由
+    fn routes_in<'a>(&'a self, dir: &Path) -> impl Iterator<Item = RouteInfoResponse> + 'a {
+        WalkDir::new(dir)
             .into_iter()
             .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().map_or(false, |ext| ext == "rs"))
-        {
-            let file_path = entry.path();
-
-            // 读取文件内容
-            let content = match fs::read_to_string(file_path) {
-                Ok(content) => content,
-                Err(e) => {
-                    tracing::warn!("Failed to read file {:?}: {}", file_path, e);
-                    continue;
-                }
-            };
-
-            // 解析文件
-            let file_url = match Url::from_file_path(file_path) {
-                Ok(url) => url,
-                Err(_) => {
-                    tracing::warn!("Failed to convert path to URL: {:?}", file_path);
-                    continue;
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+            .flat_map(move |entry| {
+                let file_path = entry.path();
+
+                // 读取文件内容
+                match self.sources.read(file_path) {
+                    Ok(content) => self.scan_file(file_path, content),
+                    Err(e) => {
+                        tracing::warn!("Failed to read file {:?}: {}", file_path, e);
+                        Vec::new()
+                    }
                 }
-            };
+            })
+    }
 
-            let rust_doc = match self.macro_analyzer.parse(file_url.clone(), content) {
-                Ok(doc) => doc,
-                Err(e) => {
-                    tracing::warn!("Failed to parse file {:?}: {}", file_path, e);
-                    continue;
-                }
-            };
-
-            // 提取宏信息
-            let rust_doc = match self.macro_analyzer.extract_macros(rust_doc) {
-                Ok(doc) => doc,
-                Err(e) => {
-                    tracing::warn!("Failed to extract macros from {:?}: {}", file_path, e);
-                    continue;
-                }
-            };
-
-            // 提取路由信息
-            for spring_macro in &rust_doc.macros {
-                if let SpringMacro::Route(route_macro) = spring_macro {
-                    // 为每个 HTTP 方法创建独立的路由条目
-                    for method in &route_macro.methods {
-                        routes.push(RouteInfoResponse {
-                            method: method.as_str().to_string(),
-                            path: route_macro.path.clone(),
-                            handler: route_macro.handler_name.clone(),
-                            is_openapi: route_macro.is_openapi,
-                            location: LocationResponse {
-                                uri: file_url.to_string(),
-                                range: RangeResponse {
-                                    start: PositionResponse {
-                                        line: route_macro.range.start.line,
-                                        character: route_macro.range.start.character,
-                                    },
-                                    end: PositionResponse {
-                                        line: route_macro.range.end.line,
-                                        character: route_macro.range.end.character,
-                                    },
+    /// 扫描单个 Rust 文件内容中的路由（路由宏和 `.route(...)` 注册）
+    ///
+    /// `file_path` 用于生成位置和处理器的完整路径；用于已打开但未保存的文档
+    pub fn scan_file(&self, file_path: &Path, content: String) -> Vec<RouteInfoResponse> {
+        let mut routes = Vec::new();
+
+        // 解析文件
+        let file_url = match Url::from_file_path(file_path) {
+            Ok(url) => url,
+            Err(_) => {
+                tracing::warn!("Failed to convert path to URL: {:?}", file_path);
+                return routes;
+            }
+        };
+
+        let rust_doc = match self.macro_analyzer.parse(file_url.clone(), content) {
+            Ok(doc) => doc,
+            Err(e) => {
+                tracing::warn!("Failed to parse file {:?}: {}", file_path, e);
+                return routes;
+            }
+        };
+
+        // 提取宏信息
+        let rust_doc = match self.macro_analyzer.extract_macros(rust_doc) {
+            Ok(doc) => doc,
+            Err(e) => {
+                tracing::warn!("Failed to extract macros from {:?}: {}", file_path, e);
+                return routes;
+            }
+        };
+
+        // 提取路由信息
+        for spring_macro in &rust_doc.macros {
+            if let SpringMacro::Route(route_macro) = spring_macro {
+                // 为每个 HTTP 方法创建独立的路由条目
+                for method in &route_macro.methods {
+                    routes.push(RouteInfoResponse {
+                        method: method.as_str().to_string(),
+                        path: route_macro.path.clone(),
+                        handler: route_macro.handler_name.clone(),
+                        handler_path: qualified_name(file_path, &route_macro.handler_name),
+                        is_openapi: route_macro.is_openapi,
+                        doc: route_macro.doc.clone(),
+                        deprecated: route_macro.deprecated,
+                        stub: route_macro.stub,
+                        cfg: route_macro.cfg.clone(),
+                        instrument: route_macro.instrument.clone(),
+                        confidence: RouteConfidence::High,
+                        location: LocationResponse {
+                            uri: file_url.to_string(),
+                            range: RangeResponse {
+                                start: PositionResponse {
+                                    line: route_macro.range.start.line,
+                                    character: route_macro.range.start.character,
+                                },
+                                end: PositionResponse {
+                                    line: route_macro.range.end.line,
+                                    character: route_macro.range.end.character,
                                 },
                             },
-                        });
-                    }
+                        },
+                    });
                 }
             }
         }
 
-        Ok(routes)
+        // 通过 `.route("路径", get(处理器))` 注册的路由，位置为路径字面量
+        for route in router_routes(&self.sources.mapper(&rust_doc.content)) {
+            routes.push(RouteInfoResponse {
+                handler_path: route.qualified_handler(file_path),
+                method: route.method,
+                path: route.path,
+                handler: route.handler,
+                is_openapi: false,
+                doc: None,
+                deprecated: false,
+                stub: false,
+                cfg: None,
+                instrument: None,
+                confidence: RouteConfidence::Low,
+                location: LocationResponse {
+                    uri: file_url.to_string(),
+                    range: RangeResponse {
+                        start: PositionResponse {
+                            line: route.path_range.start.line,
+                            character: route.path_range.start.character,
+                        },
+                        end: PositionResponse {
+                            line: route.path_range.end.line,
+                            character: route.path_range.end.character,
+                        },
+                    },
+                },
+            });
+        }
+        routes
     }
 }
 
@@ -134,26 +215,158 @@ pub struct RouteInfoResponse {
     pub path: String,
     /// 处理器函数名
     pub handler: String,
+    /// 处理器的完整路径（如 `crate::api::users::list`），文件不属于 crate 的模块树时为 `None`
+    #[serde(
+        rename = "handlerPath",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub handler_path: Option<String>,
     /// 是否为 OpenAPI 路由
     #[serde(rename = "isOpenapi")]
     pub is_openapi: bool,
-    /// 源代码位置
+    /// 处理器的文档注释（用于路由文档和 OpenAPI 描述）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// 处理器是否标注了 `#[deprecated]`（客户端以删除线显示）
+    #[serde(default)]
+    pub deprecated: bool,
+    /// 处理器是否尚未实现（函数体只有 `todo!()`、`unimplemented!()` 等占位代码）
+    #[serde(default)]
+    pub stub: bool,
+    /// 路由通过 `#[cfg_attr(条件, ...)]` 应用时的启用条件（如 `feature = "web"`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg: Option<String>,
+    /// 处理器函数上 `#[instrument]` 生成的 span 信息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instrument: Option<InstrumentInfo>,
+    /// 路由信息的可信度
+    #[serde(default)]
+    pub confidence: RouteConfidence,
+    /// 源代码位置（路由宏，或 `.route(...)` 注册的路由的路径字面量）
     pub location: LocationResponse,
 }
 
+/// 路由信息的可信度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteConfidence {
+    /// 通过路由宏声明，路径和处理器都是确定的
+    #[default]
+    High,
+    /// 通过 `Router::new().route(...)` 注册，路径可能被 `nest` 等加上前缀，处理器路径按注册所在的模块推断
+    Low,
+}
+
 /// spring/routes 请求参数
+///
+/// 大型项目中路由很多，编辑器的树视图可以按方法过滤、排序并分页请求路由列表
 #[derive(Debug, Deserialize)]
 pub struct RoutesRequest {
     /// 应用路径
     #[serde(rename = "appPath")]
     pub app_path: String,
+    /// 部分结果令牌（提供时路由列表通过 `$/progress` 分块发送）
+    #[serde(rename = "partialResultToken", default)]
+    pub partial_result_token: Option<lsp_types::ProgressToken>,
+    /// 排序方式，未指定时按扫描顺序
+    #[serde(default)]
+    pub sort: Option<RouteSort>,
+    /// 只返回这些 HTTP 方法的路由（不区分大小写），为空时不过滤
+    #[serde(default)]
+    pub methods: Vec<String>,
+    /// 跳过的路由数（在过滤和排序之后）
+    #[serde(default)]
+    pub offset: usize,
+    /// 最多返回的路由数，未指定时返回所有路由
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// 路由列表的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteSort {
+    /// 按路径，路径相同时按方法
+    Path,
+    /// 按方法，方法相同时按路径
+    Method,
+    /// 按所在文件和行号
+    File,
+}
+
+impl RoutesRequest {
+    /// 按请求参数过滤、排序和分页路由列表
+    pub fn page(&self, routes: impl IntoIterator<Item = RouteInfoResponse>) -> RoutesResponse {
+        let mut page = Vec::new();
+        let Ok(total) = self.for_each_in_page(routes, |route| {
+            page.push(route);
+            Ok::<_, Infallible>(())
+        });
+        RoutesResponse {
+            routes: page,
+            total,
+        }
+    }
+
+    /// 按请求参数过滤、排序和分页路由，当前页的路由依次交给 `emit`，返回过滤后、分页前的路由总数
+    ///
+    /// 未指定排序方式时边产出边处理，不保留整个路由列表；指定排序方式时需要先收集所有路由
+    pub fn for_each_in_page<E>(
+        &self,
+        routes: impl IntoIterator<Item = RouteInfoResponse>,
+        mut emit: impl FnMut(RouteInfoResponse) -> Result<(), E>,
+    ) -> Result<usize, E> {
+        let mut filtered = routes.into_iter().filter(|route| {
+            self.methods.is_empty()
+                || self
+                    .methods
+                    .iter()
+                    .any(|method| method.eq_ignore_ascii_case(&route.method))
+        });
+        let mut sorted;
+        let routes: &mut dyn Iterator<Item = RouteInfoResponse> = match self.sort {
+            Some(sort) => {
+                let mut routes: Vec<_> = filtered.collect();
+                match sort {
+                    RouteSort::Path => routes.sort_by(|a, b| {
+                        (a.path.as_str(), a.method.as_str())
+                            .cmp(&(b.path.as_str(), b.method.as_str()))
+                    }),
+                    RouteSort::Method => routes.sort_by(|a, b| {
+                        (a.method.as_str(), a.path.as_str())
+                            .cmp(&(b.method.as_str(), b.path.as_str()))
+                    }),
+                    RouteSort::File => routes.sort_by(|a, b| {
+                        (a.location.uri.as_str(), a.location.range.start.line)
+                            .cmp(&(b.location.uri.as_str(), b.location.range.start.line))
+                    }),
+                }
+                sorted = routes.into_iter();
+                &mut sorted
+            }
+            None => &mut filtered,
+        };
+
+        let limit = self.limit.unwrap_or(usize::MAX);
+        let mut total = 0;
+        for route in routes {
+            if total >= self.offset && total - self.offset < limit {
+                emit(route)?;
+            }
+            total += 1;
+        }
+        Ok(total)
+    }
 }
 
 /// spring/routes 响应
 #[derive(Debug, Serialize)]
 pub struct RoutesResponse {
-    /// 路由列表
+    /// 路由列表（当前页）
     pub routes: Vec<RouteInfoResponse>,
+    /// 过滤后、分页前的路由总数
+    pub total: usize,
 }
 
 /// 扫描错误
@@ -186,6 +399,216 @@ mod tests {
         // 验证默认扫描器创建成功
         assert!(true);
     }
+
+    #[test]
+    fn test_scan_router_registrations() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/main.rs"),
+            "#[get(\"/health\")]\nasync fn health() {}\n\nfn router() -> Router {\n    Router::new().route(\"/users\", get(handlers::list_users))\n}\n",
+        )
+        .unwrap();
+
+        let routes = RouteScanner::new().scan_routes(dir.path()).unwrap();
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].confidence, RouteConfidence::High);
+        let router_route = &routes[1];
+        assert_eq!(router_route.path, "/users");
+        assert_eq!(router_route.method, "GET");
+        assert_eq!(router_route.confidence, RouteConfidence::Low);
+        assert_eq!(
+            router_route.handler_path.as_deref(),
+            Some("crate::handlers::list_users")
+        );
+        assert_eq!(router_route.location.range.start.line, 4);
+    }
+
+    #[test]
+    fn test_routes_request_page() {
+        let source = "#[get(\"/users\")]\nasync fn list() {}\n\n#[post(\"/users\")]\nasync fn create() {}\n\n#[get(\"/orders\")]\nasync fn orders() {}\n\n#[delete(\"/users/{id}\")]\nasync fn remove() {}\n";
+        let routes =
+            RouteScanner::new().scan_file(Path::new("/app/src/main.rs"), source.to_string());
+        let request = |params: serde_json::Value| -> RoutesRequest {
+            let mut request = serde_json::json!({ "appPath": "/app" });
+            request
+                .as_object_mut()
+                .unwrap()
+                .extend(params.as_object().unwrap().clone());
+            serde_json::from_value(request).unwrap()
+        };
+        let summary = |response: &RoutesResponse| -> Vec<String> {
+            response
+                .routes
+                .iter()
+                .map(|route| format!("{} {}", route.method, route.path))
+                .collect()
+        };
+
+        // 默认按扫描顺序返回所有路由
+        let page = request(serde_json::json!({})).page(routes.clone());
+        assert_eq!(page.total, 4);
+        assert_eq!(page.routes.len(), 4);
+
+        let page = request(serde_json::json!({ "sort": "path", "offset": 1, "limit": 2 }))
+            .page(routes.clone());
+        assert_eq!(page.total, 4);
+        assert_eq!(summary(&page), vec!["GET /users", "POST /users"]);
+
+        let page = request(serde_json::json!({ "sort": "method", "methods": ["get", "DELETE"] }))
+            .page(routes.clone());
+        assert_eq!(page.total, 3);
+        assert_eq!(
+            summary(&page),
+            vec!["DELETE /users/{id}", "GET /orders", "GET /users"]
+        );
+
+        // 超出范围的偏移返回空页，总数不变
+        let page =
+            request(serde_json::json!({ "sort": "file", "offset": 10 })).page(routes.clone());
+        assert_eq!(page.total, 4);
+        assert!(page.routes.is_empty());
+
+        // 不排序时每个路由产出后立即交给调用方，不等待后续路由
+        let produced = std::cell::Cell::new(0);
+        let mut emitted = Vec::new();
+        let total = request(serde_json::json!({ "offset": 1, "limit": 2 }))
+            .for_each_in_page(
+                routes
+                    .into_iter()
+                    .inspect(|_| produced.set(produced.get() + 1)),
+                |route| {
+                    emitted.push((produced.get(), route.path));
+                    Ok::<_, ()>(())
+                },
+            )
+            .unwrap();
+        assert_eq!(total, 4);
+        assert_eq!(
+            emitted,
+            vec![(2, "/users".to_string()), (3, "/orders".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_find_routes_by_handler() {
+        let route = |path: &str, handler_path: &str| Route {
+            method: HttpMethod::GET,
+            path: path.to_string(),
+            handler: "list".to_string(),
+            handler_path: Some(handler_path.to_string()),
+            location: Location::new(
+                Url::parse("file:///app/src/main.rs").unwrap(),
+                lsp_types::Range::default(),
+            ),
+        };
+        let mut index = RouteIndex::new();
+        index.add_route(route("/users", "crate::api::users::list"));
+        index.add_route(route("/orders", "crate::api::orders::list"));
+
+        assert_eq!(index.find_routes_by_handler("list").len(), 2);
+        let routes = index.find_routes_by_handler("crate::api::orders::list");
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].path, "/orders");
+        assert!(index.find_routes_by_handler("crate::api::list").is_empty());
+    }
+
+    #[test]
+    fn test_path_pattern_normalize() {
+        let pattern = |path: &str| PathPattern::parse(path);
+
+        assert_eq!(pattern("/").normalized(), "/");
+        assert_eq!(pattern("/static/{*path}").normalized(), "/static/{}");
+        assert_eq!(pattern("/users/:id/").normalized(), "/users/{}");
+        assert_eq!(pattern("/files/{name}.json").normalized(), "/files/{}.json");
+        assert!(pattern("/users/{id}").same_shape(&pattern(r"/users/{user_id:\d+}/")));
+        assert!(!pattern("/users/{id}").same_shape(&pattern("/users/{id}/posts")));
+
+        assert_eq!(PathPattern::trim_trailing_slash("/users//"), "/users");
+        assert_eq!(PathPattern::trim_trailing_slash("/"), "/");
+        assert!(pattern("/users/").has_trailing_slash());
+        assert!(!pattern("/").has_trailing_slash());
+        assert!(pattern("/users/").differs_only_by_trailing_slash(&pattern("/users")));
+        assert!(!pattern("/users").differs_only_by_trailing_slash(&pattern("/users")));
+
+        // 查询字符串不参与匹配
+        assert_eq!(pattern("/search?q={q}").as_str(), "/search");
+        assert!(pattern("/users/{id}").conflicts_with(&pattern("/users/me")));
+        assert!(!pattern(r"/users/{id:\d+}").conflicts_with(&pattern("/users/me")));
+    }
+
+    #[test]
+    fn test_path_pattern_matches() {
+        let matches = |path: &str, url: &str| PathPattern::parse(path).matches(url);
+        let params = |pairs: &[(&str, &str)]| {
+            Some(
+                pairs
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(matches("/users", "/users/"), params(&[]));
+        assert_eq!(
+            matches(
+                "/users/{id}",
+                "http://localhost:8080/users/42?tab=posts#top"
+            ),
+            params(&[("id", "42")])
+        );
+        assert_eq!(matches(r"/users/{id:\d+}", "/users/me"), None);
+        assert_eq!(matches("/users/:id", "/users/7"), params(&[("id", "7")]));
+        assert_eq!(
+            matches("/static/{*path}", "/static/css/app.css"),
+            params(&[("path", "css/app.css")])
+        );
+        assert_eq!(matches("/static/{*path}", "/static"), None);
+        assert_eq!(
+            matches("/files/{name}.json", "/files/report.json"),
+            params(&[("name", "report")])
+        );
+        assert_eq!(matches("/files/{name}.json", "/files/report.xml"), None);
+        assert_eq!(matches("/users/{id}", "/users/42/posts"), None);
+        assert_eq!(matches("/", "https://example.com"), params(&[]));
+    }
+
+    #[test]
+    fn test_match_url() {
+        let route = |method: HttpMethod, path: &str, handler: &str| Route {
+            method,
+            path: path.to_string(),
+            handler: handler.to_string(),
+            handler_path: None,
+            location: Location::new(
+                Url::parse(&format!("file:///app/src/{}.rs", handler)).unwrap(),
+                lsp_types::Range::default(),
+            ),
+        };
+        let mut index = RouteIndex::new();
+        index.add_route(route(HttpMethod::GET, "/users/{id}", "show"));
+        index.add_route(route(HttpMethod::GET, "/users/me", "me"));
+        index.add_route(route(HttpMethod::DELETE, "/users/{id}", "delete"));
+
+        let handlers = |method: Option<HttpMethod>, url: &str| -> Vec<String> {
+            index
+                .match_url(method, url)
+                .into_iter()
+                .map(|route| route.handler.clone())
+                .collect()
+        };
+        assert_eq!(handlers(None, "/users/me"), vec!["me", "delete", "show"]);
+        assert_eq!(
+            handlers(Some(HttpMethod::DELETE), "/users/42"),
+            vec!["delete"]
+        );
+        assert!(handlers(None, "/orders").is_empty());
+
+        let location = RouteNavigator::new()
+            .find_handler_location(&index, "/users/me")
+            .unwrap();
+        assert_eq!(location.uri.path(), "/app/src/me.rs");
+    }
 }
 
 // ============================================================================
@@ -195,6 +618,222 @@ mod tests {
 use lsp_types::Location;
 use std::collections::HashMap;
 
+/// 路由路径模式
+///
+/// 路由路径的解析、规范化、比较和匹配统一通过这个类型完成：
+/// - 末尾斜杠：`/users/` 与 `/users` 只有末尾斜杠不同（[`PathPattern::trim_trailing_slash`]）
+/// - 形状比较：参数统一写作 `{}` 后比较，忽略参数名称、约束和末尾斜杠（[`PathPattern::normalized`]）
+/// - 冲突检测：两个模式可能匹配同一个请求路径（[`PathPattern::conflicts_with`]）
+/// - 匹配具体 URL：如 `/users/42?tab=posts` 匹配 `/users/{id}`（[`PathPattern::matches`]）
+///
+/// 查询字符串不参与路由匹配，解析时会被忽略；旧版 axum 的 `:id` 形式也视为路径参数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPattern {
+    /// 原始路径（不含查询字符串）
+    raw: String,
+    /// 解析后的路径段
+    path: RoutePath,
+}
+
+impl PathPattern {
+    /// 解析路由路径，格式错误的部分按字面量处理（格式检查见 [`parse_route_path`]）
+    pub fn parse(path: &str) -> Self {
+        let raw = path.split('?').next().unwrap_or_default();
+        Self {
+            raw: raw.to_string(),
+            path: parse_route_path(raw).0,
+        }
+    }
+
+    /// 原始路径
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// 解析后的路径段
+    pub fn route_path(&self) -> &RoutePath {
+        &self.path
+    }
+
+    /// 路径是否以斜杠结尾（根路径 `/` 除外）
+    pub fn has_trailing_slash(&self) -> bool {
+        self.raw.len() > 1 && self.raw.ends_with('/')
+    }
+
+    /// 去掉路径末尾的斜杠，根路径 `/` 保持不变
+    pub fn trim_trailing_slash(path: &str) -> &str {
+        match path.trim_end_matches('/') {
+            "" if path.starts_with('/') => "/",
+            trimmed => trimmed,
+        }
+    }
+
+    /// 两个模式是否只有末尾斜杠不同
+    pub fn differs_only_by_trailing_slash(&self, other: &PathPattern) -> bool {
+        self.raw != other.raw
+            && Self::trim_trailing_slash(&self.raw) == Self::trim_trailing_slash(&other.raw)
+    }
+
+    /// 规范化的路径：参数统一写作 `{}`，去掉末尾斜杠
+    pub fn normalized(&self) -> String {
+        let segments: Vec<String> = self
+            .path
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Literal(literal) if literal.starts_with(':') => "{}".to_string(),
+                PathSegment::Literal(literal) => literal.clone(),
+                PathSegment::Param { .. } | PathSegment::Wildcard(_) => "{}".to_string(),
+                PathSegment::Mixed(text) => mixed_params(text),
+            })
+            .collect();
+        format!("/{}", segments.join("/"))
+    }
+
+    /// 两个模式是否描述同一个路由（忽略参数名称、约束和末尾斜杠）
+    pub fn same_shape(&self, other: &PathPattern) -> bool {
+        self.normalized() == other.normalized()
+    }
+
+    /// 两个模式是否可能匹配同一个请求路径
+    ///
+    /// 例如 `/users/{id}` 与 `/users/me` 冲突，而 `/users/{id:\d+}` 与 `/users/me` 不冲突
+    pub fn conflicts_with(&self, other: &PathPattern) -> bool {
+        self.path.conflicts_with(&other.path)
+    }
+
+    /// 匹配具体的 URL，返回按顺序提取的路径参数
+    ///
+    /// URL 可以包含协议和主机（`http://localhost:8080/users/42`）、查询字符串和片段，
+    /// 末尾斜杠不影响匹配。不匹配时返回 `None`
+    pub fn matches(&self, url: &str) -> Option<Vec<(String, String)>> {
+        let mut params = Vec::new();
+        let mut segments = url_path(url)
+            .split('/')
+            .filter(|segment| !segment.is_empty());
+
+        for (index, segment) in self.path.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Wildcard(name) => {
+                    let rest: Vec<&str> = segments.by_ref().collect();
+                    if rest.is_empty() {
+                        return None;
+                    }
+                    params.push((name.clone(), rest.join("/")));
+                    // 通配参数只能是最后一段
+                    return (index + 1 == self.path.segments.len()).then_some(params);
+                }
+                PathSegment::Literal(literal) => {
+                    let value = segments.next()?;
+                    match literal.strip_prefix(':') {
+                        Some(name) => params.push((name.to_string(), value.to_string())),
+                        None if literal == value => {}
+                        None => return None,
+                    }
+                }
+                PathSegment::Param { name, constraint } => {
+                    let value = segments.next()?;
+                    if let Some(constraint) = constraint {
+                        if !constraint_matches(constraint, value) {
+                            return None;
+                        }
+                    }
+                    params.push((name.clone(), value.to_string()));
+                }
+                PathSegment::Mixed(text) => {
+                    params.extend(match_mixed(text, segments.next()?)?);
+                }
+            }
+        }
+
+        segments.next().is_none().then_some(params)
+    }
+
+    /// 模式的具体程度：字面量路径段越多越具体，用于在多个匹配的路由中选择最具体的一个
+    pub fn specificity(&self) -> usize {
+        self.path
+            .segments
+            .iter()
+            .filter(|segment| {
+                matches!(segment, PathSegment::Literal(literal) if !literal.starts_with(':'))
+            })
+            .count()
+    }
+}
+
+/// URL 中的路径部分：去掉协议、主机、查询字符串和片段
+fn url_path(url: &str) -> &str {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |start| &rest[start..]),
+        None => url,
+    };
+    path.split(['?', '#']).next().unwrap_or_default()
+}
+
+/// 将混合路径段中的参数统一写作 `{}`，如 `{name}.json` -> `{}.json`
+fn mixed_params(text: &str) -> String {
+    let mut normalized = String::new();
+    let mut depth = 0usize;
+    for ch in text.chars() {
+        match ch {
+            '{' => {
+                if depth == 0 {
+                    normalized.push_str("{}");
+                }
+                depth += 1;
+            }
+            '}' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => normalized.push(ch),
+            _ => {}
+        }
+    }
+    normalized
+}
+
+/// 匹配混合路径段（如 `{name}.json` 匹配 `report.json`），返回提取的参数
+fn match_mixed(text: &str, value: &str) -> Option<Vec<(String, String)>> {
+    let mut pattern = String::from("^");
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        pattern.push_str(&regex::escape(&rest[..start]));
+        // 约束中可能包含大括号（如 `{code:[A-Z]{3}}`）
+        let mut depth = 0usize;
+        let end = start
+            + rest[start..].find(|ch| {
+                match ch {
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            })?;
+        let param = &rest[start + 1..end];
+        match param.split_once(':') {
+            Some((name, constraint)) => {
+                names.push(name.to_string());
+                pattern.push_str(&format!("((?:{}))", constraint));
+            }
+            None => {
+                names.push(param.to_string());
+                pattern.push_str("(.+?)");
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    pattern.push_str(&regex::escape(rest));
+    pattern.push('$');
+
+    let captures = regex::Regex::new(&pattern).ok()?.captures(value)?;
+    Some(
+        names
+            .into_iter()
+            .zip(captures.iter().skip(1))
+            .map(|(name, value)| (name, value.map_or("", |m| m.as_str()).to_string()))
+            .collect(),
+    )
+}
+
 /// 路由导航器
 ///
 /// 提供路由相关的导航功能，如跳转到处理器定义
@@ -208,10 +847,14 @@ impl RouteNavigator {
         Self {}
     }
 
-    /// 查找路由处理器的定义位置
-    pub fn find_handler_location(&self, _route_path: &str) -> Option<Location> {
-        // TODO: 实现查找逻辑
-        None
+    /// 查找处理具体 URL（如 `/users/42`）的路由处理器的定义位置
+    ///
+    /// 多个路由匹配时选择最具体的一个（见 [`RouteIndex::match_url`]）
+    pub fn find_handler_location(&self, index: &RouteIndex, url: &str) -> Option<Location> {
+        index
+            .match_url(None, url)
+            .first()
+            .map(|route| route.location.clone())
     }
 }
 
@@ -239,7 +882,7 @@ impl RouteIndex {
 
     /// 添加路由
     pub fn add_route(&mut self, route: Route) {
-        let key = format!("{} {}", route.method.as_str(), route.path);
+        let key = Self::route_key(&route);
         self.routes.insert(key, route);
     }
 
@@ -249,10 +892,70 @@ impl RouteIndex {
         self.routes.get(&key)
     }
 
+    /// 按处理器查找路由
+    ///
+    /// `handler` 包含 `::` 时按完整路径（如 `crate::api::users::list`）精确匹配，
+    /// 否则按函数名匹配，可能返回不同模块中同名处理器的路由
+    pub fn find_routes_by_handler(&self, handler: &str) -> Vec<&Route> {
+        self.routes
+            .values()
+            .filter(|route| {
+                if handler.contains("::") {
+                    route.handler_path.as_deref() == Some(handler)
+                } else {
+                    route.handler == handler
+                }
+            })
+            .collect()
+    }
+
+    /// 查找匹配具体 URL 的路由（可以按 HTTP 方法过滤）
+    ///
+    /// 结果按具体程度排序：字面量路径段多的路由在前，例如 `/users/me` 排在 `/users/{id}` 之前
+    pub fn match_url(&self, method: Option<HttpMethod>, url: &str) -> Vec<&Route> {
+        let mut matched: Vec<(usize, &Route)> = self
+            .routes
+            .values()
+            .filter(|route| method.is_none_or(|method| route.method == method))
+            .filter_map(|route| {
+                let pattern = PathPattern::parse(&route.path);
+                pattern.matches(url)?;
+                Some((pattern.specificity(), route))
+            })
+            .collect();
+        matched.sort_by(|(a, a_route), (b, b_route)| {
+            b.cmp(a)
+                .then_with(|| a_route.path.cmp(&b_route.path))
+                .then_with(|| a_route.method.as_str().cmp(b_route.method.as_str()))
+        });
+        matched.into_iter().map(|(_, route)| route).collect()
+    }
+
     /// 获取所有路由
     pub fn all_routes(&self) -> Vec<&Route> {
         self.routes.values().collect()
     }
+
+    /// 遍历所有索引条目（键 -> 路由）
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &Route)> {
+        self.routes.iter()
+    }
+
+    /// 按索引键移除路由
+    pub fn remove_route(&mut self, key: &str) -> Option<Route> {
+        self.routes.remove(key)
+    }
+
+    /// 以指定键插入路由（用于测试索引一致性检查）
+    #[cfg(test)]
+    pub(crate) fn insert_raw(&mut self, key: String, route: Route) {
+        self.routes.insert(key, route);
+    }
+
+    /// 计算路由在索引中应使用的键
+    pub fn route_key(route: &Route) -> String {
+        format!("{} {}", route.method.as_str(), route.path)
+    }
 }
 
 impl Default for RouteIndex {
@@ -297,6 +1000,8 @@ pub struct Route {
     pub path: String,
     /// 处理器函数名
     pub handler: String,
+    /// 处理器的完整路径（如 `crate::api::users::list`）
+    pub handler_path: Option<String>,
     /// 源代码位置
     pub location: Location,
 }
@@ -0,0 +1,414 @@
+//! 工作空间统计模块
+//!
+//! 汇总路由、组件、任务、配置项和诊断的数量，供扩展的仪表盘使用（`spring/stats` 请求）：
+//! - 路由按所在模块（如 `crate::api::users`）分组计数
+//! - 组件区分 `#[derive(Service)]` 和 `#[component]`，并统计注入的依赖数
+//! - `config/app.toml` 中的配置项按是否被读取分为已使用和未使用：
+//!   配置节由项目中的 `#[config_prefix]` 结构体或插件 Schema 声明了对应字段时视为已使用
+//! - 诊断按规则（诊断代码）计数
+//!
+//! 统计基于扫描器已有的结果计算，服务器保存每个应用最近一次的统计，用于计算变化趋势。
+//!
+//! 项目健康摘要（[`ProjectHealth`]）汇总需要处理的问题：路由冲突、无法解析的注入和未使用的配置项，
+//! 服务器在 `Cargo.toml` 上以代码透镜显示，点击后打开问题所在的位置。
+
+use crate::analysis::rust::module_path::{display_path, module_path};
+use crate::core::messages::{localized, Locale};
+use crate::scanner::component::{ComponentInfoResponse, ComponentSource};
+use crate::scanner::config::ConfigurationStruct;
+use crate::scanner::job::JobInfoResponse;
+use crate::scanner::route::RouteInfoResponse;
+use crate::schema::SchemaProvider;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// spring/stats 请求参数
+#[derive(Debug, Deserialize)]
+pub struct StatsRequest {
+    /// 应用路径
+    #[serde(rename = "appPath")]
+    pub app_path: String,
+}
+
+/// 工作空间统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceStats {
+    /// 路由数（每个 HTTP 方法计一次）
+    pub routes: usize,
+    /// 按模块分组的路由数（键为 `crate::api::users` 形式的模块路径）
+    pub routes_by_module: BTreeMap<String, usize>,
+    /// `#[derive(Service)]` 定义的组件数
+    pub services: usize,
+    /// `#[component]` 定义的组件数
+    pub components: usize,
+    /// 组件注入的依赖总数
+    pub injected_dependencies: usize,
+    /// 定时任务数
+    pub jobs: usize,
+    /// 配置项统计
+    pub config_keys: ConfigKeyStats,
+    /// 按规则（诊断代码）分组的诊断数
+    pub diagnostics_by_rule: BTreeMap<String, usize>,
+}
+
+/// 配置项统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigKeyStats {
+    /// 被配置结构体或插件读取的配置项数
+    pub used: usize,
+    /// 没有被读取的配置项（`prefix.key` 形式，按字母排序）
+    pub unused: Vec<String>,
+}
+
+/// spring/stats 响应
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsResponse {
+    /// 当前统计
+    #[serde(flatten)]
+    pub stats: WorkspaceStats,
+    /// 与上一次请求相比的变化（指标名到差值，只包含有变化的指标）；首次请求时为 `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trends: Option<BTreeMap<String, i64>>,
+}
+
+impl WorkspaceStats {
+    /// 根据扫描结果计算统计
+    ///
+    /// # Arguments
+    ///
+    /// * `routes` - 路由扫描结果
+    /// * `components` - 组件扫描结果
+    /// * `jobs` - 任务扫描结果
+    /// * `configurations` - 项目中的配置结构体
+    /// * `app_config` - `config/app.toml` 的内容
+    /// * `schema_provider` - 插件配置 Schema
+    /// * `diagnostics_by_rule` - 按规则分组的诊断数
+    pub fn collect(
+        routes: &[RouteInfoResponse],
+        components: &[ComponentInfoResponse],
+        jobs: &[JobInfoResponse],
+        configurations: &[ConfigurationStruct],
+        app_config: Option<&toml::Table>,
+        schema_provider: &SchemaProvider,
+        diagnostics_by_rule: BTreeMap<String, usize>,
+    ) -> Self {
+        let mut routes_by_module = BTreeMap::new();
+        for route in routes {
+            *routes_by_module.entry(route_module(route)).or_insert(0) += 1;
+        }
+
+        Self {
+            routes: routes.len(),
+            routes_by_module,
+            services: components
+                .iter()
+                .filter(|c| matches!(c.source, ComponentSource::Service))
+                .count(),
+            components: components
+                .iter()
+                .filter(|c| matches!(c.source, ComponentSource::Component))
+                .count(),
+            injected_dependencies: components.iter().map(|c| c.dependencies.len()).sum(),
+            jobs: jobs.len(),
+            config_keys: config_key_stats(app_config, configurations, schema_provider),
+            diagnostics_by_rule,
+        }
+    }
+
+    /// 与上一次统计相比有变化的指标
+    ///
+    /// 指标包括各项总数、未使用的配置项数和诊断总数
+    pub fn trends(&self, previous: &WorkspaceStats) -> BTreeMap<String, i64> {
+        let current = self.metrics();
+        let previous = previous.metrics();
+        current
+            .iter()
+            .zip(&previous)
+            .filter(|((_, now), (_, before))| now != before)
+            .map(|((name, now), (_, before))| (name.to_string(), *now as i64 - *before as i64))
+            .collect()
+    }
+
+    /// 用于计算趋势的指标（名称与 JSON 字段名一致）
+    fn metrics(&self) -> [(&'static str, usize); 8] {
+        [
+            ("routes", self.routes),
+            ("services", self.services),
+            ("components", self.components),
+            ("injectedDependencies", self.injected_dependencies),
+            ("jobs", self.jobs),
+            ("configKeysUsed", self.config_keys.used),
+            ("configKeysUnused", self.config_keys.unused.len()),
+            ("diagnostics", self.diagnostics_by_rule.values().sum()),
+        ]
+    }
+}
+
+/// 项目健康摘要中的问题类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthCategory {
+    /// 路由冲突（同一请求匹配多个路由或重复注册）
+    RouteConflicts,
+    /// 无法解析的注入（组件未注册、类型或名称不存在、配置不存在）
+    UnresolvedInjections,
+    /// `config/app.toml` 中未被读取的配置项
+    UnusedConfigKeys,
+}
+
+impl HealthCategory {
+    /// 所有类别（按代码透镜中的显示顺序）
+    pub const ALL: [HealthCategory; 3] = [
+        HealthCategory::RouteConflicts,
+        HealthCategory::UnresolvedInjections,
+        HealthCategory::UnusedConfigKeys,
+    ];
+
+    /// 属于该类别的诊断代码，未使用的配置项不是诊断，返回空列表
+    pub fn diagnostic_codes(self) -> &'static [&'static str] {
+        match self {
+            HealthCategory::RouteConflicts => &["W004", "duplicate-route-registration"],
+            HealthCategory::UnresolvedInjections => &[
+                "component-not-registered",
+                "component-type-not-found",
+                "component-name-not-found",
+                "config-not-found",
+            ],
+            HealthCategory::UnusedConfigKeys => &[],
+        }
+    }
+
+    /// 问题数量的说明（如 `2 个路由冲突`）
+    pub fn describe(self, count: usize, locale: Locale) -> String {
+        let key = match self {
+            HealthCategory::RouteConflicts => "health.route-conflicts",
+            HealthCategory::UnresolvedInjections => "health.unresolved-injections",
+            HealthCategory::UnusedConfigKeys => "health.unused-config-keys",
+        };
+        localized(locale, key, &[&count])
+    }
+}
+
+/// 项目健康摘要：每个类别的问题位置
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectHealth {
+    /// 路由冲突诊断的位置
+    pub route_conflicts: Vec<lsp_types::Location>,
+    /// 无法解析的注入诊断的位置
+    pub unresolved_injections: Vec<lsp_types::Location>,
+    /// 未使用的配置项在 `config/app.toml` 中的位置
+    pub unused_config_keys: Vec<lsp_types::Location>,
+}
+
+impl ProjectHealth {
+    /// 类别的问题位置
+    pub fn locations(&self, category: HealthCategory) -> &[lsp_types::Location] {
+        match category {
+            HealthCategory::RouteConflicts => &self.route_conflicts,
+            HealthCategory::UnresolvedInjections => &self.unresolved_injections,
+            HealthCategory::UnusedConfigKeys => &self.unused_config_keys,
+        }
+    }
+
+    /// 是否没有任何问题
+    pub fn is_healthy(&self) -> bool {
+        HealthCategory::ALL
+            .iter()
+            .all(|category| self.locations(*category).is_empty())
+    }
+
+    /// 摘要文本（如 `2 个路由冲突 · 0 个未解析的注入 · 1 个未使用的配置项`）
+    pub fn summary(&self, locale: Locale) -> String {
+        HealthCategory::ALL
+            .iter()
+            .map(|category| category.describe(self.locations(*category).len(), locale))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// 路由所在的模块，无法确定时为 `crate`
+fn route_module(route: &RouteInfoResponse) -> String {
+    let module = lsp_types::Url::parse(&route.location.uri)
+        .ok()
+        .and_then(|uri| uri.to_file_path().ok())
+        .and_then(|path| module_path(&path))
+        .unwrap_or_default();
+    display_path(&module)
+}
+
+/// 统计 `config/app.toml` 中已使用和未使用的配置项
+pub fn config_key_stats(
+    app_config: Option<&toml::Table>,
+    configurations: &[ConfigurationStruct],
+    schema_provider: &SchemaProvider,
+) -> ConfigKeyStats {
+    let mut stats = ConfigKeyStats::default();
+    let Some(app_config) = app_config else {
+        return stats;
+    };
+
+    for (prefix, section) in app_config {
+        let Some(section) = section.as_table() else {
+            continue;
+        };
+        for key in section.keys() {
+            let declared = configurations.iter().any(|config| {
+                config.prefix == *prefix && config.fields.iter().any(|field| field.name == *key)
+            });
+            if declared || schema_provider.has_property(prefix, key) {
+                stats.used += 1;
+            } else {
+                stats.unused.push(format!("{}.{}", prefix, key));
+            }
+        }
+    }
+
+    stats.unused.sort();
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::types::{LocationResponse, PositionResponse, RangeResponse};
+    use crate::scanner::component::ComponentScope;
+    use crate::scanner::config::ConfigField;
+
+    fn location(uri: &str) -> LocationResponse {
+        let position = PositionResponse {
+            line: 0,
+            character: 0,
+        };
+        LocationResponse {
+            uri: uri.to_string(),
+            range: RangeResponse {
+                start: position.clone(),
+                end: position,
+            },
+        }
+    }
+
+    fn route(uri: &str) -> RouteInfoResponse {
+        RouteInfoResponse {
+            method: "GET".to_string(),
+            path: "/".to_string(),
+            handler: "index".to_string(),
+            handler_path: None,
+            is_openapi: false,
+            doc: None,
+            deprecated: false,
+            stub: false,
+            cfg: None,
+            instrument: None,
+            confidence: crate::scanner::route::RouteConfidence::High,
+            location: location(uri),
+        }
+    }
+
+    fn component(source: ComponentSource, dependencies: &[&str]) -> ComponentInfoResponse {
+        ComponentInfoResponse {
+            name: "Svc".to_string(),
+            qualified_name: None,
+            type_name: "Svc".to_string(),
+            scope: ComponentScope::Singleton,
+            source,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            deprecated: false,
+            cfg: None,
+            location: location("file:///app/src/main.rs"),
+        }
+    }
+
+    #[test]
+    fn test_collect_stats() {
+        let routes = vec![
+            route("file:///app/src/main.rs"),
+            route("file:///app/src/api/users.rs"),
+            route("file:///app/src/api/users/mod.rs"),
+        ];
+        let components = vec![
+            component(ComponentSource::Service, &["DbConn", "RedisConn"]),
+            component(ComponentSource::Component, &[]),
+        ];
+        let configurations = vec![ConfigurationStruct {
+            name: "ShopConfig".to_string(),
+            prefix: "shop".to_string(),
+            fields: vec![ConfigField {
+                name: "currency".to_string(),
+                type_name: "String".to_string(),
+                optional: false,
+                description: None,
+                range: Default::default(),
+                has_default: false,
+            }],
+            location: None,
+        }];
+        let app_config: toml::Table =
+            "[web]\nport = 8080\nprot = 1\n\n[shop]\ncurrency = \"EUR\"\nlegacy = true\n"
+                .parse()
+                .unwrap();
+
+        let stats = WorkspaceStats::collect(
+            &routes,
+            &components,
+            &[],
+            &configurations,
+            Some(&app_config),
+            &SchemaProvider::default(),
+            BTreeMap::from([("E001".to_string(), 2)]),
+        );
+
+        assert_eq!(stats.routes, 3);
+        assert_eq!(
+            stats.routes_by_module,
+            BTreeMap::from([
+                ("crate".to_string(), 1),
+                ("crate::api::users".to_string(), 2)
+            ])
+        );
+        assert_eq!(stats.services, 1);
+        assert_eq!(stats.components, 1);
+        assert_eq!(stats.injected_dependencies, 2);
+        assert_eq!(stats.config_keys.used, 2);
+        assert_eq!(stats.config_keys.unused, vec!["shop.legacy", "web.prot"]);
+
+        let mut next = stats.clone();
+        next.routes += 1;
+        next.diagnostics_by_rule.clear();
+        assert_eq!(
+            next.trends(&stats),
+            BTreeMap::from([("diagnostics".to_string(), -2), ("routes".to_string(), 1)])
+        );
+        assert!(stats.trends(&stats).is_empty());
+    }
+
+    #[test]
+    fn test_project_health_summary() {
+        let location = lsp_types::Location::new(
+            lsp_types::Url::parse("file:///app/src/main.rs").unwrap(),
+            lsp_types::Range::default(),
+        );
+        let mut health = ProjectHealth::default();
+        assert!(health.is_healthy());
+
+        health.route_conflicts = vec![location.clone(), location.clone()];
+        health.unused_config_keys = vec![location];
+        assert!(!health.is_healthy());
+        assert_eq!(
+            health.summary(Locale::default()),
+            "2 个路由冲突 · 0 个未解析的注入 · 1 个未使用的配置项"
+        );
+        assert_eq!(
+            health.summary(Locale::En),
+            "2 route conflicts · 0 unresolved injections · 1 unused config keys"
+        );
+        assert_eq!(health.locations(HealthCategory::UnusedConfigKeys).len(), 1);
+        assert!(HealthCategory::UnusedConfigKeys
+            .diagnostic_codes()
+            .is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+//! 消息流监听器扫描器模块
+//!
+//! 扫描项目中的所有消息流监听器（带有 `#[stream_listener(...)]` 的函数）
+
+use crate::core::document::SourceReader;
+use crate::protocol::types::{LocationResponse, PositionResponse, RangeResponse};
+use lsp_types::Url;
+use proc_macro2::TokenTree;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use syn::spanned::Spanned;
+use walkdir::WalkDir;
+
+use super::route::ScanError;
+
+/// 消息流监听器宏名称
+const STREAM_LISTENER_ATTRIBUTE: &str = "stream_listener";
+
+/// 消息流监听器扫描器
+#[derive(Debug, Clone, Default)]
+pub struct StreamScanner {
+    sources: SourceReader,
+}
+
+impl StreamScanner {
+    /// 创建新的消息流监听器扫描器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 使用指定的方式读取源文件（语言服务器中优先读取编辑器中打开的文档）
+    pub fn with_sources(mut self, sources: SourceReader) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// 扫描项目中的所有消息流监听器
+    #[cfg_attr(feature = "profiling", tracing::instrument(level = "trace", skip_all))]
+    pub fn scan_streams(&self, project_path: &Path) -> Result<Vec<StreamListenerInfo>, ScanError> {
+        let src_path = project_path.join("src");
+        if !src_path.exists() {
+            return Err(ScanError::InvalidProject(
+                "src directory not found".to_string(),
+            ));
+        }
+
+        let mut listeners = Vec::new();
+
+        for entry in WalkDir::new(&src_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+        {
+            let file_path = entry.path();
+
+            let content = match self.sources.read(file_path) {
+                Ok(content) => content,
+                Err(e) => {
+                    tracing::warn!("Failed to read file {:?}: {}", file_path, e);
+                    continue;
+                }
+            };
+
+            let Ok(file_url) = Url::from_file_path(file_path) else {
+                tracing::warn!("Failed to convert path to URL: {:?}", file_path);
+                continue;
+            };
+
+            listeners.extend(self.analyze_file(&file_url, &content));
+        }
+
+        Ok(listeners)
+    }
+
+    /// 分析单个文件中的消息流监听器
+    pub fn analyze_file(&self, uri: &Url, content: &str) -> Vec<StreamListenerInfo> {
+        let Ok(file) = syn::parse_file(content) else {
+            return vec![];
+        };
+        let lines = self.sources.mapper(content);
+
+        let mut listeners = Vec::new();
+        for item in &file.items {
+            let syn::Item::Fn(item_fn) = item else {
+                continue;
+            };
+
+            for attr in &item_fn.attrs {
+                if !attr.path().is_ident(STREAM_LISTENER_ATTRIBUTE) {
+                    continue;
+                }
+
+                let range = lines.span_range(attr.span());
+                listeners.push(StreamListenerInfo {
+                    topics: listener_topics(attr),
+                    handler: item_fn.sig.ident.to_string(),
+                    location: LocationResponse {
+                        uri: uri.to_string(),
+                        range: RangeResponse {
+                            start: PositionResponse {
+                                line: range.start.line,
+                                character: range.start.character,
+                            },
+                            end: PositionResponse {
+                                line: range.end.line,
+                                character: range.end.character,
+                            },
+                        },
+                    },
+                });
+            }
+        }
+
+        listeners
+    }
+}
+
+/// 宏参数中的主题名称（开头的字符串字面量，如 `#[stream_listener("a", "b", ...)]`）
+fn listener_topics(attr: &syn::Attribute) -> Vec<String> {
+    let Ok(list) = attr.meta.require_list() else {
+        return vec![];
+    };
+
+    list.tokens
+        .clone()
+        .into_iter()
+        .filter(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+        .map_while(|token| match token {
+            TokenTree::Literal(literal) => {
+                match syn::parse_str::<syn::LitStr>(&literal.to_string()) {
+                    Ok(topic) => Some(topic.value()),
+                    Err(_) => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// 消息流监听器信息（用于 JSON 序列化）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamListenerInfo {
+    /// 监听的主题
+    pub topics: Vec<String>,
+    /// 处理器函数名
+    pub handler: String,
+    /// 源代码位置
+    pub location: LocationResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_stream_listeners() {
+        let uri = Url::parse("file:///project/src/consumer.rs").unwrap();
+        let listeners = StreamScanner::new().analyze_file(
+            &uri,
+            r#"
+#[stream_listener("order-created", "order-paid", kafka_consumer_options = consumer_options)]
+async fn on_order(Json(order): Json<Order>) {}
+
+#[get("/")]
+async fn index() {}
+"#,
+        );
+
+        assert_eq!(listeners.len(), 1);
+        assert_eq!(listeners[0].handler, "on_order");
+        assert_eq!(listeners[0].topics, vec!["order-created", "order-paid"]);
+        assert_eq!(listeners[0].location.range.start.line, 1);
+    }
+}
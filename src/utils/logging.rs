@@ -30,6 +30,8 @@ use std::io;
 use std::path::PathBuf;
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
 
+use crate::core::profiling::ProfileLayer;
+
 /// 日志配置
 #[derive(Debug, Clone)]
 pub struct LogConfig {
@@ -171,11 +173,13 @@ pub fn init_logging_with_config(config: LogConfig) -> Result<(), Box<dyn std::er
         tracing_subscriber::registry()
             .with(stderr_layer)
             .with(file_layer)
+            .with(profile_layer())
             .try_init()?;
     } else {
         // 只使用 stderr 输出
         tracing_subscriber::registry()
             .with(stderr_layer)
+            .with(profile_layer())
             .try_init()?;
     }
 
@@ -190,6 +194,11 @@ pub fn init_logging_with_config(config: LogConfig) -> Result<(), Box<dyn std::er
     Ok(())
 }
 
+/// 性能剖析层，只在启用 `profiling` feature 时安装
+fn profile_layer() -> Option<ProfileLayer> {
+    cfg!(feature = "profiling").then_some(ProfileLayer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,6 +4,9 @@
 //! - 服务器运行状态
 //! - 性能指标（文档数量、内存使用等）
 //! - 错误统计
+//! - 按方法统计的请求延迟直方图和缓存命中率
+//! - 错误恢复统计（解析失败、捕获的 panic、按方法统计的处理失败），只保存在本地，
+//!   通过状态查询和关闭时的日志查看，便于发现和报告系统性的问题
 //!
 //! ## 使用示例
 //!
@@ -19,7 +22,9 @@
 //! println!("Errors: {}", metrics.error_count);
 //! ```
 
+use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -43,6 +48,16 @@ pub struct ServerStatus {
     hover_count: Arc<AtomicU64>,
     /// 诊断发布数
     diagnostic_count: Arc<AtomicU64>,
+    /// 按方法统计的延迟直方图
+    latencies: Arc<DashMap<String, LatencyHistogram>>,
+    /// 按缓存名称统计的命中情况
+    caches: Arc<DashMap<String, CacheCounters>>,
+    /// 捕获的 panic 数
+    panic_count: Arc<AtomicU64>,
+    /// 按文档类型统计的解析失败次数
+    parse_failures: Arc<DashMap<String, u64>>,
+    /// 按方法统计的处理失败次数
+    method_errors: Arc<DashMap<String, u64>>,
 }
 
 impl ServerStatus {
@@ -56,6 +71,11 @@ impl ServerStatus {
             completion_count: Arc::new(AtomicU64::new(0)),
             hover_count: Arc::new(AtomicU64::new(0)),
             diagnostic_count: Arc::new(AtomicU64::new(0)),
+            latencies: Arc::new(DashMap::new()),
+            caches: Arc::new(DashMap::new()),
+            panic_count: Arc::new(AtomicU64::new(0)),
+            parse_failures: Arc::new(DashMap::new()),
+            method_errors: Arc::new(DashMap::new()),
         }
     }
 
@@ -94,6 +114,39 @@ impl ServerStatus {
         self.diagnostic_count.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// 记录一次请求或通知的处理耗时
+    pub fn record_latency(&self, method: &str, elapsed: Duration) {
+        self.latencies
+            .entry(method.to_string())
+            .or_default()
+            .record(elapsed);
+    }
+
+    /// 记录缓存命中
+    pub fn record_cache_hit(&self, cache: &str) {
+        self.caches.entry(cache.to_string()).or_default().hits += 1;
+    }
+
+    /// 记录缓存未命中
+    pub fn record_cache_miss(&self, cache: &str) {
+        self.caches.entry(cache.to_string()).or_default().misses += 1;
+    }
+
+    /// 记录捕获的 panic
+    pub fn record_panic(&self) {
+        self.panic_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 记录文档解析失败（`kind` 为文档类型，如 `rust`、`toml`）
+    pub fn record_parse_failure(&self, kind: &str) {
+        *self.parse_failures.entry(kind.to_string()).or_default() += 1;
+    }
+
+    /// 记录请求或通知处理失败
+    pub fn record_method_error(&self, method: &str) {
+        *self.method_errors.entry(method.to_string()).or_default() += 1;
+    }
+
     /// 获取服务器运行时长
     pub fn uptime(&self) -> Duration {
         self.start_time.elapsed()
@@ -122,6 +175,66 @@ impl ServerStatus {
             } else {
                 0.0
             },
+            memory: MemoryUsage::default(),
+            errors: self.get_error_report(),
+        }
+    }
+
+    /// 获取错误恢复统计
+    pub fn get_error_report(&self) -> ErrorReport {
+        let collect = |counters: &DashMap<String, u64>| {
+            counters
+                .iter()
+                .map(|entry| (entry.key().clone(), *entry.value()))
+                .collect()
+        };
+        ErrorReport {
+            panics: self.panic_count.load(Ordering::Relaxed),
+            parse_failures: collect(&self.parse_failures),
+            errors_by_method: collect(&self.method_errors),
+        }
+    }
+
+    /// 获取性能报告
+    ///
+    /// 方法按总耗时降序排列，便于定位最耗时的请求类型
+    pub fn get_perf_report(&self) -> PerfReport {
+        let mut methods: Vec<MethodLatency> = self
+            .latencies
+            .iter()
+            .map(|entry| entry.value().summarize(entry.key()))
+            .collect();
+        methods.sort_by(|a, b| {
+            b.total_ms
+                .partial_cmp(&a.total_ms)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.method.cmp(&b.method))
+        });
+
+        let mut caches: Vec<CacheStats> = self
+            .caches
+            .iter()
+            .map(|entry| {
+                let counters = entry.value();
+                let total = counters.hits + counters.misses;
+                CacheStats {
+                    name: entry.key().clone(),
+                    hits: counters.hits,
+                    misses: counters.misses,
+                    hit_rate: if total > 0 {
+                        counters.hits as f64 / total as f64
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect();
+        caches.sort_by(|a, b| a.name.cmp(&b.name));
+
+        PerfReport {
+            uptime_seconds: self.uptime().as_secs(),
+            methods,
+            caches,
         }
     }
 
@@ -134,6 +247,11 @@ impl ServerStatus {
         self.completion_count.store(0, Ordering::Relaxed);
         self.hover_count.store(0, Ordering::Relaxed);
         self.diagnostic_count.store(0, Ordering::Relaxed);
+        self.latencies.clear();
+        self.caches.clear();
+        self.panic_count.store(0, Ordering::Relaxed);
+        self.parse_failures.clear();
+        self.method_errors.clear();
     }
 }
 
@@ -166,6 +284,68 @@ pub struct ServerMetrics {
     pub requests_per_second: f64,
     /// 错误率（错误数/总请求数）
     pub error_rate: f64,
+    /// 估算的内存占用（由语言服务器填充）
+    #[serde(default)]
+    pub memory: MemoryUsage,
+    /// 错误恢复统计
+    #[serde(default)]
+    pub errors: ErrorReport,
+}
+
+/// 错误恢复统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorReport {
+    /// 捕获的 panic 数
+    pub panics: u64,
+    /// 按文档类型统计的解析失败次数
+    pub parse_failures: BTreeMap<String, u64>,
+    /// 按方法统计的请求和通知处理失败次数
+    pub errors_by_method: BTreeMap<String, u64>,
+}
+
+impl ErrorReport {
+    /// 是否没有记录任何错误
+    pub fn is_empty(&self) -> bool {
+        self.panics == 0 && self.parse_failures.is_empty() && self.errors_by_method.is_empty()
+    }
+
+    /// 格式化为人类可读的字符串
+    pub fn format(&self) -> String {
+        let mut output = format!("Error Report:\n- Panics caught: {}\n", self.panics);
+        for (kind, count) in &self.parse_failures {
+            output.push_str(&format!("- parse failures ({}): {}\n", kind, count));
+        }
+        for (method, count) in &self.errors_by_method {
+            output.push_str(&format!("- errors in {}: {}\n", method, count));
+        }
+        output
+    }
+}
+
+/// 估算的内存占用（字节）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryUsage {
+    /// 打开的文档内容
+    pub documents: usize,
+    /// 依赖注入验证保留的源文件和配置文件内容
+    pub sources: usize,
+    /// 依赖注入验证保留的解析结果
+    pub parsed: usize,
+    /// 内存预算（0 表示不限制）
+    pub budget: usize,
+}
+
+impl MemoryUsage {
+    /// 总占用
+    pub fn total(&self) -> usize {
+        self.documents + self.sources + self.parsed
+    }
+
+    /// 是否超出预算
+    pub fn exceeds_budget(&self) -> bool {
+        self.budget > 0 && self.total() > self.budget
+    }
 }
 
 impl ServerMetrics {
@@ -179,7 +359,8 @@ impl ServerMetrics {
              - Errors: {} ({:.2}% error rate)\n\
              - Completions: {}\n\
              - Hovers: {}\n\
-             - Diagnostics: {}",
+             - Diagnostics: {}\n\
+             - Memory: {} KiB",
             self.uptime_seconds,
             self.document_count,
             self.request_count,
@@ -188,11 +369,198 @@ impl ServerMetrics {
             self.error_rate * 100.0,
             self.completion_count,
             self.hover_count,
-            self.diagnostic_count
+            self.diagnostic_count,
+            self.memory.total() / 1024
         )
     }
 }
 
+/// 延迟直方图的桶上界（毫秒），最后一个桶收集超过上界的所有样本
+const LATENCY_BUCKETS_MS: [u64; 10] = [1, 5, 10, 25, 50, 100, 250, 500, 1000, 2500];
+
+/// 单个方法的延迟直方图
+#[derive(Debug, Clone, Default)]
+struct LatencyHistogram {
+    /// 每个桶的样本数（比桶上界多一个溢出桶）
+    buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    /// 样本总数
+    count: u64,
+    /// 总耗时（微秒）
+    total_us: u64,
+    /// 最大耗时（微秒）
+    max_us: u64,
+}
+
+impl LatencyHistogram {
+    fn record(&mut self, elapsed: Duration) {
+        let us = elapsed.as_micros().min(u64::MAX as u128) as u64;
+        let index = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| us <= bound * 1000)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+
+        self.buckets[index] += 1;
+        self.count += 1;
+        self.total_us = self.total_us.saturating_add(us);
+        self.max_us = self.max_us.max(us);
+    }
+
+    /// 估算分位数：返回样本落入的桶上界，溢出桶使用最大值
+    fn percentile_ms(&self, percentile: f64) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+
+        let rank = ((self.count as f64) * percentile).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let upper_ms = match LATENCY_BUCKETS_MS.get(index) {
+                    Some(&bound) => bound as f64,
+                    None => self.max_us as f64 / 1000.0,
+                };
+                // 分位数不会超过实际观测到的最大值
+                return upper_ms.min(self.max_us as f64 / 1000.0);
+            }
+        }
+
+        self.max_us as f64 / 1000.0
+    }
+
+    fn summarize(&self, method: &str) -> MethodLatency {
+        let total_ms = self.total_us as f64 / 1000.0;
+
+        MethodLatency {
+            method: method.to_string(),
+            count: self.count,
+            total_ms,
+            mean_ms: if self.count > 0 {
+                total_ms / self.count as f64
+            } else {
+                0.0
+            },
+            max_ms: self.max_us as f64 / 1000.0,
+            p50_ms: self.percentile_ms(0.50),
+            p95_ms: self.percentile_ms(0.95),
+            p99_ms: self.percentile_ms(0.99),
+            buckets: self
+                .buckets
+                .iter()
+                .enumerate()
+                .map(|(index, &count)| LatencyBucket {
+                    le_ms: LATENCY_BUCKETS_MS.get(index).copied(),
+                    count,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 单个缓存的命中计数
+#[derive(Debug, Clone, Default)]
+struct CacheCounters {
+    hits: u64,
+    misses: u64,
+}
+
+/// 性能报告
+///
+/// 通过 `spring/perfReport` 请求返回给客户端，可以附加到性能问题报告中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfReport {
+    /// 运行时长（秒）
+    pub uptime_seconds: u64,
+    /// 按方法统计的延迟（按总耗时降序）
+    pub methods: Vec<MethodLatency>,
+    /// 缓存命中统计
+    pub caches: Vec<CacheStats>,
+}
+
+/// 单个方法的延迟统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MethodLatency {
+    /// 方法名（如 `textDocument/completion`）
+    pub method: String,
+    /// 调用次数
+    pub count: u64,
+    /// 总耗时（毫秒）
+    pub total_ms: f64,
+    /// 平均耗时（毫秒）
+    pub mean_ms: f64,
+    /// 最大耗时（毫秒）
+    pub max_ms: f64,
+    /// 50 分位耗时（毫秒，按桶上界估算）
+    pub p50_ms: f64,
+    /// 95 分位耗时（毫秒，按桶上界估算）
+    pub p95_ms: f64,
+    /// 99 分位耗时（毫秒，按桶上界估算）
+    pub p99_ms: f64,
+    /// 直方图桶
+    pub buckets: Vec<LatencyBucket>,
+}
+
+/// 直方图桶
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyBucket {
+    /// 桶上界（毫秒，包含）；`None` 表示溢出桶
+    pub le_ms: Option<u64>,
+    /// 样本数
+    pub count: u64,
+}
+
+/// 缓存命中统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    /// 缓存名称
+    pub name: String,
+    /// 命中次数
+    pub hits: u64,
+    /// 未命中次数
+    pub misses: u64,
+    /// 命中率（命中数/总查询数）
+    pub hit_rate: f64,
+}
+
+impl PerfReport {
+    /// 格式化为人类可读的字符串
+    pub fn format(&self) -> String {
+        let mut output = format!("Performance Report (uptime {}s):\n", self.uptime_seconds);
+
+        if self.methods.is_empty() {
+            output.push_str("- No requests recorded\n");
+        }
+        for method in &self.methods {
+            output.push_str(&format!(
+                "- {}: {} calls, mean {:.2}ms, p50 {:.2}ms, p95 {:.2}ms, p99 {:.2}ms, max {:.2}ms\n",
+                method.method,
+                method.count,
+                method.mean_ms,
+                method.p50_ms,
+                method.p95_ms,
+                method.p99_ms,
+                method.max_ms
+            ));
+        }
+
+        for cache in &self.caches {
+            output.push_str(&format!(
+                "- cache {}: {} hits, {} misses ({:.2}% hit rate)\n",
+                cache.name,
+                cache.hits,
+                cache.misses,
+                cache.hit_rate * 100.0
+            ));
+        }
+
+        output
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -309,6 +677,29 @@ mod tests {
         assert!(metrics.requests_per_second <= 2.0);
     }
 
+    #[test]
+    fn test_error_report() {
+        let status = ServerStatus::new();
+        assert!(status.get_error_report().is_empty());
+
+        status.record_panic();
+        status.record_parse_failure("rust");
+        status.record_parse_failure("rust");
+        status.record_method_error("textDocument/hover");
+
+        let report = status.get_metrics().errors;
+        assert_eq!(report.panics, 1);
+        assert_eq!(report.parse_failures["rust"], 2);
+        assert_eq!(report.errors_by_method["textDocument/hover"], 1);
+        let formatted = report.format();
+        assert!(formatted.contains("Panics caught: 1"));
+        assert!(formatted.contains("parse failures (rust): 2"));
+        assert!(formatted.contains("errors in textDocument/hover: 1"));
+
+        status.reset();
+        assert!(status.get_error_report().is_empty());
+    }
+
     #[test]
     fn test_metrics_format() {
         let status = ServerStatus::new();
@@ -366,6 +757,68 @@ mod tests {
         assert!(metrics.requests_per_second >= 0.0);
     }
 
+    #[test]
+    fn test_latency_histogram() {
+        let status = ServerStatus::new();
+
+        status.record_latency("textDocument/hover", Duration::from_micros(500));
+        status.record_latency("textDocument/hover", Duration::from_millis(3));
+        status.record_latency("textDocument/hover", Duration::from_millis(40));
+        status.record_latency("textDocument/completion", Duration::from_secs(5));
+
+        let report = status.get_perf_report();
+        assert_eq!(report.methods.len(), 2);
+
+        // 按总耗时降序排列
+        let completion = &report.methods[0];
+        assert_eq!(completion.method, "textDocument/completion");
+        assert_eq!(completion.count, 1);
+        // 溢出桶的分位数使用实际最大值
+        assert_eq!(completion.p99_ms, 5000.0);
+        assert_eq!(completion.buckets.last().unwrap().le_ms, None);
+        assert_eq!(completion.buckets.last().unwrap().count, 1);
+
+        let hover = &report.methods[1];
+        assert_eq!(hover.count, 3);
+        assert_eq!(hover.max_ms, 40.0);
+        assert_eq!(hover.p50_ms, 5.0);
+        assert_eq!(hover.p99_ms, 40.0);
+        assert!((hover.mean_ms - 14.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cache_hit_rate() {
+        let status = ServerStatus::new();
+
+        status.record_cache_hit("completion");
+        status.record_cache_hit("completion");
+        status.record_cache_hit("completion");
+        status.record_cache_miss("completion");
+        status.record_cache_miss("parse");
+
+        let report = status.get_perf_report();
+        assert_eq!(report.caches.len(), 2);
+        assert_eq!(report.caches[0].name, "completion");
+        assert_eq!(report.caches[0].hit_rate, 0.75);
+        assert_eq!(report.caches[1].name, "parse");
+        assert_eq!(report.caches[1].hit_rate, 0.0);
+
+        let formatted = report.format();
+        assert!(formatted.contains("cache completion: 3 hits, 1 misses"));
+    }
+
+    #[test]
+    fn test_perf_report_serialization() {
+        let status = ServerStatus::new();
+        status.record_latency("spring/routes", Duration::from_millis(12));
+
+        let json = serde_json::to_value(status.get_perf_report()).unwrap();
+        assert!(json["uptimeSeconds"].is_u64());
+        assert_eq!(json["methods"][0]["method"], "spring/routes");
+        assert_eq!(json["methods"][0]["p50Ms"], 12.0);
+        assert!(json["methods"][0]["buckets"].is_array());
+    }
+
     #[test]
     fn test_reset() {
         let status = ServerStatus::new();
@@ -74,6 +74,9 @@ export class LanguageClientManager implements vscode.Disposable {
             '**/{*.toml,*.rs,Cargo.toml}'
           ),
         },
+        initializationOptions: {
+          locale: this.getLocale(),
+        },
         // 不指定 outputChannel，让 LSP 客户端自动创建
         // 这样会创建一个名为 "Spring RS" 的输出通道用于语言服务器日志
       };
@@ -184,6 +187,23 @@ export class LanguageClientManager implements vscode.Disposable {
     return this.client !== undefined;
   }
 
+  /**
+   * 诊断消息使用的语言
+   *
+   * `spring-rs.locale` 为 `auto` 时跟随 VS Code 的显示语言：中文显示语言使用简体中文，其他语言使用英文
+   *
+   * @returns 传给语言服务器初始化选项的语言标签
+   */
+  private getLocale(): string {
+    const locale = vscode.workspace
+      .getConfiguration('spring-rs')
+      .get<string>('locale', 'auto');
+    if (locale !== 'auto') {
+      return locale;
+    }
+    return vscode.env.language.toLowerCase().startsWith('zh') ? 'zh-CN' : 'en';
+  }
+
   /**
    * 查找语言服务器可执行文件
    * 
@@ -17,6 +17,11 @@ export interface Component {
    */
   name: string;
 
+  /**
+   * 定义组件的条目的完整路径（如 crate::services::UserService）
+   */
+  qualifiedName?: string;
+
   /**
    * 组件类型（完整的 Rust 类型名）
    */
@@ -37,6 +42,11 @@ export interface Component {
    */
   dependencies: string[];
 
+  /**
+   * 注册组件的条件（#[cfg(...)]，如 feature = "redis"）
+   */
+  cfg?: string;
+
   /**
    * 组件定义位置
    */
@@ -69,17 +79,54 @@ export interface Route {
    */
   handler: string;
 
+  /**
+   * 处理器的完整路径（如 crate::api::users::list）
+   */
+  handlerPath?: string;
+
   /**
    * 是否为 OpenAPI 路由
    */
   isOpenapi: boolean;
 
+  /**
+   * 通过 #[cfg_attr(条件, ...)] 应用的路由的启用条件（如 feature = "web"）
+   */
+  cfg?: string;
+
+  /**
+   * 处理器函数上 #[instrument] 生成的 span 信息
+   */
+  instrument?: InstrumentInfo;
+
   /**
    * 路由定义位置（可选）
    */
   location?: Location;
 }
 
+/**
+ * InstrumentInfo 接口
+ *
+ * 表示处理器函数上 #[instrument] 生成的 span
+ */
+export interface InstrumentInfo {
+  /**
+   * span 名称
+   */
+  spanName: string;
+
+  /**
+   * span 级别（如 info）
+   */
+  level: string;
+
+  /**
+   * 记录的字段
+   */
+  fields: string[];
+}
+
 /**
  * Configuration 接口
  * 
@@ -182,6 +229,11 @@ export interface Job {
    */
   schedule: string;
 
+  /**
+   * Cron 任务的时区（未指定时为 UTC）
+   */
+  timezone?: string;
+
   /**
    * 源代码位置
    */
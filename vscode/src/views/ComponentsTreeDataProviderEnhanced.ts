@@ -416,6 +416,10 @@ class ComponentTreeNode extends vscode.TreeItem {
       tooltip.appendMarkdown(`**Defined with:** \`#[derive(Service)]\` 🔵\n\n`);
     }
 
+    if (this.component.cfg) {
+      tooltip.appendMarkdown(`**Enabled with:** \`cfg(${this.component.cfg})\`\n\n`);
+    }
+
     if (this.source === DataSource.Runtime) {
       tooltip.appendMarkdown('✅ **Runtime Information**\n\n');
     } else {
@@ -266,6 +266,9 @@ export class JobTreeItem extends vscode.TreeItem {
     tooltip.appendMarkdown(`### ${this.job.name}\n\n`);
     tooltip.appendMarkdown(`**Type:** ${this.job.jobType}\n\n`);
     tooltip.appendMarkdown(`**Schedule:** \`${this.job.schedule}\`\n\n`);
+    if (this.job.timezone) {
+      tooltip.appendMarkdown(`**Time zone:** \`${this.job.timezone}\`\n\n`);
+    }
 
     if (this.job.jobType === 'Cron') {
       tooltip.appendMarkdown(`\n*Cron expression*\n`);
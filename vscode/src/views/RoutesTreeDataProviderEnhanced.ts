@@ -305,7 +305,9 @@ class RouteTreeNode extends vscode.TreeItem {
     super(route.path, vscode.TreeItemCollapsibleState.None);
 
     this.contextValue = `spring:route-${source}`;
-    this.description = route.handler || '';
+    this.description = route.cfg
+      ? `${route.handler || ''} [cfg(${route.cfg})]`.trim()
+      : route.handler || '';
     this.tooltip = this.buildTooltip();
     this.iconPath = this.getIcon();
 
@@ -325,13 +327,17 @@ class RouteTreeNode extends vscode.TreeItem {
     tooltip.appendMarkdown(`### ${this.route.method} ${this.route.path}\n\n`);
     
     if (this.route.handler) {
-      tooltip.appendMarkdown(`**Handler:** \`${this.route.handler}\`\n\n`);
+      tooltip.appendMarkdown(`**Handler:** \`${this.route.handlerPath ?? this.route.handler}\`\n\n`);
     }
 
     if (this.route.isOpenapi) {
       tooltip.appendMarkdown(`**Type:** OpenAPI Route 📖\n\n`);
     }
 
+    if (this.route.cfg) {
+      tooltip.appendMarkdown(`**Enabled with:** \`cfg(${this.route.cfg})\`\n\n`);
+    }
+
     if (this.source === DataSource.Runtime) {
       tooltip.appendMarkdown('✅ **Runtime Information**\n\n');
     } else {
@@ -16,6 +16,8 @@ export interface Route {
     path: string;
     handler: string;
     isOpenapi: boolean;
+    // 通过 #[cfg_attr(条件, ...)] 应用的路由的启用条件
+    cfg?: string;
     location: {
         uri: string;
         range: {
@@ -102,6 +104,10 @@ export class RouteTreeItem extends vscode.TreeItem {
             md.appendMarkdown('📄 OpenAPI documented\n\n');
         }
 
+        if (this.route.cfg) {
+            md.appendMarkdown(`⚙️ Enabled with \`cfg(${this.route.cfg})\`\n\n`);
+        }
+
         if (this.source === RouteSource.Runtime) {
             md.appendMarkdown('✅ **Runtime Statistics**\n\n');
             if (this.route.requestCount !== undefined) {
@@ -122,13 +128,14 @@ export class RouteTreeItem extends vscode.TreeItem {
     }
 
     private buildDescription(): string {
+        const badge = this.route.cfg ? ` [cfg(${this.route.cfg})]` : '';
         if (this.source === RouteSource.Runtime && this.route.requestCount !== undefined) {
-            return `(${this.route.requestCount} requests)`;
+            return `(${this.route.requestCount} requests)${badge}`;
         }
         if (this.source === RouteSource.Static) {
-            return '(static)';
+            return `(static)${badge}`;
         }
-        return '';
+        return badge.trim();
     }
 
     private getIcon(): vscode.ThemeIcon {